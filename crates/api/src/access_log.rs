@@ -0,0 +1,366 @@
+//! Structured per-request access logging with configurable sinks
+//!
+//! An outer middleware emits one JSON line per handled request (route,
+//! method, status, latency, org, API key, upstream MCPs) to a sink chosen
+//! by `ACCESS_LOG_SINK`: `stdout`, `file` (daily-rotated by date suffix)
+//! or `http` (batched NDJSON POSTs to a log shipper). Writes happen on a
+//! dedicated task fed by a channel so the request path never blocks on a
+//! slow disk or shipper, matching the otel exporter's layout.
+//!
+//! Auth context isn't visible to an outer middleware, so inner layers and
+//! handlers that know the org/key attach an [`AccessContext`] to the
+//! response extensions and the middleware picks it up on the way out.
+//!
+//! Disabled entirely when `ACCESS_LOG_SINK` is unset; health checks and
+//! the metrics scrape are excluded by default (`ACCESS_LOG_EXCLUDE_PATHS`
+//! overrides the exclusion list).
+
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use serde::Serialize;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::config::Config;
+
+/// Entries buffered before the HTTP shipper forces a flush
+const MAX_BATCH_SIZE: usize = 256;
+
+/// How often buffered entries are shipped
+const FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Shipper requests must complete within this window
+const SHIP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Paths excluded from access logging unless overridden
+const DEFAULT_EXCLUDED_PATHS: &[&str] = &[
+    "/health",
+    "/health/live",
+    "/health/startup",
+    "/health/ready",
+    "/metrics",
+];
+
+static LOGGER: OnceLock<AccessLogger> = OnceLock::new();
+
+/// One access log line, serialized as JSON
+#[derive(Debug, Serialize)]
+pub struct AccessLogEntry {
+    /// RFC 3339 completion time
+    pub timestamp: String,
+    /// Matched route pattern (e.g. `/api/v1/mcps/:mcp_id`)
+    pub route: String,
+    pub method: String,
+    pub status: u16,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<Uuid>,
+    /// Upstream MCP instance IDs a proxy request touched
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub upstream_mcps: Vec<String>,
+}
+
+/// Request identity attached to response extensions by layers that know
+/// it (the auth middleware for dashboard routes, the MCP proxy handler)
+#[derive(Debug, Clone, Default)]
+pub struct AccessContext {
+    pub org_id: Option<Uuid>,
+    pub api_key_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub upstream_mcps: Vec<String>,
+}
+
+/// Where finished entries are written
+enum Sink {
+    Stdout,
+    /// Appends to `{path}.{YYYY-MM-DD}`, switching files at date rollover
+    File {
+        path: String,
+    },
+    /// Batched NDJSON POSTs to a shipper endpoint
+    Http {
+        endpoint: String,
+    },
+}
+
+struct AccessLogger {
+    tx: Option<mpsc::UnboundedSender<AccessLogEntry>>,
+    excluded_paths: Vec<String>,
+}
+
+/// Initialize the global logger from config (call once at startup)
+pub fn init(config: &Config) {
+    let logger = match config.access_log_sink.as_deref() {
+        Some(sink_name) => {
+            let sink = match sink_name {
+                "stdout" => Sink::Stdout,
+                "file" => Sink::File {
+                    path: config.access_log_file.clone(),
+                },
+                "http" => match config.access_log_endpoint.clone() {
+                    Some(endpoint) => Sink::Http { endpoint },
+                    None => {
+                        tracing::error!(
+                            "ACCESS_LOG_SINK=http requires ACCESS_LOG_HTTP_ENDPOINT; access logging disabled"
+                        );
+                        let _ = LOGGER.set(AccessLogger {
+                            tx: None,
+                            excluded_paths: vec![],
+                        });
+                        return;
+                    }
+                },
+                other => {
+                    tracing::error!(
+                        sink = %other,
+                        "Unknown ACCESS_LOG_SINK (expected stdout, file or http); access logging disabled"
+                    );
+                    let _ = LOGGER.set(AccessLogger {
+                        tx: None,
+                        excluded_paths: vec![],
+                    });
+                    return;
+                }
+            };
+
+            let (tx, rx) = mpsc::unbounded_channel();
+            tokio::spawn(write_loop(sink, rx));
+            tracing::info!(sink = %sink_name, "Access logging enabled");
+            AccessLogger {
+                tx: Some(tx),
+                excluded_paths: config.access_log_exclude_paths.clone(),
+            }
+        }
+        None => AccessLogger {
+            tx: None,
+            excluded_paths: vec![],
+        },
+    };
+    let _ = LOGGER.set(logger);
+}
+
+/// Default exclusion list, used when `ACCESS_LOG_EXCLUDE_PATHS` is unset
+pub fn default_excluded_paths() -> Vec<String> {
+    DEFAULT_EXCLUDED_PATHS
+        .iter()
+        .map(|p| p.to_string())
+        .collect()
+}
+
+/// Middleware emitting one entry per handled request
+///
+/// A no-op pass-through while no sink is configured or for excluded
+/// routes.
+pub async fn access_log_middleware(request: Request, next: Next) -> Response {
+    let Some(logger) = LOGGER.get() else {
+        return next.run(request).await;
+    };
+    let Some(tx) = logger.tx.as_ref() else {
+        return next.run(request).await;
+    };
+
+    let method = request.method().as_str().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+
+    if logger.excluded_paths.iter().any(|p| p == &route) {
+        return next.run(request).await;
+    }
+
+    let start = Instant::now();
+    let response = next.run(request).await;
+
+    let context = response
+        .extensions()
+        .get::<AccessContext>()
+        .cloned()
+        .unwrap_or_default();
+
+    let entry = AccessLogEntry {
+        timestamp: OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .unwrap_or_default(),
+        route,
+        method,
+        status: response.status().as_u16(),
+        latency_ms: start.elapsed().as_millis() as u64,
+        org_id: context.org_id,
+        api_key_id: context.api_key_id,
+        user_id: context.user_id,
+        upstream_mcps: context.upstream_mcps,
+    };
+    let _ = tx.send(entry);
+
+    response
+}
+
+/// Background task: drain the channel into the configured sink
+async fn write_loop(sink: Sink, mut rx: mpsc::UnboundedReceiver<AccessLogEntry>) {
+    match sink {
+        Sink::Stdout => {
+            while let Some(entry) = rx.recv().await {
+                if let Ok(line) = serde_json::to_string(&entry) {
+                    println!("{}", line);
+                }
+            }
+        }
+        Sink::File { path } => file_loop(path, rx).await,
+        Sink::Http { endpoint } => http_loop(endpoint, rx).await,
+    }
+}
+
+/// Append entries to a date-suffixed file, rolling over at midnight UTC
+async fn file_loop(path: String, mut rx: mpsc::UnboundedReceiver<AccessLogEntry>) {
+    use std::io::Write;
+
+    let mut current_date: Option<time::Date> = None;
+    let mut file: Option<std::fs::File> = None;
+
+    while let Some(entry) = rx.recv().await {
+        let today = OffsetDateTime::now_utc().date();
+        if current_date != Some(today) {
+            let dated_path = format!("{}.{}", path, today);
+            if let Some(parent) = std::path::Path::new(&dated_path).parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            match std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&dated_path)
+            {
+                Ok(f) => {
+                    current_date = Some(today);
+                    file = Some(f);
+                }
+                Err(e) => {
+                    tracing::error!(path = %dated_path, "Failed to open access log file: {}", e);
+                    file = None;
+                    // Retry the open on the next entry rather than spinning
+                    current_date = Some(today);
+                }
+            }
+        }
+
+        if let (Some(f), Ok(line)) = (file.as_mut(), serde_json::to_string(&entry)) {
+            if let Err(e) = writeln!(f, "{}", line) {
+                tracing::error!("Failed to write access log entry: {}", e);
+            }
+        }
+    }
+}
+
+/// Batch entries and POST them to the shipper as NDJSON (best-effort)
+async fn http_loop(endpoint: String, mut rx: mpsc::UnboundedReceiver<AccessLogEntry>) {
+    let client = match reqwest::Client::builder().timeout(SHIP_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::error!("Failed to build access log shipper client: {}", e);
+            return;
+        }
+    };
+
+    let mut batch: Vec<AccessLogEntry> = Vec::new();
+    let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            entry = rx.recv() => {
+                match entry {
+                    Some(entry) => {
+                        batch.push(entry);
+                        if batch.len() >= MAX_BATCH_SIZE {
+                            ship(&client, &endpoint, std::mem::take(&mut batch)).await;
+                        }
+                    }
+                    // All senders dropped - flush what's left and stop
+                    None => {
+                        if !batch.is_empty() {
+                            ship(&client, &endpoint, batch).await;
+                        }
+                        return;
+                    }
+                }
+            }
+            _ = interval.tick() => {
+                if !batch.is_empty() {
+                    ship(&client, &endpoint, std::mem::take(&mut batch)).await;
+                }
+            }
+        }
+    }
+}
+
+async fn ship(client: &reqwest::Client, endpoint: &str, batch: Vec<AccessLogEntry>) {
+    let body: String = batch
+        .iter()
+        .filter_map(|entry| serde_json::to_string(entry).ok())
+        .map(|line| line + "\n")
+        .collect();
+
+    match client
+        .post(endpoint)
+        .header("content-type", "application/x-ndjson")
+        .body(body)
+        .send()
+        .await
+    {
+        Ok(response) if !response.status().is_success() => {
+            tracing::warn!(
+                status = %response.status(),
+                "Access log shipper rejected batch"
+            );
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::warn!("Failed to ship access log batch: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_serialization_skips_absent_context() {
+        let entry = AccessLogEntry {
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            route: "/api/v1/mcps".to_string(),
+            method: "GET".to_string(),
+            status: 200,
+            latency_ms: 12,
+            org_id: None,
+            api_key_id: None,
+            user_id: None,
+            upstream_mcps: vec![],
+        };
+        let json = serde_json::to_string(&entry).unwrap();
+        assert!(json.contains("\"route\":\"/api/v1/mcps\""));
+        assert!(!json.contains("org_id"));
+        assert!(!json.contains("upstream_mcps"));
+    }
+
+    #[test]
+    fn test_default_exclusions_cover_health_and_metrics() {
+        let excluded = default_excluded_paths();
+        assert!(excluded.contains(&"/health".to_string()));
+        assert!(excluded.contains(&"/metrics".to_string()));
+        assert!(!excluded.contains(&"/mcp".to_string()));
+    }
+}
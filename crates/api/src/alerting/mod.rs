@@ -33,6 +33,9 @@ pub enum AlertType {
     RateLimitViolation,
     SuspiciousActivity,
     AuthenticationAnomaly,
+    MalwareDetected,
+    UpstreamResponseTruncated,
+    McpHealthFlapping,
 }
 
 impl AlertType {
@@ -45,6 +48,39 @@ impl AlertType {
             Self::RateLimitViolation => "rate_limit_violation",
             Self::SuspiciousActivity => "suspicious_activity",
             Self::AuthenticationAnomaly => "authentication_anomaly",
+            Self::MalwareDetected => "malware_detected",
+            Self::UpstreamResponseTruncated => "upstream_response_truncated",
+            Self::McpHealthFlapping => "mcp_health_flapping",
+        }
+    }
+
+    /// All alert types, for admin configuration endpoints
+    pub const ALL: &'static [AlertType] = &[
+        Self::BruteForceAttack,
+        Self::PrivilegeEscalation,
+        Self::DataExfiltration,
+        Self::ConfigurationChange,
+        Self::RateLimitViolation,
+        Self::SuspiciousActivity,
+        Self::AuthenticationAnomaly,
+        Self::MalwareDetected,
+        Self::UpstreamResponseTruncated,
+        Self::McpHealthFlapping,
+    ];
+
+    /// Built-in severity used when no override is configured
+    pub fn default_severity(&self) -> Severity {
+        match self {
+            Self::PrivilegeEscalation => Severity::Critical,
+            Self::ConfigurationChange => Severity::Critical,
+            Self::MalwareDetected => Severity::Critical,
+            Self::BruteForceAttack => Severity::High,
+            Self::DataExfiltration => Severity::High,
+            Self::RateLimitViolation => Severity::Medium,
+            Self::SuspiciousActivity => Severity::Medium,
+            Self::UpstreamResponseTruncated => Severity::Medium,
+            Self::McpHealthFlapping => Severity::Medium,
+            Self::AuthenticationAnomaly => Severity::Low,
         }
     }
 }
@@ -115,9 +151,9 @@ impl AlertService {
         metadata: serde_json::Value,
     ) -> ApiResult<Option<Uuid>> {
         // Get alert configuration
-        let config: Option<(i32, i32)> = sqlx::query_as(
+        let config: Option<(i32, i32, Option<String>)> = sqlx::query_as(
             r#"
-            SELECT threshold_count, threshold_window_seconds
+            SELECT threshold_count, threshold_window_seconds, severity_override
             FROM alert_configurations
             WHERE alert_type = $1 AND enabled = true
             "#,
@@ -126,7 +162,7 @@ impl AlertService {
         .fetch_optional(&self.pool)
         .await?;
 
-        let Some((threshold_count, window_seconds)) = config else {
+        let Some((threshold_count, window_seconds, severity_override)) = config else {
             // Alert type not configured or disabled
             return Ok(None);
         };
@@ -144,16 +180,11 @@ impl AlertService {
             return Ok(None);
         }
 
-        // Determine severity based on alert type
-        let severity = match alert_type {
-            AlertType::PrivilegeEscalation => Severity::Critical,
-            AlertType::ConfigurationChange => Severity::Critical,
-            AlertType::BruteForceAttack => Severity::High,
-            AlertType::DataExfiltration => Severity::High,
-            AlertType::RateLimitViolation => Severity::Medium,
-            AlertType::SuspiciousActivity => Severity::Medium,
-            AlertType::AuthenticationAnomaly => Severity::Low,
-        };
+        // Configured override wins over the built-in severity for the type
+        let severity = severity_override
+            .as_deref()
+            .map(parse_severity)
+            .unwrap_or_else(|| alert_type.default_severity());
 
         // Create alert
         let alert_id = self
@@ -334,9 +365,83 @@ impl AlertService {
                     serde_json::to_string_pretty(metadata).unwrap_or_default()
                 ),
             ),
+            AlertType::MalwareDetected => (
+                "Malware Detected in Uploaded File".to_string(),
+                format!(
+                    "A virus scan flagged an uploaded file. The file has been \
+                     quarantined and cannot be downloaded. Details: {}",
+                    serde_json::to_string_pretty(metadata).unwrap_or_default()
+                ),
+            ),
+            AlertType::UpstreamResponseTruncated => (
+                "Upstream MCP Responses Repeatedly Truncated".to_string(),
+                format!(
+                    "An upstream MCP keeps returning responses over its size \
+                     limit; callers are receiving truncated errors. Details: {}",
+                    serde_json::to_string_pretty(metadata).unwrap_or_default()
+                ),
+            ),
+            AlertType::McpHealthFlapping => (
+                format!("MCP Health Flapping ({} transitions)", event_count),
+                format!(
+                    "An MCP keeps flipping between healthy and unhealthy in \
+                     automatic health checks. This usually means an unstable \
+                     upstream or intermittent connectivity. Details: {}",
+                    serde_json::to_string_pretty(metadata).unwrap_or_default()
+                ),
+            ),
         }
     }
 
+    /// Acknowledge an alert; returns false when the alert does not exist
+    /// or is already acknowledged
+    pub async fn acknowledge_alert(&self, alert_id: Uuid, user_id: Uuid) -> ApiResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE security_alerts
+            SET acknowledged_at = NOW(),
+                acknowledged_by = $2
+            WHERE id = $1 AND acknowledged_at IS NULL
+            "#,
+        )
+        .bind(alert_id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Resolve an alert; returns false when the alert does not exist
+    /// or is already resolved
+    pub async fn resolve_alert(
+        &self,
+        alert_id: Uuid,
+        user_id: Uuid,
+        resolution_notes: Option<&str>,
+    ) -> ApiResult<bool> {
+        // Resolving implies acknowledging so the schema invariant
+        // (acknowledged before resolved) always holds
+        let result = sqlx::query(
+            r#"
+            UPDATE security_alerts
+            SET acknowledged_at = COALESCE(acknowledged_at, NOW()),
+                acknowledged_by = COALESCE(acknowledged_by, $2),
+                resolved_at = NOW(),
+                resolved_by = $2,
+                resolution_notes = $3
+            WHERE id = $1 AND resolved_at IS NULL
+            "#,
+        )
+        .bind(alert_id)
+        .bind(user_id)
+        .bind(resolution_notes)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Mark alert as notified
     pub async fn mark_notified(
         &self,
@@ -374,6 +479,9 @@ fn parse_alert_type(s: &str) -> AlertType {
         "rate_limit_violation" => AlertType::RateLimitViolation,
         "suspicious_activity" => AlertType::SuspiciousActivity,
         "authentication_anomaly" => AlertType::AuthenticationAnomaly,
+        "malware_detected" => AlertType::MalwareDetected,
+        "upstream_response_truncated" => AlertType::UpstreamResponseTruncated,
+        "mcp_health_flapping" => AlertType::McpHealthFlapping,
         _ => AlertType::SuspiciousActivity,
     }
 }
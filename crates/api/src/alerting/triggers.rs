@@ -207,3 +207,56 @@ pub async fn trigger_auth_anomaly_alert(
         )
         .await
 }
+
+/// Trigger malware detection alert
+#[allow(clippy::too_many_arguments)]
+pub async fn trigger_malware_detected_alert(
+    alert_service: &AlertService,
+    user_id: Option<Uuid>,
+    org_id: Option<Uuid>,
+    attachment_id: Uuid,
+    filename: &str,
+    signature: &str,
+    scan_engine: &str,
+) -> ApiResult<Option<Uuid>> {
+    alert_service
+        .record_event(
+            AlertType::MalwareDetected,
+            &format!("attachment:{}", attachment_id),
+            user_id,
+            org_id,
+            None,
+            json!({
+                "attachment_id": attachment_id,
+                "filename": filename,
+                "signature": signature,
+                "scan_engine": scan_engine
+            }),
+        )
+        .await
+}
+
+/// Trigger upstream response truncation alert
+///
+/// Fires when an MCP repeatedly returns responses over its size limit
+/// (threshold configured in alert_configurations)
+pub async fn trigger_upstream_truncation_alert(
+    alert_service: &AlertService,
+    org_id: Option<Uuid>,
+    mcp_id: Uuid,
+    method: &str,
+) -> ApiResult<Option<Uuid>> {
+    alert_service
+        .record_event(
+            AlertType::UpstreamResponseTruncated,
+            &format!("mcp:{}:truncated", mcp_id),
+            None,
+            org_id,
+            None,
+            json!({
+                "mcp_id": mcp_id,
+                "method": method
+            }),
+        )
+        .await
+}
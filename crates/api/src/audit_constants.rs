@@ -85,6 +85,12 @@ pub mod auth_event {
     /// User failed to verify 2FA code (potential brute force attempt)
     pub const TWO_FA_FAILED: &str = "2fa_failed";
 
+    /// User registered a WebAuthn passkey
+    pub const PASSKEY_REGISTERED: &str = "passkey_registered";
+
+    /// User removed a WebAuthn passkey
+    pub const PASSKEY_REMOVED: &str = "passkey_removed";
+
     // OAuth Events
     /// User initiated OAuth authentication (clicked Sign in with Google/GitHub)
     pub const OAUTH_INITIATED: &str = "oauth_initiated";
@@ -219,6 +225,12 @@ pub mod admin_action {
     /// Custom organization limits cleared
     pub const CUSTOM_LIMITS_CLEARED: &str = "clear_custom_limits";
 
+    /// Temporary rate limit exemption granted (CRITICAL for bypasses)
+    pub const RATE_LIMIT_EXEMPTION_GRANTED: &str = "grant_rate_limit_exemption";
+
+    /// Rate limit exemption revoked before its expiry
+    pub const RATE_LIMIT_EXEMPTION_REVOKED: &str = "revoke_rate_limit_exemption";
+
     // Email Management (SOC 2 CC7.1)
     /// Admin composed and sent an email
     pub const SEND_EMAIL: &str = "send_email";
@@ -232,6 +244,57 @@ pub mod admin_action {
 
     /// Support ticket reply sent
     pub const TICKET_REPLY_SENT: &str = "ticket_reply_sent";
+
+    /// Tag added to or removed from a support ticket
+    pub const TICKET_TAGS_CHANGED: &str = "ticket_tags_changed";
+
+    /// Ticket tag created in or deleted from the registry
+    pub const SUPPORT_TAG_REGISTRY_CHANGED: &str = "support_tag_registry_changed";
+
+    // Security Action Approvals
+    /// Second-admin approval requested for a destructive security action
+    pub const SECURITY_APPROVAL_REQUESTED: &str = "security_approval_requested";
+
+    /// Security action approved and executed by a second admin (CRITICAL)
+    pub const SECURITY_APPROVAL_APPROVED: &str = "security_approval_approved";
+
+    /// Security action rejected by a second admin
+    pub const SECURITY_APPROVAL_REJECTED: &str = "security_approval_rejected";
+
+    // Reserved Subdomain Management
+    /// Subdomain added to the admin-managed reserved list
+    pub const RESERVED_SUBDOMAIN_ADDED: &str = "reserved_subdomain_added";
+
+    /// Subdomain removed from the admin-managed reserved list
+    pub const RESERVED_SUBDOMAIN_REMOVED: &str = "reserved_subdomain_removed";
+
+    // Support Routing Rules
+    /// Ticket routing rule created
+    pub const SUPPORT_RULE_CREATED: &str = "support_rule_created";
+
+    /// Ticket routing rule updated
+    pub const SUPPORT_RULE_UPDATED: &str = "support_rule_updated";
+
+    /// Ticket routing rule deleted
+    pub const SUPPORT_RULE_DELETED: &str = "support_rule_deleted";
+
+    // Platform Incidents
+    /// Platform incident opened
+    pub const INCIDENT_CREATED: &str = "incident_created";
+
+    /// Platform incident updated (status change or timeline entry)
+    pub const INCIDENT_UPDATED: &str = "incident_updated";
+
+    // Alert Configuration
+    /// Alert rule disabled by admin (CRITICAL - security degradation)
+    pub const ALERT_CONFIG_DISABLED: &str = "alert_config_disabled";
+
+    // Spam Quarantine Review
+    /// Quarantined submission approved (ticket created)
+    pub const SPAM_QUARANTINE_APPROVED: &str = "spam_quarantine_approved";
+
+    /// Quarantined submission discarded
+    pub const SPAM_QUARANTINE_DISCARDED: &str = "spam_quarantine_discarded";
 }
 
 /// Target types for admin audit logs
@@ -258,6 +321,18 @@ pub mod target_type {
 
     /// Support ticket
     pub const TICKET: &str = "ticket";
+
+    /// Spam quarantine entry
+    pub const SPAM_QUARANTINE: &str = "spam_quarantine";
+
+    /// Security alerting rule configuration
+    pub const ALERT_CONFIG: &str = "alert_config";
+
+    /// Support ticket routing rule
+    pub const SUPPORT_RULE: &str = "support_rule";
+
+    /// Platform incident
+    pub const INCIDENT: &str = "incident";
 }
 
 #[cfg(test)]
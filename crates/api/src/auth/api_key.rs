@@ -8,6 +8,10 @@ use uuid::Uuid;
 type HmacSha256 = Hmac<Sha256>;
 
 const API_KEY_PREFIX: &str = "pmcp_";
+/// Prefix for superadmin-issued admin API tokens (see admin_api_tokens table)
+const ADMIN_TOKEN_PREFIX: &str = "pmcpa_";
+/// Prefix for org-issued SCIM provisioning tokens (see scim_tokens table)
+const SCIM_TOKEN_PREFIX: &str = "pmcps_";
 const API_KEY_VERSION: &str = "01";
 
 /// API Key manager for generation and validation
@@ -27,6 +31,22 @@ impl ApiKeyManager {
     /// Generate a new API key
     /// Returns (full_key, key_hash, key_prefix)
     pub fn generate_key(&self) -> Result<(String, String, String), ApiKeyError> {
+        self.generate_with_prefix(API_KEY_PREFIX)
+    }
+
+    /// Generate a new admin API token (same scheme as API keys, distinct prefix)
+    /// Returns (full_token, token_hash, token_prefix)
+    pub fn generate_admin_token(&self) -> Result<(String, String, String), ApiKeyError> {
+        self.generate_with_prefix(ADMIN_TOKEN_PREFIX)
+    }
+
+    /// Generate a new SCIM provisioning token (same scheme, distinct prefix)
+    /// Returns (full_token, token_hash, token_prefix)
+    pub fn generate_scim_token(&self) -> Result<(String, String, String), ApiKeyError> {
+        self.generate_with_prefix(SCIM_TOKEN_PREFIX)
+    }
+
+    fn generate_with_prefix(&self, prefix: &str) -> Result<(String, String, String), ApiKeyError> {
         // Generate random identifier
         let key_id = Uuid::new_v4();
         let random_bytes: [u8; 16] = rand::random();
@@ -43,25 +63,44 @@ impl ApiKeyManager {
         let sig_hex = hex::encode(&signature[..8]); // Use first 8 bytes of signature
 
         // Full key: prefix + payload + signature
-        let full_key = format!("{}{}{}", API_KEY_PREFIX, payload, sig_hex);
+        let full_key = format!("{}{}{}", prefix, payload, sig_hex);
 
         // Hash for storage
         let key_hash = self.hash_key(&full_key);
 
         // Prefix for display (first 12 chars after prefix)
-        let key_prefix = format!("{}{}...", API_KEY_PREFIX, &payload[..12]);
+        let key_prefix = format!("{}{}...", prefix, &payload[..12]);
 
         Ok((full_key, key_hash, key_prefix))
     }
 
     /// Validate an API key format and signature
     pub fn validate_key(&self, key: &str) -> Result<bool, ApiKeyError> {
+        self.validate_with_prefix(key, API_KEY_PREFIX)
+    }
+
+    /// Validate an admin API token's format and signature
+    pub fn validate_admin_token(&self, key: &str) -> Result<bool, ApiKeyError> {
+        self.validate_with_prefix(key, ADMIN_TOKEN_PREFIX)
+    }
+
+    /// Check whether a presented credential uses the admin token prefix
+    pub fn is_admin_token(key: &str) -> bool {
+        key.starts_with(ADMIN_TOKEN_PREFIX)
+    }
+
+    /// Validate a SCIM provisioning token's format and signature
+    pub fn validate_scim_token(&self, key: &str) -> Result<bool, ApiKeyError> {
+        self.validate_with_prefix(key, SCIM_TOKEN_PREFIX)
+    }
+
+    fn validate_with_prefix(&self, key: &str, prefix: &str) -> Result<bool, ApiKeyError> {
         // Check prefix
-        if !key.starts_with(API_KEY_PREFIX) {
+        if !key.starts_with(prefix) {
             return Ok(false);
         }
 
-        let key_body = &key[API_KEY_PREFIX.len()..];
+        let key_body = &key[prefix.len()..];
 
         // Key body should be: version(2) + uuid(32) + random(32) + signature(16) = 82 chars
         if key_body.len() != 82 {
@@ -98,6 +137,98 @@ impl ApiKeyManager {
             key.to_string()
         }
     }
+
+    /// Check whether a CIDR restriction entry is well-formed
+    ///
+    /// Accepts `addr/prefix` (IPv4 or IPv6) or a bare address, which is
+    /// treated as a /32 (or /128) single-host range.
+    pub fn is_valid_cidr(cidr: &str) -> bool {
+        parse_cidr(cidr).is_some()
+    }
+
+    /// Check whether a source IP falls inside any of the allowed CIDR ranges
+    ///
+    /// An empty allowlist means unrestricted. Unparseable entries are
+    /// skipped (they are rejected at write time, so this only matters for
+    /// rows predating validation).
+    pub fn ip_allowed(ip: &str, allowed_cidrs: &[String]) -> bool {
+        if allowed_cidrs.is_empty() {
+            return true;
+        }
+        let Ok(ip) = ip.parse::<std::net::IpAddr>() else {
+            return false;
+        };
+        allowed_cidrs
+            .iter()
+            .filter_map(|cidr| parse_cidr(cidr))
+            .any(|(network, prefix_len, is_v6)| ip_in_cidr(ip, network, prefix_len, is_v6))
+    }
+
+    /// Check whether a request origin matches any of the allowed origins
+    ///
+    /// Origins compare case-insensitively after stripping any trailing
+    /// slash. A `*.example.com` host wildcard matches any subdomain (but
+    /// not the apex). An empty allowlist means unrestricted; a missing
+    /// origin only passes when the key has no origin restriction.
+    pub fn origin_allowed(origin: &str, allowed_origins: &[String]) -> bool {
+        if allowed_origins.is_empty() {
+            return true;
+        }
+        let origin = origin.trim_end_matches('/').to_ascii_lowercase();
+        allowed_origins.iter().any(|allowed| {
+            let allowed = allowed.trim_end_matches('/').to_ascii_lowercase();
+            if let Some((scheme, host)) = allowed.split_once("://") {
+                if let Some(suffix) = host.strip_prefix("*.") {
+                    return origin
+                        .strip_prefix(scheme)
+                        .and_then(|rest| rest.strip_prefix("://"))
+                        .is_some_and(|origin_host| {
+                            origin_host.ends_with(suffix)
+                                && origin_host.len() > suffix.len()
+                                && origin_host[..origin_host.len() - suffix.len()].ends_with('.')
+                        });
+                }
+            }
+            origin == allowed
+        })
+    }
+}
+
+/// Parse a CIDR entry into (network address, prefix length, is_ipv6)
+///
+/// Bare addresses parse as single-host ranges. Returns None for malformed
+/// entries, out-of-range prefixes, or a prefix on the wrong family.
+fn parse_cidr(cidr: &str) -> Option<(u128, u8, bool)> {
+    let (addr, prefix) = match cidr.split_once('/') {
+        Some((addr, prefix)) => (addr, Some(prefix.parse::<u8>().ok()?)),
+        None => (cidr, None),
+    };
+    let addr = addr.trim().parse::<std::net::IpAddr>().ok()?;
+    let (bits, max_prefix, is_v6) = match addr {
+        std::net::IpAddr::V4(v4) => (u128::from(u32::from(v4)), 32, false),
+        std::net::IpAddr::V6(v6) => (u128::from(v6), 128, true),
+    };
+    let prefix = prefix.unwrap_or(max_prefix);
+    if prefix > max_prefix {
+        return None;
+    }
+    Some((bits, prefix, is_v6))
+}
+
+/// Check whether an IP falls inside a parsed CIDR range
+fn ip_in_cidr(ip: std::net::IpAddr, network: u128, prefix_len: u8, is_v6: bool) -> bool {
+    let (ip_bits, max_prefix) = match ip {
+        std::net::IpAddr::V4(v4) => (u128::from(u32::from(v4)), 32u8),
+        std::net::IpAddr::V6(v6) => (u128::from(v6), 128u8),
+    };
+    if is_v6 != (max_prefix == 128) {
+        return false; // Family mismatch never matches
+    }
+    if prefix_len == 0 {
+        return true;
+    }
+    let shift = u32::from(max_prefix - prefix_len);
+    (ip_bits >> shift) == (network >> shift)
 }
 
 /// Constant-time comparison to prevent timing attacks
@@ -144,6 +275,118 @@ mod tests {
         assert!(prefix.ends_with("..."));
     }
 
+    #[test]
+    fn test_generate_and_validate_admin_token() {
+        let manager = ApiKeyManager::new("test-secret-key-32-chars-minimum!");
+
+        let (token, hash, prefix) = manager
+            .generate_admin_token()
+            .expect("Failed to generate token");
+
+        assert!(ApiKeyManager::is_admin_token(&token));
+        assert!(manager
+            .validate_admin_token(&token)
+            .expect("Validation failed"));
+
+        // Admin tokens must not validate as org API keys, and vice versa
+        assert!(!manager.validate_key(&token).expect("Validation failed"));
+        let (key, _, _) = manager.generate_key().expect("Failed to generate key");
+        assert!(!manager
+            .validate_admin_token(&key)
+            .expect("Validation failed"));
+
+        assert_eq!(manager.hash_key(&token), hash);
+        assert!(prefix.starts_with(ADMIN_TOKEN_PREFIX));
+    }
+
+    #[test]
+    fn test_generate_and_validate_scim_token() {
+        let manager = ApiKeyManager::new("test-secret-key-32-chars-minimum!");
+
+        let (token, hash, prefix) = manager
+            .generate_scim_token()
+            .expect("Failed to generate token");
+
+        assert!(manager
+            .validate_scim_token(&token)
+            .expect("Validation failed"));
+
+        // SCIM tokens must not validate as org API keys or admin tokens
+        assert!(!manager.validate_key(&token).expect("Validation failed"));
+        assert!(!manager
+            .validate_admin_token(&token)
+            .expect("Validation failed"));
+
+        assert_eq!(manager.hash_key(&token), hash);
+        assert!(prefix.starts_with(SCIM_TOKEN_PREFIX));
+    }
+
+    #[test]
+    fn test_ip_allowed_cidr_matching() {
+        let cidrs = vec!["10.0.0.0/8".to_string(), "192.168.1.42".to_string()];
+
+        assert!(ApiKeyManager::ip_allowed("10.1.2.3", &cidrs));
+        assert!(ApiKeyManager::ip_allowed("192.168.1.42", &cidrs));
+        assert!(!ApiKeyManager::ip_allowed("192.168.1.43", &cidrs));
+        assert!(!ApiKeyManager::ip_allowed("11.0.0.1", &cidrs));
+
+        // IPv6 ranges, and family mismatch never matches
+        let v6 = vec!["2001:db8::/32".to_string()];
+        assert!(ApiKeyManager::ip_allowed("2001:db8::1", &v6));
+        assert!(!ApiKeyManager::ip_allowed("2001:db9::1", &v6));
+        assert!(!ApiKeyManager::ip_allowed("10.0.0.1", &v6));
+
+        // Empty allowlist = unrestricted; garbage IP never passes a restriction
+        assert!(ApiKeyManager::ip_allowed("10.0.0.1", &[]));
+        assert!(!ApiKeyManager::ip_allowed("not-an-ip", &cidrs));
+    }
+
+    #[test]
+    fn test_is_valid_cidr() {
+        assert!(ApiKeyManager::is_valid_cidr("10.0.0.0/8"));
+        assert!(ApiKeyManager::is_valid_cidr("192.168.1.1"));
+        assert!(ApiKeyManager::is_valid_cidr("2001:db8::/32"));
+        assert!(!ApiKeyManager::is_valid_cidr("10.0.0.0/33"));
+        assert!(!ApiKeyManager::is_valid_cidr("example.com"));
+        assert!(!ApiKeyManager::is_valid_cidr("10.0.0.0/"));
+    }
+
+    #[test]
+    fn test_origin_allowed_matching() {
+        let origins = vec![
+            "https://app.example.com".to_string(),
+            "https://*.acme.io".to_string(),
+        ];
+
+        assert!(ApiKeyManager::origin_allowed(
+            "https://app.example.com",
+            &origins
+        ));
+        // Case-insensitive, trailing slash ignored
+        assert!(ApiKeyManager::origin_allowed(
+            "https://App.Example.com/",
+            &origins
+        ));
+        // Wildcard matches subdomains but not the apex, and not other schemes
+        assert!(ApiKeyManager::origin_allowed(
+            "https://dash.acme.io",
+            &origins
+        ));
+        assert!(!ApiKeyManager::origin_allowed("https://acme.io", &origins));
+        assert!(!ApiKeyManager::origin_allowed(
+            "http://dash.acme.io",
+            &origins
+        ));
+        assert!(!ApiKeyManager::origin_allowed(
+            "https://notacme.io",
+            &origins
+        ));
+        assert!(!ApiKeyManager::origin_allowed("https://evil.com", &origins));
+
+        // Empty allowlist = unrestricted
+        assert!(ApiKeyManager::origin_allowed("https://evil.com", &[]));
+    }
+
     #[test]
     fn test_invalid_key() {
         let manager = ApiKeyManager::new("test-secret-key-32-chars-minimum!");
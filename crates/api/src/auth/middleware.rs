@@ -1,5 +1,9 @@
 //! Authentication middleware for Axum
 
+// Self-hosted builds compile out the Supabase verification path; the cache
+// plumbing stays in AuthState so the struct layout is identical either way.
+#![cfg_attr(feature = "self-hosted", allow(dead_code))]
+
 use axum::{
     extract::{Request, State},
     http::{
@@ -15,6 +19,7 @@ use serde::Deserialize;
 use serde_json::json;
 use sqlx::{FromRow, PgPool};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{broadcast, Mutex, RwLock};
@@ -47,10 +52,121 @@ pub(crate) type InFlightRequests = Arc<Mutex<HashMap<String, broadcast::Sender<I
 pub(crate) struct CachedSupabaseAuth {
     user: SupabaseUserResponse,
     cached_at: Instant,
+    /// Last cache hit, for LRU eviction
+    last_used: Instant,
+}
+
+/// Bounded LRU cache for Supabase token verification results with
+/// hit/miss/eviction/invalidation counters (crate-internal, not part of
+/// the public API)
+///
+/// Entries are keyed by the raw bearer token; revocation events (logout,
+/// suspension, role changes) call [`TokenCache::invalidate_user`] so a
+/// revoked token stops resolving before its TTL runs out.
+#[derive(Debug, Default)]
+pub(crate) struct TokenCache {
+    entries: RwLock<HashMap<String, CachedSupabaseAuth>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    invalidations: AtomicU64,
 }
 
-/// Thread-safe token cache type (crate-internal, not part of public API)
-pub(crate) type TokenCache = Arc<RwLock<HashMap<String, CachedSupabaseAuth>>>;
+/// Counter snapshot for the admin metrics endpoint
+#[derive(Debug)]
+pub(crate) struct TokenCacheStats {
+    pub(crate) entries: usize,
+    pub(crate) capacity: usize,
+    pub(crate) hits: u64,
+    pub(crate) misses: u64,
+    pub(crate) evictions: u64,
+    pub(crate) invalidations: u64,
+}
+
+impl TokenCache {
+    /// Look up a fresh cached verification, counting hits and misses
+    ///
+    /// Takes the write lock so the hit can bump `last_used` for LRU;
+    /// entries past their TTL are dropped on the way out.
+    async fn get(&self, token: &str) -> Option<SupabaseUserResponse> {
+        let mut entries = self.entries.write().await;
+        match entries.get_mut(token) {
+            Some(cached) if cached.cached_at.elapsed() < TOKEN_CACHE_TTL => {
+                cached.last_used = Instant::now();
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(cached.user.clone())
+            }
+            Some(_) => {
+                entries.remove(token);
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Cache a successful verification, evicting the least recently used
+    /// entry when at capacity (SOC 2 CC6.1: bounded memory)
+    async fn insert(&self, token: String, user: SupabaseUserResponse) {
+        let mut entries = self.entries.write().await;
+        if entries.len() >= MAX_CACHE_ENTRIES && !entries.contains_key(&token) {
+            if let Some(lru_key) = entries
+                .iter()
+                .min_by_key(|(_, v)| v.last_used)
+                .map(|(k, _)| k.clone())
+            {
+                entries.remove(&lru_key);
+                self.evictions.fetch_add(1, Ordering::Relaxed);
+                tracing::debug!("Evicted least recently used token cache entry");
+            }
+        }
+        let now = Instant::now();
+        entries.insert(
+            token,
+            CachedSupabaseAuth {
+                user,
+                cached_at: now,
+                last_used: now,
+            },
+        );
+    }
+
+    /// Drop every cached entry belonging to a user, matched by Supabase
+    /// user ID or email (the cache only sees Supabase IDs, which differ
+    /// from our internal user IDs for linked accounts). Returns how many
+    /// entries were removed.
+    pub(crate) async fn invalidate_user(&self, user_id: &str, email: Option<&str>) -> usize {
+        let mut entries = self.entries.write().await;
+        let before = entries.len();
+        entries.retain(|_, cached| {
+            let id_match = cached.user.id == user_id;
+            let email_match = matches!(
+                (email, cached.user.email.as_deref()),
+                (Some(e), Some(cached_email)) if e.eq_ignore_ascii_case(cached_email)
+            );
+            !id_match && !email_match
+        });
+        let removed = before - entries.len();
+        self.invalidations
+            .fetch_add(removed as u64, Ordering::Relaxed);
+        removed
+    }
+
+    /// Snapshot counters and current size for the metrics endpoint
+    pub(crate) async fn stats(&self) -> TokenCacheStats {
+        TokenCacheStats {
+            entries: self.entries.read().await.len(),
+            capacity: MAX_CACHE_ENTRIES,
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+        }
+    }
+}
 
 use super::{api_key::ApiKeyManager, jwt::JwtManager, password, sessions};
 
@@ -62,6 +178,9 @@ struct ApiKeyRow {
     org_id: Uuid,
     scopes: Option<serde_json::Value>,
     subscription_tier: String,
+    /// False when the request authenticated with a pre-rotation secret
+    /// that is still inside its overlap window
+    current_secret: bool,
 }
 
 /// Database row type for organization membership lookup
@@ -95,7 +214,13 @@ impl AuthUser {
 pub enum AuthMethod {
     Jwt,
     SupabaseJwt,
-    ApiKey { key_id: Uuid },
+    ApiKey {
+        key_id: Uuid,
+    },
+    /// Superadmin-issued scoped token for internal automation
+    AdminToken {
+        token_id: Uuid,
+    },
 }
 
 /// Response from Supabase /auth/v1/user endpoint
@@ -115,7 +240,7 @@ pub struct AuthState {
     pub supabase_anon_key: String,
     pub http_client: Client,
     /// Cache for Supabase token verification results to prevent rate limiting
-    pub(crate) token_cache: TokenCache,
+    pub(crate) token_cache: Arc<TokenCache>,
     /// Track in-flight Supabase verification requests for request coalescing
     /// This prevents multiple parallel requests from all hitting Supabase simultaneously
     pub(crate) in_flight_requests: InFlightRequests,
@@ -216,7 +341,19 @@ pub async fn require_auth(
     let auth_result = if let Some(token) = extract_bearer_token(&request) {
         authenticate_jwt(&auth_state, &token).await
     } else if let Some(key) = extract_api_key(&request) {
-        authenticate_api_key(&auth_state, &key, ip_address, user_agent).await
+        if ApiKeyManager::is_admin_token(&key) {
+            authenticate_admin_token(
+                &auth_state,
+                &key,
+                &path,
+                request.method(),
+                ip_address,
+                user_agent,
+            )
+            .await
+        } else {
+            authenticate_api_key(&auth_state, &key, ip_address, user_agent).await
+        }
     } else {
         tracing::warn!(path = %path, "require_auth: no valid auth found (header or cookie)");
         Err(AuthError::MissingAuth)
@@ -232,8 +369,32 @@ pub async fn require_auth(
                 auth_method = ?auth_user.auth_method,
                 "require_auth: authentication successful"
             );
+
+            // Viewer role: read-only access to org resources
+            if auth_user.role == "viewer"
+                && !is_read_only_method(request.method())
+                && !is_viewer_writable_endpoint(&path)
+            {
+                tracing::warn!(
+                    path = %path,
+                    user_id = ?auth_user.user_id,
+                    "require_auth: viewer attempted write operation"
+                );
+                return viewer_read_only_response();
+            }
+
+            let access_context = crate::access_log::AccessContext {
+                org_id: auth_user.org_id,
+                api_key_id: None,
+                user_id: auth_user.user_id,
+                upstream_mcps: vec![],
+            };
+
             request.extensions_mut().insert(auth_user);
-            next.run(request).await
+            let mut response = next.run(request).await;
+            // Identity for the access log middleware further out
+            response.extensions_mut().insert(access_context);
+            response
         }
         Err(err) => {
             tracing::warn!(path = %path, error = ?err, "require_auth: authentication failed");
@@ -418,26 +579,30 @@ async fn authenticate_jwt(auth_state: &AuthState, token: &str) -> Result<AuthUse
     }
 
     // If PlexMCP JWT validation fails, try Supabase
-    tracing::info!("authenticate_jwt: PlexMCP JWT validation failed, trying Supabase API");
-
-    // If that fails and we have Supabase configured, verify via Supabase API
-    if !auth_state.supabase_url.is_empty() {
-        // Verify token by calling Supabase's user endpoint
-        let supabase_user = match verify_supabase_token_via_api(auth_state, token).await {
-            Ok(user) => user,
-            Err(e) => {
-                tracing::warn!("authenticate_jwt: Supabase verification failed: {:?}", e);
-                return Err(e);
-            }
-        };
+    // (compiled out entirely in self-hosted builds - local JWTs only)
+    #[cfg(not(feature = "self-hosted"))]
+    {
+        tracing::info!("authenticate_jwt: PlexMCP JWT validation failed, trying Supabase API");
+
+        // If that fails and we have Supabase configured, verify via Supabase API
+        if !auth_state.supabase_url.is_empty() {
+            // Verify token by calling Supabase's user endpoint
+            let supabase_user = match verify_supabase_token_via_api(auth_state, token).await {
+                Ok(user) => user,
+                Err(e) => {
+                    tracing::warn!("authenticate_jwt: Supabase verification failed: {:?}", e);
+                    return Err(e);
+                }
+            };
 
-        // Parse the user ID from Supabase response
-        let user_id = Uuid::parse_str(&supabase_user.id).map_err(|_| AuthError::InvalidToken)?;
+            // Parse the user ID from Supabase response
+            let user_id =
+                Uuid::parse_str(&supabase_user.id).map_err(|_| AuthError::InvalidToken)?;
 
-        // Ensure the OAuth user exists in our users table (for foreign key constraints)
-        // This handles users who authenticate via OAuth but don't have a record yet
-        // Returns resolved_user_id which may differ from OAuth user_id if there's an existing user
-        let (org_id, role, resolved_user_id) = ensure_oauth_user_exists(
+            // Ensure the OAuth user exists in our users table (for foreign key constraints)
+            // This handles users who authenticate via OAuth but don't have a record yet
+            // Returns resolved_user_id which may differ from OAuth user_id if there's an existing user
+            let (org_id, role, resolved_user_id) = ensure_oauth_user_exists(
             &auth_state.pool,
             user_id,
             supabase_user.email.as_deref(),
@@ -448,16 +613,17 @@ async fn authenticate_jwt(auth_state: &AuthState, token: &str) -> Result<AuthUse
             AuthError::DatabaseError
         })?;
 
-        // Use resolved_user_id for AuthUser - this ensures FK constraints work
-        // because it references a user that actually exists in the users table
-        return Ok(AuthUser {
-            user_id: Some(resolved_user_id),
-            org_id: Some(org_id),
-            role,
-            email: supabase_user.email,
-            auth_method: AuthMethod::SupabaseJwt,
-            session_id: None, // Supabase JWTs are externally managed (no session tracking)
-        });
+            // Use resolved_user_id for AuthUser - this ensures FK constraints work
+            // because it references a user that actually exists in the users table
+            return Ok(AuthUser {
+                user_id: Some(resolved_user_id),
+                org_id: Some(org_id),
+                role,
+                email: supabase_user.email,
+                auth_method: AuthMethod::SupabaseJwt,
+                session_id: None, // Supabase JWTs are externally managed (no session tracking)
+            });
+        }
     }
 
     Err(AuthError::InvalidToken)
@@ -485,14 +651,9 @@ async fn verify_supabase_token_via_api(
     }
 
     // Check cache first to avoid hitting Supabase rate limits
-    {
-        let cache = auth_state.token_cache.read().await;
-        if let Some(cached) = cache.get(token) {
-            if cached.cached_at.elapsed() < TOKEN_CACHE_TTL {
-                tracing::debug!("Using cached Supabase auth for user {}", cached.user.id);
-                return Ok(cached.user.clone());
-            }
-        }
+    if let Some(user) = auth_state.token_cache.get(token).await {
+        tracing::debug!("Using cached Supabase auth for user {}", user.id);
+        return Ok(user);
     }
 
     // Request coalescing: check if there's already an in-flight request for this token
@@ -547,29 +708,12 @@ async fn verify_supabase_token_via_api(
         }
     }
 
-    // Cache successful results (with size limit to prevent memory exhaustion)
+    // Cache successful results (bounded by LRU eviction)
     if let Ok(ref user) = result {
-        let mut cache = auth_state.token_cache.write().await;
-
-        // SOC 2 CC6.1: Evict oldest entry if at capacity
-        if cache.len() >= MAX_CACHE_ENTRIES {
-            if let Some(oldest_key) = cache
-                .iter()
-                .min_by_key(|(_, v)| v.cached_at)
-                .map(|(k, _)| k.clone())
-            {
-                cache.remove(&oldest_key);
-                tracing::debug!("Evicted oldest cache entry to stay under limit");
-            }
-        }
-
-        cache.insert(
-            token.to_string(),
-            CachedSupabaseAuth {
-                user: user.clone(),
-                cached_at: Instant::now(),
-            },
-        );
+        auth_state
+            .token_cache
+            .insert(token.to_string(), user.clone())
+            .await;
         tracing::debug!("Cached Supabase auth for user {}", user.id);
     }
 
@@ -856,11 +1000,16 @@ async fn authenticate_api_key(
 
     let api_key: ApiKeyRow = match sqlx::query_as(
         r#"
-        SELECT ak.id, ak.org_id, ak.scopes, o.subscription_tier
+        SELECT ak.id, ak.org_id, ak.scopes, o.subscription_tier,
+               (ak.key_hash = $1) AS current_secret
         FROM api_keys ak
         JOIN organizations o ON o.id = ak.org_id
-        WHERE ak.key_hash = $1
+        WHERE (ak.key_hash = $1
+               OR (ak.previous_key_hash = $1 AND ak.rotation_expires_at > NOW()))
           AND (ak.expires_at IS NULL OR ak.expires_at > NOW())
+          AND (o.max_api_key_age_days IS NULL
+               OR COALESCE(ak.last_rotated_at, ak.created_at)
+                  > NOW() - make_interval(days => o.max_api_key_age_days))
         "#,
     )
     .bind(&key_hash)
@@ -882,16 +1031,18 @@ async fn authenticate_api_key(
         Err(_) => return Err(AuthError::DatabaseError),
     };
 
-    // Update last used timestamp (fire and forget)
+    // Update last used timestamp (fire and forget); the pre-rotation secret
+    // tracks separately so admins can see whether it is still in use
     let pool = auth_state.pool.clone();
     let key_id = api_key.id;
+    let current_secret = api_key.current_secret;
     tokio::spawn(async move {
-        let _ = sqlx::query(
+        let query = if current_secret {
             "UPDATE api_keys SET last_used_at = NOW(), request_count = request_count + 1 WHERE id = $1"
-        )
-        .bind(key_id)
-        .execute(&pool)
-        .await;
+        } else {
+            "UPDATE api_keys SET previous_last_used_at = NOW(), request_count = request_count + 1 WHERE id = $1"
+        };
+        let _ = sqlx::query(query).bind(key_id).execute(&pool).await;
     });
 
     Ok(AuthUser {
@@ -940,6 +1091,192 @@ fn log_api_key_failure(
     });
 }
 
+/// Database row for admin token lookup
+#[derive(Debug, FromRow)]
+struct AdminTokenRow {
+    id: Uuid,
+    scopes: Vec<String>,
+    created_by: Uuid,
+    email: String,
+}
+
+/// Scope an admin token needs for the given path and method
+///
+/// Admin routes look like `/api/v1/admin/<area>/...`; the required scope is
+/// `<area>:read` for read-only methods and `<area>:write` otherwise. Returns
+/// None for paths outside the admin API (tokens are never valid there).
+fn admin_token_required_scope(path: &str, method: &Method) -> Option<String> {
+    let rest = path.strip_prefix("/api/v1/admin/")?;
+    let area = rest.split('/').next().filter(|s| !s.is_empty())?;
+    let action = if is_read_only_method(method) {
+        "read"
+    } else {
+        "write"
+    };
+    Some(format!("{}:{}", area, action))
+}
+
+/// Check whether granted scopes satisfy a required scope (write implies read)
+fn admin_token_scope_allows(scopes: &[String], required: &str) -> bool {
+    if scopes.iter().any(|s| s == required) {
+        return true;
+    }
+    match required.strip_suffix(":read") {
+        Some(area) => scopes.iter().any(|s| *s == format!("{}:write", area)),
+        None => false,
+    }
+}
+
+/// Authenticate a superadmin-issued admin API token
+///
+/// Tokens are validated like API keys (HMAC signature, hashed lookup) but
+/// live in admin_api_tokens: scope-restricted, expiring and revocable. The
+/// scope check happens here so handlers never see an out-of-scope request,
+/// and every use - allowed or denied - lands in admin_token_usage_log.
+async fn authenticate_admin_token(
+    auth_state: &AuthState,
+    key: &str,
+    path: &str,
+    method: &Method,
+    ip_address: Option<String>,
+    user_agent: Option<String>,
+) -> Result<AuthUser, AuthError> {
+    let key_prefix = ApiKeyManager::extract_prefix(key);
+
+    // Validate token format and signature
+    if !auth_state
+        .api_key_manager
+        .validate_admin_token(key)
+        .map_err(|_| AuthError::InvalidApiKey)?
+    {
+        log_api_key_failure(
+            auth_state.pool.clone(),
+            key_prefix,
+            "admin_token_invalid_signature".to_string(),
+            ip_address,
+            user_agent,
+        );
+        return Err(AuthError::InvalidApiKey);
+    }
+
+    // Hash and look up in database
+    let token_hash = auth_state.api_key_manager.hash_key(key);
+
+    let token: AdminTokenRow = match sqlx::query_as(
+        r#"
+        SELECT t.id, t.scopes, t.created_by, u.email
+        FROM admin_api_tokens t
+        JOIN users u ON u.id = t.created_by
+        WHERE t.token_hash = $1
+          AND t.revoked_at IS NULL
+          AND t.expires_at > NOW()
+        "#,
+    )
+    .bind(&token_hash)
+    .fetch_optional(&auth_state.pool)
+    .await
+    {
+        Ok(Some(token)) => token,
+        Ok(None) => {
+            log_api_key_failure(
+                auth_state.pool.clone(),
+                key_prefix,
+                "admin_token_not_found_expired_or_revoked".to_string(),
+                ip_address,
+                user_agent,
+            );
+            return Err(AuthError::InvalidApiKey);
+        }
+        Err(_) => return Err(AuthError::DatabaseError),
+    };
+
+    // Scope check: tokens only reach admin routes, and only the areas granted
+    let (outcome, required_scope) = match admin_token_required_scope(path, method) {
+        None => ("denied_path", None),
+        Some(required) => {
+            if admin_token_scope_allows(&token.scopes, &required) {
+                ("ok", Some(required))
+            } else {
+                ("denied_scope", Some(required))
+            }
+        }
+    };
+
+    log_admin_token_usage(
+        auth_state.pool.clone(),
+        token.id,
+        method.to_string(),
+        path.to_string(),
+        outcome,
+        ip_address,
+        user_agent,
+    );
+
+    if outcome != "ok" {
+        tracing::warn!(
+            token_id = %token.id,
+            path = %path,
+            required_scope = ?required_scope,
+            outcome = %outcome,
+            "Admin token request denied"
+        );
+        return Err(AuthError::InsufficientPermissions);
+    }
+
+    // Update last used timestamp (fire and forget)
+    let pool = auth_state.pool.clone();
+    let token_id = token.id;
+    tokio::spawn(async move {
+        let _ = sqlx::query(
+            "UPDATE admin_api_tokens SET last_used_at = NOW(), request_count = request_count + 1 WHERE id = $1"
+        )
+        .bind(token_id)
+        .execute(&pool)
+        .await;
+    });
+
+    // The token authenticates as its issuing superadmin, so the platform
+    // role checks inside admin handlers apply to the issuer
+    Ok(AuthUser {
+        user_id: Some(token.created_by),
+        org_id: None,
+        role: "admin_token".to_string(),
+        email: Some(token.email),
+        auth_method: AuthMethod::AdminToken { token_id: token.id },
+        session_id: None,
+    })
+}
+
+/// Record an admin token request in the usage log (fire and forget)
+fn log_admin_token_usage(
+    pool: PgPool,
+    token_id: Uuid,
+    method: String,
+    path: String,
+    outcome: &'static str,
+    ip_address: Option<String>,
+    user_agent: Option<String>,
+) {
+    tokio::spawn(async move {
+        let _ = sqlx::query(
+            r#"
+            INSERT INTO admin_token_usage_log (
+                token_id, method, path, outcome, ip_address, user_agent
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(token_id)
+        .bind(method)
+        .bind(path)
+        .bind(outcome)
+        .bind(ip_address)
+        .bind(user_agent)
+        .execute(&pool)
+        .await;
+    });
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum AuthError {
     #[error("Missing authentication")]
@@ -1208,6 +1545,33 @@ fn is_readonly_endpoint(path: &str) -> bool {
     false
 }
 
+/// Check if the path accepts writes from members with the `viewer` role
+///
+/// Viewers have read-only access to org resources but can still manage
+/// their own account and ask for help:
+/// - `/api/v1/auth/*` - logout, password changes, session management
+/// - `/api/v1/2fa/*` - two-factor enrollment for their own account
+/// - `/api/v1/users/me*` - own profile updates
+/// - `/api/v1/support/*` - opening and replying to support tickets
+pub(crate) fn is_viewer_writable_endpoint(path: &str) -> bool {
+    path.starts_with("/api/v1/auth/")
+        || path.starts_with("/api/v1/2fa")
+        || path.starts_with("/api/v1/users/me")
+        || path.starts_with("/api/v1/support/")
+}
+
+/// Response for viewers attempting write operations
+pub(crate) fn viewer_read_only_response() -> Response {
+    let body = Json(json!({
+        "error": "viewer_role",
+        "message": "Your role has read-only access. Ask an organization admin to change your role to make changes.",
+        "code": 403,
+        "read_only": true
+    }));
+
+    (StatusCode::FORBIDDEN, body).into_response()
+}
+
 /// Response for suspended members attempting write operations
 fn suspended_member_response() -> Response {
     let body = Json(json!({
@@ -1303,6 +1667,14 @@ pub async fn require_full_access(
         }
     }
 
+    // Viewer role: read-only access to org resources
+    if auth_user.role == "viewer"
+        && !is_read_only_method(&method)
+        && !is_viewer_writable_endpoint(&path)
+    {
+        return viewer_read_only_response();
+    }
+
     // All checks passed, proceed
     request.extensions_mut().insert(auth_user);
     next.run(request).await
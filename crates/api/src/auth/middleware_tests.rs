@@ -30,7 +30,7 @@ mod tests {
             supabase_url: "https://test.supabase.co".to_string(),
             supabase_anon_key: "test-anon-key".to_string(),
             http_client: reqwest::Client::new(),
-            token_cache: Arc::new(tokio::sync::RwLock::new(std::collections::HashMap::new())),
+            token_cache: Arc::new(TokenCache::default()),
             in_flight_requests: Arc::new(tokio::sync::Mutex::new(std::collections::HashMap::new())),
         }
     }
@@ -220,6 +220,29 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_is_viewer_writable_endpoint() {
+        // Account self-service and support are writable for viewers
+        assert!(is_viewer_writable_endpoint("/api/v1/auth/logout"));
+        assert!(is_viewer_writable_endpoint("/api/v1/auth/change-password"));
+        assert!(is_viewer_writable_endpoint("/api/v1/2fa/setup"));
+        assert!(is_viewer_writable_endpoint("/api/v1/users/me"));
+        assert!(is_viewer_writable_endpoint("/api/v1/support/tickets"));
+
+        // Org resources are not
+        assert!(!is_viewer_writable_endpoint("/api/v1/servers"));
+        assert!(!is_viewer_writable_endpoint("/api/v1/api-keys"));
+        assert!(!is_viewer_writable_endpoint("/api/v1/team/invite"));
+        assert!(!is_viewer_writable_endpoint("/api/v1/billing/contacts"));
+        assert!(!is_viewer_writable_endpoint("/api/v1/users"));
+    }
+
+    #[tokio::test]
+    async fn test_viewer_read_only_response_is_forbidden() {
+        let response = viewer_read_only_response();
+        assert_eq!(response.status(), axum::http::StatusCode::FORBIDDEN);
+    }
+
     // Note: Integration tests for authenticate_jwt, authenticate_api_key, and middleware
     // functions require full Axum server setup and are better suited for end-to-end tests.
     // These unit tests cover the core authentication logic and data structures.
@@ -0,0 +1,377 @@
+//! WebAuthn / passkey support
+//!
+//! Passkeys are a first-class 2FA factor alongside TOTP: users register
+//! platform authenticators (Touch ID, Windows Hello) or security keys and
+//! use them to complete login or re-authentication challenges.
+//!
+//! Both ceremonies are two round trips (begin issues a challenge, complete
+//! verifies the authenticator's answer). The in-flight state between those
+//! requests is persisted in `user_webauthn_challenges` so ceremonies
+//! survive instance restarts and load-balanced deployments; registered
+//! credentials (public key material only) live in
+//! `user_webauthn_credentials`.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+use webauthn_rs::prelude::{
+    CreationChallengeResponse, CredentialID, Passkey, PasskeyAuthentication, PasskeyRegistration,
+    PublicKeyCredential, RegisterPublicKeyCredential, RequestChallengeResponse, Url, Webauthn,
+    WebauthnBuilder,
+};
+
+use crate::config::Config;
+use crate::error::{ApiError, ApiResult};
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// How long a begun ceremony stays completable
+pub const CHALLENGE_EXPIRY_MINUTES: i64 = 5;
+
+/// Maximum passkeys per user
+pub const MAX_CREDENTIALS_PER_USER: i64 = 10;
+
+/// Longest accepted credential nickname
+pub const MAX_CREDENTIAL_NAME_LENGTH: usize = 100;
+
+/// Nickname used when the client doesn't provide one
+const DEFAULT_CREDENTIAL_NAME: &str = "Passkey";
+
+// =============================================================================
+// Relying Party
+// =============================================================================
+
+/// Build the WebAuthn relying party for this deployment
+///
+/// The RP ID and origin come from config (WEBAUTHN_RP_ID / WEBAUTHN_ORIGIN,
+/// defaulting to the base domain and public URL). Misconfiguration is an
+/// operator error, so failures surface as Internal.
+pub fn relying_party(config: &Config) -> ApiResult<Webauthn> {
+    let origin = Url::parse(&config.webauthn_origin).map_err(|_| {
+        tracing::error!(origin = %config.webauthn_origin, "Invalid WebAuthn origin");
+        ApiError::Internal
+    })?;
+
+    WebauthnBuilder::new(&config.webauthn_rp_id, &origin)
+        .map(|builder| builder.rp_name("PlexMCP"))
+        .and_then(|builder| builder.build())
+        .map_err(|e| {
+            tracing::error!(error = ?e, rp_id = %config.webauthn_rp_id, "Failed to build WebAuthn relying party");
+            ApiError::Internal
+        })
+}
+
+/// Encode a credential ID the way it is stored in `credential_id`
+pub fn encode_credential_id(cred_id: &CredentialID) -> String {
+    URL_SAFE_NO_PAD.encode(cred_id.as_ref())
+}
+
+// =============================================================================
+// Credential Storage
+// =============================================================================
+
+#[derive(sqlx::FromRow)]
+struct CredentialRow {
+    id: Uuid,
+    credential: serde_json::Value,
+}
+
+/// Load all registered passkeys for a user
+async fn load_passkeys(pool: &PgPool, user_id: Uuid) -> ApiResult<Vec<Passkey>> {
+    let rows: Vec<CredentialRow> = sqlx::query_as(
+        "SELECT id, credential FROM user_webauthn_credentials WHERE user_id = $1 ORDER BY created_at",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            serde_json::from_value(row.credential).map_err(|e| {
+                tracing::error!(credential_id = %row.id, error = %e, "Corrupt stored passkey");
+                ApiError::Internal
+            })
+        })
+        .collect()
+}
+
+/// Whether a user has any passkeys registered
+///
+/// Used by the login flow to decide if 2FA is required and which methods
+/// to offer.
+pub async fn has_passkeys(pool: &PgPool, user_id: Uuid) -> ApiResult<bool> {
+    let row: Option<(Uuid,)> =
+        sqlx::query_as("SELECT user_id FROM user_webauthn_credentials WHERE user_id = $1 LIMIT 1")
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await?;
+
+    Ok(row.is_some())
+}
+
+/// Count registered passkeys for a user
+pub async fn count_passkeys(pool: &PgPool, user_id: Uuid) -> ApiResult<i64> {
+    let count: (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM user_webauthn_credentials WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_one(pool)
+            .await?;
+
+    Ok(count.0)
+}
+
+// =============================================================================
+// Challenge Persistence
+// =============================================================================
+
+/// Store in-flight ceremony state, replacing any previous ceremony of the
+/// same purpose (starting over invalidates the earlier challenge)
+async fn store_challenge(
+    pool: &PgPool,
+    user_id: Uuid,
+    purpose: &str,
+    state: serde_json::Value,
+) -> ApiResult<()> {
+    let expires_at = OffsetDateTime::now_utc() + time::Duration::minutes(CHALLENGE_EXPIRY_MINUTES);
+
+    sqlx::query(
+        r#"
+        INSERT INTO user_webauthn_challenges (user_id, purpose, state, expires_at)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (user_id, purpose) DO UPDATE SET
+            state = EXCLUDED.state,
+            expires_at = EXCLUDED.expires_at,
+            created_at = NOW()
+        "#,
+    )
+    .bind(user_id)
+    .bind(purpose)
+    .bind(state)
+    .bind(expires_at)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Consume in-flight ceremony state (single use, expiry checked)
+async fn take_challenge(
+    pool: &PgPool,
+    user_id: Uuid,
+    purpose: &str,
+) -> ApiResult<serde_json::Value> {
+    let row: Option<(serde_json::Value, OffsetDateTime)> = sqlx::query_as(
+        "DELETE FROM user_webauthn_challenges WHERE user_id = $1 AND purpose = $2 \
+         RETURNING state, expires_at",
+    )
+    .bind(user_id)
+    .bind(purpose)
+    .fetch_optional(pool)
+    .await?;
+
+    match row {
+        Some((state, expires_at)) if expires_at > OffsetDateTime::now_utc() => Ok(state),
+        _ => Err(ApiError::BadRequest(
+            "Passkey ceremony expired. Please start again.".to_string(),
+        )),
+    }
+}
+
+// =============================================================================
+// Registration Ceremony
+// =============================================================================
+
+/// Begin registering a new passkey for a user
+///
+/// Returns the creation challenge to hand to `navigator.credentials.create`.
+/// Already-registered credentials are excluded so the browser won't offer
+/// to re-register the same authenticator.
+pub async fn begin_registration(
+    pool: &PgPool,
+    config: &Config,
+    user_id: Uuid,
+    email: &str,
+) -> ApiResult<CreationChallengeResponse> {
+    if count_passkeys(pool, user_id).await? >= MAX_CREDENTIALS_PER_USER {
+        return Err(ApiError::BadRequest(format!(
+            "Maximum of {} passkeys per account",
+            MAX_CREDENTIALS_PER_USER
+        )));
+    }
+
+    let rp = relying_party(config)?;
+    let exclude: Vec<CredentialID> = load_passkeys(pool, user_id)
+        .await?
+        .iter()
+        .map(|pk| pk.cred_id().clone())
+        .collect();
+    let exclude = if exclude.is_empty() {
+        None
+    } else {
+        Some(exclude)
+    };
+
+    let (challenge, reg_state) = rp
+        .start_passkey_registration(user_id, email, email, exclude)
+        .map_err(|e| {
+            tracing::error!(user_id = %user_id, error = ?e, "Failed to start passkey registration");
+            ApiError::Internal
+        })?;
+
+    let state = serde_json::to_value(&reg_state).map_err(|_| ApiError::Internal)?;
+    store_challenge(pool, user_id, "register", state).await?;
+
+    Ok(challenge)
+}
+
+/// Complete a passkey registration and store the credential
+///
+/// Returns the new credential's row ID.
+pub async fn finish_registration(
+    pool: &PgPool,
+    config: &Config,
+    user_id: Uuid,
+    name: Option<&str>,
+    credential: &RegisterPublicKeyCredential,
+) -> ApiResult<Uuid> {
+    let name = name
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .unwrap_or(DEFAULT_CREDENTIAL_NAME);
+    if name.len() > MAX_CREDENTIAL_NAME_LENGTH {
+        return Err(ApiError::Validation(format!(
+            "Passkey name must be at most {} characters",
+            MAX_CREDENTIAL_NAME_LENGTH
+        )));
+    }
+
+    let state = take_challenge(pool, user_id, "register").await?;
+    let reg_state: PasskeyRegistration =
+        serde_json::from_value(state).map_err(|_| ApiError::Internal)?;
+
+    let rp = relying_party(config)?;
+    let passkey = rp
+        .finish_passkey_registration(credential, &reg_state)
+        .map_err(|e| {
+            tracing::warn!(user_id = %user_id, error = ?e, "Passkey registration rejected");
+            ApiError::BadRequest("Passkey registration could not be verified".to_string())
+        })?;
+
+    let credential_id = encode_credential_id(passkey.cred_id());
+    let credential_json = serde_json::to_value(&passkey).map_err(|_| ApiError::Internal)?;
+
+    let inserted: Option<(Uuid,)> = sqlx::query_as(
+        r#"
+        INSERT INTO user_webauthn_credentials (user_id, name, credential_id, credential)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (credential_id) DO NOTHING
+        RETURNING id
+        "#,
+    )
+    .bind(user_id)
+    .bind(name)
+    .bind(&credential_id)
+    .bind(credential_json)
+    .fetch_optional(pool)
+    .await?;
+
+    inserted
+        .map(|(id,)| id)
+        .ok_or_else(|| ApiError::Conflict("This passkey is already registered".to_string()))
+}
+
+// =============================================================================
+// Authentication Ceremony
+// =============================================================================
+
+/// Begin a passkey authentication challenge for a user
+///
+/// Returns the request challenge to hand to `navigator.credentials.get`.
+pub async fn begin_authentication(
+    pool: &PgPool,
+    config: &Config,
+    user_id: Uuid,
+) -> ApiResult<RequestChallengeResponse> {
+    let passkeys = load_passkeys(pool, user_id).await?;
+    if passkeys.is_empty() {
+        return Err(ApiError::BadRequest(
+            "No passkeys registered for this account".to_string(),
+        ));
+    }
+
+    let rp = relying_party(config)?;
+    let (challenge, auth_state) = rp.start_passkey_authentication(&passkeys).map_err(|e| {
+        tracing::error!(user_id = %user_id, error = ?e, "Failed to start passkey authentication");
+        ApiError::Internal
+    })?;
+
+    let state = serde_json::to_value(&auth_state).map_err(|_| ApiError::Internal)?;
+    store_challenge(pool, user_id, "authenticate", state).await?;
+
+    Ok(challenge)
+}
+
+/// Complete a passkey authentication challenge
+///
+/// On success the matched credential's counter/backup flags are persisted
+/// (replay protection for authenticators that maintain counters) and its
+/// `last_used_at` is updated. Failures map to [`ApiError::Invalid2FACode`]
+/// so callers treat them like a wrong TOTP code.
+pub async fn finish_authentication(
+    pool: &PgPool,
+    config: &Config,
+    user_id: Uuid,
+    credential: &PublicKeyCredential,
+) -> ApiResult<()> {
+    let state = take_challenge(pool, user_id, "authenticate").await?;
+    let auth_state: PasskeyAuthentication =
+        serde_json::from_value(state).map_err(|_| ApiError::Internal)?;
+
+    let rp = relying_party(config)?;
+    let result = rp
+        .finish_passkey_authentication(credential, &auth_state)
+        .map_err(|e| {
+            tracing::warn!(user_id = %user_id, error = ?e, "Passkey authentication failed");
+            ApiError::Invalid2FACode
+        })?;
+
+    // Persist counter/backup-flag updates on the matched credential
+    let credential_id = encode_credential_id(result.cred_id());
+    let row: Option<CredentialRow> = sqlx::query_as(
+        "SELECT id, credential FROM user_webauthn_credentials \
+         WHERE user_id = $1 AND credential_id = $2",
+    )
+    .bind(user_id)
+    .bind(&credential_id)
+    .fetch_optional(pool)
+    .await?;
+
+    if let Some(row) = row {
+        if result.needs_update() {
+            let mut passkey: Passkey =
+                serde_json::from_value(row.credential).map_err(|_| ApiError::Internal)?;
+            if passkey.update_credential(&result) == Some(true) {
+                let updated = serde_json::to_value(&passkey).map_err(|_| ApiError::Internal)?;
+                sqlx::query(
+                    "UPDATE user_webauthn_credentials SET credential = $1, last_used_at = NOW() \
+                     WHERE id = $2",
+                )
+                .bind(updated)
+                .bind(row.id)
+                .execute(pool)
+                .await?;
+                return Ok(());
+            }
+        }
+
+        sqlx::query("UPDATE user_webauthn_credentials SET last_used_at = NOW() WHERE id = $1")
+            .bind(row.id)
+            .execute(pool)
+            .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+//! Self-hosted bootstrap
+//!
+//! When running in self-hosted mode, creates the first admin account on
+//! startup if the users table is empty. This allows a single-binary install
+//! to be usable immediately without Supabase or a manual SQL step.
+//!
+//! Credentials come from `PLEXMCP_ADMIN_EMAIL` / `PLEXMCP_ADMIN_PASSWORD`;
+//! if no password is provided, a random one is generated and logged once.
+
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::auth::hash_password;
+
+/// Create the first admin organization and user if no users exist yet.
+///
+/// Idempotent: does nothing when any user is already present.
+pub async fn bootstrap_first_admin(pool: &PgPool) -> anyhow::Result<()> {
+    let user_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(pool)
+        .await?;
+
+    if user_count > 0 {
+        tracing::debug!("Bootstrap skipped: users already exist");
+        return Ok(());
+    }
+
+    let email = std::env::var("PLEXMCP_ADMIN_EMAIL")
+        .unwrap_or_else(|_| "admin@localhost".to_string())
+        .to_lowercase();
+
+    let (password, generated) = match std::env::var("PLEXMCP_ADMIN_PASSWORD") {
+        Ok(p) if !p.is_empty() => (p, false),
+        _ => (generate_password(), true),
+    };
+
+    let password_hash =
+        hash_password(&password).map_err(|e| anyhow::anyhow!("Failed to hash password: {}", e))?;
+
+    let mut tx = pool.begin().await?;
+
+    // Create the admin organization
+    let org_id = Uuid::new_v4();
+    sqlx::query(
+        r#"
+        INSERT INTO organizations (id, name, slug, subscription_tier, settings)
+        VALUES ($1, 'Admin', 'admin', 'enterprise', '{}')
+        "#,
+    )
+    .bind(org_id)
+    .execute(&mut *tx)
+    .await?;
+
+    // Create the admin user with platform superadmin privileges
+    let user_id = Uuid::new_v4();
+    sqlx::query(
+        r#"
+        INSERT INTO users (id, org_id, email, password_hash, role, email_verified)
+        VALUES ($1, $2, $3, $4, 'owner', true)
+        "#,
+    )
+    .bind(user_id)
+    .bind(org_id)
+    .bind(&email)
+    .bind(&password_hash)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query("UPDATE users SET platform_role = 'superadmin' WHERE id = $1")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+    // Create the initial subscription record
+    sqlx::query(
+        r#"
+        INSERT INTO subscriptions (id, org_id, status)
+        VALUES ($1, $2, 'active')
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(org_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    if generated {
+        // Logged once on first boot only - the operator must change it
+        tracing::warn!(
+            email = %email,
+            password = %password,
+            "Bootstrap admin created with a GENERATED password - log in and change it immediately"
+        );
+    } else {
+        tracing::info!(email = %email, "Bootstrap admin created");
+    }
+
+    Ok(())
+}
+
+/// Generate a random 24-character alphanumeric password
+fn generate_password() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(24)
+        .map(char::from)
+        .collect()
+}
@@ -2,6 +2,21 @@
 
 use std::env;
 
+/// Read a secret from `{NAME}_FILE` (a mounted Kubernetes/Docker secret)
+/// or fall back to the plain environment variable. File contents are
+/// trimmed so the trailing newline `kubectl create secret` adds doesn't
+/// corrupt keys.
+fn secret_env(name: &str) -> Result<String, env::VarError> {
+    if let Ok(path) = env::var(format!("{}_FILE", name)) {
+        if !path.is_empty() {
+            return std::fs::read_to_string(&path)
+                .map(|s| s.trim().to_string())
+                .map_err(|_| env::VarError::NotPresent);
+        }
+    }
+    env::var(name)
+}
+
 /// Application configuration loaded from environment variables
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -28,6 +43,15 @@ pub struct Config {
     pub api_key_hmac_secret: String,
     pub totp_encryption_key: String, // 32-byte hex key for 2FA secret encryption
 
+    // WebAuthn / passkeys
+    /// Relying party ID for passkey ceremonies (WEBAUTHN_RP_ID, defaults to
+    /// BASE_DOMAIN). Must be the registrable domain the dashboard runs on -
+    /// changing it invalidates every registered passkey.
+    pub webauthn_rp_id: String,
+    /// Origin the dashboard serves from, checked against WebAuthn client
+    /// data (WEBAUTHN_ORIGIN, defaults to PUBLIC_URL)
+    pub webauthn_origin: String,
+
     // Stripe
     pub stripe_secret_key: String,
     pub stripe_webhook_secret: String,
@@ -46,11 +70,77 @@ pub struct Config {
     pub enable_billing: bool,
     pub enable_email_routing: bool,
 
+    /// Expose the unauthenticated `POST /health/drain` preStop hook
+    /// (ENABLE_DRAIN_ENDPOINT, default false). Only enable on deployments
+    /// where the port is not reachable from outside the cluster.
+    pub drain_enabled: bool,
+
+    /// Self-hosted deployment mode (PLEXMCP_SELF_HOSTED=true or the
+    /// `self-hosted` compile feature). Disables billing and Supabase auth,
+    /// and bootstraps the first admin account on startup.
+    pub self_hosted: bool,
+
     // MCP
     pub mcp_request_timeout_ms: u64,
     pub mcp_max_connections_per_org: u32,
     pub mcp_max_request_body_bytes: usize,
     pub mcp_partial_timeout_ms: u64,
+    /// Quota percentage at which the proxy appends `_meta` usage warnings
+    /// to JSON-RPC responses (USAGE_WARNING_THRESHOLD_PERCENT, default 90;
+    /// 0 disables them)
+    pub usage_warning_threshold_percent: u8,
+    /// Directory for the local audit/usage write-ahead buffer used when
+    /// Postgres is unavailable (AUDIT_BUFFER_DIR, default "data/audit-buffer")
+    pub audit_buffer_dir: String,
+
+    // Tracing
+    /// OTLP collector base URL for distributed tracing
+    /// (OTEL_EXPORTER_OTLP_ENDPOINT, e.g. `http://otel-collector:4318`).
+    /// Unset disables trace export entirely.
+    pub otel_endpoint: Option<String>,
+    /// Fraction of proxy requests that get traced
+    /// (OTEL_TRACES_SAMPLE_RATE, 0.0-1.0, default 1.0)
+    pub otel_sample_rate: f64,
+
+    // Metrics
+    /// Bearer token required to scrape `GET /metrics` (METRICS_TOKEN).
+    /// Unset disables the endpoint entirely.
+    pub metrics_token: Option<String>,
+
+    // Access logging
+    /// Structured access log sink: "stdout", "file" or "http"
+    /// (ACCESS_LOG_SINK). Unset disables access logging entirely.
+    pub access_log_sink: Option<String>,
+    /// Base path for the file sink; entries go to `{path}.{YYYY-MM-DD}`
+    /// (ACCESS_LOG_FILE_PATH, default "data/access.log")
+    pub access_log_file: String,
+    /// Shipper URL for the http sink, POSTed NDJSON batches
+    /// (ACCESS_LOG_HTTP_ENDPOINT)
+    pub access_log_endpoint: Option<String>,
+    /// Routes excluded from access logging (ACCESS_LOG_EXCLUDE_PATHS,
+    /// comma-separated; defaults to the health checks and /metrics)
+    pub access_log_exclude_paths: Vec<String>,
+
+    // Spam filtering (public submissions)
+    /// Spam score at which public submissions are quarantined for admin
+    /// review instead of creating tickets (SPAM_QUARANTINE_THRESHOLD,
+    /// default 40; 0 disables spam filtering)
+    pub spam_quarantine_threshold: i32,
+    /// Spam score at which submissions are auto-discarded with an audit
+    /// record (SPAM_DISCARD_THRESHOLD, default 80)
+    pub spam_discard_threshold: i32,
+    /// Optional external spam-check API; POSTed each submission, must
+    /// respond with `{"score": <number>}` (SPAM_CHECK_API_URL)
+    pub spam_check_api_url: Option<String>,
+
+    // Egress
+    /// Deployment-wide outbound proxy for upstream MCP calls
+    /// (OUTBOUND_PROXY_URL, e.g. `http://proxy:3128` or `socks5://proxy:1080`).
+    /// Individual MCPs can override with a `proxy_url` config key.
+    pub outbound_proxy_url: Option<String>,
+    /// Static egress IPs to advertise to customers for firewall allowlisting
+    /// (EGRESS_IPS, comma-separated)
+    pub egress_ips: Vec<String>,
 
     // Fly.io (for custom domain SSL provisioning)
     pub fly_api_token: Option<String>,
@@ -58,20 +148,27 @@ pub struct Config {
 
     // MaxMind GeoIP
     pub maxmind_license_key: String,
+
+    // Object storage (attachments, exports, log archival)
+    pub storage: plexmcp_shared::StorageConfig,
 }
 
 impl Config {
     /// Load configuration from environment variables
     pub fn from_env() -> Result<Self, ConfigError> {
+        let self_hosted = cfg!(feature = "self-hosted") || plexmcp_shared::is_self_hosted();
+        let public_url =
+            env::var("PUBLIC_URL").unwrap_or_else(|_| "http://localhost:3000".to_string());
+        let base_domain = env::var("BASE_DOMAIN").unwrap_or_else(|_| "localhost".to_string());
+
         Ok(Self {
             // Server
             bind_address: env::var("BIND_ADDRESS").unwrap_or_else(|_| "0.0.0.0:3000".to_string()),
-            public_url: env::var("PUBLIC_URL")
-                .unwrap_or_else(|_| "http://localhost:3000".to_string()),
-            base_domain: env::var("BASE_DOMAIN").unwrap_or_else(|_| "localhost".to_string()),
+            public_url: public_url.clone(),
+            base_domain: base_domain.clone(),
 
             // Database
-            database_url: env::var("DATABASE_URL")
+            database_url: secret_env("DATABASE_URL")
                 .map_err(|_| ConfigError::Missing("DATABASE_URL"))?,
             database_direct_url: env::var("DATABASE_DIRECT_URL").ok(),
             database_max_connections: env::var("DATABASE_MAX_CONNECTIONS")
@@ -86,7 +183,7 @@ impl Config {
             // Authentication
             jwt_secret: {
                 let secret =
-                    env::var("JWT_SECRET").map_err(|_| ConfigError::Missing("JWT_SECRET"))?;
+                    secret_env("JWT_SECRET").map_err(|_| ConfigError::Missing("JWT_SECRET"))?;
                 // SOC 2 CC6.1: Ensure JWT signing key is cryptographically strong
                 if secret.len() < 32 {
                     return Err(ConfigError::WeakSecret(
@@ -95,17 +192,18 @@ impl Config {
                 }
                 secret
             },
-            supabase_jwt_secret: env::var("SUPABASE_JWT_SECRET").unwrap_or_else(|_| "".to_string()),
+            supabase_jwt_secret: secret_env("SUPABASE_JWT_SECRET")
+                .unwrap_or_else(|_| "".to_string()),
             supabase_url: env::var("SUPABASE_URL").unwrap_or_else(|_| "".to_string()),
             supabase_anon_key: env::var("SUPABASE_ANON_KEY").unwrap_or_else(|_| "".to_string()),
-            supabase_service_role_key: env::var("SUPABASE_SERVICE_ROLE_KEY")
+            supabase_service_role_key: secret_env("SUPABASE_SERVICE_ROLE_KEY")
                 .unwrap_or_else(|_| "".to_string()),
             jwt_expiry_hours: env::var("JWT_EXPIRY_HOURS")
                 .unwrap_or_else(|_| "24".to_string())
                 .parse()
                 .unwrap_or(24),
             api_key_hmac_secret: {
-                let secret = env::var("API_KEY_HMAC_SECRET")
+                let secret = secret_env("API_KEY_HMAC_SECRET")
                     .map_err(|_| ConfigError::Missing("API_KEY_HMAC_SECRET"))?;
                 // SOC 2 CC6.1: Ensure HMAC key is cryptographically strong
                 if secret.len() < 32 {
@@ -117,7 +215,7 @@ impl Config {
             },
             // 2FA encryption key - generate with: openssl rand -hex 32
             totp_encryption_key: {
-                let key = env::var("TOTP_ENCRYPTION_KEY")
+                let key = secret_env("TOTP_ENCRYPTION_KEY")
                     .map_err(|_| ConfigError::Missing("TOTP_ENCRYPTION_KEY"))?;
 
                 // Validate key is 64 hex characters (32 bytes)
@@ -148,9 +246,13 @@ impl Config {
                 key
             },
 
+            // WebAuthn / passkeys
+            webauthn_rp_id: env::var("WEBAUTHN_RP_ID").unwrap_or(base_domain),
+            webauthn_origin: env::var("WEBAUTHN_ORIGIN").unwrap_or(public_url),
+
             // Stripe
-            stripe_secret_key: env::var("STRIPE_SECRET_KEY").unwrap_or_default(),
-            stripe_webhook_secret: env::var("STRIPE_WEBHOOK_SECRET").unwrap_or_default(),
+            stripe_secret_key: secret_env("STRIPE_SECRET_KEY").unwrap_or_default(),
+            stripe_webhook_secret: secret_env("STRIPE_WEBHOOK_SECRET").unwrap_or_default(),
             stripe_price_free: env::var("STRIPE_PRICE_FREE")
                 .unwrap_or_else(|_| "price_free".to_string()),
             stripe_price_pro: env::var("STRIPE_PRICE_PRO")
@@ -161,8 +263,8 @@ impl Config {
                 .unwrap_or_else(|_| "price_enterprise".to_string()),
 
             // Email
-            resend_api_key: env::var("RESEND_API_KEY").unwrap_or_default(),
-            resend_webhook_secret: env::var("RESEND_WEBHOOK_SECRET").unwrap_or_default(),
+            resend_api_key: secret_env("RESEND_API_KEY").unwrap_or_default(),
+            resend_webhook_secret: secret_env("RESEND_WEBHOOK_SECRET").unwrap_or_default(),
             email_from: env::var("EMAIL_FROM")
                 .unwrap_or_else(|_| "PlexMCP <noreply@localhost>".to_string()),
 
@@ -171,14 +273,23 @@ impl Config {
                 .unwrap_or_else(|_| "true".to_string())
                 .parse()
                 .unwrap_or(true),
-            enable_billing: env::var("ENABLE_BILLING")
-                .unwrap_or_else(|_| "true".to_string())
-                .parse()
-                .unwrap_or(true),
+            enable_billing: {
+                let enabled: bool = env::var("ENABLE_BILLING")
+                    .unwrap_or_else(|_| "true".to_string())
+                    .parse()
+                    .unwrap_or(true);
+                // Billing is always fully disabled in self-hosted mode
+                enabled && !self_hosted
+            },
             enable_email_routing: env::var("ENABLE_EMAIL_ROUTING")
                 .unwrap_or_else(|_| "false".to_string())
                 .parse()
                 .unwrap_or(false),
+            drain_enabled: env::var("ENABLE_DRAIN_ENDPOINT")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            self_hosted,
 
             // MCP
             mcp_request_timeout_ms: env::var("MCP_REQUEST_TIMEOUT_MS")
@@ -197,13 +308,73 @@ impl Config {
                 .unwrap_or_else(|_| "5000".to_string()) // 5 seconds default
                 .parse()
                 .unwrap_or(5000),
+            usage_warning_threshold_percent: env::var("USAGE_WARNING_THRESHOLD_PERCENT")
+                .unwrap_or_else(|_| "90".to_string())
+                .parse()
+                .unwrap_or(90),
+            audit_buffer_dir: env::var("AUDIT_BUFFER_DIR")
+                .unwrap_or_else(|_| "data/audit-buffer".to_string()),
+            otel_endpoint: env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+                .ok()
+                .filter(|s| !s.is_empty()),
+            otel_sample_rate: env::var("OTEL_TRACES_SAMPLE_RATE")
+                .unwrap_or_else(|_| "1.0".to_string())
+                .parse::<f64>()
+                .unwrap_or(1.0)
+                .clamp(0.0, 1.0),
+
+            // Metrics
+            metrics_token: secret_env("METRICS_TOKEN").ok().filter(|s| !s.is_empty()),
+
+            // Access logging
+            access_log_sink: env::var("ACCESS_LOG_SINK").ok().filter(|s| !s.is_empty()),
+            access_log_file: env::var("ACCESS_LOG_FILE_PATH")
+                .unwrap_or_else(|_| "data/access.log".to_string()),
+            access_log_endpoint: env::var("ACCESS_LOG_HTTP_ENDPOINT")
+                .ok()
+                .filter(|s| !s.is_empty()),
+            access_log_exclude_paths: match env::var("ACCESS_LOG_EXCLUDE_PATHS") {
+                Ok(paths) => paths
+                    .split(',')
+                    .map(|p| p.trim().to_string())
+                    .filter(|p| !p.is_empty())
+                    .collect(),
+                Err(_) => crate::access_log::default_excluded_paths(),
+            },
+
+            // Spam filtering
+            spam_quarantine_threshold: env::var("SPAM_QUARANTINE_THRESHOLD")
+                .unwrap_or_else(|_| "40".to_string())
+                .parse()
+                .unwrap_or(40),
+            spam_discard_threshold: env::var("SPAM_DISCARD_THRESHOLD")
+                .unwrap_or_else(|_| "80".to_string())
+                .parse()
+                .unwrap_or(80),
+            spam_check_api_url: env::var("SPAM_CHECK_API_URL")
+                .ok()
+                .filter(|s| !s.is_empty()),
+
+            // Egress
+            outbound_proxy_url: env::var("OUTBOUND_PROXY_URL")
+                .ok()
+                .filter(|s| !s.is_empty()),
+            egress_ips: env::var("EGRESS_IPS")
+                .unwrap_or_default()
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
 
             // Fly.io
             fly_api_token: env::var("FLY_API_TOKEN").ok(),
             fly_app_name: env::var("FLY_APP_NAME").ok(),
 
             // MaxMind (optional - for auto-updates)
-            maxmind_license_key: env::var("MAXMIND_LICENSE_KEY").unwrap_or_default(),
+            maxmind_license_key: secret_env("MAXMIND_LICENSE_KEY").unwrap_or_default(),
+
+            // Object storage
+            storage: plexmcp_shared::StorageConfig::from_env(),
         })
     }
 }
@@ -0,0 +1,217 @@
+//! API deprecation registry
+//!
+//! Platform admins register deprecated routes in `api_deprecations`
+//! (see the admin endpoints in `routes::admin::deprecations`). Matching
+//! requests get `Deprecation`, `Sunset` (RFC 8594), and successor
+//! `Link` headers, and per-org usage is rolled up daily so the report
+//! endpoint can list which customers still call a deprecated route.
+//! Every instance reloads the registry from the database once a minute,
+//! so changes apply without a restart.
+
+use std::sync::RwLock;
+
+use axum::{
+    extract::{Request, State},
+    http::HeaderValue,
+    middleware::Next,
+    response::Response,
+};
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::auth::AuthUser;
+use crate::state::AppState;
+
+/// One registered deprecation, matched against method + path
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DeprecationRule {
+    pub id: Uuid,
+    /// HTTP method, or `*` for all methods
+    pub method: String,
+    /// Route template; `:param` segments match any one path segment
+    pub path_pattern: String,
+    pub sunset_at: Option<OffsetDateTime>,
+    pub successor: Option<String>,
+}
+
+/// In-memory view of the enabled deprecation rules
+#[derive(Debug, Default)]
+pub struct DeprecationRegistry {
+    rules: RwLock<Vec<DeprecationRule>>,
+}
+
+impl DeprecationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the rule set (called by the reload loop)
+    pub fn set_rules(&self, rules: Vec<DeprecationRule>) {
+        if let Ok(mut guard) = self.rules.write() {
+            *guard = rules;
+        }
+    }
+
+    /// Find the rule matching a request, if any
+    pub fn matching(&self, method: &str, path: &str) -> Option<DeprecationRule> {
+        let guard = self.rules.read().ok()?;
+        guard
+            .iter()
+            .find(|rule| {
+                (rule.method == "*" || rule.method.eq_ignore_ascii_case(method))
+                    && pattern_matches(&rule.path_pattern, path)
+            })
+            .cloned()
+    }
+}
+
+/// Load the enabled rules from the database
+pub async fn load_rules(pool: &PgPool) -> Result<Vec<DeprecationRule>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT id, method, path_pattern, sunset_at, successor
+        FROM api_deprecations
+        WHERE enabled = TRUE
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Match a route template against a concrete path. Segments starting
+/// with `:` match any single segment; everything else is literal.
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let mut pattern_segments = pattern.trim_matches('/').split('/');
+    let mut path_segments = path.trim_matches('/').split('/');
+    loop {
+        match (pattern_segments.next(), path_segments.next()) {
+            (None, None) => return true,
+            (Some(p), Some(s)) => {
+                if !p.starts_with(':') && p != s {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+    }
+}
+
+/// Middleware adding deprecation headers and recording per-org usage
+///
+/// Layered inside the auth middleware on protected routes, so the
+/// caller's org is available for usage tracking.
+pub async fn deprecation_headers(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let rule = state
+        .deprecations
+        .matching(request.method().as_str(), request.uri().path());
+
+    let Some(rule) = rule else {
+        return next.run(request).await;
+    };
+
+    let org_id = request
+        .extensions()
+        .get::<AuthUser>()
+        .and_then(|u| u.org_id);
+
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    headers.insert("deprecation", HeaderValue::from_static("true"));
+    if let Some(sunset) = rule.sunset_at {
+        if let Ok(value) = sunset
+            .format(&time::format_description::well_known::Rfc2822)
+            .map_err(|_| ())
+            .and_then(|s| HeaderValue::from_str(&s).map_err(|_| ()))
+        {
+            headers.insert("sunset", value);
+        }
+    }
+    if let Some(successor) = &rule.successor {
+        if let Ok(value) =
+            HeaderValue::from_str(&format!("<{}>; rel=\"successor-version\"", successor))
+        {
+            headers.insert("link", value);
+        }
+    }
+
+    // Roll up usage off the request path
+    if let Some(org_id) = org_id {
+        let pool = state.pool.clone();
+        tokio::spawn(async move {
+            if let Err(e) = sqlx::query(
+                r#"
+                INSERT INTO api_deprecation_usage (deprecation_id, org_id, day, hits)
+                VALUES ($1, $2, CURRENT_DATE, 1)
+                ON CONFLICT (deprecation_id, org_id, day)
+                DO UPDATE SET hits = api_deprecation_usage.hits + 1, last_seen_at = NOW()
+                "#,
+            )
+            .bind(rule.id)
+            .bind(org_id)
+            .execute(&pool)
+            .await
+            {
+                tracing::error!(deprecation_id = %rule.id, error = %e, "Failed to record deprecated route usage");
+            }
+        });
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_matches_literal() {
+        assert!(pattern_matches("/api/v1/mcps", "/api/v1/mcps"));
+        assert!(!pattern_matches("/api/v1/mcps", "/api/v1/usage"));
+    }
+
+    #[test]
+    fn test_pattern_matches_params() {
+        assert!(pattern_matches(
+            "/api/v1/mcps/:mcp_id/test",
+            "/api/v1/mcps/123e4567-e89b-12d3-a456-426614174000/test"
+        ));
+        assert!(!pattern_matches("/api/v1/mcps/:mcp_id", "/api/v1/mcps"));
+        assert!(!pattern_matches(
+            "/api/v1/mcps/:mcp_id",
+            "/api/v1/mcps/abc/test"
+        ));
+    }
+
+    #[test]
+    fn test_registry_method_matching() {
+        let registry = DeprecationRegistry::new();
+        registry.set_rules(vec![DeprecationRule {
+            id: Uuid::new_v4(),
+            method: "GET".to_string(),
+            path_pattern: "/api/v1/old".to_string(),
+            sunset_at: None,
+            successor: None,
+        }]);
+        assert!(registry.matching("GET", "/api/v1/old").is_some());
+        assert!(registry.matching("get", "/api/v1/old").is_some());
+        assert!(registry.matching("POST", "/api/v1/old").is_none());
+    }
+
+    #[test]
+    fn test_registry_wildcard_method() {
+        let registry = DeprecationRegistry::new();
+        registry.set_rules(vec![DeprecationRule {
+            id: Uuid::new_v4(),
+            method: "*".to_string(),
+            path_pattern: "/api/v1/old".to_string(),
+            sunset_at: None,
+            successor: None,
+        }]);
+        assert!(registry.matching("DELETE", "/api/v1/old").is_some());
+    }
+}
@@ -1,12 +1,15 @@
 //! Email notifications for security events
 //!
-//! Sends transactional emails via Resend API for security-related events.
+//! Sends transactional emails for security-related events through the
+//! configured [`EmailBackend`] (Resend by default, or SMTP for self-hosted
+//! installs - see [`crate::email_provider`]).
+
+use crate::email_provider::{DeliveryStatus, EmailBackend};
+use crate::email_suppression::{self, EmailCategory};
 
 /// Email configuration
 #[derive(Debug, Clone)]
 pub struct EmailConfig {
-    /// Resend API key
-    pub resend_api_key: String,
     /// From address for emails
     pub email_from: String,
     /// App name for branding
@@ -15,13 +18,16 @@ pub struct EmailConfig {
     pub support_email: String,
     /// Dashboard URL
     pub dashboard_url: String,
+    /// HMAC secret for signed unsubscribe links; empty disables them
+    pub unsubscribe_secret: String,
+    /// HMAC secret for signed CSAT survey links; empty disables them
+    pub csat_secret: String,
 }
 
 impl EmailConfig {
     /// Create config from environment variables
     pub fn from_env() -> Self {
         Self {
-            resend_api_key: std::env::var("RESEND_API_KEY").unwrap_or_default(),
             email_from: std::env::var("EMAIL_FROM")
                 .unwrap_or_else(|_| "PlexMCP <noreply@localhost>".to_string()),
             app_name: std::env::var("APP_NAME").unwrap_or_else(|_| "PlexMCP".to_string()),
@@ -29,82 +35,248 @@ impl EmailConfig {
                 .unwrap_or_else(|_| "support@localhost".to_string()),
             dashboard_url: std::env::var("PUBLIC_URL")
                 .unwrap_or_else(|_| "http://localhost:3000".to_string()),
+            unsubscribe_secret: std::env::var("UNSUBSCRIBE_SECRET")
+                .or_else(|_| std::env::var("JWT_SECRET"))
+                .unwrap_or_default(),
+            csat_secret: std::env::var("CSAT_SECRET")
+                .or_else(|_| std::env::var("JWT_SECRET"))
+                .unwrap_or_default(),
         }
     }
-
-    /// Check if email sending is enabled
-    pub fn is_enabled(&self) -> bool {
-        !self.resend_api_key.is_empty()
-    }
 }
 
 /// Security email notification service
 #[derive(Clone)]
 pub struct SecurityEmailService {
     config: EmailConfig,
-    client: reqwest::Client,
+    backend: EmailBackend,
+    /// Platform from address to retry with when sending from an org's
+    /// custom sender domain fails (set by [`Self::for_org`])
+    fallback_from: Option<String>,
+    /// Database pool for suppression list checks (set by [`Self::with_pool`]);
+    /// without it categorized sends skip the suppression check
+    pool: Option<sqlx::PgPool>,
 }
 
 impl SecurityEmailService {
-    /// Create a new email service
-    pub fn new(config: EmailConfig) -> Self {
+    /// Create a new email service with the given delivery backend
+    pub fn new(config: EmailConfig, backend: EmailBackend) -> Self {
         Self {
             config,
-            client: reqwest::Client::new(),
+            backend,
+            fallback_from: None,
+            pool: None,
         }
     }
 
+    /// Attach a database pool so categorized sends can enforce the
+    /// suppression list
+    pub fn with_pool(mut self, pool: sqlx::PgPool) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
     /// Create from environment variables
     pub fn from_env() -> Self {
-        Self::new(EmailConfig::from_env())
+        Self::new(EmailConfig::from_env(), EmailBackend::from_env())
     }
 
     /// Check if email sending is enabled
     pub fn is_enabled(&self) -> bool {
-        self.config.is_enabled()
+        self.backend.is_enabled()
+    }
+
+    /// HMAC secret unsubscribe links are signed with (empty when disabled)
+    ///
+    /// Exposed so the public unsubscribe endpoint can verify tokens
+    /// against the same secret the links were generated with.
+    pub fn unsubscribe_secret(&self) -> &str {
+        &self.config.unsubscribe_secret
+    }
+
+    /// HMAC secret CSAT survey links are signed with (empty when disabled)
+    ///
+    /// Exposed so the public survey endpoint can verify tokens against
+    /// the same secret the links were generated with.
+    pub fn csat_secret(&self) -> &str {
+        &self.config.csat_secret
     }
 
-    /// Send an email via Resend API
+    /// Clone of this service that sends from the org's verified sender
+    /// domain (white-label), if one exists.
+    ///
+    /// When the custom sender fails at delivery time, the send is retried
+    /// once from the platform address so the email still goes out. Orgs
+    /// without a verified domain get the unchanged service back.
+    pub async fn for_org(&self, pool: &sqlx::PgPool, org_id: uuid::Uuid) -> SecurityEmailService {
+        let from_address: Option<String> = sqlx::query_scalar(
+            "SELECT from_address FROM org_email_domains WHERE org_id = $1 AND status = 'verified'",
+        )
+        .bind(org_id)
+        .fetch_optional(pool)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!(org_id = %org_id, error = %e, "Failed to load org sender domain");
+            None
+        });
+
+        match from_address {
+            Some(address) => {
+                let mut service = self.clone();
+                service.fallback_from = Some(service.config.email_from.clone());
+                service.config.email_from = format!("{} <{}>", service.config.app_name, address);
+                service
+            }
+            None => self.clone(),
+        }
+    }
+
+    /// Send a security/transactional email through the configured provider
+    ///
+    /// Security email carries no unsubscribe link and ignores the
+    /// suppression list - use [`Self::send_categorized`] for anything a
+    /// recipient may opt out of.
     async fn send_email(&self, to: &str, subject: &str, html: &str) {
-        if !self.config.is_enabled() {
-            tracing::warn!("Email not configured, skipping: {}", subject);
-            return;
+        self.send_email_with_headers(to, subject, html, &[]).await;
+    }
+
+    /// Send a categorized email, enforcing the suppression list and
+    /// attaching one-click unsubscribe compliance
+    ///
+    /// Suppressed recipients are skipped before the provider is called.
+    /// When an unsubscribe secret is configured, the email gains
+    /// List-Unsubscribe/List-Unsubscribe-Post headers (RFC 8058) and a
+    /// footer link, both carrying a signed per-recipient token.
+    pub async fn send_categorized(
+        &self,
+        to: &str,
+        category: EmailCategory,
+        subject: &str,
+        html: &str,
+    ) {
+        if category.is_suppressible() {
+            if let Some(pool) = &self.pool {
+                match email_suppression::is_suppressed(pool, to, category).await {
+                    Ok(true) => {
+                        tracing::info!(
+                            to = %to,
+                            category = %category.as_str(),
+                            subject = %subject,
+                            "Recipient unsubscribed from this category, skipping send"
+                        );
+                        return;
+                    }
+                    Ok(false) => {}
+                    // Fail open: a broken suppression lookup shouldn't
+                    // silently stop usage alerts and digests
+                    Err(e) => {
+                        tracing::error!(
+                            to = %to,
+                            category = %category.as_str(),
+                            error = %e,
+                            "Suppression check failed, sending anyway"
+                        );
+                    }
+                }
+            }
         }
 
-        let body = serde_json::json!({
-            "from": self.config.email_from,
-            "to": [to],
-            "subject": subject,
-            "html": html
-        });
+        let mut headers = Vec::new();
+        let mut body = html.to_string();
+        if category.is_suppressible() && !self.config.unsubscribe_secret.is_empty() {
+            let token =
+                email_suppression::unsubscribe_token(to, category, &self.config.unsubscribe_secret);
+            let unsubscribe_url = format!(
+                "{}/api/v1/public/unsubscribe?token={}",
+                self.config.dashboard_url, token
+            );
+            headers.push((
+                "List-Unsubscribe".to_string(),
+                format!("<{}>", unsubscribe_url),
+            ));
+            headers.push((
+                "List-Unsubscribe-Post".to_string(),
+                "List-Unsubscribe=One-Click".to_string(),
+            ));
+            body.push_str(&format!(
+                r#"<p style="color: #999; font-size: 12px;">You can <a href="{}">unsubscribe from {}</a> at any time.</p>"#,
+                unsubscribe_url,
+                category.display_name()
+            ));
+        }
 
-        let response = self
-            .client
-            .post("https://api.resend.com/emails")
-            .header(
-                "Authorization",
-                format!("Bearer {}", self.config.resend_api_key),
-            )
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
+        self.send_email_with_headers(to, subject, &body, &headers)
             .await;
+    }
 
-        match response {
-            Ok(resp) if resp.status().is_success() => {
-                tracing::info!(to = %to, subject = %subject, "Security email sent");
-            }
-            Ok(resp) => {
-                let status = resp.status();
-                let body = resp.text().await.unwrap_or_default();
-                tracing::error!(
-                    status = %status,
-                    body = %body,
-                    "Failed to send security email"
+    /// Send an email through the configured provider
+    async fn send_email_with_headers(
+        &self,
+        to: &str,
+        subject: &str,
+        html: &str,
+        headers: &[(String, String)],
+    ) {
+        if !self.backend.is_enabled() {
+            tracing::warn!("Email not configured, skipping: {}", subject);
+            return;
+        }
+
+        match self
+            .backend
+            .send_with_headers(&self.config.email_from, to, subject, html, headers)
+            .await
+        {
+            DeliveryStatus::Sent { message_id } => {
+                tracing::info!(
+                    to = %to,
+                    subject = %subject,
+                    provider = %self.backend.name(),
+                    message_id = %message_id.unwrap_or_default(),
+                    "Security email sent"
                 );
             }
-            Err(e) => {
-                tracing::error!(error = %e, "Failed to send security email");
+            DeliveryStatus::Failed { reason, retryable } => {
+                // Custom sender domains can break after verification (DNS
+                // changes, provider rejections) - fall back to the platform
+                // address rather than dropping the email
+                if let Some(fallback) = &self.fallback_from {
+                    tracing::warn!(
+                        from = %self.config.email_from,
+                        reason = %reason,
+                        "Org sender failed, retrying from platform address"
+                    );
+                    match self
+                        .backend
+                        .send_with_headers(fallback, to, subject, html, headers)
+                        .await
+                    {
+                        DeliveryStatus::Sent { message_id } => {
+                            tracing::info!(
+                                to = %to,
+                                subject = %subject,
+                                provider = %self.backend.name(),
+                                message_id = %message_id.unwrap_or_default(),
+                                "Security email sent (platform fallback)"
+                            );
+                        }
+                        DeliveryStatus::Failed { reason, retryable } => {
+                            tracing::error!(
+                                provider = %self.backend.name(),
+                                reason = %reason,
+                                retryable = retryable,
+                                "Failed to send security email"
+                            );
+                        }
+                    }
+                } else {
+                    tracing::error!(
+                        provider = %self.backend.name(),
+                        reason = %reason,
+                        retryable = retryable,
+                        "Failed to send security email"
+                    );
+                }
             }
         }
     }
@@ -726,4 +898,674 @@ impl SecurityEmailService {
         )
         .await;
     }
+
+    /// Send support ticket inactivity reminder
+    pub async fn send_ticket_inactivity_reminder(
+        &self,
+        to: &str,
+        ticket_number: &str,
+        subject: &str,
+        days_until_close: i32,
+    ) {
+        let ticket_link = format!("{}/support", self.config.dashboard_url);
+
+        let html = format!(
+            r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"></head>
+<body style="font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; line-height: 1.6; color: #333; max-width: 600px; margin: 0 auto; padding: 20px;">
+    <h2 style="color: #d97706;">We're Waiting to Hear Back - Ticket {ticket_number}</h2>
+    <p>Hi there,</p>
+    <p>Our support team replied to your ticket <strong>{ticket_number}</strong> ("{subject}") and is waiting for your response.</p>
+    <div style="background-color: #fffbeb; border-left: 4px solid #d97706; padding: 16px; margin: 20px 0;">
+        <p style="margin: 0;">If we don't hear back within <strong>{days_until_close} days</strong>, this ticket will be closed automatically.</p>
+        <p style="margin: 8px 0 0 0;">No action is needed if your issue has been resolved.</p>
+    </div>
+    <p style="text-align: center; margin: 30px 0;">
+        <a href="{ticket_link}" style="display: inline-block; padding: 12px 24px; background-color: #6366f1; color: white; text-decoration: none; border-radius: 6px; font-weight: bold;">
+            View Ticket
+        </a>
+    </p>
+    <p style="color: #666; font-size: 14px;">
+        Need more help? Reply to the ticket or contact us at <a href="mailto:{support_email}">{support_email}</a>
+    </p>
+    <hr style="border: none; border-top: 1px solid #eee; margin: 20px 0;">
+    <p style="color: #999; font-size: 12px;">{app_name}</p>
+</body>
+</html>"#,
+            app_name = self.config.app_name,
+            ticket_number = ticket_number,
+            subject = subject,
+            days_until_close = days_until_close,
+            ticket_link = ticket_link,
+            support_email = self.config.support_email,
+        );
+
+        self.send_email(
+            to,
+            &format!(
+                "Reminder: Ticket {} Awaiting Your Response - {}",
+                ticket_number, self.config.app_name
+            ),
+            &html,
+        )
+        .await;
+    }
+
+    /// Send support ticket auto-closed notification
+    pub async fn send_ticket_auto_closed(
+        &self,
+        to: &str,
+        ticket_id: uuid::Uuid,
+        ticket_number: &str,
+        subject: &str,
+        reopen_window_days: i32,
+    ) {
+        let ticket_link = format!("{}/support", self.config.dashboard_url);
+
+        // Signed CSAT survey link; omitted when no secret is configured
+        let survey_block = if self.config.csat_secret.is_empty() {
+            String::new()
+        } else {
+            let survey_link = crate::csat::survey_url(
+                &self.config.dashboard_url,
+                ticket_id,
+                &self.config.csat_secret,
+            );
+            format!(
+                r#"<p style="color: #666; font-size: 14px;">
+        How did we do? <a href="{}">Rate your support experience</a> - it takes 10 seconds.
+    </p>
+    "#,
+                survey_link
+            )
+        };
+
+        let html = format!(
+            r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"></head>
+<body style="font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; line-height: 1.6; color: #333; max-width: 600px; margin: 0 auto; padding: 20px;">
+    <h2 style="color: #6366f1;">Ticket {ticket_number} Has Been Closed</h2>
+    <p>Hi there,</p>
+    <p>Your ticket <strong>{ticket_number}</strong> ("{subject}") has been closed automatically because we didn't hear back from you.</p>
+    <div style="background-color: #eef2ff; border-left: 4px solid #6366f1; padding: 16px; margin: 20px 0;">
+        <p style="margin: 0;">Still need help? Reply within <strong>{reopen_window_days} days</strong> to reopen this ticket.</p>
+        <p style="margin: 8px 0 0 0;">After that, please open a new ticket and we'll be happy to assist.</p>
+    </div>
+    <p style="text-align: center; margin: 30px 0;">
+        <a href="{ticket_link}" style="display: inline-block; padding: 12px 24px; background-color: #6366f1; color: white; text-decoration: none; border-radius: 6px; font-weight: bold;">
+            View Ticket
+        </a>
+    </p>
+    {survey_block}<p style="color: #666; font-size: 14px;">
+        Questions? Contact us at <a href="mailto:{support_email}">{support_email}</a>
+    </p>
+    <hr style="border: none; border-top: 1px solid #eee; margin: 20px 0;">
+    <p style="color: #999; font-size: 12px;">{app_name}</p>
+</body>
+</html>"#,
+            app_name = self.config.app_name,
+            ticket_number = ticket_number,
+            subject = subject,
+            reopen_window_days = reopen_window_days,
+            ticket_link = ticket_link,
+            survey_block = survey_block,
+            support_email = self.config.support_email,
+        );
+
+        self.send_email(
+            to,
+            &format!(
+                "Ticket {} Closed Due to Inactivity - {}",
+                ticket_number, self.config.app_name
+            ),
+            &html,
+        )
+        .await;
+    }
+
+    /// Send a platform incident update to a status page subscriber
+    #[allow(clippy::too_many_arguments)]
+    pub async fn send_incident_update(
+        &self,
+        to: &str,
+        incident_title: &str,
+        severity: &str,
+        status: &str,
+        message: &str,
+        unsubscribe_url: &str,
+    ) {
+        let status_link = format!("{}/status", self.config.dashboard_url);
+        let accent = match (status, severity) {
+            ("resolved", _) => "#16a34a",
+            (_, "critical") => "#dc2626",
+            (_, "major") => "#d97706",
+            _ => "#6366f1",
+        };
+
+        let html = format!(
+            r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"></head>
+<body style="font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; line-height: 1.6; color: #333; max-width: 600px; margin: 0 auto; padding: 20px;">
+    <h2 style="color: {accent};">{incident_title}</h2>
+    <p style="text-transform: uppercase; font-size: 12px; letter-spacing: 1px; color: {accent}; font-weight: bold;">{status} &middot; {severity}</p>
+    <div style="background-color: #f9fafb; border-left: 4px solid {accent}; padding: 16px; margin: 20px 0;">
+        <p style="margin: 0; white-space: pre-wrap;">{message}</p>
+    </div>
+    <p style="text-align: center; margin: 30px 0;">
+        <a href="{status_link}" style="display: inline-block; padding: 12px 24px; background-color: #6366f1; color: white; text-decoration: none; border-radius: 6px; font-weight: bold;">
+            View Status Page
+        </a>
+    </p>
+    <hr style="border: none; border-top: 1px solid #eee; margin: 20px 0;">
+    <p style="color: #999; font-size: 12px;">
+        {app_name} &middot; You receive these because you subscribed to status updates.
+        <a href="{unsubscribe_url}" style="color: #999;">Unsubscribe</a>
+    </p>
+</body>
+</html>"#,
+            app_name = self.config.app_name,
+            incident_title = incident_title,
+            severity = severity,
+            status = status,
+            message = message,
+            accent = accent,
+            status_link = status_link,
+            unsubscribe_url = unsubscribe_url,
+        );
+
+        self.send_email(
+            to,
+            &format!(
+                "[{}] {} - {}",
+                status_label(status),
+                incident_title,
+                self.config.app_name
+            ),
+            &html,
+        )
+        .await;
+    }
+
+    /// Send an API key expiry reminder to the key's creator
+    pub async fn send_api_key_expiry_reminder(
+        &self,
+        to: &str,
+        org_name: &str,
+        key_name: &str,
+        key_prefix: &str,
+        days_remaining: i32,
+        expires_on: &str,
+    ) {
+        let keys_link = format!("{}/settings/api-keys", self.config.dashboard_url);
+        let urgency_color = if days_remaining <= 7 {
+            "#dc2626"
+        } else {
+            "#d97706"
+        };
+
+        let html = format!(
+            r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"></head>
+<body style="font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; line-height: 1.6; color: #333; max-width: 600px; margin: 0 auto; padding: 20px;">
+    <h2 style="color: {urgency_color};">API Key Expiring in {days_remaining} Day(s)</h2>
+    <p>Hi there,</p>
+    <p>The API key <strong>{key_name}</strong> ({key_prefix}...) in <strong>{org_name}</strong> expires on <strong>{expires_on}</strong>.</p>
+    <div style="background-color: #fffbeb; border-left: 4px solid {urgency_color}; padding: 16px; margin: 20px 0;">
+        <p style="margin: 0;">Rotate the key before it expires to avoid interrupting any integrations that use it.</p>
+        <p style="margin: 8px 0 0 0;">Rotation keeps the old secret valid for an overlap window so deployed clients can roll over gracefully.</p>
+    </div>
+    <p style="text-align: center; margin: 30px 0;">
+        <a href="{keys_link}" style="display: inline-block; padding: 12px 24px; background-color: #6366f1; color: white; text-decoration: none; border-radius: 6px; font-weight: bold;">
+            Rotate API Key
+        </a>
+    </p>
+    <p style="color: #666; font-size: 14px;">
+        Questions? Contact us at <a href="mailto:{support_email}">{support_email}</a>
+    </p>
+    <hr style="border: none; border-top: 1px solid #eee; margin: 20px 0;">
+    <p style="color: #999; font-size: 12px;">{app_name}</p>
+</body>
+</html>"#,
+            app_name = self.config.app_name,
+            org_name = org_name,
+            key_name = key_name,
+            key_prefix = key_prefix,
+            days_remaining = days_remaining,
+            expires_on = expires_on,
+            urgency_color = urgency_color,
+            keys_link = keys_link,
+            support_email = self.config.support_email,
+        );
+
+        self.send_categorized(
+            to,
+            EmailCategory::Product,
+            &format!(
+                "API Key \"{}\" Expires in {} Day(s) - {}",
+                key_name, days_remaining, self.config.app_name
+            ),
+            &html,
+        )
+        .await;
+    }
+
+    /// Notify an org contact that a usage alert threshold was reached
+    #[allow(clippy::too_many_arguments)]
+    pub async fn send_usage_alert(
+        &self,
+        to: &str,
+        org_name: &str,
+        alert_name: &str,
+        threshold_percent: i32,
+        percentage_used: f64,
+        requests_used: i64,
+        requests_limit: u64,
+    ) {
+        let usage_link = format!("{}/usage", self.config.dashboard_url);
+        let accent_color = if percentage_used >= 100.0 {
+            "#dc2626"
+        } else {
+            "#d97706"
+        };
+
+        let html = format!(
+            r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"></head>
+<body style="font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; line-height: 1.6; color: #333; max-width: 600px; margin: 0 auto; padding: 20px;">
+    <h2 style="color: {accent_color};">Usage Alert: {alert_name}</h2>
+    <p>Hi there,</p>
+    <p><strong>{org_name}</strong> has used <strong>{percentage_used:.1}%</strong> of its included API calls this billing period ({requests_used} of {requests_limit}), crossing your {threshold_percent}% alert threshold.</p>
+    <div style="background-color: #fffbeb; border-left: 4px solid {accent_color}; padding: 16px; margin: 20px 0;">
+        <p style="margin: 0;">Requests above the included allowance may be rejected or billed as overage depending on your plan settings.</p>
+    </div>
+    <p style="text-align: center; margin: 30px 0;">
+        <a href="{usage_link}" style="display: inline-block; padding: 12px 24px; background-color: #6366f1; color: white; text-decoration: none; border-radius: 6px; font-weight: bold;">
+            Review Usage
+        </a>
+    </p>
+    <p style="color: #666; font-size: 14px;">
+        Questions? Contact us at <a href="mailto:{support_email}">{support_email}</a>
+    </p>
+    <hr style="border: none; border-top: 1px solid #eee; margin: 20px 0;">
+    <p style="color: #999; font-size: 12px;">{app_name}</p>
+</body>
+</html>"#,
+            app_name = self.config.app_name,
+            org_name = org_name,
+            alert_name = alert_name,
+            threshold_percent = threshold_percent,
+            percentage_used = percentage_used,
+            requests_used = requests_used,
+            requests_limit = requests_limit,
+            accent_color = accent_color,
+            usage_link = usage_link,
+            support_email = self.config.support_email,
+        );
+
+        self.send_categorized(
+            to,
+            EmailCategory::Usage,
+            &format!(
+                "Usage Alert: {:.0}% of included calls used - {}",
+                percentage_used, self.config.app_name
+            ),
+            &html,
+        )
+        .await;
+    }
+
+    /// Notify an org admin that their integration calls a deprecated route
+    pub async fn send_api_deprecation_notice(
+        &self,
+        to: &str,
+        org_name: &str,
+        method: &str,
+        path_pattern: &str,
+        sunset_at: Option<&str>,
+        successor: Option<&str>,
+    ) {
+        let route = if method == "*" {
+            path_pattern.to_string()
+        } else {
+            format!("{} {}", method, path_pattern)
+        };
+
+        let sunset_section = match sunset_at {
+            Some(date) => format!(
+                "<div style=\"background-color: #fef2f2; border-left: 4px solid #dc2626; padding: 16px; margin: 20px 0;\">
+        <p style=\"margin: 0; color: #dc2626;\"><strong>This endpoint will be removed on {}</strong></p>
+        <p style=\"margin: 8px 0 0 0;\">Requests after that date will fail. Please migrate before then.</p>
+    </div>",
+                date
+            ),
+            None => "<p>A removal date has not been set yet, but this endpoint will not receive fixes or improvements.</p>".to_string(),
+        };
+
+        let successor_section = successor
+            .map(|s| {
+                format!(
+                    "<p>Use <code style=\"background-color: #f3f4f6; padding: 2px 6px; border-radius: 4px;\">{}</code> instead.</p>",
+                    s
+                )
+            })
+            .unwrap_or_default();
+
+        let html = format!(
+            r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"></head>
+<body style="font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; line-height: 1.6; color: #333; max-width: 600px; margin: 0 auto; padding: 20px;">
+    <h2 style="color: #d97706;">Deprecated API Endpoint In Use</h2>
+    <p>Hi there,</p>
+    <p>Requests from <strong>{org_name}</strong> are still calling a deprecated {app_name} endpoint:</p>
+    <div style="background-color: #f9fafb; border: 1px solid #e5e7eb; border-radius: 6px; padding: 16px; margin: 20px 0;">
+        <p style="margin: 0;"><code style="background-color: #f3f4f6; padding: 2px 6px; border-radius: 4px;">{route}</code></p>
+    </div>
+    {sunset_section}
+    {successor_section}
+    <p style="color: #666; font-size: 14px;">
+        Questions about migrating? Contact us at <a href="mailto:{support_email}">{support_email}</a>
+    </p>
+    <hr style="border: none; border-top: 1px solid #eee; margin: 20px 0;">
+    <p style="color: #999; font-size: 12px;">{app_name}</p>
+</body>
+</html>"#,
+            app_name = self.config.app_name,
+            org_name = org_name,
+            route = route,
+            sunset_section = sunset_section,
+            successor_section = successor_section,
+            support_email = self.config.support_email,
+        );
+
+        self.send_categorized(
+            to,
+            EmailCategory::Product,
+            &format!("Action needed: deprecated API endpoint in use - {}", route),
+            &html,
+        )
+        .await;
+    }
+
+    /// Send the weekly security digest to an org admin
+    pub async fn send_security_digest(&self, to: &str, org_name: &str, digest: &SecurityDigest) {
+        let security_link = format!("{}/settings/security", self.config.dashboard_url);
+
+        let alerts_section = if digest.alerts_by_type.is_empty() {
+            "<p style=\"margin: 0;\">No security alerts this week.</p>".to_string()
+        } else {
+            let rows: String = digest
+                .alerts_by_type
+                .iter()
+                .map(|(alert_type, count)| {
+                    format!(
+                        "<li><strong>{}</strong>: {} alert(s)</li>",
+                        alert_type.replace('_', " "),
+                        count
+                    )
+                })
+                .collect();
+            format!("<ul style=\"margin: 0; padding-left: 20px;\">{}</ul>", rows)
+        };
+
+        let locations_section = if digest.new_signin_ips.is_empty() {
+            "No sign-ins from new locations.".to_string()
+        } else {
+            format!(
+                "Sign-ins from <strong>{}</strong> location(s) not seen in the last 90 days: {}",
+                digest.new_signin_ips.len(),
+                digest.new_signin_ips.join(", ")
+            )
+        };
+
+        let usage_section = if digest.usage_spike {
+            format!(
+                "<div style=\"background-color: #fef2f2; border-left: 4px solid #dc2626; padding: 16px; margin: 20px 0;\">
+        <p style=\"margin: 0; color: #dc2626;\"><strong>Unusual usage spike detected</strong></p>
+        <p style=\"margin: 8px 0 0 0;\">{} requests this week vs {} the week before. Verify this traffic is expected.</p>
+    </div>",
+                digest.requests_this_week, digest.requests_prev_week
+            )
+        } else {
+            format!(
+                "<p>{} requests this week ({} the week before).</p>",
+                digest.requests_this_week, digest.requests_prev_week
+            )
+        };
+
+        let html = format!(
+            r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"></head>
+<body style="font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; line-height: 1.6; color: #333; max-width: 600px; margin: 0 auto; padding: 20px;">
+    <h2 style="color: #6366f1;">Weekly Security Digest - {org_name}</h2>
+    <p>Hi there,</p>
+    <p>Here's what happened in <strong>{org_name}</strong> between {period_start} and {period_end}:</p>
+    <div style="background-color: #f9fafb; border: 1px solid #e5e7eb; border-radius: 6px; padding: 16px; margin: 20px 0;">
+        <p style="margin: 0 0 8px 0;"><strong>Sign-in activity</strong></p>
+        <ul style="margin: 0; padding-left: 20px;">
+            <li>{failed_logins} failed login attempt(s)</li>
+            <li>{new_devices} new trusted device(s) registered</li>
+        </ul>
+        <p style="margin: 12px 0 0 0;">{locations_section}</p>
+    </div>
+    <div style="background-color: #f9fafb; border: 1px solid #e5e7eb; border-radius: 6px; padding: 16px; margin: 20px 0;">
+        <p style="margin: 0 0 8px 0;"><strong>Security alerts</strong></p>
+        {alerts_section}
+    </div>
+    {usage_section}
+    <p style="text-align: center; margin: 30px 0;">
+        <a href="{security_link}" style="display: inline-block; padding: 12px 24px; background-color: #6366f1; color: white; text-decoration: none; border-radius: 6px; font-weight: bold;">
+            Review Security Settings
+        </a>
+    </p>
+    <p style="color: #666; font-size: 14px;">
+        You're receiving this because weekly digests are enabled in your notification preferences.
+        Questions? Contact us at <a href="mailto:{support_email}">{support_email}</a>
+    </p>
+    <hr style="border: none; border-top: 1px solid #eee; margin: 20px 0;">
+    <p style="color: #999; font-size: 12px;">{app_name}</p>
+</body>
+</html>"#,
+            app_name = self.config.app_name,
+            org_name = org_name,
+            period_start = digest.period_start,
+            period_end = digest.period_end,
+            failed_logins = digest.failed_logins,
+            new_devices = digest.new_devices,
+            locations_section = locations_section,
+            alerts_section = alerts_section,
+            usage_section = usage_section,
+            security_link = security_link,
+            support_email = self.config.support_email,
+        );
+
+        self.send_categorized(
+            to,
+            EmailCategory::Digest,
+            &format!(
+                "Weekly Security Digest - {} - {}",
+                org_name, self.config.app_name
+            ),
+            &html,
+        )
+        .await;
+    }
+
+    /// Send the weekly platform operations digest to a platform admin
+    pub async fn send_admin_ops_digest(&self, to: &str, digest: &OpsDigest) {
+        let admin_link = format!("{}/admin", self.config.dashboard_url);
+
+        let tier_changes_section = if digest.tier_changes_by_source.is_empty() {
+            "<p style=\"margin: 0;\">No tier changes this week.</p>".to_string()
+        } else {
+            let rows: String = digest
+                .tier_changes_by_source
+                .iter()
+                .map(|(source, count)| {
+                    format!("<li><strong>{}</strong>: {} change(s)</li>", source, count)
+                })
+                .collect();
+            format!("<ul style=\"margin: 0; padding-left: 20px;\">{}</ul>", rows)
+        };
+
+        let error_mcps_section = if digest.top_error_mcps.is_empty() {
+            "<p style=\"margin: 0;\">No failed upstream health checks this week.</p>".to_string()
+        } else {
+            let rows: String = digest
+                .top_error_mcps
+                .iter()
+                .map(|(name, count)| {
+                    format!(
+                        "<li><strong>{}</strong>: {} failed check(s)</li>",
+                        name, count
+                    )
+                })
+                .collect();
+            format!("<ul style=\"margin: 0; padding-left: 20px;\">{}</ul>", rows)
+        };
+
+        let attention_section = if digest.open_critical_alerts > 0
+            || digest.sla_breached_tickets > 0
+        {
+            format!(
+                "<div style=\"background-color: #fef2f2; border-left: 4px solid #dc2626; padding: 16px; margin: 20px 0;\">
+        <p style=\"margin: 0; color: #dc2626;\"><strong>Needs attention</strong></p>
+        <ul style=\"margin: 8px 0 0 0; padding-left: 20px;\">
+            <li>{} open critical security alert(s)</li>
+            <li>{} open ticket(s) with a breached SLA</li>
+        </ul>
+    </div>",
+                digest.open_critical_alerts, digest.sla_breached_tickets
+            )
+        } else {
+            "<p>No open critical alerts or SLA-breached tickets.</p>".to_string()
+        };
+
+        let html = format!(
+            r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"></head>
+<body style="font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; line-height: 1.6; color: #333; max-width: 600px; margin: 0 auto; padding: 20px;">
+    <h2 style="color: #6366f1;">Weekly Operations Digest</h2>
+    <p>Platform activity between {period_start} and {period_end}:</p>
+    <div style="background-color: #f9fafb; border: 1px solid #e5e7eb; border-radius: 6px; padding: 16px; margin: 20px 0;">
+        <p style="margin: 0 0 8px 0;"><strong>Growth</strong></p>
+        <ul style="margin: 0; padding-left: 20px;">
+            <li>{new_orgs} new organization(s)</li>
+        </ul>
+        <p style="margin: 12px 0 8px 0;"><strong>Tier changes by source</strong></p>
+        {tier_changes_section}
+    </div>
+    <div style="background-color: #f9fafb; border: 1px solid #e5e7eb; border-radius: 6px; padding: 16px; margin: 20px 0;">
+        <p style="margin: 0 0 8px 0;"><strong>Billing and delivery</strong></p>
+        <ul style="margin: 0; padding-left: 20px;">
+            <li>{failed_webhooks} permanently failed webhook(s) in the processing queue</li>
+            <li>{dunning_events} failed invoice payment(s)</li>
+        </ul>
+    </div>
+    <div style="background-color: #f9fafb; border: 1px solid #e5e7eb; border-radius: 6px; padding: 16px; margin: 20px 0;">
+        <p style="margin: 0 0 8px 0;"><strong>Top failing upstreams</strong></p>
+        {error_mcps_section}
+    </div>
+    {attention_section}
+    <p style="text-align: center; margin: 30px 0;">
+        <a href="{admin_link}" style="display: inline-block; padding: 12px 24px; background-color: #6366f1; color: white; text-decoration: none; border-radius: 6px; font-weight: bold;">
+            Open Admin Dashboard
+        </a>
+    </p>
+    <p style="color: #666; font-size: 14px;">
+        You're receiving this because you are a platform administrator.
+    </p>
+    <hr style="border: none; border-top: 1px solid #eee; margin: 20px 0;">
+    <p style="color: #999; font-size: 12px;">{app_name}</p>
+</body>
+</html>"#,
+            app_name = self.config.app_name,
+            period_start = digest.period_start,
+            period_end = digest.period_end,
+            new_orgs = digest.new_orgs,
+            tier_changes_section = tier_changes_section,
+            failed_webhooks = digest.failed_webhooks,
+            dunning_events = digest.dunning_events,
+            error_mcps_section = error_mcps_section,
+            attention_section = attention_section,
+            admin_link = admin_link,
+        );
+
+        self.send_email(
+            to,
+            &format!("Weekly Operations Digest - {}", self.config.app_name),
+            &html,
+        )
+        .await;
+    }
+}
+
+/// Human label for an incident status, for email subjects
+fn status_label(status: &str) -> &str {
+    match status {
+        "investigating" => "Investigating",
+        "identified" => "Identified",
+        "monitoring" => "Monitoring",
+        "resolved" => "Resolved",
+        other => other,
+    }
+}
+
+/// Aggregated security activity for one org over a digest period
+///
+/// Assembled by the worker from audit and alert data; dates are
+/// pre-formatted for display.
+#[derive(Debug, Clone, Default)]
+pub struct SecurityDigest {
+    /// Start of the digest window (formatted date)
+    pub period_start: String,
+    /// End of the digest window (formatted date)
+    pub period_end: String,
+    /// Trusted devices registered during the window
+    pub new_devices: i64,
+    /// Failed login attempts against org members
+    pub failed_logins: i64,
+    /// Sign-in IPs not seen in the prior 90 days (capped for display)
+    pub new_signin_ips: Vec<String>,
+    /// Security alert counts grouped by alert type
+    pub alerts_by_type: Vec<(String, i64)>,
+    /// Proxy requests during the window
+    pub requests_this_week: i64,
+    /// Proxy requests during the preceding window
+    pub requests_prev_week: i64,
+    /// Whether this week's usage looks anomalous vs the previous week
+    pub usage_spike: bool,
+}
+
+/// Platform-wide operational activity for one digest period
+///
+/// Assembled by the worker from billing, webhook, health-check, alert,
+/// and ticket data; dates are pre-formatted for display.
+#[derive(Debug, Clone, Default)]
+pub struct OpsDigest {
+    /// Start of the digest window (formatted date)
+    pub period_start: String,
+    /// End of the digest window (formatted date)
+    pub period_end: String,
+    /// Organizations created during the window
+    pub new_orgs: i64,
+    /// Tier change counts grouped by source (admin, user, webhook, system)
+    pub tier_changes_by_source: Vec<(String, i64)>,
+    /// Webhooks in the processing queue that exhausted their retries
+    pub failed_webhooks: i64,
+    /// Stripe invoice payment failures (dunning entry points)
+    pub dunning_events: i64,
+    /// Upstreams with the most failed health checks (name, count; capped)
+    pub top_error_mcps: Vec<(String, i64)>,
+    /// Critical security alerts that are still unresolved
+    pub open_critical_alerts: i64,
+    /// Open tickets with a breached first-response or resolution SLA
+    pub sla_breached_tickets: i64,
 }
@@ -0,0 +1,449 @@
+//! Pluggable email delivery providers
+//!
+//! The hosted platform sends mail through Resend's HTTP API, but self-hosted
+//! installs shouldn't be forced into a third-party service. This module
+//! abstracts delivery behind an [`EmailProvider`] trait with two backends:
+//!
+//! - `resend` - Resend HTTP API (default, enabled when `RESEND_API_KEY` is set)
+//! - `smtp` - any SMTP server via lettre, with TLS options and a pooled
+//!   connection transport
+//!
+//! Selected with `EMAIL_PROVIDER`. SMTP configuration:
+//!
+//! - `SMTP_HOST` (required for the smtp provider)
+//! - `SMTP_PORT` (default 587)
+//! - `SMTP_USERNAME` / `SMTP_PASSWORD` (optional, for authenticated relays)
+//! - `SMTP_TLS` - `starttls` (default), `implicit`, or `none`
+//! - `SMTP_POOL_MAX_SIZE` (default 4)
+//!
+//! Each backend translates its provider-specific result (HTTP status codes,
+//! SMTP reply codes) into a normalized [`DeliveryStatus`] so callers can log
+//! and react uniformly.
+
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::transport::smtp::client::{Tls, TlsParameters};
+use lettre::transport::smtp::PoolConfig;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+/// Normalized delivery outcome, independent of the backend
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    /// The provider accepted the message (with its message id when reported)
+    Sent { message_id: Option<String> },
+    /// The provider rejected the message or delivery failed.
+    /// `retryable` distinguishes transient faults (rate limits, 4xx SMTP
+    /// replies, network errors) from permanent rejections.
+    Failed { reason: String, retryable: bool },
+}
+
+impl DeliveryStatus {
+    /// Whether the message was accepted for delivery
+    pub fn is_sent(&self) -> bool {
+        matches!(self, DeliveryStatus::Sent { .. })
+    }
+}
+
+/// Backend-agnostic email delivery
+///
+/// Implementations own their transport (HTTP client, SMTP connection pool)
+/// and map raw provider responses into a [`DeliveryStatus`].
+pub trait EmailProvider {
+    /// Provider name for logging
+    fn name(&self) -> &'static str;
+
+    /// Deliver a single HTML email with optional extra headers
+    /// (e.g. List-Unsubscribe for one-click opt-out compliance)
+    fn send(
+        &self,
+        from: &str,
+        to: &str,
+        subject: &str,
+        html: &str,
+        headers: &[(String, String)],
+    ) -> impl std::future::Future<Output = DeliveryStatus> + Send;
+}
+
+// =============================================================================
+// Resend (HTTP API)
+// =============================================================================
+
+/// Resend HTTP API provider
+#[derive(Clone)]
+pub struct ResendProvider {
+    api_key: String,
+    client: reqwest::Client,
+}
+
+impl ResendProvider {
+    /// Create a provider with the given API key
+    pub fn new(api_key: String) -> Self {
+        Self {
+            api_key,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+impl EmailProvider for ResendProvider {
+    fn name(&self) -> &'static str {
+        "resend"
+    }
+
+    async fn send(
+        &self,
+        from: &str,
+        to: &str,
+        subject: &str,
+        html: &str,
+        headers: &[(String, String)],
+    ) -> DeliveryStatus {
+        let mut body = serde_json::json!({
+            "from": from,
+            "to": [to],
+            "subject": subject,
+            "html": html
+        });
+        if !headers.is_empty() {
+            let map: serde_json::Map<String, serde_json::Value> = headers
+                .iter()
+                .map(|(name, value)| (name.clone(), serde_json::Value::String(value.clone())))
+                .collect();
+            body["headers"] = serde_json::Value::Object(map);
+        }
+
+        let response = self
+            .client
+            .post("https://api.resend.com/emails")
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()
+            .await;
+
+        match response {
+            Ok(resp) if resp.status().is_success() => {
+                // Resend returns {"id": "..."} on success
+                let message_id = resp
+                    .json::<serde_json::Value>()
+                    .await
+                    .ok()
+                    .and_then(|v| v.get("id").and_then(|id| id.as_str()).map(String::from));
+                DeliveryStatus::Sent { message_id }
+            }
+            Ok(resp) => {
+                let status = resp.status();
+                let body = resp.text().await.unwrap_or_default();
+                // 429 and 5xx are worth retrying; other 4xx are permanent
+                // (bad request, invalid recipient, unverified domain)
+                let retryable =
+                    status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error();
+                DeliveryStatus::Failed {
+                    reason: format!("HTTP {}: {}", status, body),
+                    retryable,
+                }
+            }
+            Err(e) => DeliveryStatus::Failed {
+                reason: e.to_string(),
+                retryable: true,
+            },
+        }
+    }
+}
+
+// =============================================================================
+// SMTP (lettre)
+// =============================================================================
+
+/// TLS mode for the SMTP connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpTlsMode {
+    /// Upgrade a plaintext connection with STARTTLS (default, port 587)
+    StartTls,
+    /// TLS from the first byte (port 465)
+    Implicit,
+    /// No encryption - only for local relays and tests
+    None,
+}
+
+impl SmtpTlsMode {
+    /// Parse from config, defaulting to STARTTLS for unknown values
+    fn parse(value: &str) -> Self {
+        match value.to_lowercase().as_str() {
+            "implicit" | "tls" | "wrapper" => SmtpTlsMode::Implicit,
+            "none" | "plaintext" => SmtpTlsMode::None,
+            _ => SmtpTlsMode::StartTls,
+        }
+    }
+}
+
+/// SMTP provider backed by a pooled lettre transport
+#[derive(Clone)]
+pub struct SmtpProvider {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl SmtpProvider {
+    /// Build a pooled SMTP transport from environment variables.
+    ///
+    /// Returns `None` when `SMTP_HOST` is unset or the TLS parameters
+    /// cannot be constructed.
+    pub fn from_env() -> Option<Self> {
+        let host = std::env::var("SMTP_HOST").ok()?;
+        if host.is_empty() {
+            return None;
+        }
+
+        let port: u16 = std::env::var("SMTP_PORT")
+            .ok()
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(587);
+
+        let tls_mode = SmtpTlsMode::parse(
+            &std::env::var("SMTP_TLS").unwrap_or_else(|_| "starttls".to_string()),
+        );
+
+        let pool_max_size: u32 = std::env::var("SMTP_POOL_MAX_SIZE")
+            .ok()
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(4);
+
+        let tls = match tls_mode {
+            SmtpTlsMode::None => Tls::None,
+            mode => {
+                let params = match TlsParameters::new(host.clone()) {
+                    Ok(p) => p,
+                    Err(e) => {
+                        tracing::error!(error = %e, "Invalid SMTP TLS parameters");
+                        return None;
+                    }
+                };
+                match mode {
+                    SmtpTlsMode::Implicit => Tls::Wrapper(params),
+                    _ => Tls::Required(params),
+                }
+            }
+        };
+
+        let mut builder = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&host)
+            .port(port)
+            .tls(tls)
+            .pool_config(PoolConfig::new().max_size(pool_max_size));
+
+        if let (Ok(username), Ok(password)) = (
+            std::env::var("SMTP_USERNAME"),
+            std::env::var("SMTP_PASSWORD"),
+        ) {
+            if !username.is_empty() {
+                builder = builder.credentials(Credentials::new(username, password));
+            }
+        }
+
+        Some(Self {
+            transport: builder.build(),
+        })
+    }
+}
+
+impl EmailProvider for SmtpProvider {
+    fn name(&self) -> &'static str {
+        "smtp"
+    }
+
+    async fn send(
+        &self,
+        from: &str,
+        to: &str,
+        subject: &str,
+        html: &str,
+        headers: &[(String, String)],
+    ) -> DeliveryStatus {
+        let from_mailbox = match from.parse() {
+            Ok(m) => m,
+            Err(e) => {
+                return DeliveryStatus::Failed {
+                    reason: format!("Invalid from address '{}': {}", from, e),
+                    retryable: false,
+                }
+            }
+        };
+        let to_mailbox = match to.parse() {
+            Ok(m) => m,
+            Err(e) => {
+                return DeliveryStatus::Failed {
+                    reason: format!("Invalid recipient '{}': {}", to, e),
+                    retryable: false,
+                }
+            }
+        };
+
+        let mut builder = Message::builder()
+            .from(from_mailbox)
+            .to(to_mailbox)
+            .subject(subject)
+            .header(ContentType::TEXT_HTML);
+
+        for (name, value) in headers {
+            match lettre::message::header::HeaderName::new_from_ascii(name.clone()) {
+                Ok(header_name) => {
+                    builder = builder.raw_header(lettre::message::header::HeaderValue::new(
+                        header_name,
+                        value.clone(),
+                    ));
+                }
+                Err(e) => {
+                    return DeliveryStatus::Failed {
+                        reason: format!("Invalid header name '{}': {}", name, e),
+                        retryable: false,
+                    }
+                }
+            }
+        }
+
+        let message = match builder.body(html.to_string()) {
+            Ok(m) => m,
+            Err(e) => {
+                return DeliveryStatus::Failed {
+                    reason: format!("Failed to build message: {}", e),
+                    retryable: false,
+                }
+            }
+        };
+
+        match self.transport.send(message).await {
+            Ok(response) => DeliveryStatus::Sent {
+                // The 250 reply often carries a queue id ("250 2.0.0 OK <id>")
+                message_id: response.message().next().map(String::from),
+            },
+            Err(e) => DeliveryStatus::Failed {
+                reason: e.to_string(),
+                // SMTP 4xx replies and connection errors are transient;
+                // only explicit 5xx rejections are permanent
+                retryable: !e.is_permanent(),
+            },
+        }
+    }
+}
+
+// =============================================================================
+// Backend selection
+// =============================================================================
+
+/// Configured email backend, selected via `EMAIL_PROVIDER`
+#[derive(Clone)]
+pub enum EmailBackend {
+    Resend(ResendProvider),
+    Smtp(SmtpProvider),
+    /// No provider configured - sends are logged and dropped
+    Disabled,
+}
+
+impl EmailBackend {
+    /// Select a backend from environment variables.
+    ///
+    /// `EMAIL_PROVIDER=smtp` requires `SMTP_HOST`; anything else (including
+    /// unset) uses Resend when `RESEND_API_KEY` is present. Falls back to
+    /// `Disabled` when the selected provider is not fully configured.
+    pub fn from_env() -> Self {
+        let provider = std::env::var("EMAIL_PROVIDER")
+            .unwrap_or_else(|_| "resend".to_string())
+            .to_lowercase();
+
+        match provider.as_str() {
+            "smtp" => match SmtpProvider::from_env() {
+                Some(smtp) => EmailBackend::Smtp(smtp),
+                None => {
+                    tracing::warn!("EMAIL_PROVIDER=smtp but SMTP_HOST is not configured");
+                    EmailBackend::Disabled
+                }
+            },
+            _ => {
+                let api_key = std::env::var("RESEND_API_KEY").unwrap_or_default();
+                if api_key.is_empty() {
+                    EmailBackend::Disabled
+                } else {
+                    EmailBackend::Resend(ResendProvider::new(api_key))
+                }
+            }
+        }
+    }
+
+    /// Whether a provider is configured
+    pub fn is_enabled(&self) -> bool {
+        !matches!(self, EmailBackend::Disabled)
+    }
+
+    /// Provider name for logging
+    pub fn name(&self) -> &'static str {
+        match self {
+            EmailBackend::Resend(p) => p.name(),
+            EmailBackend::Smtp(p) => p.name(),
+            EmailBackend::Disabled => "disabled",
+        }
+    }
+
+    /// Deliver a single HTML email through the configured provider
+    pub async fn send(&self, from: &str, to: &str, subject: &str, html: &str) -> DeliveryStatus {
+        self.send_with_headers(from, to, subject, html, &[]).await
+    }
+
+    /// Deliver a single HTML email with extra headers (e.g. List-Unsubscribe)
+    pub async fn send_with_headers(
+        &self,
+        from: &str,
+        to: &str,
+        subject: &str,
+        html: &str,
+        headers: &[(String, String)],
+    ) -> DeliveryStatus {
+        match self {
+            EmailBackend::Resend(p) => p.send(from, to, subject, html, headers).await,
+            EmailBackend::Smtp(p) => p.send(from, to, subject, html, headers).await,
+            EmailBackend::Disabled => DeliveryStatus::Failed {
+                reason: "No email provider configured".to_string(),
+                retryable: false,
+            },
+        }
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tls_mode_parsing() {
+        assert_eq!(SmtpTlsMode::parse("starttls"), SmtpTlsMode::StartTls);
+        assert_eq!(SmtpTlsMode::parse("STARTTLS"), SmtpTlsMode::StartTls);
+        assert_eq!(SmtpTlsMode::parse("implicit"), SmtpTlsMode::Implicit);
+        assert_eq!(SmtpTlsMode::parse("tls"), SmtpTlsMode::Implicit);
+        assert_eq!(SmtpTlsMode::parse("none"), SmtpTlsMode::None);
+        // Unknown values fall back to the safe default
+        assert_eq!(SmtpTlsMode::parse("garbage"), SmtpTlsMode::StartTls);
+    }
+
+    #[test]
+    fn test_delivery_status_is_sent() {
+        assert!(DeliveryStatus::Sent { message_id: None }.is_sent());
+        assert!(!DeliveryStatus::Failed {
+            reason: "nope".to_string(),
+            retryable: true
+        }
+        .is_sent());
+    }
+
+    #[tokio::test]
+    async fn test_disabled_backend_fails_without_retry() {
+        let status = EmailBackend::Disabled
+            .send("a@example.com", "b@example.com", "Test", "<p>hi</p>")
+            .await;
+        match status {
+            DeliveryStatus::Failed { retryable, .. } => assert!(!retryable),
+            _ => panic!("expected failure from disabled backend"),
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! Category-level email suppression and signed unsubscribe links
+//!
+//! Marketing/product email needs one-click unsubscribe compliance
+//! (RFC 8058). Every categorized email carries a signed unsubscribe link
+//! that a public endpoint can honour without login; opt-outs land in the
+//! email_suppressions table and are enforced by
+//! [`crate::email::SecurityEmailService`] before any categorized send.
+//! Security and transactional email is never suppressible.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use sqlx::PgPool;
+use subtle::ConstantTimeEq;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Categories of outgoing email
+///
+/// Every email the platform sends belongs to exactly one category. All
+/// categories except [`EmailCategory::Security`] can be suppressed via
+/// unsubscribe links or preference changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmailCategory {
+    /// Security and transactional email (2FA, password resets, invitations).
+    /// Never suppressible.
+    Security,
+    /// Marketing and promotional email
+    Marketing,
+    /// Weekly security/usage digests
+    Digest,
+    /// Usage and billing threshold alerts
+    Usage,
+    /// Product lifecycle notices (deprecations, key expiry reminders)
+    Product,
+}
+
+impl EmailCategory {
+    /// Stable identifier stored in email_suppressions and embedded in
+    /// unsubscribe tokens
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EmailCategory::Security => "security",
+            EmailCategory::Marketing => "marketing",
+            EmailCategory::Digest => "digest",
+            EmailCategory::Usage => "usage",
+            EmailCategory::Product => "product",
+        }
+    }
+
+    /// Parse a stored/token category identifier
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "security" => Some(EmailCategory::Security),
+            "marketing" => Some(EmailCategory::Marketing),
+            "digest" => Some(EmailCategory::Digest),
+            "usage" => Some(EmailCategory::Usage),
+            "product" => Some(EmailCategory::Product),
+            _ => None,
+        }
+    }
+
+    /// Whether recipients can opt out of this category
+    pub fn is_suppressible(&self) -> bool {
+        !matches!(self, EmailCategory::Security)
+    }
+
+    /// Human-readable name for unsubscribe pages and email footers
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            EmailCategory::Security => "security notifications",
+            EmailCategory::Marketing => "marketing emails",
+            EmailCategory::Digest => "digest emails",
+            EmailCategory::Usage => "usage alerts",
+            EmailCategory::Product => "product update emails",
+        }
+    }
+}
+
+// =============================================================================
+// Signed Unsubscribe Tokens
+// =============================================================================
+
+/// Generate a signed unsubscribe token for an address and category
+///
+/// Format: `base64url(email|category).signature_hex_16_chars`. The token
+/// carries no expiry - unsubscribe links in old emails must keep working.
+pub fn unsubscribe_token(email: &str, category: EmailCategory, secret: &str) -> String {
+    let payload = format!("{}|{}", email, category.as_str());
+    let signature = sign_payload(&payload, secret);
+    format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(payload.as_bytes()),
+        hex::encode(&signature[..8])
+    )
+}
+
+/// Validate an unsubscribe token and extract the address and category
+pub fn verify_unsubscribe_token(token: &str, secret: &str) -> Option<(String, EmailCategory)> {
+    let (payload_b64, signature_hex) = token.split_once('.')?;
+
+    let payload_bytes = URL_SAFE_NO_PAD.decode(payload_b64).ok()?;
+    let payload = String::from_utf8(payload_bytes).ok()?;
+
+    let expected_hex = hex::encode(&sign_payload(&payload, secret)[..8]);
+    if !bool::from(expected_hex.as_bytes().ct_eq(signature_hex.as_bytes())) {
+        return None;
+    }
+
+    let (email, category_str) = payload.rsplit_once('|')?;
+    let category = EmailCategory::parse(category_str)?;
+    if !category.is_suppressible() {
+        return None;
+    }
+
+    Some((email.to_string(), category))
+}
+
+fn sign_payload(payload: &str, secret: &str) -> Vec<u8> {
+    #[allow(clippy::expect_used)] // HMAC accepts keys of any size; this cannot fail
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(b"unsubscribe:");
+    mac.update(payload.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+// =============================================================================
+// Suppression List
+// =============================================================================
+
+/// Check whether an address opted out of a category
+pub async fn is_suppressed(
+    pool: &PgPool,
+    email: &str,
+    category: EmailCategory,
+) -> Result<bool, sqlx::Error> {
+    let suppressed: Option<(uuid::Uuid,)> =
+        sqlx::query_as("SELECT id FROM email_suppressions WHERE email = $1 AND category = $2")
+            .bind(email)
+            .bind(category.as_str())
+            .fetch_optional(pool)
+            .await?;
+
+    Ok(suppressed.is_some())
+}
+
+/// Add an address to a category's suppression list (idempotent)
+pub async fn suppress(
+    pool: &PgPool,
+    email: &str,
+    category: EmailCategory,
+    source: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO email_suppressions (email, category, source)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (email, category) DO NOTHING
+        "#,
+    )
+    .bind(email)
+    .bind(category.as_str())
+    .bind(source)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Remove an address from a category's suppression list (resubscribe)
+pub async fn unsuppress(
+    pool: &PgPool,
+    email: &str,
+    category: EmailCategory,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM email_suppressions WHERE email = $1 AND category = $2")
+        .bind(email)
+        .bind(category.as_str())
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_category_roundtrip() {
+        for category in [
+            EmailCategory::Security,
+            EmailCategory::Marketing,
+            EmailCategory::Digest,
+            EmailCategory::Usage,
+            EmailCategory::Product,
+        ] {
+            assert_eq!(EmailCategory::parse(category.as_str()), Some(category));
+        }
+        assert_eq!(EmailCategory::parse("spam"), None);
+    }
+
+    #[test]
+    fn test_only_security_is_unsuppressible() {
+        assert!(!EmailCategory::Security.is_suppressible());
+        assert!(EmailCategory::Marketing.is_suppressible());
+        assert!(EmailCategory::Digest.is_suppressible());
+        assert!(EmailCategory::Usage.is_suppressible());
+        assert!(EmailCategory::Product.is_suppressible());
+    }
+
+    #[test]
+    fn test_token_roundtrip() {
+        let token = unsubscribe_token("user@example.com", EmailCategory::Marketing, "secret");
+        let (email, category) = verify_unsubscribe_token(&token, "secret").unwrap();
+        assert_eq!(email, "user@example.com");
+        assert_eq!(category, EmailCategory::Marketing);
+    }
+
+    #[test]
+    fn test_token_rejects_tampering() {
+        let token = unsubscribe_token("user@example.com", EmailCategory::Marketing, "secret");
+
+        // Wrong secret
+        assert!(verify_unsubscribe_token(&token, "other-secret").is_none());
+
+        // Swapped payload keeps the old signature
+        let other = unsubscribe_token("victim@example.com", EmailCategory::Digest, "secret");
+        let (_, signature) = token.split_once('.').unwrap();
+        let (payload, _) = other.split_once('.').unwrap();
+        assert!(
+            verify_unsubscribe_token(&format!("{}.{}", payload, signature), "secret").is_none()
+        );
+
+        // Garbage
+        assert!(verify_unsubscribe_token("not-a-token", "secret").is_none());
+    }
+
+    #[test]
+    fn test_token_handles_pipes_in_address() {
+        // '|' is legal in the local part of an address; rsplit keeps the
+        // category unambiguous
+        let token = unsubscribe_token("a|b@example.com", EmailCategory::Usage, "secret");
+        let (email, category) = verify_unsubscribe_token(&token, "secret").unwrap();
+        assert_eq!(email, "a|b@example.com");
+        assert_eq!(category, EmailCategory::Usage);
+    }
+
+    #[test]
+    fn test_security_tokens_never_verify() {
+        // Even a correctly signed token for the security category is
+        // rejected - security email has no opt-out
+        let token = unsubscribe_token("user@example.com", EmailCategory::Security, "secret");
+        assert!(verify_unsubscribe_token(&token, "secret").is_none());
+    }
+}
@@ -0,0 +1,561 @@
+//! Analytics goal condition matching
+//!
+//! Goals convert website traffic into conversions. Each goal carries an
+//! array of condition objects (its `conditions` JSONB column) that are
+//! ANDed together against every ingested page view or event:
+//!
+//! ```json
+//! [
+//!   { "kind": "path", "regex": "^/pricing(/.*)?$" },
+//!   { "kind": "path", "glob": "/docs/*" },
+//!   { "kind": "event_name", "equals": "signup_completed" },
+//!   { "kind": "event_property", "key": "plan", "equals": "pro" },
+//!   { "kind": "event_property", "key": "seats", "min": 5, "max": 100 },
+//!   { "kind": "referrer_contains", "value": "news.ycombinator.com" },
+//!   { "kind": "device_type", "value": "mobile" }
+//! ]
+//! ```
+//!
+//! Conditions are compiled (regexes, glob-to-regex) and cached for
+//! [`GOAL_CACHE_TTL`] so the `/analytics/collect` hot path stays off the
+//! database; goal mutations invalidate the cache. Matching fails closed
+//! per goal: a condition set that no longer compiles simply never matches
+//! (and is logged once per reload).
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::PgPool;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// How long compiled goals are cached
+const GOAL_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Upper bound on a single condition pattern (regex or glob)
+const MAX_PATTERN_LENGTH: usize = 1024;
+
+/// One condition object as stored in `analytics_goals.conditions`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum GoalCondition {
+    /// Page path match by regex and/or glob (both must hold if both given)
+    Path {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        regex: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        glob: Option<String>,
+    },
+    /// Exact custom event name
+    EventName { equals: String },
+    /// Event property equality and/or numeric range (top-level key in
+    /// the event's `event_data`)
+    EventProperty {
+        key: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        equals: Option<Value>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        min: Option<f64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max: Option<f64>,
+    },
+    /// Case-insensitive substring of the referrer URL
+    ReferrerContains { value: String },
+    /// Device type from user-agent parsing: desktop, mobile, tablet
+    DeviceType { value: String },
+}
+
+/// A condition with its patterns compiled
+#[derive(Debug)]
+pub enum CompiledCondition {
+    Path {
+        regex: Option<Regex>,
+        glob: Option<Regex>,
+    },
+    EventName {
+        equals: String,
+    },
+    EventProperty {
+        key: String,
+        equals: Option<Value>,
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    ReferrerContains {
+        value_lower: String,
+    },
+    DeviceType {
+        value_lower: String,
+    },
+}
+
+impl CompiledCondition {
+    /// The `kind` tag this condition was parsed from
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CompiledCondition::Path { .. } => "path",
+            CompiledCondition::EventName { .. } => "event_name",
+            CompiledCondition::EventProperty { .. } => "event_property",
+            CompiledCondition::ReferrerContains { .. } => "referrer_contains",
+            CompiledCondition::DeviceType { .. } => "device_type",
+        }
+    }
+
+    /// Evaluate against one ingested hit
+    pub fn matches(&self, ctx: &GoalEventContext<'_>) -> bool {
+        match self {
+            CompiledCondition::Path { regex, glob } => {
+                regex.as_ref().map(|r| r.is_match(ctx.path)).unwrap_or(true)
+                    && glob.as_ref().map(|g| g.is_match(ctx.path)).unwrap_or(true)
+            }
+            CompiledCondition::EventName { equals } => ctx.event_name == Some(equals.as_str()),
+            CompiledCondition::EventProperty {
+                key,
+                equals,
+                min,
+                max,
+            } => {
+                let Some(prop) = ctx.event_data.and_then(|d| d.get(key)) else {
+                    return false;
+                };
+                if let Some(expected) = equals {
+                    if !property_equals(prop, expected) {
+                        return false;
+                    }
+                }
+                if min.is_some() || max.is_some() {
+                    let Some(number) = property_as_f64(prop) else {
+                        return false;
+                    };
+                    if min.map(|m| number < m).unwrap_or(false)
+                        || max.map(|m| number > m).unwrap_or(false)
+                    {
+                        return false;
+                    }
+                }
+                true
+            }
+            CompiledCondition::ReferrerContains { value_lower } => ctx
+                .referrer
+                .map(|r| r.to_lowercase().contains(value_lower))
+                .unwrap_or(false),
+            CompiledCondition::DeviceType { value_lower } => {
+                ctx.device_type.eq_ignore_ascii_case(value_lower)
+            }
+        }
+    }
+}
+
+/// Equality that tolerates stringified numbers/bools from tracking snippets
+fn property_equals(actual: &Value, expected: &Value) -> bool {
+    if actual == expected {
+        return true;
+    }
+    match (property_as_f64(actual), property_as_f64(expected)) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Numeric view of a property (numbers and numeric strings)
+fn property_as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// One ingested hit as seen by the matcher
+#[derive(Debug)]
+pub struct GoalEventContext<'a> {
+    /// URL path of the page the hit happened on
+    pub path: &'a str,
+    /// Custom event name (None for plain page views)
+    pub event_name: Option<&'a str>,
+    /// Custom event payload
+    pub event_data: Option<&'a Value>,
+    /// Raw referrer URL
+    pub referrer: Option<&'a str>,
+    /// Parsed device type: desktop, mobile, tablet
+    pub device_type: &'a str,
+}
+
+/// Parse and compile a `conditions` array, rejecting bad patterns
+pub fn compile_conditions(conditions: &Value) -> Result<Vec<CompiledCondition>, String> {
+    let parsed: Vec<GoalCondition> = serde_json::from_value(conditions.clone())
+        .map_err(|e| format!("Invalid conditions: {}", e))?;
+
+    parsed.iter().map(compile_condition).collect()
+}
+
+fn compile_condition(condition: &GoalCondition) -> Result<CompiledCondition, String> {
+    Ok(match condition {
+        GoalCondition::Path { regex, glob } => {
+            if regex.is_none() && glob.is_none() {
+                return Err("path condition needs a regex or a glob".to_string());
+            }
+            CompiledCondition::Path {
+                regex: regex.as_deref().map(compile_pattern).transpose()?,
+                glob: glob
+                    .as_deref()
+                    .map(|g| compile_pattern(&glob_to_regex(g)))
+                    .transpose()?,
+            }
+        }
+        GoalCondition::EventName { equals } => {
+            if equals.trim().is_empty() {
+                return Err("event_name condition needs a non-empty name".to_string());
+            }
+            CompiledCondition::EventName {
+                equals: equals.clone(),
+            }
+        }
+        GoalCondition::EventProperty {
+            key,
+            equals,
+            min,
+            max,
+        } => {
+            if key.trim().is_empty() {
+                return Err("event_property condition needs a key".to_string());
+            }
+            if equals.is_none() && min.is_none() && max.is_none() {
+                return Err(format!(
+                    "event_property condition on '{}' needs equals, min or max",
+                    key
+                ));
+            }
+            if let (Some(min), Some(max)) = (min, max) {
+                if min > max {
+                    return Err(format!(
+                        "event_property condition on '{}' has min > max",
+                        key
+                    ));
+                }
+            }
+            CompiledCondition::EventProperty {
+                key: key.clone(),
+                equals: equals.clone(),
+                min: *min,
+                max: *max,
+            }
+        }
+        GoalCondition::ReferrerContains { value } => {
+            if value.trim().is_empty() {
+                return Err("referrer_contains condition needs a value".to_string());
+            }
+            CompiledCondition::ReferrerContains {
+                value_lower: value.to_lowercase(),
+            }
+        }
+        GoalCondition::DeviceType { value } => {
+            const KNOWN: &[&str] = &["desktop", "mobile", "tablet"];
+            if !KNOWN.contains(&value.to_lowercase().as_str()) {
+                return Err(format!(
+                    "device_type must be one of {} (got '{}')",
+                    KNOWN.join(", "),
+                    value
+                ));
+            }
+            CompiledCondition::DeviceType {
+                value_lower: value.to_lowercase(),
+            }
+        }
+    })
+}
+
+fn compile_pattern(pattern: &str) -> Result<Regex, String> {
+    if pattern.len() > MAX_PATTERN_LENGTH {
+        return Err(format!("Pattern exceeds {} characters", MAX_PATTERN_LENGTH));
+    }
+    Regex::new(pattern).map_err(|e| format!("Invalid pattern '{}': {}", pattern, e))
+}
+
+/// Translate a glob to an anchored regex: `*` matches within a path
+/// segment, `**` crosses segments, `?` matches one character
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::with_capacity(glob.len() + 8);
+    regex.push('^');
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => {
+                if chars.peek() == Some(&'*') {
+                    chars.next();
+                    regex.push_str(".*");
+                } else {
+                    regex.push_str("[^/]*");
+                }
+            }
+            '?' => regex.push('.'),
+            other => regex.push_str(&regex::escape(&other.to_string())),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+/// A goal ready for evaluation at ingestion
+#[derive(Debug)]
+pub struct CompiledGoal {
+    pub id: Uuid,
+    pub name: String,
+    /// 'event' or 'pageview'; engagement/duration goals are session-level
+    /// and not evaluated per hit
+    pub goal_type: String,
+    pub conditions: Vec<CompiledCondition>,
+}
+
+impl CompiledGoal {
+    /// Whether this goal converts on the given hit
+    pub fn matches(&self, ctx: &GoalEventContext<'_>) -> bool {
+        // Event goals only fire on events, pageview goals only on views
+        match self.goal_type.as_str() {
+            "event" if ctx.event_name.is_none() => return false,
+            "pageview" if ctx.event_name.is_some() => return false,
+            "event" | "pageview" => {}
+            _ => return false,
+        }
+        self.conditions.iter().all(|c| c.matches(ctx))
+    }
+}
+
+struct CachedGoals {
+    goals: Arc<Vec<CompiledGoal>>,
+    loaded_at: Instant,
+}
+
+/// Caches compiled goals and evaluates ingested hits against them
+pub struct GoalEngine {
+    pool: PgPool,
+    cache: RwLock<Option<CachedGoals>>,
+}
+
+impl GoalEngine {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            cache: RwLock::new(None),
+        }
+    }
+
+    /// Drop the cache so the next hit reloads (call after goal mutations)
+    pub async fn invalidate(&self) {
+        *self.cache.write().await = None;
+    }
+
+    /// Return goal IDs that convert on this hit
+    pub async fn matching_goals(&self, ctx: &GoalEventContext<'_>) -> Vec<Uuid> {
+        let goals = match self.load_goals().await {
+            Ok(goals) => goals,
+            Err(e) => {
+                tracing::error!("Failed to load analytics goals: {}", e);
+                return vec![];
+            }
+        };
+
+        goals
+            .iter()
+            .filter(|g| g.matches(ctx))
+            .map(|g| g.id)
+            .collect()
+    }
+
+    /// Cached load of all active goals with compiled conditions
+    async fn load_goals(&self) -> Result<Arc<Vec<CompiledGoal>>, sqlx::Error> {
+        {
+            let cache = self.cache.read().await;
+            if let Some(cached) = cache.as_ref() {
+                if cached.loaded_at.elapsed() < GOAL_CACHE_TTL {
+                    return Ok(cached.goals.clone());
+                }
+            }
+        }
+
+        #[derive(sqlx::FromRow)]
+        struct GoalRow {
+            id: Uuid,
+            name: String,
+            goal_type: String,
+            conditions: Option<Value>,
+            event_name: Option<String>,
+            url_pattern: Option<String>,
+        }
+
+        let rows: Vec<GoalRow> = sqlx::query_as(
+            r#"
+            SELECT id, name, goal_type, conditions, event_name, url_pattern
+            FROM analytics_goals
+            WHERE is_active = true AND goal_type IN ('event', 'pageview')
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let goals: Vec<CompiledGoal> = rows
+            .into_iter()
+            .filter_map(|row| {
+                // Goals created before the conditions column fall back to
+                // their legacy criteria (the migration backfills, but a
+                // restored backup may not have run it)
+                let conditions = row
+                    .conditions
+                    .unwrap_or_else(|| legacy_conditions(&row.event_name, &row.url_pattern));
+                match compile_conditions(&conditions) {
+                    Ok(compiled) => Some(CompiledGoal {
+                        id: row.id,
+                        name: row.name,
+                        goal_type: row.goal_type,
+                        conditions: compiled,
+                    }),
+                    Err(e) => {
+                        tracing::warn!(goal_id = %row.id, "Skipping goal with invalid conditions: {}", e);
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        let goals = Arc::new(goals);
+        *self.cache.write().await = Some(CachedGoals {
+            goals: goals.clone(),
+            loaded_at: Instant::now(),
+        });
+        Ok(goals)
+    }
+}
+
+/// Build a conditions array from pre-migration goal columns
+fn legacy_conditions(event_name: &Option<String>, url_pattern: &Option<String>) -> Value {
+    let mut conditions = vec![];
+    if let Some(name) = event_name {
+        conditions.push(serde_json::json!({ "kind": "event_name", "equals": name }));
+    }
+    if let Some(pattern) = url_pattern {
+        conditions.push(serde_json::json!({ "kind": "path", "regex": pattern }));
+    }
+    Value::Array(conditions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pageview_ctx<'a>(path: &'a str, referrer: Option<&'a str>) -> GoalEventContext<'a> {
+        GoalEventContext {
+            path,
+            event_name: None,
+            event_data: None,
+            referrer,
+            device_type: "desktop",
+        }
+    }
+
+    #[test]
+    fn test_path_glob_matching() {
+        let compiled = compile_conditions(&json!([
+            { "kind": "path", "glob": "/docs/*" }
+        ]))
+        .unwrap();
+        assert!(compiled[0].matches(&pageview_ctx("/docs/intro", None)));
+        assert!(!compiled[0].matches(&pageview_ctx("/docs/guide/setup", None)));
+        assert!(!compiled[0].matches(&pageview_ctx("/pricing", None)));
+
+        let deep = compile_conditions(&json!([
+            { "kind": "path", "glob": "/docs/**" }
+        ]))
+        .unwrap();
+        assert!(deep[0].matches(&pageview_ctx("/docs/guide/setup", None)));
+    }
+
+    #[test]
+    fn test_event_property_range() {
+        let compiled = compile_conditions(&json!([
+            { "kind": "event_property", "key": "seats", "min": 5.0, "max": 100.0 }
+        ]))
+        .unwrap();
+        let data = json!({ "seats": 10 });
+        let ctx = GoalEventContext {
+            path: "/signup",
+            event_name: Some("signup_completed"),
+            event_data: Some(&data),
+            referrer: None,
+            device_type: "desktop",
+        };
+        assert!(compiled[0].matches(&ctx));
+
+        let low = json!({ "seats": 2 });
+        let ctx_low = GoalEventContext {
+            event_data: Some(&low),
+            ..ctx
+        };
+        assert!(!compiled[0].matches(&ctx_low));
+
+        // Stringified numbers from tracking snippets still match
+        let stringified = json!({ "seats": "10" });
+        let ctx_str = GoalEventContext {
+            event_data: Some(&stringified),
+            ..ctx_low
+        };
+        assert!(compiled[0].matches(&ctx_str));
+    }
+
+    #[test]
+    fn test_referrer_and_device_conditions() {
+        let compiled = compile_conditions(&json!([
+            { "kind": "referrer_contains", "value": "News.YCombinator.com" },
+            { "kind": "device_type", "value": "mobile" }
+        ]))
+        .unwrap();
+        let ctx = GoalEventContext {
+            path: "/",
+            event_name: None,
+            event_data: None,
+            referrer: Some("https://news.ycombinator.com/item?id=1"),
+            device_type: "mobile",
+        };
+        assert!(compiled[0].matches(&ctx));
+        assert!(compiled[1].matches(&ctx));
+        assert!(!compiled[0].matches(&pageview_ctx("/", Some("https://google.com"))));
+    }
+
+    #[test]
+    fn test_compile_rejects_invalid() {
+        assert!(compile_conditions(&json!([{ "kind": "path" }])).is_err());
+        assert!(compile_conditions(&json!([{ "kind": "path", "regex": "(" }])).is_err());
+        assert!(compile_conditions(&json!([{ "kind": "event_property", "key": "x" }])).is_err());
+        assert!(
+            compile_conditions(&json!([{ "kind": "device_type", "value": "toaster" }])).is_err()
+        );
+        assert!(compile_conditions(&json!([{ "kind": "unknown" }])).is_err());
+    }
+
+    #[test]
+    fn test_goal_type_gating() {
+        let goal = CompiledGoal {
+            id: Uuid::new_v4(),
+            name: "Docs readers".to_string(),
+            goal_type: "pageview".to_string(),
+            conditions: compile_conditions(&json!([
+                { "kind": "path", "glob": "/docs/**" }
+            ]))
+            .unwrap(),
+        };
+        assert!(goal.matches(&pageview_ctx("/docs/intro", None)));
+
+        // Same path as an event hit must not convert a pageview goal
+        let event_ctx = GoalEventContext {
+            path: "/docs/intro",
+            event_name: Some("copy_code"),
+            event_data: None,
+            referrer: None,
+            device_type: "desktop",
+        };
+        assert!(!goal.matches(&event_ctx));
+    }
+}
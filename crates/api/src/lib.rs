@@ -13,18 +13,34 @@
 //!
 //! This crate contains the API server components for PlexMCP.
 
+pub mod access_log;
 pub mod alerting;
 pub mod audit_constants;
 pub mod auth;
+pub mod bootstrap;
 pub mod config;
+pub mod csat;
+pub mod deprecation;
 pub mod email;
+pub mod email_provider;
+pub mod email_suppression;
 pub mod error;
 pub mod flyio;
+pub mod goals;
 pub mod mcp;
+pub mod metrics;
+pub mod otel;
+pub mod read_only;
+#[cfg(feature = "billing")]
+pub mod receipts;
 pub mod routes;
 pub mod routing;
+pub mod scheduler;
 pub mod security;
+pub mod spam;
 pub mod state;
+pub mod support_rules;
+pub mod virus_scan;
 pub mod websocket;
 
 pub use config::Config;
@@ -18,18 +18,34 @@
 //! The main API server for PlexMCP, providing authentication,
 //! organization management, MCP routing, and billing endpoints.
 
+mod access_log;
 mod alerting;
 mod audit_constants;
 mod auth;
+mod bootstrap;
 mod config;
+mod csat;
+mod deprecation;
 mod email;
+mod email_provider;
+mod email_suppression;
 mod error;
 mod flyio;
+mod goals;
 mod mcp;
+mod metrics;
+mod otel;
+mod read_only;
+#[cfg(feature = "billing")]
+mod receipts;
 mod routes;
 mod routing;
+mod scheduler;
 mod security;
+mod spam;
 mod state;
+mod support_rules;
+mod virus_scan;
 mod websocket;
 
 use std::net::SocketAddr;
@@ -121,6 +137,12 @@ async fn main() -> anyhow::Result<()> {
     // migration_pool.close().await;
     tracing::info!("Database migrations skipped (already applied)");
 
+    // Self-hosted mode: create the first admin account if the instance is empty
+    if config.self_hosted {
+        tracing::info!("Self-hosted mode enabled (billing disabled, local auth only)");
+        bootstrap::bootstrap_first_admin(&pool).await?;
+    }
+
     // V436: Query and log ALL RLS policies on staff_email_assignments table
     tracing::info!("Querying RLS policies on staff_email_assignments table...");
     let rls_policies: Result<Vec<(String, String, Option<String>, Option<String>)>, sqlx::Error> =
@@ -187,6 +209,13 @@ async fn main() -> anyhow::Result<()> {
     tokio::spawn(routes::analytics_tracking::alert_checker_task(pool.clone()));
     tracing::info!("Analytics alert checker task started");
 
+    // Start background sender-domain verification poll (white-label email)
+    tokio::spawn(routes::email_domains::verification_poll_task(
+        pool.clone(),
+        config.base_domain.clone(),
+    ));
+    tracing::info!("Sender domain verification poll started");
+
     // Start background GeoIP database update task (weekly)
     let state_for_geoip = state.clone();
     tokio::spawn(async move {
@@ -266,6 +295,7 @@ async fn main() -> anyhow::Result<()> {
 
     // Build the router
     // SOC 2 CC6.1: Security headers middleware adds X-Frame-Options, X-Content-Type-Options, etc.
+    let probes = state.probes.clone();
     let app = create_router(state)
         .layer(middleware::from_fn(security_headers_middleware))
         .layer(cors)
@@ -275,13 +305,54 @@ async fn main() -> anyhow::Result<()> {
     let addr: SocketAddr = config.bind_address.parse()?;
     tracing::info!("Starting server on {}", addr);
 
-    // Start the server
+    // Initialization is done - the startup probe can begin passing
+    probes.mark_started();
+
+    // Start the server; SIGTERM/ctrl-c flips readiness to draining and
+    // lets in-flight requests finish before the process exits
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal(probes))
+        .await?;
 
     Ok(())
 }
 
+/// Wait for a shutdown signal, then start draining.
+///
+/// On Kubernetes the preStop hook should hit `POST /health/drain` first so
+/// the pod leaves the endpoint list before SIGTERM arrives; this handler
+/// covers deployments without a preStop hook.
+async fn shutdown_signal(probes: routes::health::ProbeState) {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c().await.ok();
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sig) => {
+                sig.recv().await;
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to install SIGTERM handler");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    tracing::info!("Shutdown signal received - draining");
+    probes.start_draining();
+}
+
 /// Background task to update GeoIP database weekly
 async fn geoip_update_task(state: AppState) {
     // Check every 24 hours
@@ -0,0 +1,219 @@
+//! Adaptive timeout tuning for MCP instances
+//!
+//! Static timeouts are either too tight for slow-but-healthy upstreams or too
+//! loose for fast ones. This module tracks recent request latencies per MCP
+//! and derives an effective timeout from a latency percentile plus a safety
+//! margin, clamped to a configured floor and ceiling. Until enough samples
+//! have been collected the caller falls back to its static timeout.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Manages adaptive timeouts for all MCP instances
+pub struct AdaptiveTimeoutManager {
+    windows: Arc<RwLock<HashMap<Uuid, LatencyWindow>>>,
+    config: AdaptiveTimeoutConfig,
+}
+
+#[derive(Clone)]
+pub struct AdaptiveTimeoutConfig {
+    /// Latency percentile the effective timeout is derived from (0.0-1.0)
+    pub percentile: f64,
+    /// Safety margin added on top of the percentile latency
+    pub margin: Duration,
+    /// Lower bound for the effective timeout
+    pub floor: Duration,
+    /// Upper bound for the effective timeout
+    pub ceiling: Duration,
+    /// Samples required before adaptive values take effect
+    pub min_samples: usize,
+    /// Number of recent samples kept per MCP
+    pub window_size: usize,
+}
+
+impl Default for AdaptiveTimeoutConfig {
+    fn default() -> Self {
+        Self {
+            percentile: 0.95,
+            margin: Duration::from_millis(500),
+            floor: Duration::from_secs(1),
+            ceiling: Duration::from_secs(30), // Matches the static REQUEST_TIMEOUT
+            min_samples: 20,
+            window_size: 256,
+        }
+    }
+}
+
+/// Rolling latency window for a single MCP
+#[derive(Debug, Default)]
+struct LatencyWindow {
+    /// Recent successful request latencies in milliseconds
+    samples: VecDeque<u64>,
+    /// Current effective timeout in milliseconds (None until min_samples)
+    effective_ms: Option<u64>,
+}
+
+/// Point-in-time view of an MCP's adaptive timeout state, exposed on the
+/// MCP detail endpoint
+#[derive(Debug, Clone, Serialize)]
+pub struct AdaptiveTimeoutSnapshot {
+    /// Number of latency samples currently in the window
+    pub sample_count: usize,
+    pub p50_ms: Option<u64>,
+    pub p95_ms: Option<u64>,
+    /// Effective timeout currently applied to requests (None while the
+    /// static timeout is still in use)
+    pub effective_timeout_ms: Option<u64>,
+    /// Whether adaptive values are active (enough samples collected)
+    pub adaptive_active: bool,
+}
+
+impl AdaptiveTimeoutManager {
+    pub fn new(config: AdaptiveTimeoutConfig) -> Self {
+        Self {
+            windows: Arc::new(RwLock::new(HashMap::new())),
+            config,
+        }
+    }
+
+    /// Record a successful request latency and recompute the effective
+    /// timeout for this MCP
+    pub async fn record_latency(&self, mcp_id: Uuid, latency: Duration) {
+        let mut windows = self.windows.write().await;
+        let window = windows.entry(mcp_id).or_default();
+
+        window.samples.push_back(latency.as_millis() as u64);
+        while window.samples.len() > self.config.window_size {
+            window.samples.pop_front();
+        }
+
+        if window.samples.len() < self.config.min_samples {
+            return;
+        }
+
+        let mut sorted: Vec<u64> = window.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let pct = percentile_ms(&sorted, self.config.percentile);
+
+        let effective = Duration::from_millis(pct)
+            .saturating_add(self.config.margin)
+            .clamp(self.config.floor, self.config.ceiling);
+        let effective_ms = effective.as_millis() as u64;
+
+        match window.effective_ms {
+            None => {
+                tracing::info!(
+                    mcp_id = %mcp_id,
+                    samples = window.samples.len(),
+                    effective_timeout_ms = effective_ms,
+                    "Adaptive timeout active"
+                );
+                window.effective_ms = Some(effective_ms);
+            }
+            // Re-log only on meaningful (>10%) shifts to avoid log churn
+            Some(previous) if effective_ms.abs_diff(previous) * 10 > previous => {
+                tracing::info!(
+                    mcp_id = %mcp_id,
+                    previous_ms = previous,
+                    effective_timeout_ms = effective_ms,
+                    "Adaptive timeout adjusted"
+                );
+                window.effective_ms = Some(effective_ms);
+            }
+            Some(_) => {
+                window.effective_ms = Some(effective_ms);
+            }
+        }
+    }
+
+    /// Current effective timeout for an MCP, or None while the static
+    /// timeout should still be used
+    pub async fn effective_timeout(&self, mcp_id: Uuid) -> Option<Duration> {
+        let windows = self.windows.read().await;
+        windows
+            .get(&mcp_id)
+            .and_then(|w| w.effective_ms)
+            .map(Duration::from_millis)
+    }
+
+    /// Snapshot of the adaptive timeout state for an MCP (None if no
+    /// requests have been observed yet)
+    pub async fn snapshot(&self, mcp_id: Uuid) -> Option<AdaptiveTimeoutSnapshot> {
+        let windows = self.windows.read().await;
+        let window = windows.get(&mcp_id)?;
+
+        let mut sorted: Vec<u64> = window.samples.iter().copied().collect();
+        sorted.sort_unstable();
+
+        Some(AdaptiveTimeoutSnapshot {
+            sample_count: window.samples.len(),
+            p50_ms: (!sorted.is_empty()).then(|| percentile_ms(&sorted, 0.50)),
+            p95_ms: (!sorted.is_empty()).then(|| percentile_ms(&sorted, 0.95)),
+            effective_timeout_ms: window.effective_ms,
+            adaptive_active: window.effective_ms.is_some(),
+        })
+    }
+}
+
+/// Nearest-rank percentile over a sorted sample slice
+fn percentile_ms(sorted: &[u64], percentile: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = ((sorted.len() as f64) * percentile).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        let sorted: Vec<u64> = (1..=100).collect();
+        assert_eq!(percentile_ms(&sorted, 0.50), 50);
+        assert_eq!(percentile_ms(&sorted, 0.95), 95);
+        assert_eq!(percentile_ms(&sorted, 1.0), 100);
+    }
+
+    #[tokio::test]
+    async fn test_no_effective_timeout_until_min_samples() {
+        let manager = AdaptiveTimeoutManager::new(AdaptiveTimeoutConfig::default());
+        let mcp_id = Uuid::new_v4();
+
+        for _ in 0..19 {
+            manager
+                .record_latency(mcp_id, Duration::from_millis(100))
+                .await;
+        }
+        assert!(manager.effective_timeout(mcp_id).await.is_none());
+
+        manager
+            .record_latency(mcp_id, Duration::from_millis(100))
+            .await;
+        assert!(manager.effective_timeout(mcp_id).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_effective_timeout_clamped_to_floor() {
+        let manager = AdaptiveTimeoutManager::new(AdaptiveTimeoutConfig::default());
+        let mcp_id = Uuid::new_v4();
+
+        // Fast upstream: p95 + margin would be well under the 1s floor
+        for _ in 0..50 {
+            manager
+                .record_latency(mcp_id, Duration::from_millis(10))
+                .await;
+        }
+
+        assert_eq!(
+            manager.effective_timeout(mcp_id).await,
+            Some(Duration::from_secs(1))
+        );
+    }
+}
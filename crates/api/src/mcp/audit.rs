@@ -8,13 +8,19 @@
 //!
 //! This module provides async logging that doesn't block request processing.
 
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use std::sync::Arc;
 use uuid::Uuid;
 
+use super::audit_buffer::AuditWriteBuffer;
+
 /// MCP Request Log Entry
 ///
 /// Captures all relevant information about an MCP proxy request for audit logging.
-#[derive(Debug, Clone)]
+/// Serializable so failed writes can be parked in the disk buffer and
+/// replayed once the database recovers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct McpRequestLog {
     /// Unique identifier for this request (generated by handler)
     pub request_id: Uuid,
@@ -79,6 +85,19 @@ pub struct McpRequestLog {
 
     /// Additional metadata in JSONB format
     pub metadata: Option<serde_json::Value>,
+
+    /// Original JSON-RPC payload (`{"method", "params"}`) captured for
+    /// replay. `None` when the request exceeded the capture size limit.
+    /// Defaulted on deserialization so buffer entries written before this
+    /// field existed still replay cleanly.
+    #[serde(default)]
+    pub request_payload: Option<serde_json::Value>,
+
+    /// Redacted `{"request", "response"}` copy stored when the org opted
+    /// into payload capture (see mcp::payload_capture). Defaulted on
+    /// deserialization like `request_payload`.
+    #[serde(default)]
+    pub captured_payload: Option<serde_json::Value>,
 }
 
 impl Default for McpRequestLog {
@@ -105,6 +124,8 @@ impl Default for McpRequestLog {
             rate_limit_hit: false,
             quota_exceeded: false,
             metadata: None,
+            request_payload: None,
+            captured_payload: None,
         }
     }
 }
@@ -134,13 +155,16 @@ impl Default for McpRequestLog {
 ///     ..Default::default()
 /// };
 ///
-/// log_mcp_request(pool.clone(), log);
+/// log_mcp_request(pool.clone(), audit_buffer.clone(), log);
 /// ```
-pub fn log_mcp_request(pool: PgPool, log: McpRequestLog) {
+pub fn log_mcp_request(pool: PgPool, buffer: Arc<AuditWriteBuffer>, log: McpRequestLog) {
     // Spawn async task to log request (don't block response)
     tokio::spawn(async move {
-        if let Err(e) = log_mcp_request_impl(&pool, log).await {
-            tracing::error!("Failed to log MCP request: {}", e);
+        if let Err(e) = log_mcp_request_impl(&pool, log.clone()).await {
+            // Database unavailable or insert failed: park the record in the
+            // disk buffer so it can be replayed once Postgres recovers
+            tracing::error!("Failed to log MCP request, buffering to disk: {}", e);
+            buffer.buffer_request_log(log).await;
         }
     });
 }
@@ -174,12 +198,14 @@ async fn log_mcp_request_impl(pool: &PgPool, log: McpRequestLog) -> Result<(), s
             rate_limit_hit,
             quota_exceeded,
             metadata,
+            request_payload,
+            captured_payload,
             created_at
         )
         VALUES (
             $1, $2, $3, $4, $5, $6, $7, $8, $9, $10,
             $11, $12, $13, $14, $15, $16, $17, $18, $19, $20,
-            $21, NOW()
+            $21, $22, $23, NOW()
         )
         "#,
     )
@@ -204,6 +230,8 @@ async fn log_mcp_request_impl(pool: &PgPool, log: McpRequestLog) -> Result<(), s
     .bind(log.rate_limit_hit)
     .bind(log.quota_exceeded)
     .bind(log.metadata)
+    .bind(log.request_payload)
+    .bind(log.captured_payload)
     .execute(pool)
     .await?;
 
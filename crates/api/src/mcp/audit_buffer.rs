@@ -0,0 +1,405 @@
+//! Disk-backed fallback buffer for audit and usage writes
+//!
+//! The proxy's audit and usage writes are fire-and-forget, so a brief
+//! Postgres outage silently drops records. This module parks failed
+//! writes in a local NDJSON write-ahead file instead; a background loop
+//! replays them once the database is reachable again. Every buffered
+//! entry carries a UUID that is inserted into `audit_buffer_replayed` in
+//! the same transaction as the replayed write, so a crash mid-replay
+//! never applies an entry twice. The current buffer depth is exposed on
+//! `/health` so operators can see when data is queued.
+
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::audit::McpRequestLog;
+
+/// How often the recovery loop attempts a replay
+pub const REPLAY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// How long replayed entry IDs are kept for dedup before pruning
+const REPLAY_DEDUP_RETENTION_DAYS: i64 = 7;
+
+/// One buffered write, tagged by kind
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum BufferedEntry {
+    McpRequestLog {
+        entry_id: Uuid,
+        record: Box<McpRequestLog>,
+    },
+    UsageEvent {
+        entry_id: Uuid,
+        record: BufferedUsageEvent,
+    },
+}
+
+impl BufferedEntry {
+    fn entry_id(&self) -> Uuid {
+        match self {
+            Self::McpRequestLog { entry_id, .. } | Self::UsageEvent { entry_id, .. } => *entry_id,
+        }
+    }
+}
+
+/// A usage event parked on disk when the billing write failed
+///
+/// Mirrors `plexmcp_billing::UsageEvent` plus the original event time, so
+/// replay attributes usage to the period it actually happened in rather
+/// than the recovery time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BufferedUsageEvent {
+    pub org_id: Uuid,
+    pub api_key_id: Option<Uuid>,
+    pub mcp_instance_id: Option<Uuid>,
+    pub request_count: i32,
+    pub token_count: i32,
+    pub error_count: i32,
+    pub latency_ms: Option<i32>,
+    /// When the event was originally recorded (unix timestamp)
+    pub recorded_at_unix: i64,
+}
+
+/// Disk-backed write-ahead buffer for failed audit/usage writes
+pub struct AuditWriteBuffer {
+    path: PathBuf,
+    /// Number of records currently parked on disk
+    depth: AtomicU64,
+    /// Serializes file appends and replay rewrites
+    file_lock: tokio::sync::Mutex<()>,
+}
+
+impl AuditWriteBuffer {
+    /// Open (or create) the buffer under the given directory
+    ///
+    /// Records from a previous process run are picked up, so data buffered
+    /// right before a restart is still replayed.
+    pub fn new(dir: &str) -> Self {
+        let dir = PathBuf::from(dir);
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            tracing::warn!(dir = %dir.display(), error = %e, "Failed to create audit buffer directory");
+        }
+        let path = dir.join("audit-buffer.ndjson");
+        let existing = std::fs::read_to_string(&path)
+            .map(|s| s.lines().filter(|l| !l.trim().is_empty()).count() as u64)
+            .unwrap_or(0);
+        if existing > 0 {
+            tracing::info!(
+                depth = existing,
+                "Audit buffer has records from a previous run pending replay"
+            );
+        }
+        Self {
+            path,
+            depth: AtomicU64::new(existing),
+            file_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Number of records currently buffered on disk
+    pub fn depth(&self) -> u64 {
+        self.depth.load(Ordering::Relaxed)
+    }
+
+    /// Park a failed mcp_request_log write for later replay
+    pub async fn buffer_request_log(&self, record: McpRequestLog) {
+        self.append(BufferedEntry::McpRequestLog {
+            entry_id: Uuid::new_v4(),
+            record: Box::new(record),
+        })
+        .await;
+    }
+
+    /// Park a failed usage write for later replay
+    pub async fn buffer_usage_event(&self, record: BufferedUsageEvent) {
+        self.append(BufferedEntry::UsageEvent {
+            entry_id: Uuid::new_v4(),
+            record,
+        })
+        .await;
+    }
+
+    async fn append(&self, entry: BufferedEntry) {
+        let Ok(mut line) = serde_json::to_string(&entry) else {
+            return;
+        };
+        line.push('\n');
+
+        let _guard = self.file_lock.lock().await;
+        let result = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await;
+        match result {
+            Ok(mut file) => {
+                use tokio::io::AsyncWriteExt;
+                // flush() waits for tokio's background write to reach the
+                // OS; without it a drop right after write_all can race
+                // readers (including our own replay)
+                let write = async {
+                    file.write_all(line.as_bytes()).await?;
+                    file.flush().await
+                };
+                if let Err(e) = write.await {
+                    tracing::error!(error = %e, "Failed to append to audit buffer - record lost");
+                } else {
+                    self.depth.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to open audit buffer file - record lost");
+            }
+        }
+    }
+
+    /// Replay buffered records into Postgres if it is reachable
+    ///
+    /// Stops at the first failure and keeps the unprocessed tail on disk
+    /// for the next attempt. Returns the number of entries applied (dedup
+    /// skips count as applied since the data is already in the database).
+    pub async fn replay(&self, pool: &PgPool) -> u64 {
+        if self.depth() == 0 {
+            return 0;
+        }
+
+        // Don't churn through the file while the database is still down
+        if sqlx::query("SELECT 1").execute(pool).await.is_err() {
+            return 0;
+        }
+
+        let _guard = self.file_lock.lock().await;
+        let contents = match tokio::fs::read_to_string(&self.path).await {
+            Ok(contents) => contents,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to read audit buffer for replay");
+                return 0;
+            }
+        };
+
+        let lines: Vec<&str> = contents.lines().filter(|l| !l.trim().is_empty()).collect();
+        let mut applied = 0u64;
+        let mut failed_at: Option<usize> = None;
+
+        for (i, line) in lines.iter().enumerate() {
+            let entry: BufferedEntry = match serde_json::from_str(line) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    // Malformed line (e.g. torn write on crash): drop it
+                    tracing::warn!(error = %e, "Dropping malformed audit buffer line");
+                    applied += 1;
+                    continue;
+                }
+            };
+
+            match replay_entry(pool, &entry).await {
+                Ok(()) => applied += 1,
+                Err(e) => {
+                    tracing::warn!(
+                        error = %e,
+                        entry_id = %entry.entry_id(),
+                        "Audit buffer replay interrupted, will retry"
+                    );
+                    failed_at = Some(i);
+                    break;
+                }
+            }
+        }
+
+        // Rewrite the file with whatever wasn't applied (empty on success)
+        let remainder: String = match failed_at {
+            Some(i) => lines[i..].iter().flat_map(|l| [*l, "\n"]).collect(),
+            None => String::new(),
+        };
+        if let Err(e) = tokio::fs::write(&self.path, &remainder).await {
+            tracing::error!(error = %e, "Failed to rewrite audit buffer after replay");
+        } else {
+            self.depth
+                .store(remainder.lines().count() as u64, Ordering::Relaxed);
+        }
+
+        if applied > 0 {
+            tracing::info!(
+                applied,
+                remaining = self.depth(),
+                "Replayed buffered audit/usage records"
+            );
+            // Opportunistic dedup-table pruning; failures are harmless
+            let _ = sqlx::query(
+                "DELETE FROM audit_buffer_replayed WHERE replayed_at < NOW() - make_interval(days => $1)",
+            )
+            .bind(REPLAY_DEDUP_RETENTION_DAYS)
+            .execute(pool)
+            .await;
+        }
+
+        applied
+    }
+}
+
+/// Apply one buffered entry inside a dedup transaction
+async fn replay_entry(pool: &PgPool, entry: &BufferedEntry) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    // Claim the entry; zero rows means a previous (crashed) replay already
+    // applied it and the write must be skipped
+    let claimed = sqlx::query(
+        "INSERT INTO audit_buffer_replayed (entry_id) VALUES ($1) ON CONFLICT DO NOTHING",
+    )
+    .bind(entry.entry_id())
+    .execute(&mut *tx)
+    .await?;
+    if claimed.rows_affected() == 0 {
+        tx.commit().await?;
+        return Ok(());
+    }
+
+    match entry {
+        BufferedEntry::McpRequestLog { record, .. } => {
+            sqlx::query(
+                r#"
+                INSERT INTO mcp_request_log (
+                    request_id, user_id, organization_id, tenant_id,
+                    mcp_server_name, endpoint_path, http_method, http_status_code,
+                    request_size_bytes, response_size_bytes, latency_ms, tokens_used,
+                    api_key_id, session_id, source_ip, user_agent,
+                    error_message, error_code, rate_limit_hit, quota_exceeded,
+                    metadata, created_at
+                )
+                VALUES (
+                    $1, $2, $3, $4, $5, $6, $7, $8, $9, $10,
+                    $11, $12, $13, $14, $15, $16, $17, $18, $19, $20,
+                    $21, NOW()
+                )
+                "#,
+            )
+            .bind(record.request_id)
+            .bind(record.user_id)
+            .bind(record.organization_id)
+            .bind(record.tenant_id)
+            .bind(&record.mcp_server_name)
+            .bind(&record.endpoint_path)
+            .bind(&record.http_method)
+            .bind(record.http_status_code)
+            .bind(record.request_size_bytes)
+            .bind(record.response_size_bytes)
+            .bind(record.latency_ms)
+            .bind(record.tokens_used)
+            .bind(record.api_key_id)
+            .bind(record.session_id)
+            .bind(&record.source_ip)
+            .bind(&record.user_agent)
+            .bind(&record.error_message)
+            .bind(&record.error_code)
+            .bind(record.rate_limit_hit)
+            .bind(record.quota_exceeded)
+            .bind(&record.metadata)
+            .execute(&mut *tx)
+            .await?;
+        }
+        BufferedEntry::UsageEvent { record, .. } => {
+            // Attribute the usage to the day it actually happened
+            let recorded_at = OffsetDateTime::from_unix_timestamp(record.recorded_at_unix)
+                .unwrap_or_else(|_| OffsetDateTime::now_utc());
+            let period_start = recorded_at.replace_time(time::Time::MIDNIGHT);
+            let period_end = period_start + time::Duration::days(1);
+
+            sqlx::query(
+                r#"
+                INSERT INTO usage_records (
+                    id, org_id, api_key_id, mcp_instance_id, request_count,
+                    token_count, error_count, latency_ms_avg, period_start, period_end
+                ) VALUES (
+                    $1, $2, $3, $4, $5, $6, $7, $8, $9, $10
+                )
+                "#,
+            )
+            .bind(Uuid::new_v4())
+            .bind(record.org_id)
+            .bind(record.api_key_id)
+            .bind(record.mcp_instance_id)
+            .bind(record.request_count)
+            .bind(record.token_count)
+            .bind(record.error_count)
+            .bind(record.latency_ms)
+            .bind(period_start)
+            .bind(period_end)
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    tx.commit().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_buffer() -> (AuditWriteBuffer, tempfile::TempDir) {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let buffer = AuditWriteBuffer::new(dir.path().to_str().unwrap());
+        (buffer, dir)
+    }
+
+    #[tokio::test]
+    async fn test_buffer_tracks_depth() {
+        let (buffer, _dir) = temp_buffer();
+        assert_eq!(buffer.depth(), 0);
+
+        buffer.buffer_request_log(McpRequestLog::default()).await;
+        buffer
+            .buffer_usage_event(BufferedUsageEvent {
+                org_id: Uuid::new_v4(),
+                api_key_id: None,
+                mcp_instance_id: None,
+                request_count: 1,
+                token_count: 0,
+                error_count: 0,
+                latency_ms: Some(12),
+                recorded_at_unix: OffsetDateTime::now_utc().unix_timestamp(),
+            })
+            .await;
+
+        assert_eq!(buffer.depth(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_buffer_survives_reopen() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().to_str().unwrap().to_string();
+
+        let buffer = AuditWriteBuffer::new(&path);
+        buffer.buffer_request_log(McpRequestLog::default()).await;
+        drop(buffer);
+
+        // A new instance (e.g. after restart) picks up the pending records
+        let reopened = AuditWriteBuffer::new(&path);
+        assert_eq!(reopened.depth(), 1);
+    }
+
+    #[test]
+    fn test_buffered_entry_roundtrip() {
+        let entry = BufferedEntry::McpRequestLog {
+            entry_id: Uuid::new_v4(),
+            record: Box::new(McpRequestLog {
+                mcp_server_name: "github".to_string(),
+                endpoint_path: "tools/call".to_string(),
+                ..Default::default()
+            }),
+        };
+        let json = serde_json::to_string(&entry).unwrap();
+        let parsed: BufferedEntry = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.entry_id(), entry.entry_id());
+        match parsed {
+            BufferedEntry::McpRequestLog { record, .. } => {
+                assert_eq!(record.mcp_server_name, "github");
+            }
+            _ => panic!("wrong kind after roundtrip"),
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! Upstream auth header templating
+//!
+//! Different upstreams want different auth header shapes (Bearer,
+//! X-Api-Key, custom schemes). Instead of one enum arm per shape, configs
+//! can declare header templates with typed credential slots:
+//!
+//! ```json
+//! {
+//!   "auth_type": "headers",
+//!   "auth_headers": { "Authorization": "Token {{credentials.token}}" },
+//!   "credentials": { "token": "secret-value" }
+//! }
+//! ```
+//!
+//! or pick a provider preset (`"auth_preset": "github"`) that expands to
+//! the right templates. Slots are substituted at render time, right before
+//! the headers reach `McpClient`; rendered values never go through
+//! `Debug`/log formatting (see the redacting `Debug` impl on `McpAuth`),
+//! and render errors name the slot, never its value.
+
+use serde_json::Value;
+
+/// Maximum headers one config may declare
+const MAX_AUTH_HEADERS: usize = 16;
+
+/// Provider presets: header-name/template pairs for common upstreams.
+/// Templates reference credential slots; fixed values (API version pins)
+/// are allowed too.
+const PRESETS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "openai",
+        &[("Authorization", "Bearer {{credentials.api_key}}")],
+    ),
+    (
+        "anthropic",
+        &[
+            ("x-api-key", "{{credentials.api_key}}"),
+            ("anthropic-version", "2023-06-01"),
+        ],
+    ),
+    (
+        "github",
+        &[
+            ("Authorization", "Bearer {{credentials.token}}"),
+            ("X-GitHub-Api-Version", "2022-11-28"),
+        ],
+    ),
+    ("gitlab", &[("PRIVATE-TOKEN", "{{credentials.token}}")]),
+    (
+        "supabase",
+        &[
+            ("apikey", "{{credentials.api_key}}"),
+            ("Authorization", "Bearer {{credentials.api_key}}"),
+        ],
+    ),
+    (
+        "notion",
+        &[
+            ("Authorization", "Bearer {{credentials.token}}"),
+            ("Notion-Version", "2022-06-28"),
+        ],
+    ),
+];
+
+/// Names accepted in `auth_preset` (for validation error messages)
+pub fn preset_names() -> Vec<&'static str> {
+    PRESETS.iter().map(|(name, _)| *name).collect()
+}
+
+/// Render auth headers from an MCP config
+///
+/// Uses `auth_preset` when present, otherwise the `auth_headers` template
+/// map. Credential slots come from the `credentials` object. Errors
+/// reference slot and header names only - never credential values.
+pub fn render_auth_headers(config: &Value) -> Result<Vec<(String, String)>, String> {
+    let templates: Vec<(String, String)> = match config.get("auth_preset").and_then(|v| v.as_str())
+    {
+        Some(preset) => {
+            let Some((_, headers)) = PRESETS.iter().find(|(name, _)| *name == preset) else {
+                return Err(format!(
+                    "unknown auth_preset '{}' (supported: {})",
+                    preset,
+                    preset_names().join(", ")
+                ));
+            };
+            headers
+                .iter()
+                .map(|(name, template)| (name.to_string(), template.to_string()))
+                .collect()
+        }
+        None => {
+            let Some(map) = config.get("auth_headers").and_then(|v| v.as_object()) else {
+                return Err(
+                    "auth_type 'headers' requires an auth_headers object or auth_preset"
+                        .to_string(),
+                );
+            };
+            let mut templates = Vec::with_capacity(map.len());
+            for (name, template) in map {
+                let Some(template) = template.as_str() else {
+                    return Err(format!("auth_headers['{}'] must be a string", name));
+                };
+                templates.push((name.clone(), template.to_string()));
+            }
+            templates
+        }
+    };
+
+    if templates.is_empty() {
+        return Err("auth_headers cannot be empty".to_string());
+    }
+    if templates.len() > MAX_AUTH_HEADERS {
+        return Err(format!("at most {} auth headers allowed", MAX_AUTH_HEADERS));
+    }
+
+    let credentials = config.get("credentials").and_then(|v| v.as_object());
+
+    let mut rendered = Vec::with_capacity(templates.len());
+    for (name, template) in templates {
+        if !is_valid_header_name(&name) {
+            return Err(format!("invalid header name '{}'", name));
+        }
+        let value = substitute(&template, credentials)?;
+        if value.chars().any(|c| c.is_control()) {
+            return Err(format!(
+                "rendered value for header '{}' contains control characters",
+                name
+            ));
+        }
+        rendered.push((name, value));
+    }
+
+    Ok(rendered)
+}
+
+/// Substitute `{{credentials.<slot>}}` references in a template
+fn substitute(
+    template: &str,
+    credentials: Option<&serde_json::Map<String, Value>>,
+) -> Result<String, String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            return Err("unterminated '{{' in header template".to_string());
+        };
+        let reference = after[..end].trim();
+        let Some(slot) = reference.strip_prefix("credentials.") else {
+            return Err(format!(
+                "unsupported template reference '{{{{{}}}}}' (only credentials.<slot> is allowed)",
+                reference
+            ));
+        };
+        let value = credentials
+            .and_then(|c| c.get(slot))
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| format!("credential slot '{}' is not set", slot))?;
+        out.push_str(value);
+        rest = &after[end + 2..];
+    }
+
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// RFC 7230 token characters for header names
+fn is_valid_header_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 128
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_render_custom_headers() {
+        let config = json!({
+            "auth_headers": {
+                "Authorization": "Token {{credentials.token}}",
+                "X-Org": "plexmcp"
+            },
+            "credentials": { "token": "abc123" }
+        });
+        let mut headers = render_auth_headers(&config).unwrap();
+        headers.sort();
+        assert_eq!(
+            headers,
+            vec![
+                ("Authorization".to_string(), "Token abc123".to_string()),
+                ("X-Org".to_string(), "plexmcp".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_preset() {
+        let config = json!({
+            "auth_preset": "github",
+            "credentials": { "token": "ghp_secret" }
+        });
+        let headers = render_auth_headers(&config).unwrap();
+        assert!(headers.contains(&("Authorization".to_string(), "Bearer ghp_secret".to_string())));
+        assert!(headers.contains(&("X-GitHub-Api-Version".to_string(), "2022-11-28".to_string())));
+    }
+
+    #[test]
+    fn test_unknown_preset_lists_supported() {
+        let config = json!({ "auth_preset": "nope" });
+        let err = render_auth_headers(&config).unwrap_err();
+        assert!(err.contains("unknown auth_preset"));
+        assert!(err.contains("github"));
+    }
+
+    #[test]
+    fn test_missing_slot_names_slot_not_value() {
+        let config = json!({
+            "auth_headers": { "Authorization": "Bearer {{credentials.api_key}}" },
+            "credentials": { "token": "super-secret" }
+        });
+        let err = render_auth_headers(&config).unwrap_err();
+        assert!(err.contains("api_key"));
+        assert!(!err.contains("super-secret"));
+    }
+
+    #[test]
+    fn test_rejects_invalid_header_name_and_control_chars() {
+        let config = json!({
+            "auth_headers": { "Bad Header": "x" }
+        });
+        assert!(render_auth_headers(&config).is_err());
+
+        let config = json!({
+            "auth_headers": { "X-Token": "{{credentials.token}}" },
+            "credentials": { "token": "line1\nline2" }
+        });
+        assert!(render_auth_headers(&config).is_err());
+    }
+
+    #[test]
+    fn test_rejects_non_credential_reference() {
+        let config = json!({
+            "auth_headers": { "X-Env": "{{env.HOME}}" }
+        });
+        let err = render_auth_headers(&config).unwrap_err();
+        assert!(err.contains("credentials.<slot>"));
+    }
+}
@@ -7,16 +7,35 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
+
+use serde::Serialize;
+use tokio::sync::{mpsc, RwLock};
 use uuid::Uuid;
 
 /// Manages circuit breakers for all MCP instances
 pub struct McpCircuitBreakerManager {
     breakers: Arc<RwLock<HashMap<Uuid, CircuitBreakerState>>>,
     config: CircuitBreakerConfig,
+    /// Per-MCP config overrides (persisted in the MCP's config JSON under
+    /// `circuit_breaker` and synced in whenever the MCP is loaded)
+    overrides: Arc<RwLock<HashMap<Uuid, CircuitBreakerConfig>>>,
+    /// Open/close transitions are sent here when a listener is attached
+    /// (the per-MCP health webhooks); None drops them
+    transitions: Arc<RwLock<Option<mpsc::UnboundedSender<CircuitTransition>>>>,
 }
 
-#[derive(Clone)]
+/// One circuit breaker state change, emitted to the transition listener
+#[derive(Debug, Clone)]
+pub struct CircuitTransition {
+    pub mcp_id: Uuid,
+    /// "closed" or "open"
+    pub from: &'static str,
+    pub to: &'static str,
+    pub consecutive_failures: u32,
+    pub backoff_ms: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct CircuitBreakerConfig {
     /// Number of consecutive failures before opening circuit
     pub failure_threshold: u32,
@@ -26,6 +45,34 @@ pub struct CircuitBreakerConfig {
     pub max_backoff: Duration,
 }
 
+impl CircuitBreakerConfig {
+    /// Parse a per-MCP override from the MCP's config JSON, e.g.
+    /// `{"circuit_breaker": {"failure_threshold": 3, "min_backoff_ms": 500}}`.
+    /// Missing fields fall back to the platform defaults; returns None when
+    /// no override block is present.
+    pub fn from_config(config: &serde_json::Value) -> Option<Self> {
+        let block = config.get("circuit_breaker")?.as_object()?;
+        let defaults = Self::default();
+        Some(Self {
+            failure_threshold: block
+                .get("failure_threshold")
+                .and_then(|v| v.as_u64())
+                .map(|v| v as u32)
+                .unwrap_or(defaults.failure_threshold),
+            min_backoff: block
+                .get("min_backoff_ms")
+                .and_then(|v| v.as_u64())
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.min_backoff),
+            max_backoff: block
+                .get("max_backoff_ms")
+                .and_then(|v| v.as_u64())
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.max_backoff),
+        })
+    }
+}
+
 impl Default for CircuitBreakerConfig {
     fn default() -> Self {
         Self {
@@ -41,6 +88,8 @@ struct CircuitBreakerState {
     consecutive_failures: u32,
     last_failure_time: Option<Instant>,
     current_backoff: Duration,
+    /// Closed-to-open transitions since process start
+    times_opened: u32,
 }
 
 impl Default for CircuitBreakerState {
@@ -49,25 +98,142 @@ impl Default for CircuitBreakerState {
             consecutive_failures: 0,
             last_failure_time: None,
             current_backoff: Duration::from_secs(1),
+            times_opened: 0,
         }
     }
 }
 
+/// Point-in-time view of an MCP's circuit breaker, exposed on
+/// `GET /mcps/:mcp_id/circuit`
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitBreakerSnapshot {
+    /// "closed", "open" or "half_open"
+    pub state: &'static str,
+    pub consecutive_failures: u32,
+    /// Closed-to-open transitions since process start
+    pub times_opened: u32,
+    /// Current backoff applied while the circuit is open
+    pub current_backoff_ms: u64,
+    /// Time until the next test request is allowed (open circuits only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backoff_remaining_ms: Option<u64>,
+    // Effective configuration (override or platform default)
+    pub failure_threshold: u32,
+    pub min_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    /// Whether a per-MCP override is active
+    pub override_active: bool,
+}
+
 impl McpCircuitBreakerManager {
     pub fn new(config: CircuitBreakerConfig) -> Self {
         Self {
             breakers: Arc::new(RwLock::new(HashMap::new())),
             config,
+            overrides: Arc::new(RwLock::new(HashMap::new())),
+            transitions: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Attach the open/close transition listener (replaces any previous one)
+    pub async fn set_transition_listener(&self, tx: mpsc::UnboundedSender<CircuitTransition>) {
+        *self.transitions.write().await = Some(tx);
+    }
+
+    async fn emit_transition(&self, transition: CircuitTransition) {
+        if let Some(tx) = self.transitions.read().await.as_ref() {
+            // A closed receiver just means nobody is listening anymore
+            let _ = tx.send(transition);
+        }
+    }
+
+    /// Effective config for an MCP (override if set, platform default
+    /// otherwise)
+    async fn config_for(&self, mcp_id: Uuid) -> CircuitBreakerConfig {
+        let overrides = self.overrides.read().await;
+        overrides
+            .get(&mcp_id)
+            .cloned()
+            .unwrap_or_else(|| self.config.clone())
+    }
+
+    /// Set or clear the per-MCP config override; no-op when unchanged so
+    /// the proxy path can sync on every load without write-lock churn
+    pub async fn apply_override(&self, mcp_id: Uuid, config: Option<CircuitBreakerConfig>) {
+        {
+            let overrides = self.overrides.read().await;
+            if overrides.get(&mcp_id) == config.as_ref() {
+                return;
+            }
+        }
+        let mut overrides = self.overrides.write().await;
+        match config {
+            Some(config) => {
+                overrides.insert(mcp_id, config);
+            }
+            None => {
+                overrides.remove(&mcp_id);
+            }
+        }
+    }
+
+    /// Current circuit state for an MCP; an MCP with no recorded requests
+    /// reports a closed circuit with default counters
+    pub async fn snapshot(&self, mcp_id: Uuid) -> CircuitBreakerSnapshot {
+        let config = self.config_for(mcp_id).await;
+        let override_active = self.overrides.read().await.contains_key(&mcp_id);
+
+        let breakers = self.breakers.read().await;
+        let state = breakers.get(&mcp_id).cloned().unwrap_or_default();
+
+        let (circuit_state, backoff_remaining) =
+            if state.consecutive_failures >= config.failure_threshold {
+                match state.last_failure_time {
+                    Some(last_failure) if last_failure.elapsed() < state.current_backoff => {
+                        ("open", Some(state.current_backoff - last_failure.elapsed()))
+                    }
+                    _ => ("half_open", None),
+                }
+            } else {
+                ("closed", None)
+            };
+
+        CircuitBreakerSnapshot {
+            state: circuit_state,
+            consecutive_failures: state.consecutive_failures,
+            times_opened: state.times_opened,
+            current_backoff_ms: state.current_backoff.as_millis() as u64,
+            backoff_remaining_ms: backoff_remaining.map(|d| d.as_millis() as u64),
+            failure_threshold: config.failure_threshold,
+            min_backoff_ms: config.min_backoff.as_millis() as u64,
+            max_backoff_ms: config.max_backoff.as_millis() as u64,
+            override_active,
+        }
+    }
+
+    /// Current state label for every breaker this process has tracked,
+    /// for the metrics endpoint
+    pub async fn all_states(&self) -> Vec<(Uuid, &'static str)> {
+        let mcp_ids: Vec<Uuid> = {
+            let breakers = self.breakers.read().await;
+            breakers.keys().copied().collect()
+        };
+
+        let mut states = Vec::with_capacity(mcp_ids.len());
+        for mcp_id in mcp_ids {
+            states.push((mcp_id, self.snapshot(mcp_id).await.state));
         }
+        states
     }
 
     /// Check if circuit breaker allows the request
     pub async fn is_call_permitted(&self, mcp_id: Uuid) -> bool {
+        let config = self.config_for(mcp_id).await;
         let breakers = self.breakers.read().await;
 
         if let Some(state) = breakers.get(&mcp_id) {
             // Circuit is open if we have enough consecutive failures
-            if state.consecutive_failures >= self.config.failure_threshold {
+            if state.consecutive_failures >= config.failure_threshold {
                 // Check if backoff period has elapsed
                 if let Some(last_failure) = state.last_failure_time {
                     let elapsed = last_failure.elapsed();
@@ -97,56 +263,100 @@ impl McpCircuitBreakerManager {
 
     /// Record a successful call - resets circuit breaker
     pub async fn record_success(&self, mcp_id: Uuid) {
-        let mut breakers = self.breakers.write().await;
+        let config = self.config_for(mcp_id).await;
+        let mut was_open = false;
 
-        if let Some(state) = breakers.get_mut(&mcp_id) {
-            if state.consecutive_failures > 0 {
-                tracing::info!(
-                    mcp_id = %mcp_id,
-                    previous_failures = state.consecutive_failures,
-                    "Circuit breaker reset - request succeeded"
-                );
-                *state = CircuitBreakerState::default();
+        {
+            let mut breakers = self.breakers.write().await;
+
+            if let Some(state) = breakers.get_mut(&mcp_id) {
+                if state.consecutive_failures > 0 {
+                    was_open = state.consecutive_failures >= config.failure_threshold;
+                    tracing::info!(
+                        mcp_id = %mcp_id,
+                        previous_failures = state.consecutive_failures,
+                        "Circuit breaker reset - request succeeded"
+                    );
+                    // Reset failure tracking but keep the transition counter so
+                    // the tuning endpoint can still report how often this
+                    // circuit has opened
+                    *state = CircuitBreakerState {
+                        times_opened: state.times_opened,
+                        ..CircuitBreakerState::default()
+                    };
+                }
             }
         }
+
+        if was_open {
+            self.emit_transition(CircuitTransition {
+                mcp_id,
+                from: "open",
+                to: "closed",
+                consecutive_failures: 0,
+                backoff_ms: 0,
+            })
+            .await;
+        }
     }
 
     /// Record a failed call - increments failure count and opens circuit if threshold reached
     pub async fn record_failure(&self, mcp_id: Uuid) {
-        let mut breakers = self.breakers.write().await;
-
-        let state = breakers
-            .entry(mcp_id)
-            .or_insert_with(CircuitBreakerState::default);
-        state.consecutive_failures += 1;
-        state.last_failure_time = Some(Instant::now());
-
-        // Calculate exponential backoff
-        if state.consecutive_failures >= self.config.failure_threshold {
-            let backoff_multiplier = 2u32.pow(
-                state
-                    .consecutive_failures
-                    .saturating_sub(self.config.failure_threshold),
-            );
-            state.current_backoff = self.config.min_backoff * backoff_multiplier;
-
-            if state.current_backoff > self.config.max_backoff {
-                state.current_backoff = self.config.max_backoff;
+        let config = self.config_for(mcp_id).await;
+        let mut opened: Option<CircuitTransition> = None;
+
+        {
+            let mut breakers = self.breakers.write().await;
+
+            let state = breakers
+                .entry(mcp_id)
+                .or_insert_with(CircuitBreakerState::default);
+            let was_open = state.consecutive_failures >= config.failure_threshold;
+            state.consecutive_failures += 1;
+            state.last_failure_time = Some(Instant::now());
+
+            // Calculate exponential backoff
+            if state.consecutive_failures >= config.failure_threshold {
+                let backoff_multiplier = 2u32.pow(
+                    state
+                        .consecutive_failures
+                        .saturating_sub(config.failure_threshold),
+                );
+                state.current_backoff = config.min_backoff * backoff_multiplier;
+
+                if state.current_backoff > config.max_backoff {
+                    state.current_backoff = config.max_backoff;
+                }
+
+                if !was_open {
+                    state.times_opened += 1;
+                    opened = Some(CircuitTransition {
+                        mcp_id,
+                        from: "closed",
+                        to: "open",
+                        consecutive_failures: state.consecutive_failures,
+                        backoff_ms: state.current_backoff.as_millis() as u64,
+                    });
+                }
+
+                tracing::warn!(
+                    mcp_id = %mcp_id,
+                    consecutive_failures = state.consecutive_failures,
+                    backoff = ?state.current_backoff,
+                    "Circuit breaker OPENED"
+                );
+            } else {
+                tracing::debug!(
+                    mcp_id = %mcp_id,
+                    consecutive_failures = state.consecutive_failures,
+                    threshold = config.failure_threshold,
+                    "Failure recorded - circuit still closed"
+                );
             }
+        }
 
-            tracing::warn!(
-                mcp_id = %mcp_id,
-                consecutive_failures = state.consecutive_failures,
-                backoff = ?state.current_backoff,
-                "Circuit breaker OPENED"
-            );
-        } else {
-            tracing::debug!(
-                mcp_id = %mcp_id,
-                consecutive_failures = state.consecutive_failures,
-                threshold = self.config.failure_threshold,
-                "Failure recorded - circuit still closed"
-            );
+        if let Some(transition) = opened {
+            self.emit_transition(transition).await;
         }
     }
 
@@ -186,3 +396,106 @@ pub enum CircuitBreakerError<E> {
     /// Inner error from the operation
     Inner(E),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_override_lowers_failure_threshold() {
+        let manager = McpCircuitBreakerManager::new(CircuitBreakerConfig::default());
+        let mcp_id = Uuid::new_v4();
+
+        manager
+            .apply_override(
+                mcp_id,
+                Some(CircuitBreakerConfig {
+                    failure_threshold: 2,
+                    min_backoff: Duration::from_secs(60),
+                    max_backoff: Duration::from_secs(60),
+                }),
+            )
+            .await;
+
+        manager.record_failure(mcp_id).await;
+        assert!(manager.is_call_permitted(mcp_id).await);
+
+        manager.record_failure(mcp_id).await;
+        assert!(!manager.is_call_permitted(mcp_id).await);
+
+        let snapshot = manager.snapshot(mcp_id).await;
+        assert_eq!(snapshot.state, "open");
+        assert_eq!(snapshot.times_opened, 1);
+        assert!(snapshot.override_active);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_half_open_after_backoff() {
+        let manager = McpCircuitBreakerManager::new(CircuitBreakerConfig::default());
+        let mcp_id = Uuid::new_v4();
+
+        manager
+            .apply_override(
+                mcp_id,
+                Some(CircuitBreakerConfig {
+                    failure_threshold: 1,
+                    min_backoff: Duration::from_millis(10),
+                    max_backoff: Duration::from_millis(10),
+                }),
+            )
+            .await;
+
+        manager.record_failure(mcp_id).await;
+        assert_eq!(manager.snapshot(mcp_id).await.state, "open");
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(manager.snapshot(mcp_id).await.state, "half_open");
+
+        // Success while half-open closes the circuit but keeps the counter
+        manager.record_success(mcp_id).await;
+        let snapshot = manager.snapshot(mcp_id).await;
+        assert_eq!(snapshot.state, "closed");
+        assert_eq!(snapshot.times_opened, 1);
+    }
+
+    #[tokio::test]
+    async fn test_transition_listener_sees_open_and_close() {
+        let manager = McpCircuitBreakerManager::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            min_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(10),
+        });
+        let mcp_id = Uuid::new_v4();
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        manager.set_transition_listener(tx).await;
+
+        manager.record_failure(mcp_id).await;
+        let opened = rx.recv().await.expect("open transition");
+        assert_eq!((opened.from, opened.to), ("closed", "open"));
+        assert_eq!(opened.consecutive_failures, 1);
+
+        manager.record_success(mcp_id).await;
+        let closed = rx.recv().await.expect("close transition");
+        assert_eq!((closed.from, closed.to), ("open", "closed"));
+
+        // A success on an already-closed circuit emits nothing
+        manager.record_success(mcp_id).await;
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_from_config_fills_defaults() {
+        assert!(CircuitBreakerConfig::from_config(&serde_json::json!({})).is_none());
+
+        let config = CircuitBreakerConfig::from_config(&serde_json::json!({
+            "circuit_breaker": {"failure_threshold": 3}
+        }))
+        .expect("override block should parse");
+        assert_eq!(config.failure_threshold, 3);
+        assert_eq!(
+            config.min_backoff,
+            CircuitBreakerConfig::default().min_backoff
+        );
+    }
+}
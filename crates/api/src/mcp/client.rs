@@ -8,7 +8,7 @@ use std::time::Duration;
 
 use reqwest::Client;
 use serde_json::Value;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{Child, Command};
 use tokio::sync::Mutex;
 
@@ -21,14 +21,35 @@ const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 #[allow(dead_code)] // Reserved for future use
 const INIT_TIMEOUT: Duration = Duration::from_secs(10);
 
-/// Maximum number of retry attempts for transient failures
-const MAX_RETRIES: usize = 3;
+/// Default cap on upstream response size in bytes (10MB); per-MCP override
+/// via `max_response_bytes` in the MCP config
+const DEFAULT_MAX_RESPONSE_BYTES: u64 = 10 * 1024 * 1024;
 
-/// Initial backoff duration for retries (100ms)
-const RETRY_BASE_DELAY: Duration = Duration::from_millis(100);
+/// Resolve the effective response size limit for a transport
+fn effective_response_limit(limit: Option<u64>) -> u64 {
+    limit.unwrap_or(DEFAULT_MAX_RESPONSE_BYTES)
+}
+
+/// Recent stderr lines kept per stdio process for the logs endpoint
+const STDIO_LOG_BUFFER_LINES: usize = 200;
+
+/// Cache key for transport HTTP clients: (proxy URL, connect timeout ms)
+type TransportClientKey = (Option<String>, Option<u64>);
+
+/// One captured stderr line from a managed stdio process
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StdioLogLine {
+    #[serde(with = "time::serde::rfc3339")]
+    pub timestamp: time::OffsetDateTime,
+    pub line: String,
+}
 
-/// Maximum backoff duration for retries (5 seconds)
-const RETRY_MAX_DELAY: Duration = Duration::from_secs(5);
+/// Status of a managed stdio process, surfaced on the lifecycle routes
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StdioProcessStatus {
+    pub running: bool,
+    pub pid: Option<u32>,
+}
 
 /// Error type for MCP client operations
 #[derive(Debug, thiserror::Error)]
@@ -54,8 +75,14 @@ pub enum McpClientError {
     #[error("Invalid response from server")]
     InvalidResponse,
 
+    #[error("Upstream session expired; re-login scheduled")]
+    SessionExpired,
+
     #[error("Process spawn failed: {0}")]
     ProcessError(String),
+
+    #[error("SSH tunnel error: {0}")]
+    TunnelError(#[from] super::ssh_tunnel::TunnelError),
 }
 
 impl McpClientError {
@@ -73,6 +100,19 @@ impl McpClientError {
             McpClientError::InvalidResponse => false,
             McpClientError::ProcessError(_) => false,
             McpClientError::JsonError(_) => false,
+
+            // The jar is invalidated on expiry; the retry would reuse the
+            // same stale cookie, so let the next request log in fresh
+            McpClientError::SessionExpired => false,
+
+            // Tunnel connect failures are often transient (bastion restart,
+            // network blip); bad keys are not
+            McpClientError::TunnelError(e) => matches!(
+                e,
+                super::ssh_tunnel::TunnelError::ConnectFailed(_, _)
+                    | super::ssh_tunnel::TunnelError::Io(_)
+                    | super::ssh_tunnel::TunnelError::Database(_)
+            ),
         }
     }
 }
@@ -104,15 +144,88 @@ fn parse_response_body(body: &str) -> McpResult<JsonRpcResponse> {
     serde_json::from_str(trimmed).map_err(McpClientError::from)
 }
 
+/// Read a response body with a hard size cap, enforced chunk by chunk so a
+/// misbehaving upstream can't make the proxy buffer an arbitrarily large
+/// body. Returns the collected text and whether it was cut off at the cap.
+async fn read_body_capped(
+    mut response: reqwest::Response,
+    max_bytes: u64,
+) -> McpResult<(String, bool)> {
+    let mut body: Vec<u8> = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() as u64 + chunk.len() as u64 > max_bytes {
+            let remaining = (max_bytes as usize).saturating_sub(body.len());
+            body.extend_from_slice(&chunk[..remaining]);
+            return Ok((String::from_utf8_lossy(&body).into_owned(), true));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok((String::from_utf8_lossy(&body).into_owned(), false))
+}
+
+/// Parse an SSE response body - look for the final result event
+fn parse_sse_body(text: &str) -> McpResult<JsonRpcResponse> {
+    let mut last_data: Option<JsonRpcResponse> = None;
+
+    for line in text.lines() {
+        if let Some(data) = line.strip_prefix("data: ") {
+            if let Ok(parsed) = serde_json::from_str::<JsonRpcResponse>(data) {
+                last_data = Some(parsed);
+            }
+        }
+    }
+
+    last_data.ok_or(McpClientError::InvalidResponse)
+}
+
+/// Build an HTTP client for a transport: optionally routed through an
+/// outbound proxy (HTTP CONNECT or SOCKS5, e.g. `http://proxy:3128` or
+/// `socks5://proxy:1080`) and with an optional per-MCP connect timeout
+fn build_transport_client(
+    proxy_url: Option<&str>,
+    connect_timeout: Option<Duration>,
+) -> Result<Client, String> {
+    let mut builder = Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .pool_max_idle_per_host(10);
+    if let Some(proxy_url) = proxy_url {
+        let proxy = reqwest::Proxy::all(proxy_url).map_err(|e| e.to_string())?;
+        builder = builder.proxy(proxy);
+    }
+    if let Some(connect_timeout) = connect_timeout {
+        builder = builder.connect_timeout(connect_timeout);
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
 /// MCP Client for connecting to upstream MCP servers
 pub struct McpClient {
     http_client: Client,
     /// Active stdio processes, keyed by MCP ID
     stdio_processes: Arc<Mutex<HashMap<String, StdioProcess>>>,
+    /// Ring buffers of recent stderr lines per stdio process, kept after
+    /// exit so the logs endpoint can show why a process died
+    stdio_logs: Arc<Mutex<HashMap<String, std::collections::VecDeque<StdioLogLine>>>>,
     /// Session IDs for HTTP MCP endpoints, keyed by endpoint URL
     http_sessions: Arc<Mutex<HashMap<String, String>>>,
     /// Circuit breaker manager for all MCP instances
     circuit_breakers: Arc<crate::mcp::circuit_breaker::McpCircuitBreakerManager>,
+    /// Adaptive timeout manager tracking per-MCP latency percentiles
+    adaptive_timeouts: Arc<crate::mcp::adaptive_timeout::AdaptiveTimeoutManager>,
+    /// SSH tunnel manager for MCPs behind a bastion (None when not wired,
+    /// e.g. in tests - tunnel transports then fail with NotInitialized)
+    tunnels: Option<Arc<crate::mcp::ssh_tunnel::SshTunnelManager>>,
+    /// HTTP clients for per-MCP outbound proxies and connect-timeout
+    /// overrides, keyed by (proxy URL, connect timeout ms); the default
+    /// client already carries the deployment-wide proxy
+    transport_clients: Arc<Mutex<HashMap<TransportClientKey, Client>>>,
+    /// Per-MCP timeout/retry policies (persisted in the MCP's config JSON
+    /// under `retry_policy` and synced in whenever the MCP is loaded)
+    retry_policies: Arc<Mutex<HashMap<uuid::Uuid, super::retry_policy::RetryPolicy>>>,
+    /// Pool for invalidating cookie jars when an upstream session expires
+    /// (None when not wired, e.g. in tests - expired sessions then surface
+    /// as errors without triggering a re-login)
+    session_store: Option<sqlx::PgPool>,
 }
 
 /// Wrapper for a stdio MCP process
@@ -139,16 +252,157 @@ impl McpClient {
                 crate::mcp::circuit_breaker::CircuitBreakerConfig::default(),
             ));
 
+        let adaptive_timeouts =
+            Arc::new(crate::mcp::adaptive_timeout::AdaptiveTimeoutManager::new(
+                crate::mcp::adaptive_timeout::AdaptiveTimeoutConfig::default(),
+            ));
+
         Self {
             http_client,
             stdio_processes: Arc::new(Mutex::new(HashMap::new())),
+            stdio_logs: Arc::new(Mutex::new(HashMap::new())),
             http_sessions: Arc::new(Mutex::new(HashMap::new())),
             circuit_breakers,
+            adaptive_timeouts,
+            tunnels: None,
+            transport_clients: Arc::new(Mutex::new(HashMap::new())),
+            retry_policies: Arc::new(Mutex::new(HashMap::new())),
+            session_store: None,
+        }
+    }
+
+    /// Create a client that can open SSH tunnels for bastion-gated MCPs
+    pub fn with_tunnels(tunnels: Arc<crate::mcp::ssh_tunnel::SshTunnelManager>) -> Self {
+        let mut client = Self::new();
+        client.tunnels = Some(tunnels);
+        client
+    }
+
+    /// Enable the re-login hook for cookie-auth MCPs: when an upstream
+    /// rejects a session with 401, the stored jar is dropped so the next
+    /// request triggers a fresh login (see `crate::mcp::session_auth`)
+    pub fn with_session_store(mut self, pool: sqlx::PgPool) -> Self {
+        self.session_store = Some(pool);
+        self
+    }
+
+    /// Route all upstream calls through a deployment-wide outbound proxy
+    /// (so customers can allowlist a static egress IP). Falls back to direct
+    /// connections if the proxy URL is invalid.
+    pub fn with_outbound_proxy(mut self, proxy_url: &str) -> Self {
+        match build_transport_client(Some(proxy_url), None) {
+            Ok(client) => {
+                tracing::info!(proxy = %proxy_url, "Upstream MCP calls routed through outbound proxy");
+                self.http_client = client;
+            }
+            Err(e) => {
+                tracing::error!(proxy = %proxy_url, "Invalid OUTBOUND_PROXY_URL, using direct connections: {}", e);
+            }
+        }
+        self
+    }
+
+    /// Get the HTTP client for a transport: a dedicated client when a
+    /// `proxy_url` or connect-timeout override is configured, otherwise the
+    /// default (deployment-wide) client
+    async fn client_for(
+        &self,
+        proxy_url: Option<&str>,
+        connect_timeout: Option<Duration>,
+    ) -> McpResult<Client> {
+        if proxy_url.is_none() && connect_timeout.is_none() {
+            return Ok(self.http_client.clone());
+        }
+
+        let key = (
+            proxy_url.map(String::from),
+            connect_timeout.map(|t| t.as_millis() as u64),
+        );
+        let mut clients = self.transport_clients.lock().await;
+        if let Some(client) = clients.get(&key) {
+            return Ok(client.clone());
+        }
+
+        let client = build_transport_client(proxy_url, connect_timeout)
+            .map_err(|e| McpClientError::McpError(format!("Invalid proxy_url: {}", e)))?;
+        clients.insert(key, client.clone());
+        Ok(client)
+    }
+
+    /// Effective timeout/retry policy for an MCP (platform defaults when
+    /// the MCP has no `retry_policy` override)
+    async fn retry_policy_for(&self, mcp_id: uuid::Uuid) -> super::retry_policy::RetryPolicy {
+        self.retry_policies
+            .lock()
+            .await
+            .get(&mcp_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Same lookup keyed by the string MCP ID the transport helpers carry;
+    /// non-UUID IDs (tests, ad-hoc probes) get the platform defaults
+    async fn retry_policy_for_str(&self, mcp_id: &str) -> super::retry_policy::RetryPolicy {
+        match uuid::Uuid::parse_str(mcp_id) {
+            Ok(mcp_uuid) => self.retry_policy_for(mcp_uuid).await,
+            Err(_) => super::retry_policy::RetryPolicy::default(),
+        }
+    }
+
+    /// Sync the per-MCP timeout/retry policy from the MCP's config JSON
+    /// (None clears it back to platform defaults)
+    pub async fn apply_retry_policy(
+        &self,
+        mcp_id: uuid::Uuid,
+        policy: Option<super::retry_policy::RetryPolicy>,
+    ) {
+        let mut policies = self.retry_policies.lock().await;
+        match policy {
+            Some(policy) => {
+                policies.insert(mcp_id, policy);
+            }
+            None => {
+                policies.remove(&mcp_id);
+            }
         }
     }
 
+    /// Resolve the URL to actually send requests to: for tunneled
+    /// transports, ensure the tunnel is up and rewrite the endpoint to the
+    /// local forwarded port
+    async fn resolve_endpoint(
+        &self,
+        endpoint_url: &str,
+        tunnel: Option<&crate::mcp::ssh_tunnel::SshTunnelConfig>,
+        mcp_id: &str,
+    ) -> McpResult<String> {
+        let Some(tunnel_config) = tunnel else {
+            return Ok(endpoint_url.to_string());
+        };
+
+        let Some(manager) = &self.tunnels else {
+            tracing::error!(mcp_id = %mcp_id, "MCP requires an SSH tunnel but no tunnel manager is configured");
+            return Err(McpClientError::NotInitialized);
+        };
+
+        let local_port = manager.ensure_tunnel(mcp_id, tunnel_config).await?;
+
+        let mut url = url::Url::parse(endpoint_url).map_err(|_| McpClientError::InvalidResponse)?;
+        url.set_host(Some("127.0.0.1"))
+            .map_err(|_| McpClientError::InvalidResponse)?;
+        url.set_port(Some(local_port))
+            .map_err(|_| McpClientError::InvalidResponse)?;
+        Ok(url.to_string())
+    }
+
     /// Initialize an HTTP MCP session and return the session ID
-    pub async fn init_http_session(&self, endpoint_url: &str, auth: &McpAuth) -> McpResult<String> {
+    pub async fn init_http_session(
+        &self,
+        endpoint_url: &str,
+        auth: &McpAuth,
+        proxy_url: Option<&str>,
+        timeouts: super::retry_policy::TransportTimeouts,
+    ) -> McpResult<String> {
         let init_request = JsonRpcRequest {
             jsonrpc: "2.0".to_string(),
             id: Some(JsonRpcId::Number(1)),
@@ -163,7 +417,13 @@ impl McpClient {
             })),
         };
 
-        let mut req_builder = self.http_client.post(endpoint_url);
+        let mut req_builder = self
+            .client_for(proxy_url, timeouts.connect)
+            .await?
+            .post(endpoint_url);
+        if let Some(read_timeout) = timeouts.read {
+            req_builder = req_builder.timeout(read_timeout);
+        }
 
         // Add authentication headers
         match auth {
@@ -177,6 +437,17 @@ impl McpClient {
             McpAuth::Basic { username, password } => {
                 req_builder = req_builder.basic_auth(username, Some(password));
             }
+            McpAuth::Headers { headers } => {
+                for (name, value) in headers {
+                    req_builder = req_builder.header(name.as_str(), value.as_str());
+                }
+            }
+            McpAuth::Query { params } => {
+                req_builder = req_builder.query(params);
+            }
+            McpAuth::Cookie { cookie } => {
+                req_builder = req_builder.header("Cookie", cookie.as_str());
+            }
         }
 
         let response = req_builder
@@ -195,7 +466,10 @@ impl McpClient {
 
         // Parse response to ensure it succeeded
         // Handle both JSON and SSE (Server-Sent Events) formats
-        let body = response.text().await?;
+        let (body, truncated) = read_body_capped(response, DEFAULT_MAX_RESPONSE_BYTES).await?;
+        if truncated {
+            return Err(McpClientError::InvalidResponse);
+        }
         let _json_response: JsonRpcResponse = parse_response_body(&body)?;
 
         if let Some(session_id) = session_id {
@@ -214,6 +488,8 @@ impl McpClient {
         &self,
         endpoint_url: &str,
         auth: &McpAuth,
+        proxy_url: Option<&str>,
+        timeouts: super::retry_policy::TransportTimeouts,
     ) -> McpResult<Option<String>> {
         // Check if we have a cached session
         {
@@ -228,7 +504,9 @@ impl McpClient {
         }
 
         // No cached session, initialize one
-        let session_id = self.init_http_session(endpoint_url, auth).await?;
+        let session_id = self
+            .init_http_session(endpoint_url, auth, proxy_url, timeouts)
+            .await?;
         if session_id.is_empty() {
             Ok(None)
         } else {
@@ -241,12 +519,23 @@ impl McpClient {
         &self,
         endpoint_url: &str,
         auth: &McpAuth,
+        proxy_url: Option<&str>,
+        timeouts: super::retry_policy::TransportTimeouts,
         request: &JsonRpcRequest,
+        max_response_bytes: u64,
     ) -> McpResult<JsonRpcResponse> {
         // Get or create a session for this endpoint
-        let session_id = self.get_or_create_session(endpoint_url, auth).await?;
+        let session_id = self
+            .get_or_create_session(endpoint_url, auth, proxy_url, timeouts)
+            .await?;
 
-        let mut req_builder = self.http_client.post(endpoint_url);
+        let mut req_builder = self
+            .client_for(proxy_url, timeouts.connect)
+            .await?
+            .post(endpoint_url);
+        if let Some(read_timeout) = timeouts.read {
+            req_builder = req_builder.timeout(read_timeout);
+        }
 
         // Add authentication headers
         match auth {
@@ -260,6 +549,17 @@ impl McpClient {
             McpAuth::Basic { username, password } => {
                 req_builder = req_builder.basic_auth(username, Some(password));
             }
+            McpAuth::Headers { headers } => {
+                for (name, value) in headers {
+                    req_builder = req_builder.header(name.as_str(), value.as_str());
+                }
+            }
+            McpAuth::Query { params } => {
+                req_builder = req_builder.query(params);
+            }
+            McpAuth::Cookie { cookie } => {
+                req_builder = req_builder.header("Cookie", cookie.as_str());
+            }
         }
 
         // Add session ID header if we have one
@@ -267,57 +567,97 @@ impl McpClient {
             req_builder = req_builder.header("Mcp-Session-Id", session_id.as_str());
         }
 
-        let response = req_builder
+        // Propagate trace context to the upstream and record the call as a
+        // child span (no-op outside a traced request)
+        let upstream_ctx = crate::otel::current_context().map(|ctx| ctx.child());
+        if let Some(ref ctx) = upstream_ctx {
+            req_builder = req_builder.header("traceparent", ctx.traceparent());
+        }
+        let upstream_start = std::time::SystemTime::now();
+
+        let result = req_builder
             .header("Content-Type", "application/json")
             .header("Accept", "application/json, text/event-stream")
             .json(request)
             .send()
-            .await?;
+            .await;
+
+        if let Some(ctx) = upstream_ctx {
+            if ctx.sampled {
+                crate::otel::record_span(crate::otel::SpanRecord::finish(
+                    &ctx,
+                    "mcp.upstream.request",
+                    upstream_start,
+                    vec![
+                        ("http.url", endpoint_url.to_string()),
+                        ("rpc.method", request.method.clone()),
+                    ],
+                    result.is_err(),
+                ));
+            }
+        }
+
+        let response = result?;
+
+        // A 401 on cookie auth means the upstream session died; surface a
+        // dedicated error so send_request can invalidate the jar
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED
+            && matches!(auth, McpAuth::Cookie { .. })
+        {
+            return Err(McpClientError::SessionExpired);
+        }
 
         // Check content type for SSE vs JSON
         let content_type = response
             .headers()
             .get("content-type")
             .and_then(|v| v.to_str().ok())
-            .unwrap_or("");
+            .unwrap_or("")
+            .to_string();
+
+        // Stream the body in with the per-MCP size cap applied
+        let (body, truncated) = read_body_capped(response, max_response_bytes).await?;
+        if truncated {
+            tracing::warn!(
+                endpoint_url = %endpoint_url,
+                limit_bytes = %max_response_bytes,
+                "Upstream response exceeded size limit; truncated"
+            );
+            return Ok(JsonRpcResponse::error(
+                request.id.clone(),
+                JsonRpcError::response_too_large(max_response_bytes),
+            ));
+        }
 
         if content_type.contains("text/event-stream") {
             // Handle SSE response - collect all events
-            self.handle_sse_response(response).await
+            parse_sse_body(&body)
         } else {
             // Standard JSON response
-            let json_response: JsonRpcResponse = response.json().await?;
-            Ok(json_response)
-        }
-    }
-
-    /// Handle SSE response stream
-    async fn handle_sse_response(&self, response: reqwest::Response) -> McpResult<JsonRpcResponse> {
-        let text = response.text().await?;
-
-        // Parse SSE events - look for the final result
-        let mut last_data: Option<JsonRpcResponse> = None;
-
-        for line in text.lines() {
-            if let Some(data) = line.strip_prefix("data: ") {
-                if let Ok(parsed) = serde_json::from_str::<JsonRpcResponse>(data) {
-                    last_data = Some(parsed);
-                }
-            }
+            serde_json::from_str(&body).map_err(McpClientError::from)
         }
-
-        last_data.ok_or(McpClientError::InvalidResponse)
     }
 
     /// Initialize a stdio MCP process
+    ///
+    /// With sandbox limits the command is checked against the interpreter
+    /// allowlist and spawned with a scrubbed environment and rlimits
+    /// (see `crate::mcp::sandbox`).
     pub async fn init_stdio_process(
         &self,
         mcp_id: &str,
         command: &str,
         args: &[String],
         env: &HashMap<String, String>,
+        sandbox: Option<&super::sandbox::SandboxLimits>,
     ) -> McpResult<()> {
         let mut cmd = Command::new(command);
+        if let Some(limits) = sandbox {
+            super::sandbox::validate_command(command).map_err(McpClientError::ProcessError)?;
+            // env_clear happens here, so the org env below is all the
+            // child sees beyond the sandbox PATH
+            super::sandbox::apply(&mut cmd, limits);
+        }
         cmd.args(args)
             .envs(env)
             .stdin(std::process::Stdio::piped())
@@ -343,8 +683,10 @@ impl McpClient {
             .take()
             .ok_or_else(|| McpClientError::ProcessError("Failed to capture stderr".to_string()))?;
 
-        // Spawn background task to read stderr and log it
+        // Spawn background task to read stderr, log it, and keep the tail
+        // in the per-process ring buffer for the logs endpoint
         let mcp_id_clone = mcp_id.to_string();
+        let logs = self.stdio_logs.clone();
         tokio::spawn(async move {
             let mut reader = BufReader::new(stderr);
             let mut line = String::new();
@@ -372,6 +714,16 @@ impl McpClient {
                         } else {
                             tracing::debug!(mcp_id = %mcp_id_clone, stderr = %trimmed);
                         }
+
+                        let mut logs = logs.lock().await;
+                        let buffer = logs.entry(mcp_id_clone.clone()).or_default();
+                        if buffer.len() >= STDIO_LOG_BUFFER_LINES {
+                            buffer.pop_front();
+                        }
+                        buffer.push_back(StdioLogLine {
+                            timestamp: time::OffsetDateTime::now_utc(),
+                            line: trimmed.to_string(),
+                        });
                     }
                     Err(e) => {
                         tracing::error!(mcp_id = %mcp_id_clone, error = %e, "Failed to read stderr");
@@ -401,7 +753,16 @@ impl McpClient {
         &self,
         mcp_id: &str,
         request: &JsonRpcRequest,
+        max_response_bytes: u64,
     ) -> McpResult<JsonRpcResponse> {
+        // Per-MCP read timeout override, resolved before taking the
+        // process lock
+        let read_timeout = self
+            .retry_policy_for_str(mcp_id)
+            .await
+            .read_timeout
+            .unwrap_or(REQUEST_TIMEOUT);
+
         let mut processes = self.stdio_processes.lock().await;
         let process = processes
             .get_mut(mcp_id)
@@ -418,8 +779,12 @@ impl McpClient {
         // Read response from stdout with timeout
         let mut response_line = String::new();
 
-        let read_result = tokio::time::timeout(REQUEST_TIMEOUT, async {
-            process.stdout.read_line(&mut response_line).await
+        // Cap the line read so a runaway upstream can't make us buffer an
+        // arbitrarily long line (+2 leaves room for a trailing newline on a
+        // line of exactly the limit)
+        let read_result = tokio::time::timeout(read_timeout, async {
+            let mut limited = (&mut process.stdout).take(max_response_bytes.saturating_add(2));
+            limited.read_line(&mut response_line).await
         })
         .await;
 
@@ -469,6 +834,29 @@ impl McpClient {
             return Err(McpClientError::InvalidResponse);
         }
 
+        if response_line.trim_end().len() as u64 > max_response_bytes {
+            // The rest of the oversized line is still in the pipe and the
+            // session can't be resynchronized, so drop the process entirely
+            tracing::warn!(
+                mcp_id = %mcp_id,
+                limit_bytes = %max_response_bytes,
+                "Stdio response exceeded size limit; killing process"
+            );
+
+            let removed = processes.remove(mcp_id);
+            drop(processes);
+
+            if let Some(mut proc) = removed {
+                let _ = proc.child.kill().await;
+                let _ = proc.child.wait().await; // REAP ZOMBIE
+            }
+
+            return Ok(JsonRpcResponse::error(
+                request.id.clone(),
+                JsonRpcError::response_too_large(max_response_bytes),
+            ));
+        }
+
         // Parse response
         let response: JsonRpcResponse = serde_json::from_str(&response_line)?;
         Ok(response)
@@ -524,27 +912,103 @@ impl McpClient {
         request: &JsonRpcRequest,
     ) -> McpResult<JsonRpcResponse> {
         match transport {
-            McpTransport::Http { endpoint_url, auth } => {
-                self.send_http_request(endpoint_url, auth, request).await
+            McpTransport::Http {
+                endpoint_url,
+                auth,
+                tunnel,
+                proxy_url,
+                max_response_bytes,
+            } => {
+                let url = self
+                    .resolve_endpoint(endpoint_url, tunnel.as_ref(), mcp_id)
+                    .await?;
+                let timeouts = self.retry_policy_for_str(mcp_id).await.transport_timeouts();
+                let result = self
+                    .send_http_request(
+                        &url,
+                        auth,
+                        proxy_url.as_deref(),
+                        timeouts,
+                        request,
+                        effective_response_limit(*max_response_bytes),
+                    )
+                    .await;
+                if matches!(result, Err(McpClientError::SessionExpired)) {
+                    self.expire_cookie_session(mcp_id).await;
+                }
+                result
             }
-            McpTransport::Sse { endpoint_url, auth } => {
+            McpTransport::Sse {
+                endpoint_url,
+                auth,
+                tunnel,
+                proxy_url,
+                max_response_bytes,
+            } => {
                 // SSE uses same HTTP endpoint but may return SSE stream
-                self.send_http_request(endpoint_url, auth, request).await
+                let url = self
+                    .resolve_endpoint(endpoint_url, tunnel.as_ref(), mcp_id)
+                    .await?;
+                let timeouts = self.retry_policy_for_str(mcp_id).await.transport_timeouts();
+                let result = self
+                    .send_http_request(
+                        &url,
+                        auth,
+                        proxy_url.as_deref(),
+                        timeouts,
+                        request,
+                        effective_response_limit(*max_response_bytes),
+                    )
+                    .await;
+                if matches!(result, Err(McpClientError::SessionExpired)) {
+                    self.expire_cookie_session(mcp_id).await;
+                }
+                result
             }
-            McpTransport::Stdio { command, args, env } => {
+            McpTransport::Stdio {
+                command,
+                args,
+                env,
+                max_response_bytes,
+                sandbox,
+            } => {
                 // Ensure process is running
                 {
                     let processes = self.stdio_processes.lock().await;
                     if !processes.contains_key(mcp_id) {
                         drop(processes);
-                        self.init_stdio_process(mcp_id, command, args, env).await?;
+                        self.init_stdio_process(mcp_id, command, args, env, sandbox.as_ref())
+                            .await?;
                     }
                 }
-                self.send_stdio_request(mcp_id, request).await
+                self.send_stdio_request(
+                    mcp_id,
+                    request,
+                    effective_response_limit(*max_response_bytes),
+                )
+                .await
             }
         }
     }
 
+    /// Re-login hook: drop the stored cookie jar for an MCP whose session
+    /// the upstream just rejected, so the next request logs in fresh
+    async fn expire_cookie_session(&self, mcp_id: &str) {
+        let Some(pool) = &self.session_store else {
+            return;
+        };
+        let Ok(mcp_uuid) = uuid::Uuid::parse_str(mcp_id) else {
+            return;
+        };
+        tracing::warn!(
+            mcp_id = %mcp_id,
+            "Upstream rejected session cookies - invalidating jar for re-login"
+        );
+        if let Err(e) = super::session_auth::invalidate_session(pool, mcp_uuid).await {
+            tracing::error!(mcp_id = %mcp_id, "Failed to invalidate cookie jar: {}", e);
+        }
+    }
+
     /// Send request with circuit breaker protection
     ///
     /// Wraps send_request with circuit breaker pattern to prevent cascading failures.
@@ -564,10 +1028,47 @@ impl McpClient {
         let mcp_id_str = mcp_id_str.to_string();
         let request = request.clone();
 
+        // An explicit per-MCP read timeout wins over adaptive tuning;
+        // otherwise use the adaptive timeout once enough latency samples
+        // exist, falling back to the static default
+        let effective_timeout = match self.retry_policy_for(mcp_id).await.read_timeout {
+            Some(read_timeout) => read_timeout,
+            None => self
+                .adaptive_timeouts
+                .effective_timeout(mcp_id)
+                .await
+                .unwrap_or(REQUEST_TIMEOUT),
+        };
+
         let result = self
             .circuit_breakers
             .call(mcp_id, || async {
-                self.send_request(&transport, &mcp_id_str, &request).await
+                let start = std::time::Instant::now();
+                match tokio::time::timeout(
+                    effective_timeout,
+                    self.send_request(&transport, &mcp_id_str, &request),
+                )
+                .await
+                {
+                    Ok(Ok(response)) => {
+                        self.adaptive_timeouts
+                            .record_latency(mcp_id, start.elapsed())
+                            .await;
+                        crate::metrics::registry()
+                            .record_upstream(mcp_id, start.elapsed())
+                            .await;
+                        Ok(response)
+                    }
+                    Ok(Err(e)) => Err(e),
+                    Err(_) => {
+                        tracing::warn!(
+                            mcp_id = %mcp_id,
+                            timeout_ms = effective_timeout.as_millis() as u64,
+                            "Request exceeded effective timeout"
+                        );
+                        Err(McpClientError::Timeout)
+                    }
+                }
             })
             .await;
 
@@ -585,9 +1086,13 @@ impl McpClient {
 
     /// Send request with retry logic and circuit breaker protection
     ///
-    /// Combines circuit breaker pattern with exponential backoff retry logic.
-    /// Only retries on transient errors (network issues, timeouts).
-    /// Permanent errors (invalid JSON, MCP errors) are not retried.
+    /// Combines circuit breaker pattern with jittered backoff retries
+    /// following the MCP's retry policy (count and strategy from the
+    /// `retry_policy` config block, platform defaults otherwise). Only
+    /// retries on transient errors (network issues, timeouts) and only for
+    /// idempotent methods - a timed-out `tools/call` may have executed
+    /// upstream, so it is never replayed. Permanent errors (invalid JSON,
+    /// MCP errors) are not retried.
     pub async fn send_request_with_retry(
         &self,
         mcp_id: uuid::Uuid,
@@ -595,14 +1100,15 @@ impl McpClient {
         mcp_id_str: &str,
         request: &JsonRpcRequest,
     ) -> McpResult<JsonRpcResponse> {
-        use tokio_retry::strategy::{jitter, ExponentialBackoff};
         use tokio_retry::Retry;
 
-        // Create exponential backoff strategy with jitter
-        let retry_strategy = ExponentialBackoff::from_millis(RETRY_BASE_DELAY.as_millis() as u64)
-            .max_delay(RETRY_MAX_DELAY)
-            .take(MAX_RETRIES)
-            .map(jitter);
+        if !super::retry_policy::is_idempotent(&request.method) {
+            return self
+                .send_request_with_breaker(mcp_id, transport, mcp_id_str, request)
+                .await;
+        }
+
+        let retry_strategy = self.retry_policy_for(mcp_id).await.delays();
 
         let transport = transport.clone();
         let mcp_id_str = mcp_id_str.to_string();
@@ -741,6 +1247,59 @@ impl McpClient {
         Ok(result)
     }
 
+    /// Subscribe to update notifications for a resource on an upstream MCP
+    ///
+    /// Subscribe results are typically empty objects, so the raw result
+    /// value is returned as-is.
+    pub async fn subscribe_resource(
+        &self,
+        transport: &McpTransport,
+        mcp_id: &str,
+        uri: &str,
+    ) -> McpResult<Value> {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: Some(JsonRpcId::Number(4)),
+            method: "resources/subscribe".to_string(),
+            params: Some(serde_json::to_value(ResourceReadParams {
+                uri: uri.to_string(),
+            })?),
+        };
+
+        let response = self.send_request(transport, mcp_id, &request).await?;
+
+        if let Some(error) = response.error {
+            return Err(McpClientError::McpError(error.message));
+        }
+
+        Ok(response.result.unwrap_or_else(|| serde_json::json!({})))
+    }
+
+    /// Cancel a resource subscription on an upstream MCP
+    pub async fn unsubscribe_resource(
+        &self,
+        transport: &McpTransport,
+        mcp_id: &str,
+        uri: &str,
+    ) -> McpResult<Value> {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: Some(JsonRpcId::Number(4)),
+            method: "resources/unsubscribe".to_string(),
+            params: Some(serde_json::to_value(ResourceReadParams {
+                uri: uri.to_string(),
+            })?),
+        };
+
+        let response = self.send_request(transport, mcp_id, &request).await?;
+
+        if let Some(error) = response.error {
+            return Err(McpClientError::McpError(error.message));
+        }
+
+        Ok(response.result.unwrap_or_else(|| serde_json::json!({})))
+    }
+
     /// Get prompts from an upstream MCP
     pub async fn get_prompts(
         &self,
@@ -884,6 +1443,124 @@ impl McpClient {
         Ok(result.prompts)
     }
 
+    /// Whether a warm connection already exists for this transport: a cached
+    /// HTTP session for HTTP/SSE, or a running process for stdio
+    pub async fn is_connection_warm(&self, transport: &McpTransport, mcp_id: &str) -> bool {
+        match transport {
+            McpTransport::Http { endpoint_url, .. } | McpTransport::Sse { endpoint_url, .. } => {
+                let sessions = self.http_sessions.lock().await;
+                sessions.contains_key(endpoint_url)
+            }
+            McpTransport::Stdio { .. } => {
+                let processes = self.stdio_processes.lock().await;
+                processes.contains_key(mcp_id)
+            }
+        }
+    }
+
+    /// Pre-establish the connection for a transport so the next real request
+    /// skips TCP/TLS (and session/process) setup
+    ///
+    /// Used by the keep-warm sweep and on config save for keep-warm MCPs.
+    /// Failures are logged rather than returned - the next real request will
+    /// surface them.
+    pub async fn warm_connection(&self, transport: &McpTransport, mcp_id: &str) {
+        let result: McpResult<()> = async {
+            match transport {
+                McpTransport::Http {
+                    endpoint_url,
+                    auth,
+                    tunnel,
+                    proxy_url,
+                    ..
+                }
+                | McpTransport::Sse {
+                    endpoint_url,
+                    auth,
+                    tunnel,
+                    proxy_url,
+                    ..
+                } => {
+                    let url = self
+                        .resolve_endpoint(endpoint_url, tunnel.as_ref(), mcp_id)
+                        .await?;
+                    let timeouts = self.retry_policy_for_str(mcp_id).await.transport_timeouts();
+                    self.get_or_create_session(&url, auth, proxy_url.as_deref(), timeouts)
+                        .await?;
+                    Ok(())
+                }
+                McpTransport::Stdio {
+                    command,
+                    args,
+                    env,
+                    sandbox,
+                    ..
+                } => {
+                    let processes = self.stdio_processes.lock().await;
+                    if processes.contains_key(mcp_id) {
+                        return Ok(());
+                    }
+                    drop(processes);
+                    self.init_stdio_process(mcp_id, command, args, env, sandbox.as_ref())
+                        .await
+                }
+            }
+        }
+        .await;
+
+        match result {
+            Ok(()) => {
+                tracing::debug!(mcp_id = %mcp_id, "Connection warmed");
+            }
+            Err(e) => {
+                tracing::warn!(mcp_id = %mcp_id, error = %e, "Failed to warm connection");
+            }
+        }
+    }
+
+    /// Adaptive timeout state for an MCP (None if no requests observed),
+    /// surfaced on the MCP detail endpoint
+    pub async fn adaptive_timeout_snapshot(
+        &self,
+        mcp_id: uuid::Uuid,
+    ) -> Option<crate::mcp::adaptive_timeout::AdaptiveTimeoutSnapshot> {
+        self.adaptive_timeouts.snapshot(mcp_id).await
+    }
+
+    /// Circuit breaker state for an MCP, surfaced on the circuit tuning
+    /// endpoint
+    pub async fn circuit_snapshot(
+        &self,
+        mcp_id: uuid::Uuid,
+    ) -> crate::mcp::circuit_breaker::CircuitBreakerSnapshot {
+        self.circuit_breakers.snapshot(mcp_id).await
+    }
+
+    /// State label of every circuit breaker tracked in this process,
+    /// surfaced on the metrics endpoint
+    pub async fn circuit_states(&self) -> Vec<(uuid::Uuid, &'static str)> {
+        self.circuit_breakers.all_states().await
+    }
+
+    /// Sync the per-MCP circuit breaker override from the MCP's config
+    /// JSON (None clears it back to platform defaults)
+    pub async fn apply_circuit_override(
+        &self,
+        mcp_id: uuid::Uuid,
+        config: Option<crate::mcp::circuit_breaker::CircuitBreakerConfig>,
+    ) {
+        self.circuit_breakers.apply_override(mcp_id, config).await;
+    }
+
+    /// Attach the circuit breaker transition listener (feeds the per-MCP
+    /// health webhooks)
+    pub async fn set_circuit_transition_listener(
+        &self,
+        tx: tokio::sync::mpsc::UnboundedSender<crate::mcp::circuit_breaker::CircuitTransition>,
+    ) {
+        self.circuit_breakers.set_transition_listener(tx).await;
+    }
+
     /// Gracefully shutdown all stdio processes
     pub async fn shutdown(&self) {
         let mut processes = self.stdio_processes.lock().await;
@@ -918,6 +1595,107 @@ impl McpClient {
         tracing::info!("All stdio processes shut down");
     }
 
+    /// Status of the managed stdio process for an MCP
+    ///
+    /// Reaps the entry if the child has already exited so the lifecycle
+    /// endpoints don't report zombies as running.
+    pub async fn stdio_process_status(&self, mcp_id: &str) -> StdioProcessStatus {
+        let mut processes = self.stdio_processes.lock().await;
+        if let Some(process) = processes.get_mut(mcp_id) {
+            match process.child.try_wait() {
+                Ok(None) => {
+                    return StdioProcessStatus {
+                        running: true,
+                        pid: process.child.id(),
+                    };
+                }
+                Ok(Some(status)) => {
+                    tracing::info!(mcp_id = %mcp_id, ?status, "Stdio process exited");
+                    processes.remove(mcp_id);
+                }
+                Err(e) => {
+                    tracing::error!(mcp_id = %mcp_id, error = %e, "Failed to poll stdio process");
+                    processes.remove(mcp_id);
+                }
+            }
+        }
+        StdioProcessStatus {
+            running: false,
+            pid: None,
+        }
+    }
+
+    /// Recent stderr output from a managed stdio process
+    ///
+    /// The buffer survives process exit, so this is the first stop when a
+    /// stdio MCP died at startup.
+    pub async fn stdio_process_logs(&self, mcp_id: &str) -> Vec<StdioLogLine> {
+        self.stdio_logs
+            .lock()
+            .await
+            .get(mcp_id)
+            .map(|buffer| buffer.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Start the managed stdio process for an MCP if it isn't already running
+    pub async fn start_stdio_process(
+        &self,
+        mcp_id: &str,
+        transport: &McpTransport,
+    ) -> McpResult<StdioProcessStatus> {
+        let McpTransport::Stdio {
+            command,
+            args,
+            env,
+            sandbox,
+            ..
+        } = transport
+        else {
+            return Err(McpClientError::ProcessError(
+                "MCP does not use the stdio transport".to_string(),
+            ));
+        };
+
+        let status = self.stdio_process_status(mcp_id).await;
+        if status.running {
+            return Ok(status);
+        }
+        self.init_stdio_process(mcp_id, command, args, env, sandbox.as_ref())
+            .await?;
+        Ok(self.stdio_process_status(mcp_id).await)
+    }
+
+    /// Stop the managed stdio process for an MCP
+    ///
+    /// Same graceful-then-kill sequence as `shutdown`, for one process.
+    /// Returns false if no process was running.
+    pub async fn stop_stdio_process(&self, mcp_id: &str) -> bool {
+        let mut processes = self.stdio_processes.lock().await;
+        let Some(mut process) = processes.remove(mcp_id) else {
+            return false;
+        };
+        drop(processes);
+
+        // Try graceful shutdown by closing stdin
+        drop(process.stdin);
+
+        match tokio::time::timeout(std::time::Duration::from_secs(5), process.child.wait()).await {
+            Ok(Ok(status)) => {
+                tracing::info!("Process {} exited gracefully: {:?}", mcp_id, status);
+            }
+            Ok(Err(e)) => {
+                tracing::error!("Error waiting for process {}: {}", mcp_id, e);
+            }
+            Err(_) => {
+                tracing::warn!("Killing unresponsive process {}", mcp_id);
+                let _ = process.child.kill().await;
+                let _ = process.child.wait().await; // REAP ZOMBIE
+            }
+        }
+        true
+    }
+
     /// Monitor HTTP session count (called periodically)
     ///
     /// Note: Currently logs session count. Full cleanup implementation
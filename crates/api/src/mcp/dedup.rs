@@ -0,0 +1,252 @@
+//! Duplicate request detection for the MCP proxy
+//!
+//! Buggy clients re-send the same tool call dozens of times in a row.
+//! The tracker hashes method+params per API key and counts identical
+//! requests inside a short rolling window; every duplicate is rolled up
+//! into `mcp_duplicate_requests` for the diagnostics endpoint. MCPs can
+//! additionally opt in to dedup-and-replay (`dedup.serve_cached` in
+//! their config): the previous response is then returned to duplicate
+//! tools/call requests instead of hitting the upstream again.
+//!
+//! Like the tool cache, state is in-memory and per instance - detection
+//! is a diagnostic aid, not an exactness guarantee.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::mcp::types::{JsonRpcRequest, JsonRpcResponse};
+
+/// Requests repeated within this window count as duplicates
+const DEDUP_WINDOW: Duration = Duration::from_secs(30);
+
+/// Upper bound on tracked entries across all keys (memory guard)
+const MAX_ENTRIES: usize = 10_000;
+
+/// Responses larger than this are never kept for replay
+const MAX_CACHED_RESPONSE_BYTES: usize = 64 * 1024;
+
+/// What the tracker saw for one incoming request
+pub struct DedupObservation {
+    /// Identical requests already seen in the window (0 = first sight)
+    pub prior_count: u32,
+    /// Hash recorded in the rollup table (hex SHA-256)
+    pub request_hash: String,
+    /// Previous response, when the upstream call was kept for replay
+    pub cached_response: Option<JsonRpcResponse>,
+}
+
+struct DedupEntry {
+    count: u32,
+    expires_at: Instant,
+    cached_response: Option<JsonRpcResponse>,
+}
+
+/// In-memory duplicate request tracker shared across proxy requests
+pub struct RequestDedup {
+    entries: RwLock<HashMap<(Uuid, String), DedupEntry>>,
+}
+
+impl RequestDedup {
+    pub fn new() -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Hash a request's method and params
+    ///
+    /// Params are keyed by their serialized JSON: byte-identical resends
+    /// (the buggy-client case) collide, reordered-key equivalents miss.
+    pub fn hash_request(request: &JsonRpcRequest) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(request.method.as_bytes());
+        hasher.update([0]);
+        if let Some(ref params) = request.params {
+            hasher.update(serde_json::to_string(params).unwrap_or_default().as_bytes());
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    /// Record one incoming request and report what preceded it
+    pub async fn observe(&self, api_key_id: Uuid, request: &JsonRpcRequest) -> DedupObservation {
+        let request_hash = Self::hash_request(request);
+        let now = Instant::now();
+
+        let mut entries = self.entries.write().await;
+        if entries.len() >= MAX_ENTRIES
+            && !entries.contains_key(&(api_key_id, request_hash.clone()))
+        {
+            entries.retain(|_, e| e.expires_at > now);
+        }
+
+        let entry = entries
+            .entry((api_key_id, request_hash.clone()))
+            .or_insert(DedupEntry {
+                count: 0,
+                expires_at: now + DEDUP_WINDOW,
+                cached_response: None,
+            });
+        if entry.expires_at <= now {
+            // Window lapsed: restart counting from this request
+            entry.count = 0;
+            entry.cached_response = None;
+        }
+        let prior_count = entry.count;
+        entry.count += 1;
+        entry.expires_at = now + DEDUP_WINDOW;
+
+        DedupObservation {
+            prior_count,
+            request_hash,
+            cached_response: entry.cached_response.clone(),
+        }
+    }
+
+    /// Keep a successful response for replay to later duplicates
+    ///
+    /// No-op when the window entry is gone or the response is too large.
+    pub async fn store_response(
+        &self,
+        api_key_id: Uuid,
+        request_hash: &str,
+        response: &JsonRpcResponse,
+    ) {
+        if response.error.is_some() {
+            return;
+        }
+        let size = serde_json::to_vec(response).map(|v| v.len()).unwrap_or(0);
+        if size == 0 || size > MAX_CACHED_RESPONSE_BYTES {
+            return;
+        }
+
+        let mut entries = self.entries.write().await;
+        if let Some(entry) = entries.get_mut(&(api_key_id, request_hash.to_string())) {
+            if entry.expires_at > Instant::now() {
+                entry.cached_response = Some(response.clone());
+            }
+        }
+    }
+
+    /// Remove expired entries (called from the periodic cleanup task)
+    pub async fn purge_expired(&self) {
+        let now = Instant::now();
+        let mut entries = self.entries.write().await;
+        entries.retain(|_, e| e.expires_at > now);
+    }
+}
+
+impl Default for RequestDedup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether an MCP opted in to replaying cached responses to duplicates
+/// (`"dedup": {"serve_cached": true}` in its config)
+pub fn serve_cached_enabled(config: &Value) -> bool {
+    config
+        .get("dedup")
+        .and_then(|d| d.get("serve_cached"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request(method: &str, params: Value) -> JsonRpcRequest {
+        JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: None,
+            method: method.to_string(),
+            params: Some(params),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_observe_counts_identical_requests() {
+        let dedup = RequestDedup::new();
+        let key = Uuid::new_v4();
+        let req = request(
+            "tools/call",
+            json!({"name": "github:search", "arguments": {}}),
+        );
+
+        assert_eq!(dedup.observe(key, &req).await.prior_count, 0);
+        assert_eq!(dedup.observe(key, &req).await.prior_count, 1);
+        assert_eq!(dedup.observe(key, &req).await.prior_count, 2);
+
+        // Different params are a different request
+        let other = request(
+            "tools/call",
+            json!({"name": "github:search", "arguments": {"q": 1}}),
+        );
+        assert_eq!(dedup.observe(key, &other).await.prior_count, 0);
+
+        // Same request from another key is tracked separately
+        assert_eq!(dedup.observe(Uuid::new_v4(), &req).await.prior_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_store_response_replays_to_duplicates() {
+        let dedup = RequestDedup::new();
+        let key = Uuid::new_v4();
+        let req = request("tools/call", json!({"name": "github:search"}));
+
+        let observation = dedup.observe(key, &req).await;
+        assert!(observation.cached_response.is_none());
+
+        let response = JsonRpcResponse::success(None, json!({"content": []}));
+        dedup
+            .store_response(key, &observation.request_hash, &response)
+            .await;
+
+        let observation = dedup.observe(key, &req).await;
+        assert_eq!(observation.prior_count, 1);
+        assert!(observation.cached_response.is_some());
+
+        // Error responses are never kept
+        let error = JsonRpcResponse::error(
+            None,
+            crate::mcp::types::JsonRpcError::internal_error("upstream down"),
+        );
+        dedup
+            .store_response(key, &observation.request_hash, &error)
+            .await;
+        assert!(dedup.observe(key, &req).await.cached_response.is_some());
+    }
+
+    #[test]
+    fn test_serve_cached_enabled() {
+        assert!(!serve_cached_enabled(&json!({})));
+        assert!(!serve_cached_enabled(&json!({"dedup": {}})));
+        assert!(!serve_cached_enabled(
+            &json!({"dedup": {"serve_cached": false}})
+        ));
+        assert!(serve_cached_enabled(
+            &json!({"dedup": {"serve_cached": true}})
+        ));
+    }
+
+    #[test]
+    fn test_hash_request_is_stable() {
+        let a = request("tools/call", json!({"name": "x"}));
+        let b = request("tools/call", json!({"name": "x"}));
+        assert_eq!(
+            RequestDedup::hash_request(&a),
+            RequestDedup::hash_request(&b)
+        );
+        let c = request("tools/list", json!({"name": "x"}));
+        assert_ne!(
+            RequestDedup::hash_request(&a),
+            RequestDedup::hash_request(&c)
+        );
+    }
+}
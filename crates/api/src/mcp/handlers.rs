@@ -8,13 +8,19 @@ use std::time::Duration;
 use futures::stream::{FuturesUnordered, StreamExt};
 use serde_json::Value;
 use sqlx::PgPool;
+use tokio::sync::{mpsc, oneshot};
 use uuid::Uuid;
 
 use super::client::McpClient;
 use super::router::{McpMethod, McpRouter};
+use super::streaming::McpStreamEvent;
 use super::types::*;
 use crate::routes::mcp_proxy::McpFilter;
 
+/// Buffered partial-result events before a slow SSE consumer applies
+/// backpressure to the fan-out
+const STREAMING_AGGREGATION_BUFFER: usize = 32;
+
 /// MCP Proxy Handler
 ///
 /// Orchestrates handling of MCP requests by aggregating results from
@@ -24,6 +30,12 @@ pub struct McpProxyHandler {
     router: McpRouter,
     pool: PgPool,
     config: Arc<crate::config::Config>,
+    /// Org moderation rules applied to tool call arguments and results
+    /// (None in contexts without moderation, e.g. health checks)
+    moderation: Option<Arc<super::moderation::ModerationEngine>>,
+    /// Shared tools/call result cache (None in contexts without caching,
+    /// e.g. health checks)
+    tool_cache: Option<Arc<super::tool_cache::ToolCallCache>>,
 }
 
 /// Upstream MCP configuration loaded from database
@@ -35,6 +47,25 @@ pub struct UpstreamMcp {
     pub is_active: bool,
     pub request_timeout_ms: i32,
     pub partial_timeout_ms: Option<i32>,
+    /// Result cache settings, if this MCP opted in via config
+    pub cache: Option<super::tool_cache::ToolCacheConfig>,
+    /// Latest background health check verdict (`healthy`, `unhealthy`,
+    /// or `unknown` when never checked)
+    pub health_status: String,
+}
+
+/// Per-org health filtering mode for aggregated listings, read from the
+/// organization's `mcp_health_filtering` setting
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HealthFilterMode {
+    /// Advertise everything regardless of health (default)
+    Off,
+    /// Skip unhealthy upstreams entirely; their tools disappear from
+    /// listings and no request is sent to them
+    Omit,
+    /// Still query unhealthy upstreams but flag them in `_meta` so
+    /// clients can mark their tools as degraded
+    Degraded,
 }
 
 /// Response wrapper that includes MCP tracking metadata for analytics
@@ -96,6 +127,14 @@ impl McpTrackedResponse {
     }
 }
 
+/// A streaming fan-out in flight: per-MCP `partial` events followed by
+/// the aggregated `result` event, plus a completion channel carrying the
+/// tracked response for usage logging once every upstream has answered
+pub struct StreamingAggregation {
+    pub events: mpsc::Receiver<McpStreamEvent>,
+    pub completion: oneshot::Receiver<McpTrackedResponse>,
+}
+
 impl McpProxyHandler {
     /// Create a new proxy handler with shared MCP client
     pub fn new(
@@ -108,9 +147,23 @@ impl McpProxyHandler {
             router: McpRouter::new(),
             pool,
             config,
+            moderation: None,
+            tool_cache: None,
         }
     }
 
+    /// Enable content moderation for this handler (proxy path)
+    pub fn with_moderation(mut self, moderation: Arc<super::moderation::ModerationEngine>) -> Self {
+        self.moderation = Some(moderation);
+        self
+    }
+
+    /// Enable tools/call result caching for this handler (proxy path)
+    pub fn with_tool_cache(mut self, tool_cache: Arc<super::tool_cache::ToolCallCache>) -> Self {
+        self.tool_cache = Some(tool_cache);
+        self
+    }
+
     /// Helper to safely create a success response with JSON serialization error handling
     fn success_response<T: serde::Serialize>(id: Option<JsonRpcId>, value: &T) -> JsonRpcResponse {
         match serde_json::to_value(value) {
@@ -149,11 +202,13 @@ impl McpProxyHandler {
             status: String,
             request_timeout_ms: i32,
             partial_timeout_ms: Option<i32>,
+            health_status: String,
         }
 
         let rows: Vec<McpRow> = sqlx::query_as(
             r#"
-            SELECT id, name, mcp_type, config, status, request_timeout_ms, partial_timeout_ms
+            SELECT id, name, mcp_type, config, status, request_timeout_ms, partial_timeout_ms,
+                   COALESCE(health_status, 'unknown') as health_status
             FROM mcp_instances
             WHERE org_id = $1 AND status = 'active'
             ORDER BY name
@@ -163,11 +218,33 @@ impl McpProxyHandler {
         .fetch_all(&self.pool)
         .await?;
 
-        let mcps: Vec<UpstreamMcp> = rows
+        let mut oauth_mcp_ids: Vec<Uuid> = Vec::new();
+        // Cookie-auth MCPs keep their config around for the login details
+        let mut cookie_mcps: Vec<(Uuid, Value)> = Vec::new();
+        let mut circuit_overrides: Vec<(
+            Uuid,
+            Option<super::circuit_breaker::CircuitBreakerConfig>,
+        )> = Vec::new();
+        let mut retry_overrides: Vec<(Uuid, Option<super::retry_policy::RetryPolicy>)> = Vec::new();
+        let mut mcps: Vec<UpstreamMcp> = rows
             .into_iter()
             .filter_map(|row| {
                 let config = row.config;
-                let transport = self.parse_transport(&row.mcp_type, config)?;
+                let cache = super::tool_cache::ToolCacheConfig::from_config(&config);
+                match config.get("auth_type").and_then(|v| v.as_str()) {
+                    Some("oauth") => oauth_mcp_ids.push(row.id),
+                    Some("cookie") => cookie_mcps.push((row.id, config.clone())),
+                    _ => {}
+                }
+                circuit_overrides.push((
+                    row.id,
+                    super::circuit_breaker::CircuitBreakerConfig::from_config(&config),
+                ));
+                retry_overrides.push((
+                    row.id,
+                    super::retry_policy::RetryPolicy::from_config(&config),
+                ));
+                let transport = self.parse_transport(&row.mcp_type, config, org_id)?;
                 Some(UpstreamMcp {
                     id: row.id,
                     name: row.name,
@@ -175,6 +252,8 @@ impl McpProxyHandler {
                     is_active: row.status == "active",
                     request_timeout_ms: row.request_timeout_ms,
                     partial_timeout_ms: row.partial_timeout_ms,
+                    cache,
+                    health_status: row.health_status,
                 })
             })
             // Apply MCP access filtering based on API key settings
@@ -201,19 +280,144 @@ impl McpProxyHandler {
             })
             .collect();
 
+        // Keep the breaker manager in sync with per-MCP circuit overrides
+        // (no-op when nothing changed, so this is cheap on the hot path)
+        for (mcp_id, circuit) in circuit_overrides {
+            self.client.apply_circuit_override(mcp_id, circuit).await;
+        }
+
+        // Same sync for per-MCP timeout/retry policies
+        for (mcp_id, policy) in retry_overrides {
+            self.client.apply_retry_policy(mcp_id, policy).await;
+        }
+
+        // Apply the org-wide response size ceiling: a per-MCP
+        // max_response_bytes can tighten it but never exceed it
+        if let Some(org_cap) = self.org_response_cap(org_id).await {
+            for mcp in &mut mcps {
+                mcp.transport.clamp_response_limit(org_cap);
+            }
+        }
+
+        // Inject vault-managed bearer tokens for OAuth MCPs, refreshing any
+        // that are close to expiry. Failures leave the MCP unauthenticated
+        // rather than dropping it, so the upstream error surfaces normally.
+        if !oauth_mcp_ids.is_empty() {
+            match crate::auth::totp::parse_encryption_key(&self.config.totp_encryption_key) {
+                Ok(key) => {
+                    for mcp in mcps.iter_mut().filter(|m| oauth_mcp_ids.contains(&m.id)) {
+                        match super::oauth::get_valid_access_token(&self.pool, &key, org_id, mcp.id)
+                            .await
+                        {
+                            Ok(Some(token)) => {
+                                if let McpTransport::Http { auth, .. }
+                                | McpTransport::Sse { auth, .. } = &mut mcp.transport
+                                {
+                                    *auth = McpAuth::Bearer { token };
+                                }
+                            }
+                            Ok(None) => {
+                                tracing::warn!(
+                                    mcp_id = %mcp.id,
+                                    "MCP configured for OAuth but not connected"
+                                );
+                            }
+                            Err(e) => {
+                                tracing::warn!(
+                                    mcp_id = %mcp.id,
+                                    "Failed to obtain OAuth access token: {}", e
+                                );
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("TOTP_ENCRYPTION_KEY not usable for OAuth vault: {}", e);
+                }
+            }
+        }
+
+        // Inject jar-managed session cookies for cookie-auth MCPs, logging
+        // in to the upstream first when the jar is missing or expired. As
+        // with OAuth, failures leave the MCP unauthenticated so the
+        // upstream error surfaces normally.
+        if !cookie_mcps.is_empty() {
+            match crate::auth::totp::parse_encryption_key(&self.config.totp_encryption_key) {
+                Ok(key) => {
+                    for (mcp_id, config) in &cookie_mcps {
+                        let Some(mcp) = mcps.iter_mut().find(|m| m.id == *mcp_id) else {
+                            continue;
+                        };
+                        match super::session_auth::get_valid_cookie_header(
+                            &self.pool, &key, org_id, *mcp_id, config,
+                        )
+                        .await
+                        {
+                            Ok(cookie) => {
+                                if let McpTransport::Http { auth, .. }
+                                | McpTransport::Sse { auth, .. } = &mut mcp.transport
+                                {
+                                    *auth = McpAuth::Cookie { cookie };
+                                }
+                            }
+                            Err(e) => {
+                                tracing::warn!(
+                                    mcp_id = %mcp_id,
+                                    "Failed to obtain session cookies: {}", e
+                                );
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("TOTP_ENCRYPTION_KEY not usable for cookie jar: {}", e);
+                }
+            }
+        }
+
         Ok(mcps)
     }
 
     /// Parse transport configuration from database
-    fn parse_transport(&self, mcp_type: &str, config: Value) -> Option<McpTransport> {
+    fn parse_transport(&self, mcp_type: &str, config: Value, org_id: Uuid) -> Option<McpTransport> {
         let endpoint_url = config.get("endpoint_url")?.as_str()?.to_string();
 
         // Parse authentication
         let auth = self.parse_auth(&config);
 
+        // Optional SSH tunnel block; the owning org is injected here (not
+        // taken from config) so key lookups can't cross tenants
+        let tunnel = config.get("ssh_tunnel").and_then(|v| {
+            let mut t: crate::mcp::ssh_tunnel::SshTunnelConfig =
+                serde_json::from_value(v.clone()).ok()?;
+            t.org_id = Some(org_id);
+            Some(t)
+        });
+
+        // Optional per-MCP outbound proxy
+        let proxy_url = config
+            .get("proxy_url")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+
+        // Optional per-MCP cap on upstream response size
+        let max_response_bytes = config.get("max_response_bytes").and_then(|v| v.as_u64());
+
         match mcp_type {
-            "http" => Some(McpTransport::Http { endpoint_url, auth }),
-            "sse" | "websocket" => Some(McpTransport::Sse { endpoint_url, auth }),
+            "http" => Some(McpTransport::Http {
+                endpoint_url,
+                auth,
+                tunnel,
+                proxy_url,
+                max_response_bytes,
+            }),
+            "sse" | "websocket" => Some(McpTransport::Sse {
+                endpoint_url,
+                auth,
+                tunnel,
+                proxy_url,
+                max_response_bytes,
+            }),
             "stdio" => {
                 let command = config.get("command")?.as_str()?.to_string();
                 let args = config
@@ -234,11 +438,26 @@ impl McpProxyHandler {
                             .collect()
                     })
                     .unwrap_or_default();
-                Some(McpTransport::Stdio { command, args, env })
+                let sandbox = config
+                    .get("sandbox")
+                    .and_then(|s| serde_json::from_value(s.clone()).ok());
+                Some(McpTransport::Stdio {
+                    command,
+                    args,
+                    env,
+                    max_response_bytes,
+                    sandbox,
+                })
             }
             _ => {
                 // Default to HTTP if type is unknown but we have an endpoint
-                Some(McpTransport::Http { endpoint_url, auth })
+                Some(McpTransport::Http {
+                    endpoint_url,
+                    auth,
+                    tunnel,
+                    proxy_url,
+                    max_response_bytes,
+                })
             }
         }
     }
@@ -288,6 +507,40 @@ impl McpProxyHandler {
                     .to_string();
                 McpAuth::Basic { username, password }
             }
+            // Templated headers with credential slots, or a provider preset
+            // (see crate::mcp::auth_template)
+            "headers" => match super::auth_template::render_auth_headers(config) {
+                Ok(headers) => McpAuth::Headers { headers },
+                Err(e) => {
+                    // Render errors only name slots/headers, never values
+                    tracing::warn!(error = %e, "Invalid auth header template - connecting unauthenticated");
+                    McpAuth::None
+                }
+            },
+            // Signed or static query parameters appended to every request
+            // URL (values live in config alongside api_key and friends)
+            "query" => {
+                let params: Vec<(String, String)> = config
+                    .get("query_params")
+                    .and_then(|v| v.as_object())
+                    .map(|obj| {
+                        obj.iter()
+                            .filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_string())))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                if params.is_empty() {
+                    McpAuth::None
+                } else {
+                    McpAuth::Query { params }
+                }
+            }
+            // Jar-managed: a Cookie header is injected after load from
+            // mcp_cookie_jars (see load_mcps_filtered)
+            "cookie" => McpAuth::None,
+            // Vault-managed: a bearer token is injected after load from
+            // mcp_oauth_credentials (see load_mcps_filtered)
+            "oauth" => McpAuth::None,
             _ => McpAuth::None,
         }
     }
@@ -352,6 +605,26 @@ impl McpProxyHandler {
                 self.handle_prompts_get_filtered(org_id, request.id, request.params, &filter)
                     .await
             }
+            McpMethod::ResourcesSubscribe => {
+                self.handle_resources_subscription_filtered(
+                    org_id,
+                    request.id,
+                    request.params,
+                    &filter,
+                    true,
+                )
+                .await
+            }
+            McpMethod::ResourcesUnsubscribe => {
+                self.handle_resources_subscription_filtered(
+                    org_id,
+                    request.id,
+                    request.params,
+                    &filter,
+                    false,
+                )
+                .await
+            }
             _ => McpTrackedResponse::without_mcps(JsonRpcResponse::error(
                 request.id,
                 JsonRpcError::method_not_found(&request.method),
@@ -367,7 +640,10 @@ impl McpProxyHandler {
             protocol_version: "2024-11-05".to_string(),
             capabilities: Capabilities {
                 tools: Some(ToolsCapability::default()),
-                resources: Some(ResourcesCapability::default()),
+                resources: Some(ResourcesCapability {
+                    subscribe: true,
+                    ..Default::default()
+                }),
                 prompts: Some(PromptsCapability::default()),
                 logging: Some(LoggingCapability::default()),
                 ..Default::default()
@@ -384,6 +660,117 @@ impl McpProxyHandler {
         McpTrackedResponse::without_mcps(Self::success_response(id, &result))
     }
 
+    /// Read the org-wide cap on upstream response size from the
+    /// organization's `max_response_bytes` setting
+    ///
+    /// Any lookup problem (missing org, non-numeric value, database error)
+    /// degrades to None - the per-MCP caps and the client default still
+    /// apply.
+    async fn org_response_cap(&self, org_id: Uuid) -> Option<u64> {
+        let setting: Result<Option<Option<String>>, sqlx::Error> = sqlx::query_scalar(
+            "SELECT settings->>'max_response_bytes' FROM organizations WHERE id = $1",
+        )
+        .bind(org_id)
+        .fetch_optional(&self.pool)
+        .await;
+
+        match setting {
+            Ok(value) => {
+                let value = value.flatten()?;
+                match value.parse::<u64>() {
+                    Ok(cap) if cap > 0 => Some(cap),
+                    _ => {
+                        tracing::warn!(
+                            org_id = %org_id,
+                            value = %value,
+                            "Invalid max_response_bytes setting, ignoring"
+                        );
+                        None
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::warn!(org_id = %org_id, error = %e, "Failed to read response size setting");
+                None
+            }
+        }
+    }
+
+    /// Read the org's health filtering mode for aggregated listings
+    ///
+    /// Any lookup problem (missing org, bad value, database error)
+    /// degrades to `Off` - a settings hiccup must not break listings.
+    async fn health_filter_mode(&self, org_id: Uuid) -> HealthFilterMode {
+        let setting: Result<Option<Option<String>>, sqlx::Error> = sqlx::query_scalar(
+            "SELECT settings->>'mcp_health_filtering' FROM organizations WHERE id = $1",
+        )
+        .bind(org_id)
+        .fetch_optional(&self.pool)
+        .await;
+
+        match setting {
+            Ok(value) => match value.flatten().as_deref() {
+                Some("omit") => HealthFilterMode::Omit,
+                Some("degraded") => HealthFilterMode::Degraded,
+                Some("off") | None => HealthFilterMode::Off,
+                Some(other) => {
+                    tracing::warn!(
+                        org_id = %org_id,
+                        value = %other,
+                        "Unknown mcp_health_filtering setting, treating as off"
+                    );
+                    HealthFilterMode::Off
+                }
+            },
+            Err(e) => {
+                tracing::warn!(org_id = %org_id, error = %e, "Failed to read health filtering setting");
+                HealthFilterMode::Off
+            }
+        }
+    }
+
+    /// Split loaded MCPs by health according to the org's filtering mode
+    ///
+    /// Returns the MCPs to query plus the `_meta.unavailable_mcps`
+    /// entries; in `Omit` mode unhealthy upstreams are removed from the
+    /// query set, in `Degraded` mode they stay but are flagged.
+    fn partition_by_health(
+        mcps: Vec<UpstreamMcp>,
+        mode: HealthFilterMode,
+    ) -> (Vec<UpstreamMcp>, Vec<UnavailableMcp>) {
+        if mode == HealthFilterMode::Off {
+            return (mcps, vec![]);
+        }
+
+        let mut unavailable = Vec::new();
+        let queried = mcps
+            .into_iter()
+            .filter(|mcp| {
+                if mcp.health_status != "unhealthy" {
+                    return true;
+                }
+                match mode {
+                    HealthFilterMode::Omit => {
+                        unavailable.push(UnavailableMcp {
+                            mcp_name: mcp.name.clone(),
+                            status: "down".to_string(),
+                        });
+                        false
+                    }
+                    _ => {
+                        unavailable.push(UnavailableMcp {
+                            mcp_name: mcp.name.clone(),
+                            status: "degraded".to_string(),
+                        });
+                        true
+                    }
+                }
+            })
+            .collect();
+
+        (queried, unavailable)
+    }
+
     /// Handle tools/list - aggregate tools from all MCPs
     #[allow(dead_code)] // Reserved for direct MCP protocol use
     async fn handle_tools_list(&self, org_id: Uuid, id: Option<JsonRpcId>) -> McpTrackedResponse {
@@ -419,15 +806,24 @@ impl McpProxyHandler {
             }
         };
 
-        // Capture MCP IDs for analytics tracking (before any processing)
+        // Health-aware filtering: down upstreams are omitted or flagged
+        // per the org's mcp_health_filtering setting
+        let health_mode = self.health_filter_mode(org_id).await;
+        let (mcps, unavailable_mcps) = Self::partition_by_health(mcps, health_mode);
+        let meta = (!unavailable_mcps.is_empty()).then_some(AggregatedMeta { unavailable_mcps });
+
+        // Capture MCP IDs for analytics tracking (before any processing);
+        // omitted upstreams were never queried, so they don't count
         let accessed_mcp_ids: Vec<Uuid> = mcps.iter().map(|m| m.id).collect();
 
         if mcps.is_empty() {
-            // Return empty list if no MCPs configured - no MCPs to track
+            // Return empty list if no MCPs configured (or all are down
+            // and omitted) - no MCPs to track
             let result = AggregatedToolsListResult {
                 tools: vec![],
                 errors: vec![],
                 next_cursor: None,
+                meta,
             };
             return McpTrackedResponse::with_mcps(
                 Self::success_response(id, &result),
@@ -520,6 +916,7 @@ impl McpProxyHandler {
             tools: all_tools,
             errors,
             next_cursor: None,
+            meta,
         };
 
         // Return with all MCP IDs that were queried (for per-MCP usage tracking)
@@ -622,6 +1019,62 @@ impl McpProxyHandler {
         // Capture MCP ID for analytics tracking (before the call)
         let mcp_id = mcp.id;
 
+        // Inspect outgoing arguments against org moderation rules
+        let mut arguments = params.arguments;
+        if let Some(moderation) = &self.moderation {
+            use super::moderation::{Direction, ModerationOutcome};
+            match moderation
+                .inspect(org_id, &params.name, Direction::Request, &arguments)
+                .await
+            {
+                ModerationOutcome::Allowed => {}
+                ModerationOutcome::Redacted(redacted) => arguments = redacted,
+                ModerationOutcome::Denied { rule_name } => {
+                    return McpTrackedResponse::with_single_mcp(
+                        JsonRpcResponse::error(
+                            id,
+                            JsonRpcError::moderation_blocked(format!(
+                                "Request blocked by moderation rule '{}'",
+                                rule_name
+                            )),
+                        ),
+                        mcp_id,
+                    );
+                }
+            }
+        }
+
+        // Serve repeated identical calls from the result cache when the MCP
+        // opted in (cache lookups happen after request moderation so denied
+        // calls never hit the cache)
+        let cache_config = mcp
+            .cache
+            .as_ref()
+            .filter(|_| self.tool_cache.is_some())
+            .cloned();
+        let tool_cacheable = cache_config
+            .as_ref()
+            .map(|c| c.is_cacheable(&parsed.tool_name))
+            .unwrap_or(false);
+        if tool_cacheable {
+            if let Some(cache) = &self.tool_cache {
+                if let Some(cached) = cache
+                    .get(org_id, mcp_id, &parsed.tool_name, &arguments)
+                    .await
+                {
+                    tracing::debug!(
+                        mcp_id = %mcp_id,
+                        tool = %parsed.tool_name,
+                        "Serving tools/call result from cache"
+                    );
+                    return McpTrackedResponse::with_single_mcp(
+                        JsonRpcResponse::success(id, cached),
+                        mcp_id,
+                    );
+                }
+            }
+        }
+
         // Call the tool on the upstream MCP
         let result = self
             .client
@@ -629,12 +1082,65 @@ impl McpProxyHandler {
                 &mcp.transport,
                 &mcp.id.to_string(),
                 &parsed.tool_name,
-                params.arguments,
+                arguments.clone(),
             )
             .await;
 
         match result {
             Ok(tool_result) => {
+                // Inspect the result before it reaches the client
+                let tool_result = if let Some(moderation) = &self.moderation {
+                    match self
+                        .moderate_tool_result(moderation, org_id, &params.name, tool_result)
+                        .await
+                    {
+                        Ok(result) => result,
+                        Err(rule_name) => {
+                            return McpTrackedResponse::with_single_mcp(
+                                JsonRpcResponse::error(
+                                    id,
+                                    JsonRpcError::moderation_blocked(format!(
+                                        "Response blocked by moderation rule '{}'",
+                                        rule_name
+                                    )),
+                                ),
+                                mcp_id,
+                            );
+                        }
+                    }
+                } else {
+                    tool_result
+                };
+
+                // Cache the (moderated) result, or flush the MCP's entries
+                // when a non-cacheable tool - presumed to be a write - ran
+                if let (Some(cache), Some(config)) = (&self.tool_cache, &cache_config) {
+                    if tool_cacheable {
+                        if let Ok(value) = serde_json::to_value(&tool_result) {
+                            cache
+                                .insert(
+                                    org_id,
+                                    mcp_id,
+                                    &parsed.tool_name,
+                                    &arguments,
+                                    value,
+                                    config.ttl,
+                                )
+                                .await;
+                        }
+                    } else if config.invalidate_on_write {
+                        let removed = cache.invalidate_mcp(org_id, mcp_id).await;
+                        if removed > 0 {
+                            tracing::debug!(
+                                mcp_id = %mcp_id,
+                                tool = %parsed.tool_name,
+                                removed,
+                                "Invalidated cached tool results after write"
+                            );
+                        }
+                    }
+                }
+
                 // Success - track the single MCP that was called
                 McpTrackedResponse::with_single_mcp(
                     Self::success_response(id, &tool_result),
@@ -657,6 +1163,47 @@ impl McpProxyHandler {
         }
     }
 
+    /// Run a tool result through response moderation
+    ///
+    /// Returns the (possibly redacted) result, or the matching rule name
+    /// when a deny rule fired.
+    async fn moderate_tool_result(
+        &self,
+        moderation: &super::moderation::ModerationEngine,
+        org_id: Uuid,
+        tool_name: &str,
+        tool_result: ToolCallResult,
+    ) -> Result<ToolCallResult, String> {
+        use super::moderation::{Direction, ModerationOutcome};
+
+        let payload = match serde_json::to_value(&tool_result) {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::error!("Failed to serialize tool result for moderation: {}", e);
+                return Ok(tool_result);
+            }
+        };
+
+        match moderation
+            .inspect(org_id, tool_name, Direction::Response, &payload)
+            .await
+        {
+            ModerationOutcome::Allowed => Ok(tool_result),
+            ModerationOutcome::Denied { rule_name } => Err(rule_name),
+            ModerationOutcome::Redacted(redacted) => {
+                match serde_json::from_value(redacted) {
+                    Ok(result) => Ok(result),
+                    Err(e) => {
+                        // Redaction only rewrites string values, so this
+                        // shouldn't happen; fail closed if it does
+                        tracing::error!("Failed to deserialize redacted tool result: {}", e);
+                        Err("redaction".to_string())
+                    }
+                }
+            }
+        }
+    }
+
     /// Handle resources/list - aggregate resources from all MCPs
     #[allow(dead_code)] // Reserved for direct MCP protocol use
     async fn handle_resources_list(
@@ -917,6 +1464,123 @@ impl McpProxyHandler {
         }
     }
 
+    /// Handle resources/subscribe and resources/unsubscribe with MCP filtering
+    ///
+    /// Routes the subscription change to a specific MCP using the same
+    /// `plexmcp://mcp_name/uri` namespacing as resources/read. Update
+    /// notifications are emitted on the upstream's session; delivery to the
+    /// client depends on it holding a streaming session to the proxy.
+    async fn handle_resources_subscription_filtered(
+        &self,
+        org_id: Uuid,
+        id: Option<JsonRpcId>,
+        params: Option<Value>,
+        filter: &McpFilter,
+        subscribe: bool,
+    ) -> McpTrackedResponse {
+        let params: ResourceReadParams = match params {
+            Some(p) => match serde_json::from_value(p) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    // Error before MCP selection
+                    return McpTrackedResponse::without_mcps(JsonRpcResponse::error(
+                        id,
+                        JsonRpcError::invalid_params(format!("Invalid params: {}", e)),
+                    ));
+                }
+            },
+            None => {
+                // Error before MCP selection
+                return McpTrackedResponse::without_mcps(JsonRpcResponse::error(
+                    id,
+                    JsonRpcError::invalid_params("Missing params"),
+                ));
+            }
+        };
+
+        // Parse the prefixed URI
+        let parsed = match self.router.parse_resource_uri(&params.uri) {
+            Some(p) => p,
+            None => {
+                // Error before MCP selection
+                return McpTrackedResponse::without_mcps(JsonRpcResponse::error(
+                    id,
+                    JsonRpcError::invalid_params(format!(
+                        "Invalid resource URI format. Expected 'plexmcp://mcp_name/uri', got: {}",
+                        params.uri
+                    )),
+                ));
+            }
+        };
+
+        // Load MCPs and find the target (with filtering)
+        let mcps = match self.load_mcps_filtered(org_id, Some(filter)).await {
+            Ok(m) => m,
+            Err(e) => {
+                // Error before MCP selection
+                return McpTrackedResponse::without_mcps(JsonRpcResponse::error(
+                    id,
+                    JsonRpcError::internal_error(format!("Failed to load MCPs: {}", e)),
+                ));
+            }
+        };
+
+        let target_mcp = mcps.iter().find(|m| m.name == parsed.mcp_name);
+        let mcp = match target_mcp {
+            Some(m) => m,
+            None => {
+                // MCP not found - no MCP to track
+                return McpTrackedResponse::without_mcps(JsonRpcResponse::error(
+                    id,
+                    JsonRpcError::invalid_params(format!(
+                        "MCP not found or access denied: {}",
+                        parsed.mcp_name
+                    )),
+                ));
+            }
+        };
+
+        // Capture MCP ID for analytics tracking (before the call)
+        let mcp_id = mcp.id;
+
+        // Forward the subscription change to the upstream MCP
+        let result = if subscribe {
+            self.client
+                .subscribe_resource(&mcp.transport, &mcp.id.to_string(), &parsed.original_uri)
+                .await
+        } else {
+            self.client
+                .unsubscribe_resource(&mcp.transport, &mcp.id.to_string(), &parsed.original_uri)
+                .await
+        };
+
+        match result {
+            Ok(sub_result) => {
+                // Success - track the single MCP that was accessed
+                McpTrackedResponse::with_single_mcp(Self::success_response(id, &sub_result), mcp_id)
+            }
+            Err(e) => {
+                // Error during the call - still track the MCP (it was accessed)
+                McpTrackedResponse::with_single_mcp(
+                    JsonRpcResponse::error(
+                        id,
+                        JsonRpcError::internal_error(format!(
+                            "Resource {} failed on {}: {}",
+                            if subscribe {
+                                "subscribe"
+                            } else {
+                                "unsubscribe"
+                            },
+                            parsed.mcp_name,
+                            e
+                        )),
+                    ),
+                    mcp_id,
+                )
+            }
+        }
+    }
+
     /// Handle prompts/list - aggregate prompts from all MCPs
     #[allow(dead_code)] // Reserved for direct MCP protocol use
     async fn handle_prompts_list(&self, org_id: Uuid, id: Option<JsonRpcId>) -> McpTrackedResponse {
@@ -1172,6 +1836,189 @@ impl McpProxyHandler {
             }
         }
     }
+
+    /// Handle an aggregation method in streaming mode
+    ///
+    /// Fans the request out to all accessible MCPs and emits each
+    /// upstream's result as a `partial` SSE event the moment it arrives,
+    /// so slow MCPs don't block fast ones. A `result` event with the
+    /// fully aggregated response closes the stream, and the completion
+    /// channel delivers the same tracked response for usage logging.
+    ///
+    /// Returns None for methods that don't aggregate (tools/call etc.),
+    /// which should take the regular buffered path.
+    pub async fn handle_aggregation_streaming(
+        &self,
+        org_id: Uuid,
+        request: JsonRpcRequest,
+        filter: &McpFilter,
+    ) -> Option<StreamingAggregation> {
+        let method = McpRouter::get_method_type(&request.method);
+        if !method.aggregates_results() {
+            return None;
+        }
+
+        // The JSON key the aggregated items live under, matching the
+        // buffered Aggregated*ListResult shapes
+        let items_key = match method {
+            McpMethod::ToolsList => "tools",
+            McpMethod::ResourcesList => "resources",
+            McpMethod::PromptsList => "prompts",
+            _ => unreachable!("aggregates_results() covers exactly these methods"),
+        };
+
+        let (tx, rx) = mpsc::channel(STREAMING_AGGREGATION_BUFFER);
+        let (done_tx, done_rx) = oneshot::channel();
+
+        let mcps = match self.load_mcps_filtered(org_id, Some(filter)).await {
+            Ok(m) => m,
+            Err(e) => {
+                let response = JsonRpcResponse::error(
+                    request.id,
+                    JsonRpcError::internal_error(format!("Failed to load MCPs: {}", e)),
+                );
+                let _ = tx
+                    .send(McpStreamEvent::FinalResult {
+                        response: response.clone(),
+                    })
+                    .await;
+                let _ = done_tx.send(McpTrackedResponse::without_mcps(response));
+                return Some(StreamingAggregation {
+                    events: rx,
+                    completion: done_rx,
+                });
+            }
+        };
+
+        // Health-aware filtering applies to streamed tools/list too, so
+        // buffered and streaming clients see the same tool set
+        let health_mode = if matches!(method, McpMethod::ToolsList) {
+            self.health_filter_mode(org_id).await
+        } else {
+            HealthFilterMode::Off
+        };
+        let (mcps, unavailable_mcps) = Self::partition_by_health(mcps, health_mode);
+
+        let accessed_mcp_ids: Vec<Uuid> = mcps.iter().map(|m| m.id).collect();
+        let client = self.client.clone();
+        let default_timeout_ms = self.config.mcp_partial_timeout_ms;
+
+        tokio::spawn(async move {
+            let router = McpRouter::new();
+            let mut tasks = FuturesUnordered::new();
+
+            for mcp in mcps {
+                let timeout_ms = mcp
+                    .partial_timeout_ms
+                    .map(|t| t as u64)
+                    .unwrap_or(default_timeout_ms);
+
+                let client = client.clone();
+                let router = &router;
+                let method = method.clone();
+
+                tasks.push(async move {
+                    let mcp_id = mcp.id.to_string();
+                    let fetch = async {
+                        // Prefix inside the task so each chunk is already
+                        // namespaced when it reaches the client
+                        match method {
+                            McpMethod::ToolsList => client
+                                .get_tools_with_breaker(mcp.id, &mcp.transport, &mcp_id)
+                                .await
+                                .map(|tools| {
+                                    serde_json::to_value(router.prefix_tools(&mcp.name, tools))
+                                        .unwrap_or_default()
+                                }),
+                            McpMethod::ResourcesList => client
+                                .get_resources_with_breaker(mcp.id, &mcp.transport, &mcp_id)
+                                .await
+                                .map(|resources| {
+                                    serde_json::to_value(
+                                        router.prefix_resources(&mcp.name, resources),
+                                    )
+                                    .unwrap_or_default()
+                                }),
+                            _ => client
+                                .get_prompts_with_breaker(mcp.id, &mcp.transport, &mcp_id)
+                                .await
+                                .map(|prompts| {
+                                    serde_json::to_value(router.prefix_prompts(&mcp.name, prompts))
+                                        .unwrap_or_default()
+                                }),
+                        }
+                    };
+
+                    match tokio::time::timeout(Duration::from_millis(timeout_ms), fetch).await {
+                        Ok(Ok(items)) => Ok((mcp.name, items)),
+                        Ok(Err(e)) => {
+                            tracing::error!(mcp = %mcp.name, error = %e, "MCP error");
+                            Err(McpError {
+                                mcp_name: mcp.name,
+                                error: e.to_string(),
+                            })
+                        }
+                        Err(_) => {
+                            tracing::warn!(mcp = %mcp.name, timeout_ms = timeout_ms, "MCP timeout");
+                            Err(McpError {
+                                mcp_name: mcp.name,
+                                error: format!("Timeout after {}ms", timeout_ms),
+                            })
+                        }
+                    }
+                });
+            }
+
+            let mut all_items = Vec::new();
+            let mut errors = Vec::new();
+
+            while let Some(result) = tasks.next().await {
+                match result {
+                    Ok((mcp_name, items)) => {
+                        if let Value::Array(list) = &items {
+                            all_items.extend(list.iter().cloned());
+                        }
+                        let _ = tx
+                            .send(McpStreamEvent::PartialResult {
+                                source: mcp_name,
+                                data: serde_json::json!({ items_key: items }),
+                            })
+                            .await;
+                    }
+                    Err(e) => {
+                        let _ = tx
+                            .send(McpStreamEvent::PartialResult {
+                                source: e.mcp_name.clone(),
+                                data: serde_json::json!({ "error": e.error }),
+                            })
+                            .await;
+                        errors.push(e);
+                    }
+                }
+            }
+
+            let mut result = serde_json::json!({ items_key: all_items });
+            if !errors.is_empty() {
+                result["errors"] = serde_json::to_value(&errors).unwrap_or_default();
+            }
+            if !unavailable_mcps.is_empty() {
+                result["_meta"] = serde_json::json!({ "unavailable_mcps": unavailable_mcps });
+            }
+
+            let response = JsonRpcResponse::success(request.id, result);
+            let _ = tx
+                .send(McpStreamEvent::FinalResult {
+                    response: response.clone(),
+                })
+                .await;
+            let _ = done_tx.send(McpTrackedResponse::with_mcps(response, accessed_mcp_ids));
+        });
+
+        Some(StreamingAggregation {
+            events: rx,
+            completion: done_rx,
+        })
+    }
 }
 
 #[cfg(test)]
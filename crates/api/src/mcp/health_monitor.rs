@@ -0,0 +1,296 @@
+//! Background health monitoring for upstream MCPs
+//!
+//! The manual `/mcps/:mcp_id/health-check` route only runs when someone
+//! clicks the button, so an MCP that dies overnight stays marked healthy
+//! until the next request fails. This sweep periodically tests every
+//! active MCP that hasn't been checked recently, records the result in
+//! `mcp_test_history` (with a NULL `tested_by`, distinguishing automatic
+//! checks from manual ones), flips `health_status` on the instance, and
+//! feeds transitions into the alerting pipeline so a flapping MCP raises
+//! a security alert once it crosses the configured threshold.
+
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::client::McpClient;
+use super::streaming::StreamingSessionManager;
+use super::tool_catalog::ToolCatalogCache;
+use super::types::Tool;
+use crate::alerting::{AlertService, AlertType};
+
+/// How often the monitor sweep runs
+pub const HEALTH_MONITOR_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Minimum age of the last check before the sweep re-tests an MCP; keeps
+/// the sweep from piling onto MCPs that were just tested manually
+const RECHECK_AFTER: Duration = Duration::from_secs(240);
+
+/// Upper bound on checks per sweep so one sweep can't monopolise upstream
+/// connections on large deployments; the oldest-checked MCPs go first, so
+/// the backlog drains across consecutive sweeps
+const MAX_CHECKS_PER_SWEEP: i64 = 50;
+
+/// Test every active MCP that is due for a check
+pub async fn health_monitor_sweep(
+    pool: &PgPool,
+    client: &McpClient,
+    alerts: &AlertService,
+    streams: &StreamingSessionManager,
+    tool_catalog: &ToolCatalogCache,
+) {
+    #[derive(sqlx::FromRow)]
+    struct MonitorRow {
+        id: Uuid,
+        org_id: Uuid,
+        name: String,
+        mcp_type: String,
+        config: Value,
+        health_status: String,
+        tools_json: Option<Value>,
+    }
+
+    let rows: Vec<MonitorRow> = match sqlx::query_as(
+        r#"
+        SELECT id, org_id, name, mcp_type, config, health_status, tools_json
+        FROM mcp_instances
+        WHERE status = 'active'
+          AND (last_health_check_at IS NULL OR last_health_check_at < NOW() - $1::interval)
+        ORDER BY last_health_check_at ASC NULLS FIRST
+        LIMIT $2
+        "#,
+    )
+    .bind(format!("{} seconds", RECHECK_AFTER.as_secs()))
+    .bind(MAX_CHECKS_PER_SWEEP)
+    .fetch_all(pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            tracing::error!(error = %e, "Health monitor sweep failed to load MCPs");
+            return;
+        }
+    };
+
+    for row in rows {
+        let previous_status = row.health_status.clone();
+        let result = check_mcp(
+            pool,
+            client,
+            streams,
+            tool_catalog,
+            &row.id,
+            row.org_id,
+            &row.name,
+            &row.mcp_type,
+            &row.config,
+            row.tools_json.as_ref(),
+        )
+        .await;
+
+        // A transition in either direction counts as one flap event; the
+        // alert_configurations threshold decides how many transitions in
+        // what window constitute "flapping"
+        if let Some((new_status, evidence)) = result {
+            if new_status != previous_status && previous_status != "unknown" {
+                super::health_webhooks::notify(
+                    pool,
+                    super::health_webhooks::McpWebhookEvent {
+                        event: "mcp.health_changed",
+                        mcp_id: row.id,
+                        from: previous_status.clone(),
+                        to: new_status.clone(),
+                        evidence,
+                    },
+                );
+                plexmcp_shared::org_webhooks::emit(
+                    pool,
+                    row.org_id,
+                    "mcp.status_changed",
+                    serde_json::json!({
+                        "mcp_id": row.id,
+                        "from": previous_status.clone(),
+                        "to": new_status.clone(),
+                    }),
+                )
+                .await;
+                let outcome = alerts
+                    .record_event(
+                        AlertType::McpHealthFlapping,
+                        &row.id.to_string(),
+                        None,
+                        Some(row.org_id),
+                        None,
+                        serde_json::json!({
+                            "mcp_id": row.id,
+                            "from": previous_status,
+                            "to": new_status,
+                        }),
+                    )
+                    .await;
+                if let Err(e) = outcome {
+                    tracing::error!(mcp_id = %row.id, error = %e, "Failed to record MCP flap event");
+                }
+            }
+        }
+    }
+}
+
+/// Run one automatic health check; returns the new health status plus the
+/// probe evidence (for webhook payloads), or None when the check could not
+/// be recorded
+#[allow(clippy::too_many_arguments)]
+async fn check_mcp(
+    pool: &PgPool,
+    client: &McpClient,
+    streams: &StreamingSessionManager,
+    tool_catalog: &ToolCatalogCache,
+    mcp_id: &Uuid,
+    org_id: Uuid,
+    mcp_name: &str,
+    mcp_type: &str,
+    config: &Value,
+    previous_tools_json: Option<&Value>,
+) -> Option<(String, Value)> {
+    let now = OffsetDateTime::now_utc();
+    let start = Instant::now();
+    let mcp_id_str = mcp_id.to_string();
+
+    let transport = crate::routes::mcps::parse_transport(mcp_type, config, org_id);
+
+    let connection_warm = match &transport {
+        Some(t) => client.is_connection_warm(t, &mcp_id_str).await,
+        None => false,
+    };
+
+    // Initialize handshake plus tools/list, mirroring the manual check but
+    // without the resources pass (the sweep only needs up/down + latency)
+    let mut current_tools: Option<Vec<Tool>> = None;
+    let (health_status, latency_ms, tools_count, error) = match transport {
+        None => (
+            "unhealthy",
+            start.elapsed().as_millis() as i32,
+            None,
+            Some("Invalid MCP configuration: missing endpoint_url".to_string()),
+        ),
+        Some(transport) => match client.initialize(&transport, &mcp_id_str).await {
+            Ok(_) => match client.get_tools(&transport, &mcp_id_str).await {
+                Ok(tools) => {
+                    let count = tools.len() as i32;
+                    current_tools = Some(tools);
+                    (
+                        "healthy",
+                        start.elapsed().as_millis() as i32,
+                        Some(count),
+                        None,
+                    )
+                }
+                Err(e) => (
+                    "unhealthy",
+                    start.elapsed().as_millis() as i32,
+                    None,
+                    Some(format!("Failed to list tools: {}", e)),
+                ),
+            },
+            Err(e) => (
+                "unhealthy",
+                start.elapsed().as_millis() as i32,
+                None,
+                Some(e.to_string()),
+            ),
+        },
+    };
+
+    // Failed checks keep the previous tools snapshot: an unreachable MCP
+    // hasn't changed its tool list, it just couldn't be asked
+    let current_tools_json = current_tools
+        .as_ref()
+        .and_then(|t| serde_json::to_value(t).ok());
+
+    let updated = sqlx::query(
+        r#"
+        UPDATE mcp_instances
+        SET health_status = $2,
+            last_health_check_at = $3,
+            last_latency_ms = $4,
+            tools_json = COALESCE($5, tools_json),
+            tools_count = COALESCE($6, tools_count),
+            updated_at = NOW()
+        WHERE id = $1
+        "#,
+    )
+    .bind(mcp_id)
+    .bind(health_status)
+    .bind(now)
+    .bind(latency_ms)
+    .bind(&current_tools_json)
+    .bind(tools_count)
+    .execute(pool)
+    .await;
+
+    if let Err(e) = updated {
+        tracing::error!(mcp_id = %mcp_id, error = %e, "Failed to record automatic health check");
+        return None;
+    }
+
+    // NULL tested_by marks this as an automatic check in the history
+    if let Err(e) = sqlx::query(
+        r#"
+        INSERT INTO mcp_test_history (
+            mcp_id, org_id, health_status, tools_count, latency_ms,
+            connection_warm, error_message, tested_at, tested_by
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, NULL)
+        "#,
+    )
+    .bind(mcp_id)
+    .bind(org_id)
+    .bind(health_status)
+    .bind(tools_count)
+    .bind(latency_ms)
+    .bind(connection_warm)
+    .bind(&error)
+    .bind(now)
+    .execute(pool)
+    .await
+    {
+        tracing::error!(mcp_id = %mcp_id, error = %e, "Failed to save automatic test history");
+    }
+
+    if let Some(error) = &error {
+        tracing::warn!(mcp_id = %mcp_id, error = %error, "Automatic health check failed");
+    }
+
+    // Diff the fetched tool list against the previous snapshot and push
+    // change notifications; a missing baseline just establishes one
+    if let Some(tools) = &current_tools {
+        let mut current_names: Vec<String> = tools.iter().map(|t| t.name.clone()).collect();
+        current_names.sort();
+        if let Some(previous_names) = super::tool_changes::tool_names(previous_tools_json) {
+            if let Some(change) = super::tool_changes::detect(&previous_names, &current_names) {
+                super::tool_changes::notify(
+                    pool,
+                    streams,
+                    tool_catalog,
+                    org_id,
+                    *mcp_id,
+                    mcp_name,
+                    change,
+                )
+                .await;
+            }
+        }
+    }
+
+    let evidence = serde_json::json!({
+        "source": "automatic",
+        "latency_ms": latency_ms,
+        "tools_count": tools_count,
+        "connection_warm": connection_warm,
+        "error": error,
+    });
+
+    Some((health_status.to_string(), evidence))
+}
@@ -0,0 +1,232 @@
+//! Per-MCP webhooks for health and circuit breaker transitions
+//!
+//! Separately from the platform's generic webhooks, an MCP can have one
+//! callback URL that receives an immediate, minimal POST whenever its
+//! health flips (up/down) or its circuit breaker opens or closes. The
+//! payload carries the MCP id, old and new state, and the probe evidence
+//! that triggered the transition, and is signed with the webhook secret
+//! (HMAC-SHA256 of the body in `X-PlexMCP-Signature`). Delivery is
+//! fire-and-forget; repeated failures disable the webhook.
+
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Delivery timeout per attempt
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Consecutive delivery failures before the webhook is disabled
+const MAX_CONSECUTIVE_FAILURES: i32 = 10;
+
+/// One health or circuit transition to deliver
+#[derive(Debug, Clone)]
+pub struct McpWebhookEvent {
+    /// "mcp.health_changed" or "mcp.circuit_changed"
+    pub event: &'static str,
+    pub mcp_id: Uuid,
+    pub from: String,
+    pub to: String,
+    /// Probe evidence: latency, error, failure counters - whatever the
+    /// source observed when the state flipped
+    pub evidence: Value,
+}
+
+/// Queue an event for delivery without blocking the caller
+pub fn notify(pool: &PgPool, event: McpWebhookEvent) {
+    let pool = pool.clone();
+    tokio::spawn(async move {
+        // Failures (including "no webhook configured") are already logged
+        // or expected; background delivery has no one to report to
+        let _ = deliver(&pool, event).await;
+    });
+}
+
+/// Send a signed test event through the normal delivery path
+///
+/// Used by the test-delivery endpoint so customers can exercise their
+/// receiver end-to-end; success/failure counters update exactly as for
+/// real events, and the outcome is reported to the caller.
+pub async fn send_test(
+    pool: &PgPool,
+    mcp_id: Uuid,
+    requested_by: Option<Uuid>,
+) -> Result<(), String> {
+    deliver(
+        pool,
+        McpWebhookEvent {
+            event: "mcp.webhook_test",
+            mcp_id,
+            from: "test".to_string(),
+            to: "test".to_string(),
+            evidence: json!({
+                "source": "manual",
+                "requested_by": requested_by,
+            }),
+        },
+    )
+    .await
+}
+
+/// Deliver one event to the MCP's webhook, if one is configured
+async fn deliver(pool: &PgPool, event: McpWebhookEvent) -> Result<(), String> {
+    let webhook: Option<(Uuid, String, String, i32)> = match sqlx::query_as(
+        r#"
+        SELECT id, url, secret, consecutive_failures
+        FROM mcp_health_webhooks
+        WHERE mcp_id = $1 AND enabled
+        "#,
+    )
+    .bind(event.mcp_id)
+    .fetch_optional(pool)
+    .await
+    {
+        Ok(row) => row,
+        Err(e) => {
+            tracing::error!(mcp_id = %event.mcp_id, error = %e, "Failed to load MCP webhook");
+            return Err(format!("Failed to load webhook: {}", e));
+        }
+    };
+
+    let Some((webhook_id, url, secret, consecutive_failures)) = webhook else {
+        return Err("No enabled webhook configured for this MCP".to_string());
+    };
+
+    let occurred_at = OffsetDateTime::now_utc()
+        .format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_default();
+
+    let body = json!({
+        "event": event.event,
+        "mcp_id": event.mcp_id,
+        "from": event.from,
+        "to": event.to,
+        "evidence": event.evidence,
+        "occurred_at": occurred_at,
+    });
+    let body = match serde_json::to_string(&body) {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::error!(mcp_id = %event.mcp_id, error = %e, "Failed to serialize webhook payload");
+            return Err(format!("Failed to serialize payload: {}", e));
+        }
+    };
+
+    let result = post_signed(&url, &secret, event.event, &body).await;
+
+    match result {
+        Ok(()) => {
+            tracing::debug!(
+                mcp_id = %event.mcp_id,
+                event = event.event,
+                "MCP webhook delivered"
+            );
+            if let Err(e) = sqlx::query(
+                r#"
+                UPDATE mcp_health_webhooks
+                SET last_success_at = NOW(), consecutive_failures = 0, updated_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(webhook_id)
+            .execute(pool)
+            .await
+            {
+                tracing::error!(webhook_id = %webhook_id, error = %e, "Failed to record webhook success");
+            }
+            Ok(())
+        }
+        Err(reason) => {
+            let failures = consecutive_failures + 1;
+            let disable = failures >= MAX_CONSECUTIVE_FAILURES;
+            tracing::warn!(
+                mcp_id = %event.mcp_id,
+                event = event.event,
+                failures = failures,
+                disabled = disable,
+                reason = %reason,
+                "MCP webhook delivery failed"
+            );
+            if let Err(e) = sqlx::query(
+                r#"
+                UPDATE mcp_health_webhooks
+                SET last_failure_at = NOW(),
+                    consecutive_failures = $2,
+                    enabled = enabled AND NOT $3,
+                    disabled_reason = CASE WHEN $3 THEN $4 ELSE disabled_reason END,
+                    updated_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(webhook_id)
+            .bind(failures)
+            .bind(disable)
+            .bind(format!(
+                "Disabled after {} consecutive delivery failures",
+                failures
+            ))
+            .execute(pool)
+            .await
+            {
+                tracing::error!(webhook_id = %webhook_id, error = %e, "Failed to record webhook failure");
+            }
+            Err(reason)
+        }
+    }
+}
+
+/// POST the signed payload; any non-2xx response counts as a failure
+async fn post_signed(url: &str, secret: &str, event: &str, body: &str) -> Result<(), String> {
+    let client = reqwest::Client::builder()
+        .timeout(DELIVERY_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let response = client
+        .post(url)
+        .header("content-type", "application/json")
+        .header("x-plexmcp-event", event)
+        .header("x-plexmcp-signature", sign_payload(secret, body))
+        .body(body.to_string())
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("HTTP {}", response.status()))
+    }
+}
+
+/// HMAC-SHA256 of the payload body, hex-encoded with a scheme prefix so
+/// receivers can verify `sha256=<hex>` against their copy of the secret
+pub fn sign_payload(secret: &str, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .unwrap_or_else(|_| unreachable!("HMAC accepts keys of any length"));
+    mac.update(body.as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_is_stable_and_keyed() {
+        let sig = sign_payload("secret", "{\"event\":\"mcp.health_changed\"}");
+        assert!(sig.starts_with("sha256="));
+        assert_eq!(
+            sig,
+            sign_payload("secret", "{\"event\":\"mcp.health_changed\"}")
+        );
+        assert_ne!(
+            sig,
+            sign_payload("other", "{\"event\":\"mcp.health_changed\"}")
+        );
+    }
+}
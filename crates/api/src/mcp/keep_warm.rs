@@ -0,0 +1,71 @@
+//! Keep-warm connection sweeps for low-latency MCPs
+//!
+//! The first request to an upstream after idle pays TCP+TLS setup (and, for
+//! stdio, process spawn). Orgs on tiers with keep-warm access can flag MCPs
+//! with `keep_warm: true` in their config; this sweep periodically touches
+//! those connections so they stay established between real requests.
+
+use std::time::Duration;
+
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::client::McpClient;
+use plexmcp_shared::SubscriptionTier;
+
+/// How often the keep-warm sweep runs
+pub const KEEP_WARM_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Warm connections for all active MCPs flagged `keep_warm` in their config
+///
+/// Tier-gated at runtime as well as on config save, so a downgrade stops
+/// the pings without requiring a config rewrite.
+pub async fn keep_warm_sweep(pool: &PgPool, client: &McpClient) {
+    #[derive(sqlx::FromRow)]
+    struct KeepWarmRow {
+        id: Uuid,
+        org_id: Uuid,
+        mcp_type: String,
+        config: Value,
+        subscription_tier: String,
+    }
+
+    let rows: Vec<KeepWarmRow> = match sqlx::query_as(
+        r#"
+        SELECT m.id, m.org_id, m.mcp_type, m.config, o.subscription_tier
+        FROM mcp_instances m
+        JOIN organizations o ON o.id = m.org_id
+        WHERE m.status = 'active' AND m.config->>'keep_warm' = 'true'
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            tracing::error!(error = %e, "Keep-warm sweep failed to load MCPs");
+            return;
+        }
+    };
+
+    for row in rows {
+        let tier: SubscriptionTier = row
+            .subscription_tier
+            .parse()
+            .unwrap_or(SubscriptionTier::Free);
+        if !tier.keep_warm_allowed() {
+            continue;
+        }
+
+        let Some(transport) =
+            crate::routes::mcps::parse_transport(&row.mcp_type, &row.config, row.org_id)
+        else {
+            continue;
+        };
+
+        client
+            .warm_connection(&transport, &row.id.to_string())
+            .await;
+    }
+}
@@ -0,0 +1,144 @@
+//! Per-organization JSON-RPC method allowlist
+//!
+//! Enterprises can lock their proxy down to a subset of MCP methods
+//! (e.g. only `tools/list` and `tools/call`, never resources or prompts).
+//! The allowlist is stored in `organizations.settings` under
+//! `mcp_method_allowlist` as a JSON array of method names; an absent or
+//! empty list means no restriction. Entries are either exact method names
+//! or a `namespace/*` wildcard (`"tools/*"` covers `tools/list` and
+//! `tools/call`).
+//!
+//! Protocol plumbing (`initialize`, `ping` and `notifications/*`) is
+//! always allowed - without it clients could never complete the
+//! handshake to call the methods they are allowed.
+
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Settings key under `organizations.settings`
+pub const SETTINGS_KEY: &str = "mcp_method_allowlist";
+
+/// Methods the proxy accepts, used to validate allowlist entries
+pub const KNOWN_METHODS: &[&str] = &[
+    "tools/list",
+    "tools/call",
+    "resources/list",
+    "resources/read",
+    "resources/subscribe",
+    "resources/unsubscribe",
+    "prompts/list",
+    "prompts/get",
+    "logging/setLevel",
+    "completion/complete",
+    "context/get",
+    "context/set",
+    "context/delete",
+    "context/list",
+];
+
+/// Load the org's method allowlist; `None` means unrestricted
+///
+/// Any lookup problem (missing org, malformed value, database error)
+/// degrades to unrestricted - a settings hiccup must not take the proxy
+/// down for the whole org.
+pub async fn load(pool: &PgPool, org_id: Uuid) -> Option<Vec<String>> {
+    let setting: Result<Option<Option<Value>>, sqlx::Error> = sqlx::query_scalar(
+        "SELECT settings->'mcp_method_allowlist' FROM organizations WHERE id = $1",
+    )
+    .bind(org_id)
+    .fetch_optional(pool)
+    .await;
+
+    let value = match setting {
+        Ok(value) => value.flatten()?,
+        Err(e) => {
+            tracing::warn!(org_id = %org_id, error = %e, "Failed to read method allowlist");
+            return None;
+        }
+    };
+
+    let methods: Vec<String> = match serde_json::from_value(value) {
+        Ok(methods) => methods,
+        Err(e) => {
+            tracing::warn!(org_id = %org_id, error = %e, "Malformed method allowlist, ignoring");
+            return None;
+        }
+    };
+
+    if methods.is_empty() {
+        return None;
+    }
+    Some(methods)
+}
+
+/// Whether a method passes the allowlist
+pub fn is_allowed(allowlist: &[String], method: &str) -> bool {
+    // Protocol plumbing is exempt; blocking it would only break the
+    // handshake, not restrict capabilities
+    if method == "initialize" || method == "ping" || method.starts_with("notifications/") {
+        return true;
+    }
+
+    allowlist
+        .iter()
+        .any(|entry| match entry.strip_suffix("/*") {
+            Some(namespace) => method
+                .strip_prefix(namespace)
+                .is_some_and(|rest| rest.starts_with('/')),
+            None => entry == method,
+        })
+}
+
+/// Whether an allowlist entry is a known method or a valid wildcard
+pub fn is_valid_entry(entry: &str) -> bool {
+    match entry.strip_suffix("/*") {
+        Some(namespace) => KNOWN_METHODS.iter().any(|m| {
+            m.strip_prefix(namespace)
+                .is_some_and(|rest| rest.starts_with('/'))
+        }),
+        None => KNOWN_METHODS.contains(&entry),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allowlist(entries: &[&str]) -> Vec<String> {
+        entries.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_exact_match_and_plumbing_exemption() {
+        let list = allowlist(&["tools/list", "tools/call"]);
+        assert!(is_allowed(&list, "tools/call"));
+        assert!(!is_allowed(&list, "resources/read"));
+        assert!(!is_allowed(&list, "prompts/get"));
+
+        // Handshake methods pass regardless of the list
+        assert!(is_allowed(&list, "initialize"));
+        assert!(is_allowed(&list, "ping"));
+        assert!(is_allowed(&list, "notifications/initialized"));
+    }
+
+    #[test]
+    fn test_wildcard_entries() {
+        let list = allowlist(&["tools/*"]);
+        assert!(is_allowed(&list, "tools/list"));
+        assert!(is_allowed(&list, "tools/call"));
+        assert!(!is_allowed(&list, "resources/list"));
+        // Prefix must end at a path segment: "tools/*" is not "toolsextra/x"
+        assert!(!is_allowed(&list, "toolsextra/list"));
+    }
+
+    #[test]
+    fn test_entry_validation() {
+        assert!(is_valid_entry("tools/call"));
+        assert!(is_valid_entry("tools/*"));
+        assert!(is_valid_entry("resources/*"));
+        assert!(!is_valid_entry("tools/delete"));
+        assert!(!is_valid_entry("everything/*"));
+        assert!(!is_valid_entry("initialize")); // plumbing is implicit, not listable
+    }
+}
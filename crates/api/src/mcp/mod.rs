@@ -19,12 +19,31 @@
 //! - SSE streaming support for long-running operations
 //! - HTTP and Stdio transport support
 
+pub mod adaptive_timeout;
 pub mod audit;
+pub mod audit_buffer;
+pub mod auth_template;
 pub mod circuit_breaker;
 pub mod client;
+pub mod dedup;
 pub mod handlers;
+pub mod health_monitor;
+pub mod health_webhooks;
+pub mod keep_warm;
+pub mod method_allowlist;
+pub mod moderation;
+pub mod oauth;
+pub mod payload_capture;
+pub mod retry_policy;
 pub mod router;
+pub mod sandbox;
+pub mod session_auth;
+pub mod session_context;
+pub mod ssh_tunnel;
 pub mod streaming;
+pub mod tool_cache;
+pub mod tool_catalog;
+pub mod tool_changes;
 pub mod types;
 
 pub use audit::{
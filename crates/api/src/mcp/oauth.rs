@@ -0,0 +1,309 @@
+//! OAuth token vault for upstream MCPs
+//!
+//! Some upstream MCPs (GitHub, Linear) authenticate with short-lived OAuth
+//! access tokens rather than static API keys. Orgs connect an MCP once via
+//! the authorization-code flow; the vault stores the resulting tokens
+//! AES-GCM encrypted (same envelope as 2FA secrets) and hands the proxy a
+//! valid access token on demand, refreshing against the provider's token
+//! endpoint shortly before expiry.
+
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::auth::totp::{decrypt_secret, encrypt_secret};
+
+/// Refresh when the access token has less than this long to live
+const REFRESH_MARGIN_SECS: i64 = 60;
+
+/// Provider token endpoints must respond within this window
+const TOKEN_REQUEST_TIMEOUT_SECS: u64 = 15;
+
+#[derive(Debug, thiserror::Error)]
+pub enum OAuthError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("token encryption failed")]
+    Encryption,
+    #[error("token endpoint request failed: {0}")]
+    TokenRequest(String),
+    #[error("access token expired and no refresh token is stored")]
+    ExpiredNoRefresh,
+}
+
+/// Token response from a provider's token endpoint (RFC 6749 §5.1)
+#[derive(Debug, serde::Deserialize)]
+pub struct TokenResponse {
+    pub access_token: String,
+    #[serde(default = "default_token_type")]
+    pub token_type: String,
+    pub expires_in: Option<i64>,
+    pub refresh_token: Option<String>,
+    pub scope: Option<String>,
+}
+
+fn default_token_type() -> String {
+    "Bearer".to_string()
+}
+
+/// Stored credential row with decryption inputs
+#[derive(Debug, sqlx::FromRow)]
+struct CredentialRow {
+    token_endpoint: String,
+    client_id: String,
+    client_secret_encrypted: Option<String>,
+    client_secret_nonce: Option<String>,
+    access_token_encrypted: String,
+    access_token_nonce: String,
+    refresh_token_encrypted: Option<String>,
+    refresh_token_nonce: Option<String>,
+    expires_at: Option<OffsetDateTime>,
+}
+
+/// Exchange an authorization code for tokens at the provider
+pub async fn exchange_code(
+    token_endpoint: &str,
+    client_id: &str,
+    client_secret: Option<&str>,
+    code: &str,
+    redirect_uri: &str,
+) -> Result<TokenResponse, OAuthError> {
+    let mut params = vec![
+        ("grant_type", "authorization_code"),
+        ("code", code),
+        ("redirect_uri", redirect_uri),
+        ("client_id", client_id),
+    ];
+    if let Some(secret) = client_secret {
+        params.push(("client_secret", secret));
+    }
+    token_request(token_endpoint, &params).await
+}
+
+/// Fetch a valid access token for an MCP, refreshing it first if needed
+///
+/// Returns `Ok(None)` when the MCP has no stored credentials (the caller
+/// falls back to whatever auth the MCP config carries).
+pub async fn get_valid_access_token(
+    pool: &PgPool,
+    encryption_key: &[u8; 32],
+    org_id: Uuid,
+    mcp_id: Uuid,
+) -> Result<Option<String>, OAuthError> {
+    let row: Option<CredentialRow> = sqlx::query_as(
+        r#"
+        SELECT token_endpoint, client_id, client_secret_encrypted, client_secret_nonce,
+               access_token_encrypted, access_token_nonce,
+               refresh_token_encrypted, refresh_token_nonce, expires_at
+        FROM mcp_oauth_credentials
+        WHERE mcp_id = $1 AND org_id = $2
+        "#,
+    )
+    .bind(mcp_id)
+    .bind(org_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(row) = row else {
+        return Ok(None);
+    };
+
+    // Still comfortably valid (or the provider never reported a lifetime)
+    let needs_refresh = row.expires_at.is_some_and(|exp| {
+        exp - OffsetDateTime::now_utc() < time::Duration::seconds(REFRESH_MARGIN_SECS)
+    });
+    if !needs_refresh {
+        let token = decrypt_secret(
+            &row.access_token_encrypted,
+            &row.access_token_nonce,
+            encryption_key,
+        )
+        .map_err(|_| OAuthError::Encryption)?;
+        return Ok(Some(token));
+    }
+
+    let (Some(refresh_enc), Some(refresh_nonce)) =
+        (&row.refresh_token_encrypted, &row.refresh_token_nonce)
+    else {
+        return Err(OAuthError::ExpiredNoRefresh);
+    };
+    let refresh_token = decrypt_secret(refresh_enc, refresh_nonce, encryption_key)
+        .map_err(|_| OAuthError::Encryption)?;
+    let client_secret = match (&row.client_secret_encrypted, &row.client_secret_nonce) {
+        (Some(enc), Some(nonce)) => {
+            Some(decrypt_secret(enc, nonce, encryption_key).map_err(|_| OAuthError::Encryption)?)
+        }
+        _ => None,
+    };
+
+    let mut params = vec![
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh_token.as_str()),
+        ("client_id", row.client_id.as_str()),
+    ];
+    if let Some(secret) = client_secret.as_deref() {
+        params.push(("client_secret", secret));
+    }
+    let tokens = token_request(&row.token_endpoint, &params).await?;
+
+    // Providers may rotate the refresh token; keep the old one otherwise
+    store_refreshed_tokens(pool, encryption_key, mcp_id, &tokens).await?;
+
+    tracing::info!(mcp_id = %mcp_id, "Refreshed OAuth access token for upstream MCP");
+    Ok(Some(tokens.access_token))
+}
+
+/// Persist tokens from a code exchange, replacing any existing credential set
+#[allow(clippy::too_many_arguments)]
+pub async fn store_credentials(
+    pool: &PgPool,
+    encryption_key: &[u8; 32],
+    org_id: Uuid,
+    mcp_id: Uuid,
+    token_endpoint: &str,
+    client_id: &str,
+    client_secret: Option<&str>,
+    tokens: &TokenResponse,
+    connected_by: Option<Uuid>,
+) -> Result<(), OAuthError> {
+    let (access_enc, access_nonce) =
+        encrypt_secret(&tokens.access_token, encryption_key).map_err(|_| OAuthError::Encryption)?;
+    let refresh = tokens
+        .refresh_token
+        .as_deref()
+        .map(|t| encrypt_secret(t, encryption_key))
+        .transpose()
+        .map_err(|_| OAuthError::Encryption)?;
+    let secret = client_secret
+        .map(|s| encrypt_secret(s, encryption_key))
+        .transpose()
+        .map_err(|_| OAuthError::Encryption)?;
+    let expires_at = tokens
+        .expires_in
+        .map(|secs| OffsetDateTime::now_utc() + time::Duration::seconds(secs));
+
+    sqlx::query(
+        r#"
+        INSERT INTO mcp_oauth_credentials (
+            org_id, mcp_id, token_endpoint, client_id,
+            client_secret_encrypted, client_secret_nonce,
+            access_token_encrypted, access_token_nonce,
+            refresh_token_encrypted, refresh_token_nonce,
+            token_type, scope, expires_at, connected_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+        ON CONFLICT (mcp_id) DO UPDATE SET
+            token_endpoint = EXCLUDED.token_endpoint,
+            client_id = EXCLUDED.client_id,
+            client_secret_encrypted = EXCLUDED.client_secret_encrypted,
+            client_secret_nonce = EXCLUDED.client_secret_nonce,
+            access_token_encrypted = EXCLUDED.access_token_encrypted,
+            access_token_nonce = EXCLUDED.access_token_nonce,
+            refresh_token_encrypted = EXCLUDED.refresh_token_encrypted,
+            refresh_token_nonce = EXCLUDED.refresh_token_nonce,
+            token_type = EXCLUDED.token_type,
+            scope = EXCLUDED.scope,
+            expires_at = EXCLUDED.expires_at,
+            connected_by = EXCLUDED.connected_by,
+            updated_at = NOW()
+        "#,
+    )
+    .bind(org_id)
+    .bind(mcp_id)
+    .bind(token_endpoint)
+    .bind(client_id)
+    .bind(secret.as_ref().map(|(e, _)| e))
+    .bind(secret.as_ref().map(|(_, n)| n))
+    .bind(&access_enc)
+    .bind(&access_nonce)
+    .bind(refresh.as_ref().map(|(e, _)| e))
+    .bind(refresh.as_ref().map(|(_, n)| n))
+    .bind(&tokens.token_type)
+    .bind(&tokens.scope)
+    .bind(expires_at)
+    .bind(connected_by)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Update stored tokens after a refresh (keeps the old refresh token when
+/// the provider did not rotate it)
+async fn store_refreshed_tokens(
+    pool: &PgPool,
+    encryption_key: &[u8; 32],
+    mcp_id: Uuid,
+    tokens: &TokenResponse,
+) -> Result<(), OAuthError> {
+    let (access_enc, access_nonce) =
+        encrypt_secret(&tokens.access_token, encryption_key).map_err(|_| OAuthError::Encryption)?;
+    let refresh = tokens
+        .refresh_token
+        .as_deref()
+        .map(|t| encrypt_secret(t, encryption_key))
+        .transpose()
+        .map_err(|_| OAuthError::Encryption)?;
+    let expires_at = tokens
+        .expires_in
+        .map(|secs| OffsetDateTime::now_utc() + time::Duration::seconds(secs));
+
+    sqlx::query(
+        r#"
+        UPDATE mcp_oauth_credentials SET
+            access_token_encrypted = $2,
+            access_token_nonce = $3,
+            refresh_token_encrypted = COALESCE($4, refresh_token_encrypted),
+            refresh_token_nonce = COALESCE($5, refresh_token_nonce),
+            expires_at = $6,
+            updated_at = NOW()
+        WHERE mcp_id = $1
+        "#,
+    )
+    .bind(mcp_id)
+    .bind(&access_enc)
+    .bind(&access_nonce)
+    .bind(refresh.as_ref().map(|(e, _)| e))
+    .bind(refresh.as_ref().map(|(_, n)| n))
+    .bind(expires_at)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// POST form-encoded params to a token endpoint and parse the response
+async fn token_request(
+    token_endpoint: &str,
+    params: &[(&str, &str)],
+) -> Result<TokenResponse, OAuthError> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(TOKEN_REQUEST_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| OAuthError::TokenRequest(e.to_string()))?;
+
+    let response = client
+        .post(token_endpoint)
+        .header("Accept", "application/json")
+        .form(params)
+        .send()
+        .await
+        .map_err(|e| OAuthError::TokenRequest(e.to_string()))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        // Never log the body verbatim at error level upstream; callers get
+        // a truncated form suitable for surfacing to the org
+        let snippet: String = body.chars().take(200).collect();
+        return Err(OAuthError::TokenRequest(format!(
+            "provider returned {}: {}",
+            status, snippet
+        )));
+    }
+
+    response
+        .json::<TokenResponse>()
+        .await
+        .map_err(|e| OAuthError::TokenRequest(format!("invalid token response: {}", e)))
+}
@@ -0,0 +1,224 @@
+//! Opt-in MCP payload capture with redaction and retention
+//!
+//! Orgs debugging a flaky integration can enable full request/response
+//! payload capture: each proxied request then stores a redacted copy of
+//! both payloads in `mcp_request_log.captured_payload`, retrievable via
+//! `GET /api/v1/mcp/logs/:log_id/payload`. Configuration lives in
+//! `organizations.settings` under `payload_capture`:
+//!
+//! ```json
+//! {"enabled": true, "retention_days": 7, "redact_paths": ["$.params.arguments.password"]}
+//! ```
+//!
+//! Redaction rules are JSONPath-style dotted paths (`$.a.b.c`, `*`
+//! matches any object key, arrays are traversed element-wise) applied at
+//! write time, so secrets never reach the database. A scheduled job
+//! clears captured payloads once the org's retention window elapses.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Settings key under `organizations.settings`
+pub const SETTINGS_KEY: &str = "payload_capture";
+
+/// Valid retention range in days
+pub const MIN_RETENTION_DAYS: i64 = 1;
+pub const MAX_RETENTION_DAYS: i64 = 90;
+
+/// Replacement written over redacted values
+const REDACTED: &str = "[REDACTED]";
+
+fn default_retention_days() -> i64 {
+    7
+}
+
+/// Per-org payload capture configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayloadCaptureConfig {
+    pub enabled: bool,
+    #[serde(default = "default_retention_days")]
+    pub retention_days: i64,
+    #[serde(default)]
+    pub redact_paths: Vec<String>,
+}
+
+impl Default for PayloadCaptureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retention_days: default_retention_days(),
+            redact_paths: vec![],
+        }
+    }
+}
+
+/// Load the org's capture config; any lookup problem degrades to
+/// disabled - capture is best-effort debugging aid, never a hard
+/// dependency of the request path
+pub async fn load(pool: &PgPool, org_id: Uuid) -> PayloadCaptureConfig {
+    let setting: Result<Option<Option<Value>>, sqlx::Error> =
+        sqlx::query_scalar("SELECT settings->'payload_capture' FROM organizations WHERE id = $1")
+            .bind(org_id)
+            .fetch_optional(pool)
+            .await;
+
+    let value = match setting {
+        Ok(value) => match value.flatten() {
+            Some(value) => value,
+            None => return PayloadCaptureConfig::default(),
+        },
+        Err(e) => {
+            tracing::warn!(org_id = %org_id, error = %e, "Failed to read payload capture config");
+            return PayloadCaptureConfig::default();
+        }
+    };
+
+    match serde_json::from_value(value) {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::warn!(org_id = %org_id, error = %e, "Malformed payload capture config, ignoring");
+            PayloadCaptureConfig::default()
+        }
+    }
+}
+
+/// Redact all values matched by the configured paths in place
+pub fn redact(value: &mut Value, paths: &[String]) {
+    for path in paths {
+        let segments: Vec<&str> = match path.strip_prefix("$.") {
+            Some(rest) => rest.split('.').collect(),
+            None => continue, // invalid paths are rejected at config time
+        };
+        apply(value, &segments);
+    }
+}
+
+fn apply(value: &mut Value, segments: &[&str]) {
+    // Arrays are traversed element-wise at every step, so one rule covers
+    // every element of e.g. a tools/call batch
+    if let Value::Array(items) = value {
+        for item in items {
+            apply(item, segments);
+        }
+        return;
+    }
+
+    let Some((head, rest)) = segments.split_first() else {
+        return;
+    };
+    let Value::Object(map) = value else {
+        return;
+    };
+
+    if rest.is_empty() {
+        if *head == "*" {
+            for entry in map.values_mut() {
+                *entry = Value::String(REDACTED.to_string());
+            }
+        } else if let Some(entry) = map.get_mut(*head) {
+            *entry = Value::String(REDACTED.to_string());
+        }
+    } else if *head == "*" {
+        for entry in map.values_mut() {
+            apply(entry, rest);
+        }
+    } else if let Some(entry) = map.get_mut(*head) {
+        apply(entry, rest);
+    }
+}
+
+/// Whether a redaction rule is syntactically valid (`$.` prefix, no
+/// empty segments)
+pub fn is_valid_path(path: &str) -> bool {
+    match path.strip_prefix("$.") {
+        Some(rest) => !rest.is_empty() && rest.split('.').all(|segment| !segment.is_empty()),
+        None => false,
+    }
+}
+
+/// Clear captured payloads older than each org's retention window
+///
+/// Runs as the `payload_capture_retention` scheduler job; the partial
+/// index on `captured_payload IS NOT NULL` keeps the sweep cheap.
+pub async fn run_retention_sweep(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        UPDATE mcp_request_log l
+        SET captured_payload = NULL
+        FROM organizations o
+        WHERE l.organization_id = o.id
+          AND l.captured_payload IS NOT NULL
+          AND l.created_at < NOW() - make_interval(days =>
+                LEAST(GREATEST(COALESCE((o.settings->'payload_capture'->>'retention_days')::int, 7), 1), 90))
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_redact_nested_and_wildcard_paths() {
+        let mut value = json!({
+            "method": "tools/call",
+            "params": {
+                "name": "github:create_issue",
+                "arguments": {"password": "hunter2", "title": "bug"},
+                "headers": {"authorization": "Bearer abc", "accept": "json"}
+            }
+        });
+
+        redact(
+            &mut value,
+            &[
+                "$.params.arguments.password".to_string(),
+                "$.params.headers.*".to_string(),
+            ],
+        );
+
+        assert_eq!(value["params"]["arguments"]["password"], "[REDACTED]");
+        assert_eq!(value["params"]["arguments"]["title"], "bug");
+        assert_eq!(value["params"]["headers"]["authorization"], "[REDACTED]");
+        assert_eq!(value["params"]["headers"]["accept"], "[REDACTED]");
+        assert_eq!(value["method"], "tools/call");
+    }
+
+    #[test]
+    fn test_redact_traverses_arrays() {
+        let mut value = json!({
+            "result": {"content": [{"text": "secret", "type": "text"}, {"text": "more"}]}
+        });
+
+        redact(&mut value, &["$.result.content.text".to_string()]);
+
+        assert_eq!(value["result"]["content"][0]["text"], "[REDACTED]");
+        assert_eq!(value["result"]["content"][0]["type"], "text");
+        assert_eq!(value["result"]["content"][1]["text"], "[REDACTED]");
+    }
+
+    #[test]
+    fn test_path_validation() {
+        assert!(is_valid_path("$.params.arguments.password"));
+        assert!(is_valid_path("$.params.headers.*"));
+        assert!(!is_valid_path("params.password"));
+        assert!(!is_valid_path("$."));
+        assert!(!is_valid_path("$.params..password"));
+    }
+
+    #[test]
+    fn test_config_defaults() {
+        let config: PayloadCaptureConfig =
+            serde_json::from_value(json!({"enabled": true})).unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.retention_days, 7);
+        assert!(config.redact_paths.is_empty());
+    }
+}
@@ -0,0 +1,361 @@
+//! Per-MCP request timeout and retry policy
+//!
+//! The client ships platform-wide defaults (30s request timeout, 3 retries
+//! with exponential backoff), but upstreams vary: a local stdio MCP can
+//! answer in milliseconds while a rate-limited SaaS API wants long timeouts
+//! and patient retries. Each MCP can override connect/read timeouts, retry
+//! count and backoff strategy via a `retry_policy` block in its config JSON,
+//! e.g.
+//!
+//! ```json
+//! {"retry_policy": {"read_timeout_ms": 60000, "max_retries": 5, "backoff": "fixed"}}
+//! ```
+//!
+//! Retries only ever apply to idempotent JSON-RPC methods (see
+//! [`is_idempotent`]): a timed-out `tools/call` may have executed upstream,
+//! so replaying it could run the side effect twice.
+
+use std::time::Duration;
+
+/// Default retry attempts after the initial request
+const DEFAULT_MAX_RETRIES: usize = 3;
+
+/// Default delay before the first retry (100ms)
+const DEFAULT_BASE_DELAY: Duration = Duration::from_millis(100);
+
+/// Default upper bound on any single retry delay (5 seconds)
+const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// How retry delays grow between attempts
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BackoffStrategy {
+    /// Delay doubles after every attempt (the platform default)
+    #[default]
+    Exponential,
+    /// Delay grows by the base delay after every attempt
+    Linear,
+    /// Every retry waits the base delay
+    Fixed,
+}
+
+impl BackoffStrategy {
+    /// Parse the `backoff` config value; None for unknown strategies
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "exponential" => Some(Self::Exponential),
+            "linear" => Some(Self::Linear),
+            "fixed" => Some(Self::Fixed),
+            _ => None,
+        }
+    }
+
+    /// Label used in validation messages and API responses
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Exponential => "exponential",
+            Self::Linear => "linear",
+            Self::Fixed => "fixed",
+        }
+    }
+}
+
+/// Connect/read timeout overrides extracted from a [`RetryPolicy`], threaded
+/// into the transport-level request helpers (which don't see the full policy)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TransportTimeouts {
+    /// TCP connect timeout for HTTP/SSE transports
+    pub connect: Option<Duration>,
+    /// Overall per-request timeout, covering the response body read
+    pub read: Option<Duration>,
+}
+
+/// Effective timeout and retry behavior for one MCP
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// TCP connect timeout for HTTP/SSE transports (None uses the client
+    /// default)
+    pub connect_timeout: Option<Duration>,
+    /// Per-request timeout; when set it also takes precedence over the
+    /// adaptive timeout (see `crate::mcp::adaptive_timeout`)
+    pub read_timeout: Option<Duration>,
+    /// Retry attempts after the initial request (0 disables retries)
+    pub max_retries: usize,
+    /// How delays grow between attempts
+    pub backoff: BackoffStrategy,
+    /// Delay before the first retry
+    pub base_delay: Duration,
+    /// Upper bound on any single retry delay
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            connect_timeout: None,
+            read_timeout: None,
+            max_retries: DEFAULT_MAX_RETRIES,
+            backoff: BackoffStrategy::default(),
+            base_delay: DEFAULT_BASE_DELAY,
+            max_delay: DEFAULT_MAX_DELAY,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Parse a per-MCP override from the MCP's config JSON. Missing fields
+    /// fall back to the platform defaults; returns None when no
+    /// `retry_policy` block is present.
+    pub fn from_config(config: &serde_json::Value) -> Option<Self> {
+        let block = config.get("retry_policy")?.as_object()?;
+        let defaults = Self::default();
+        Some(Self {
+            connect_timeout: block
+                .get("connect_timeout_ms")
+                .and_then(|v| v.as_u64())
+                .map(Duration::from_millis),
+            read_timeout: block
+                .get("read_timeout_ms")
+                .and_then(|v| v.as_u64())
+                .map(Duration::from_millis),
+            max_retries: block
+                .get("max_retries")
+                .and_then(|v| v.as_u64())
+                .map(|v| v as usize)
+                .unwrap_or(defaults.max_retries),
+            backoff: block
+                .get("backoff")
+                .and_then(|v| v.as_str())
+                .and_then(BackoffStrategy::parse)
+                .unwrap_or(defaults.backoff),
+            base_delay: block
+                .get("base_delay_ms")
+                .and_then(|v| v.as_u64())
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.base_delay),
+            max_delay: block
+                .get("max_delay_ms")
+                .and_then(|v| v.as_u64())
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.max_delay),
+        })
+    }
+
+    /// Problems with the `retry_policy` block, for the config validation
+    /// endpoint. Empty when the block is absent or well-formed.
+    pub fn validate_block(config: &serde_json::Value) -> Vec<String> {
+        let Some(block) = config.get("retry_policy") else {
+            return Vec::new();
+        };
+        let Some(block) = block.as_object() else {
+            return vec!["retry_policy must be an object".to_string()];
+        };
+
+        let mut problems = Vec::new();
+
+        for field in ["connect_timeout_ms", "read_timeout_ms"] {
+            match block.get(field).and_then(|v| v.as_u64()) {
+                Some(ms) if !(100..=600_000).contains(&ms) => {
+                    problems.push(format!("{} must be between 100 and 600000", field));
+                }
+                None if block.contains_key(field) => {
+                    problems.push(format!("{} must be a positive integer", field));
+                }
+                _ => {}
+            }
+        }
+
+        match block.get("max_retries").and_then(|v| v.as_u64()) {
+            Some(n) if n > 10 => {
+                problems.push("max_retries must be between 0 and 10".to_string());
+            }
+            None if block.contains_key("max_retries") => {
+                problems.push("max_retries must be a non-negative integer".to_string());
+            }
+            _ => {}
+        }
+
+        if let Some(value) = block.get("backoff") {
+            let valid = value.as_str().and_then(BackoffStrategy::parse).is_some();
+            if !valid {
+                problems.push("backoff must be one of: exponential, linear, fixed".to_string());
+            }
+        }
+
+        let base = block.get("base_delay_ms").and_then(|v| v.as_u64());
+        let max = block.get("max_delay_ms").and_then(|v| v.as_u64());
+        for (field, value) in [("base_delay_ms", base), ("max_delay_ms", max)] {
+            match value {
+                Some(ms) if !(10..=600_000).contains(&ms) => {
+                    problems.push(format!("{} must be between 10 and 600000", field));
+                }
+                None if block.contains_key(field) => {
+                    problems.push(format!("{} must be a positive integer", field));
+                }
+                _ => {}
+            }
+        }
+        if let (Some(base), Some(max)) = (base, max) {
+            if max < base {
+                problems.push("max_delay_ms must not be less than base_delay_ms".to_string());
+            }
+        }
+
+        problems
+    }
+
+    /// The timeout overrides carried down to the transport helpers
+    pub fn transport_timeouts(&self) -> TransportTimeouts {
+        TransportTimeouts {
+            connect: self.connect_timeout,
+            read: self.read_timeout,
+        }
+    }
+
+    /// Un-jittered delay before each retry attempt, capped at `max_delay`
+    fn base_delays(&self) -> Vec<Duration> {
+        let base_ms = self.base_delay.as_millis() as u64;
+        (1..=self.max_retries as u32)
+            .map(|attempt| {
+                let ms = match self.backoff {
+                    BackoffStrategy::Exponential => {
+                        base_ms.saturating_mul(2u64.saturating_pow(attempt - 1))
+                    }
+                    BackoffStrategy::Linear => base_ms.saturating_mul(attempt as u64),
+                    BackoffStrategy::Fixed => base_ms,
+                };
+                Duration::from_millis(ms).min(self.max_delay)
+            })
+            .collect()
+    }
+
+    /// Jittered delay before each retry attempt, one entry per retry
+    pub fn delays(&self) -> Vec<Duration> {
+        self.base_delays()
+            .into_iter()
+            .map(tokio_retry::strategy::jitter)
+            .collect()
+    }
+
+    /// One-line summary for the config validation endpoint
+    pub fn describe(&self) -> String {
+        let mut parts = vec![format!(
+            "{} retries, {} backoff ({}ms base, {}ms max)",
+            self.max_retries,
+            self.backoff.as_str(),
+            self.base_delay.as_millis(),
+            self.max_delay.as_millis()
+        )];
+        if let Some(t) = self.connect_timeout {
+            parts.push(format!("{}ms connect timeout", t.as_millis()));
+        }
+        if let Some(t) = self.read_timeout {
+            parts.push(format!("{}ms read timeout", t.as_millis()));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Whether a JSON-RPC method is safe to retry after a transient failure
+///
+/// Read-only MCP methods can be replayed freely; `tools/call` (and anything
+/// unrecognized) must not be, because the upstream may have executed the
+/// side effect before the response was lost.
+pub fn is_idempotent(method: &str) -> bool {
+    matches!(
+        method,
+        "initialize"
+            | "ping"
+            | "tools/list"
+            | "resources/list"
+            | "resources/templates/list"
+            | "resources/read"
+            | "prompts/list"
+            | "prompts/get"
+            | "completion/complete"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_config_fills_defaults() {
+        assert!(RetryPolicy::from_config(&serde_json::json!({})).is_none());
+
+        let policy = RetryPolicy::from_config(&serde_json::json!({
+            "retry_policy": {"max_retries": 5, "read_timeout_ms": 60000}
+        }))
+        .expect("override block should parse");
+        assert_eq!(policy.max_retries, 5);
+        assert_eq!(policy.read_timeout, Some(Duration::from_secs(60)));
+        assert_eq!(policy.connect_timeout, None);
+        assert_eq!(policy.backoff, BackoffStrategy::Exponential);
+        assert_eq!(policy.base_delay, RetryPolicy::default().base_delay);
+    }
+
+    #[test]
+    fn test_backoff_strategies() {
+        let policy = RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(250),
+            ..RetryPolicy::default()
+        };
+        // Exponential: 100, 200, 400 -> capped at 250
+        assert_eq!(
+            policy.base_delays(),
+            vec![
+                Duration::from_millis(100),
+                Duration::from_millis(200),
+                Duration::from_millis(250),
+            ]
+        );
+
+        let linear = RetryPolicy {
+            backoff: BackoffStrategy::Linear,
+            max_delay: Duration::from_secs(5),
+            ..policy.clone()
+        };
+        assert_eq!(
+            linear.base_delays(),
+            vec![
+                Duration::from_millis(100),
+                Duration::from_millis(200),
+                Duration::from_millis(300),
+            ]
+        );
+
+        let fixed = RetryPolicy {
+            backoff: BackoffStrategy::Fixed,
+            ..policy
+        };
+        assert_eq!(fixed.base_delays(), vec![Duration::from_millis(100); 3]);
+    }
+
+    #[test]
+    fn test_validate_block() {
+        assert!(RetryPolicy::validate_block(&serde_json::json!({})).is_empty());
+        assert!(RetryPolicy::validate_block(&serde_json::json!({
+            "retry_policy": {"max_retries": 0, "backoff": "fixed", "base_delay_ms": 50}
+        }))
+        .is_empty());
+
+        let problems = RetryPolicy::validate_block(&serde_json::json!({
+            "retry_policy": {
+                "max_retries": 50,
+                "backoff": "quadratic",
+                "connect_timeout_ms": 5,
+            }
+        }));
+        assert_eq!(problems.len(), 3);
+    }
+
+    #[test]
+    fn test_idempotent_methods() {
+        assert!(is_idempotent("tools/list"));
+        assert!(is_idempotent("resources/read"));
+        assert!(!is_idempotent("tools/call"));
+        assert!(!is_idempotent("something/unknown"));
+    }
+}
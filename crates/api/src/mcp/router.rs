@@ -203,7 +203,11 @@ impl McpMethod {
     pub fn requires_routing(&self) -> bool {
         matches!(
             self,
-            McpMethod::ToolsCall | McpMethod::ResourcesRead | McpMethod::PromptsGet
+            McpMethod::ToolsCall
+                | McpMethod::ResourcesRead
+                | McpMethod::ResourcesSubscribe
+                | McpMethod::ResourcesUnsubscribe
+                | McpMethod::PromptsGet
         )
     }
 
@@ -322,6 +326,8 @@ mod tests {
     fn test_method_requires_routing() {
         assert!(McpMethod::ToolsCall.requires_routing());
         assert!(McpMethod::ResourcesRead.requires_routing());
+        assert!(McpMethod::ResourcesSubscribe.requires_routing());
+        assert!(McpMethod::ResourcesUnsubscribe.requires_routing());
         assert!(!McpMethod::ToolsList.requires_routing());
         assert!(!McpMethod::Initialize.requires_routing());
     }
@@ -0,0 +1,162 @@
+//! Resource-limited sandbox for managed stdio MCP processes
+//!
+//! Stdio MCPs run as child processes of the API server, so an
+//! org-provided command spec must not be able to exhaust the host or
+//! read the server's environment. The sandbox scrubs the environment
+//! down to a minimal PATH plus the org-configured variables, restricts
+//! the command to a small interpreter allowlist, and applies rlimits
+//! (memory, CPU, open files) in the child before exec.
+//!
+//! Config shape (optional `sandbox` object on a stdio MCP):
+//!
+//! ```json
+//! {
+//!     "command": "npx",
+//!     "args": ["-y", "@modelcontextprotocol/server-filesystem"],
+//!     "sandbox": { "max_memory_mb": 256, "max_cpu_seconds": 0 }
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Interpreters an org may launch as a managed stdio MCP. Bare names
+/// only - the binary is resolved through the sandbox PATH, never from
+/// an org-controlled location.
+const ALLOWED_COMMANDS: &[&str] = &[
+    "node", "npx", "bun", "bunx", "deno", "python", "python3", "uv", "uvx",
+];
+
+/// Minimal PATH visible inside the sandbox
+const SANDBOX_PATH: &str = "/usr/local/bin:/usr/bin:/bin";
+
+/// Resource limits applied to a sandboxed stdio process
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SandboxLimits {
+    /// Address-space cap in megabytes
+    #[serde(default = "default_max_memory_mb")]
+    pub max_memory_mb: u64,
+    /// Total CPU seconds the process may consume (0 = unlimited, the
+    /// default - stdio MCPs are long-running servers)
+    #[serde(default)]
+    pub max_cpu_seconds: u64,
+    /// Open file descriptor cap
+    #[serde(default = "default_max_open_files")]
+    pub max_open_files: u64,
+}
+
+fn default_max_memory_mb() -> u64 {
+    512
+}
+
+fn default_max_open_files() -> u64 {
+    256
+}
+
+impl Default for SandboxLimits {
+    fn default() -> Self {
+        Self {
+            max_memory_mb: default_max_memory_mb(),
+            max_cpu_seconds: 0,
+            max_open_files: default_max_open_files(),
+        }
+    }
+}
+
+/// Check a command spec against the interpreter allowlist
+pub fn validate_command(command: &str) -> Result<(), String> {
+    if command.contains('/') || command.contains('\\') {
+        return Err(format!(
+            "Command must be a bare interpreter name, not a path: {}",
+            command
+        ));
+    }
+    if !ALLOWED_COMMANDS.contains(&command) {
+        return Err(format!(
+            "Command '{}' is not allowed. Supported interpreters: {}",
+            command,
+            ALLOWED_COMMANDS.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// Apply the sandbox to a command before spawn: scrub the environment
+/// and install rlimits in the child. The caller adds the org-configured
+/// env vars afterwards.
+pub fn apply(cmd: &mut tokio::process::Command, limits: &SandboxLimits) {
+    // Managed processes never inherit the API server's environment
+    // (DATABASE_URL, signing keys, ...)
+    cmd.env_clear();
+    cmd.env("PATH", SANDBOX_PATH);
+    cmd.kill_on_drop(true);
+
+    #[cfg(unix)]
+    {
+        let max_memory_bytes = limits.max_memory_mb.saturating_mul(1024 * 1024);
+        let max_cpu_seconds = limits.max_cpu_seconds;
+        let max_open_files = limits.max_open_files;
+        // SAFETY: pre_exec runs between fork and exec; setsid/setrlimit
+        // are async-signal-safe and touch no allocator state
+        unsafe {
+            cmd.pre_exec(move || {
+                // Own session so the whole process tree dies together
+                libc::setsid();
+                set_rlimit(libc::RLIMIT_AS, max_memory_bytes);
+                if max_cpu_seconds > 0 {
+                    set_rlimit(libc::RLIMIT_CPU, max_cpu_seconds);
+                }
+                set_rlimit(libc::RLIMIT_NOFILE, max_open_files);
+                Ok(())
+            });
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+type RlimitResource = libc::__rlimit_resource_t;
+#[cfg(all(unix, not(target_os = "linux")))]
+type RlimitResource = libc::c_int;
+
+/// Best effort: failing to tighten a limit must not abort the spawn
+#[cfg(unix)]
+fn set_rlimit(resource: RlimitResource, value: u64) {
+    let rlim = libc::rlimit {
+        rlim_cur: value,
+        rlim_max: value,
+    };
+    unsafe {
+        libc::setrlimit(resource, &rlim);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_command_allows_interpreters() {
+        assert!(validate_command("npx").is_ok());
+        assert!(validate_command("python3").is_ok());
+    }
+
+    #[test]
+    fn test_validate_command_rejects_paths() {
+        let err = validate_command("/usr/bin/node").unwrap_err();
+        assert!(err.contains("bare interpreter name"));
+        assert!(validate_command("../node").is_err());
+    }
+
+    #[test]
+    fn test_validate_command_rejects_unknown_binaries() {
+        let err = validate_command("bash").unwrap_err();
+        assert!(err.contains("not allowed"));
+    }
+
+    #[test]
+    fn test_sandbox_limits_defaults() {
+        let limits: SandboxLimits = serde_json::from_str("{}").unwrap();
+        assert_eq!(limits.max_memory_mb, 512);
+        assert_eq!(limits.max_cpu_seconds, 0);
+        assert_eq!(limits.max_open_files, 256);
+    }
+}
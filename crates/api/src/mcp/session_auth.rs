@@ -0,0 +1,299 @@
+//! Cookie session management for upstream MCPs
+//!
+//! Some upstream MCPs authenticate with session cookies obtained from a
+//! login endpoint rather than static headers. The MCP config carries the
+//! login details (`auth_type: "cookie"`, `login_url`, `login_body`); the
+//! jar stores the resulting cookies AES-GCM encrypted (same envelope as
+//! 2FA secrets and OAuth tokens) and hands the proxy a ready `Cookie`
+//! header value on demand, logging in again when the jar is missing or
+//! expired. When an upstream returns 401 mid-session, the client
+//! invalidates the jar so the next request triggers a fresh login.
+
+use serde_json::Value;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::auth::totp::{decrypt_secret, encrypt_secret};
+
+/// Re-login when the session has less than this long to live
+const EXPIRY_MARGIN_SECS: i64 = 60;
+
+/// Login endpoints must respond within this window
+const LOGIN_REQUEST_TIMEOUT_SECS: u64 = 15;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SessionAuthError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("cookie encryption failed")]
+    Encryption,
+    #[error("invalid cookie auth config: {0}")]
+    Config(String),
+    #[error("login request failed: {0}")]
+    Login(String),
+}
+
+/// Login details parsed from an MCP's config blob
+struct LoginConfig {
+    login_url: String,
+    /// Credential fields posted to the login endpoint
+    body: Value,
+    /// "json" (default) or "form"
+    content_type: String,
+    /// Fallback session lifetime when Set-Cookie carries no Max-Age
+    session_ttl_secs: Option<i64>,
+}
+
+impl LoginConfig {
+    fn from_config(config: &Value) -> Result<Self, SessionAuthError> {
+        let login_url = config
+            .get("login_url")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| SessionAuthError::Config("login_url is required".to_string()))?
+            .to_string();
+        let body = config
+            .get("login_body")
+            .cloned()
+            .unwrap_or_else(|| Value::Object(Default::default()));
+        if !body.is_object() {
+            return Err(SessionAuthError::Config(
+                "login_body must be a JSON object".to_string(),
+            ));
+        }
+        let content_type = config
+            .get("login_content_type")
+            .and_then(|v| v.as_str())
+            .unwrap_or("json")
+            .to_string();
+        if content_type != "json" && content_type != "form" {
+            return Err(SessionAuthError::Config(
+                "login_content_type must be 'json' or 'form'".to_string(),
+            ));
+        }
+        let session_ttl_secs = config.get("session_ttl_secs").and_then(|v| v.as_i64());
+
+        Ok(Self {
+            login_url,
+            body,
+            content_type,
+            session_ttl_secs,
+        })
+    }
+}
+
+/// Stored jar row with decryption inputs
+#[derive(Debug, sqlx::FromRow)]
+struct JarRow {
+    cookies_encrypted: String,
+    cookies_nonce: String,
+    expires_at: Option<OffsetDateTime>,
+}
+
+/// Fetch a valid `Cookie` header value for an MCP, logging in first if the
+/// jar is missing or close to expiry
+pub async fn get_valid_cookie_header(
+    pool: &PgPool,
+    encryption_key: &[u8; 32],
+    org_id: Uuid,
+    mcp_id: Uuid,
+    config: &Value,
+) -> Result<String, SessionAuthError> {
+    let row: Option<JarRow> = sqlx::query_as(
+        r#"
+        SELECT cookies_encrypted, cookies_nonce, expires_at
+        FROM mcp_cookie_jars
+        WHERE mcp_id = $1 AND org_id = $2
+        "#,
+    )
+    .bind(mcp_id)
+    .bind(org_id)
+    .fetch_optional(pool)
+    .await?;
+
+    if let Some(row) = row {
+        // Still comfortably valid (or no lifetime was ever reported)
+        let expired = row.expires_at.is_some_and(|exp| {
+            exp - OffsetDateTime::now_utc() < time::Duration::seconds(EXPIRY_MARGIN_SECS)
+        });
+        if !expired {
+            return decrypt_secret(&row.cookies_encrypted, &row.cookies_nonce, encryption_key)
+                .map_err(|_| SessionAuthError::Encryption);
+        }
+    }
+
+    let login = LoginConfig::from_config(config)?;
+    let (cookie_header, expires_at) = perform_login(&login).await?;
+
+    let (enc, nonce) =
+        encrypt_secret(&cookie_header, encryption_key).map_err(|_| SessionAuthError::Encryption)?;
+    sqlx::query(
+        r#"
+        INSERT INTO mcp_cookie_jars (mcp_id, org_id, cookies_encrypted, cookies_nonce, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (mcp_id) DO UPDATE SET
+            cookies_encrypted = EXCLUDED.cookies_encrypted,
+            cookies_nonce = EXCLUDED.cookies_nonce,
+            expires_at = EXCLUDED.expires_at,
+            updated_at = NOW()
+        "#,
+    )
+    .bind(mcp_id)
+    .bind(org_id)
+    .bind(&enc)
+    .bind(&nonce)
+    .bind(expires_at)
+    .execute(pool)
+    .await?;
+
+    tracing::info!(mcp_id = %mcp_id, "Logged in to upstream MCP, session cookies stored");
+    Ok(cookie_header)
+}
+
+/// Drop the stored jar so the next request triggers a fresh login
+///
+/// Called by the client when an upstream rejects the session mid-flight
+/// (the re-login hook), and by the dashboard when credentials change.
+pub async fn invalidate_session(pool: &PgPool, mcp_id: Uuid) -> Result<(), SessionAuthError> {
+    sqlx::query("DELETE FROM mcp_cookie_jars WHERE mcp_id = $1")
+        .bind(mcp_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// POST the configured credentials to the login endpoint and collect the
+/// session cookies from Set-Cookie response headers
+async fn perform_login(
+    login: &LoginConfig,
+) -> Result<(String, Option<OffsetDateTime>), SessionAuthError> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(LOGIN_REQUEST_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| SessionAuthError::Login(e.to_string()))?;
+
+    let req = client.post(&login.login_url);
+    let req = if login.content_type == "form" {
+        let fields: Vec<(String, String)> = login
+            .body
+            .as_object()
+            .map(|obj| {
+                obj.iter()
+                    .map(|(k, v)| {
+                        let value = match v {
+                            Value::String(s) => s.clone(),
+                            other => other.to_string(),
+                        };
+                        (k.clone(), value)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        req.form(&fields)
+    } else {
+        req.json(&login.body)
+    };
+
+    let response = req
+        .send()
+        .await
+        .map_err(|e| SessionAuthError::Login(e.to_string()))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        // Never surface the body; login failures can echo credentials
+        return Err(SessionAuthError::Login(format!(
+            "login endpoint returned {}",
+            status
+        )));
+    }
+
+    let mut pairs: Vec<String> = Vec::new();
+    let mut min_max_age: Option<i64> = None;
+    for header in response.headers().get_all("set-cookie") {
+        let Ok(raw) = header.to_str() else { continue };
+        if let Some((pair, max_age)) = parse_set_cookie(raw) {
+            pairs.push(pair);
+            if let Some(age) = max_age {
+                min_max_age = Some(min_max_age.map_or(age, |m: i64| m.min(age)));
+            }
+        }
+    }
+    if pairs.is_empty() {
+        return Err(SessionAuthError::Login(
+            "login endpoint set no cookies".to_string(),
+        ));
+    }
+
+    // The shortest-lived cookie bounds the whole session
+    let ttl = min_max_age.or(login.session_ttl_secs);
+    let expires_at = ttl.map(|secs| OffsetDateTime::now_utc() + time::Duration::seconds(secs));
+
+    Ok((pairs.join("; "), expires_at))
+}
+
+/// Extract the `name=value` pair and Max-Age (if any) from a Set-Cookie
+/// header, discarding attributes like Path and HttpOnly
+fn parse_set_cookie(raw: &str) -> Option<(String, Option<i64>)> {
+    let mut segments = raw.split(';');
+    let pair = segments.next()?.trim();
+    if !pair.contains('=') || pair.is_empty() {
+        return None;
+    }
+    let max_age = segments.find_map(|seg| {
+        let (name, value) = seg.trim().split_once('=')?;
+        if name.eq_ignore_ascii_case("max-age") {
+            value.trim().parse::<i64>().ok()
+        } else {
+            None
+        }
+    });
+    Some((pair.to_string(), max_age))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_set_cookie_strips_attributes() {
+        let (pair, max_age) = parse_set_cookie("session=abc123; Path=/; HttpOnly; Secure").unwrap();
+        assert_eq!(pair, "session=abc123");
+        assert_eq!(max_age, None);
+    }
+
+    #[test]
+    fn test_parse_set_cookie_reads_max_age() {
+        let (pair, max_age) =
+            parse_set_cookie("sid=xyz; Max-Age=3600; Path=/; SameSite=Lax").unwrap();
+        assert_eq!(pair, "sid=xyz");
+        assert_eq!(max_age, Some(3600));
+    }
+
+    #[test]
+    fn test_parse_set_cookie_rejects_malformed() {
+        assert!(parse_set_cookie("not-a-cookie; Path=/").is_none());
+    }
+
+    // LoginConfig deliberately has no Debug impl (the body holds
+    // credentials), so these extract the error by hand
+    #[test]
+    fn test_login_config_requires_login_url() {
+        let Err(err) = LoginConfig::from_config(&json!({"auth_type": "cookie"})) else {
+            panic!("expected config error");
+        };
+        assert!(err.to_string().contains("login_url"));
+    }
+
+    #[test]
+    fn test_login_config_validates_content_type() {
+        let Err(err) = LoginConfig::from_config(&json!({
+            "login_url": "https://mcp.example.com/login",
+            "login_content_type": "xml"
+        })) else {
+            panic!("expected config error");
+        };
+        assert!(err.to_string().contains("login_content_type"));
+    }
+}
@@ -0,0 +1,297 @@
+//! Session-scoped context storage for MCP clients
+//!
+//! Client workflows often need small amounts of state between tool calls
+//! (cursor tokens, workspace selection). The proxy serves the special
+//! `context/get`, `context/set`, and `context/delete` JSON-RPC methods
+//! locally from a per-API-key KV store instead of forwarding them
+//! upstream. Values are AES-GCM encrypted at rest (same envelope as 2FA
+//! secrets and OAuth tokens), capped in size and count, and carry a
+//! mandatory TTL; every query is scoped to the authenticated org and API
+//! key, so one key can never read another's context.
+
+use serde_json::{json, Value};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::auth::totp::{decrypt_secret, encrypt_secret, parse_encryption_key};
+use crate::mcp::types::{JsonRpcError, JsonRpcRequest, JsonRpcResponse};
+use crate::state::AppState;
+
+/// Longest allowed context key (matches the column width)
+const MAX_KEY_LENGTH: usize = 128;
+
+/// Largest serialized value accepted - this is a scratchpad for cursor
+/// tokens and selections, not a document store
+const MAX_VALUE_BYTES: usize = 4096;
+
+/// Most live entries one API key may hold
+const MAX_ENTRIES_PER_KEY: i64 = 64;
+
+/// TTL applied when `ttl_seconds` is omitted
+const DEFAULT_TTL_SECONDS: i64 = 3600;
+
+/// Longest TTL a client may request (24h)
+const MAX_TTL_SECONDS: i64 = 86_400;
+
+/// Whether the proxy should serve `method` from the context store
+pub fn is_context_method(method: &str) -> bool {
+    method.starts_with("context/")
+}
+
+/// Dispatch one `context/*` request for the authenticated API key
+pub async fn handle_method(
+    state: &AppState,
+    org_id: Uuid,
+    api_key_id: Uuid,
+    request: &JsonRpcRequest,
+) -> JsonRpcResponse {
+    let id = request.id.clone();
+    let result = match request.method.as_str() {
+        "context/get" => handle_get(state, org_id, api_key_id, request.params.as_ref()).await,
+        "context/set" => handle_set(state, org_id, api_key_id, request.params.as_ref()).await,
+        "context/delete" => handle_delete(state, org_id, api_key_id, request.params.as_ref()).await,
+        _ => Err(JsonRpcError::method_not_found(&request.method)),
+    };
+
+    match result {
+        Ok(value) => JsonRpcResponse::success(id, value),
+        Err(error) => JsonRpcResponse::error(id, error),
+    }
+}
+
+/// Read one entry; absent and expired entries both read as `null`
+async fn handle_get(
+    state: &AppState,
+    org_id: Uuid,
+    api_key_id: Uuid,
+    params: Option<&Value>,
+) -> Result<Value, JsonRpcError> {
+    let key = extract_key(params)?;
+    let encryption_key = load_encryption_key(state)?;
+
+    let row: Option<(String, String, OffsetDateTime)> = sqlx::query_as(
+        r#"
+        SELECT value_encrypted, value_nonce, expires_at
+        FROM mcp_session_context
+        WHERE org_id = $1 AND api_key_id = $2 AND context_key = $3
+          AND expires_at > NOW()
+        "#,
+    )
+    .bind(org_id)
+    .bind(api_key_id)
+    .bind(&key)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(db_error)?;
+
+    let Some((value_encrypted, value_nonce, expires_at)) = row else {
+        return Ok(json!({ "key": key, "value": Value::Null }));
+    };
+
+    let plaintext = decrypt_secret(&value_encrypted, &value_nonce, &encryption_key)
+        .map_err(|_| JsonRpcError::internal_error("Failed to decrypt context value"))?;
+    let value: Value = serde_json::from_str(&plaintext)
+        .map_err(|_| JsonRpcError::internal_error("Stored context value is corrupt"))?;
+
+    Ok(json!({
+        "key": key,
+        "value": value,
+        "expires_at": format_rfc3339(expires_at),
+    }))
+}
+
+/// Write one entry, replacing any existing value under the same key
+async fn handle_set(
+    state: &AppState,
+    org_id: Uuid,
+    api_key_id: Uuid,
+    params: Option<&Value>,
+) -> Result<Value, JsonRpcError> {
+    let key = extract_key(params)?;
+    let value = params
+        .and_then(|p| p.get("value"))
+        .ok_or_else(|| JsonRpcError::invalid_params("value is required"))?;
+    let ttl_seconds = extract_ttl(params)?;
+    let encryption_key = load_encryption_key(state)?;
+
+    let serialized = serde_json::to_string(value)
+        .map_err(|_| JsonRpcError::invalid_params("value must be valid JSON"))?;
+    if serialized.len() > MAX_VALUE_BYTES {
+        return Err(JsonRpcError::invalid_params(format!(
+            "value exceeds {} bytes when serialized",
+            MAX_VALUE_BYTES
+        )));
+    }
+
+    let (value_encrypted, value_nonce) = encrypt_secret(&serialized, &encryption_key)
+        .map_err(|_| JsonRpcError::internal_error("Failed to encrypt context value"))?;
+
+    // Prune this key's expired entries so they don't count toward the cap
+    sqlx::query("DELETE FROM mcp_session_context WHERE api_key_id = $1 AND expires_at <= NOW()")
+        .bind(api_key_id)
+        .execute(&state.pool)
+        .await
+        .map_err(db_error)?;
+
+    let (count, exists): (i64, bool) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*),
+               COUNT(*) FILTER (WHERE context_key = $2) > 0
+        FROM mcp_session_context
+        WHERE api_key_id = $1
+        "#,
+    )
+    .bind(api_key_id)
+    .bind(&key)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(db_error)?;
+    if !exists && count >= MAX_ENTRIES_PER_KEY {
+        return Err(JsonRpcError::invalid_params(format!(
+            "context store is full ({} entries); delete a key or let one expire",
+            MAX_ENTRIES_PER_KEY
+        )));
+    }
+
+    let expires_at: (OffsetDateTime,) = sqlx::query_as(
+        r#"
+        INSERT INTO mcp_session_context
+            (org_id, api_key_id, context_key, value_encrypted, value_nonce, expires_at)
+        VALUES ($1, $2, $3, $4, $5, NOW() + make_interval(secs => $6))
+        ON CONFLICT (api_key_id, context_key) DO UPDATE SET
+            value_encrypted = EXCLUDED.value_encrypted,
+            value_nonce = EXCLUDED.value_nonce,
+            expires_at = EXCLUDED.expires_at,
+            updated_at = NOW()
+        RETURNING expires_at
+        "#,
+    )
+    .bind(org_id)
+    .bind(api_key_id)
+    .bind(&key)
+    .bind(&value_encrypted)
+    .bind(&value_nonce)
+    .bind(ttl_seconds as f64)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(db_error)?;
+
+    Ok(json!({
+        "key": key,
+        "expires_at": format_rfc3339(expires_at.0),
+    }))
+}
+
+/// Remove one entry; reports whether anything was deleted
+async fn handle_delete(
+    state: &AppState,
+    org_id: Uuid,
+    api_key_id: Uuid,
+    params: Option<&Value>,
+) -> Result<Value, JsonRpcError> {
+    let key = extract_key(params)?;
+
+    let result = sqlx::query(
+        "DELETE FROM mcp_session_context WHERE org_id = $1 AND api_key_id = $2 AND context_key = $3",
+    )
+    .bind(org_id)
+    .bind(api_key_id)
+    .bind(&key)
+    .execute(&state.pool)
+    .await
+    .map_err(db_error)?;
+
+    Ok(json!({
+        "key": key,
+        "deleted": result.rows_affected() > 0,
+    }))
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+fn extract_key(params: Option<&Value>) -> Result<String, JsonRpcError> {
+    let key = params
+        .and_then(|p| p.get("key"))
+        .and_then(|k| k.as_str())
+        .ok_or_else(|| JsonRpcError::invalid_params("key is required"))?;
+    validate_key(key)?;
+    Ok(key.to_string())
+}
+
+fn validate_key(key: &str) -> Result<(), JsonRpcError> {
+    if key.is_empty() {
+        return Err(JsonRpcError::invalid_params("key cannot be empty"));
+    }
+    if key.len() > MAX_KEY_LENGTH {
+        return Err(JsonRpcError::invalid_params(format!(
+            "key exceeds {} characters",
+            MAX_KEY_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+fn extract_ttl(params: Option<&Value>) -> Result<i64, JsonRpcError> {
+    match params.and_then(|p| p.get("ttl_seconds")) {
+        None => Ok(DEFAULT_TTL_SECONDS),
+        Some(ttl) => {
+            let ttl = ttl
+                .as_i64()
+                .ok_or_else(|| JsonRpcError::invalid_params("ttl_seconds must be an integer"))?;
+            if !(1..=MAX_TTL_SECONDS).contains(&ttl) {
+                return Err(JsonRpcError::invalid_params(format!(
+                    "ttl_seconds must be between 1 and {}",
+                    MAX_TTL_SECONDS
+                )));
+            }
+            Ok(ttl)
+        }
+    }
+}
+
+fn load_encryption_key(state: &AppState) -> Result<[u8; 32], JsonRpcError> {
+    parse_encryption_key(&state.config.totp_encryption_key)
+        .map_err(|_| JsonRpcError::internal_error("Context encryption is not configured"))
+}
+
+fn db_error(e: sqlx::Error) -> JsonRpcError {
+    tracing::error!(error = %e, "Session context query failed");
+    JsonRpcError::internal_error("Context storage unavailable")
+}
+
+fn format_rfc3339(dt: OffsetDateTime) -> String {
+    dt.format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_else(|_| dt.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_key_bounds() {
+        assert!(validate_key("workspace").is_ok());
+        assert!(validate_key("").is_err());
+        assert!(validate_key(&"k".repeat(MAX_KEY_LENGTH)).is_ok());
+        assert!(validate_key(&"k".repeat(MAX_KEY_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_extract_ttl_defaults_and_bounds() {
+        assert_eq!(extract_ttl(None).unwrap(), DEFAULT_TTL_SECONDS);
+        let params = json!({ "ttl_seconds": 120 });
+        assert_eq!(extract_ttl(Some(&params)).unwrap(), 120);
+        assert!(extract_ttl(Some(&json!({ "ttl_seconds": 0 }))).is_err());
+        assert!(extract_ttl(Some(&json!({ "ttl_seconds": MAX_TTL_SECONDS + 1 }))).is_err());
+        assert!(extract_ttl(Some(&json!({ "ttl_seconds": "soon" }))).is_err());
+    }
+
+    #[test]
+    fn test_is_context_method() {
+        assert!(is_context_method("context/get"));
+        assert!(is_context_method("context/set"));
+        assert!(!is_context_method("tools/call"));
+    }
+}
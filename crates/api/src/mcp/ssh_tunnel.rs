@@ -0,0 +1,430 @@
+//! SSH tunnel transport for upstream MCPs behind a bastion
+//!
+//! Some internal MCP servers are only reachable through a jump host. An MCP
+//! instance can declare an `ssh_tunnel` block in its config; the proxy then
+//! opens a local port forward through the bastion (using the system `ssh`
+//! binary, mirroring how stdio transports spawn processes) and sends HTTP
+//! traffic through it.
+//!
+//! Private keys are uploaded once per organization via the SSH key routes,
+//! stored AES-GCM encrypted with `TOTP_ENCRYPTION_KEY` (the same envelope
+//! used for 2FA secrets), and only written to a 0600 temp file for the
+//! lifetime of the tunnel process.
+//!
+//! Lifecycle: tunnels are spawned on first use, reused while the `ssh`
+//! process is alive and the forward accepts connections, and respawned
+//! transparently after a failure. Tunnel failures surface as
+//! [`McpClientError::TunnelError`](super::client::McpClientError) so callers
+//! can distinguish bastion problems from upstream application errors.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tokio::process::{Child, Command};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::auth::totp;
+
+/// How long to wait for the forwarded port to accept connections
+const TUNNEL_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Poll interval while waiting for the tunnel to come up
+const TUNNEL_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+fn default_ssh_port() -> u16 {
+    22
+}
+
+/// Per-MCP SSH tunnel configuration (the `ssh_tunnel` block of an MCP
+/// instance's config)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshTunnelConfig {
+    /// Bastion hostname
+    pub host: String,
+    /// Bastion SSH port (default 22)
+    #[serde(default = "default_ssh_port")]
+    pub port: u16,
+    /// SSH username on the bastion
+    pub username: String,
+    /// Encrypted private key to authenticate with (see `mcp_ssh_keys`)
+    pub key_id: Uuid,
+    /// Upstream host as seen from the bastion
+    pub remote_host: String,
+    /// Upstream port as seen from the bastion
+    pub remote_port: u16,
+    /// Owning org, injected when the transport is loaded (never from JSON)
+    /// so key lookups can't cross tenants
+    #[serde(skip)]
+    pub org_id: Option<Uuid>,
+}
+
+/// Tunnel-specific failures, kept distinct from upstream MCP errors
+#[derive(Debug, thiserror::Error)]
+pub enum TunnelError {
+    #[error("SSH key not found")]
+    KeyNotFound,
+
+    #[error("Failed to decrypt SSH key")]
+    KeyDecryption,
+
+    #[error("Invalid tunnel config: {0}")]
+    InvalidConfig(String),
+
+    #[error("Failed to start ssh process: {0}")]
+    Spawn(String),
+
+    #[error("Tunnel to {0} did not come up: {1}")]
+    ConnectFailed(String, String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// A running ssh port-forward process
+struct TunnelProcess {
+    child: Child,
+    local_port: u16,
+    key_path: PathBuf,
+}
+
+/// Manages ssh port-forward processes, keyed by MCP ID
+pub struct SshTunnelManager {
+    pool: PgPool,
+    encryption_key: [u8; 32],
+    tunnels: Arc<Mutex<HashMap<String, TunnelProcess>>>,
+}
+
+impl SshTunnelManager {
+    /// Create a manager that decrypts keys with the given 32-byte key
+    pub fn new(pool: PgPool, encryption_key: [u8; 32]) -> Self {
+        Self {
+            pool,
+            encryption_key,
+            tunnels: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Ensure a tunnel for this MCP is up, returning the local port to
+    /// connect to. Reuses a healthy tunnel, respawns a dead one.
+    pub async fn ensure_tunnel(
+        &self,
+        mcp_id: &str,
+        config: &SshTunnelConfig,
+    ) -> Result<u16, TunnelError> {
+        let mut tunnels = self.tunnels.lock().await;
+
+        // Reuse the existing tunnel if the process is alive and the
+        // forward still accepts connections
+        if let Some(tunnel) = tunnels.get_mut(mcp_id) {
+            let process_alive = tunnel.child.try_wait()?.is_none();
+            if process_alive && probe_port(tunnel.local_port).await {
+                return Ok(tunnel.local_port);
+            }
+
+            tracing::warn!(mcp_id = %mcp_id, "SSH tunnel died - respawning");
+            if let Some(mut dead) = tunnels.remove(mcp_id) {
+                let _ = dead.child.kill().await;
+                let _ = dead.child.wait().await; // reap zombie
+                let _ = tokio::fs::remove_file(&dead.key_path).await;
+            }
+        }
+
+        let tunnel = self.spawn_tunnel(mcp_id, config).await?;
+        let local_port = tunnel.local_port;
+        tunnels.insert(mcp_id.to_string(), tunnel);
+        Ok(local_port)
+    }
+
+    /// Load and decrypt the private key for a tunnel config, enforcing
+    /// that the key belongs to the MCP's organization
+    async fn load_private_key(&self, config: &SshTunnelConfig) -> Result<String, TunnelError> {
+        #[derive(sqlx::FromRow)]
+        struct KeyRow {
+            private_key_encrypted: String,
+            private_key_nonce: String,
+        }
+
+        let row: Option<KeyRow> = match config.org_id {
+            Some(org_id) => {
+                sqlx::query_as(
+                    "SELECT private_key_encrypted, private_key_nonce \
+                     FROM mcp_ssh_keys WHERE id = $1 AND org_id = $2",
+                )
+                .bind(config.key_id)
+                .bind(org_id)
+                .fetch_optional(&self.pool)
+                .await?
+            }
+            // No org context (shouldn't happen via the proxy path) - fail
+            // closed rather than allowing a cross-tenant key reference
+            None => None,
+        };
+
+        let row = row.ok_or(TunnelError::KeyNotFound)?;
+
+        totp::decrypt_secret(
+            &row.private_key_encrypted,
+            &row.private_key_nonce,
+            &self.encryption_key,
+        )
+        .map_err(|_| TunnelError::KeyDecryption)
+    }
+
+    /// Spawn an ssh port-forward and wait for it to accept connections
+    async fn spawn_tunnel(
+        &self,
+        mcp_id: &str,
+        config: &SshTunnelConfig,
+    ) -> Result<TunnelProcess, TunnelError> {
+        validate_argv_fields(config)?;
+
+        let private_key = self.load_private_key(config).await?;
+
+        // Write the key to a 0600 temp file for the life of the tunnel
+        let key_path = std::env::temp_dir().join(format!("plexmcp-tunnel-{}.key", Uuid::new_v4()));
+        tokio::fs::write(&key_path, &private_key).await?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            tokio::fs::set_permissions(&key_path, std::fs::Permissions::from_mode(0o600)).await?;
+        }
+
+        let local_port = pick_free_port().await?;
+        let forward = format!(
+            "127.0.0.1:{}:{}:{}",
+            local_port, config.remote_host, config.remote_port
+        );
+        let destination = format!("{}@{}", config.username, config.host);
+
+        let spawn_result = Command::new("ssh")
+            .arg("-N") // no remote command, forward only
+            .arg("-o")
+            .arg("BatchMode=yes") // never prompt
+            .arg("-o")
+            .arg("ExitOnForwardFailure=yes")
+            .arg("-o")
+            .arg("StrictHostKeyChecking=accept-new")
+            .arg("-o")
+            .arg("ServerAliveInterval=15")
+            .arg("-o")
+            .arg("ServerAliveCountMax=3")
+            .arg("-p")
+            .arg(config.port.to_string())
+            .arg("-i")
+            .arg(&key_path)
+            .arg("-L")
+            .arg(&forward)
+            .arg("--") // end of options: the destination is never an option
+            .arg(&destination)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true)
+            .spawn();
+
+        let mut child = match spawn_result {
+            Ok(child) => child,
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&key_path).await;
+                return Err(TunnelError::Spawn(e.to_string()));
+            }
+        };
+
+        // Wait for the forwarded port to accept connections (or the ssh
+        // process to exit, which means auth/connect failed)
+        let deadline = tokio::time::Instant::now() + TUNNEL_CONNECT_TIMEOUT;
+        loop {
+            if probe_port(local_port).await {
+                tracing::info!(
+                    mcp_id = %mcp_id,
+                    bastion = %config.host,
+                    local_port = local_port,
+                    "SSH tunnel established"
+                );
+                return Ok(TunnelProcess {
+                    child,
+                    local_port,
+                    key_path,
+                });
+            }
+
+            if let Some(status) = child.try_wait()? {
+                let stderr = read_stderr(&mut child).await;
+                let _ = tokio::fs::remove_file(&key_path).await;
+                return Err(TunnelError::ConnectFailed(
+                    config.host.clone(),
+                    format!("ssh exited with {}: {}", status, stderr.trim()),
+                ));
+            }
+
+            if tokio::time::Instant::now() >= deadline {
+                let _ = child.kill().await;
+                let _ = child.wait().await; // reap zombie
+                let stderr = read_stderr(&mut child).await;
+                let _ = tokio::fs::remove_file(&key_path).await;
+                return Err(TunnelError::ConnectFailed(
+                    config.host.clone(),
+                    if stderr.trim().is_empty() {
+                        "timed out waiting for port forward".to_string()
+                    } else {
+                        stderr.trim().to_string()
+                    },
+                ));
+            }
+
+            tokio::time::sleep(TUNNEL_POLL_INTERVAL).await;
+        }
+    }
+}
+
+/// Reject org-supplied config fields that could be misread by ssh
+///
+/// These values land in ssh argv: a `username` or `host` starting with `-`
+/// (e.g. `-oProxyCommand=...`) could be parsed as an option, and a
+/// `remote_host` containing `:` would corrupt the `-L` forward spec.
+fn validate_argv_fields(config: &SshTunnelConfig) -> Result<(), TunnelError> {
+    for (field, value) in [("host", &config.host), ("username", &config.username)] {
+        if value.is_empty()
+            || value.starts_with('-')
+            || value.contains('@')
+            || value.contains(char::is_whitespace)
+        {
+            return Err(TunnelError::InvalidConfig(format!(
+                "{} must be non-empty and free of '@', whitespace and a leading '-'",
+                field
+            )));
+        }
+    }
+
+    let remote_host = &config.remote_host;
+    if remote_host.is_empty()
+        || remote_host.starts_with('-')
+        || remote_host.contains(':')
+        || remote_host.contains(char::is_whitespace)
+    {
+        return Err(TunnelError::InvalidConfig(
+            "remote_host must be non-empty and free of ':', whitespace and a leading '-'"
+                .to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Bind to port 0 to let the kernel pick a free local port
+async fn pick_free_port() -> Result<u16, std::io::Error> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    Ok(listener.local_addr()?.port())
+}
+
+/// Whether the forwarded local port accepts TCP connections
+async fn probe_port(port: u16) -> bool {
+    matches!(
+        tokio::time::timeout(
+            Duration::from_millis(500),
+            tokio::net::TcpStream::connect(("127.0.0.1", port)),
+        )
+        .await,
+        Ok(Ok(_))
+    )
+}
+
+/// Read whatever the ssh process wrote to stderr (best effort)
+async fn read_stderr(child: &mut Child) -> String {
+    use tokio::io::AsyncReadExt;
+
+    let Some(mut stderr) = child.stderr.take() else {
+        return String::new();
+    };
+    let mut buf = String::new();
+    let _ = tokio::time::timeout(Duration::from_millis(500), stderr.read_to_string(&mut buf)).await;
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tunnel_config_parsing() {
+        let json = serde_json::json!({
+            "host": "bastion.internal",
+            "username": "deploy",
+            "key_id": "7c0e6a1e-9f5b-4f9b-bd6a-111111111111",
+            "remote_host": "mcp.private",
+            "remote_port": 8080
+        });
+
+        let config: SshTunnelConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(config.port, 22); // default
+        assert_eq!(config.remote_port, 8080);
+        // org_id is never read from JSON - it's injected by the loader
+        assert!(config.org_id.is_none());
+    }
+
+    #[test]
+    fn test_argv_fields_validation() {
+        let mut config: SshTunnelConfig = serde_json::from_value(serde_json::json!({
+            "host": "bastion.internal",
+            "username": "deploy",
+            "key_id": "7c0e6a1e-9f5b-4f9b-bd6a-111111111111",
+            "remote_host": "mcp.private",
+            "remote_port": 8080
+        }))
+        .unwrap();
+        assert!(validate_argv_fields(&config).is_ok());
+
+        // Option-like username must never reach ssh argv
+        config.username = "-oProxyCommand=touch /tmp/pwned".to_string();
+        assert!(validate_argv_fields(&config).is_err());
+        config.username = "deploy".to_string();
+
+        config.host = "-bastion".to_string();
+        assert!(validate_argv_fields(&config).is_err());
+        config.host = "user@bastion".to_string();
+        assert!(validate_argv_fields(&config).is_err());
+        config.host = "bastion.internal".to_string();
+
+        // A ':' in remote_host would corrupt the -L forward spec
+        config.remote_host = "mcp.private:22:evil".to_string();
+        assert!(validate_argv_fields(&config).is_err());
+        config.remote_host = String::new();
+        assert!(validate_argv_fields(&config).is_err());
+    }
+
+    #[test]
+    fn test_org_id_not_deserialized_from_json() {
+        let json = serde_json::json!({
+            "host": "bastion.internal",
+            "username": "deploy",
+            "key_id": "7c0e6a1e-9f5b-4f9b-bd6a-111111111111",
+            "remote_host": "mcp.private",
+            "remote_port": 8080,
+            "org_id": "7c0e6a1e-9f5b-4f9b-bd6a-222222222222"
+        });
+
+        let config: SshTunnelConfig = serde_json::from_value(json).unwrap();
+        assert!(config.org_id.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pick_free_port() {
+        let port = pick_free_port().await.unwrap();
+        assert!(port > 0);
+    }
+
+    #[tokio::test]
+    async fn test_probe_unused_port_fails() {
+        let port = pick_free_port().await.unwrap();
+        // Nothing is listening after the probe listener is dropped
+        assert!(!probe_port(port).await);
+    }
+}
@@ -1,11 +1,17 @@
-//! SSE (Server-Sent Events) streaming support for MCP proxy
+//! SSE (Server-Sent Events) and WebSocket streaming support for MCP proxy
 //!
 //! Enables streaming partial results as MCPs respond in aggregation requests,
-//! providing better user experience for long-running operations.
+//! and holds persistent sessions (GET /mcp as SSE, or a WebSocket upgrade)
+//! so clients like Claude Desktop can receive server-initiated notifications
+//! and progress events between requests.
+
+use std::collections::HashMap;
 
 use super::types::{JsonRpcError, JsonRpcResponse};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tokio::sync::{mpsc, RwLock};
+use uuid::Uuid;
 
 /// Events that can be streamed to the client via SSE
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +36,15 @@ pub enum McpStreamEvent {
     /// Error occurred
     Error { error: JsonRpcError },
 
+    /// An upstream MCP's tool list changed since the last capability refresh
+    ToolsListChanged {
+        mcp_id: Uuid,
+        /// Source MCP name (the namespace prefix in aggregated tool names)
+        mcp_name: String,
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+
     /// Heartbeat to keep connection alive
     Heartbeat,
 }
@@ -47,7 +62,155 @@ impl McpStreamEvent {
             McpStreamEvent::PartialResult { .. } => "partial",
             McpStreamEvent::FinalResult { .. } => "result",
             McpStreamEvent::Error { .. } => "error",
+            McpStreamEvent::ToolsListChanged { .. } => "tools_list_changed",
             McpStreamEvent::Heartbeat => "heartbeat",
         }
     }
+
+    /// Wrap the event as a JSON-RPC notification for WebSocket transport
+    ///
+    /// WebSocket clients speak plain JSON-RPC, so server-initiated events
+    /// arrive as `notifications/message` with the event as params. Tool
+    /// list changes use the spec-defined `notifications/tools/list_changed`
+    /// method so MCP clients re-list without custom handling.
+    pub fn to_jsonrpc_notification(&self) -> Value {
+        let method = match self {
+            McpStreamEvent::ToolsListChanged { .. } => "notifications/tools/list_changed",
+            _ => "notifications/message",
+        };
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": self,
+        })
+    }
+}
+
+/// Buffered events per session before the sender starts dropping
+const SESSION_CHANNEL_CAPACITY: usize = 32;
+
+/// Registry of open streaming sessions (SSE and WebSocket)
+///
+/// Each persistent connection registers a session keyed by a generated
+/// session ID. Server-side code can push events to one session or to every
+/// open session of an org; sessions whose client has disconnected are
+/// pruned on the next delivery attempt.
+pub struct StreamingSessionManager {
+    sessions: RwLock<HashMap<Uuid, StreamingSession>>,
+}
+
+struct StreamingSession {
+    org_id: Uuid,
+    tx: mpsc::Sender<McpStreamEvent>,
+}
+
+impl StreamingSessionManager {
+    pub fn new() -> Self {
+        Self {
+            sessions: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Register a new session; the receiver is consumed by the transport loop
+    pub async fn open(&self, org_id: Uuid) -> (Uuid, mpsc::Receiver<McpStreamEvent>) {
+        let (tx, rx) = mpsc::channel(SESSION_CHANNEL_CAPACITY);
+        let session_id = Uuid::new_v4();
+        self.sessions
+            .write()
+            .await
+            .insert(session_id, StreamingSession { org_id, tx });
+        (session_id, rx)
+    }
+
+    /// Remove a session when its connection closes
+    pub async fn close(&self, session_id: Uuid) {
+        self.sessions.write().await.remove(&session_id);
+    }
+
+    /// Push an event to every open session of an org
+    ///
+    /// Returns the number of sessions the event was delivered to. Sessions
+    /// whose receiver has gone away are removed.
+    pub async fn notify_org(&self, org_id: Uuid, event: McpStreamEvent) -> usize {
+        let mut sessions = self.sessions.write().await;
+        let mut delivered = 0;
+        let mut dead = Vec::new();
+        for (id, session) in sessions.iter() {
+            if session.org_id != org_id {
+                continue;
+            }
+            match session.tx.try_send(event.clone()) {
+                Ok(()) => delivered += 1,
+                Err(mpsc::error::TrySendError::Closed(_)) => dead.push(*id),
+                Err(mpsc::error::TrySendError::Full(_)) => {
+                    // Slow consumer: drop the event rather than block
+                }
+            }
+        }
+        for id in dead {
+            sessions.remove(&id);
+        }
+        delivered
+    }
+
+    /// Number of currently open sessions (all orgs)
+    pub async fn session_count(&self) -> usize {
+        self.sessions.read().await.len()
+    }
+}
+
+impl Default for StreamingSessionManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod session_tests {
+    use super::*;
+
+    #[test]
+    fn test_tools_list_changed_uses_spec_method() {
+        let event = McpStreamEvent::ToolsListChanged {
+            mcp_id: Uuid::new_v4(),
+            mcp_name: "github".to_string(),
+            added: vec!["create_issue".to_string()],
+            removed: vec![],
+        };
+        let notification = event.to_jsonrpc_notification();
+        assert_eq!(notification["method"], "notifications/tools/list_changed");
+        assert_eq!(notification["params"]["mcp_name"], "github");
+
+        let heartbeat = McpStreamEvent::Heartbeat.to_jsonrpc_notification();
+        assert_eq!(heartbeat["method"], "notifications/message");
+    }
+
+    #[tokio::test]
+    async fn test_notify_org_is_scoped() {
+        let manager = StreamingSessionManager::new();
+        let org_a = Uuid::new_v4();
+        let org_b = Uuid::new_v4();
+
+        let (_id_a, mut rx_a) = manager.open(org_a).await;
+        let (_id_b, mut rx_b) = manager.open(org_b).await;
+
+        let delivered = manager.notify_org(org_a, McpStreamEvent::Heartbeat).await;
+        assert_eq!(delivered, 1);
+        assert!(rx_a.try_recv().is_ok());
+        assert!(rx_b.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dead_sessions_are_pruned() {
+        let manager = StreamingSessionManager::new();
+        let org = Uuid::new_v4();
+
+        let (_session_id, rx) = manager.open(org).await;
+        drop(rx);
+
+        assert_eq!(manager.session_count().await, 1);
+        let delivered = manager.notify_org(org, McpStreamEvent::Heartbeat).await;
+        assert_eq!(delivered, 0);
+        assert_eq!(manager.session_count().await, 0);
+    }
 }
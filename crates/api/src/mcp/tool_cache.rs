@@ -0,0 +1,267 @@
+//! Opt-in result cache for repeated identical tools/call requests
+//!
+//! Read-heavy tools (search, list operations) are often called with the same
+//! arguments many times in a row. An MCP can opt in by adding a `cache`
+//! object to its config; identical calls within the TTL are then served from
+//! an in-memory per-org cache instead of hitting the upstream. Calls to
+//! tools outside the cacheable set are treated as writes and invalidate the
+//! MCP's cached entries, so stale reads after a mutation are bounded by a
+//! single round trip.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Upper bound on cached entries across all orgs (memory guard)
+const MAX_ENTRIES: usize = 10_000;
+
+/// Per-MCP cache settings parsed from the `cache` key of the config JSON
+#[derive(Debug, Clone)]
+pub struct ToolCacheConfig {
+    /// How long a cached result stays valid
+    pub ttl: Duration,
+    /// Tool names eligible for caching (None = all tools)
+    pub tools: Option<Vec<String>>,
+    /// Whether calls to non-cacheable tools flush the MCP's entries
+    pub invalidate_on_write: bool,
+}
+
+impl ToolCacheConfig {
+    /// Parse cache settings from an MCP config, if the MCP opted in
+    ///
+    /// Returns None when there is no `cache` object or `ttl_seconds` is
+    /// missing/zero, which disables caching entirely for that MCP.
+    pub fn from_config(config: &Value) -> Option<Self> {
+        let cache = config.get("cache")?;
+        let ttl_seconds = cache.get("ttl_seconds")?.as_u64()?;
+        if ttl_seconds == 0 {
+            return None;
+        }
+
+        let tools = cache.get("tools").and_then(|v| v.as_array()).map(|arr| {
+            arr.iter()
+                .filter_map(|t| t.as_str())
+                .map(|t| t.to_string())
+                .collect()
+        });
+
+        let invalidate_on_write = cache
+            .get("invalidate_on_write")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+
+        Some(Self {
+            ttl: Duration::from_secs(ttl_seconds),
+            tools,
+            invalidate_on_write,
+        })
+    }
+
+    /// Whether results for this tool may be cached
+    ///
+    /// Tool names are the unprefixed upstream names (no `mcp_name:` prefix).
+    pub fn is_cacheable(&self, tool_name: &str) -> bool {
+        match &self.tools {
+            Some(tools) => tools.iter().any(|t| t == tool_name),
+            None => true,
+        }
+    }
+}
+
+/// Cache key: one entry per identical call per org
+///
+/// Arguments are keyed by their serialized JSON. serde_json preserves object
+/// key order from the wire, so byte-identical requests hit the same entry;
+/// semantically-equal requests with reordered keys simply miss.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    org_id: Uuid,
+    mcp_id: Uuid,
+    tool_name: String,
+    arguments: String,
+}
+
+struct CacheEntry {
+    result: Value,
+    expires_at: Instant,
+}
+
+/// In-memory tools/call result cache shared across requests
+pub struct ToolCallCache {
+    entries: RwLock<HashMap<CacheKey, CacheEntry>>,
+}
+
+impl ToolCallCache {
+    pub fn new() -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Look up a cached result for an identical prior call
+    pub async fn get(
+        &self,
+        org_id: Uuid,
+        mcp_id: Uuid,
+        tool_name: &str,
+        arguments: &Value,
+    ) -> Option<Value> {
+        let key = Self::key(org_id, mcp_id, tool_name, arguments)?;
+        let entries = self.entries.read().await;
+        let entry = entries.get(&key)?;
+        if entry.expires_at <= Instant::now() {
+            return None;
+        }
+        Some(entry.result.clone())
+    }
+
+    /// Store a successful tool result
+    pub async fn insert(
+        &self,
+        org_id: Uuid,
+        mcp_id: Uuid,
+        tool_name: &str,
+        arguments: &Value,
+        result: Value,
+        ttl: Duration,
+    ) {
+        let Some(key) = Self::key(org_id, mcp_id, tool_name, arguments) else {
+            return;
+        };
+        let mut entries = self.entries.write().await;
+        if entries.len() >= MAX_ENTRIES && !entries.contains_key(&key) {
+            // Try to reclaim space from expired entries before giving up
+            let now = Instant::now();
+            entries.retain(|_, e| e.expires_at > now);
+            if entries.len() >= MAX_ENTRIES {
+                tracing::debug!("Tool call cache full, skipping insert");
+                return;
+            }
+        }
+        entries.insert(
+            key,
+            CacheEntry {
+                result,
+                expires_at: Instant::now() + ttl,
+            },
+        );
+    }
+
+    /// Drop all cached entries for one MCP (write invalidation or manual)
+    ///
+    /// Returns the number of entries removed.
+    pub async fn invalidate_mcp(&self, org_id: Uuid, mcp_id: Uuid) -> usize {
+        let mut entries = self.entries.write().await;
+        let before = entries.len();
+        entries.retain(|k, _| k.org_id != org_id || k.mcp_id != mcp_id);
+        before - entries.len()
+    }
+
+    /// Remove expired entries (called from the periodic cleanup task)
+    pub async fn purge_expired(&self) {
+        let now = Instant::now();
+        let mut entries = self.entries.write().await;
+        entries.retain(|_, e| e.expires_at > now);
+    }
+
+    fn key(org_id: Uuid, mcp_id: Uuid, tool_name: &str, arguments: &Value) -> Option<CacheKey> {
+        let arguments = serde_json::to_string(arguments).ok()?;
+        Some(CacheKey {
+            org_id,
+            mcp_id,
+            tool_name: tool_name.to_string(),
+            arguments,
+        })
+    }
+}
+
+impl Default for ToolCallCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_config_parsing() {
+        // No cache key = disabled
+        assert!(ToolCacheConfig::from_config(&json!({})).is_none());
+        // Zero TTL = disabled
+        assert!(ToolCacheConfig::from_config(&json!({"cache": {"ttl_seconds": 0}})).is_none());
+
+        let cfg = ToolCacheConfig::from_config(&json!({
+            "cache": {"ttl_seconds": 60, "tools": ["search"]}
+        }))
+        .unwrap();
+        assert_eq!(cfg.ttl, Duration::from_secs(60));
+        assert!(cfg.is_cacheable("search"));
+        assert!(!cfg.is_cacheable("create_issue"));
+        assert!(cfg.invalidate_on_write);
+
+        // No tools list = everything cacheable
+        let cfg = ToolCacheConfig::from_config(&json!({"cache": {"ttl_seconds": 60}})).unwrap();
+        assert!(cfg.is_cacheable("anything"));
+    }
+
+    #[tokio::test]
+    async fn test_hit_and_expiry() {
+        let cache = ToolCallCache::new();
+        let org = Uuid::new_v4();
+        let mcp = Uuid::new_v4();
+        let args = json!({"query": "rust"});
+
+        assert!(cache.get(org, mcp, "search", &args).await.is_none());
+
+        cache
+            .insert(
+                org,
+                mcp,
+                "search",
+                &args,
+                json!({"content": []}),
+                Duration::from_secs(60),
+            )
+            .await;
+        assert!(cache.get(org, mcp, "search", &args).await.is_some());
+
+        // Different arguments miss
+        assert!(cache
+            .get(org, mcp, "search", &json!({"query": "go"}))
+            .await
+            .is_none());
+
+        // Expired entries miss
+        cache
+            .insert(org, mcp, "old", &args, json!({}), Duration::from_secs(0))
+            .await;
+        assert!(cache.get(org, mcp, "old", &args).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_mcp_is_scoped() {
+        let cache = ToolCallCache::new();
+        let org = Uuid::new_v4();
+        let mcp_a = Uuid::new_v4();
+        let mcp_b = Uuid::new_v4();
+        let args = json!({});
+
+        let ttl = Duration::from_secs(60);
+        cache
+            .insert(org, mcp_a, "search", &args, json!(1), ttl)
+            .await;
+        cache
+            .insert(org, mcp_b, "search", &args, json!(2), ttl)
+            .await;
+
+        assert_eq!(cache.invalidate_mcp(org, mcp_a).await, 1);
+        assert!(cache.get(org, mcp_a, "search", &args).await.is_none());
+        assert!(cache.get(org, mcp_b, "search", &args).await.is_some());
+    }
+}
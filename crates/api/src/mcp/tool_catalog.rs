@@ -0,0 +1,160 @@
+//! Aggregated tool catalog with per-org caching
+//!
+//! Backs `GET /mcps/tools`: clients previously had to call tools/list on
+//! each upstream MCP to discover namespaced tools. The catalog merges one
+//! tools/list aggregation pass across all of an org's MCPs and caches the
+//! result briefly so dashboard searches don't fan out to every upstream
+//! on each keystroke. Entries are invalidated when an org's MCPs change
+//! and expire on a short TTL otherwise.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::types::{McpError, Tool};
+
+/// How long a merged catalog stays fresh before the next request rebuilds it
+const CATALOG_TTL: Duration = Duration::from_secs(60);
+
+/// One tool in the merged catalog, with its source MCP attached
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogTool {
+    /// Namespaced tool name as used by tools/call, e.g. "github:create_issue"
+    pub name: String,
+    /// Source MCP name (the namespace prefix)
+    pub mcp_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub input_schema: serde_json::Value,
+}
+
+/// Merged catalog for one org, including partial-failure errors so
+/// clients can tell an empty catalog from an unreachable upstream
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    pub tools: Vec<CatalogTool>,
+    pub errors: Vec<McpError>,
+}
+
+impl Catalog {
+    /// Build a catalog from prefixed tools/list aggregation output
+    pub fn from_aggregated(tools: Vec<Tool>, errors: Vec<McpError>) -> Self {
+        let tools = tools
+            .into_iter()
+            .map(|tool| {
+                // Aggregated tool names are "<mcp>:<tool>" (see McpRouter)
+                let mcp_name = tool
+                    .name
+                    .split_once(':')
+                    .map(|(prefix, _)| prefix.to_string())
+                    .unwrap_or_default();
+                CatalogTool {
+                    name: tool.name,
+                    mcp_name,
+                    description: tool.description,
+                    input_schema: tool.input_schema,
+                }
+            })
+            .collect();
+        Self { tools, errors }
+    }
+}
+
+struct CachedCatalog {
+    catalog: Arc<Catalog>,
+    fetched_at: Instant,
+}
+
+/// Per-org cache of merged tool catalogs
+pub struct ToolCatalogCache {
+    entries: RwLock<HashMap<Uuid, CachedCatalog>>,
+}
+
+impl ToolCatalogCache {
+    pub fn new() -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Get the cached catalog for an org if it is still fresh
+    pub async fn get(&self, org_id: Uuid) -> Option<Arc<Catalog>> {
+        let entries = self.entries.read().await;
+        entries
+            .get(&org_id)
+            .filter(|c| c.fetched_at.elapsed() < CATALOG_TTL)
+            .map(|c| Arc::clone(&c.catalog))
+    }
+
+    /// Store a freshly built catalog for an org
+    pub async fn insert(&self, org_id: Uuid, catalog: Catalog) -> Arc<Catalog> {
+        let catalog = Arc::new(catalog);
+        let mut entries = self.entries.write().await;
+        entries.insert(
+            org_id,
+            CachedCatalog {
+                catalog: Arc::clone(&catalog),
+                fetched_at: Instant::now(),
+            },
+        );
+        catalog
+    }
+
+    /// Drop the cached catalog for an org (called when its MCPs change)
+    pub async fn invalidate(&self, org_id: Uuid) {
+        let mut entries = self.entries.write().await;
+        entries.remove(&org_id);
+    }
+}
+
+impl Default for ToolCatalogCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tool(name: &str) -> Tool {
+        Tool {
+            name: name.to_string(),
+            description: Some(format!("{} description", name)),
+            input_schema: json!({"type": "object"}),
+        }
+    }
+
+    #[test]
+    fn test_catalog_extracts_mcp_name_from_prefix() {
+        let catalog = Catalog::from_aggregated(
+            vec![tool("github:create_issue"), tool("unprefixed")],
+            vec![],
+        );
+        assert_eq!(catalog.tools[0].mcp_name, "github");
+        assert_eq!(catalog.tools[0].name, "github:create_issue");
+        assert_eq!(catalog.tools[1].mcp_name, "");
+    }
+
+    #[tokio::test]
+    async fn test_cache_hit_and_invalidate() {
+        let cache = ToolCatalogCache::new();
+        let org_id = Uuid::new_v4();
+
+        assert!(cache.get(org_id).await.is_none());
+
+        cache
+            .insert(org_id, Catalog::from_aggregated(vec![tool("a:b")], vec![]))
+            .await;
+        let cached = cache.get(org_id).await.expect("catalog should be cached");
+        assert_eq!(cached.tools.len(), 1);
+
+        cache.invalidate(org_id).await;
+        assert!(cache.get(org_id).await.is_none());
+    }
+}
@@ -0,0 +1,149 @@
+//! Differential tools/list change detection
+//!
+//! Both health check paths (the background sweep and the manual
+//! `/mcps/:mcp_id/health-check` route) already fetch each upstream's tool
+//! list, but clients only noticed additions or removals on their next
+//! re-list. This module diffs the freshly fetched tool names against the
+//! snapshot stored in `mcp_instances.tools_json` and, when they differ,
+//! fans the change out: open streaming sessions of the org receive a
+//! `notifications/tools/list_changed` push, the org's webhook endpoints
+//! get an `mcp.tools_changed` event, and the cached tool catalog is
+//! invalidated so the next `GET /mcps/tools` rebuilds.
+
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::streaming::{McpStreamEvent, StreamingSessionManager};
+use super::tool_catalog::ToolCatalogCache;
+use super::types::Tool;
+
+/// Tools added and removed between two capability refreshes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolListChange {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// Extract sorted tool names from a stored `tools_json` snapshot
+///
+/// Returns None when there is no snapshot (first successful check) or the
+/// stored value doesn't parse as a tool list, so spurious "everything was
+/// added" diffs are never produced from a missing baseline.
+pub fn tool_names(tools_json: Option<&Value>) -> Option<Vec<String>> {
+    let tools: Vec<Tool> = serde_json::from_value(tools_json?.clone()).ok()?;
+    let mut names: Vec<String> = tools.into_iter().map(|t| t.name).collect();
+    names.sort();
+    Some(names)
+}
+
+/// Diff two sorted name lists; None when nothing changed
+pub fn detect(previous: &[String], current: &[String]) -> Option<ToolListChange> {
+    let added: Vec<String> = current
+        .iter()
+        .filter(|name| !previous.contains(name))
+        .cloned()
+        .collect();
+    let removed: Vec<String> = previous
+        .iter()
+        .filter(|name| !current.contains(name))
+        .cloned()
+        .collect();
+
+    if added.is_empty() && removed.is_empty() {
+        None
+    } else {
+        Some(ToolListChange { added, removed })
+    }
+}
+
+/// Fan a detected change out to everything that reacts to tool availability
+///
+/// Invalidates the org's cached tool catalog, pushes the change to open
+/// streaming/WebSocket sessions, and queues an org webhook event. All
+/// delivery is best-effort; a change notice must never fail the health
+/// check that detected it.
+pub async fn notify(
+    pool: &PgPool,
+    streams: &StreamingSessionManager,
+    tool_catalog: &ToolCatalogCache,
+    org_id: Uuid,
+    mcp_id: Uuid,
+    mcp_name: &str,
+    change: ToolListChange,
+) {
+    tracing::info!(
+        mcp_id = %mcp_id,
+        org_id = %org_id,
+        added = change.added.len(),
+        removed = change.removed.len(),
+        "Upstream tool list changed"
+    );
+
+    tool_catalog.invalidate(org_id).await;
+
+    let delivered = streams
+        .notify_org(
+            org_id,
+            McpStreamEvent::ToolsListChanged {
+                mcp_id,
+                mcp_name: mcp_name.to_string(),
+                added: change.added.clone(),
+                removed: change.removed.clone(),
+            },
+        )
+        .await;
+    tracing::debug!(
+        mcp_id = %mcp_id,
+        sessions = delivered,
+        "Pushed tools/list_changed to streaming sessions"
+    );
+
+    plexmcp_shared::org_webhooks::emit(
+        pool,
+        org_id,
+        "mcp.tools_changed",
+        serde_json::json!({
+            "mcp_id": mcp_id,
+            "mcp_name": mcp_name,
+            "added": change.added,
+            "removed": change.removed,
+        }),
+    )
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_tool_names_parses_and_sorts() {
+        let snapshot = json!([
+            {"name": "search", "inputSchema": {"type": "object"}},
+            {"name": "create_issue", "inputSchema": {"type": "object"}},
+        ]);
+        assert_eq!(
+            tool_names(Some(&snapshot)),
+            Some(names(&["create_issue", "search"]))
+        );
+
+        // No baseline or a malformed one yields no names at all
+        assert_eq!(tool_names(None), None);
+        assert_eq!(tool_names(Some(&json!("not a list"))), None);
+    }
+
+    #[test]
+    fn test_detect_added_and_removed() {
+        assert_eq!(detect(&names(&["a", "b"]), &names(&["a", "b"])), None);
+
+        let change = detect(&names(&["a", "b"]), &names(&["b", "c"])).unwrap();
+        assert_eq!(change.added, names(&["c"]));
+        assert_eq!(change.removed, names(&["a"]));
+    }
+}
@@ -80,6 +80,10 @@ impl JsonRpcError {
     pub const METHOD_NOT_FOUND: i32 = -32601;
     pub const INVALID_PARAMS: i32 = -32602;
     pub const INTERNAL_ERROR: i32 = -32603;
+    /// Request or response blocked by an org moderation rule
+    pub const MODERATION_BLOCKED: i32 = -32010;
+    /// Upstream response exceeded the configured size limit and was truncated
+    pub const RESPONSE_TOO_LARGE: i32 = -32011;
 
     pub fn parse_error(msg: impl Into<String>) -> Self {
         Self {
@@ -120,6 +124,28 @@ impl JsonRpcError {
             data: None,
         }
     }
+
+    pub fn moderation_blocked(msg: impl Into<String>) -> Self {
+        Self {
+            code: Self::MODERATION_BLOCKED,
+            message: msg.into(),
+            data: None,
+        }
+    }
+
+    pub fn response_too_large(limit_bytes: u64) -> Self {
+        Self {
+            code: Self::RESPONSE_TOO_LARGE,
+            message: format!(
+                "Upstream response exceeded the {} byte limit and was truncated",
+                limit_bytes
+            ),
+            data: Some(serde_json::json!({
+                "truncated": true,
+                "limit_bytes": limit_bytes,
+            })),
+        }
+    }
 }
 
 /// JSON-RPC notification (no id, no response expected)
@@ -397,6 +423,23 @@ pub struct McpError {
     pub error: String,
 }
 
+/// Upstream MCP excluded from (or flagged in) an aggregated listing
+/// because its last health check failed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnavailableMcp {
+    pub mcp_name: String,
+    /// `down` when the upstream's tools were omitted, `degraded` when
+    /// they are still advertised but may fail
+    pub status: String,
+}
+
+/// `_meta` section on aggregated listings so clients can inform users
+/// about upstreams that are currently unavailable
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregatedMeta {
+    pub unavailable_mcps: Vec<UnavailableMcp>,
+}
+
 /// Extended tools/list result with error info for partial failures
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -406,6 +449,10 @@ pub struct AggregatedToolsListResult {
     pub errors: Vec<McpError>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next_cursor: Option<String>,
+    /// Health-aware filtering info, present when an org has
+    /// `mcp_health_filtering` enabled and upstreams are down
+    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
+    pub meta: Option<AggregatedMeta>,
 }
 
 /// Extended resources/list result with error info for partial failures
@@ -442,11 +489,31 @@ pub enum McpTransport {
         endpoint_url: String,
         #[serde(flatten)]
         auth: McpAuth,
+        /// Optional SSH tunnel to reach the endpoint through a bastion
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tunnel: Option<super::ssh_tunnel::SshTunnelConfig>,
+        /// Optional per-MCP outbound proxy (overrides OUTBOUND_PROXY_URL)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        proxy_url: Option<String>,
+        /// Optional per-MCP cap on upstream response size in bytes
+        /// (falls back to the client default when absent)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_response_bytes: Option<u64>,
     },
     Sse {
         endpoint_url: String,
         #[serde(flatten)]
         auth: McpAuth,
+        /// Optional SSH tunnel to reach the endpoint through a bastion
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tunnel: Option<super::ssh_tunnel::SshTunnelConfig>,
+        /// Optional per-MCP outbound proxy (overrides OUTBOUND_PROXY_URL)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        proxy_url: Option<String>,
+        /// Optional per-MCP cap on upstream response size in bytes
+        /// (falls back to the client default when absent)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_response_bytes: Option<u64>,
     },
     Stdio {
         command: String,
@@ -454,11 +521,39 @@ pub enum McpTransport {
         args: Vec<String>,
         #[serde(default)]
         env: std::collections::HashMap<String, String>,
+        /// Optional per-MCP cap on upstream response size in bytes
+        /// (falls back to the client default when absent)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_response_bytes: Option<u64>,
+        /// Resource-limited sandbox for the managed process
+        /// (see `crate::mcp::sandbox`)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sandbox: Option<super::sandbox::SandboxLimits>,
     },
 }
 
+impl McpTransport {
+    /// Clamp this transport's response size cap to an org-wide ceiling
+    ///
+    /// The effective cap becomes the smaller of the org cap and the MCP's
+    /// own `max_response_bytes`, so a per-MCP setting can tighten the org
+    /// limit but never exceed it.
+    pub fn clamp_response_limit(&mut self, org_cap: u64) {
+        let (Self::Http {
+            max_response_bytes, ..
+        }
+        | Self::Sse {
+            max_response_bytes, ..
+        }
+        | Self::Stdio {
+            max_response_bytes, ..
+        }) = self;
+        *max_response_bytes = Some(max_response_bytes.map_or(org_cap, |mcp| mcp.min(org_cap)));
+    }
+}
+
 /// Authentication configuration for upstream MCPs
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Clone, Default, Serialize, Deserialize)]
 #[serde(tag = "auth_type", rename_all = "lowercase")]
 pub enum McpAuth {
     #[default]
@@ -474,6 +569,55 @@ pub enum McpAuth {
         username: String,
         password: String,
     },
+    /// Rendered header templates (see `crate::mcp::auth_template`)
+    Headers {
+        headers: Vec<(String, String)>,
+    },
+    /// Signed or static query parameters appended to every request URL
+    Query {
+        params: Vec<(String, String)>,
+    },
+    /// Session cookies from the per-MCP jar, rendered as a `Cookie` header
+    /// value after load (see `crate::mcp::session_auth`)
+    Cookie {
+        cookie: String,
+    },
+}
+
+/// Hand-written so credentials can never leak through `{:?}` formatting
+/// in logs or error messages - only header/user names are shown
+impl std::fmt::Debug for McpAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => write!(f, "None"),
+            Self::Bearer { .. } => write!(f, "Bearer {{ token: \"[REDACTED]\" }}"),
+            Self::ApiKey { header, .. } => {
+                write!(
+                    f,
+                    "ApiKey {{ header: {:?}, value: \"[REDACTED]\" }}",
+                    header
+                )
+            }
+            Self::Basic { username, .. } => write!(
+                f,
+                "Basic {{ username: {:?}, password: \"[REDACTED]\" }}",
+                username
+            ),
+            Self::Headers { headers } => {
+                let names: Vec<&str> = headers.iter().map(|(name, _)| name.as_str()).collect();
+                write!(
+                    f,
+                    "Headers {{ headers: {:?}, values: \"[REDACTED]\" }}",
+                    names
+                )
+            }
+            Self::Query { params } => {
+                let names: Vec<&str> = params.iter().map(|(name, _)| name.as_str()).collect();
+                write!(f, "Query {{ params: {:?}, values: \"[REDACTED]\" }}", names)
+            }
+            Self::Cookie { .. } => write!(f, "Cookie {{ cookie: \"[REDACTED]\" }}"),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -494,6 +638,46 @@ mod tests {
         assert_eq!(req.id, Some(JsonRpcId::Number(1)));
     }
 
+    #[test]
+    fn test_clamp_response_limit() {
+        let mut transport = McpTransport::Http {
+            endpoint_url: "https://example.com/mcp".to_string(),
+            auth: McpAuth::None,
+            tunnel: None,
+            proxy_url: None,
+            max_response_bytes: None,
+        };
+
+        // No per-MCP cap: the org ceiling applies directly
+        transport.clamp_response_limit(1_000_000);
+        let McpTransport::Http {
+            max_response_bytes, ..
+        } = &transport
+        else {
+            unreachable!()
+        };
+        assert_eq!(*max_response_bytes, Some(1_000_000));
+
+        // A larger per-MCP cap is clamped down, a smaller one survives
+        transport.clamp_response_limit(500_000);
+        let McpTransport::Http {
+            max_response_bytes, ..
+        } = &transport
+        else {
+            unreachable!()
+        };
+        assert_eq!(*max_response_bytes, Some(500_000));
+
+        transport.clamp_response_limit(2_000_000);
+        let McpTransport::Http {
+            max_response_bytes, ..
+        } = &transport
+        else {
+            unreachable!()
+        };
+        assert_eq!(*max_response_bytes, Some(500_000));
+    }
+
     #[test]
     fn test_json_rpc_response_success() {
         let resp =
@@ -528,4 +712,34 @@ mod tests {
         let params: ToolCallParams = serde_json::from_str(json).unwrap();
         assert_eq!(params.name, "github:create_issue");
     }
+
+    #[test]
+    fn test_mcp_auth_debug_redacts_credentials() {
+        let auth = McpAuth::Bearer {
+            token: "sk-secret".to_string(),
+        };
+        let debug = format!("{:?}", auth);
+        assert!(!debug.contains("sk-secret"));
+        assert!(debug.contains("[REDACTED]"));
+
+        let auth = McpAuth::Headers {
+            headers: vec![("X-Api-Key".to_string(), "topsecret".to_string())],
+        };
+        let debug = format!("{:?}", auth);
+        assert!(debug.contains("X-Api-Key"));
+        assert!(!debug.contains("topsecret"));
+
+        let auth = McpAuth::Query {
+            params: vec![("sig".to_string(), "signedvalue".to_string())],
+        };
+        let debug = format!("{:?}", auth);
+        assert!(debug.contains("sig"));
+        assert!(!debug.contains("signedvalue"));
+
+        let auth = McpAuth::Cookie {
+            cookie: "session=abc123".to_string(),
+        };
+        let debug = format!("{:?}", auth);
+        assert!(!debug.contains("abc123"));
+    }
 }
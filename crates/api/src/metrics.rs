@@ -0,0 +1,361 @@
+//! Process metrics in Prometheus exposition format
+//!
+//! Backs `GET /metrics` for Prometheus/Grafana scraping: request counts
+//! and latencies per route, upstream MCP latency histograms, circuit
+//! breaker states, rate limit rejections, and WebSocket connection
+//! counts. Counters and histograms are collected in-process with atomics
+//! and rendered as text exposition format (version 0.0.4) at scrape time;
+//! like the otel module, the wire format is assembled by hand rather than
+//! pulling in a metrics SDK for a page of plain text.
+//!
+//! The endpoint is gated behind `METRICS_TOKEN` (checked as a bearer
+//! token) and answers 404 while no token is configured, so deployments
+//! that don't scrape never expose it.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::fmt::Write as _;
+use subtle::ConstantTimeEq;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::state::AppState;
+
+/// Histogram bucket upper bounds in milliseconds, shared by the route and
+/// upstream latency histograms
+const LATENCY_BUCKETS_MS: &[u64] = &[5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+static REGISTRY: OnceLock<MetricsRegistry> = OnceLock::new();
+
+/// The process-wide metrics registry
+///
+/// A process global (matching the otel exporter) so instrumentation deep
+/// in the client layer doesn't need a handle threaded through every call.
+pub fn registry() -> &'static MetricsRegistry {
+    REGISTRY.get_or_init(MetricsRegistry::new)
+}
+
+/// Fixed-bucket latency histogram
+struct Histogram {
+    /// Cumulative counts per bucket in [`LATENCY_BUCKETS_MS`] order; the
+    /// implicit +Inf bucket is `count`
+    buckets: Vec<AtomicU64>,
+    sum_ms: AtomicU64,
+    count: AtomicU64,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        Self {
+            buckets: LATENCY_BUCKETS_MS
+                .iter()
+                .map(|_| AtomicU64::new(0))
+                .collect(),
+            sum_ms: AtomicU64::new(0),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    fn observe(&self, elapsed: Duration) {
+        let ms = elapsed.as_millis() as u64;
+        for (bucket, le) in self.buckets.iter().zip(LATENCY_BUCKETS_MS) {
+            if ms <= *le {
+                bucket.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.sum_ms.fetch_add(ms, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Append the `_bucket`/`_sum`/`_count` sample lines for one series
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        for (bucket, le) in self.buckets.iter().zip(LATENCY_BUCKETS_MS) {
+            let _ = writeln!(
+                out,
+                "{}_bucket{{{},le=\"{}\"}} {}",
+                name,
+                labels,
+                le,
+                bucket.load(Ordering::Relaxed)
+            );
+        }
+        let count = self.count.load(Ordering::Relaxed);
+        let _ = writeln!(out, "{}_bucket{{{},le=\"+Inf\"}} {}", name, labels, count);
+        let _ = writeln!(
+            out,
+            "{}_sum{{{}}} {}",
+            name,
+            labels,
+            self.sum_ms.load(Ordering::Relaxed)
+        );
+        let _ = writeln!(out, "{}_count{{{}}} {}", name, labels, count);
+    }
+}
+
+/// Per-route request counters and latency histogram
+struct RouteEntry {
+    /// Request counts by response status code
+    statuses: HashMap<u16, u64>,
+    latency: Histogram,
+}
+
+/// In-process metrics collected since startup
+pub struct MetricsRegistry {
+    /// Routes keyed by (matched path, method)
+    routes: RwLock<HashMap<(String, String), RouteEntry>>,
+
+    /// Upstream MCP request latency keyed by instance id
+    upstreams: RwLock<HashMap<Uuid, Histogram>>,
+
+    /// Upstream responses cut off at the size cap, keyed by instance id
+    response_truncations: RwLock<HashMap<Uuid, u64>>,
+
+    /// Responses rejected with 429 across all routes
+    rate_limit_rejections: AtomicU64,
+}
+
+impl MetricsRegistry {
+    fn new() -> Self {
+        Self {
+            routes: RwLock::new(HashMap::new()),
+            upstreams: RwLock::new(HashMap::new()),
+            response_truncations: RwLock::new(HashMap::new()),
+            rate_limit_rejections: AtomicU64::new(0),
+        }
+    }
+
+    /// Record one handled HTTP request
+    pub async fn record_http(&self, route: &str, method: &str, status: u16, elapsed: Duration) {
+        if status == StatusCode::TOO_MANY_REQUESTS.as_u16() {
+            self.rate_limit_rejections.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let mut routes = self.routes.write().await;
+        let entry = routes
+            .entry((route.to_string(), method.to_string()))
+            .or_insert_with(|| RouteEntry {
+                statuses: HashMap::new(),
+                latency: Histogram::new(),
+            });
+        *entry.statuses.entry(status).or_insert(0) += 1;
+        entry.latency.observe(elapsed);
+    }
+
+    /// Record one completed upstream MCP request
+    pub async fn record_upstream(&self, mcp_id: Uuid, elapsed: Duration) {
+        let mut upstreams = self.upstreams.write().await;
+        upstreams
+            .entry(mcp_id)
+            .or_insert_with(Histogram::new)
+            .observe(elapsed);
+    }
+
+    /// Record one upstream response truncated at the size cap
+    pub async fn record_response_truncation(&self, mcp_id: Uuid) {
+        let mut truncations = self.response_truncations.write().await;
+        *truncations.entry(mcp_id).or_insert(0) += 1;
+    }
+
+    /// Render everything as Prometheus text exposition format
+    ///
+    /// Gauges (circuit states, connection counts) are passed in by the
+    /// scrape handler since they live on shared state, not the registry.
+    pub async fn render(
+        &self,
+        circuit_states: &[(Uuid, &'static str)],
+        ws_connections: usize,
+        mcp_stream_sessions: usize,
+    ) -> String {
+        let mut out = String::new();
+
+        out.push_str("# HELP plexmcp_http_requests_total HTTP requests handled, by route, method and status\n");
+        out.push_str("# TYPE plexmcp_http_requests_total counter\n");
+        let routes = self.routes.read().await;
+        for ((route, method), entry) in routes.iter() {
+            for (status, count) in &entry.statuses {
+                let _ = writeln!(
+                    out,
+                    "plexmcp_http_requests_total{{route=\"{}\",method=\"{}\",status=\"{}\"}} {}",
+                    route, method, status, count
+                );
+            }
+        }
+
+        out.push_str("# HELP plexmcp_http_request_duration_ms HTTP request latency per route\n");
+        out.push_str("# TYPE plexmcp_http_request_duration_ms histogram\n");
+        for ((route, method), entry) in routes.iter() {
+            let labels = format!("route=\"{}\",method=\"{}\"", route, method);
+            entry
+                .latency
+                .render(&mut out, "plexmcp_http_request_duration_ms", &labels);
+        }
+        drop(routes);
+
+        out.push_str(
+            "# HELP plexmcp_mcp_upstream_duration_ms Upstream MCP request latency per instance\n",
+        );
+        out.push_str("# TYPE plexmcp_mcp_upstream_duration_ms histogram\n");
+        let upstreams = self.upstreams.read().await;
+        for (mcp_id, histogram) in upstreams.iter() {
+            let labels = format!("mcp_id=\"{}\"", mcp_id);
+            histogram.render(&mut out, "plexmcp_mcp_upstream_duration_ms", &labels);
+        }
+        drop(upstreams);
+
+        out.push_str("# HELP plexmcp_mcp_response_truncations_total Upstream responses cut off at the size cap, per instance\n");
+        out.push_str("# TYPE plexmcp_mcp_response_truncations_total counter\n");
+        let truncations = self.response_truncations.read().await;
+        for (mcp_id, count) in truncations.iter() {
+            let _ = writeln!(
+                out,
+                "plexmcp_mcp_response_truncations_total{{mcp_id=\"{}\"}} {}",
+                mcp_id, count
+            );
+        }
+        drop(truncations);
+
+        out.push_str("# HELP plexmcp_rate_limit_rejections_total Requests rejected with 429\n");
+        out.push_str("# TYPE plexmcp_rate_limit_rejections_total counter\n");
+        let _ = writeln!(
+            out,
+            "plexmcp_rate_limit_rejections_total {}",
+            self.rate_limit_rejections.load(Ordering::Relaxed)
+        );
+
+        out.push_str("# HELP plexmcp_circuit_breaker_state Current circuit state per MCP (1 = in this state)\n");
+        out.push_str("# TYPE plexmcp_circuit_breaker_state gauge\n");
+        for (mcp_id, state) in circuit_states {
+            let _ = writeln!(
+                out,
+                "plexmcp_circuit_breaker_state{{mcp_id=\"{}\",state=\"{}\"}} 1",
+                mcp_id, state
+            );
+        }
+
+        out.push_str("# HELP plexmcp_websocket_connections Active support WebSocket connections\n");
+        out.push_str("# TYPE plexmcp_websocket_connections gauge\n");
+        let _ = writeln!(out, "plexmcp_websocket_connections {}", ws_connections);
+
+        out.push_str(
+            "# HELP plexmcp_mcp_stream_sessions Open MCP streaming sessions (SSE and WebSocket)\n",
+        );
+        out.push_str("# TYPE plexmcp_mcp_stream_sessions gauge\n");
+        let _ = writeln!(out, "plexmcp_mcp_stream_sessions {}", mcp_stream_sessions);
+
+        out
+    }
+}
+
+/// Middleware recording count and latency for every handled request
+///
+/// Uses the matched route pattern (e.g. `/api/v1/mcps/:mcp_id`) rather
+/// than the raw path so per-resource IDs don't explode label cardinality.
+pub async fn track_http_metrics(request: Request, next: Next) -> Response {
+    let method = request.method().as_str().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string());
+
+    let start = Instant::now();
+    let response = next.run(request).await;
+
+    let route = route.unwrap_or_else(|| "unmatched".to_string());
+    registry()
+        .record_http(&route, &method, response.status().as_u16(), start.elapsed())
+        .await;
+
+    response
+}
+
+/// GET /metrics - scrape endpoint for Prometheus
+///
+/// 404s while `METRICS_TOKEN` is unset; otherwise requires the token as
+/// `Authorization: Bearer <token>` (Prometheus `bearer_token` config).
+pub async fn metrics_handler(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    let Some(expected) = state.config.metrics_token.as_deref() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let authorized = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|token| bool::from(token.as_bytes().ct_eq(expected.as_bytes())))
+        .unwrap_or(false);
+    if !authorized {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    let circuit_states = state.mcp_client.circuit_states().await;
+    let ws_connections = state.ws_state.connection_count().await;
+    let mcp_stream_sessions = state.mcp_streams.session_count().await;
+
+    let body = registry()
+        .render(&circuit_states, ws_connections, mcp_stream_sessions)
+        .await;
+
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_buckets_are_cumulative() {
+        let histogram = Histogram::new();
+        histogram.observe(Duration::from_millis(3));
+        histogram.observe(Duration::from_millis(70));
+        histogram.observe(Duration::from_secs(60));
+
+        let mut out = String::new();
+        histogram.render(&mut out, "test_ms", "route=\"/x\"");
+
+        // 3ms lands in every bucket, 70ms from le="100" up, 60s only in +Inf
+        assert!(out.contains("test_ms_bucket{route=\"/x\",le=\"5\"} 1"));
+        assert!(out.contains("test_ms_bucket{route=\"/x\",le=\"100\"} 2"));
+        assert!(out.contains("test_ms_bucket{route=\"/x\",le=\"10000\"} 2"));
+        assert!(out.contains("test_ms_bucket{route=\"/x\",le=\"+Inf\"} 3"));
+        assert!(out.contains("test_ms_count{route=\"/x\"} 3"));
+    }
+
+    #[tokio::test]
+    async fn test_render_counts_and_rejections() {
+        let registry = MetricsRegistry::new();
+        registry
+            .record_http("/api/v1/mcps", "GET", 200, Duration::from_millis(12))
+            .await;
+        registry
+            .record_http("/api/v1/mcps", "GET", 200, Duration::from_millis(20))
+            .await;
+        registry
+            .record_http("/mcp", "POST", 429, Duration::from_millis(1))
+            .await;
+
+        let mcp_id = Uuid::new_v4();
+        registry.record_response_truncation(mcp_id).await;
+        registry.record_response_truncation(mcp_id).await;
+
+        let out = registry.render(&[], 2, 1).await;
+        assert!(out.contains(
+            "plexmcp_http_requests_total{route=\"/api/v1/mcps\",method=\"GET\",status=\"200\"} 2"
+        ));
+        assert!(out.contains(&format!(
+            "plexmcp_mcp_response_truncations_total{{mcp_id=\"{}\"}} 2",
+            mcp_id
+        )));
+        assert!(out.contains("plexmcp_rate_limit_rejections_total 1"));
+        assert!(out.contains("plexmcp_websocket_connections 2"));
+        assert!(out.contains("plexmcp_mcp_stream_sessions 1"));
+    }
+}
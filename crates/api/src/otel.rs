@@ -0,0 +1,352 @@
+//! Distributed tracing with OTLP export
+//!
+//! Every MCP proxy request produces a trace spanning auth, handler
+//! dispatch and upstream calls, exported to an OpenTelemetry collector
+//! over OTLP/HTTP+JSON. The wire format is assembled by hand - the full
+//! OpenTelemetry SDK would drag in tonic/prost for a handful of JSON
+//! POSTs - and trace context rides a tokio task-local so upstream calls
+//! deep in the client layer can attach child spans and propagate W3C
+//! `traceparent` headers without threading a handle through every method.
+//!
+//! Disabled entirely when `OTEL_EXPORTER_OTLP_ENDPOINT` is unset; span
+//! recording then degrades to a no-op.
+
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::json;
+use tokio::sync::mpsc;
+
+/// Spans buffered before a flush is forced
+const MAX_BATCH_SIZE: usize = 512;
+
+/// How often buffered spans are flushed to the collector
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Collector requests must complete within this window
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// `service.name` resource attribute on exported traces
+const SERVICE_NAME: &str = "plexmcp-api";
+
+static EXPORTER: OnceLock<OtelExporter> = OnceLock::new();
+
+tokio::task_local! {
+    /// Trace context of the span currently being handled
+    pub static CURRENT_TRACE: TraceContext;
+}
+
+/// W3C trace context for one span (IDs are lowercase hex per spec)
+#[derive(Debug, Clone)]
+pub struct TraceContext {
+    /// 16-byte trace ID, 32 hex chars
+    pub trace_id: String,
+    /// 8-byte span ID, 16 hex chars
+    pub span_id: String,
+    /// Parent span ID, when this span continues an existing trace
+    pub parent_span_id: Option<String>,
+    /// Whether this trace is recorded (sampling decision is made once at
+    /// the root and inherited by every child)
+    pub sampled: bool,
+}
+
+impl TraceContext {
+    /// Start a new trace, making the sampling decision
+    pub fn new_root(sample_rate: f64) -> Self {
+        Self {
+            trace_id: hex::encode(rand::random::<[u8; 16]>()),
+            span_id: hex::encode(rand::random::<[u8; 8]>()),
+            parent_span_id: None,
+            sampled: rand::random::<f64>() < sample_rate,
+        }
+    }
+
+    /// Continue a trace from an incoming `traceparent` header
+    /// (`00-{trace_id}-{parent_id}-{flags}`); the caller's sampling
+    /// decision is honored
+    pub fn from_traceparent(header: &str) -> Option<Self> {
+        let mut parts = header.split('-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let parent_id = parts.next()?;
+        let flags = parts.next()?;
+        if version != "00"
+            || trace_id.len() != 32
+            || parent_id.len() != 16
+            || flags.len() != 2
+            || !trace_id.chars().all(|c| c.is_ascii_hexdigit())
+            || !parent_id.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return None;
+        }
+        // All-zero IDs are invalid per the spec
+        if trace_id.chars().all(|c| c == '0') || parent_id.chars().all(|c| c == '0') {
+            return None;
+        }
+        Some(Self {
+            trace_id: trace_id.to_ascii_lowercase(),
+            span_id: hex::encode(rand::random::<[u8; 8]>()),
+            parent_span_id: Some(parent_id.to_ascii_lowercase()),
+            sampled: u8::from_str_radix(flags, 16)
+                .map(|f| f & 1 == 1)
+                .unwrap_or(false),
+        })
+    }
+
+    /// Derive a child span context within the same trace
+    pub fn child(&self) -> Self {
+        Self {
+            trace_id: self.trace_id.clone(),
+            span_id: hex::encode(rand::random::<[u8; 8]>()),
+            parent_span_id: Some(self.span_id.clone()),
+            sampled: self.sampled,
+        }
+    }
+
+    /// Render as a W3C `traceparent` header value
+    pub fn traceparent(&self) -> String {
+        format!(
+            "00-{}-{}-{:02x}",
+            self.trace_id,
+            self.span_id,
+            if self.sampled { 1 } else { 0 }
+        )
+    }
+}
+
+/// Trace context of the current task, if one is in scope
+pub fn current_context() -> Option<TraceContext> {
+    CURRENT_TRACE.try_with(|ctx| ctx.clone()).ok()
+}
+
+/// A finished span ready for export
+#[derive(Debug)]
+pub struct SpanRecord {
+    pub name: &'static str,
+    pub trace_id: String,
+    pub span_id: String,
+    pub parent_span_id: Option<String>,
+    pub start: SystemTime,
+    pub end: SystemTime,
+    /// String attributes (JSON numbers/bools go in stringified)
+    pub attributes: Vec<(&'static str, String)>,
+    pub error: bool,
+}
+
+impl SpanRecord {
+    /// Build a span covering `start`..now for the given context
+    pub fn finish(
+        ctx: &TraceContext,
+        name: &'static str,
+        start: SystemTime,
+        attributes: Vec<(&'static str, String)>,
+        error: bool,
+    ) -> Self {
+        Self {
+            name,
+            trace_id: ctx.trace_id.clone(),
+            span_id: ctx.span_id.clone(),
+            parent_span_id: ctx.parent_span_id.clone(),
+            start,
+            end: SystemTime::now(),
+            attributes,
+            error,
+        }
+    }
+}
+
+/// Batching OTLP/HTTP exporter; a no-op when no endpoint is configured
+pub struct OtelExporter {
+    tx: Option<mpsc::UnboundedSender<SpanRecord>>,
+}
+
+/// Initialize the global exporter from config (call once at startup)
+pub fn init(endpoint: Option<&str>) {
+    let exporter = match endpoint {
+        Some(endpoint) => {
+            let (tx, rx) = mpsc::unbounded_channel();
+            let url = format!("{}/v1/traces", endpoint.trim_end_matches('/'));
+            tokio::spawn(export_loop(url, rx));
+            tracing::info!(endpoint = %endpoint, "OTLP trace export enabled");
+            OtelExporter { tx: Some(tx) }
+        }
+        None => OtelExporter { tx: None },
+    };
+    let _ = EXPORTER.set(exporter);
+}
+
+/// Queue a span for export; silently dropped when export is disabled
+pub fn record_span(span: SpanRecord) {
+    if let Some(tx) = EXPORTER.get().and_then(|e| e.tx.as_ref()) {
+        let _ = tx.send(span);
+    }
+}
+
+/// Record a finished child span of the current task's trace context
+/// (no-op outside a traced request or when the trace is unsampled)
+pub fn record_child_span(
+    name: &'static str,
+    start: SystemTime,
+    attributes: Vec<(&'static str, String)>,
+    error: bool,
+) {
+    if let Some(ctx) = current_context() {
+        if ctx.sampled {
+            record_span(SpanRecord::finish(
+                &ctx.child(),
+                name,
+                start,
+                attributes,
+                error,
+            ));
+        }
+    }
+}
+
+/// Background task: batch spans and POST them to the collector
+async fn export_loop(url: String, mut rx: mpsc::UnboundedReceiver<SpanRecord>) {
+    let client = match reqwest::Client::builder().timeout(EXPORT_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::error!("Failed to build OTLP export client: {}", e);
+            return;
+        }
+    };
+
+    let mut batch: Vec<SpanRecord> = Vec::new();
+    let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            span = rx.recv() => {
+                match span {
+                    Some(span) => {
+                        batch.push(span);
+                        if batch.len() >= MAX_BATCH_SIZE {
+                            flush(&client, &url, std::mem::take(&mut batch)).await;
+                        }
+                    }
+                    // All senders dropped - flush what's left and stop
+                    None => {
+                        if !batch.is_empty() {
+                            flush(&client, &url, batch).await;
+                        }
+                        return;
+                    }
+                }
+            }
+            _ = interval.tick() => {
+                if !batch.is_empty() {
+                    flush(&client, &url, std::mem::take(&mut batch)).await;
+                }
+            }
+        }
+    }
+}
+
+/// Encode a batch as OTLP JSON and send it (best-effort)
+async fn flush(client: &reqwest::Client, url: &str, batch: Vec<SpanRecord>) {
+    let span_count = batch.len();
+    let spans: Vec<serde_json::Value> = batch.iter().map(span_to_otlp).collect();
+    let payload = json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [{
+                    "key": "service.name",
+                    "value": { "stringValue": SERVICE_NAME }
+                }]
+            },
+            "scopeSpans": [{
+                "scope": { "name": "plexmcp.proxy" },
+                "spans": spans
+            }]
+        }]
+    });
+
+    match client.post(url).json(&payload).send().await {
+        Ok(resp) if !resp.status().is_success() => {
+            tracing::warn!(
+                status = %resp.status(),
+                spans = span_count,
+                "OTLP collector rejected trace export"
+            );
+        }
+        Err(e) => {
+            tracing::warn!(spans = span_count, "OTLP trace export failed: {}", e);
+        }
+        _ => {}
+    }
+}
+
+/// Map one span to its OTLP JSON representation
+fn span_to_otlp(span: &SpanRecord) -> serde_json::Value {
+    let attributes: Vec<serde_json::Value> = span
+        .attributes
+        .iter()
+        .map(|(key, value)| json!({ "key": key, "value": { "stringValue": value } }))
+        .collect();
+
+    json!({
+        "traceId": span.trace_id,
+        "spanId": span.span_id,
+        "parentSpanId": span.parent_span_id.as_deref().unwrap_or(""),
+        "name": span.name,
+        "kind": 2, // SPAN_KIND_SERVER
+        "startTimeUnixNano": unix_nanos(span.start).to_string(),
+        "endTimeUnixNano": unix_nanos(span.end).to_string(),
+        "attributes": attributes,
+        "status": { "code": if span.error { 2 } else { 1 } }
+    })
+}
+
+fn unix_nanos(t: SystemTime) -> u128 {
+    t.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traceparent_round_trip() {
+        let ctx = TraceContext::new_root(1.0);
+        let header = ctx.traceparent();
+        let parsed = TraceContext::from_traceparent(&header).expect("valid header");
+        assert_eq!(parsed.trace_id, ctx.trace_id);
+        assert_eq!(parsed.parent_span_id.as_deref(), Some(ctx.span_id.as_str()));
+        assert_ne!(parsed.span_id, ctx.span_id);
+        assert!(parsed.sampled);
+    }
+
+    #[test]
+    fn test_from_traceparent_rejects_malformed() {
+        assert!(TraceContext::from_traceparent("garbage").is_none());
+        assert!(TraceContext::from_traceparent("00-short-abc-01").is_none());
+        // All-zero trace ID is invalid per the W3C spec
+        assert!(TraceContext::from_traceparent(&format!(
+            "00-{}-{}-01",
+            "0".repeat(32),
+            "a".repeat(16)
+        ))
+        .is_none());
+    }
+
+    #[test]
+    fn test_child_inherits_trace() {
+        let root = TraceContext::new_root(1.0);
+        let child = root.child();
+        assert_eq!(child.trace_id, root.trace_id);
+        assert_eq!(child.parent_span_id.as_deref(), Some(root.span_id.as_str()));
+        assert_eq!(child.sampled, root.sampled);
+    }
+
+    #[test]
+    fn test_sample_rate_zero_never_samples() {
+        for _ in 0..50 {
+            assert!(!TraceContext::new_root(0.0).sampled);
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Platform read-only (maintenance) mode
+//!
+//! During database failovers operators can flip the platform into
+//! read-only mode: reads keep working while mutating requests are
+//! rejected with a clear 503. The flag is seeded from `READ_ONLY_MODE`
+//! at startup and toggled at runtime via the platform admin endpoint;
+//! the toggle is per-instance, so multi-instance deployments set the
+//! env flag (or call the endpoint on each instance).
+//!
+//! Requests are classified by route class rather than per-route
+//! annotations: safe methods, health checks, auth (so operators can
+//! still sign in to flip the toggle), the toggle endpoint itself, and -
+//! configurably - the MCP proxy stay up; everything else is a mutation.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use axum::{
+    extract::{Request, State},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+
+use crate::state::AppState;
+
+/// Runtime read-only mode flags
+#[derive(Debug)]
+pub struct ReadOnlyMode {
+    enabled: AtomicBool,
+    /// Whether the MCP proxy keeps accepting requests while read-only
+    allow_proxy: AtomicBool,
+}
+
+impl ReadOnlyMode {
+    /// Seed from `READ_ONLY_MODE` / `READ_ONLY_ALLOW_PROXY` (proxy
+    /// stays up by default: proxy traffic is revenue-bearing and its
+    /// writes are usage logging, which tolerates a failover)
+    pub fn from_env() -> Self {
+        let enabled = std::env::var("READ_ONLY_MODE")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+        let allow_proxy = std::env::var("READ_ONLY_ALLOW_PROXY")
+            .map(|v| v != "false" && v != "0")
+            .unwrap_or(true);
+        Self {
+            enabled: AtomicBool::new(enabled),
+            allow_proxy: AtomicBool::new(allow_proxy),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn proxy_allowed(&self) -> bool {
+        self.allow_proxy.load(Ordering::Relaxed)
+    }
+
+    pub fn set_allow_proxy(&self, allow: bool) {
+        self.allow_proxy.store(allow, Ordering::Relaxed);
+    }
+}
+
+/// How a request is treated while the platform is read-only
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteClass {
+    /// Safe method (GET/HEAD/OPTIONS) - always served
+    Read,
+    /// Infrastructure health checks - always served
+    Health,
+    /// Sign-in and session routes - served so operators can reach the toggle
+    Auth,
+    /// The read-only toggle itself - served so the mode can be turned off
+    AdminToggle,
+    /// MCP proxy traffic - served unless the proxy is also disabled
+    Proxy,
+    /// Everything else - rejected with 503
+    Mutation,
+}
+
+/// Classify a request by method and path
+pub fn classify(method: &Method, path: &str) -> RouteClass {
+    if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        return RouteClass::Read;
+    }
+    if path == "/health" || path.starts_with("/health/") {
+        return RouteClass::Health;
+    }
+    if path == "/api/v1/admin/system/read-only" {
+        return RouteClass::AdminToggle;
+    }
+    if path.starts_with("/api/v1/auth/") {
+        return RouteClass::Auth;
+    }
+    if path == "/mcp" {
+        return RouteClass::Proxy;
+    }
+    RouteClass::Mutation
+}
+
+/// Middleware rejecting mutating requests while read-only mode is on
+pub async fn read_only_guard(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !state.read_only.is_enabled() {
+        return next.run(request).await;
+    }
+
+    let class = classify(request.method(), request.uri().path());
+    let rejected = match class {
+        RouteClass::Mutation => true,
+        RouteClass::Proxy => !state.read_only.proxy_allowed(),
+        _ => false,
+    };
+
+    if rejected {
+        tracing::debug!(
+            method = %request.method(),
+            path = %request.uri().path(),
+            "Request rejected: platform is in read-only mode"
+        );
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({
+                "error": {
+                    "code": "READ_ONLY_MODE",
+                    "message": "The platform is temporarily in read-only mode for maintenance. Reads are unaffected; please retry writes shortly.",
+                }
+            })),
+        )
+            .into_response();
+    }
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_safe_methods_are_reads() {
+        assert_eq!(classify(&Method::GET, "/api/v1/mcps"), RouteClass::Read);
+        assert_eq!(classify(&Method::HEAD, "/api/v1/org"), RouteClass::Read);
+        assert_eq!(classify(&Method::OPTIONS, "/mcp"), RouteClass::Read);
+    }
+
+    #[test]
+    fn test_classify_exempt_route_classes() {
+        assert_eq!(classify(&Method::POST, "/health/drain"), RouteClass::Health);
+        assert_eq!(
+            classify(&Method::POST, "/api/v1/auth/login"),
+            RouteClass::Auth
+        );
+        assert_eq!(
+            classify(&Method::PUT, "/api/v1/admin/system/read-only"),
+            RouteClass::AdminToggle
+        );
+        assert_eq!(classify(&Method::POST, "/mcp"), RouteClass::Proxy);
+    }
+
+    #[test]
+    fn test_classify_writes_are_mutations() {
+        assert_eq!(
+            classify(&Method::POST, "/api/v1/mcps"),
+            RouteClass::Mutation
+        );
+        assert_eq!(
+            classify(&Method::DELETE, "/api/v1/api-keys/123"),
+            RouteClass::Mutation
+        );
+        assert_eq!(
+            classify(&Method::PATCH, "/api/v1/org"),
+            RouteClass::Mutation
+        );
+    }
+}
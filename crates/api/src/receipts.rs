@@ -0,0 +1,241 @@
+//! Receipt PDF generation for non-Stripe charges
+//!
+//! Manual credits, locally recorded invoices, and overage payments have no
+//! Stripe-hosted document. This module renders a simple single-page PDF
+//! receipt server-side - no external renderer or PDF dependency, just the
+//! handful of PDF primitives a text document needs. The rendered bytes are
+//! stored through the storage abstraction and listed with signed download
+//! URLs under `/billing/documents`.
+
+/// A receipt to render: header fields plus label/value detail rows
+#[derive(Debug, Clone)]
+pub struct Receipt {
+    /// Document title (e.g. "Account Credit Receipt")
+    pub title: String,
+    /// Organization the receipt is issued to
+    pub org_name: String,
+    /// Document reference (e.g. "CR-1a2b3c4d")
+    pub reference: String,
+    /// Issue date, already formatted (e.g. "2026-01-07")
+    pub issued_on: String,
+    /// Detail rows rendered as a label/value table
+    pub rows: Vec<(String, String)>,
+    /// Total amount in cents
+    pub total_cents: i64,
+    /// ISO currency code (lowercase, as stored)
+    pub currency: String,
+}
+
+/// US Letter page size in points
+const PAGE_WIDTH: f64 = 612.0;
+const PAGE_HEIGHT: f64 = 792.0;
+const MARGIN: f64 = 72.0;
+/// X position of the value column in detail rows
+const VALUE_COLUMN_X: f64 = 280.0;
+
+/// Format an amount in cents for display
+pub fn format_amount(cents: i64, currency: &str) -> String {
+    let value = cents as f64 / 100.0;
+    if currency.eq_ignore_ascii_case("usd") {
+        format!("${:.2}", value)
+    } else {
+        format!("{:.2} {}", value, currency.to_uppercase())
+    }
+}
+
+/// Escape text for a PDF literal string
+fn escape_pdf_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '(' => out.push_str("\\("),
+            ')' => out.push_str("\\)"),
+            // Standard fonts are Latin-1 only; replace anything outside it
+            c if (c as u32) > 255 => out.push('?'),
+            c if c.is_control() => out.push(' '),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Content-stream builder: places text top-down on a single page
+struct PageContent {
+    ops: String,
+    y: f64,
+}
+
+impl PageContent {
+    fn new() -> Self {
+        Self {
+            ops: String::new(),
+            y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    /// Draw text at an explicit position without advancing the cursor
+    fn text_at(&mut self, font: &str, size: f64, x: f64, y: f64, text: &str) {
+        self.ops.push_str(&format!(
+            "BT /{} {:.1} Tf 1 0 0 1 {:.1} {:.1} Tm ({}) Tj ET\n",
+            font,
+            size,
+            x,
+            y,
+            escape_pdf_text(text)
+        ));
+    }
+
+    /// Draw a line of text at the cursor and advance down
+    fn line(&mut self, font: &str, size: f64, text: &str) {
+        self.y -= size;
+        self.text_at(font, size, MARGIN, self.y, text);
+        self.y -= size * 0.6;
+    }
+
+    /// Draw a label/value detail row and advance down
+    fn row(&mut self, label: &str, value: &str) {
+        self.y -= 10.0;
+        self.text_at("F1", 10.0, MARGIN, self.y, label);
+        self.text_at("F1", 10.0, VALUE_COLUMN_X, self.y, value);
+        self.y -= 6.0;
+    }
+
+    /// Draw a horizontal rule and advance down
+    fn rule(&mut self) {
+        self.y -= 8.0;
+        self.ops.push_str(&format!(
+            "0.8 G {:.1} {:.1} m {:.1} {:.1} l S\n",
+            MARGIN,
+            self.y,
+            PAGE_WIDTH - MARGIN,
+            self.y
+        ));
+        self.y -= 8.0;
+    }
+
+    fn space(&mut self, amount: f64) {
+        self.y -= amount;
+    }
+}
+
+/// Render a receipt as a single-page PDF
+pub fn render_pdf(receipt: &Receipt) -> Vec<u8> {
+    let app_name = std::env::var("APP_NAME").unwrap_or_else(|_| "PlexMCP".to_string());
+
+    let mut page = PageContent::new();
+    page.line("F2", 18.0, &app_name);
+    page.space(8.0);
+    page.line("F2", 13.0, &receipt.title);
+    page.space(10.0);
+    page.row("Issued to", &receipt.org_name);
+    page.row("Reference", &receipt.reference);
+    page.row("Date", &receipt.issued_on);
+    page.rule();
+
+    for (label, value) in &receipt.rows {
+        page.row(label, value);
+    }
+
+    page.rule();
+    page.y -= 12.0;
+    page.text_at("F2", 12.0, MARGIN, page.y, "Total");
+    page.text_at(
+        "F2",
+        12.0,
+        VALUE_COLUMN_X,
+        page.y,
+        &format_amount(receipt.total_cents, &receipt.currency),
+    );
+    page.space(40.0);
+    page.line(
+        "F1",
+        8.0,
+        "This receipt was generated automatically and is valid without a signature.",
+    );
+
+    build_document(&page.ops)
+}
+
+/// Assemble a one-page PDF document around the given content stream
+fn build_document(content: &str) -> Vec<u8> {
+    let objects: Vec<String> = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /Font << /F1 5 0 R /F2 6 0 R >> >> /Contents 4 0 R >>",
+            PAGE_WIDTH, PAGE_HEIGHT
+        ),
+        format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            content.len(),
+            content
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold >>".to_string(),
+    ];
+
+    let mut out = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, obj) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.push_str(&format!("{} 0 obj\n{}\nendobj\n", i + 1, obj));
+    }
+
+    let xref_offset = out.len();
+    out.push_str(&format!("xref\n0 {}\n", objects.len() + 1));
+    out.push_str("0000000000 65535 f \n");
+    for offset in offsets {
+        out.push_str(&format!("{:010} 00000 n \n", offset));
+    }
+    out.push_str(&format!(
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF",
+        objects.len() + 1,
+        xref_offset
+    ));
+
+    out.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_receipt() -> Receipt {
+        Receipt {
+            title: "Account Credit Receipt".to_string(),
+            org_name: "Acme Corp".to_string(),
+            reference: "CR-1a2b3c4d".to_string(),
+            issued_on: "2026-01-07".to_string(),
+            rows: vec![("Type".to_string(), "Account credit".to_string())],
+            total_cents: 2500,
+            currency: "usd".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_produces_valid_pdf_shell() {
+        let bytes = render_pdf(&sample_receipt());
+        let text = String::from_utf8_lossy(&bytes);
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.ends_with("%%EOF"));
+        assert!(text.contains("/Type /Catalog"));
+        assert!(text.contains("Acme Corp"));
+        assert!(text.contains("$25.00"));
+    }
+
+    #[test]
+    fn test_escape_pdf_text() {
+        assert_eq!(escape_pdf_text("a(b)c\\d"), "a\\(b\\)c\\\\d");
+        assert_eq!(escape_pdf_text("café"), "café");
+        assert_eq!(escape_pdf_text("naïve — dash"), "naïve ? dash");
+    }
+
+    #[test]
+    fn test_format_amount() {
+        assert_eq!(format_amount(2500, "usd"), "$25.00");
+        assert_eq!(format_amount(199, "USD"), "$1.99");
+        assert_eq!(format_amount(2500, "eur"), "25.00 EUR");
+    }
+}
@@ -0,0 +1,510 @@
+//! Security alert administration
+//!
+//! Exposes the alert_configurations table (previously SQL-only) and the
+//! security_alerts triage workflow to the platform admin dashboard:
+//! tune thresholds, enable/disable alert types, override severities, and
+//! acknowledge/resolve fired alerts via AlertService.
+
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    alerting::AlertType,
+    auth::AuthUser,
+    error::{ApiError, ApiResult},
+    state::AppState,
+};
+
+use super::shared::require_platform_admin;
+
+/// Default and maximum page size for alert listings
+const DEFAULT_ALERT_LIMIT: i64 = 50;
+const MAX_ALERT_LIMIT: i64 = 200;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct AlertConfigResponse {
+    pub id: Uuid,
+    pub alert_type: String,
+    pub enabled: bool,
+    pub threshold_count: i32,
+    pub threshold_window_seconds: i32,
+    pub cooldown_seconds: i32,
+    pub notify_slack: bool,
+    pub notify_email: bool,
+    pub notify_pagerduty: bool,
+    /// Configured severity override; null means the built-in default applies
+    pub severity_override: Option<String>,
+    /// Justification captured when the rule was disabled
+    pub disabled_reason: Option<String>,
+    /// When set, the scheduler re-enables this disabled rule automatically
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub re_enable_at: Option<OffsetDateTime>,
+    /// Built-in severity for this alert type (what applies when no override is set)
+    #[sqlx(skip)]
+    pub default_severity: Option<&'static str>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateAlertConfigRequest {
+    pub enabled: Option<bool>,
+    pub threshold_count: Option<i32>,
+    pub threshold_window_seconds: Option<i32>,
+    pub cooldown_seconds: Option<i32>,
+    pub notify_slack: Option<bool>,
+    pub notify_email: Option<bool>,
+    pub notify_pagerduty: Option<bool>,
+    /// "low" | "medium" | "high" | "critical", or "default" to clear the override
+    pub severity_override: Option<String>,
+    /// Mandatory justification when disabling the rule
+    pub disable_reason: Option<String>,
+    /// Re-enable the rule automatically after this long (disable only)
+    pub re_enable_after_seconds: Option<i64>,
+    /// Queue the disable for second-admin approval instead of executing it
+    #[serde(default)]
+    pub require_approval: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListAlertsQuery {
+    /// "open" (default), "acknowledged", "resolved", or "all"
+    pub status: Option<String>,
+    pub alert_type: Option<String>,
+    pub severity: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct AdminAlertResponse {
+    pub id: Uuid,
+    pub alert_type: String,
+    pub severity: String,
+    pub user_id: Option<Uuid>,
+    pub org_id: Option<Uuid>,
+    pub ip_address: Option<String>,
+    pub title: String,
+    pub description: String,
+    pub metadata: serde_json::Value,
+    pub event_count: i32,
+    #[serde(with = "time::serde::rfc3339")]
+    pub first_seen_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub last_seen_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub acknowledged_at: Option<OffsetDateTime>,
+    pub acknowledged_by: Option<Uuid>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub resolved_at: Option<OffsetDateTime>,
+    pub resolved_by: Option<Uuid>,
+    pub resolution_notes: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminAlertsResponse {
+    pub alerts: Vec<AdminAlertResponse>,
+    pub total: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResolveAlertRequest {
+    pub resolution_notes: Option<String>,
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Validate an alert type string against the known AlertType variants
+fn validate_alert_type(alert_type: &str) -> ApiResult<AlertType> {
+    AlertType::ALL
+        .iter()
+        .find(|t| t.as_str() == alert_type)
+        .copied()
+        .ok_or_else(|| ApiError::Validation(format!("Unknown alert type: {}", alert_type)))
+}
+
+fn validate_severity(severity: &str) -> ApiResult<()> {
+    if !matches!(severity, "low" | "medium" | "high" | "critical") {
+        return Err(ApiError::Validation(format!(
+            "Severity must be low, medium, high, or critical: {}",
+            severity
+        )));
+    }
+    Ok(())
+}
+
+fn attach_default_severity(mut config: AlertConfigResponse) -> AlertConfigResponse {
+    config.default_severity = AlertType::ALL
+        .iter()
+        .find(|t| t.as_str() == config.alert_type)
+        .map(|t| t.default_severity().as_str());
+    config
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// GET /admin/alerts/config - list all alert configurations
+pub async fn list_alert_configs(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<Vec<AlertConfigResponse>>> {
+    require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    let configs: Vec<AlertConfigResponse> = sqlx::query_as(
+        r#"
+        SELECT id, alert_type, enabled, threshold_count, threshold_window_seconds,
+               cooldown_seconds, notify_slack, notify_email, notify_pagerduty,
+               severity_override, disabled_reason, re_enable_at, created_at, updated_at
+        FROM alert_configurations
+        ORDER BY alert_type
+        "#,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(
+        configs.into_iter().map(attach_default_severity).collect(),
+    ))
+}
+
+/// PATCH /admin/alerts/config/:alert_type - update one alert configuration
+///
+/// Disabling a rule requires a `disable_reason`, is audited at Critical
+/// severity, and can optionally be queued for second-admin approval
+/// (202 Accepted) or re-enabled automatically via `re_enable_after_seconds`.
+pub async fn update_alert_config(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    headers: HeaderMap,
+    Path(alert_type): Path<String>,
+    Json(req): Json<UpdateAlertConfigRequest>,
+) -> ApiResult<axum::response::Response> {
+    use axum::response::IntoResponse;
+
+    let admin_user_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    validate_alert_type(&alert_type)?;
+
+    // Disabling a rule is a security degradation: capture a mandatory
+    // reason and optionally hand off to the approvals workflow
+    let disabling = req.enabled == Some(false);
+    let disable_reason = if disabling {
+        let reason = req
+            .disable_reason
+            .as_deref()
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+            .ok_or_else(|| {
+                ApiError::Validation(
+                    "disable_reason is required when disabling an alert rule".to_string(),
+                )
+            })?;
+        Some(reason.to_string())
+    } else {
+        None
+    };
+    if let Some(secs) = req.re_enable_after_seconds {
+        if !disabling {
+            return Err(ApiError::Validation(
+                "re_enable_after_seconds only applies when disabling a rule".to_string(),
+            ));
+        }
+        if secs < 60 {
+            return Err(ApiError::Validation(
+                "re_enable_after_seconds must be at least 60".to_string(),
+            ));
+        }
+    }
+
+    let (ip_address, user_agent, session_id) =
+        crate::routes::admin_legacy::extract_audit_context(&headers, &auth_user);
+
+    if disabling && req.require_approval {
+        let reason = disable_reason.as_deref().unwrap_or_default();
+        let approval_id = super::approvals::create_approval(
+            &state.pool,
+            super::approvals::ACTION_DISABLE_ALERT_CONFIG,
+            crate::audit_constants::target_type::ALERT_CONFIG,
+            &alert_type,
+            reason,
+            serde_json::json!({ "re_enable_after_seconds": req.re_enable_after_seconds }),
+            admin_user_id,
+        )
+        .await?;
+
+        crate::routes::admin_legacy::log_admin_action(
+            &state.pool,
+            admin_user_id,
+            crate::audit_constants::admin_action::SECURITY_APPROVAL_REQUESTED,
+            crate::audit_constants::target_type::ALERT_CONFIG,
+            None,
+            Some(serde_json::json!({
+                "approval_id": approval_id,
+                "action": super::approvals::ACTION_DISABLE_ALERT_CONFIG,
+                "alert_type": alert_type,
+                "reason": reason,
+            })),
+            crate::audit_constants::event_type::SECURITY_SETTING,
+            crate::audit_constants::severity::WARNING,
+            ip_address,
+            user_agent,
+            session_id,
+        )
+        .await?;
+
+        return Ok((
+            StatusCode::ACCEPTED,
+            Json(super::approvals::ApprovalPendingResponse::new(approval_id)),
+        )
+            .into_response());
+    }
+
+    if let Some(count) = req.threshold_count {
+        if count < 1 {
+            return Err(ApiError::Validation(
+                "threshold_count must be at least 1".to_string(),
+            ));
+        }
+    }
+    if let Some(window) = req.threshold_window_seconds {
+        if window < 1 {
+            return Err(ApiError::Validation(
+                "threshold_window_seconds must be at least 1".to_string(),
+            ));
+        }
+    }
+    if let Some(cooldown) = req.cooldown_seconds {
+        if cooldown < 0 {
+            return Err(ApiError::Validation(
+                "cooldown_seconds must not be negative".to_string(),
+            ));
+        }
+    }
+
+    // "default" clears the override back to the built-in severity
+    let (set_severity, severity_value) = match req.severity_override.as_deref() {
+        None => (false, None),
+        Some("default") => (true, None),
+        Some(severity) => {
+            validate_severity(severity)?;
+            (true, Some(severity.to_string()))
+        }
+    };
+
+    // Toggling the rule rewrites the disable bookkeeping: a disable
+    // records the reason and optional re-enable timer, an enable clears
+    // both
+    let set_disable_fields = req.enabled.is_some();
+    let re_enable_at = disable_reason.as_ref().and(
+        req.re_enable_after_seconds
+            .map(|secs| OffsetDateTime::now_utc() + time::Duration::seconds(secs)),
+    );
+
+    let config: Option<AlertConfigResponse> = sqlx::query_as(
+        r#"
+        UPDATE alert_configurations SET
+            enabled = COALESCE($2, enabled),
+            threshold_count = COALESCE($3, threshold_count),
+            threshold_window_seconds = COALESCE($4, threshold_window_seconds),
+            cooldown_seconds = COALESCE($5, cooldown_seconds),
+            notify_slack = COALESCE($6, notify_slack),
+            notify_email = COALESCE($7, notify_email),
+            notify_pagerduty = COALESCE($8, notify_pagerduty),
+            severity_override = CASE WHEN $9 THEN $10 ELSE severity_override END,
+            disabled_reason = CASE WHEN $11 THEN $12 ELSE disabled_reason END,
+            re_enable_at = CASE WHEN $11 THEN $13 ELSE re_enable_at END,
+            updated_at = NOW()
+        WHERE alert_type = $1
+        RETURNING id, alert_type, enabled, threshold_count, threshold_window_seconds,
+                  cooldown_seconds, notify_slack, notify_email, notify_pagerduty,
+                  severity_override, disabled_reason, re_enable_at, created_at, updated_at
+        "#,
+    )
+    .bind(&alert_type)
+    .bind(req.enabled)
+    .bind(req.threshold_count)
+    .bind(req.threshold_window_seconds)
+    .bind(req.cooldown_seconds)
+    .bind(req.notify_slack)
+    .bind(req.notify_email)
+    .bind(req.notify_pagerduty)
+    .bind(set_severity)
+    .bind(severity_value)
+    .bind(set_disable_fields)
+    .bind(&disable_reason)
+    .bind(re_enable_at)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    let config = config.ok_or(ApiError::NotFound)?;
+
+    if disabling {
+        crate::routes::admin_legacy::log_admin_action(
+            &state.pool,
+            admin_user_id,
+            crate::audit_constants::admin_action::ALERT_CONFIG_DISABLED,
+            crate::audit_constants::target_type::ALERT_CONFIG,
+            None,
+            Some(serde_json::json!({
+                "alert_type": alert_type,
+                "reason": disable_reason,
+                "re_enable_after_seconds": req.re_enable_after_seconds,
+            })),
+            crate::audit_constants::event_type::SECURITY_SETTING,
+            crate::audit_constants::severity::CRITICAL,
+            ip_address,
+            user_agent,
+            session_id,
+        )
+        .await?;
+    }
+
+    tracing::info!(
+        alert_type = %alert_type,
+        updated_by = ?auth_user.user_id,
+        "Alert configuration updated"
+    );
+
+    Ok(Json(attach_default_severity(config)).into_response())
+}
+
+/// GET /admin/alerts - list security alerts with optional filters
+pub async fn list_alerts(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<ListAlertsQuery>,
+) -> ApiResult<Json<AdminAlertsResponse>> {
+    require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    let status = query.status.as_deref().unwrap_or("open");
+    if !matches!(status, "open" | "acknowledged" | "resolved" | "all") {
+        return Err(ApiError::Validation(format!(
+            "Status must be open, acknowledged, resolved, or all: {}",
+            status
+        )));
+    }
+    if let Some(alert_type) = &query.alert_type {
+        validate_alert_type(alert_type)?;
+    }
+    if let Some(severity) = &query.severity {
+        validate_severity(severity)?;
+    }
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_ALERT_LIMIT)
+        .clamp(1, MAX_ALERT_LIMIT);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let status_filter = r#"
+        ($1 = 'all'
+         OR ($1 = 'open' AND acknowledged_at IS NULL AND resolved_at IS NULL)
+         OR ($1 = 'acknowledged' AND acknowledged_at IS NOT NULL AND resolved_at IS NULL)
+         OR ($1 = 'resolved' AND resolved_at IS NOT NULL))
+    "#;
+
+    let alerts: Vec<AdminAlertResponse> = sqlx::query_as(&format!(
+        r#"
+        SELECT id, alert_type, severity, user_id, org_id, ip_address,
+               title, description, metadata, event_count,
+               first_seen_at, last_seen_at,
+               acknowledged_at, acknowledged_by,
+               resolved_at, resolved_by, resolution_notes,
+               created_at
+        FROM security_alerts
+        WHERE {status_filter}
+          AND ($2::text IS NULL OR alert_type = $2)
+          AND ($3::text IS NULL OR severity = $3)
+        ORDER BY created_at DESC
+        LIMIT $4 OFFSET $5
+        "#
+    ))
+    .bind(status)
+    .bind(&query.alert_type)
+    .bind(&query.severity)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let total: i64 = sqlx::query_scalar(&format!(
+        r#"
+        SELECT COUNT(*)::bigint
+        FROM security_alerts
+        WHERE {status_filter}
+          AND ($2::text IS NULL OR alert_type = $2)
+          AND ($3::text IS NULL OR severity = $3)
+        "#
+    ))
+    .bind(status)
+    .bind(&query.alert_type)
+    .bind(&query.severity)
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok(Json(AdminAlertsResponse { alerts, total }))
+}
+
+/// POST /admin/alerts/:alert_id/acknowledge - mark an alert as acknowledged
+pub async fn acknowledge_alert(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(alert_id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    let user_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    let updated = state
+        .alert_service
+        .acknowledge_alert(alert_id, user_id)
+        .await?;
+    if !updated {
+        return Err(ApiError::NotFound);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /admin/alerts/:alert_id/resolve - mark an alert as resolved
+pub async fn resolve_alert(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(alert_id): Path<Uuid>,
+    Json(req): Json<ResolveAlertRequest>,
+) -> ApiResult<StatusCode> {
+    let user_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    let notes = req
+        .resolution_notes
+        .as_deref()
+        .map(str::trim)
+        .filter(|n| !n.is_empty());
+
+    let updated = state
+        .alert_service
+        .resolve_alert(alert_id, user_id, notes)
+        .await?;
+    if !updated {
+        return Err(ApiError::NotFound);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,297 @@
+//! Admin organization analytics - benchmarking and comparison
+//!
+//! Provides cross-organization benchmarks (requests, error rates, MCP counts,
+//! growth) with percentile placement and churn-risk signals so the success
+//! team can prioritize outreach.
+
+use axum::{
+    extract::{Extension, Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::{auth::AuthUser, error::ApiResult, state::AppState};
+
+use super::shared::require_platform_admin;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct BenchmarkQuery {
+    /// Comparison window in days (default 30, max 365)
+    pub period_days: Option<i64>,
+    /// Maximum number of organizations to return (default 100, max 500)
+    pub limit: Option<i64>,
+    /// Sort field: "requests" (default), "error_rate", "growth", "mcps"
+    pub sort_by: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrgBenchmark {
+    pub org_id: Uuid,
+    pub name: String,
+    pub subscription_tier: String,
+    /// Total proxied requests in the current window
+    pub total_requests: i64,
+    /// Total errors in the current window
+    pub total_errors: i64,
+    /// Errors as a fraction of requests (0.0 when no requests)
+    pub error_rate: f64,
+    /// Active MCP instances
+    pub mcp_count: i64,
+    /// Request growth vs the previous window, as a percentage
+    /// (None when the previous window had no activity)
+    pub growth_pct: Option<f64>,
+    /// Percentile placement by request volume (0-100)
+    pub requests_percentile: f64,
+    /// Whether this org shows churn-risk signals
+    pub churn_risk: bool,
+    /// Human-readable churn-risk signals (empty when churn_risk is false)
+    pub churn_signals: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BenchmarkResponse {
+    pub period_days: i64,
+    pub total_orgs: usize,
+    pub orgs: Vec<OrgBenchmark>,
+}
+
+#[derive(Debug, FromRow)]
+struct OrgBenchmarkRow {
+    org_id: Uuid,
+    name: String,
+    subscription_tier: String,
+    current_requests: i64,
+    current_errors: i64,
+    previous_requests: i64,
+    mcp_count: i64,
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// GET /admin/analytics/orgs/benchmark
+///
+/// Compare organizations on usage, error rates, MCP counts, and growth.
+/// Read-only, so staff can access it too.
+pub async fn benchmark_orgs(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<BenchmarkQuery>,
+) -> ApiResult<Json<BenchmarkResponse>> {
+    require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    let period_days = query.period_days.unwrap_or(30).clamp(1, 365);
+    let limit = query.limit.unwrap_or(100).clamp(1, 500);
+
+    let rows: Vec<OrgBenchmarkRow> = sqlx::query_as(
+        r#"
+        SELECT
+            o.id as org_id,
+            o.name,
+            o.subscription_tier,
+            COALESCE(cur.requests, 0)::bigint as current_requests,
+            COALESCE(cur.errors, 0)::bigint as current_errors,
+            COALESCE(prev.requests, 0)::bigint as previous_requests,
+            COALESCE(m.mcp_count, 0)::bigint as mcp_count
+        FROM organizations o
+        LEFT JOIN (
+            SELECT org_id,
+                   SUM(request_count) as requests,
+                   SUM(error_count) as errors
+            FROM usage_records
+            WHERE period_start >= NOW() - ($1 || ' days')::interval
+            GROUP BY org_id
+        ) cur ON cur.org_id = o.id
+        LEFT JOIN (
+            SELECT org_id, SUM(request_count) as requests
+            FROM usage_records
+            WHERE period_start >= NOW() - ($1 * 2 || ' days')::interval
+              AND period_start < NOW() - ($1 || ' days')::interval
+            GROUP BY org_id
+        ) prev ON prev.org_id = o.id
+        LEFT JOIN (
+            SELECT org_id, COUNT(*) as mcp_count
+            FROM mcp_instances
+            WHERE status = 'active'
+            GROUP BY org_id
+        ) m ON m.org_id = o.id
+        "#,
+    )
+    .bind(period_days)
+    .fetch_all(&state.pool)
+    .await?;
+
+    // Percentile placement by current request volume
+    let mut volumes: Vec<i64> = rows.iter().map(|r| r.current_requests).collect();
+    volumes.sort_unstable();
+
+    let mut orgs: Vec<OrgBenchmark> = rows
+        .into_iter()
+        .map(|r| {
+            let error_rate = if r.current_requests > 0 {
+                r.current_errors as f64 / r.current_requests as f64
+            } else {
+                0.0
+            };
+
+            let growth_pct = if r.previous_requests > 0 {
+                Some(
+                    (r.current_requests - r.previous_requests) as f64 / r.previous_requests as f64
+                        * 100.0,
+                )
+            } else {
+                None
+            };
+
+            let requests_percentile = percentile_of(&volumes, r.current_requests);
+
+            let (churn_risk, churn_signals) =
+                detect_churn_signals(r.current_requests, r.previous_requests, error_rate);
+
+            OrgBenchmark {
+                org_id: r.org_id,
+                name: r.name,
+                subscription_tier: r.subscription_tier,
+                total_requests: r.current_requests,
+                total_errors: r.current_errors,
+                error_rate,
+                mcp_count: r.mcp_count,
+                growth_pct,
+                requests_percentile,
+                churn_risk,
+                churn_signals,
+            }
+        })
+        .collect();
+
+    match query.sort_by.as_deref() {
+        Some("error_rate") => {
+            orgs.sort_by(|a, b| {
+                b.error_rate
+                    .partial_cmp(&a.error_rate)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+        }
+        Some("growth") => {
+            orgs.sort_by(|a, b| {
+                b.growth_pct
+                    .unwrap_or(f64::MIN)
+                    .partial_cmp(&a.growth_pct.unwrap_or(f64::MIN))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+        }
+        Some("mcps") => orgs.sort_by_key(|o| std::cmp::Reverse(o.mcp_count)),
+        _ => orgs.sort_by_key(|o| std::cmp::Reverse(o.total_requests)),
+    }
+
+    orgs.truncate(limit as usize);
+
+    Ok(Json(BenchmarkResponse {
+        period_days,
+        total_orgs: orgs.len(),
+        orgs,
+    }))
+}
+
+// =============================================================================
+// Helper Functions
+// =============================================================================
+
+/// Percentile placement of `value` within a sorted list of volumes (0-100)
+fn percentile_of(sorted: &[i64], value: i64) -> f64 {
+    if sorted.len() <= 1 {
+        return 100.0;
+    }
+    let below = sorted.iter().filter(|&&v| v < value).count();
+    below as f64 / (sorted.len() - 1) as f64 * 100.0
+}
+
+/// Detect churn-risk signals: usage decline and elevated error rates
+fn detect_churn_signals(
+    current_requests: i64,
+    previous_requests: i64,
+    error_rate: f64,
+) -> (bool, Vec<String>) {
+    let mut signals = Vec::new();
+
+    // Significant usage decline vs the previous window
+    if previous_requests >= 100 {
+        let decline = (previous_requests - current_requests) as f64 / previous_requests as f64;
+        if decline >= 0.5 {
+            signals.push(format!(
+                "Usage declined {:.0}% vs previous period",
+                decline * 100.0
+            ));
+        }
+    }
+
+    // Went completely inactive after prior activity
+    if previous_requests > 0 && current_requests == 0 {
+        signals.push("No usage in current period after prior activity".to_string());
+    }
+
+    // Elevated error rate suggests a broken integration
+    if error_rate >= 0.25 && current_requests >= 50 {
+        signals.push(format!("High error rate ({:.0}%)", error_rate * 100.0));
+    }
+
+    (!signals.is_empty(), signals)
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile_of() {
+        let sorted = vec![0, 10, 50, 100, 1000];
+        assert_eq!(percentile_of(&sorted, 0), 0.0);
+        assert_eq!(percentile_of(&sorted, 1000), 100.0);
+        assert_eq!(percentile_of(&sorted, 50), 50.0);
+    }
+
+    #[test]
+    fn test_percentile_single_org() {
+        assert_eq!(percentile_of(&[42], 42), 100.0);
+    }
+
+    #[test]
+    fn test_churn_signals_decline() {
+        let (risk, signals) = detect_churn_signals(100, 1000, 0.0);
+        assert!(risk);
+        assert_eq!(signals.len(), 1);
+        assert!(signals[0].contains("declined"));
+    }
+
+    #[test]
+    fn test_churn_signals_inactive() {
+        let (risk, signals) = detect_churn_signals(0, 50, 0.0);
+        assert!(risk);
+        assert!(signals[0].contains("No usage"));
+    }
+
+    #[test]
+    fn test_churn_signals_healthy() {
+        let (risk, signals) = detect_churn_signals(2000, 1000, 0.01);
+        assert!(!risk);
+        assert!(signals.is_empty());
+    }
+
+    #[test]
+    fn test_churn_signals_small_orgs_ignored() {
+        // Small orgs with noisy usage shouldn't be flagged for decline
+        let (risk, _) = detect_churn_signals(1, 10, 0.0);
+        assert!(!risk);
+    }
+}
@@ -0,0 +1,377 @@
+//! Second-admin approval workflow for destructive security actions
+//!
+//! Disabling a user's 2FA or an alert rule degrades security, so those
+//! routes can queue the action here instead of executing it immediately.
+//! A different platform admin reviews the pending approval and either
+//! approves it (which executes the action) or rejects it. Pending
+//! approvals expire after 24 hours.
+
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::HeaderMap,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    audit_constants::{admin_action, event_type, severity},
+    auth::AuthUser,
+    error::{ApiError, ApiResult},
+    routes::admin_legacy::{execute_disable_user_2fa, extract_audit_context, log_admin_action},
+    state::AppState,
+};
+
+use super::shared::require_platform_admin;
+
+/// Supported approval actions
+pub const ACTION_DISABLE_USER_2FA: &str = "disable_user_2fa";
+pub const ACTION_DISABLE_ALERT_CONFIG: &str = "disable_alert_config";
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct ApprovalResponse {
+    pub id: Uuid,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: String,
+    pub reason: String,
+    pub params: serde_json::Value,
+    pub requested_by: Uuid,
+    pub status: String,
+    pub decided_by: Option<Uuid>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub decided_at: Option<OffsetDateTime>,
+    pub decision_notes: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub expires_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+/// 202 body returned by routes that queued an action for approval
+#[derive(Debug, Serialize)]
+pub struct ApprovalPendingResponse {
+    pub approval_id: Uuid,
+    pub status: String,
+    pub message: String,
+}
+
+impl ApprovalPendingResponse {
+    pub fn new(approval_id: Uuid) -> Self {
+        Self {
+            approval_id,
+            status: "pending".to_string(),
+            message: "Action queued for second-admin approval".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListApprovalsQuery {
+    /// "pending" (default), "approved", "rejected", "expired", or "all"
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RejectApprovalRequest {
+    pub decision_notes: Option<String>,
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Insert a pending approval row. Called by the routes that own the
+/// underlying action (2FA disable, alert config disable).
+pub(crate) async fn create_approval(
+    pool: &sqlx::PgPool,
+    action: &str,
+    target_type: &str,
+    target_id: &str,
+    reason: &str,
+    params: serde_json::Value,
+    requested_by: Uuid,
+) -> ApiResult<Uuid> {
+    let approval_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO security_action_approvals
+            (action, target_type, target_id, reason, params, requested_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id
+        "#,
+    )
+    .bind(action)
+    .bind(target_type)
+    .bind(target_id)
+    .bind(reason)
+    .bind(params)
+    .bind(requested_by)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(approval_id)
+}
+
+/// Mark pending approvals past their expiry as expired (lazy sweep, run
+/// before every list/decide so stale rows can never be acted on)
+async fn expire_stale_approvals(pool: &sqlx::PgPool) -> ApiResult<()> {
+    sqlx::query(
+        r#"
+        UPDATE security_action_approvals
+        SET status = 'expired'
+        WHERE status = 'pending' AND expires_at <= NOW()
+        "#,
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Execute an approved action. Each arm mirrors what the direct route
+/// does, minus the approval branch.
+async fn execute_approved_action(
+    pool: &sqlx::PgPool,
+    approval: &ApprovalResponse,
+) -> ApiResult<serde_json::Value> {
+    match approval.action.as_str() {
+        ACTION_DISABLE_USER_2FA => {
+            let user_id = Uuid::parse_str(&approval.target_id)
+                .map_err(|_| ApiError::Validation("Invalid target user id".to_string()))?;
+            let (backup_codes_deleted, trusted_devices_deleted) =
+                execute_disable_user_2fa(pool, user_id).await?;
+            Ok(serde_json::json!({
+                "backup_codes_deleted": backup_codes_deleted,
+                "trusted_devices_deleted": trusted_devices_deleted,
+            }))
+        }
+        ACTION_DISABLE_ALERT_CONFIG => {
+            let re_enable_after_seconds = approval
+                .params
+                .get("re_enable_after_seconds")
+                .and_then(|v| v.as_i64());
+            let updated = sqlx::query(
+                r#"
+                UPDATE alert_configurations SET
+                    enabled = FALSE,
+                    disabled_reason = $2,
+                    re_enable_at = CASE
+                        WHEN $3::bigint IS NOT NULL
+                        THEN NOW() + make_interval(secs => $3::bigint)
+                        ELSE NULL
+                    END,
+                    updated_at = NOW()
+                WHERE alert_type = $1
+                "#,
+            )
+            .bind(&approval.target_id)
+            .bind(&approval.reason)
+            .bind(re_enable_after_seconds)
+            .execute(pool)
+            .await?;
+            if updated.rows_affected() == 0 {
+                return Err(ApiError::NotFound);
+            }
+            Ok(serde_json::json!({
+                "alert_type": approval.target_id,
+                "re_enable_after_seconds": re_enable_after_seconds,
+            }))
+        }
+        other => Err(ApiError::Validation(format!(
+            "Unknown approval action: {}",
+            other
+        ))),
+    }
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// GET /admin/approvals - list security action approvals
+pub async fn list_approvals(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<ListApprovalsQuery>,
+) -> ApiResult<Json<Vec<ApprovalResponse>>> {
+    require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    let status = query.status.as_deref().unwrap_or("pending");
+    if !matches!(
+        status,
+        "pending" | "approved" | "rejected" | "expired" | "all"
+    ) {
+        return Err(ApiError::Validation(format!(
+            "Status must be pending, approved, rejected, expired, or all: {}",
+            status
+        )));
+    }
+
+    expire_stale_approvals(&state.pool).await?;
+
+    let approvals: Vec<ApprovalResponse> = sqlx::query_as(
+        r#"
+        SELECT id, action, target_type, target_id, reason, params,
+               requested_by, status, decided_by, decided_at, decision_notes,
+               expires_at, created_at
+        FROM security_action_approvals
+        WHERE ($1 = 'all' OR status = $1)
+        ORDER BY created_at DESC
+        LIMIT 200
+        "#,
+    )
+    .bind(status)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(approvals))
+}
+
+/// POST /admin/approvals/:approval_id/approve - approve and execute
+///
+/// The approving admin must be a different user than the one who
+/// requested the action.
+pub async fn approve_approval(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    headers: HeaderMap,
+    Path(approval_id): Path<Uuid>,
+) -> ApiResult<Json<ApprovalResponse>> {
+    let admin_user_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+    let (ip_address, user_agent, session_id) = extract_audit_context(&headers, &auth_user);
+
+    expire_stale_approvals(&state.pool).await?;
+
+    let approval: ApprovalResponse = sqlx::query_as(
+        r#"
+        SELECT id, action, target_type, target_id, reason, params,
+               requested_by, status, decided_by, decided_at, decision_notes,
+               expires_at, created_at
+        FROM security_action_approvals
+        WHERE id = $1
+        "#,
+    )
+    .bind(approval_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    if approval.status != "pending" {
+        return Err(ApiError::Validation(format!(
+            "Approval is not pending (status: {})",
+            approval.status
+        )));
+    }
+    if approval.requested_by == admin_user_id {
+        return Err(ApiError::Validation(
+            "You cannot approve your own request - a second admin must sign off".to_string(),
+        ));
+    }
+
+    let outcome = execute_approved_action(&state.pool, &approval).await?;
+
+    let approval: ApprovalResponse = sqlx::query_as(
+        r#"
+        UPDATE security_action_approvals
+        SET status = 'approved', decided_by = $2, decided_at = NOW()
+        WHERE id = $1
+        RETURNING id, action, target_type, target_id, reason, params,
+                  requested_by, status, decided_by, decided_at, decision_notes,
+                  expires_at, created_at
+        "#,
+    )
+    .bind(approval_id)
+    .bind(admin_user_id)
+    .fetch_one(&state.pool)
+    .await?;
+
+    log_admin_action(
+        &state.pool,
+        admin_user_id,
+        admin_action::SECURITY_APPROVAL_APPROVED,
+        &approval.target_type,
+        Uuid::parse_str(&approval.target_id).ok(),
+        Some(serde_json::json!({
+            "approval_id": approval_id,
+            "action": approval.action,
+            "target_id": approval.target_id,
+            "reason": approval.reason,
+            "requested_by": approval.requested_by,
+            "outcome": outcome,
+        })),
+        event_type::SECURITY_SETTING,
+        severity::CRITICAL,
+        ip_address,
+        user_agent,
+        session_id,
+    )
+    .await?;
+
+    Ok(Json(approval))
+}
+
+/// POST /admin/approvals/:approval_id/reject - reject a pending approval
+pub async fn reject_approval(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    headers: HeaderMap,
+    Path(approval_id): Path<Uuid>,
+    Json(req): Json<RejectApprovalRequest>,
+) -> ApiResult<Json<ApprovalResponse>> {
+    let admin_user_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+    let (ip_address, user_agent, session_id) = extract_audit_context(&headers, &auth_user);
+
+    expire_stale_approvals(&state.pool).await?;
+
+    let notes = req
+        .decision_notes
+        .as_deref()
+        .map(str::trim)
+        .filter(|n| !n.is_empty());
+
+    let approval: Option<ApprovalResponse> = sqlx::query_as(
+        r#"
+        UPDATE security_action_approvals
+        SET status = 'rejected', decided_by = $2, decided_at = NOW(), decision_notes = $3
+        WHERE id = $1 AND status = 'pending'
+        RETURNING id, action, target_type, target_id, reason, params,
+                  requested_by, status, decided_by, decided_at, decision_notes,
+                  expires_at, created_at
+        "#,
+    )
+    .bind(approval_id)
+    .bind(admin_user_id)
+    .bind(notes)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    let approval = approval.ok_or(ApiError::NotFound)?;
+
+    log_admin_action(
+        &state.pool,
+        admin_user_id,
+        admin_action::SECURITY_APPROVAL_REJECTED,
+        &approval.target_type,
+        Uuid::parse_str(&approval.target_id).ok(),
+        Some(serde_json::json!({
+            "approval_id": approval_id,
+            "action": approval.action,
+            "target_id": approval.target_id,
+            "requested_by": approval.requested_by,
+            "decision_notes": notes,
+        })),
+        event_type::SECURITY_SETTING,
+        severity::WARNING,
+        ip_address,
+        user_agent,
+        session_id,
+    )
+    .await?;
+
+    Ok(Json(approval))
+}
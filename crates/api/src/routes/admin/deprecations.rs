@@ -0,0 +1,295 @@
+//! Admin API deprecation registry management
+//!
+//! CRUD for `api_deprecations` plus a usage report listing which orgs
+//! still call a deprecated route. Matching and header injection happen
+//! in [`crate::deprecation`]; every instance reloads the registry
+//! within a minute of a change here. The worker emails affected orgs
+//! once per deprecation (see the deprecation notice job).
+
+use axum::{
+    extract::{Extension, Path, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::shared::require_platform_admin;
+use crate::{
+    auth::AuthUser,
+    error::{ApiError, ApiResult},
+    state::AppState,
+};
+
+/// Methods accepted in a deprecation rule
+const VALID_METHODS: &[&str] = &["*", "GET", "POST", "PUT", "PATCH", "DELETE"];
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+/// One registry entry with aggregate usage for the listing
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct DeprecationResponse {
+    pub id: Uuid,
+    pub method: String,
+    pub path_pattern: String,
+    #[serde(serialize_with = "serialize_optional_rfc3339")]
+    pub sunset_at: Option<OffsetDateTime>,
+    pub successor: Option<String>,
+    pub note: Option<String>,
+    pub enabled: bool,
+    /// Orgs that called the route in the last 30 days
+    pub active_orgs: i64,
+    /// Total hits in the last 30 days
+    pub recent_hits: i64,
+    #[serde(serialize_with = "serialize_rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateDeprecationRequest {
+    /// HTTP method, or `*` for all methods
+    pub method: String,
+    /// Route template with `:param` segments, e.g. `/api/v1/mcps/:mcp_id/test`
+    pub path_pattern: String,
+    /// RFC 3339 removal date, omitted while no date is committed
+    pub sunset_at: Option<String>,
+    /// Replacement route or migration doc, served in a Link header
+    pub successor: Option<String>,
+    pub note: Option<String>,
+}
+
+/// Update a registry entry; omitted fields are unchanged
+#[derive(Debug, Deserialize)]
+pub struct UpdateDeprecationRequest {
+    pub sunset_at: Option<String>,
+    pub successor: Option<String>,
+    pub note: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+/// One org still calling a deprecated route
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct DeprecationUsageEntry {
+    pub org_id: Uuid,
+    pub org_name: String,
+    pub total_hits: i64,
+    #[serde(serialize_with = "serialize_rfc3339")]
+    pub last_seen_at: OffsetDateTime,
+    pub notified: bool,
+}
+
+fn serialize_rfc3339<S: serde::Serializer>(
+    dt: &OffsetDateTime,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let formatted = dt
+        .format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_else(|_| dt.to_string());
+    serializer.serialize_str(&formatted)
+}
+
+fn serialize_optional_rfc3339<S: serde::Serializer>(
+    dt: &Option<OffsetDateTime>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match dt {
+        Some(dt) => serialize_rfc3339(dt, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn validate_method(method: &str) -> ApiResult<String> {
+    let normalized = if method == "*" {
+        method.to_string()
+    } else {
+        method.to_uppercase()
+    };
+    if !VALID_METHODS.contains(&normalized.as_str()) {
+        return Err(ApiError::Validation(format!(
+            "method must be one of: {}",
+            VALID_METHODS.join(", ")
+        )));
+    }
+    Ok(normalized)
+}
+
+fn parse_sunset(value: Option<&str>) -> ApiResult<Option<OffsetDateTime>> {
+    match value {
+        None => Ok(None),
+        Some(raw) => OffsetDateTime::parse(raw, &time::format_description::well_known::Rfc3339)
+            .map(Some)
+            .map_err(|_| {
+                ApiError::Validation("sunset_at must be an RFC 3339 timestamp".to_string())
+            }),
+    }
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// List all registered deprecations with 30-day usage aggregates
+pub async fn list_deprecations(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<Vec<DeprecationResponse>>> {
+    require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    let rows: Vec<DeprecationResponse> = sqlx::query_as(
+        r#"
+        SELECT d.id, d.method, d.path_pattern, d.sunset_at, d.successor, d.note,
+               d.enabled,
+               COALESCE(u.active_orgs, 0) AS active_orgs,
+               COALESCE(u.recent_hits, 0) AS recent_hits,
+               d.created_at
+        FROM api_deprecations d
+        LEFT JOIN LATERAL (
+            SELECT COUNT(DISTINCT org_id) AS active_orgs, SUM(hits) AS recent_hits
+            FROM api_deprecation_usage
+            WHERE deprecation_id = d.id AND day >= CURRENT_DATE - 30
+        ) u ON true
+        ORDER BY d.created_at DESC
+        "#,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(rows))
+}
+
+/// Register a deprecated route
+pub async fn create_deprecation(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<CreateDeprecationRequest>,
+) -> ApiResult<Json<DeprecationResponse>> {
+    let admin_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    let method = validate_method(&req.method)?;
+    if !req.path_pattern.starts_with('/') {
+        return Err(ApiError::Validation(
+            "path_pattern must start with /".to_string(),
+        ));
+    }
+    let sunset_at = parse_sunset(req.sunset_at.as_deref())?;
+
+    let row: DeprecationResponse = sqlx::query_as(
+        r#"
+        INSERT INTO api_deprecations (method, path_pattern, sunset_at, successor, note, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (method, path_pattern) DO UPDATE SET
+            sunset_at = EXCLUDED.sunset_at,
+            successor = EXCLUDED.successor,
+            note = EXCLUDED.note,
+            enabled = TRUE,
+            updated_at = NOW()
+        RETURNING id, method, path_pattern, sunset_at, successor, note, enabled,
+                  0::BIGINT AS active_orgs, 0::BIGINT AS recent_hits, created_at
+        "#,
+    )
+    .bind(&method)
+    .bind(&req.path_pattern)
+    .bind(sunset_at)
+    .bind(&req.successor)
+    .bind(&req.note)
+    .bind(admin_id)
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok(Json(row))
+}
+
+/// Update a deprecation's sunset date, successor, note, or enabled flag
+pub async fn update_deprecation(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(deprecation_id): Path<Uuid>,
+    Json(req): Json<UpdateDeprecationRequest>,
+) -> ApiResult<Json<DeprecationResponse>> {
+    require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    let sunset_at = parse_sunset(req.sunset_at.as_deref())?;
+
+    let row: DeprecationResponse = sqlx::query_as(
+        r#"
+        UPDATE api_deprecations
+        SET sunset_at = COALESCE($2, sunset_at),
+            successor = COALESCE($3, successor),
+            note = COALESCE($4, note),
+            enabled = COALESCE($5, enabled),
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING id, method, path_pattern, sunset_at, successor, note, enabled,
+                  0::BIGINT AS active_orgs, 0::BIGINT AS recent_hits, created_at
+        "#,
+    )
+    .bind(deprecation_id)
+    .bind(sunset_at)
+    .bind(&req.successor)
+    .bind(&req.note)
+    .bind(req.enabled)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(row))
+}
+
+/// Remove a deprecation (and its usage history via cascade)
+pub async fn delete_deprecation(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(deprecation_id): Path<Uuid>,
+) -> ApiResult<Json<serde_json::Value>> {
+    require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    let result = sqlx::query("DELETE FROM api_deprecations WHERE id = $1")
+        .bind(deprecation_id)
+        .execute(&state.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    Ok(Json(serde_json::json!({ "deleted": true })))
+}
+
+/// Which orgs still call a deprecated route, heaviest callers first
+pub async fn deprecation_usage_report(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(deprecation_id): Path<Uuid>,
+) -> ApiResult<Json<Vec<DeprecationUsageEntry>>> {
+    require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    sqlx::query_scalar::<_, Uuid>("SELECT id FROM api_deprecations WHERE id = $1")
+        .bind(deprecation_id)
+        .fetch_optional(&state.pool)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let entries: Vec<DeprecationUsageEntry> = sqlx::query_as(
+        r#"
+        SELECT u.org_id, o.name AS org_name,
+               SUM(u.hits)::BIGINT AS total_hits,
+               MAX(u.last_seen_at) AS last_seen_at,
+               EXISTS (
+                   SELECT 1 FROM api_deprecation_notifications n
+                   WHERE n.deprecation_id = $1 AND n.org_id = u.org_id
+               ) AS notified
+        FROM api_deprecation_usage u
+        JOIN organizations o ON o.id = u.org_id
+        WHERE u.deprecation_id = $1
+        GROUP BY u.org_id, o.name
+        ORDER BY total_hits DESC
+        "#,
+    )
+    .bind(deprecation_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(entries))
+}
@@ -0,0 +1,441 @@
+//! Admin management of platform incidents
+//!
+//! Incidents created here feed the public status endpoint in
+//! [`crate::routes::status`] and fan out email notifications to status
+//! page subscribers on every update. Notification sending runs in the
+//! background so a slow email backend never blocks incident response.
+
+use axum::{
+    extract::{Extension, Path, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::shared::require_platform_admin;
+use crate::{
+    audit_constants::{admin_action, event_type, severity, target_type},
+    auth::AuthUser,
+    error::{ApiError, ApiResult},
+    state::AppState,
+};
+
+/// Component identifiers incidents can reference
+const VALID_COMPONENTS: &[&str] = &["api", "proxy", "dashboard", "webhooks", "email", "billing"];
+
+const VALID_SEVERITIES: &[&str] = &["minor", "major", "critical"];
+const VALID_STATUSES: &[&str] = &["investigating", "identified", "monitoring", "resolved"];
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct CreateIncidentRequest {
+    pub title: String,
+    pub severity: String,
+    #[serde(default)]
+    pub affected_components: Vec<String>,
+    /// First timeline entry, sent to subscribers
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PostIncidentUpdateRequest {
+    pub message: String,
+    /// New incident status; absent keeps the current one
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IncidentResponse {
+    pub id: Uuid,
+    pub title: String,
+    pub severity: String,
+    pub status: String,
+    pub affected_components: Vec<String>,
+    pub created_by: Option<Uuid>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub started_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub resolved_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct IncidentRow {
+    id: Uuid,
+    title: String,
+    severity: String,
+    status: String,
+    affected_components: Vec<String>,
+    created_by: Option<Uuid>,
+    started_at: OffsetDateTime,
+    resolved_at: Option<OffsetDateTime>,
+    created_at: OffsetDateTime,
+    updated_at: OffsetDateTime,
+}
+
+impl From<IncidentRow> for IncidentResponse {
+    fn from(r: IncidentRow) -> Self {
+        IncidentResponse {
+            id: r.id,
+            title: r.title,
+            severity: r.severity,
+            status: r.status,
+            affected_components: r.affected_components,
+            created_by: r.created_by,
+            started_at: r.started_at,
+            resolved_at: r.resolved_at,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+        }
+    }
+}
+
+const INCIDENT_COLUMNS: &str = "id, title, severity, status, affected_components, created_by, \
+                                started_at, resolved_at, created_at, updated_at";
+
+// =============================================================================
+// Validation
+// =============================================================================
+
+fn validate_title(title: &str) -> ApiResult<()> {
+    if title.trim().is_empty() || title.len() > 200 {
+        return Err(ApiError::Validation(
+            "Incident title must be 1-200 characters".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_severity(value: &str) -> ApiResult<()> {
+    if !VALID_SEVERITIES.contains(&value) {
+        return Err(ApiError::Validation(format!(
+            "severity must be one of: {}",
+            VALID_SEVERITIES.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+fn validate_status(value: &str) -> ApiResult<()> {
+    if !VALID_STATUSES.contains(&value) {
+        return Err(ApiError::Validation(format!(
+            "status must be one of: {}",
+            VALID_STATUSES.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+fn validate_components(components: &[String]) -> ApiResult<()> {
+    for component in components {
+        if !VALID_COMPONENTS.contains(&component.as_str()) {
+            return Err(ApiError::Validation(format!(
+                "Unknown component '{}'; valid components: {}",
+                component,
+                VALID_COMPONENTS.join(", ")
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_message(message: &str) -> ApiResult<()> {
+    if message.trim().is_empty() || message.len() > 5000 {
+        return Err(ApiError::Validation(
+            "Update message must be 1-5000 characters".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// List incidents, newest first (admin)
+pub async fn list_incidents(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<Vec<IncidentResponse>>> {
+    require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    let incidents: Vec<IncidentRow> = sqlx::query_as(&format!(
+        "SELECT {} FROM platform_incidents ORDER BY started_at DESC LIMIT 100",
+        INCIDENT_COLUMNS
+    ))
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(incidents.into_iter().map(Into::into).collect()))
+}
+
+/// Open an incident (admin)
+///
+/// Writes the first timeline entry and notifies status subscribers in
+/// the background.
+pub async fn create_incident(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<CreateIncidentRequest>,
+) -> ApiResult<Json<IncidentResponse>> {
+    let admin_user_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    validate_title(&req.title)?;
+    validate_severity(&req.severity)?;
+    validate_components(&req.affected_components)?;
+    validate_message(&req.message)?;
+
+    let incident: IncidentRow = sqlx::query_as(&format!(
+        r#"
+        INSERT INTO platform_incidents (title, severity, affected_components, created_by)
+        VALUES ($1, $2, $3, $4)
+        RETURNING {}
+        "#,
+        INCIDENT_COLUMNS
+    ))
+    .bind(req.title.trim())
+    .bind(&req.severity)
+    .bind(&req.affected_components)
+    .bind(admin_user_id)
+    .fetch_one(&state.pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO platform_incident_updates (incident_id, status, message, created_by)
+        VALUES ($1, $2, $3, $4)
+        "#,
+    )
+    .bind(incident.id)
+    .bind(&incident.status)
+    .bind(req.message.trim())
+    .bind(admin_user_id)
+    .execute(&state.pool)
+    .await?;
+
+    record_incident_audit(
+        &state,
+        admin_user_id,
+        admin_action::INCIDENT_CREATED,
+        incident.id,
+        serde_json::json!({
+            "title": incident.title,
+            "severity": incident.severity,
+            "affected_components": incident.affected_components,
+        }),
+    )
+    .await;
+
+    tokio::spawn(notify_subscribers(
+        state.clone(),
+        incident.id,
+        incident.status.clone(),
+        req.message.trim().to_string(),
+    ));
+
+    tracing::info!(
+        incident_id = %incident.id,
+        severity = %incident.severity,
+        "Platform incident opened"
+    );
+    Ok(Json(incident.into()))
+}
+
+/// Post a timeline update, optionally changing the incident status (admin)
+///
+/// Moving to `resolved` stamps `resolved_at`; reopening clears it.
+pub async fn post_incident_update(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(incident_id): Path<Uuid>,
+    Json(req): Json<PostIncidentUpdateRequest>,
+) -> ApiResult<Json<IncidentResponse>> {
+    let admin_user_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    validate_message(&req.message)?;
+    if let Some(status) = req.status.as_deref() {
+        validate_status(status)?;
+    }
+
+    let current: IncidentRow = sqlx::query_as(&format!(
+        "SELECT {} FROM platform_incidents WHERE id = $1",
+        INCIDENT_COLUMNS
+    ))
+    .bind(incident_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    let new_status = req.status.as_deref().unwrap_or(&current.status).to_string();
+
+    let incident: IncidentRow = sqlx::query_as(&format!(
+        r#"
+        UPDATE platform_incidents
+        SET status = $2,
+            resolved_at = CASE
+                WHEN $2 = 'resolved' THEN COALESCE(resolved_at, NOW())
+                ELSE NULL
+            END,
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING {}
+        "#,
+        INCIDENT_COLUMNS
+    ))
+    .bind(incident_id)
+    .bind(&new_status)
+    .fetch_one(&state.pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO platform_incident_updates (incident_id, status, message, created_by)
+        VALUES ($1, $2, $3, $4)
+        "#,
+    )
+    .bind(incident_id)
+    .bind(&new_status)
+    .bind(req.message.trim())
+    .bind(admin_user_id)
+    .execute(&state.pool)
+    .await?;
+
+    record_incident_audit(
+        &state,
+        admin_user_id,
+        admin_action::INCIDENT_UPDATED,
+        incident_id,
+        serde_json::json!({
+            "title": incident.title,
+            "status": incident.status,
+        }),
+    )
+    .await;
+
+    tokio::spawn(notify_subscribers(
+        state.clone(),
+        incident_id,
+        new_status,
+        req.message.trim().to_string(),
+    ));
+
+    tracing::info!(
+        incident_id = %incident_id,
+        status = %incident.status,
+        "Platform incident updated"
+    );
+    Ok(Json(incident.into()))
+}
+
+// =============================================================================
+// Subscriber Notifications
+// =============================================================================
+
+/// Email every status subscriber about an incident update
+///
+/// Runs in the background; failures are logged, never surfaced. Skipped
+/// entirely when no unsubscribe secret is configured, because the
+/// notifications must carry a working opt-out link.
+async fn notify_subscribers(state: AppState, incident_id: Uuid, status: String, message: String) {
+    let secret = state.security_email.unsubscribe_secret().to_string();
+    if secret.is_empty() {
+        tracing::warn!(
+            incident_id = %incident_id,
+            "Skipping incident notifications: no unsubscribe secret configured"
+        );
+        return;
+    }
+
+    let incident: Option<(String, String)> = match sqlx::query_as(
+        "SELECT title, severity FROM platform_incidents WHERE id = $1",
+    )
+    .bind(incident_id)
+    .fetch_optional(&state.pool)
+    .await
+    {
+        Ok(row) => row,
+        Err(e) => {
+            tracing::error!(incident_id = %incident_id, error = %e, "Failed to load incident for notification");
+            return;
+        }
+    };
+    let Some((title, incident_severity)) = incident else {
+        return;
+    };
+
+    let subscribers: Vec<String> = match sqlx::query_scalar(
+        "SELECT email FROM status_subscribers ORDER BY created_at",
+    )
+    .fetch_all(&state.pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            tracing::error!(incident_id = %incident_id, error = %e, "Failed to load status subscribers");
+            return;
+        }
+    };
+
+    let count = subscribers.len();
+    for email in subscribers {
+        let token = crate::routes::status::status_unsubscribe_token(&email, &secret);
+        let unsubscribe_url = format!(
+            "{}/api/v1/public/status/unsubscribe?token={}",
+            state.config.public_url, token
+        );
+        state
+            .security_email
+            .send_incident_update(
+                &email,
+                &title,
+                &incident_severity,
+                &status,
+                &message,
+                &unsubscribe_url,
+            )
+            .await;
+    }
+
+    tracing::info!(
+        incident_id = %incident_id,
+        subscribers = count,
+        "Incident update notifications sent"
+    );
+}
+
+/// Record an incident change in the admin audit log; failures are
+/// logged, never surfaced
+async fn record_incident_audit(
+    state: &AppState,
+    admin_user_id: Uuid,
+    action: &str,
+    incident_id: Uuid,
+    details: serde_json::Value,
+) {
+    if let Err(e) = sqlx::query(
+        r#"
+        INSERT INTO admin_audit_log
+            (admin_user_id, action, target_type, target_id, details, event_type, severity)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(admin_user_id)
+    .bind(action)
+    .bind(target_type::INCIDENT)
+    .bind(incident_id)
+    .bind(&details)
+    .bind(event_type::ADMIN_ACTION)
+    .bind(severity::WARNING)
+    .execute(&state.pool)
+    .await
+    {
+        tracing::warn!("Failed to log incident audit: {}", e);
+    }
+}
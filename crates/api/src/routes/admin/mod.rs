@@ -8,10 +8,26 @@
 //! - `mcp_logs`: MCP proxy request logs
 //! - `staff_emails`: Staff email assignment
 //! - `organizations`: Organization listing and management
+//! - `alerts`: Security alert configuration and triage
+//! - `tokens`: Scoped admin API tokens for internal automation
 //! - `shared`: Shared types, helpers, and utilities
 
 // Sub-modules
+pub mod alerts;
+pub mod analytics;
+pub mod approvals;
+pub mod deprecations;
+pub mod incidents;
+pub mod product_metrics;
+pub mod rate_limits;
+pub mod reserved_subdomains;
+#[cfg(feature = "billing")]
+pub mod revenue;
+pub mod scheduler;
 pub mod shared;
+pub mod support_rules;
+pub mod system;
+pub mod tokens;
 
 // Re-export main router
 pub use super::admin_legacy::*;
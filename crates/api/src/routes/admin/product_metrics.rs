@@ -0,0 +1,200 @@
+//! Product metrics - activation, weekly active orgs, and churn
+//!
+//! Daily rollups are stored in `product_metrics_rollups` and computed by the
+//! worker (daily) or on demand via the refresh endpoint. Exposed to platform
+//! admins under `/admin/analytics/product`.
+
+use axum::{
+    extract::{Extension, Query, State},
+    http::header,
+    response::IntoResponse,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use time::Date;
+
+use crate::{auth::AuthUser, error::ApiResult, state::AppState};
+
+use super::shared::require_platform_admin;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct ProductMetricsQuery {
+    /// Number of trailing days of rollups to return (default 30, max 365)
+    pub days: Option<i64>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct ProductMetricsRollup {
+    pub metric_date: Date,
+    pub orgs_created: i32,
+    pub orgs_first_mcp: i32,
+    pub orgs_first_proxy_call: i32,
+    pub weekly_active_orgs: i32,
+    pub churned_orgs: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProductMetricsResponse {
+    pub days: i64,
+    pub rollups: Vec<ProductMetricsRollup>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RefreshResponse {
+    pub computed: bool,
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// GET /admin/analytics/product
+///
+/// Return daily product metrics rollups (most recent first).
+pub async fn get_product_metrics(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<ProductMetricsQuery>,
+) -> ApiResult<Json<ProductMetricsResponse>> {
+    require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    let days = query.days.unwrap_or(30).clamp(1, 365);
+    let rollups = fetch_rollups(&state.pool, days).await?;
+
+    Ok(Json(ProductMetricsResponse { days, rollups }))
+}
+
+/// GET /admin/analytics/product/export
+///
+/// Export rollups as CSV for spreadsheet analysis.
+pub async fn export_product_metrics(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<ProductMetricsQuery>,
+) -> ApiResult<impl IntoResponse> {
+    require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    let days = query.days.unwrap_or(90).clamp(1, 365);
+    let rollups = fetch_rollups(&state.pool, days).await?;
+
+    let mut csv = String::from(
+        "metric_date,orgs_created,orgs_first_mcp,orgs_first_proxy_call,weekly_active_orgs,churned_orgs\n",
+    );
+    for r in &rollups {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            r.metric_date,
+            r.orgs_created,
+            r.orgs_first_mcp,
+            r.orgs_first_proxy_call,
+            r.weekly_active_orgs,
+            r.churned_orgs
+        ));
+    }
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"product_metrics.csv\"",
+            ),
+        ],
+        csv,
+    ))
+}
+
+/// POST /admin/analytics/product/refresh
+///
+/// Recompute the rollup for the current day (write access required).
+pub async fn refresh_product_metrics(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<RefreshResponse>> {
+    require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    compute_daily_rollup(&state.pool).await?;
+
+    Ok(Json(RefreshResponse { computed: true }))
+}
+
+// =============================================================================
+// Rollup Computation
+// =============================================================================
+
+async fn fetch_rollups(pool: &PgPool, days: i64) -> ApiResult<Vec<ProductMetricsRollup>> {
+    let rollups: Vec<ProductMetricsRollup> = sqlx::query_as(
+        r#"
+        SELECT metric_date, orgs_created, orgs_first_mcp, orgs_first_proxy_call,
+               weekly_active_orgs, churned_orgs
+        FROM product_metrics_rollups
+        WHERE metric_date >= CURRENT_DATE - $1::int
+        ORDER BY metric_date DESC
+        "#,
+    )
+    .bind(days as i32)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rollups)
+}
+
+/// Compute (or recompute) the product metrics rollup for the current day.
+///
+/// Also called by the worker's daily product metrics job.
+pub async fn compute_daily_rollup(pool: &PgPool) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO product_metrics_rollups (
+            metric_date, orgs_created, orgs_first_mcp, orgs_first_proxy_call,
+            weekly_active_orgs, churned_orgs, computed_at
+        )
+        SELECT
+            CURRENT_DATE,
+            -- Orgs created today
+            (SELECT COUNT(*) FROM organizations
+             WHERE created_at >= CURRENT_DATE)::int,
+            -- Orgs whose first MCP was created today (activation step 1)
+            (SELECT COUNT(*) FROM (
+                SELECT org_id, MIN(created_at) as first_mcp_at
+                FROM mcp_instances
+                GROUP BY org_id
+            ) f WHERE f.first_mcp_at >= CURRENT_DATE)::int,
+            -- Orgs whose first proxy usage was recorded today (activation step 2)
+            (SELECT COUNT(*) FROM (
+                SELECT org_id, MIN(period_start) as first_usage_at
+                FROM usage_records
+                WHERE request_count > 0
+                GROUP BY org_id
+            ) f WHERE f.first_usage_at >= CURRENT_DATE)::int,
+            -- Orgs with any usage in the trailing 7 days
+            (SELECT COUNT(DISTINCT org_id) FROM usage_records
+             WHERE period_start >= NOW() - INTERVAL '7 days'
+               AND request_count > 0)::int,
+            -- Orgs whose last usage is 30+ days ago (churned after prior activity)
+            (SELECT COUNT(*) FROM (
+                SELECT org_id, MAX(period_start) as last_usage_at
+                FROM usage_records
+                WHERE request_count > 0
+                GROUP BY org_id
+            ) l WHERE l.last_usage_at < NOW() - INTERVAL '30 days')::int,
+            NOW()
+        ON CONFLICT (metric_date) DO UPDATE SET
+            orgs_created = EXCLUDED.orgs_created,
+            orgs_first_mcp = EXCLUDED.orgs_first_mcp,
+            orgs_first_proxy_call = EXCLUDED.orgs_first_proxy_call,
+            weekly_active_orgs = EXCLUDED.weekly_active_orgs,
+            churned_orgs = EXCLUDED.churned_orgs,
+            computed_at = EXCLUDED.computed_at
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
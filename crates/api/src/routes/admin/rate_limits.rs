@@ -0,0 +1,616 @@
+//! Admin rate limit overrides - per-org and per-API-key
+//!
+//! Platform admins can override the requests-per-minute limits the
+//! RateLimiter applies, either org-wide or for individual API keys.
+//! Overrides are stored in rate_limit_overrides, pushed into the live
+//! limiter immediately on change, and reloaded periodically by every
+//! instance so no restart is needed.
+
+use axum::{
+    extract::{Extension, Path, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::shared::require_platform_admin;
+use crate::{
+    audit_constants::{admin_action, event_type, severity, target_type},
+    auth::AuthUser,
+    error::{ApiError, ApiResult},
+    state::AppState,
+};
+
+/// Valid range for an override, matching the table CHECK constraint
+const MIN_RPM: u32 = 1;
+const MAX_RPM: u32 = 100_000;
+
+/// Exemptions are temporary by design: cap both the duration and the
+/// multiplier so "lift the limit for a migration" can't quietly become a
+/// permanent unlimited grant
+const MAX_EXEMPTION_DAYS: i64 = 30;
+const MAX_MULTIPLIER: f32 = 100.0;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+/// Replace the rate limit overrides for an org. PUT semantics: an absent
+/// or null `requests_per_minute` clears the org-wide override, and
+/// `api_key_overrides` replaces all per-key overrides (empty clears them).
+#[derive(Debug, Deserialize)]
+pub struct PutRateLimitsRequest {
+    /// Org-wide requests-per-minute override
+    pub requests_per_minute: Option<u32>,
+    /// Per-API-key overrides (keys must belong to the org)
+    #[serde(default)]
+    pub api_key_overrides: Vec<ApiKeyOverrideRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiKeyOverrideRequest {
+    pub api_key_id: Uuid,
+    pub requests_per_minute: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrgRateLimitsResponse {
+    pub org_id: Uuid,
+    /// Org-wide override, None when the tier defaults apply
+    pub requests_per_minute: Option<u32>,
+    pub api_key_overrides: Vec<ApiKeyOverrideEntry>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct ApiKeyOverrideEntry {
+    pub api_key_id: Uuid,
+    /// Key name for display; None if the key was deleted
+    pub key_name: Option<String>,
+    pub requests_per_minute: i32,
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// Get the rate limit overrides configured for an organization
+pub async fn get_org_rate_limits(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(org_id): Path<Uuid>,
+) -> ApiResult<Json<OrgRateLimitsResponse>> {
+    require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    require_org_exists(&state, org_id).await?;
+    build_response(&state, org_id).await.map(Json)
+}
+
+/// Replace the rate limit overrides for an organization
+///
+/// Writes the override table and pushes it into this instance's live
+/// limiter immediately; other instances pick the change up on their next
+/// periodic reload.
+pub async fn put_org_rate_limits(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(org_id): Path<Uuid>,
+    Json(req): Json<PutRateLimitsRequest>,
+) -> ApiResult<Json<OrgRateLimitsResponse>> {
+    let admin_user_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    require_org_exists(&state, org_id).await?;
+
+    if let Some(rpm) = req.requests_per_minute {
+        validate_rpm(rpm)?;
+    }
+    for key_override in &req.api_key_overrides {
+        validate_rpm(key_override.requests_per_minute)?;
+    }
+
+    // All referenced API keys must belong to the org
+    for key_override in &req.api_key_overrides {
+        let belongs: Option<(Uuid,)> =
+            sqlx::query_as("SELECT id FROM api_keys WHERE id = $1 AND org_id = $2")
+                .bind(key_override.api_key_id)
+                .bind(org_id)
+                .fetch_optional(&state.pool)
+                .await?;
+        if belongs.is_none() {
+            return Err(ApiError::Validation(format!(
+                "API key {} does not belong to this organization",
+                key_override.api_key_id
+            )));
+        }
+    }
+
+    // Replace the org's overrides in one transaction
+    let mut tx = state.pool.begin().await?;
+
+    sqlx::query("DELETE FROM rate_limit_overrides WHERE org_id = $1")
+        .bind(org_id)
+        .execute(&mut *tx)
+        .await?;
+
+    if let Some(rpm) = req.requests_per_minute {
+        sqlx::query(
+            r#"
+            INSERT INTO rate_limit_overrides (org_id, api_key_id, requests_per_minute, created_by)
+            VALUES ($1, NULL, $2, $3)
+            "#,
+        )
+        .bind(org_id)
+        .bind(rpm as i32)
+        .bind(admin_user_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    for key_override in &req.api_key_overrides {
+        sqlx::query(
+            r#"
+            INSERT INTO rate_limit_overrides (org_id, api_key_id, requests_per_minute, created_by)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(org_id)
+        .bind(key_override.api_key_id)
+        .bind(key_override.requests_per_minute as i32)
+        .bind(admin_user_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    // Hot-apply to the live limiter without waiting for the next reload
+    apply_live_overrides(&state).await;
+
+    tracing::info!(
+        org_id = %org_id,
+        admin = %admin_user_id,
+        org_rpm = ?req.requests_per_minute,
+        key_overrides = req.api_key_overrides.len(),
+        "Rate limit overrides updated"
+    );
+
+    build_response(&state, org_id).await.map(Json)
+}
+
+// =============================================================================
+// Temporary Exemptions
+// =============================================================================
+
+/// Grant a temporary exemption: a multiplier on the effective limit or a
+/// full bypass, with a mandatory reason and expiry
+#[derive(Debug, Deserialize)]
+pub struct CreateExemptionRequest {
+    /// Omit for an org-wide exemption, set to scope it to one key
+    pub api_key_id: Option<Uuid>,
+    /// "multiplier" or "bypass"
+    pub kind: String,
+    /// Required for "multiplier" (must be > 1), forbidden for "bypass"
+    pub multiplier: Option<f32>,
+    /// Why the exemption was granted (required, lands in the audit log)
+    pub reason: String,
+    /// RFC 3339; must be in the future, at most 30 days out
+    pub expires_at: String,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct ExemptionEntry {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    /// None for an org-wide exemption
+    pub api_key_id: Option<Uuid>,
+    /// Key name for display; None for org-wide or deleted keys
+    pub key_name: Option<String>,
+    pub kind: String,
+    pub multiplier: Option<f32>,
+    pub reason: String,
+    #[serde(serialize_with = "serialize_rfc3339")]
+    pub expires_at: OffsetDateTime,
+    #[serde(serialize_with = "serialize_optional_rfc3339")]
+    pub revoked_at: Option<OffsetDateTime>,
+    pub created_by: Option<Uuid>,
+    #[serde(serialize_with = "serialize_rfc3339")]
+    pub created_at: OffsetDateTime,
+    /// Currently in force (not revoked, not expired)
+    pub active: bool,
+}
+
+/// List an org's rate limit exemptions, most recent first
+///
+/// Includes revoked and expired rows so support can see the history.
+pub async fn list_rate_limit_exemptions(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(org_id): Path<Uuid>,
+) -> ApiResult<Json<Vec<ExemptionEntry>>> {
+    require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    require_org_exists(&state, org_id).await?;
+
+    let exemptions: Vec<ExemptionEntry> = sqlx::query_as(
+        r#"
+        SELECT e.id, e.org_id, e.api_key_id, ak.name AS key_name, e.kind,
+               e.multiplier, e.reason, e.expires_at, e.revoked_at,
+               e.created_by, e.created_at,
+               (e.revoked_at IS NULL AND e.expires_at > NOW()) AS active
+        FROM rate_limit_exemptions e
+        LEFT JOIN api_keys ak ON ak.id = e.api_key_id
+        WHERE e.org_id = $1
+        ORDER BY e.created_at DESC
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(exemptions))
+}
+
+/// Grant a temporary rate limit exemption for an org or one of its keys
+///
+/// Applies to the live limiter immediately; reversion is automatic at
+/// `expires_at` (the limiter re-checks expiry on every lookup).
+pub async fn create_rate_limit_exemption(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(org_id): Path<Uuid>,
+    Json(req): Json<CreateExemptionRequest>,
+) -> ApiResult<Json<ExemptionEntry>> {
+    let admin_user_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    require_org_exists(&state, org_id).await?;
+
+    let multiplier = validate_exemption_kind(&req.kind, req.multiplier)?;
+
+    if req.reason.trim().is_empty() {
+        return Err(ApiError::Validation(
+            "A reason is required for every exemption".to_string(),
+        ));
+    }
+
+    let expires_at = OffsetDateTime::parse(
+        &req.expires_at,
+        &time::format_description::well_known::Rfc3339,
+    )
+    .map_err(|_| ApiError::Validation("expires_at must be an RFC 3339 timestamp".to_string()))?;
+    let now = OffsetDateTime::now_utc();
+    if expires_at <= now {
+        return Err(ApiError::Validation(
+            "expires_at must be in the future".to_string(),
+        ));
+    }
+    if expires_at > now + time::Duration::days(MAX_EXEMPTION_DAYS) {
+        return Err(ApiError::Validation(format!(
+            "Exemptions may last at most {} days",
+            MAX_EXEMPTION_DAYS
+        )));
+    }
+
+    // A per-key exemption must reference a key in this org
+    if let Some(api_key_id) = req.api_key_id {
+        let belongs: Option<(Uuid,)> =
+            sqlx::query_as("SELECT id FROM api_keys WHERE id = $1 AND org_id = $2")
+                .bind(api_key_id)
+                .bind(org_id)
+                .fetch_optional(&state.pool)
+                .await?;
+        if belongs.is_none() {
+            return Err(ApiError::Validation(format!(
+                "API key {} does not belong to this organization",
+                api_key_id
+            )));
+        }
+    }
+
+    // One active exemption per target keeps precedence unambiguous
+    let existing: Option<(Uuid,)> = sqlx::query_as(
+        r#"
+        SELECT id FROM rate_limit_exemptions
+        WHERE org_id = $1 AND api_key_id IS NOT DISTINCT FROM $2
+          AND revoked_at IS NULL AND expires_at > NOW()
+        "#,
+    )
+    .bind(org_id)
+    .bind(req.api_key_id)
+    .fetch_optional(&state.pool)
+    .await?;
+    if existing.is_some() {
+        return Err(ApiError::Validation(
+            "An active exemption already exists for this target; revoke it first".to_string(),
+        ));
+    }
+
+    let exemption: ExemptionEntry = sqlx::query_as(
+        r#"
+        WITH inserted AS (
+            INSERT INTO rate_limit_exemptions
+                (org_id, api_key_id, kind, multiplier, reason, expires_at, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+        )
+        SELECT i.id, i.org_id, i.api_key_id, ak.name AS key_name, i.kind,
+               i.multiplier, i.reason, i.expires_at, i.revoked_at,
+               i.created_by, i.created_at, TRUE AS active
+        FROM inserted i
+        LEFT JOIN api_keys ak ON ak.id = i.api_key_id
+        "#,
+    )
+    .bind(org_id)
+    .bind(req.api_key_id)
+    .bind(&req.kind)
+    .bind(multiplier)
+    .bind(req.reason.trim())
+    .bind(expires_at)
+    .bind(admin_user_id)
+    .fetch_one(&state.pool)
+    .await?;
+
+    record_exemption_audit(
+        &state,
+        admin_user_id,
+        admin_action::RATE_LIMIT_EXEMPTION_GRANTED,
+        &exemption,
+        // A bypass removes the limit entirely; flag it for review
+        if exemption.kind == "bypass" {
+            severity::CRITICAL
+        } else {
+            severity::WARNING
+        },
+    )
+    .await;
+
+    apply_live_overrides(&state).await;
+
+    tracing::info!(
+        org_id = %org_id,
+        admin = %admin_user_id,
+        exemption_id = %exemption.id,
+        kind = %exemption.kind,
+        expires_at = %req.expires_at,
+        "Rate limit exemption granted"
+    );
+
+    Ok(Json(exemption))
+}
+
+/// Revoke an exemption before its expiry (soft delete; the row stays for
+/// audit)
+pub async fn revoke_rate_limit_exemption(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(exemption_id): Path<Uuid>,
+) -> ApiResult<Json<ExemptionEntry>> {
+    let admin_user_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    let exemption: ExemptionEntry = sqlx::query_as(
+        r#"
+        WITH revoked AS (
+            UPDATE rate_limit_exemptions
+            SET revoked_at = NOW()
+            WHERE id = $1 AND revoked_at IS NULL
+            RETURNING *
+        )
+        SELECT r.id, r.org_id, r.api_key_id, ak.name AS key_name, r.kind,
+               r.multiplier, r.reason, r.expires_at, r.revoked_at,
+               r.created_by, r.created_at, FALSE AS active
+        FROM revoked r
+        LEFT JOIN api_keys ak ON ak.id = r.api_key_id
+        "#,
+    )
+    .bind(exemption_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    record_exemption_audit(
+        &state,
+        admin_user_id,
+        admin_action::RATE_LIMIT_EXEMPTION_REVOKED,
+        &exemption,
+        severity::INFO,
+    )
+    .await;
+
+    apply_live_overrides(&state).await;
+
+    tracing::info!(
+        org_id = %exemption.org_id,
+        admin = %admin_user_id,
+        exemption_id = %exemption_id,
+        "Rate limit exemption revoked"
+    );
+
+    Ok(Json(exemption))
+}
+
+/// Validate kind/multiplier together; returns the multiplier to store
+fn validate_exemption_kind(kind: &str, multiplier: Option<f32>) -> Result<Option<f32>, ApiError> {
+    match kind {
+        "multiplier" => {
+            let m = multiplier.ok_or_else(|| {
+                ApiError::Validation(
+                    "multiplier is required when kind is \"multiplier\"".to_string(),
+                )
+            })?;
+            if !m.is_finite() || m <= 1.0 || m > MAX_MULTIPLIER {
+                return Err(ApiError::Validation(format!(
+                    "multiplier must be greater than 1 and at most {}",
+                    MAX_MULTIPLIER
+                )));
+            }
+            Ok(Some(m))
+        }
+        "bypass" => {
+            if multiplier.is_some() {
+                return Err(ApiError::Validation(
+                    "multiplier must be omitted when kind is \"bypass\"".to_string(),
+                ));
+            }
+            Ok(None)
+        }
+        _ => Err(ApiError::Validation(
+            "kind must be \"multiplier\" or \"bypass\"".to_string(),
+        )),
+    }
+}
+
+/// SOC 2 CC6.1: every exemption grant/revoke lands in the admin audit log
+async fn record_exemption_audit(
+    state: &AppState,
+    admin_user_id: Uuid,
+    action: &str,
+    exemption: &ExemptionEntry,
+    severity: &str,
+) {
+    let details = serde_json::json!({
+        "exemption_id": exemption.id,
+        "api_key_id": exemption.api_key_id,
+        "kind": exemption.kind,
+        "multiplier": exemption.multiplier,
+        "reason": exemption.reason,
+        "expires_at": exemption
+            .expires_at
+            .format(&time::format_description::well_known::Rfc3339)
+            .ok(),
+    });
+
+    if let Err(e) = sqlx::query(
+        r#"
+        INSERT INTO admin_audit_log (
+            admin_user_id, action, target_type, target_id, details,
+            event_type, severity
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(admin_user_id)
+    .bind(action)
+    .bind(target_type::ORGANIZATION)
+    .bind(exemption.org_id)
+    .bind(&details)
+    .bind(event_type::CONFIGURATION)
+    .bind(severity)
+    .execute(&state.pool)
+    .await
+    {
+        tracing::warn!(error = %e, "Failed to write exemption audit log entry");
+    }
+}
+
+/// Hot-apply the override + exemption tables to this instance's limiter
+async fn apply_live_overrides(state: &AppState) {
+    match plexmcp_shared::rate_limit::load_overrides(&state.pool).await {
+        Ok(overrides) => state.rate_limiter.set_overrides(overrides).await,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to hot-apply rate limit overrides");
+        }
+    }
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+fn validate_rpm(rpm: u32) -> Result<(), ApiError> {
+    if !(MIN_RPM..=MAX_RPM).contains(&rpm) {
+        return Err(ApiError::Validation(format!(
+            "requests_per_minute must be between {} and {}",
+            MIN_RPM, MAX_RPM
+        )));
+    }
+    Ok(())
+}
+
+async fn require_org_exists(state: &AppState, org_id: Uuid) -> Result<(), ApiError> {
+    sqlx::query_scalar::<_, Uuid>("SELECT id FROM organizations WHERE id = $1")
+        .bind(org_id)
+        .fetch_optional(&state.pool)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    Ok(())
+}
+
+async fn build_response(state: &AppState, org_id: Uuid) -> Result<OrgRateLimitsResponse, ApiError> {
+    let org_rpm: Option<(i32,)> = sqlx::query_as(
+        "SELECT requests_per_minute FROM rate_limit_overrides WHERE org_id = $1 AND api_key_id IS NULL",
+    )
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    let api_key_overrides: Vec<ApiKeyOverrideEntry> = sqlx::query_as(
+        r#"
+        SELECT rlo.api_key_id, ak.name AS key_name, rlo.requests_per_minute
+        FROM rate_limit_overrides rlo
+        LEFT JOIN api_keys ak ON ak.id = rlo.api_key_id
+        WHERE rlo.org_id = $1 AND rlo.api_key_id IS NOT NULL
+        ORDER BY ak.name NULLS LAST
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(OrgRateLimitsResponse {
+        org_id,
+        requests_per_minute: org_rpm.map(|(rpm,)| rpm as u32),
+        api_key_overrides,
+    })
+}
+
+fn serialize_rfc3339<S: serde::Serializer>(
+    dt: &OffsetDateTime,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let formatted = dt
+        .format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_else(|_| dt.to_string());
+    serializer.serialize_str(&formatted)
+}
+
+fn serialize_optional_rfc3339<S: serde::Serializer>(
+    dt: &Option<OffsetDateTime>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match dt {
+        Some(dt) => serialize_rfc3339(dt, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_rpm_bounds() {
+        assert!(validate_rpm(0).is_err());
+        assert!(validate_rpm(1).is_ok());
+        assert!(validate_rpm(100_000).is_ok());
+        assert!(validate_rpm(100_001).is_err());
+    }
+
+    #[test]
+    fn test_validate_exemption_kind() {
+        // Multiplier requires a factor above 1 and within the cap
+        assert_eq!(
+            validate_exemption_kind("multiplier", Some(2.0)).unwrap(),
+            Some(2.0)
+        );
+        assert!(validate_exemption_kind("multiplier", None).is_err());
+        assert!(validate_exemption_kind("multiplier", Some(1.0)).is_err());
+        assert!(validate_exemption_kind("multiplier", Some(f32::NAN)).is_err());
+        assert!(validate_exemption_kind("multiplier", Some(MAX_MULTIPLIER + 1.0)).is_err());
+
+        // Bypass carries no multiplier
+        assert_eq!(validate_exemption_kind("bypass", None).unwrap(), None);
+        assert!(validate_exemption_kind("bypass", Some(2.0)).is_err());
+
+        assert!(validate_exemption_kind("unlimited", None).is_err());
+    }
+}
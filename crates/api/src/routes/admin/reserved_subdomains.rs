@@ -0,0 +1,318 @@
+//! Admin-managed reserved subdomains
+//!
+//! The routing layer ships with a hardcoded baseline of reserved
+//! subdomains (api, www, admin, ...). Platform admins can reserve
+//! additional names at runtime - trademarks, abuse takedowns, upcoming
+//! product names - without a deploy. Baseline entries are immutable;
+//! only runtime entries can be removed.
+
+use axum::{
+    extract::{Extension, Path, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::shared::require_platform_admin;
+use crate::{
+    audit_constants::{admin_action, event_type, severity, target_type},
+    auth::AuthUser,
+    error::{ApiError, ApiResult},
+    routing::RESERVED_SUBDOMAINS,
+    state::AppState,
+};
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct ReserveSubdomainRequest {
+    pub subdomain: String,
+    /// Why the name is being reserved (required, lands in the audit log)
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReservedSubdomainEntry {
+    pub subdomain: String,
+    /// None for baseline entries, which carry no recorded reason
+    pub reason: Option<String>,
+    /// Compiled-in baseline entries cannot be removed
+    pub built_in: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct ReservedSubdomainRow {
+    subdomain: String,
+    reason: String,
+    created_by: Option<Uuid>,
+    created_at: OffsetDateTime,
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// List all reserved subdomains: the compiled-in baseline followed by
+/// admin-managed entries
+pub async fn list_reserved_subdomains(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<Vec<ReservedSubdomainEntry>>> {
+    require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    let mut entries: Vec<ReservedSubdomainEntry> = RESERVED_SUBDOMAINS
+        .iter()
+        .map(|subdomain| ReservedSubdomainEntry {
+            subdomain: subdomain.to_string(),
+            reason: None,
+            built_in: true,
+            created_by: None,
+            created_at: None,
+        })
+        .collect();
+
+    let managed: Vec<ReservedSubdomainRow> = sqlx::query_as(
+        "SELECT subdomain, reason, created_by, created_at FROM reserved_subdomains ORDER BY subdomain",
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    entries.extend(managed.into_iter().map(|row| ReservedSubdomainEntry {
+        subdomain: row.subdomain,
+        reason: Some(row.reason),
+        built_in: false,
+        created_by: row.created_by,
+        created_at: Some(row.created_at),
+    }));
+
+    Ok(Json(entries))
+}
+
+/// Reserve a subdomain so no organization can claim it
+///
+/// Takes effect immediately: the host cache entry for the name is
+/// dropped, so the next request to it is rejected as reserved. Fails if
+/// an org already uses the name - suspend or migrate them first.
+pub async fn create_reserved_subdomain(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<ReserveSubdomainRequest>,
+) -> ApiResult<Json<ReservedSubdomainEntry>> {
+    let admin_user_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    let subdomain = req.subdomain.trim().to_lowercase();
+    validate_reserved_subdomain(&subdomain)?;
+
+    if req.reason.trim().is_empty() {
+        return Err(ApiError::Validation(
+            "A reason is required for every reservation".to_string(),
+        ));
+    }
+
+    if RESERVED_SUBDOMAINS.contains(&subdomain.as_str()) {
+        return Err(ApiError::Conflict(
+            "This subdomain is already reserved by the baseline list".to_string(),
+        ));
+    }
+
+    // Reserving a name an org actively uses would break their routing;
+    // make the admin resolve that explicitly first
+    let in_use: Option<(Uuid,)> = sqlx::query_as(
+        "SELECT id FROM organizations WHERE custom_subdomain = $1 OR auto_subdomain = $1",
+    )
+    .bind(&subdomain)
+    .fetch_optional(&state.pool)
+    .await?;
+    if let Some((org_id,)) = in_use {
+        return Err(ApiError::Conflict(format!(
+            "This subdomain is in use by organization {}; move them off it first",
+            org_id
+        )));
+    }
+
+    let entry: ReservedSubdomainRow = sqlx::query_as(
+        r#"
+        INSERT INTO reserved_subdomains (subdomain, reason, created_by)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (subdomain) DO NOTHING
+        RETURNING subdomain, reason, created_by, created_at
+        "#,
+    )
+    .bind(&subdomain)
+    .bind(req.reason.trim())
+    .bind(admin_user_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::Conflict("This subdomain is already reserved".to_string()))?;
+
+    // Drop any cached resolution so the reservation applies immediately
+    state
+        .host_resolver
+        .invalidate_host(&format!("{}.{}", subdomain, state.config.base_domain));
+
+    record_reservation_audit(
+        &state,
+        admin_user_id,
+        admin_action::RESERVED_SUBDOMAIN_ADDED,
+        &subdomain,
+        Some(req.reason.trim()),
+    )
+    .await;
+
+    tracing::info!(
+        subdomain = %subdomain,
+        admin = %admin_user_id,
+        "Subdomain reserved"
+    );
+
+    Ok(Json(ReservedSubdomainEntry {
+        subdomain: entry.subdomain,
+        reason: Some(entry.reason),
+        built_in: false,
+        created_by: entry.created_by,
+        created_at: Some(entry.created_at),
+    }))
+}
+
+/// Release an admin-managed reservation; baseline entries cannot be removed
+pub async fn delete_reserved_subdomain(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(subdomain): Path<String>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let admin_user_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    let subdomain = subdomain.trim().to_lowercase();
+
+    if RESERVED_SUBDOMAINS.contains(&subdomain.as_str()) {
+        return Err(ApiError::Validation(
+            "Baseline reserved subdomains cannot be removed".to_string(),
+        ));
+    }
+
+    let deleted: Option<(String,)> =
+        sqlx::query_as("DELETE FROM reserved_subdomains WHERE subdomain = $1 RETURNING subdomain")
+            .bind(&subdomain)
+            .fetch_optional(&state.pool)
+            .await?;
+
+    if deleted.is_none() {
+        return Err(ApiError::NotFound);
+    }
+
+    state
+        .host_resolver
+        .invalidate_host(&format!("{}.{}", subdomain, state.config.base_domain));
+
+    record_reservation_audit(
+        &state,
+        admin_user_id,
+        admin_action::RESERVED_SUBDOMAIN_REMOVED,
+        &subdomain,
+        None,
+    )
+    .await;
+
+    tracing::info!(
+        subdomain = %subdomain,
+        admin = %admin_user_id,
+        "Subdomain reservation released"
+    );
+
+    Ok(Json(serde_json::json!({ "deleted": subdomain })))
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Validate a subdomain label for reservation
+///
+/// Looser than the org-facing custom subdomain rules: admins can reserve
+/// short names (e.g. "ai") that orgs could never claim, up to the DNS
+/// label limit of 63 characters.
+fn validate_reserved_subdomain(subdomain: &str) -> Result<(), ApiError> {
+    if subdomain.is_empty() || subdomain.len() > 63 {
+        return Err(ApiError::Validation(
+            "Subdomain must be between 1 and 63 characters".to_string(),
+        ));
+    }
+
+    if !subdomain
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err(ApiError::Validation(
+            "Subdomain can only contain lowercase letters, numbers, and hyphens".to_string(),
+        ));
+    }
+
+    if subdomain.starts_with('-') || subdomain.ends_with('-') {
+        return Err(ApiError::Validation(
+            "Subdomain cannot start or end with a hyphen".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// SOC 2 CC6.1: every reservation change lands in the admin audit log
+async fn record_reservation_audit(
+    state: &AppState,
+    admin_user_id: Uuid,
+    action: &str,
+    subdomain: &str,
+    reason: Option<&str>,
+) {
+    let details = serde_json::json!({
+        "subdomain": subdomain,
+        "reason": reason,
+    });
+
+    if let Err(e) = sqlx::query(
+        r#"
+        INSERT INTO admin_audit_log (
+            admin_user_id, action, target_type, target_id, details,
+            event_type, severity
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(admin_user_id)
+    .bind(action)
+    .bind(target_type::SYSTEM)
+    .bind(None::<Uuid>)
+    .bind(&details)
+    .bind(event_type::CONFIGURATION)
+    .bind(severity::WARNING)
+    .execute(&state.pool)
+    .await
+    {
+        tracing::warn!(error = %e, "Failed to write reservation audit log entry");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_reserved_subdomain() {
+        // Short names are allowed for reservations, unlike org claims
+        assert!(validate_reserved_subdomain("ai").is_ok());
+        assert!(validate_reserved_subdomain("my-brand").is_ok());
+        assert!(validate_reserved_subdomain("x9").is_ok());
+
+        assert!(validate_reserved_subdomain("").is_err());
+        assert!(validate_reserved_subdomain(&"a".repeat(64)).is_err());
+        assert!(validate_reserved_subdomain("Upper").is_err());
+        assert!(validate_reserved_subdomain("-leading").is_err());
+        assert!(validate_reserved_subdomain("trailing-").is_err());
+        assert!(validate_reserved_subdomain("dots.not.allowed").is_err());
+    }
+}
@@ -0,0 +1,88 @@
+//! Admin revenue analytics routes
+//!
+//! Serves MRR/ARR, expansion/contraction, overage revenue, and cohort LTV
+//! snapshots computed by the billing crate's `RevenueAnalyticsService`.
+//! Only compiled when the billing feature is enabled.
+
+use axum::{
+    extract::{Extension, Query, State},
+    Json,
+};
+use plexmcp_billing::RevenueSnapshot;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::AuthUser,
+    error::{ApiError, ApiResult},
+    state::AppState,
+};
+
+use super::shared::require_platform_admin;
+
+#[derive(Debug, Deserialize)]
+pub struct RevenueQuery {
+    /// Number of trailing days of snapshots to return (default 90, max 365)
+    pub days: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RevenueResponse {
+    /// Most recent snapshot (None until the first refresh has run)
+    pub latest: Option<RevenueSnapshot>,
+    /// Daily snapshots for trend charts, most recent first
+    pub history: Vec<RevenueSnapshot>,
+}
+
+/// GET /admin/analytics/revenue
+///
+/// Return the latest revenue snapshot plus daily history for trends.
+pub async fn get_revenue_analytics(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<RevenueQuery>,
+) -> ApiResult<Json<RevenueResponse>> {
+    require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    let billing = state
+        .billing
+        .as_ref()
+        .ok_or_else(|| ApiError::Database("Billing not configured".into()))?;
+
+    let days = query.days.unwrap_or(90).clamp(1, 365);
+
+    let latest = billing
+        .revenue
+        .get_latest_snapshot()
+        .await
+        .map_err(|e| ApiError::Database(format!("Billing error: {}", e)))?;
+    let history = billing
+        .revenue
+        .get_snapshots(days)
+        .await
+        .map_err(|e| ApiError::Database(format!("Billing error: {}", e)))?;
+
+    Ok(Json(RevenueResponse { latest, history }))
+}
+
+/// POST /admin/analytics/revenue/refresh
+///
+/// Recompute today's revenue snapshot (write access required).
+pub async fn refresh_revenue_analytics(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<RevenueSnapshot>> {
+    require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    let billing = state
+        .billing
+        .as_ref()
+        .ok_or_else(|| ApiError::Database("Billing not configured".into()))?;
+
+    let snapshot = billing
+        .revenue
+        .compute_snapshot()
+        .await
+        .map_err(|e| ApiError::Database(format!("Billing error: {}", e)))?;
+
+    Ok(Json(snapshot))
+}
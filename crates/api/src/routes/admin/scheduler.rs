@@ -0,0 +1,77 @@
+//! Platform scheduler visibility - job status and manual triggers
+//!
+//! Read side lists every registered job with its cron expression,
+//! last-run outcome and computed next run. The trigger endpoint runs a
+//! job out of schedule; it takes the same cross-instance lock as
+//! scheduled runs, so a manual trigger can never overlap one.
+
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Serialize;
+
+use super::shared::require_platform_admin;
+use crate::{
+    auth::AuthUser,
+    error::{ApiError, ApiResult},
+    scheduler::{JobStatus, TriggerOutcome},
+    state::AppState,
+};
+
+#[derive(Debug, Serialize)]
+pub struct SchedulerResponse {
+    pub jobs: Vec<JobStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TriggerResponse {
+    pub job_name: String,
+    pub status: String,
+}
+
+/// List registered scheduler jobs with last/next run status
+pub async fn get_scheduler(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<SchedulerResponse>> {
+    require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    let jobs = state.scheduler.status().await?;
+    Ok(Json(SchedulerResponse { jobs }))
+}
+
+/// Trigger a scheduler job out of schedule
+///
+/// Returns 202 once the job is claimed and started in the background;
+/// 409 if a run (scheduled or manual, on any instance) holds the lock.
+pub async fn trigger_scheduler_job(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(job_name): Path<String>,
+) -> ApiResult<(StatusCode, Json<TriggerResponse>)> {
+    let admin_user_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    match state.scheduler.trigger(&job_name).await? {
+        TriggerOutcome::Started => {
+            tracing::info!(
+                admin = %admin_user_id,
+                job = %job_name,
+                "Scheduler job manually triggered"
+            );
+            Ok((
+                StatusCode::ACCEPTED,
+                Json(TriggerResponse {
+                    job_name,
+                    status: "started".to_string(),
+                }),
+            ))
+        }
+        TriggerOutcome::AlreadyRunning => Err(ApiError::Validation(format!(
+            "Job '{}' is already running",
+            job_name
+        ))),
+        TriggerOutcome::UnknownJob => Err(ApiError::NotFound),
+    }
+}
@@ -3,8 +3,55 @@
 //! This module contains common types, helper functions, and utilities
 //! used across multiple admin sub-modules.
 
+use sqlx::FromRow;
 use uuid::Uuid;
 
+use crate::{
+    auth::AuthUser,
+    error::{ApiError, ApiResult},
+};
+
+/// Check if the authenticated user has platform admin privileges
+///
+/// Superadmins and admins always pass; staff pass only for read-only
+/// endpoints (`require_write = false`).
+pub async fn require_platform_admin(
+    pool: &sqlx::PgPool,
+    auth_user: &AuthUser,
+    require_write: bool,
+) -> ApiResult<Uuid> {
+    let user_id = auth_user.user_id.ok_or(ApiError::Unauthorized)?;
+
+    #[derive(FromRow)]
+    struct PlatformRoleRow {
+        platform_role: String,
+    }
+
+    let row: Option<PlatformRoleRow> = sqlx::query_as(
+        "SELECT platform_role::TEXT as platform_role FROM public.users WHERE id = $1",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let platform_role = row
+        .map(|r| r.platform_role)
+        .unwrap_or_else(|| "user".to_string());
+
+    match platform_role.as_str() {
+        "superadmin" | "admin" => Ok(user_id),
+        "staff" if !require_write => Ok(user_id), // Staff can read but not write
+        _ => {
+            tracing::warn!(
+                user_id = %user_id,
+                platform_role = %platform_role,
+                "Unauthorized admin access attempt"
+            );
+            Err(ApiError::Forbidden)
+        }
+    }
+}
+
 /// Log comprehensive database error details for debugging
 ///
 /// This helper provides detailed error logging for database operations,
@@ -0,0 +1,378 @@
+//! Admin CRUD for support ticket routing rules
+//!
+//! Rules live in `support_routing_rules` and are executed by
+//! [`crate::support_rules`] on ticket creation and customer replies.
+//! Conditions and actions are JSONB blocks validated here so the engine
+//! never sees a malformed rule; evaluation order is the `position`
+//! field, lowest first.
+
+use axum::{
+    extract::{Extension, Path, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::shared::require_platform_admin;
+use crate::{
+    audit_constants::{admin_action, event_type, severity, target_type},
+    auth::AuthUser,
+    error::{ApiError, ApiResult},
+    state::AppState,
+    support_rules,
+};
+
+/// Hard cap so a runaway integration cannot fill the rules table
+const MAX_RULES: i64 = 100;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct CreateRuleRequest {
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub position: i32,
+    /// ticket_created (default), customer_reply, or both
+    pub applies_to: Option<String>,
+    #[serde(default)]
+    pub conditions: serde_json::Value,
+    #[serde(default)]
+    pub actions: serde_json::Value,
+    #[serde(default)]
+    pub stop_processing: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Partial update: absent fields keep their current value
+#[derive(Debug, Deserialize)]
+pub struct UpdateRuleRequest {
+    pub name: Option<String>,
+    pub enabled: Option<bool>,
+    pub position: Option<i32>,
+    pub applies_to: Option<String>,
+    pub conditions: Option<serde_json::Value>,
+    pub actions: Option<serde_json::Value>,
+    pub stop_processing: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RuleResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub enabled: bool,
+    pub position: i32,
+    pub applies_to: String,
+    pub conditions: serde_json::Value,
+    pub actions: serde_json::Value,
+    pub stop_processing: bool,
+    pub match_count: i64,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_matched_at: Option<OffsetDateTime>,
+    pub created_by: Option<Uuid>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct RuleRow {
+    id: Uuid,
+    name: String,
+    enabled: bool,
+    position: i32,
+    applies_to: String,
+    conditions: serde_json::Value,
+    actions: serde_json::Value,
+    stop_processing: bool,
+    match_count: i64,
+    last_matched_at: Option<OffsetDateTime>,
+    created_by: Option<Uuid>,
+    created_at: OffsetDateTime,
+    updated_at: OffsetDateTime,
+}
+
+impl From<RuleRow> for RuleResponse {
+    fn from(r: RuleRow) -> Self {
+        RuleResponse {
+            id: r.id,
+            name: r.name,
+            enabled: r.enabled,
+            position: r.position,
+            applies_to: r.applies_to,
+            conditions: r.conditions,
+            actions: r.actions,
+            stop_processing: r.stop_processing,
+            match_count: r.match_count,
+            last_matched_at: r.last_matched_at,
+            created_by: r.created_by,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+        }
+    }
+}
+
+const RULE_COLUMNS: &str = "id, name, enabled, position, applies_to, conditions, actions, \
+                            stop_processing, match_count, last_matched_at, created_by, \
+                            created_at, updated_at";
+
+// =============================================================================
+// Validation
+// =============================================================================
+
+fn validate_name(name: &str) -> ApiResult<()> {
+    if name.trim().is_empty() || name.len() > 200 {
+        return Err(ApiError::Validation(
+            "Rule name must be 1-200 characters".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_applies_to(applies_to: &str) -> ApiResult<()> {
+    if !["ticket_created", "customer_reply", "both"].contains(&applies_to) {
+        return Err(ApiError::Validation(
+            "applies_to must be ticket_created, customer_reply, or both".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Validate rule blocks and check any assignee exists
+async fn validate_rule_blocks(
+    state: &AppState,
+    conditions: &serde_json::Value,
+    actions: &serde_json::Value,
+) -> ApiResult<()> {
+    support_rules::validate_rule(conditions, actions).map_err(ApiError::Validation)?;
+
+    // assign_to must reference a real user or every match fails silently
+    if let Some(assignee) = actions.get("assign_to").and_then(|a| a.as_str()) {
+        let assignee: Uuid = assignee
+            .parse()
+            .map_err(|_| ApiError::Validation("assign_to must be a user id".to_string()))?;
+        let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)")
+            .bind(assignee)
+            .fetch_one(&state.pool)
+            .await?;
+        if !exists {
+            return Err(ApiError::Validation(
+                "assign_to references a user that does not exist".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// List all routing rules in evaluation order
+pub async fn list_rules(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<Vec<RuleResponse>>> {
+    require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    let rules: Vec<RuleRow> = sqlx::query_as(&format!(
+        "SELECT {} FROM support_routing_rules ORDER BY position, created_at",
+        RULE_COLUMNS
+    ))
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(rules.into_iter().map(Into::into).collect()))
+}
+
+/// Create a routing rule
+pub async fn create_rule(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<CreateRuleRequest>,
+) -> ApiResult<Json<RuleResponse>> {
+    let admin_user_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    validate_name(&req.name)?;
+    let applies_to = req.applies_to.as_deref().unwrap_or("ticket_created");
+    validate_applies_to(applies_to)?;
+    validate_rule_blocks(&state, &req.conditions, &req.actions).await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM support_routing_rules")
+        .fetch_one(&state.pool)
+        .await?;
+    if count >= MAX_RULES {
+        return Err(ApiError::Validation(format!(
+            "At most {} routing rules are supported",
+            MAX_RULES
+        )));
+    }
+
+    let rule: RuleRow = sqlx::query_as(&format!(
+        r#"
+        INSERT INTO support_routing_rules
+            (name, enabled, position, applies_to, conditions, actions, stop_processing, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING {}
+        "#,
+        RULE_COLUMNS
+    ))
+    .bind(req.name.trim())
+    .bind(req.enabled)
+    .bind(req.position)
+    .bind(applies_to)
+    .bind(&req.conditions)
+    .bind(&req.actions)
+    .bind(req.stop_processing)
+    .bind(admin_user_id)
+    .fetch_one(&state.pool)
+    .await?;
+
+    record_rule_audit(
+        &state,
+        admin_user_id,
+        admin_action::SUPPORT_RULE_CREATED,
+        rule.id,
+        serde_json::json!({
+            "name": rule.name,
+            "enabled": rule.enabled,
+            "applies_to": rule.applies_to,
+        }),
+    )
+    .await;
+
+    tracing::info!(rule_id = %rule.id, name = %rule.name, "Support routing rule created");
+    Ok(Json(rule.into()))
+}
+
+/// Update a routing rule (partial)
+pub async fn update_rule(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(rule_id): Path<Uuid>,
+    Json(req): Json<UpdateRuleRequest>,
+) -> ApiResult<Json<RuleResponse>> {
+    let admin_user_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    let current: RuleRow = sqlx::query_as(&format!(
+        "SELECT {} FROM support_routing_rules WHERE id = $1",
+        RULE_COLUMNS
+    ))
+    .bind(rule_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    let name = req.name.unwrap_or(current.name);
+    validate_name(&name)?;
+    let applies_to = req.applies_to.unwrap_or(current.applies_to);
+    validate_applies_to(&applies_to)?;
+    let conditions = req.conditions.unwrap_or(current.conditions);
+    let actions = req.actions.unwrap_or(current.actions);
+    validate_rule_blocks(&state, &conditions, &actions).await?;
+
+    let rule: RuleRow = sqlx::query_as(&format!(
+        r#"
+        UPDATE support_routing_rules
+        SET name = $2, enabled = $3, position = $4, applies_to = $5,
+            conditions = $6, actions = $7, stop_processing = $8, updated_at = NOW()
+        WHERE id = $1
+        RETURNING {}
+        "#,
+        RULE_COLUMNS
+    ))
+    .bind(rule_id)
+    .bind(name.trim())
+    .bind(req.enabled.unwrap_or(current.enabled))
+    .bind(req.position.unwrap_or(current.position))
+    .bind(&applies_to)
+    .bind(&conditions)
+    .bind(&actions)
+    .bind(req.stop_processing.unwrap_or(current.stop_processing))
+    .fetch_one(&state.pool)
+    .await?;
+
+    record_rule_audit(
+        &state,
+        admin_user_id,
+        admin_action::SUPPORT_RULE_UPDATED,
+        rule.id,
+        serde_json::json!({
+            "name": rule.name,
+            "enabled": rule.enabled,
+            "applies_to": rule.applies_to,
+        }),
+    )
+    .await;
+
+    tracing::info!(rule_id = %rule.id, "Support routing rule updated");
+    Ok(Json(rule.into()))
+}
+
+/// Delete a routing rule
+pub async fn delete_rule(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(rule_id): Path<Uuid>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let admin_user_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    let deleted: Option<(String,)> =
+        sqlx::query_as("DELETE FROM support_routing_rules WHERE id = $1 RETURNING name")
+            .bind(rule_id)
+            .fetch_optional(&state.pool)
+            .await?;
+    let Some((name,)) = deleted else {
+        return Err(ApiError::NotFound);
+    };
+
+    record_rule_audit(
+        &state,
+        admin_user_id,
+        admin_action::SUPPORT_RULE_DELETED,
+        rule_id,
+        serde_json::json!({ "name": name }),
+    )
+    .await;
+
+    tracing::info!(rule_id = %rule_id, name = %name, "Support routing rule deleted");
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+/// Record a rule change in the admin audit log; failures are logged,
+/// never surfaced
+async fn record_rule_audit(
+    state: &AppState,
+    admin_user_id: Uuid,
+    action: &str,
+    rule_id: Uuid,
+    details: serde_json::Value,
+) {
+    if let Err(e) = sqlx::query(
+        r#"
+        INSERT INTO admin_audit_log
+            (admin_user_id, action, target_type, target_id, details, event_type, severity)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(admin_user_id)
+    .bind(action)
+    .bind(target_type::SUPPORT_RULE)
+    .bind(rule_id)
+    .bind(&details)
+    .bind(event_type::CONFIGURATION)
+    .bind(severity::INFO)
+    .execute(&state.pool)
+    .await
+    {
+        tracing::warn!("Failed to log support rule audit: {}", e);
+    }
+}
@@ -0,0 +1,108 @@
+//! Platform system controls - read-only (maintenance) mode toggle and
+//! auth cache metrics
+//!
+//! Lets platform admins flip this instance into read-only mode during
+//! database failovers: reads keep working, mutations get a 503 (see
+//! `crate::read_only`). The toggle is per-instance; deployments with
+//! multiple API instances set the `READ_ONLY_MODE` env flag instead.
+//! The auth cache endpoint reports this instance's token verification
+//! cache counters (hits, misses, LRU evictions, targeted invalidations).
+
+use axum::{
+    extract::{Extension, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+use super::shared::require_platform_admin;
+use crate::{auth::AuthUser, error::ApiResult, state::AppState};
+
+#[derive(Debug, Deserialize)]
+pub struct PutReadOnlyRequest {
+    pub enabled: bool,
+    /// Whether the MCP proxy keeps serving while read-only (default true)
+    pub allow_proxy: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadOnlyResponse {
+    pub enabled: bool,
+    pub allow_proxy: bool,
+}
+
+/// Get this instance's read-only mode state
+pub async fn get_read_only(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<ReadOnlyResponse>> {
+    require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    Ok(Json(ReadOnlyResponse {
+        enabled: state.read_only.is_enabled(),
+        allow_proxy: state.read_only.proxy_allowed(),
+    }))
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuthCacheMetricsResponse {
+    /// Tokens currently cached on this instance
+    pub entries: usize,
+    /// Maximum entries before LRU eviction kicks in
+    pub capacity: usize,
+    pub hits: u64,
+    pub misses: u64,
+    /// Fraction of lookups served from cache; None before any lookup
+    pub hit_rate: Option<f64>,
+    /// Entries dropped to stay under capacity
+    pub evictions: u64,
+    /// Entries dropped by logout/suspension/role-change events
+    pub invalidations: u64,
+}
+
+/// Get this instance's token cache metrics
+pub async fn get_auth_cache_metrics(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<AuthCacheMetricsResponse>> {
+    require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    let stats = state.token_cache.stats().await;
+    let lookups = stats.hits + stats.misses;
+    let hit_rate = (lookups > 0).then(|| stats.hits as f64 / lookups as f64);
+
+    Ok(Json(AuthCacheMetricsResponse {
+        entries: stats.entries,
+        capacity: stats.capacity,
+        hits: stats.hits,
+        misses: stats.misses,
+        hit_rate,
+        evictions: stats.evictions,
+        invalidations: stats.invalidations,
+    }))
+}
+
+/// Toggle read-only mode on this instance
+pub async fn put_read_only(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<PutReadOnlyRequest>,
+) -> ApiResult<Json<ReadOnlyResponse>> {
+    let admin_user_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    state.read_only.set_enabled(req.enabled);
+    state
+        .read_only
+        .set_allow_proxy(req.allow_proxy.unwrap_or(true));
+
+    tracing::warn!(
+        admin = %admin_user_id,
+        enabled = req.enabled,
+        allow_proxy = state.read_only.proxy_allowed(),
+        "Platform read-only mode toggled"
+    );
+
+    Ok(Json(ReadOnlyResponse {
+        enabled: state.read_only.is_enabled(),
+        allow_proxy: state.read_only.proxy_allowed(),
+    }))
+}
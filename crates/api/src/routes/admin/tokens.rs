@@ -0,0 +1,343 @@
+//! Admin API token management - superadmin only
+//!
+//! Scoped tokens for internal automation against admin routes, so platform
+//! engineers don't have to script with personal JWTs. Tokens are issued by
+//! superadmins, restricted to granted scopes (e.g. support:read), expire,
+//! can be revoked, and every request made with one is recorded in
+//! admin_token_usage_log (see the admin token branch in auth middleware).
+
+use axum::{
+    extract::{Extension, Path, Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    auth::{AuthMethod, AuthUser},
+    error::{ApiError, ApiResult},
+    state::AppState,
+};
+
+/// Scope areas that can be granted, matching /api/v1/admin/<area> prefixes.
+/// "tokens" is deliberately absent: tokens can never manage tokens.
+const ADMIN_TOKEN_SCOPE_AREAS: &[&str] = &[
+    "analytics",
+    "billing",
+    "mcp",
+    "organizations",
+    "orgs",
+    "stats",
+    "support",
+    "users",
+];
+
+/// Default and maximum token lifetime in days
+const DEFAULT_EXPIRY_DAYS: i64 = 30;
+const MAX_EXPIRY_DAYS: i64 = 90;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct CreateAdminTokenRequest {
+    /// Human-readable purpose, e.g. "support dashboard sync"
+    pub name: String,
+    /// Granted scopes as "<area>:<read|write>", e.g. ["support:read"]
+    pub scopes: Vec<String>,
+    /// Token lifetime in days (default 30, max 90)
+    pub expires_in_days: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminTokenResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub token_prefix: String,
+    pub scopes: Vec<String>,
+    pub created_by: Uuid,
+    #[serde(with = "time::serde::rfc3339")]
+    pub expires_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_used_at: Option<OffsetDateTime>,
+    pub request_count: i64,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub revoked_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreatedAdminTokenResponse {
+    /// The full token - shown once, never retrievable again
+    pub token: String,
+    #[serde(flatten)]
+    pub details: AdminTokenResponse,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TokenUsageQuery {
+    /// Maximum number of entries to return (default 100, max 500)
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct TokenUsageEntry {
+    pub method: String,
+    pub path: String,
+    pub outcome: String,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, FromRow)]
+struct AdminTokenRow {
+    id: Uuid,
+    name: String,
+    token_prefix: String,
+    scopes: Vec<String>,
+    created_by: Uuid,
+    expires_at: OffsetDateTime,
+    last_used_at: Option<OffsetDateTime>,
+    request_count: i64,
+    revoked_at: Option<OffsetDateTime>,
+    created_at: OffsetDateTime,
+}
+
+impl From<AdminTokenRow> for AdminTokenResponse {
+    fn from(row: AdminTokenRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            token_prefix: row.token_prefix,
+            scopes: row.scopes,
+            created_by: row.created_by,
+            expires_at: row.expires_at,
+            last_used_at: row.last_used_at,
+            request_count: row.request_count,
+            revoked_at: row.revoked_at,
+            created_at: row.created_at,
+        }
+    }
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Check that the caller is a superadmin authenticated with a personal
+/// session - tokens are issued and revoked by humans, never by other tokens
+async fn require_superadmin(pool: &sqlx::PgPool, auth_user: &AuthUser) -> ApiResult<Uuid> {
+    if matches!(auth_user.auth_method, AuthMethod::AdminToken { .. }) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let user_id = auth_user.user_id.ok_or(ApiError::Unauthorized)?;
+
+    #[derive(FromRow)]
+    struct PlatformRoleRow {
+        platform_role: String,
+    }
+
+    let row: Option<PlatformRoleRow> = sqlx::query_as(
+        "SELECT platform_role::TEXT as platform_role FROM public.users WHERE id = $1",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    match row.map(|r| r.platform_role).as_deref() {
+        Some("superadmin") => Ok(user_id),
+        _ => {
+            tracing::warn!(
+                user_id = %user_id,
+                "Non-superadmin attempted admin token management"
+            );
+            Err(ApiError::Forbidden)
+        }
+    }
+}
+
+/// Validate a requested scope as "<area>:<read|write>" with a known area
+fn validate_scope(scope: &str) -> Result<(), ApiError> {
+    let (area, action) = scope
+        .split_once(':')
+        .ok_or_else(|| ApiError::Validation(format!("Invalid scope format: {}", scope)))?;
+
+    if !ADMIN_TOKEN_SCOPE_AREAS.contains(&area) {
+        return Err(ApiError::Validation(format!(
+            "Unknown scope area: {}",
+            area
+        )));
+    }
+    if !matches!(action, "read" | "write") {
+        return Err(ApiError::Validation(format!(
+            "Scope action must be read or write: {}",
+            scope
+        )));
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// GET /admin/tokens - list all admin tokens (superadmin only)
+pub async fn list_admin_tokens(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<Vec<AdminTokenResponse>>> {
+    require_superadmin(&state.pool, &auth_user).await?;
+
+    let rows: Vec<AdminTokenRow> = sqlx::query_as(
+        r#"
+        SELECT id, name, token_prefix, scopes, created_by, expires_at,
+               last_used_at, request_count, revoked_at, created_at
+        FROM admin_api_tokens
+        ORDER BY created_at DESC
+        "#,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(rows.into_iter().map(Into::into).collect()))
+}
+
+/// POST /admin/tokens - issue a new admin token (superadmin only)
+///
+/// The full token is returned once in the response and never retrievable
+/// again; only its hash is stored.
+pub async fn create_admin_token(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<CreateAdminTokenRequest>,
+) -> ApiResult<Json<CreatedAdminTokenResponse>> {
+    let user_id = require_superadmin(&state.pool, &auth_user).await?;
+
+    let name = payload.name.trim();
+    if name.is_empty() || name.len() > 100 {
+        return Err(ApiError::Validation(
+            "Token name must be 1-100 characters".to_string(),
+        ));
+    }
+
+    if payload.scopes.is_empty() {
+        return Err(ApiError::Validation(
+            "At least one scope is required".to_string(),
+        ));
+    }
+    for scope in &payload.scopes {
+        validate_scope(scope)?;
+    }
+
+    let expires_in_days = payload.expires_in_days.unwrap_or(DEFAULT_EXPIRY_DAYS);
+    if !(1..=MAX_EXPIRY_DAYS).contains(&expires_in_days) {
+        return Err(ApiError::Validation(format!(
+            "expires_in_days must be between 1 and {}",
+            MAX_EXPIRY_DAYS
+        )));
+    }
+
+    let (full_token, token_hash, token_prefix) =
+        state.api_key_manager.generate_admin_token().map_err(|e| {
+            tracing::error!(error = ?e, "Failed to generate admin token");
+            ApiError::Internal
+        })?;
+
+    let row: AdminTokenRow = sqlx::query_as(
+        r#"
+        INSERT INTO admin_api_tokens (name, token_hash, token_prefix, scopes, created_by, expires_at)
+        VALUES ($1, $2, $3, $4, $5, NOW() + ($6 || ' days')::INTERVAL)
+        RETURNING id, name, token_prefix, scopes, created_by, expires_at,
+                  last_used_at, request_count, revoked_at, created_at
+        "#,
+    )
+    .bind(name)
+    .bind(&token_hash)
+    .bind(&token_prefix)
+    .bind(&payload.scopes)
+    .bind(user_id)
+    .bind(expires_in_days.to_string())
+    .fetch_one(&state.pool)
+    .await?;
+
+    tracing::info!(
+        token_id = %row.id,
+        created_by = %user_id,
+        scopes = ?payload.scopes,
+        expires_in_days = %expires_in_days,
+        "Admin API token issued"
+    );
+
+    Ok(Json(CreatedAdminTokenResponse {
+        token: full_token,
+        details: row.into(),
+    }))
+}
+
+/// DELETE /admin/tokens/:token_id - revoke a token (superadmin only)
+pub async fn revoke_admin_token(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(token_id): Path<Uuid>,
+) -> ApiResult<Json<AdminTokenResponse>> {
+    let user_id = require_superadmin(&state.pool, &auth_user).await?;
+
+    let row: Option<AdminTokenRow> = sqlx::query_as(
+        r#"
+        UPDATE admin_api_tokens
+        SET revoked_at = NOW(), revoked_by = $2
+        WHERE id = $1 AND revoked_at IS NULL
+        RETURNING id, name, token_prefix, scopes, created_by, expires_at,
+                  last_used_at, request_count, revoked_at, created_at
+        "#,
+    )
+    .bind(token_id)
+    .bind(user_id)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    let row = row.ok_or(ApiError::NotFound)?;
+
+    tracing::info!(
+        token_id = %token_id,
+        revoked_by = %user_id,
+        "Admin API token revoked"
+    );
+
+    Ok(Json(row.into()))
+}
+
+/// GET /admin/tokens/:token_id/usage - audit trail for a token (superadmin only)
+pub async fn get_admin_token_usage(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(token_id): Path<Uuid>,
+    Query(query): Query<TokenUsageQuery>,
+) -> ApiResult<Json<Vec<TokenUsageEntry>>> {
+    require_superadmin(&state.pool, &auth_user).await?;
+
+    let limit = query.limit.unwrap_or(100).clamp(1, 500);
+
+    let entries: Vec<TokenUsageEntry> = sqlx::query_as(
+        r#"
+        SELECT method, path, outcome, ip_address, user_agent, created_at
+        FROM admin_token_usage_log
+        WHERE token_id = $1
+        ORDER BY created_at DESC
+        LIMIT $2
+        "#,
+    )
+    .bind(token_id)
+    .bind(limit)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(entries))
+}
@@ -5,7 +5,7 @@
 
 use axum::{
     extract::{Extension, Path, Query, State},
-    http::HeaderMap,
+    http::{HeaderMap, StatusCode},
     Json,
 };
 use serde::{Deserialize, Serialize};
@@ -42,6 +42,7 @@ struct AdminTierChangeResult {
 // =============================================================================
 
 /// Log comprehensive database error details for debugging
+#[cfg(feature = "billing")]
 fn log_db_err(req_id: Uuid, step: &'static str, e: &sqlx::Error) {
     if let Some(db) = e.as_database_error() {
         tracing::error!(
@@ -443,7 +444,7 @@ fn get_tier_limit(tier: &str) -> i64 {
 
 /// Enhanced audit logging with SOC 2 compliance and error propagation
 #[allow(clippy::too_many_arguments)]
-async fn log_admin_action(
+pub(crate) async fn log_admin_action(
     pool: &sqlx::PgPool,
     admin_user_id: Uuid,
     action: &str,
@@ -518,7 +519,7 @@ fn sanitize_pii(mut details: serde_json::Value) -> serde_json::Value {
 }
 
 /// Extract IP, user agent, and session ID from request (CRITICAL issue fix)
-fn extract_audit_context(
+pub(crate) fn extract_audit_context(
     headers: &HeaderMap,
     auth_user: &AuthUser,
 ) -> (Option<String>, Option<String>, Option<Uuid>) {
@@ -1056,6 +1057,7 @@ pub async fn update_user(
         }
 
         // Parse and validate subscription_start_date
+        #[cfg_attr(not(feature = "billing"), allow(unused_variables))]
         let start_date = if let Some(ref date_str) = req.subscription_start_date {
             let parsed = time::OffsetDateTime::parse(
                 date_str,
@@ -1193,6 +1195,13 @@ pub async fn update_user(
         .execute(&state.pool)
         .await?;
 
+        // Cached token verifications predate the role change; drop them
+        // so the new role applies on the next request
+        state
+            .token_cache
+            .invalidate_user(&user_id.to_string(), Some(&current_user.email))
+            .await;
+
         log_admin_action(
             &state.pool,
             admin_user_id,
@@ -1532,6 +1541,17 @@ pub async fn force_password_reset(
     }))
 }
 
+/// Request for 2FA disable: justification is mandatory, and the action
+/// can optionally be queued for a second admin's sign-off
+#[derive(Debug, Deserialize)]
+pub struct Disable2FARequest {
+    /// Mandatory justification recorded in the audit log
+    pub reason: String,
+    /// Queue for second-admin approval instead of executing immediately
+    #[serde(default)]
+    pub require_approval: bool,
+}
+
 /// Response for 2FA disable
 #[derive(Debug, Serialize)]
 pub struct Disable2FAResponse {
@@ -1541,18 +1561,66 @@ pub struct Disable2FAResponse {
     pub message: String,
 }
 
+/// Disable a user's 2FA: clear the secret, backup codes, and trusted
+/// devices. Shared between the direct admin route and approval execution.
+///
+/// Returns (backup_codes_deleted, trusted_devices_deleted).
+pub(crate) async fn execute_disable_user_2fa(
+    pool: &sqlx::PgPool,
+    user_id: Uuid,
+) -> ApiResult<(i64, i64)> {
+    sqlx::query(
+        r#"
+        UPDATE user_2fa
+        SET is_enabled = FALSE, secret_key = NULL, updated_at = NOW()
+        WHERE user_id = $1
+        "#,
+    )
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+
+    let backup_result = sqlx::query("DELETE FROM user_2fa_backup_codes WHERE user_id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    let devices_result = sqlx::query("DELETE FROM user_trusted_devices WHERE user_id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    Ok((
+        backup_result.rows_affected() as i64,
+        devices_result.rows_affected() as i64,
+    ))
+}
+
 /// Disable 2FA for a user (admin override)
+///
+/// Requires a reason; with `require_approval` the action is queued for a
+/// second admin instead of executing immediately (202 Accepted).
 pub async fn disable_user_2fa(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
     headers: HeaderMap,
     Path(user_id): Path<Uuid>,
-) -> ApiResult<Json<Disable2FAResponse>> {
+    Json(req): Json<Disable2FARequest>,
+) -> ApiResult<axum::response::Response> {
+    use axum::response::IntoResponse;
+
     require_platform_admin(&state, &auth_user, true).await?;
 
     let admin_user_id = auth_user.user_id.ok_or(ApiError::Unauthorized)?;
     let (ip_address, user_agent, session_id) = extract_audit_context(&headers, &auth_user);
 
+    let reason = req.reason.trim();
+    if reason.is_empty() {
+        return Err(ApiError::Validation(
+            "A reason is required to disable a user's 2FA".to_string(),
+        ));
+    }
+
     // Verify user exists
     let user_exists: Option<(bool,)> =
         sqlx::query_as("SELECT TRUE FROM public.users WHERE id = $1")
@@ -1564,33 +1632,48 @@ pub async fn disable_user_2fa(
         return Err(ApiError::NotFound);
     }
 
-    // Disable 2FA
-    sqlx::query(
-        r#"
-        UPDATE user_2fa
-        SET is_enabled = FALSE, secret_key = NULL, updated_at = NOW()
-        WHERE user_id = $1
-        "#,
-    )
-    .bind(user_id)
-    .execute(&state.pool)
-    .await?;
-
-    // Delete backup codes
-    let backup_result = sqlx::query("DELETE FROM user_2fa_backup_codes WHERE user_id = $1")
-        .bind(user_id)
-        .execute(&state.pool)
+    if req.require_approval {
+        let approval_id = super::admin::approvals::create_approval(
+            &state.pool,
+            super::admin::approvals::ACTION_DISABLE_USER_2FA,
+            target_type::USER,
+            &user_id.to_string(),
+            reason,
+            serde_json::json!({}),
+            admin_user_id,
+        )
         .await?;
 
-    let backup_codes_deleted = backup_result.rows_affected() as i64;
-
-    // Delete trusted devices
-    let devices_result = sqlx::query("DELETE FROM user_trusted_devices WHERE user_id = $1")
-        .bind(user_id)
-        .execute(&state.pool)
+        log_admin_action(
+            &state.pool,
+            admin_user_id,
+            admin_action::SECURITY_APPROVAL_REQUESTED,
+            target_type::USER,
+            Some(user_id),
+            Some(serde_json::json!({
+                "approval_id": approval_id,
+                "action": super::admin::approvals::ACTION_DISABLE_USER_2FA,
+                "reason": reason,
+            })),
+            event_type::SECURITY_SETTING,
+            severity::WARNING,
+            ip_address,
+            user_agent,
+            session_id,
+        )
         .await?;
 
-    let trusted_devices_deleted = devices_result.rows_affected() as i64;
+        return Ok((
+            StatusCode::ACCEPTED,
+            Json(super::admin::approvals::ApprovalPendingResponse::new(
+                approval_id,
+            )),
+        )
+            .into_response());
+    }
+
+    let (backup_codes_deleted, trusted_devices_deleted) =
+        execute_disable_user_2fa(&state.pool, user_id).await?;
 
     log_admin_action(
         &state.pool,
@@ -1599,6 +1682,7 @@ pub async fn disable_user_2fa(
         target_type::USER,
         Some(user_id),
         Some(serde_json::json!({
+            "reason": reason,
             "backup_codes_deleted": backup_codes_deleted,
             "trusted_devices_deleted": trusted_devices_deleted
         })),
@@ -1615,7 +1699,8 @@ pub async fn disable_user_2fa(
         backup_codes_deleted,
         trusted_devices_deleted,
         message: "Two-factor authentication disabled".to_string(),
-    }))
+    })
+    .into_response())
 }
 
 /// Request for suspending a user
@@ -1706,6 +1791,17 @@ pub async fn suspend_user(
 
     let sessions_revoked = result.rows_affected() as i64;
 
+    // Suspension must take effect immediately: drop any cached token
+    // verifications for this user so in-flight tokens stop resolving
+    let target_email: Option<String> = sqlx::query_scalar("SELECT email FROM users WHERE id = $1")
+        .bind(user_id)
+        .fetch_optional(&state.pool)
+        .await?;
+    state
+        .token_cache
+        .invalidate_user(&user_id.to_string(), target_email.as_deref())
+        .await;
+
     log_admin_action(
         &state.pool,
         admin_user_id,
@@ -2982,6 +3078,7 @@ struct OrgBasicInfo {
     stripe_customer_id: Option<String>,
 }
 
+#[cfg(feature = "billing")]
 #[derive(Debug, FromRow)]
 struct SubscriptionRow {
     stripe_subscription_id: String,
@@ -2993,6 +3090,7 @@ struct SubscriptionRow {
     cancel_at_period_end: bool,
 }
 
+#[cfg(feature = "billing")]
 #[derive(Debug, FromRow)]
 struct SpendCapRow {
     cap_amount_cents: i64,
@@ -3001,6 +3099,7 @@ struct SpendCapRow {
     paused_at: Option<OffsetDateTime>,
 }
 
+#[cfg(feature = "billing")]
 #[derive(Debug, FromRow)]
 struct BillingEventRow {
     id: Uuid,
@@ -116,6 +116,26 @@ pub struct CollectRequest {
     pub time_on_page: Option<i32>,
     /// Scroll depth on previous page (0-100)
     pub scroll_depth: Option<i32>,
+    /// Batched scroll-depth/click-position events for heatmaps
+    /// (recorded only when interaction collection is enabled and the
+    /// session falls inside the configured sample)
+    pub interactions: Option<Vec<InteractionEventInput>>,
+}
+
+/// Maximum interaction events accepted per collect request
+const MAX_INTERACTIONS_PER_REQUEST: usize = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct InteractionEventInput {
+    /// "scroll_depth" or "click"
+    pub kind: String,
+    /// Scroll depth as a percentage of page height (scroll_depth events)
+    pub scroll_depth_pct: Option<i16>,
+    /// Click position as a percentage of page width/height (click events)
+    pub click_x_pct: Option<i16>,
+    pub click_y_pct: Option<i16>,
+    pub viewport_width: Option<i32>,
+    pub viewport_height: Option<i32>,
 }
 
 #[derive(Debug, Serialize)]
@@ -301,6 +321,22 @@ fn extract_domain(url: &str) -> Option<String> {
         .and_then(|u| u.host_str().map(|s| s.to_string()))
 }
 
+/// Deterministic per-session sampling for high-volume interaction events
+///
+/// Hashes the session id so a session is either fully sampled or fully
+/// excluded; sampling individual events would skew scroll distributions.
+fn session_sampled(session_id: Uuid, sample_rate: f64) -> bool {
+    if sample_rate >= 1.0 {
+        return true;
+    }
+    if sample_rate <= 0.0 {
+        return false;
+    }
+    let bytes = session_id.as_bytes();
+    let value = u64::from_be_bytes(bytes[..8].try_into().unwrap_or([0u8; 8]));
+    (value as f64 / u64::MAX as f64) < sample_rate
+}
+
 /// Parse date range from query parameters with 30-day default
 /// Returns (start, end, period_label)
 fn parse_date_range(query: &AnalyticsQuery) -> (OffsetDateTime, OffsetDateTime, String) {
@@ -568,16 +604,23 @@ pub async fn collect(
         .to_string();
 
     // Check analytics settings
-    let settings: Option<(bool, bool, bool, bool, bool)> = sqlx::query_as(
-        "SELECT respect_dnt, filter_bots, anonymize_ip, bot_detection_enabled, exclude_admin_visits FROM analytics_settings LIMIT 1"
+    let settings: Option<(bool, bool, bool, bool, bool, bool, f64)> = sqlx::query_as(
+        "SELECT respect_dnt, filter_bots, anonymize_ip, bot_detection_enabled, exclude_admin_visits, interaction_events_enabled, interaction_sample_rate FROM analytics_settings LIMIT 1"
     )
     .fetch_optional(&state.pool)
     .await
     .ok()
     .flatten();
 
-    let (respect_dnt, filter_bots, _anonymize_ip, bot_detection_enabled, exclude_admin_visits) =
-        settings.unwrap_or((true, true, true, true, true));
+    let (
+        respect_dnt,
+        filter_bots,
+        _anonymize_ip,
+        bot_detection_enabled,
+        exclude_admin_visits,
+        interaction_events_enabled,
+        interaction_sample_rate,
+    ) = settings.unwrap_or((true, true, true, true, true, false, 0.1));
 
     // Check Do Not Track header
     if respect_dnt {
@@ -801,6 +844,47 @@ pub async fn collect(
         .ok();
     }
 
+    // Record sampled heatmap interactions (scroll depth / click positions)
+    if interaction_events_enabled {
+        if let Some(interactions) = &req.interactions {
+            if session_sampled(final_session_id, interaction_sample_rate) {
+                record_interactions(
+                    &state.pool,
+                    final_session_id,
+                    visitor.id,
+                    &url_path,
+                    interactions,
+                )
+                .await;
+            }
+        }
+    }
+
+    // Evaluate goal conditions against this hit and record conversions
+    // (deduped per goal+session by the partial unique index)
+    let goal_ctx = crate::goals::GoalEventContext {
+        path: &url_path,
+        event_name: req.event_name.as_deref(),
+        event_data: req.event_data.as_ref(),
+        referrer: req.referrer.as_deref(),
+        device_type: &device_type,
+    };
+    for goal_id in state.goal_engine.matching_goals(&goal_ctx).await {
+        sqlx::query(
+            r#"
+            INSERT INTO analytics_conversions (goal_id, session_id, visitor_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (goal_id, session_id) WHERE session_id IS NOT NULL DO NOTHING
+            "#,
+        )
+        .bind(goal_id)
+        .bind(final_session_id)
+        .bind(visitor.id)
+        .execute(&state.pool)
+        .await
+        .ok();
+    }
+
     // Update realtime table
     sqlx::query(
         r#"
@@ -826,6 +910,82 @@ pub async fn collect(
     }))
 }
 
+/// Validate and batch-insert interaction events for a sampled session
+///
+/// Invalid entries (unknown kind, out-of-range percentages) are dropped
+/// rather than failing the whole collect request.
+async fn record_interactions(
+    pool: &sqlx::PgPool,
+    session_id: Uuid,
+    visitor_id: Uuid,
+    url_path: &str,
+    interactions: &[InteractionEventInput],
+) {
+    let mut kinds: Vec<String> = Vec::new();
+    let mut scroll_depths: Vec<Option<i16>> = Vec::new();
+    let mut xs: Vec<Option<i16>> = Vec::new();
+    let mut ys: Vec<Option<i16>> = Vec::new();
+    let mut widths: Vec<Option<i32>> = Vec::new();
+    let mut heights: Vec<Option<i32>> = Vec::new();
+
+    for event in interactions.iter().take(MAX_INTERACTIONS_PER_REQUEST) {
+        match event.kind.as_str() {
+            "scroll_depth" => {
+                let Some(pct) = event.scroll_depth_pct.filter(|p| (0..=100).contains(p)) else {
+                    continue;
+                };
+                kinds.push(event.kind.clone());
+                scroll_depths.push(Some(pct));
+                xs.push(None);
+                ys.push(None);
+            }
+            "click" => {
+                let (Some(x), Some(y)) = (
+                    event.click_x_pct.filter(|p| (0..=100).contains(p)),
+                    event.click_y_pct.filter(|p| (0..=100).contains(p)),
+                ) else {
+                    continue;
+                };
+                kinds.push(event.kind.clone());
+                scroll_depths.push(None);
+                xs.push(Some(x));
+                ys.push(Some(y));
+            }
+            _ => continue,
+        }
+        widths.push(event.viewport_width);
+        heights.push(event.viewport_height);
+    }
+
+    if kinds.is_empty() {
+        return;
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO analytics_interaction_events
+            (session_id, visitor_id, url_path, event_kind,
+             scroll_depth_pct, click_x_pct, click_y_pct,
+             viewport_width, viewport_height)
+        SELECT $1, $2, $3, t.kind, t.scroll, t.x, t.y, t.vw, t.vh
+        FROM UNNEST($4::text[], $5::smallint[], $6::smallint[], $7::smallint[], $8::int[], $9::int[])
+            AS t(kind, scroll, x, y, vw, vh)
+        "#,
+    )
+    .bind(session_id)
+    .bind(visitor_id)
+    .bind(url_path)
+    .bind(&kinds)
+    .bind(&scroll_depths)
+    .bind(&xs)
+    .bind(&ys)
+    .bind(&widths)
+    .bind(&heights)
+    .execute(pool)
+    .await
+    .ok();
+}
+
 // =============================================================================
 // Admin Analytics Endpoints
 // =============================================================================
@@ -1673,6 +1833,175 @@ pub async fn get_event_details(
     }))
 }
 
+// =============================================================================
+// Heatmap Aggregation Endpoints
+// =============================================================================
+
+/// Width of a click density bucket, as a percentage of page width/height
+const CLICK_BUCKET_SIZE_PCT: i32 = 5;
+
+#[derive(Debug, Deserialize)]
+pub struct HeatmapQuery {
+    /// Page path to aggregate, e.g. "/pricing"
+    pub path: String,
+    pub start: Option<String>,
+    pub end: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct ScrollDepthBucket {
+    /// Inclusive lower bound of the decile, e.g. 30 for 30-39%
+    pub depth_from: i32,
+    pub events: i64,
+    /// Share of scroll events that reached at least this depth
+    pub reached_pct: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScrollDistributionResponse {
+    pub path: String,
+    pub period: String,
+    pub total_events: i64,
+    pub buckets: Vec<ScrollDepthBucket>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct ClickDensityBucket {
+    /// Bucket index along page width (bucket covers x_bucket*5% .. +5%)
+    pub x_bucket: i32,
+    /// Bucket index along page height
+    pub y_bucket: i32,
+    pub clicks: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClickDensityResponse {
+    pub path: String,
+    pub period: String,
+    pub bucket_size_pct: i32,
+    pub total_clicks: i64,
+    pub buckets: Vec<ClickDensityBucket>,
+}
+
+/// Get per-page scroll depth distribution (decile buckets)
+pub async fn get_scroll_distribution(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<HeatmapQuery>,
+) -> ApiResult<Json<ScrollDistributionResponse>> {
+    require_admin(&state.pool, &auth_user).await?;
+
+    let (start, end, period) = parse_date_range(&AnalyticsQuery {
+        start: query.start.clone(),
+        end: query.end.clone(),
+        limit: None,
+    });
+
+    #[derive(FromRow)]
+    struct BucketRow {
+        bucket: i32,
+        events: i64,
+    }
+
+    let rows: Vec<BucketRow> = sqlx::query_as(
+        r#"
+        SELECT LEAST(scroll_depth_pct / 10, 9)::int as bucket, COUNT(*)::bigint as events
+        FROM analytics_interaction_events
+        WHERE event_kind = 'scroll_depth'
+          AND url_path = $1
+          AND created_at >= $2 AND created_at <= $3
+        GROUP BY bucket
+        ORDER BY bucket
+        "#,
+    )
+    .bind(&query.path)
+    .bind(start)
+    .bind(end)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let total_events: i64 = rows.iter().map(|r| r.events).sum();
+
+    // Fill all ten deciles; reached_pct is cumulative from the deepest
+    // bucket down so the dashboard can render a fall-off curve directly
+    let mut buckets = Vec::with_capacity(10);
+    for decile in 0..10 {
+        let events = rows
+            .iter()
+            .find(|r| r.bucket == decile)
+            .map(|r| r.events)
+            .unwrap_or(0);
+        let reached: i64 = rows
+            .iter()
+            .filter(|r| r.bucket >= decile)
+            .map(|r| r.events)
+            .sum();
+        let reached_pct = if total_events > 0 {
+            (reached as f64 / total_events as f64) * 100.0
+        } else {
+            0.0
+        };
+        buckets.push(ScrollDepthBucket {
+            depth_from: decile * 10,
+            events,
+            reached_pct,
+        });
+    }
+
+    Ok(Json(ScrollDistributionResponse {
+        path: query.path,
+        period,
+        total_events,
+        buckets,
+    }))
+}
+
+/// Get per-page click density buckets (5% grid)
+pub async fn get_click_density(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<HeatmapQuery>,
+) -> ApiResult<Json<ClickDensityResponse>> {
+    require_admin(&state.pool, &auth_user).await?;
+
+    let (start, end, period) = parse_date_range(&AnalyticsQuery {
+        start: query.start.clone(),
+        end: query.end.clone(),
+        limit: None,
+    });
+
+    let buckets: Vec<ClickDensityBucket> = sqlx::query_as(
+        r#"
+        SELECT LEAST(click_x_pct / $4, $5 - 1)::int as x_bucket,
+               LEAST(click_y_pct / $4, $5 - 1)::int as y_bucket,
+               COUNT(*)::bigint as clicks
+        FROM analytics_interaction_events
+        WHERE event_kind = 'click'
+          AND url_path = $1
+          AND created_at >= $2 AND created_at <= $3
+        GROUP BY x_bucket, y_bucket
+        ORDER BY clicks DESC
+        "#,
+    )
+    .bind(&query.path)
+    .bind(start)
+    .bind(end)
+    .bind(CLICK_BUCKET_SIZE_PCT)
+    .bind(100 / CLICK_BUCKET_SIZE_PCT)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let total_clicks: i64 = buckets.iter().map(|b| b.clicks).sum();
+
+    Ok(Json(ClickDensityResponse {
+        path: query.path,
+        period,
+        bucket_size_pct: CLICK_BUCKET_SIZE_PCT,
+        total_clicks,
+        buckets,
+    }))
+}
+
 // Goal types
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct Goal {
@@ -1684,6 +2013,8 @@ pub struct Goal {
     pub goal_type: String,
     pub min_duration_seconds: Option<i32>,
     pub min_page_views: Option<i32>,
+    /// Condition objects ANDed at ingestion (see the goals module)
+    pub conditions: Option<serde_json::Value>,
     pub is_active: bool,
     #[serde(with = "timestamp_format")]
     pub created_at: OffsetDateTime,
@@ -1705,6 +2036,8 @@ pub struct CreateGoalRequest {
     pub goal_type: String,
     pub min_duration_seconds: Option<i32>,
     pub min_page_views: Option<i32>,
+    /// Condition objects ANDed at ingestion (see the goals module)
+    pub conditions: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -1716,6 +2049,7 @@ pub struct UpdateGoalRequest {
     pub goal_type: Option<String>,
     pub min_duration_seconds: Option<i32>,
     pub min_page_views: Option<i32>,
+    pub conditions: Option<serde_json::Value>,
     pub is_active: Option<bool>,
 }
 
@@ -1729,7 +2063,7 @@ pub async fn list_goals(
     let goals: Vec<Goal> = sqlx::query_as(
         r#"
         SELECT id, name, description, event_name, url_pattern, goal_type,
-               min_duration_seconds, min_page_views, is_active, created_at, updated_at
+               min_duration_seconds, min_page_views, conditions, is_active, created_at, updated_at
         FROM analytics_goals
         ORDER BY created_at DESC
         "#,
@@ -1748,12 +2082,17 @@ pub async fn create_goal(
 ) -> ApiResult<Json<Goal>> {
     require_admin(&state.pool, &auth_user).await?;
 
+    if let Some(conditions) = &req.conditions {
+        crate::goals::compile_conditions(conditions)
+            .map_err(|e| ApiError::BadRequest(format!("Invalid goal conditions: {}", e)))?;
+    }
+
     let goal: Goal = sqlx::query_as(
         r#"
-        INSERT INTO analytics_goals (name, description, event_name, url_pattern, goal_type, min_duration_seconds, min_page_views)
-        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        INSERT INTO analytics_goals (name, description, event_name, url_pattern, goal_type, min_duration_seconds, min_page_views, conditions)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
         RETURNING id, name, description, event_name, url_pattern, goal_type,
-                  min_duration_seconds, min_page_views, is_active, created_at, updated_at
+                  min_duration_seconds, min_page_views, conditions, is_active, created_at, updated_at
         "#
     )
     .bind(&req.name)
@@ -1763,9 +2102,12 @@ pub async fn create_goal(
     .bind(&req.goal_type)
     .bind(req.min_duration_seconds)
     .bind(req.min_page_views)
+    .bind(&req.conditions)
     .fetch_one(&state.pool)
     .await?;
 
+    state.goal_engine.invalidate().await;
+
     Ok(Json(goal))
 }
 
@@ -1778,6 +2120,11 @@ pub async fn update_goal(
 ) -> ApiResult<Json<Goal>> {
     require_admin(&state.pool, &auth_user).await?;
 
+    if let Some(conditions) = &req.conditions {
+        crate::goals::compile_conditions(conditions)
+            .map_err(|e| ApiError::BadRequest(format!("Invalid goal conditions: {}", e)))?;
+    }
+
     let goal: Goal = sqlx::query_as(
         r#"
         UPDATE analytics_goals SET
@@ -1788,11 +2135,12 @@ pub async fn update_goal(
             goal_type = COALESCE($6, goal_type),
             min_duration_seconds = COALESCE($7, min_duration_seconds),
             min_page_views = COALESCE($8, min_page_views),
-            is_active = COALESCE($9, is_active),
+            conditions = COALESCE($9, conditions),
+            is_active = COALESCE($10, is_active),
             updated_at = NOW()
         WHERE id = $1
         RETURNING id, name, description, event_name, url_pattern, goal_type,
-                  min_duration_seconds, min_page_views, is_active, created_at, updated_at
+                  min_duration_seconds, min_page_views, conditions, is_active, created_at, updated_at
         "#,
     )
     .bind(goal_id)
@@ -1803,10 +2151,13 @@ pub async fn update_goal(
     .bind(&req.goal_type)
     .bind(req.min_duration_seconds)
     .bind(req.min_page_views)
+    .bind(&req.conditions)
     .bind(req.is_active)
     .fetch_one(&state.pool)
     .await?;
 
+    state.goal_engine.invalidate().await;
+
     Ok(Json(goal))
 }
 
@@ -1823,9 +2174,102 @@ pub async fn delete_goal(
         .execute(&state.pool)
         .await?;
 
+    state.goal_engine.invalidate().await;
+
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[derive(Debug, Deserialize)]
+pub struct TestGoalRequest {
+    /// Path of the sample hit, e.g. "/pricing"
+    pub path: String,
+    pub event_name: Option<String>,
+    pub event_data: Option<serde_json::Value>,
+    pub referrer: Option<String>,
+    #[serde(default = "default_test_device_type")]
+    pub device_type: String,
+}
+
+fn default_test_device_type() -> String {
+    "desktop".to_string()
+}
+
+#[derive(Debug, Serialize)]
+pub struct TestGoalConditionResult {
+    pub kind: &'static str,
+    pub matched: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TestGoalResponse {
+    /// True when the goal type applies to the sample and all conditions pass
+    pub matched: bool,
+    /// False when the goal type cannot match this kind of hit
+    /// (e.g. an event goal against a plain pageview)
+    pub goal_type_applicable: bool,
+    pub conditions: Vec<TestGoalConditionResult>,
+}
+
+/// Evaluate a sample event against a goal's conditions without recording anything
+pub async fn test_goal(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    axum::extract::Path(goal_id): axum::extract::Path<Uuid>,
+    Json(req): Json<TestGoalRequest>,
+) -> ApiResult<Json<TestGoalResponse>> {
+    require_admin(&state.pool, &auth_user).await?;
+
+    let goal: Goal = sqlx::query_as(
+        r#"
+        SELECT id, name, description, event_name, url_pattern, goal_type,
+               min_duration_seconds, min_page_views, conditions, is_active, created_at, updated_at
+        FROM analytics_goals
+        WHERE id = $1
+        "#,
+    )
+    .bind(goal_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    let compiled = match &goal.conditions {
+        Some(conditions) => crate::goals::compile_conditions(conditions)
+            .map_err(|e| ApiError::BadRequest(format!("Goal has invalid conditions: {}", e)))?,
+        None => Vec::new(),
+    };
+
+    let ctx = crate::goals::GoalEventContext {
+        path: &req.path,
+        event_name: req.event_name.as_deref(),
+        event_data: req.event_data.as_ref(),
+        referrer: req.referrer.as_deref(),
+        device_type: &req.device_type,
+    };
+
+    let goal_type_applicable = match goal.goal_type.as_str() {
+        "event" => ctx.event_name.is_some(),
+        "pageview" => true,
+        // Engagement/duration goals are evaluated per session, not per hit
+        _ => false,
+    };
+
+    let condition_results: Vec<TestGoalConditionResult> = compiled
+        .iter()
+        .map(|c| TestGoalConditionResult {
+            kind: c.kind(),
+            matched: c.matches(&ctx),
+        })
+        .collect();
+
+    let matched = goal_type_applicable && condition_results.iter().all(|c| c.matched);
+
+    Ok(Json(TestGoalResponse {
+        matched,
+        goal_type_applicable,
+        conditions: condition_results,
+    }))
+}
+
 // Settings types
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct AnalyticsSettings {
@@ -1841,6 +2285,9 @@ pub struct AnalyticsSettings {
     pub excluded_paths: Option<Vec<String>>,
     pub excluded_ips: Option<Vec<String>>,
     pub filter_bots: bool,
+    pub interaction_events_enabled: bool,
+    pub interaction_sample_rate: f64,
+    pub interaction_retention_days: i32,
     #[serde(with = "timestamp_format")]
     pub created_at: OffsetDateTime,
     #[serde(with = "timestamp_format")]
@@ -1860,6 +2307,9 @@ pub struct UpdateSettingsRequest {
     pub excluded_paths: Option<Vec<String>>,
     pub excluded_ips: Option<Vec<String>>,
     pub filter_bots: Option<bool>,
+    pub interaction_events_enabled: Option<bool>,
+    pub interaction_sample_rate: Option<f64>,
+    pub interaction_retention_days: Option<i32>,
 }
 
 /// Get analytics settings
@@ -1874,6 +2324,7 @@ pub async fn get_settings(
         SELECT id, anonymize_ip, collect_city, respect_dnt, cookie_consent_required,
                raw_data_retention_days, aggregate_retention_days, enable_realtime,
                realtime_max_visitors, excluded_paths, excluded_ips, filter_bots,
+               interaction_events_enabled, interaction_sample_rate, interaction_retention_days,
                created_at, updated_at
         FROM analytics_settings
         LIMIT 1
@@ -1893,6 +2344,21 @@ pub async fn update_settings(
 ) -> ApiResult<Json<AnalyticsSettings>> {
     require_admin(&state.pool, &auth_user).await?;
 
+    if let Some(rate) = req.interaction_sample_rate {
+        if !(0.0..=1.0).contains(&rate) {
+            return Err(ApiError::Validation(
+                "interaction_sample_rate must be between 0.0 and 1.0".to_string(),
+            ));
+        }
+    }
+    if let Some(days) = req.interaction_retention_days {
+        if days < 1 {
+            return Err(ApiError::Validation(
+                "interaction_retention_days must be at least 1".to_string(),
+            ));
+        }
+    }
+
     let settings: AnalyticsSettings = sqlx::query_as(
         r#"
         UPDATE analytics_settings SET
@@ -1907,10 +2373,14 @@ pub async fn update_settings(
             excluded_paths = COALESCE($9, excluded_paths),
             excluded_ips = COALESCE($10, excluded_ips),
             filter_bots = COALESCE($11, filter_bots),
+            interaction_events_enabled = COALESCE($12, interaction_events_enabled),
+            interaction_sample_rate = COALESCE($13, interaction_sample_rate),
+            interaction_retention_days = COALESCE($14, interaction_retention_days),
             updated_at = NOW()
         RETURNING id, anonymize_ip, collect_city, respect_dnt, cookie_consent_required,
                   raw_data_retention_days, aggregate_retention_days, enable_realtime,
                   realtime_max_visitors, excluded_paths, excluded_ips, filter_bots,
+                  interaction_events_enabled, interaction_sample_rate, interaction_retention_days,
                   created_at, updated_at
         "#,
     )
@@ -1925,6 +2395,9 @@ pub async fn update_settings(
     .bind(&req.excluded_paths)
     .bind(&req.excluded_ips)
     .bind(req.filter_bots)
+    .bind(req.interaction_events_enabled)
+    .bind(req.interaction_sample_rate)
+    .bind(req.interaction_retention_days)
     .fetch_one(&state.pool)
     .await?;
 
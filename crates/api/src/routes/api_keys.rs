@@ -1,7 +1,7 @@
 //! API key management routes
 
 use axum::{
-    extract::{Extension, Path, State},
+    extract::{Extension, Path, Query, State},
     http::StatusCode,
     Json,
 };
@@ -10,17 +10,30 @@ use sqlx::FromRow;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
+use super::list_query::{ListParams, ListQuery};
 use crate::{
     auth::AuthUser,
     error::{ApiError, ApiResult},
     state::AppState,
 };
-use plexmcp_shared::types::{CustomLimits, EffectiveLimits, SubscriptionTier};
+use plexmcp_shared::types::{CustomLimits, EffectiveLimits, PaginatedResponse, SubscriptionTier};
 
 // =============================================================================
 // Request/Response Types
 // =============================================================================
 
+/// Query params for listing API keys
+#[derive(Debug, Deserialize)]
+pub struct ListApiKeysQuery {
+    pub status: Option<String>,
+    /// Case-insensitive substring match on the key name
+    pub name: Option<String>,
+    /// Exact match on the batch label
+    pub label: Option<String>,
+    #[serde(flatten)]
+    pub list: ListParams,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CreateApiKeyRequest {
     pub name: String,
@@ -38,6 +51,10 @@ pub struct CreateApiKeyRequest {
     pub mcp_access_mode: String,
     /// When mcp_access_mode='selected', the MCP IDs this key can access
     pub allowed_mcp_ids: Option<Vec<Uuid>>,
+    /// CIDR ranges the key may be used from (empty/missing = any address)
+    pub allowed_cidrs: Option<Vec<String>>,
+    /// Origins the key may be used from (empty/missing = any origin)
+    pub allowed_origins: Option<Vec<String>>,
 }
 
 fn default_mcp_access_mode() -> String {
@@ -56,18 +73,21 @@ pub struct UpdateApiKeyRequest {
     /// ISO 8601 date string for expiration, empty string to clear, null/missing to not change
     #[serde(default)]
     pub expires_at: Option<String>,
+    /// Suppress in-band quota warnings (`_meta` on proxy responses) for this key
+    pub suppress_usage_warnings: Option<bool>,
+    /// CIDR ranges the key may be used from; empty list clears the restriction
+    pub allowed_cidrs: Option<Vec<String>>,
+    /// Origins the key may be used from; empty list clears the restriction
+    pub allowed_origins: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct RotateApiKeyRequest {
     /// Optional PIN for encrypting the new key (required if user has PIN set)
     pub pin: Option<String>,
-}
-
-#[derive(Debug, Serialize)]
-pub struct ApiKeyListResponse {
-    pub api_keys: Vec<ApiKeySummary>,
-    pub total: i64,
+    /// Hours the old secret stays valid after rotation (0-168, default 24).
+    /// Pass 0 to invalidate the old secret immediately.
+    pub overlap_hours: Option<i64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -88,6 +108,14 @@ pub struct ApiKeySummary {
     pub mcp_access_mode: String,
     /// When mcp_access_mode='selected', only these MCP IDs are accessible
     pub allowed_mcp_ids: Option<Vec<Uuid>>,
+    /// Whether in-band quota warnings are suppressed for this key
+    pub suppress_usage_warnings: bool,
+    /// Batch label for grouped provisioning and bulk revocation
+    pub label: Option<String>,
+    /// CIDR ranges the key may be used from (None = any address)
+    pub allowed_cidrs: Option<Vec<String>>,
+    /// Origins the key may be used from (None = any origin)
+    pub allowed_origins: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize)]
@@ -109,6 +137,29 @@ pub struct ApiKeyDetailResponse {
     pub mcp_access_mode: String,
     /// When mcp_access_mode='selected', only these MCP IDs are accessible
     pub allowed_mcp_ids: Option<Vec<Uuid>>,
+    /// Whether in-band quota warnings are suppressed for this key
+    pub suppress_usage_warnings: bool,
+    /// Batch label for grouped provisioning and bulk revocation
+    pub label: Option<String>,
+    /// CIDR ranges the key may be used from (None = any address)
+    pub allowed_cidrs: Option<Vec<String>>,
+    /// Origins the key may be used from (None = any origin)
+    pub allowed_origins: Option<Vec<String>>,
+    /// Active rotation overlap window, if the old secret is still valid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rotation: Option<RotationStatus>,
+}
+
+/// Rotation overlap state shown on the key detail endpoint
+#[derive(Debug, Serialize)]
+pub struct RotationStatus {
+    /// Prefix of the pre-rotation secret still accepted during the overlap
+    pub previous_key_prefix: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub old_key_valid_until: OffsetDateTime,
+    /// Last successful auth with the old secret (None = nothing still uses it)
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub previous_last_used_at: Option<OffsetDateTime>,
 }
 
 #[derive(Debug, Serialize)]
@@ -134,6 +185,9 @@ pub struct ApiKeyRotatedResponse {
     pub old_key_prefix: String,
     #[serde(with = "time::serde::rfc3339")]
     pub rotated_at: OffsetDateTime,
+    /// When the old secret stops being accepted (None = already invalid)
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub old_key_valid_until: Option<OffsetDateTime>,
 }
 
 #[derive(Debug, Serialize)]
@@ -168,6 +222,28 @@ struct ApiKeyRow {
     created_at: OffsetDateTime,
     mcp_access_mode: String,
     allowed_mcp_ids: Option<Vec<Uuid>>,
+    suppress_usage_warnings: bool,
+    label: Option<String>,
+    previous_key_prefix: Option<String>,
+    rotation_expires_at: Option<OffsetDateTime>,
+    previous_last_used_at: Option<OffsetDateTime>,
+    allowed_cidrs: Option<Vec<String>>,
+    allowed_origins: Option<Vec<String>>,
+}
+
+impl ApiKeyRow {
+    /// Rotation overlap state for detail responses, if a window is open
+    fn rotation_status(&self) -> Option<RotationStatus> {
+        let expires_at = self.rotation_expires_at?;
+        if expires_at <= OffsetDateTime::now_utc() {
+            return None;
+        }
+        Some(RotationStatus {
+            previous_key_prefix: self.previous_key_prefix.clone().unwrap_or_default(),
+            old_key_valid_until: expires_at,
+            previous_last_used_at: self.previous_last_used_at,
+        })
+    }
 }
 
 #[derive(Debug, FromRow)]
@@ -182,11 +258,20 @@ struct UsageRow {
 // Handlers
 // =============================================================================
 
+/// Sort keys accepted by the API key listing; first entry is the default
+const API_KEY_SORT_KEYS: &[(&str, &str)] = &[
+    ("created_at", "created_at"),
+    ("name", "name"),
+    ("last_used", "last_used_at"),
+    ("expires_at", "expires_at"),
+];
+
 /// List all API keys in the organization
 pub async fn list_api_keys(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
-) -> ApiResult<Json<ApiKeyListResponse>> {
+    Query(query): Query<ListApiKeysQuery>,
+) -> ApiResult<Json<PaginatedResponse<ApiKeySummary>>> {
     let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
 
     // Viewers cannot list API keys
@@ -194,41 +279,39 @@ pub async fn list_api_keys(
         return Err(ApiError::Forbidden);
     }
 
+    let order_by = query.list.order_by(API_KEY_SORT_KEYS)?;
+
+    let mut list_query = ListQuery::new("api_keys")
+        .eq_uuid("org_id", org_id)
+        .eq_text("status", query.status)
+        .contains("name", query.name)
+        .eq_text("label", query.label);
+
     // Members can only see their own keys, admin/owner see all
-    let keys: Vec<ApiKeyRow> = if ["owner", "admin"].contains(&auth_user.role.as_str()) {
-        // Admin/Owner: see all keys in the org
-        sqlx::query_as(
-            r#"
-            SELECT id, name, key_prefix, scopes, rate_limit_rpm, expires_at,
-                   last_used_at, request_count, created_by, created_at,
-                   mcp_access_mode, allowed_mcp_ids
-            FROM api_keys
-            WHERE org_id = $1
-            ORDER BY created_at DESC
-            "#,
-        )
-        .bind(org_id)
-        .fetch_all(&state.pool)
-        .await?
-    } else {
-        // Member: only see keys they created
-        sqlx::query_as(
-            r#"
-            SELECT id, name, key_prefix, scopes, rate_limit_rpm, expires_at,
-                   last_used_at, request_count, created_by, created_at,
-                   mcp_access_mode, allowed_mcp_ids
-            FROM api_keys
-            WHERE org_id = $1 AND created_by = $2
-            ORDER BY created_at DESC
-            "#,
-        )
-        .bind(org_id)
-        .bind(auth_user.user_id)
-        .fetch_all(&state.pool)
-        .await?
-    };
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        let Some(user_id) = auth_user.user_id else {
+            return Ok(Json(PaginatedResponse::new(
+                vec![],
+                0,
+                query.list.page(),
+                query.list.per_page(),
+            )));
+        };
+        list_query = list_query.eq_uuid("created_by", user_id);
+    }
 
-    let total = keys.len() as i64;
+    let (keys, total): (Vec<ApiKeyRow>, i64) = list_query
+        .fetch_page(
+            &state.pool,
+            "id, name, key_prefix, scopes, rate_limit_rpm, expires_at, \
+             last_used_at, request_count, created_by, created_at, \
+             mcp_access_mode, allowed_mcp_ids, suppress_usage_warnings, label, \
+             previous_key_prefix, rotation_expires_at, previous_last_used_at, \
+             allowed_cidrs, allowed_origins",
+            &order_by,
+            &query.list,
+        )
+        .await?;
 
     let api_keys: Vec<ApiKeySummary> = keys
         .into_iter()
@@ -246,11 +329,20 @@ pub async fn list_api_keys(
                 created_at: k.created_at,
                 mcp_access_mode: k.mcp_access_mode,
                 allowed_mcp_ids: k.allowed_mcp_ids,
+                suppress_usage_warnings: k.suppress_usage_warnings,
+                label: k.label,
+                allowed_cidrs: k.allowed_cidrs,
+                allowed_origins: k.allowed_origins,
             }
         })
         .collect();
 
-    Ok(Json(ApiKeyListResponse { api_keys, total }))
+    Ok(Json(PaginatedResponse::new(
+        api_keys,
+        total,
+        query.list.page(),
+        query.list.per_page(),
+    )))
 }
 
 /// Get a specific API key by ID
@@ -271,7 +363,9 @@ pub async fn get_api_key(
         r#"
         SELECT id, name, key_prefix, scopes, rate_limit_rpm, expires_at,
                last_used_at, request_count, created_by, created_at,
-               mcp_access_mode, allowed_mcp_ids
+               mcp_access_mode, allowed_mcp_ids, suppress_usage_warnings, label,
+                   previous_key_prefix, rotation_expires_at, previous_last_used_at,
+                   allowed_cidrs, allowed_origins
         FROM api_keys
         WHERE id = $1 AND org_id = $2
         "#,
@@ -287,6 +381,7 @@ pub async fn get_api_key(
         return Err(ApiError::Forbidden);
     }
 
+    let rotation = key.rotation_status();
     let scopes: Vec<String> = serde_json::from_value(key.scopes).unwrap_or_default();
 
     Ok(Json(ApiKeyDetailResponse {
@@ -302,6 +397,11 @@ pub async fn get_api_key(
         created_at: key.created_at,
         mcp_access_mode: key.mcp_access_mode,
         allowed_mcp_ids: key.allowed_mcp_ids,
+        suppress_usage_warnings: key.suppress_usage_warnings,
+        label: key.label,
+        allowed_cidrs: key.allowed_cidrs,
+        allowed_origins: key.allowed_origins,
+        rotation,
     }))
 }
 
@@ -394,6 +494,16 @@ pub async fn create_api_key(
         ));
     }
 
+    // Validate network restrictions (empty lists are stored as NULL = unrestricted)
+    let allowed_cidrs = match &req.allowed_cidrs {
+        Some(list) => normalize_cidr_list(list)?,
+        None => None,
+    };
+    let allowed_origins = match &req.allowed_origins {
+        Some(list) => normalize_origin_list(list)?,
+        None => None,
+    };
+
     // If PIN is provided, verify it BEFORE creating the key
     if let Some(ref pin) = req.pin {
         if let Some(user_id) = auth_user.user_id {
@@ -407,8 +517,8 @@ pub async fn create_api_key(
 
     sqlx::query(
         r#"
-        INSERT INTO api_keys (id, org_id, name, key_hash, key_prefix, scopes, rate_limit_rpm, expires_at, created_by, mcp_access_mode, allowed_mcp_ids)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        INSERT INTO api_keys (id, org_id, name, key_hash, key_prefix, scopes, rate_limit_rpm, expires_at, created_by, mcp_access_mode, allowed_mcp_ids, allowed_cidrs, allowed_origins)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
         "#
     )
     .bind(key_id)
@@ -422,6 +532,8 @@ pub async fn create_api_key(
     .bind(auth_user.user_id)  // created_by - track who created this key for member self-management
     .bind(&req.mcp_access_mode)
     .bind(&req.allowed_mcp_ids)
+    .bind(&allowed_cidrs)
+    .bind(&allowed_origins)
     .execute(&state.pool)
     .await?;
 
@@ -551,6 +663,33 @@ pub async fn update_api_key(
             .await?;
     }
 
+    // Update network restrictions if provided (empty list clears the restriction)
+    if let Some(ref list) = req.allowed_cidrs {
+        let allowed_cidrs = normalize_cidr_list(list)?;
+        sqlx::query("UPDATE api_keys SET allowed_cidrs = $1 WHERE id = $2")
+            .bind(&allowed_cidrs)
+            .bind(key_id)
+            .execute(&state.pool)
+            .await?;
+    }
+    if let Some(ref list) = req.allowed_origins {
+        let allowed_origins = normalize_origin_list(list)?;
+        sqlx::query("UPDATE api_keys SET allowed_origins = $1 WHERE id = $2")
+            .bind(&allowed_origins)
+            .bind(key_id)
+            .execute(&state.pool)
+            .await?;
+    }
+
+    // Update usage warning suppression if provided
+    if let Some(suppress) = req.suppress_usage_warnings {
+        sqlx::query("UPDATE api_keys SET suppress_usage_warnings = $1 WHERE id = $2")
+            .bind(suppress)
+            .bind(key_id)
+            .execute(&state.pool)
+            .await?;
+    }
+
     // Update expires_at if provided
     if let Some(ref expires_at_str) = req.expires_at {
         let expires_at: Option<time::OffsetDateTime> = if expires_at_str.is_empty() {
@@ -604,7 +743,9 @@ pub async fn update_api_key(
         r#"
         SELECT id, name, key_prefix, scopes, rate_limit_rpm, expires_at,
                last_used_at, request_count, created_by, created_at,
-               mcp_access_mode, allowed_mcp_ids
+               mcp_access_mode, allowed_mcp_ids, suppress_usage_warnings, label,
+                   previous_key_prefix, rotation_expires_at, previous_last_used_at,
+                   allowed_cidrs, allowed_origins
         FROM api_keys
         WHERE id = $1
         "#,
@@ -613,6 +754,7 @@ pub async fn update_api_key(
     .fetch_one(&state.pool)
     .await?;
 
+    let rotation = key.rotation_status();
     let scopes: Vec<String> = serde_json::from_value(key.scopes).unwrap_or_default();
 
     Ok(Json(ApiKeyDetailResponse {
@@ -628,10 +770,15 @@ pub async fn update_api_key(
         created_at: key.created_at,
         mcp_access_mode: key.mcp_access_mode,
         allowed_mcp_ids: key.allowed_mcp_ids,
+        suppress_usage_warnings: key.suppress_usage_warnings,
+        label: key.label,
+        allowed_cidrs: key.allowed_cidrs,
+        allowed_origins: key.allowed_origins,
+        rotation,
     }))
 }
 
-/// Rotate an API key (generate new secret, invalidate old)
+/// Rotate an API key (generate new secret, old stays valid for an overlap window)
 pub async fn rotate_api_key(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
@@ -650,7 +797,9 @@ pub async fn rotate_api_key(
         r#"
         SELECT id, name, key_prefix, scopes, rate_limit_rpm, expires_at,
                last_used_at, request_count, created_by, created_at,
-               mcp_access_mode, allowed_mcp_ids
+               mcp_access_mode, allowed_mcp_ids, suppress_usage_warnings, label,
+                   previous_key_prefix, rotation_expires_at, previous_last_used_at,
+                   allowed_cidrs, allowed_origins
         FROM api_keys
         WHERE id = $1 AND org_id = $2
         "#,
@@ -668,6 +817,20 @@ pub async fn rotate_api_key(
 
     let old_prefix = current.key_prefix.clone();
 
+    // The old secret stays valid for the overlap window so deployed clients
+    // can roll over gracefully; 0 invalidates it immediately
+    let overlap_hours = body.as_ref().and_then(|b| b.overlap_hours).unwrap_or(24);
+    if !(0..=168).contains(&overlap_hours) {
+        return Err(ApiError::Validation(
+            "overlap_hours must be between 0 and 168".to_string(),
+        ));
+    }
+    let old_key_valid_until = if overlap_hours > 0 {
+        Some(OffsetDateTime::now_utc() + time::Duration::hours(overlap_hours))
+    } else {
+        None
+    };
+
     // Generate new key
     let (full_key, key_hash, key_prefix) = state
         .api_key_manager
@@ -687,13 +850,26 @@ pub async fn rotate_api_key(
         }
     }
 
-    // Update the key hash and prefix, clear encrypted_key since it's a new key
+    // Update the key hash and prefix, clear encrypted_key since it's a new
+    // key. The old hash moves into the previous_* columns for the overlap
+    // window (or is dropped entirely when overlap_hours = 0).
     sqlx::query(
-        "UPDATE api_keys SET key_hash = $1, key_prefix = $2, request_count = 0, encrypted_key = NULL, key_nonce = NULL WHERE id = $3"
+        r#"
+        UPDATE api_keys
+        SET previous_key_hash = CASE WHEN $4::timestamptz IS NULL THEN NULL ELSE key_hash END,
+            previous_key_prefix = CASE WHEN $4::timestamptz IS NULL THEN NULL ELSE key_prefix END,
+            rotation_expires_at = $4,
+            previous_last_used_at = NULL,
+            key_hash = $1, key_prefix = $2, request_count = 0,
+            last_rotated_at = NOW(), expiry_reminder_threshold_days = NULL,
+            encrypted_key = NULL, key_nonce = NULL
+        WHERE id = $3
+        "#,
     )
     .bind(&key_hash)
     .bind(&key_prefix)
     .bind(key_id)
+    .bind(old_key_valid_until)
     .execute(&state.pool)
     .await?;
 
@@ -718,9 +894,96 @@ pub async fn rotate_api_key(
         key_prefix,
         old_key_prefix: old_prefix,
         rotated_at: OffsetDateTime::now_utc(),
+        old_key_valid_until,
     }))
 }
 
+/// Force the rotation cutoff, invalidating the old secret immediately
+///
+/// Ends an open overlap window early once the operator has confirmed
+/// (via `rotation.previous_last_used_at`) that no client still uses the
+/// pre-rotation secret.
+pub async fn cutoff_api_key_rotation(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(key_id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    // Viewers cannot manage API keys
+    if auth_user.role.as_str() == "viewer" {
+        return Err(ApiError::Forbidden);
+    }
+
+    let key: Option<(Option<Uuid>, Option<OffsetDateTime>)> = sqlx::query_as(
+        "SELECT created_by, rotation_expires_at FROM api_keys WHERE id = $1 AND org_id = $2",
+    )
+    .bind(key_id)
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await?;
+    let (created_by, rotation_expires_at) = key.ok_or(ApiError::NotFound)?;
+
+    // Members can only manage their own keys
+    if auth_user.role.as_str() == "member" && created_by != auth_user.user_id {
+        return Err(ApiError::Forbidden);
+    }
+
+    if rotation_expires_at.is_none() {
+        return Err(ApiError::Validation(
+            "No rotation overlap window is open for this key".to_string(),
+        ));
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE api_keys
+        SET previous_key_hash = NULL, previous_key_prefix = NULL,
+            rotation_expires_at = NULL, previous_last_used_at = NULL
+        WHERE id = $1 AND org_id = $2
+        "#,
+    )
+    .bind(key_id)
+    .bind(org_id)
+    .execute(&state.pool)
+    .await?;
+
+    tracing::info!(key_id = %key_id, org_id = %org_id, "Rotation overlap window force-closed");
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Set or clear an API key's cost center tag
+///
+/// Used as a fallback when the MCP instance carries no tag of its own in
+/// the `/usage/by-tag` chargeback reports. Org owners/admins only.
+pub async fn set_api_key_cost_center(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(key_id): Path<Uuid>,
+    Json(req): Json<super::mcps::SetCostCenterRequest>,
+) -> ApiResult<StatusCode> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let cost_center = super::mcps::validate_cost_center(req.cost_center)?;
+
+    let result = sqlx::query("UPDATE api_keys SET cost_center = $3 WHERE id = $1 AND org_id = $2")
+        .bind(key_id)
+        .bind(org_id)
+        .bind(cost_center)
+        .execute(&state.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// Delete an API key
 pub async fn delete_api_key(
     State(state): State<AppState>,
@@ -786,7 +1049,9 @@ pub async fn get_api_key_usage(
         r#"
         SELECT id, name, key_prefix, scopes, rate_limit_rpm, expires_at,
                last_used_at, request_count, created_by, created_at,
-               mcp_access_mode, allowed_mcp_ids
+               mcp_access_mode, allowed_mcp_ids, suppress_usage_warnings, label,
+                   previous_key_prefix, rotation_expires_at, previous_last_used_at,
+                   allowed_cidrs, allowed_origins
         FROM api_keys
         WHERE id = $1 AND org_id = $2
         "#,
@@ -850,6 +1115,470 @@ pub async fn get_api_key_usage(
     }))
 }
 
+// =============================================================================
+// Bulk Provisioning (MSP-style key minting)
+// =============================================================================
+
+/// Maximum keys mintable in a single bulk request
+const MAX_BULK_KEYS: usize = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct BulkCreateApiKeysRequest {
+    /// Name template applied to each key; `{n}` expands to the 1-based index
+    pub name_template: String,
+    /// Number of keys to mint (ignored when `keys` provides per-key specs)
+    pub count: Option<u32>,
+    /// Optional per-key overrides; when present, one key is minted per entry
+    pub keys: Option<Vec<BulkKeySpec>>,
+    /// Batch label stamped on every key, used for revocation by label
+    pub label: Option<String>,
+    /// Client idempotency key; retrying with the same key returns the
+    /// original batch metadata (without secrets) instead of minting again
+    pub idempotency_key: Option<String>,
+    /// Default scopes for keys without their own
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// Default rate limit for keys without their own
+    pub rate_limit_rpm: Option<i32>,
+    /// Default expiration for keys without their own
+    pub expires_in_days: Option<i32>,
+    /// MCP access mode applied to every key: 'all' (default), 'selected', or 'none'
+    #[serde(default = "default_mcp_access_mode")]
+    pub mcp_access_mode: String,
+    /// When mcp_access_mode='selected', the MCP IDs the keys can access
+    pub allowed_mcp_ids: Option<Vec<Uuid>>,
+}
+
+/// Per-key overrides in a bulk creation request
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BulkKeySpec {
+    /// Explicit name; falls back to the name template
+    pub name: Option<String>,
+    pub scopes: Option<Vec<String>>,
+    pub rate_limit_rpm: Option<i32>,
+    pub expires_in_days: Option<i32>,
+    pub allowed_mcp_ids: Option<Vec<Uuid>>,
+}
+
+/// One NDJSON line in the bulk creation response
+#[derive(Debug, Serialize)]
+struct BulkCreatedKeyLine {
+    id: Uuid,
+    name: String,
+    /// Full key secret - only present on first creation, never on replay
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key: Option<String>,
+    key_prefix: String,
+    label: Option<String>,
+    /// True when this line comes from an idempotent replay of an earlier batch
+    replayed: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BulkRevokeApiKeysRequest {
+    /// Revoke every key in the org carrying this batch label
+    pub label: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkRevokeResponse {
+    pub label: String,
+    pub revoked: u64,
+}
+
+/// Render a name template for the 1-based key index
+fn render_key_name(template: &str, index: usize) -> String {
+    template.replace("{n}", &index.to_string())
+}
+
+/// Bulk-create API keys with templated naming
+///
+/// Streams the created keys back as NDJSON, one key per line; secrets are
+/// shown once and cannot be recovered later. Owner/admin only - this is the
+/// programmatic provisioning path for MSP-style customers.
+pub async fn bulk_create_api_keys(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<BulkCreateApiKeysRequest>,
+) -> ApiResult<axum::response::Response> {
+    use axum::body::Body;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    // Bulk provisioning is owner/admin only
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    // Resolve how many keys to mint and their per-key specs
+    let specs: Vec<BulkKeySpec> = match &req.keys {
+        Some(keys) if !keys.is_empty() => keys.clone(),
+        Some(_) => {
+            return Err(ApiError::Validation(
+                "keys must not be empty when provided".to_string(),
+            ));
+        }
+        None => {
+            let count = req.count.unwrap_or(0) as usize;
+            if count == 0 {
+                return Err(ApiError::Validation(
+                    "Either count or keys is required".to_string(),
+                ));
+            }
+            vec![BulkKeySpec::default(); count]
+        }
+    };
+
+    if specs.len() > MAX_BULK_KEYS {
+        return Err(ApiError::Validation(format!(
+            "At most {} keys can be created per bulk request",
+            MAX_BULK_KEYS
+        )));
+    }
+
+    // Template must vary per key when it names more than one key
+    if req.name_template.trim().is_empty() || req.name_template.len() > 100 {
+        return Err(ApiError::Validation(
+            "name_template must be between 1 and 100 characters".to_string(),
+        ));
+    }
+    let template_used = specs.iter().filter(|s| s.name.is_none()).count();
+    if template_used > 1 && !req.name_template.contains("{n}") {
+        return Err(ApiError::Validation(
+            "name_template must contain {n} when naming more than one key".to_string(),
+        ));
+    }
+
+    // Validate per-key rate limits up front so the stream can't fail on them
+    for spec in &specs {
+        let rpm = spec.rate_limit_rpm.or(req.rate_limit_rpm).unwrap_or(60);
+        if !(1..=10000).contains(&rpm) {
+            return Err(ApiError::Validation(
+                "Rate limit must be between 1 and 10000 requests per minute".to_string(),
+            ));
+        }
+    }
+
+    if !["all", "selected", "none"].contains(&req.mcp_access_mode.as_str()) {
+        return Err(ApiError::Validation(
+            "mcp_access_mode must be 'all', 'selected', or 'none'".to_string(),
+        ));
+    }
+
+    let label = match req.label.as_deref() {
+        Some(raw) => {
+            let trimmed = raw.trim();
+            if trimmed.is_empty() || trimmed.len() > 100 {
+                return Err(ApiError::Validation(
+                    "label must be between 1 and 100 characters".to_string(),
+                ));
+            }
+            Some(trimmed.to_string())
+        }
+        None => None,
+    };
+
+    // Idempotent replay: return the original batch metadata without secrets
+    if let Some(ref idem_key) = req.idempotency_key {
+        let existing: Option<(serde_json::Value,)> = sqlx::query_as(
+            "SELECT key_ids FROM api_key_bulk_operations WHERE org_id = $1 AND idempotency_key = $2",
+        )
+        .bind(org_id)
+        .bind(idem_key)
+        .fetch_optional(&state.pool)
+        .await?;
+
+        if let Some((key_ids_json,)) = existing {
+            let key_ids: Vec<Uuid> = serde_json::from_value(key_ids_json).unwrap_or_default();
+            let keys: Vec<ApiKeyRow> = sqlx::query_as(
+                r#"
+                SELECT id, name, key_prefix, scopes, rate_limit_rpm, expires_at,
+                       last_used_at, request_count, created_by, created_at,
+                       mcp_access_mode, allowed_mcp_ids, suppress_usage_warnings, label,
+                   previous_key_prefix, rotation_expires_at, previous_last_used_at,
+                   allowed_cidrs, allowed_origins
+                FROM api_keys
+                WHERE org_id = $1 AND id = ANY($2)
+                ORDER BY created_at
+                "#,
+            )
+            .bind(org_id)
+            .bind(&key_ids)
+            .fetch_all(&state.pool)
+            .await?;
+
+            let mut body = String::new();
+            for key in keys {
+                let line = BulkCreatedKeyLine {
+                    id: key.id,
+                    name: key.name,
+                    key: None,
+                    key_prefix: key.key_prefix,
+                    label: key.label,
+                    replayed: true,
+                };
+                if let Ok(json) = serde_json::to_string(&line) {
+                    body.push_str(&json);
+                    body.push('\n');
+                }
+            }
+
+            return axum::response::Response::builder()
+                .status(StatusCode::OK)
+                .header("content-type", "application/x-ndjson")
+                .body(Body::from(body))
+                .map_err(|_| ApiError::Internal);
+        }
+    }
+
+    // Check tier limits for the whole batch up front
+    let effective_limits = get_org_effective_limits(&state.pool, org_id).await?;
+    let max_keys = effective_limits.max_api_keys;
+    if max_keys != u32::MAX {
+        let key_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM api_keys WHERE org_id = $1")
+            .bind(org_id)
+            .fetch_one(&state.pool)
+            .await?;
+        if key_count.0 + specs.len() as i64 > max_keys as i64 {
+            return Err(ApiError::QuotaExceeded(format!(
+                "Creating {} keys would exceed the {} API key limit. Contact support to increase your limit.",
+                specs.len(),
+                max_keys
+            )));
+        }
+    }
+
+    // Mint keys in a background task and stream each one out as it lands
+    let (tx, rx) = tokio::sync::mpsc::channel::<Result<String, std::convert::Infallible>>(16);
+    let state_task = state.clone();
+    let user_id = auth_user.user_id;
+    let idempotency_key = req.idempotency_key.clone();
+
+    tokio::spawn(async move {
+        let mut created_ids: Vec<Uuid> = Vec::with_capacity(specs.len());
+
+        for (i, spec) in specs.iter().enumerate() {
+            let name = spec
+                .name
+                .clone()
+                .unwrap_or_else(|| render_key_name(&req.name_template, i + 1));
+            let scopes = spec.scopes.as_ref().unwrap_or(&req.scopes);
+            let rate_limit_rpm = spec.rate_limit_rpm.or(req.rate_limit_rpm).unwrap_or(60);
+            let expires_at = spec
+                .expires_in_days
+                .or(req.expires_in_days)
+                .map(|days| OffsetDateTime::now_utc() + time::Duration::days(days as i64));
+            let allowed_mcp_ids = spec
+                .allowed_mcp_ids
+                .as_ref()
+                .or(req.allowed_mcp_ids.as_ref());
+
+            let Ok((full_key, key_hash, key_prefix)) = state_task.api_key_manager.generate_key()
+            else {
+                let _ = tx
+                    .send(Ok(
+                        "{\"error\":\"Key generation failed; batch aborted\"}\n".to_string()
+                    ))
+                    .await;
+                break;
+            };
+
+            let key_id = Uuid::new_v4();
+            let scopes_json = serde_json::to_value(scopes).unwrap_or_default();
+
+            let insert = sqlx::query(
+                r#"
+                INSERT INTO api_keys (id, org_id, name, key_hash, key_prefix, scopes, rate_limit_rpm,
+                                      expires_at, created_by, mcp_access_mode, allowed_mcp_ids, label)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                "#,
+            )
+            .bind(key_id)
+            .bind(org_id)
+            .bind(name.trim())
+            .bind(&key_hash)
+            .bind(&key_prefix)
+            .bind(&scopes_json)
+            .bind(rate_limit_rpm)
+            .bind(expires_at)
+            .bind(user_id)
+            .bind(&req.mcp_access_mode)
+            .bind(allowed_mcp_ids)
+            .bind(&label)
+            .execute(&state_task.pool)
+            .await;
+
+            if let Err(e) = insert {
+                tracing::error!(org_id = %org_id, error = %e, "Bulk key creation failed mid-batch");
+                let _ = tx
+                    .send(Ok(
+                        "{\"error\":\"Key creation failed; batch aborted\"}\n".to_string()
+                    ))
+                    .await;
+                break;
+            }
+
+            created_ids.push(key_id);
+
+            let line = BulkCreatedKeyLine {
+                id: key_id,
+                name: name.trim().to_string(),
+                key: Some(full_key),
+                key_prefix,
+                label: label.clone(),
+                replayed: false,
+            };
+            let Ok(mut json) = serde_json::to_string(&line) else {
+                continue;
+            };
+            json.push('\n');
+            if tx.send(Ok(json)).await.is_err() {
+                // Client went away - keys already created stay valid
+                break;
+            }
+        }
+
+        // Record the operation so a retry with the same idempotency key
+        // replays metadata instead of minting a second batch
+        if let Some(idem_key) = idempotency_key {
+            let key_ids_json = serde_json::to_value(&created_ids).unwrap_or_default();
+            if let Err(e) = sqlx::query(
+                r#"
+                INSERT INTO api_key_bulk_operations (org_id, idempotency_key, key_ids, created_by)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (org_id, idempotency_key) DO NOTHING
+                "#,
+            )
+            .bind(org_id)
+            .bind(&idem_key)
+            .bind(&key_ids_json)
+            .bind(user_id)
+            .execute(&state_task.pool)
+            .await
+            {
+                tracing::error!(org_id = %org_id, error = %e, "Failed to record bulk operation");
+            }
+        }
+    });
+
+    axum::response::Response::builder()
+        .status(StatusCode::CREATED)
+        .header("content-type", "application/x-ndjson")
+        .body(Body::from_stream(ReceiverStream::new(rx)))
+        .map_err(|_| ApiError::Internal)
+}
+
+/// Revoke every API key in the org carrying a batch label
+///
+/// Owner/admin only. Deletes the keys outright, same as individual deletion.
+pub async fn bulk_revoke_api_keys(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<BulkRevokeApiKeysRequest>,
+) -> ApiResult<Json<BulkRevokeResponse>> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let label = req.label.trim();
+    if label.is_empty() || label.len() > 100 {
+        return Err(ApiError::Validation(
+            "label must be between 1 and 100 characters".to_string(),
+        ));
+    }
+
+    let result = sqlx::query("DELETE FROM api_keys WHERE org_id = $1 AND label = $2")
+        .bind(org_id)
+        .bind(label)
+        .execute(&state.pool)
+        .await?;
+
+    tracing::info!(
+        org_id = %org_id,
+        label = %label,
+        revoked = result.rows_affected(),
+        "Bulk API key revocation by label"
+    );
+
+    Ok(Json(BulkRevokeResponse {
+        label: label.to_string(),
+        revoked: result.rows_affected(),
+    }))
+}
+
+// =============================================================================
+// Network Restriction Helpers
+// =============================================================================
+
+/// Maximum entries in a key's CIDR or origin allowlist
+const MAX_NETWORK_RESTRICTION_ENTRIES: usize = 50;
+
+/// Validate and normalize a CIDR allowlist; an empty list becomes None
+/// (unrestricted)
+fn normalize_cidr_list(list: &[String]) -> Result<Option<Vec<String>>, ApiError> {
+    if list.len() > MAX_NETWORK_RESTRICTION_ENTRIES {
+        return Err(ApiError::Validation(format!(
+            "At most {} CIDR ranges are allowed per key",
+            MAX_NETWORK_RESTRICTION_ENTRIES
+        )));
+    }
+    let normalized: Vec<String> = list
+        .iter()
+        .map(|entry| {
+            let trimmed = entry.trim();
+            if !crate::auth::ApiKeyManager::is_valid_cidr(trimmed) {
+                return Err(ApiError::Validation(format!(
+                    "Invalid CIDR range: {} (expected e.g. 10.0.0.0/8 or 2001:db8::/32)",
+                    trimmed
+                )));
+            }
+            Ok(trimmed.to_string())
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(if normalized.is_empty() {
+        None
+    } else {
+        Some(normalized)
+    })
+}
+
+/// Validate and normalize an origin allowlist; an empty list becomes None
+/// (unrestricted)
+fn normalize_origin_list(list: &[String]) -> Result<Option<Vec<String>>, ApiError> {
+    if list.len() > MAX_NETWORK_RESTRICTION_ENTRIES {
+        return Err(ApiError::Validation(format!(
+            "At most {} origins are allowed per key",
+            MAX_NETWORK_RESTRICTION_ENTRIES
+        )));
+    }
+    let normalized: Vec<String> = list
+        .iter()
+        .map(|entry| {
+            let trimmed = entry.trim().trim_end_matches('/');
+            let valid = trimmed.len() <= 255
+                && !trimmed.contains(char::is_whitespace)
+                && trimmed
+                    .split_once("://")
+                    .is_some_and(|(scheme, host)| !scheme.is_empty() && !host.is_empty());
+            if !valid {
+                return Err(ApiError::Validation(format!(
+                    "Invalid origin: {} (expected e.g. https://app.example.com or https://*.example.com)",
+                    trimmed
+                )));
+            }
+            Ok(trimmed.to_ascii_lowercase())
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(if normalized.is_empty() {
+        None
+    } else {
+        Some(normalized)
+    })
+}
+
 // =============================================================================
 // Helper Functions for Custom Limits
 // =============================================================================
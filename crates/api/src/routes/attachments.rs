@@ -0,0 +1,464 @@
+//! Support ticket attachment routes
+//!
+//! Attachments are stored through the shared storage backend and scanned
+//! asynchronously via [`crate::virus_scan`]. The attachment record carries
+//! the scan status; downloads are blocked until the verdict is `clean`, and
+//! detections raise a `malware_detected` security alert.
+
+use axum::{
+    body::Bytes,
+    extract::{Extension, Path, Query, State},
+    http::HeaderMap,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    alerting::trigger_malware_detected_alert,
+    auth::AuthUser,
+    error::{ApiError, ApiResult},
+    state::AppState,
+    virus_scan::ScanVerdict,
+};
+
+use super::support::resolve_user_context;
+
+/// Maximum attachment size (the global body limit is also 10MB)
+const MAX_ATTACHMENT_SIZE_BYTES: usize = 10 * 1024 * 1024;
+
+/// Signed download URLs are valid for 5 minutes
+const DOWNLOAD_URL_EXPIRY_SECS: u64 = 300;
+
+// =============================================================================
+// Types
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct UploadAttachmentQuery {
+    pub filename: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Attachment {
+    pub id: Uuid,
+    pub ticket_id: Uuid,
+    pub uploaded_by: Option<Uuid>,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub scan_status: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AttachmentListResponse {
+    pub attachments: Vec<Attachment>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AttachmentDownloadResponse {
+    pub filename: String,
+    /// Time-limited signed URL
+    pub download_url: String,
+    pub expires_in_secs: u64,
+}
+
+#[derive(Debug, FromRow)]
+struct AttachmentRow {
+    id: Uuid,
+    ticket_id: Uuid,
+    uploaded_by: Option<Uuid>,
+    filename: String,
+    content_type: String,
+    size_bytes: i64,
+    scan_status: String,
+    created_at: OffsetDateTime,
+}
+
+impl From<AttachmentRow> for Attachment {
+    fn from(r: AttachmentRow) -> Self {
+        Self {
+            id: r.id,
+            ticket_id: r.ticket_id,
+            uploaded_by: r.uploaded_by,
+            filename: r.filename,
+            content_type: r.content_type,
+            size_bytes: r.size_bytes,
+            scan_status: r.scan_status,
+            created_at: r.created_at,
+        }
+    }
+}
+
+// =============================================================================
+// Helper Functions
+// =============================================================================
+
+/// Verify the authenticated user can access the ticket (same rules as replies)
+async fn verify_ticket_access(
+    state: &AppState,
+    auth_user: &AuthUser,
+    ticket_id: Uuid,
+) -> ApiResult<Uuid> {
+    let (user_id, org_id) = resolve_user_context(&state.pool, auth_user).await?;
+
+    let ticket_exists: bool = if let Some(org_id) = org_id {
+        sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM support_tickets WHERE id = $1 AND organization_id = $2)",
+        )
+        .bind(ticket_id)
+        .bind(org_id)
+        .fetch_one(&state.pool)
+        .await?
+    } else {
+        sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM support_tickets WHERE id = $1 AND user_id = $2)",
+        )
+        .bind(ticket_id)
+        .bind(user_id)
+        .fetch_one(&state.pool)
+        .await?
+    };
+
+    if !ticket_exists {
+        return Err(ApiError::NotFound);
+    }
+
+    Ok(user_id)
+}
+
+/// Reject filenames that could break storage keys or mislead reviewers
+fn validate_filename(filename: &str) -> ApiResult<()> {
+    if filename.is_empty() || filename.len() > 255 {
+        return Err(ApiError::Validation(
+            "filename must be between 1 and 255 characters".into(),
+        ));
+    }
+    if filename.contains('/') || filename.contains('\\') || filename.contains("..") {
+        return Err(ApiError::Validation(
+            "filename must not contain path separators".into(),
+        ));
+    }
+    if filename.chars().any(|c| c.is_control()) {
+        return Err(ApiError::Validation(
+            "filename must not contain control characters".into(),
+        ));
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// Upload an attachment to a ticket
+///
+/// The raw file bytes are the request body; the filename comes from the
+/// `filename` query parameter. The response carries `scan_status: pending`
+/// until the asynchronous virus scan completes.
+pub async fn upload_attachment(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(ticket_id): Path<Uuid>,
+    Query(query): Query<UploadAttachmentQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ApiResult<Json<Attachment>> {
+    let user_id = verify_ticket_access(&state, &auth_user, ticket_id).await?;
+
+    validate_filename(&query.filename)?;
+
+    if body.is_empty() {
+        return Err(ApiError::BadRequest("Attachment is empty".into()));
+    }
+    if body.len() > MAX_ATTACHMENT_SIZE_BYTES {
+        return Err(ApiError::Validation(format!(
+            "Attachment exceeds the {} MB size limit",
+            MAX_ATTACHMENT_SIZE_BYTES / (1024 * 1024)
+        )));
+    }
+
+    let Some(storage) = state.storage.clone() else {
+        tracing::error!("Attachment upload rejected: storage backend not available");
+        return Err(ApiError::ServiceUnavailable);
+    };
+
+    let content_type = headers
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream")
+        .to_string();
+
+    let attachment_id = Uuid::new_v4();
+    let storage_key = format!(
+        "attachments/{}/{}/{}",
+        ticket_id, attachment_id, query.filename
+    );
+
+    storage
+        .put(&storage_key, &body, &content_type)
+        .await
+        .map_err(|e| {
+            tracing::error!(key = %storage_key, error = %e, "Failed to store attachment");
+            ApiError::Internal
+        })?;
+
+    // Without a scanner there is nothing to wait for - mark clean up front
+    // so downloads are not blocked on a scan that will never run
+    let scanner_enabled = state.virus_scanner.is_enabled();
+    let (initial_status, scan_engine) = if scanner_enabled {
+        ("pending", None)
+    } else {
+        ("clean", Some(state.virus_scanner.name()))
+    };
+
+    let attachment: AttachmentRow = sqlx::query_as(
+        r#"
+        INSERT INTO ticket_attachments (
+            id, ticket_id, uploaded_by, filename, content_type, size_bytes,
+            storage_key, scan_status, scan_engine,
+            scanned_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9,
+                CASE WHEN $8 = 'clean' THEN NOW() END)
+        RETURNING id, ticket_id, uploaded_by, filename, content_type, size_bytes,
+                  scan_status, created_at
+        "#,
+    )
+    .bind(attachment_id)
+    .bind(ticket_id)
+    .bind(user_id)
+    .bind(&query.filename)
+    .bind(&content_type)
+    .bind(body.len() as i64)
+    .bind(&storage_key)
+    .bind(initial_status)
+    .bind(scan_engine)
+    .fetch_one(&state.pool)
+    .await?;
+
+    if scanner_enabled {
+        let state_for_scan = state.clone();
+        let filename = query.filename.clone();
+        tokio::spawn(async move {
+            run_scan(
+                state_for_scan,
+                attachment_id,
+                ticket_id,
+                filename,
+                content_type,
+                body,
+            )
+            .await;
+        });
+    }
+
+    tracing::info!(
+        ticket_id = %ticket_id,
+        attachment_id = %attachment_id,
+        user_id = %user_id,
+        size_bytes = attachment.size_bytes,
+        "Attachment uploaded to support ticket"
+    );
+
+    Ok(Json(attachment.into()))
+}
+
+/// List attachments on a ticket
+pub async fn list_attachments(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(ticket_id): Path<Uuid>,
+) -> ApiResult<Json<AttachmentListResponse>> {
+    verify_ticket_access(&state, &auth_user, ticket_id).await?;
+
+    let attachments: Vec<AttachmentRow> = sqlx::query_as(
+        r#"
+        SELECT id, ticket_id, uploaded_by, filename, content_type, size_bytes,
+               scan_status, created_at
+        FROM ticket_attachments
+        WHERE ticket_id = $1
+        ORDER BY created_at ASC
+        "#,
+    )
+    .bind(ticket_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(AttachmentListResponse {
+        attachments: attachments.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Get a time-limited download URL for an attachment
+///
+/// Blocked until the virus scan verdict is `clean`.
+pub async fn download_attachment(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((ticket_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> ApiResult<Json<AttachmentDownloadResponse>> {
+    verify_ticket_access(&state, &auth_user, ticket_id).await?;
+
+    #[derive(FromRow)]
+    struct DownloadRow {
+        filename: String,
+        storage_key: String,
+        scan_status: String,
+    }
+
+    let row: DownloadRow = sqlx::query_as(
+        r#"
+        SELECT filename, storage_key, scan_status
+        FROM ticket_attachments
+        WHERE id = $1 AND ticket_id = $2
+        "#,
+    )
+    .bind(attachment_id)
+    .bind(ticket_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    match row.scan_status.as_str() {
+        "clean" => {}
+        "pending" | "scanning" => {
+            return Err(ApiError::Conflict(
+                "Attachment is still being scanned for viruses".into(),
+            ));
+        }
+        "infected" => {
+            return Err(ApiError::BadRequest(
+                "Attachment was flagged by virus scanning and cannot be downloaded".into(),
+            ));
+        }
+        _ => {
+            return Err(ApiError::Conflict(
+                "Virus scan did not complete for this attachment; please re-upload".into(),
+            ));
+        }
+    }
+
+    let Some(storage) = state.storage.as_ref() else {
+        return Err(ApiError::ServiceUnavailable);
+    };
+
+    let download_url = storage
+        .signed_get_url(&row.storage_key, DOWNLOAD_URL_EXPIRY_SECS)
+        .await
+        .map_err(|e| {
+            tracing::error!(key = %row.storage_key, error = %e, "Failed to sign attachment URL");
+            ApiError::Internal
+        })?;
+
+    Ok(Json(AttachmentDownloadResponse {
+        filename: row.filename,
+        download_url,
+        expires_in_secs: DOWNLOAD_URL_EXPIRY_SECS,
+    }))
+}
+
+// =============================================================================
+// Asynchronous scanning
+// =============================================================================
+
+/// Run the virus scan for a freshly uploaded attachment and record the verdict
+pub(crate) async fn run_scan(
+    state: AppState,
+    attachment_id: Uuid,
+    ticket_id: Uuid,
+    filename: String,
+    content_type: String,
+    data: Bytes,
+) {
+    let engine = state.virus_scanner.name();
+
+    if let Err(e) = sqlx::query(
+        "UPDATE ticket_attachments SET scan_status = 'scanning', scan_engine = $2 WHERE id = $1",
+    )
+    .bind(attachment_id)
+    .bind(engine)
+    .execute(&state.pool)
+    .await
+    {
+        tracing::error!(attachment_id = %attachment_id, error = %e, "Failed to mark attachment scanning");
+    }
+
+    let verdict = state.virus_scanner.scan(&data, &content_type).await;
+
+    let (status, detail) = match &verdict {
+        ScanVerdict::Clean => ("clean", None),
+        ScanVerdict::Infected { signature } => ("infected", Some(signature.clone())),
+        ScanVerdict::Failed { reason } => ("error", Some(reason.clone())),
+    };
+
+    if let Err(e) = sqlx::query(
+        r#"
+        UPDATE ticket_attachments
+        SET scan_status = $2, scan_detail = $3, scanned_at = NOW()
+        WHERE id = $1
+        "#,
+    )
+    .bind(attachment_id)
+    .bind(status)
+    .bind(&detail)
+    .execute(&state.pool)
+    .await
+    {
+        tracing::error!(attachment_id = %attachment_id, error = %e, "Failed to record scan verdict");
+        return;
+    }
+
+    match verdict {
+        ScanVerdict::Clean => {
+            tracing::debug!(
+                attachment_id = %attachment_id,
+                engine = engine,
+                "Attachment scan clean"
+            );
+        }
+        ScanVerdict::Infected { signature } => {
+            tracing::warn!(
+                attachment_id = %attachment_id,
+                ticket_id = %ticket_id,
+                signature = %signature,
+                "Malware detected in ticket attachment"
+            );
+
+            // Resolve the ticket owner for alert attribution
+            let owner: Option<(Option<Uuid>, Option<Uuid>)> = sqlx::query_as(
+                "SELECT user_id, organization_id FROM support_tickets WHERE id = $1",
+            )
+            .bind(ticket_id)
+            .fetch_optional(&state.pool)
+            .await
+            .unwrap_or_default();
+            let (user_id, org_id) = owner.unwrap_or((None, None));
+
+            if let Err(e) = trigger_malware_detected_alert(
+                &state.alert_service,
+                user_id,
+                org_id,
+                attachment_id,
+                &filename,
+                &signature,
+                engine,
+            )
+            .await
+            {
+                tracing::error!(attachment_id = %attachment_id, error = %e, "Failed to trigger malware alert");
+            }
+        }
+        ScanVerdict::Failed { reason } => {
+            tracing::error!(
+                attachment_id = %attachment_id,
+                reason = %reason,
+                "Attachment scan failed"
+            );
+        }
+    }
+}
@@ -481,3 +481,349 @@ pub async fn log_audit_event(
 
     Ok(())
 }
+
+// =============================================================================
+// Org-Level Audit Log Export
+// =============================================================================
+
+/// Max page size for the JSON listing; streaming exports page internally
+const MAX_AUDIT_PAGE_SIZE: i64 = 200;
+
+/// Batch size used when streaming CSV/JSONL exports
+const AUDIT_EXPORT_BATCH_SIZE: i64 = 500;
+
+/// Query parameters for the org audit log listing/export
+#[derive(Debug, Deserialize)]
+pub struct OrgAuditLogQuery {
+    /// Restrict to one log: "auth" or "admin" (default: both)
+    pub source: Option<String>,
+    /// Exact event type (auth events) or action (admin events)
+    pub event_type: Option<String>,
+    /// info, warning or critical
+    pub severity: Option<String>,
+    /// RFC3339 inclusive lower bound on created_at
+    pub from: Option<String>,
+    /// RFC3339 inclusive upper bound on created_at
+    pub to: Option<String>,
+    /// Opaque cursor from a previous page's next_cursor
+    pub cursor: Option<String>,
+    /// Page size (default 50, max 200); ignored for streaming exports
+    pub limit: Option<i64>,
+    /// "json" (default, paginated), "csv" or "jsonl" (streamed in full)
+    pub format: Option<String>,
+}
+
+/// One entry in the unified org audit trail
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct OrgAuditLogEntry {
+    pub id: Uuid,
+    /// Which log the entry came from: "auth" or "admin"
+    pub source: String,
+    /// Auth event type, or the admin action name
+    pub event_type: String,
+    pub severity: String,
+    /// Actor email (auth) or acting admin's email (admin)
+    pub actor: String,
+    /// "target_type:target_id" for admin actions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<String>,
+    pub details: Option<serde_json::Value>,
+    #[serde(serialize_with = "serialize_rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+fn serialize_rfc3339<S: serde::Serializer>(
+    dt: &OffsetDateTime,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let formatted = dt
+        .format(&time::format_description::well_known::Rfc3339)
+        .map_err(serde::ser::Error::custom)?;
+    serializer.serialize_str(&formatted)
+}
+
+/// One page of the org audit trail
+#[derive(Debug, Serialize)]
+pub struct OrgAuditLogPage {
+    pub entries: Vec<OrgAuditLogEntry>,
+    /// Pass back as `cursor` to fetch the next page; absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Keyset cursor over (created_at, id), encoded as "{unix_nanos}:{uuid}"
+fn encode_cursor(entry: &OrgAuditLogEntry) -> String {
+    format!("{}:{}", entry.created_at.unix_timestamp_nanos(), entry.id)
+}
+
+fn decode_cursor(cursor: &str) -> Result<(OffsetDateTime, Uuid), ApiError> {
+    let invalid = || ApiError::Validation("Invalid cursor".to_string());
+    let (nanos, id) = cursor.split_once(':').ok_or_else(invalid)?;
+    let nanos: i128 = nanos.parse().map_err(|_| invalid())?;
+    let created_at = OffsetDateTime::from_unix_timestamp_nanos(nanos).map_err(|_| invalid())?;
+    let id = Uuid::parse_str(id).map_err(|_| invalid())?;
+    Ok((created_at, id))
+}
+
+/// Resolved filters for one page fetch
+struct OrgAuditLogFilter {
+    source: Option<String>,
+    event_type: Option<String>,
+    severity: Option<String>,
+    from: Option<OffsetDateTime>,
+    to: Option<OffsetDateTime>,
+}
+
+/// Fetch one keyset page of the unified org audit trail, newest first
+///
+/// Auth events are scoped through org membership; admin events are included
+/// when they target the org (or one of its members) or were performed by a
+/// member. The UNION keeps both logs behind one filter/cursor surface.
+async fn fetch_audit_page(
+    pool: &sqlx::PgPool,
+    org_id: Uuid,
+    filter: &OrgAuditLogFilter,
+    cursor: Option<(OffsetDateTime, Uuid)>,
+    limit: i64,
+) -> Result<Vec<OrgAuditLogEntry>, ApiError> {
+    let (cursor_ts, cursor_id) = match cursor {
+        Some((ts, id)) => (Some(ts), Some(id)),
+        None => (None, None),
+    };
+
+    let entries: Vec<OrgAuditLogEntry> = sqlx::query_as(
+        r#"
+        SELECT id, source, event_type, severity, actor, target, ip_address, details, created_at
+        FROM (
+            SELECT a.id, 'auth' AS source, a.event_type, a.severity, a.email AS actor,
+                   NULL::text AS target, a.ip_address, a.metadata AS details, a.created_at
+            FROM auth_audit_log a
+            WHERE a.user_id IN (SELECT user_id FROM organization_members WHERE org_id = $1)
+            UNION ALL
+            SELECT l.id, 'admin' AS source, l.action AS event_type,
+                   COALESCE(l.severity, 'info') AS severity,
+                   COALESCE(u.email, l.admin_user_id::text) AS actor,
+                   l.target_type || COALESCE(':' || l.target_id::text, '') AS target,
+                   l.ip_address, l.details, l.created_at
+            FROM admin_audit_log l
+            LEFT JOIN users u ON u.id = l.admin_user_id
+            WHERE l.target_id = $1
+               OR l.target_id IN (SELECT user_id FROM organization_members WHERE org_id = $1)
+               OR l.admin_user_id IN (SELECT user_id FROM organization_members WHERE org_id = $1)
+        ) entries
+        WHERE ($2::text IS NULL OR source = $2)
+          AND ($3::text IS NULL OR event_type = $3)
+          AND ($4::text IS NULL OR severity = $4)
+          AND ($5::timestamptz IS NULL OR created_at >= $5)
+          AND ($6::timestamptz IS NULL OR created_at <= $6)
+          AND ($7::timestamptz IS NULL OR (created_at, id) < ($7, $8))
+        ORDER BY created_at DESC, id DESC
+        LIMIT $9
+        "#,
+    )
+    .bind(org_id)
+    .bind(&filter.source)
+    .bind(&filter.event_type)
+    .bind(&filter.severity)
+    .bind(filter.from)
+    .bind(filter.to)
+    .bind(cursor_ts)
+    .bind(cursor_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(entries)
+}
+
+/// CSV-escape a field (quote when it contains separators or quotes)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn audit_csv_line(entry: &OrgAuditLogEntry) -> String {
+    let created_at = entry
+        .created_at
+        .format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_default();
+    let details = entry
+        .details
+        .as_ref()
+        .map(|d| d.to_string())
+        .unwrap_or_default();
+    format!(
+        "{},{},{},{},{},{},{},{},{}\n",
+        entry.id,
+        entry.source,
+        csv_field(&entry.event_type),
+        entry.severity,
+        csv_field(&entry.actor),
+        csv_field(entry.target.as_deref().unwrap_or("")),
+        csv_field(entry.ip_address.as_deref().unwrap_or("")),
+        csv_field(&details),
+        created_at
+    )
+}
+
+/// List or export the org's audit trail
+///
+/// `GET /api/v1/audit/logs` - org owners/admins only. The default JSON
+/// response is cursor-paginated; `?format=csv` or `?format=jsonl` streams
+/// the full filtered history instead so compliance exports aren't capped
+/// by the page size.
+pub async fn list_org_audit_logs(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    axum::extract::Query(query): axum::extract::Query<OrgAuditLogQuery>,
+) -> ApiResult<axum::response::Response> {
+    use axum::response::IntoResponse;
+
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    if let Some(source) = query.source.as_deref() {
+        if !["auth", "admin"].contains(&source) {
+            return Err(ApiError::Validation(
+                "source must be 'auth' or 'admin'".to_string(),
+            ));
+        }
+    }
+    if let Some(severity) = query.severity.as_deref() {
+        if !["info", "warning", "critical"].contains(&severity) {
+            return Err(ApiError::Validation(
+                "severity must be 'info', 'warning' or 'critical'".to_string(),
+            ));
+        }
+    }
+
+    let parse_bound = |value: &Option<String>,
+                       name: &str|
+     -> Result<Option<OffsetDateTime>, ApiError> {
+        value
+            .as_deref()
+            .map(|s| {
+                OffsetDateTime::parse(s, &time::format_description::well_known::Rfc3339).map_err(
+                    |_| ApiError::Validation(format!("{} must be an RFC3339 timestamp", name)),
+                )
+            })
+            .transpose()
+    };
+
+    let filter = OrgAuditLogFilter {
+        source: query.source.clone(),
+        event_type: query.event_type.clone(),
+        severity: query.severity.clone(),
+        from: parse_bound(&query.from, "from")?,
+        to: parse_bound(&query.to, "to")?,
+    };
+
+    let format = query.format.as_deref().unwrap_or("json");
+    match format {
+        "json" => {
+            let limit = query.limit.unwrap_or(50).clamp(1, MAX_AUDIT_PAGE_SIZE);
+            let cursor = query.cursor.as_deref().map(decode_cursor).transpose()?;
+
+            // Fetch one extra row to detect whether another page exists
+            let mut entries =
+                fetch_audit_page(&state.pool, org_id, &filter, cursor, limit + 1).await?;
+            let next_cursor = if entries.len() as i64 > limit {
+                entries.truncate(limit as usize);
+                entries.last().map(encode_cursor)
+            } else {
+                None
+            };
+
+            Ok(Json(OrgAuditLogPage {
+                entries,
+                next_cursor,
+            })
+            .into_response())
+        }
+        "csv" | "jsonl" => {
+            use axum::body::Body;
+            use tokio_stream::wrappers::ReceiverStream;
+
+            let (tx, rx) =
+                tokio::sync::mpsc::channel::<Result<String, std::convert::Infallible>>(16);
+            let pool = state.pool.clone();
+            let as_csv = format == "csv";
+
+            tokio::spawn(async move {
+                if as_csv {
+                    let header =
+                        "id,source,event_type,severity,actor,target,ip_address,details,created_at\n";
+                    if tx.send(Ok(header.to_string())).await.is_err() {
+                        return;
+                    }
+                }
+
+                let mut cursor: Option<(OffsetDateTime, Uuid)> = None;
+                loop {
+                    let entries = match fetch_audit_page(
+                        &pool,
+                        org_id,
+                        &filter,
+                        cursor,
+                        AUDIT_EXPORT_BATCH_SIZE,
+                    )
+                    .await
+                    {
+                        Ok(entries) => entries,
+                        Err(e) => {
+                            tracing::error!(org_id = %org_id, error = %e, "Audit export query failed");
+                            return;
+                        }
+                    };
+
+                    let done = (entries.len() as i64) < AUDIT_EXPORT_BATCH_SIZE;
+                    cursor = entries.last().map(|e| (e.created_at, e.id));
+
+                    for entry in &entries {
+                        let line = if as_csv {
+                            audit_csv_line(entry)
+                        } else {
+                            match serde_json::to_string(entry) {
+                                Ok(json) => format!("{}\n", json),
+                                Err(_) => continue,
+                            }
+                        };
+                        if tx.send(Ok(line)).await.is_err() {
+                            return;
+                        }
+                    }
+
+                    if done {
+                        return;
+                    }
+                }
+            });
+
+            let (content_type, filename) = if as_csv {
+                ("text/csv; charset=utf-8", "audit-logs.csv")
+            } else {
+                ("application/x-ndjson", "audit-logs.jsonl")
+            };
+
+            axum::response::Response::builder()
+                .header("content-type", content_type)
+                .header(
+                    "content-disposition",
+                    format!("attachment; filename=\"{}\"", filename),
+                )
+                .body(Body::from_stream(ReceiverStream::new(rx)))
+                .map_err(|_| ApiError::Internal)
+        }
+        other => Err(ApiError::Validation(format!(
+            "Unknown format '{}'. Supported: json, csv, jsonl",
+            other
+        ))),
+    }
+}
@@ -14,7 +14,7 @@ use crate::{
     audit_constants::{auth_event, event_type, severity},
     auth::{
         generate_impossible_hash, hash_password, sessions, totp, validate_password_strength,
-        verify_password, AuthUser, TokenManager, VerificationTokenType,
+        verify_password, webauthn, AuthUser, TokenManager, VerificationTokenType,
     },
     error::{ApiError, ApiResult},
     state::AppState,
@@ -99,6 +99,8 @@ pub struct TwoFactorRequiredResponse {
     pub temp_token: String,
     /// User ID (for frontend reference)
     pub user_id: Uuid,
+    /// Available second factors ("totp", "webauthn")
+    pub methods: Vec<String>,
 }
 
 /// Unified login response - either full auth or 2FA required
@@ -685,14 +687,18 @@ async fn login_inner(
 
     tracing::info!(user_id = %user.id, "login: Password verified successfully");
 
-    // Check if user has 2FA enabled
+    // Check if user has 2FA enabled (TOTP and/or registered passkeys)
     let has_2fa: Option<(bool,)> =
         sqlx::query_as("SELECT is_enabled FROM user_2fa WHERE user_id = $1")
             .bind(user.id)
             .fetch_optional(&state.pool)
             .await?;
 
-    let two_fa_enabled = has_2fa.map(|r| r.0).unwrap_or(false);
+    let totp_enabled = has_2fa.map(|r| r.0).unwrap_or(false);
+    let has_passkeys = webauthn::has_passkeys(&state.pool, user.id)
+        .await
+        .unwrap_or(false);
+    let two_fa_enabled = totp_enabled || has_passkeys;
 
     // Check if device is trusted (skip 2FA if so)
     let device_trusted = if two_fa_enabled {
@@ -736,6 +742,7 @@ async fn login_inner(
                 requires_2fa: true,
                 temp_token,
                 user_id: user.id,
+                methods: two_fa_methods(totp_enabled, has_passkeys),
             },
         )));
     }
@@ -813,6 +820,40 @@ async fn login_inner(
     })))
 }
 
+/// Second factors available to a user, for the 2FA-required login response
+fn two_fa_methods(totp_enabled: bool, has_passkeys: bool) -> Vec<String> {
+    let mut methods = Vec::new();
+    if totp_enabled {
+        methods.push("totp".to_string());
+    }
+    if has_passkeys {
+        methods.push("webauthn".to_string());
+    }
+    methods
+}
+
+/// Look up a pending 2FA login token, cleaning it up when expired
+async fn lookup_login_token(state: &AppState, token_hash: &str) -> ApiResult<Uuid> {
+    let row: Option<(Uuid, OffsetDateTime)> = sqlx::query_as(
+        "SELECT user_id, expires_at FROM user_2fa_login_tokens WHERE token_hash = $1",
+    )
+    .bind(token_hash)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    let (user_id, expires_at) = row.ok_or(ApiError::InvalidToken)?;
+
+    if expires_at < OffsetDateTime::now_utc() {
+        sqlx::query("DELETE FROM user_2fa_login_tokens WHERE token_hash = $1")
+            .bind(token_hash)
+            .execute(&state.pool)
+            .await?;
+        return Err(ApiError::InvalidToken);
+    }
+
+    Ok(user_id)
+}
+
 /// Complete login with 2FA code
 /// Called after initial login returns 2FA required
 pub async fn login_2fa(
@@ -1347,6 +1388,194 @@ pub async fn login_2fa(
     }))
 }
 
+/// Request to begin passkey verification during login
+#[derive(Debug, Deserialize)]
+pub struct LoginWebauthnBeginRequest {
+    /// Temporary token from initial login
+    pub temp_token: String,
+}
+
+/// Request to complete login with a passkey assertion
+#[derive(Debug, Deserialize)]
+pub struct LoginWebauthnCompleteRequest {
+    /// Temporary token from initial login
+    pub temp_token: String,
+    /// Assertion response from `navigator.credentials.get`
+    pub credential: webauthn_rs::prelude::PublicKeyCredential,
+    /// If true, create a device token to skip 2FA on future logins (30 days)
+    #[serde(default)]
+    pub remember_device: bool,
+}
+
+/// Begin passkey verification for a pending 2FA login
+///
+/// Called with the temp token from the initial login response; returns the
+/// WebAuthn challenge for `navigator.credentials.get`.
+pub async fn login_2fa_webauthn_begin(
+    State(state): State<AppState>,
+    Json(req): Json<LoginWebauthnBeginRequest>,
+) -> ApiResult<Json<webauthn_rs::prelude::RequestChallengeResponse>> {
+    let token_hash = totp::hash_token(&req.temp_token);
+    let user_id = lookup_login_token(&state, &token_hash).await?;
+
+    let challenge = webauthn::begin_authentication(&state.pool, &state.config, user_id).await?;
+
+    Ok(Json(challenge))
+}
+
+/// Complete login with a passkey assertion
+///
+/// The passkey counterpart of [`login_2fa`]: verifies the assertion against
+/// the user's registered credentials, consumes the temp token, and issues
+/// the normal token pair.
+pub async fn login_2fa_webauthn_complete(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<LoginWebauthnCompleteRequest>,
+) -> ApiResult<Json<AuthResponse>> {
+    let (ip_address, user_agent) = extract_auth_audit_context(&headers);
+    let token_hash = totp::hash_token(&req.temp_token);
+
+    // SOC 2 CC6.1: Rate limit 2FA attempts to prevent bypass attacks
+    match state.rate_limiter.check_2fa_attempts(&token_hash).await {
+        Ok(result) if !result.allowed => {
+            let retry_after = result.retry_after_seconds.unwrap_or(60);
+            return Err(ApiError::TooManyRequests(format!(
+                "Too many 2FA attempts. Please try again in {} seconds.",
+                retry_after
+            )));
+        }
+        Err(e) => {
+            tracing::error!(error = ?e, "login_2fa_webauthn: Rate limit check failed, allowing request");
+        }
+        _ => {}
+    }
+
+    let user_id = lookup_login_token(&state, &token_hash).await?;
+
+    // Verify the assertion; failures count like a wrong TOTP code
+    if let Err(e) =
+        webauthn::finish_authentication(&state.pool, &state.config, user_id, &req.credential).await
+    {
+        let _ = log_auth_event(
+            &state.pool,
+            Some(user_id),
+            auth_event::TWO_FA_FAILED,
+            None,
+            Some(serde_json::json!({"method": "webauthn"})),
+            event_type::AUTHENTICATION,
+            severity::WARNING,
+            ip_address.clone(),
+            user_agent.clone(),
+            false,
+            None,
+            Some("webauthn".to_string()),
+        )
+        .await;
+        return Err(e);
+    }
+
+    // Consume the temp login token
+    sqlx::query("DELETE FROM user_2fa_login_tokens WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&state.pool)
+        .await?;
+
+    // Update last login
+    sqlx::query("UPDATE users SET last_login_at = NOW() WHERE id = $1")
+        .bind(user_id)
+        .execute(&state.pool)
+        .await?;
+
+    // Fetch user profile for token generation. Passkeys are registered by
+    // authenticated users, so a users row always exists by this point.
+    let user: UserProfileRow = sqlx::query_as(
+        r#"
+        SELECT u.id, u.org_id, u.email, u.role, o.name as org_name,
+               u.is_admin, u.platform_role::text as platform_role
+        FROM users u
+        JOIN organizations o ON o.id = u.org_id
+        WHERE u.id = $1
+        "#,
+    )
+    .bind(user_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::BadRequest("User not found".to_string()))?;
+
+    // Generate tokens
+    let (access_token, access_jti, refresh_token, refresh_jti) = state
+        .jwt_manager
+        .generate_token_pair(user.id, user.org_id, &user.role, &user.email)
+        .map_err(|_| ApiError::Internal)?;
+
+    // Save session for revocation support
+    let access_expires_at =
+        OffsetDateTime::now_utc() + Duration::hours(state.config.jwt_expiry_hours);
+    let refresh_expires_at = OffsetDateTime::now_utc() + Duration::days(30);
+    sessions::save_session(
+        &state.pool,
+        user.id,
+        &access_jti,
+        access_expires_at,
+        &refresh_jti,
+        refresh_expires_at,
+        ip_address.as_deref(),
+        user_agent.as_deref(),
+    )
+    .await?;
+
+    // Log successful 2FA verification
+    log_auth_event(
+        &state.pool,
+        Some(user_id),
+        auth_event::TWO_FA_VERIFIED,
+        Some(user.email.clone()),
+        Some(serde_json::json!({"method": "webauthn", "remember_device": req.remember_device})),
+        event_type::AUTHENTICATION,
+        severity::INFO,
+        ip_address,
+        user_agent,
+        true,
+        None,
+        Some("webauthn".to_string()),
+    )
+    .await?;
+
+    // Create device trust token if requested
+    let device_token = if req.remember_device {
+        let user_agent = headers.get("user-agent").and_then(|h| h.to_str().ok());
+        let client_ip = extract_client_ip(&headers);
+
+        match trust_device(&state.pool, user_id, user_agent, client_ip.as_deref()).await {
+            Ok(token) => Some(token),
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to create device trust token");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    Ok(Json(AuthResponse {
+        access_token,
+        refresh_token,
+        token_type: "Bearer".to_string(),
+        expires_in: state.jwt_manager.access_token_expiry_seconds(),
+        user: UserResponse {
+            id: user.id,
+            email: user.email,
+            role: user.role,
+            org_id: user.org_id,
+            org_name: user.org_name,
+            is_admin: user.is_admin,
+            platform_role: user.platform_role,
+        },
+        device_token,
+    }))
+}
+
 /// Refresh access token
 pub async fn refresh(
     State(state): State<AppState>,
@@ -1616,7 +1845,12 @@ pub enum Check2FAResponse {
     Ok,
     /// 2FA verification required
     #[serde(rename = "2fa_required")]
-    TwoFactorRequired { temp_token: String, user_id: Uuid },
+    TwoFactorRequired {
+        temp_token: String,
+        user_id: Uuid,
+        /// Available second factors ("totp", "webauthn")
+        methods: Vec<String>,
+    },
     /// 2FA already pending - reserved for potential future use
     /// Currently we always generate new tokens, but frontend handles this case as fallback
     #[allow(dead_code)]
@@ -1703,7 +1937,11 @@ pub async fn check_2fa_required(
             .fetch_optional(&state.pool)
             .await?;
 
-    let two_fa_enabled = has_2fa.map(|r| r.0).unwrap_or(false);
+    let totp_enabled = has_2fa.map(|r| r.0).unwrap_or(false);
+    let has_passkeys = webauthn::has_passkeys(&state.pool, resolved_user_id)
+        .await
+        .unwrap_or(false);
+    let two_fa_enabled = totp_enabled || has_passkeys;
 
     if two_fa_enabled {
         // Always generate a new temporary login token for 2FA verification
@@ -1766,6 +2004,7 @@ pub async fn check_2fa_required(
         return Ok(Json(Check2FAResponse::TwoFactorRequired {
             temp_token,
             user_id: resolved_user_id,
+            methods: two_fa_methods(totp_enabled, has_passkeys),
         }));
     }
 
@@ -1929,6 +2168,16 @@ pub async fn logout(
         .fetch_optional(&state.pool)
         .await?;
 
+    // Drop cached token verifications so the logged-out token stops
+    // resolving before its TTL runs out
+    let invalidated = state
+        .token_cache
+        .invalidate_user(&user_id.to_string(), email.as_deref())
+        .await;
+    if invalidated > 0 {
+        tracing::debug!(user_id = %user_id, invalidated, "Token cache invalidated on logout");
+    }
+
     // Log logout event
     log_auth_event(
         &state.pool,
@@ -2705,6 +2954,12 @@ pub async fn logout_all(
         .fetch_optional(&state.pool)
         .await?;
 
+    // Cached verifications must not outlive the revoked sessions
+    state
+        .token_cache
+        .invalidate_user(&user_id.to_string(), email.as_deref())
+        .await;
+
     log_auth_event(
         &state.pool,
         Some(user_id),
@@ -0,0 +1,233 @@
+//! Public uptime/latency badges for MCPs
+//!
+//! Embeddable shields-style SVG badges rendered from the last 30 days of
+//! `mcp_test_history`, for dropping into READMEs and status pages:
+//!
+//! ```text
+//! GET /api/v1/public/badges/:org_slug/:mcp_id/uptime.svg
+//! GET /api/v1/public/badges/:org_slug/:mcp_id/latency.svg
+//! ```
+//!
+//! No auth - badges are only served when the org has opted in by setting
+//! `public_badges_enabled: true` in its settings (via `PATCH /org`).
+//! Orgs that haven't opted in get a 404 either way, so the endpoints
+//! don't confirm whether an org or MCP exists.
+
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use uuid::Uuid;
+
+use crate::{error::ApiError, state::AppState};
+
+/// Browsers and proxies may cache a badge this long (seconds)
+const BADGE_CACHE_SECONDS: u32 = 300;
+
+/// History window the badge summarizes
+const BADGE_WINDOW_DAYS: i32 = 30;
+
+// Shields.io flat-badge palette
+const COLOR_GREEN: &str = "#4c1";
+const COLOR_YELLOW: &str = "#dfb317";
+const COLOR_RED: &str = "#e05d44";
+const COLOR_GRAY: &str = "#9f9f9f";
+
+/// Resolve the badge target, enforcing the org opt-in
+///
+/// Returns the MCP's UUID only when the org slug matches, the org has
+/// badges enabled, and the MCP belongs to it; every failure mode is the
+/// same NotFound.
+async fn resolve_badge_mcp(
+    state: &AppState,
+    org_slug: &str,
+    mcp_id: Uuid,
+) -> Result<Uuid, ApiError> {
+    let enabled: Option<(bool,)> = sqlx::query_as(
+        r#"
+        SELECT COALESCE((o.settings->>'public_badges_enabled')::boolean, false)
+        FROM organizations o
+        JOIN mcp_instances m ON m.org_id = o.id
+        WHERE o.slug = $1 AND m.id = $2
+        "#,
+    )
+    .bind(org_slug)
+    .bind(mcp_id)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    match enabled {
+        Some((true,)) => Ok(mcp_id),
+        _ => Err(ApiError::NotFound),
+    }
+}
+
+/// 30-day uptime badge
+///
+/// Uptime is the share of recorded health checks that came back healthy.
+pub async fn uptime_badge(
+    State(state): State<AppState>,
+    Path((org_slug, mcp_id)): Path<(String, Uuid)>,
+) -> Result<Response, ApiError> {
+    let mcp_id = resolve_badge_mcp(&state, &org_slug, mcp_id).await?;
+
+    let (total, healthy): (i64, i64) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*),
+               COUNT(*) FILTER (WHERE health_status = 'healthy')
+        FROM mcp_test_history
+        WHERE mcp_id = $1 AND tested_at > NOW() - make_interval(days => $2)
+        "#,
+    )
+    .bind(mcp_id)
+    .bind(BADGE_WINDOW_DAYS)
+    .fetch_one(&state.pool)
+    .await?;
+
+    let (value, color) = if total == 0 {
+        ("no data".to_string(), COLOR_GRAY)
+    } else {
+        let uptime = healthy as f64 / total as f64 * 100.0;
+        let color = if uptime >= 99.0 {
+            COLOR_GREEN
+        } else if uptime >= 95.0 {
+            COLOR_YELLOW
+        } else {
+            COLOR_RED
+        };
+        (format!("{:.2}%", uptime), color)
+    };
+
+    Ok(badge_response(render_badge("uptime 30d", &value, color)))
+}
+
+/// 30-day median latency badge
+pub async fn latency_badge(
+    State(state): State<AppState>,
+    Path((org_slug, mcp_id)): Path<(String, Uuid)>,
+) -> Result<Response, ApiError> {
+    let mcp_id = resolve_badge_mcp(&state, &org_slug, mcp_id).await?;
+
+    let median: Option<f64> = sqlx::query_scalar(
+        r#"
+        SELECT percentile_cont(0.5) WITHIN GROUP (ORDER BY latency_ms)
+        FROM mcp_test_history
+        WHERE mcp_id = $1
+          AND latency_ms IS NOT NULL
+          AND tested_at > NOW() - make_interval(days => $2)
+        "#,
+    )
+    .bind(mcp_id)
+    .bind(BADGE_WINDOW_DAYS)
+    .fetch_one(&state.pool)
+    .await?;
+
+    let (value, color) = match median {
+        None => ("no data".to_string(), COLOR_GRAY),
+        Some(ms) => {
+            let color = if ms < 500.0 {
+                COLOR_GREEN
+            } else if ms < 2000.0 {
+                COLOR_YELLOW
+            } else {
+                COLOR_RED
+            };
+            (format!("{} ms", ms.round() as i64), color)
+        }
+    };
+
+    Ok(badge_response(render_badge("latency p50", &value, color)))
+}
+
+/// Wrap rendered SVG with the content-type and caching headers
+fn badge_response(svg: String) -> Response {
+    (
+        StatusCode::OK,
+        [
+            (
+                header::CONTENT_TYPE,
+                "image/svg+xml; charset=utf-8".to_string(),
+            ),
+            (
+                header::CACHE_CONTROL,
+                format!("public, max-age={}", BADGE_CACHE_SECONDS),
+            ),
+        ],
+        svg,
+    )
+        .into_response()
+}
+
+/// Approximate rendered text width in the 11px Verdana shields uses
+///
+/// A flat per-character estimate keeps the badge self-contained (no font
+/// metrics tables); README rendering tolerates a few pixels of slack.
+fn text_width(text: &str) -> u32 {
+    (text.chars().count() as u32) * 7 + 10
+}
+
+/// Render a shields.io-style flat SVG badge
+fn render_badge(label: &str, value: &str, color: &str) -> String {
+    let label = xml_escape(label);
+    let value = xml_escape(value);
+    let label_width = text_width(&label);
+    let value_width = text_width(&value);
+    let total_width = label_width + value_width;
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{total_width}" height="20" role="img" aria-label="{label}: {value}">
+  <linearGradient id="s" x2="0" y2="100%">
+    <stop offset="0" stop-color="#bbb" stop-opacity=".1"/>
+    <stop offset="1" stop-opacity=".1"/>
+  </linearGradient>
+  <clipPath id="r"><rect width="{total_width}" height="20" rx="3" fill="#fff"/></clipPath>
+  <g clip-path="url(#r)">
+    <rect width="{label_width}" height="20" fill="#555"/>
+    <rect x="{label_width}" width="{value_width}" height="20" fill="{color}"/>
+    <rect width="{total_width}" height="20" fill="url(#s)"/>
+  </g>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+    <text x="{label_mid}" y="14">{label}</text>
+    <text x="{value_mid}" y="14">{value}</text>
+  </g>
+</svg>"##,
+        total_width = total_width,
+        label_width = label_width,
+        value_width = value_width,
+        color = color,
+        label = label,
+        value = value,
+        label_mid = label_width / 2,
+        value_mid = label_width + value_width / 2,
+    )
+}
+
+/// Escape text interpolated into SVG attributes/content
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_badge_contains_label_and_value() {
+        let svg = render_badge("uptime 30d", "99.98%", COLOR_GREEN);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(">uptime 30d</text>"));
+        assert!(svg.contains(">99.98%</text>"));
+        assert!(svg.contains(COLOR_GREEN));
+    }
+
+    #[test]
+    fn test_render_badge_escapes_markup() {
+        let svg = render_badge("<script>", "a\"b", COLOR_GRAY);
+        assert!(!svg.contains("<script>"));
+        assert!(svg.contains("&lt;script&gt;"));
+    }
+}
@@ -1,7 +1,7 @@
 //! Billing routes for Stripe integration
 
 use axum::{
-    extract::{Extension, Query, State},
+    extract::{Extension, Path, Query, State},
     http::{HeaderMap, StatusCode},
     Json,
 };
@@ -1819,6 +1819,12 @@ pub struct InvoiceDetailResponse {
     pub period_start: Option<String>,
     pub period_end: Option<String>,
     pub billing_reason: Option<String>,
+    /// Purchase order number from the org's invoice settings
+    pub po_number: Option<String>,
+    /// Billing address from the org's invoice settings
+    pub billing_address: Option<String>,
+    /// Memo text from the org's invoice settings
+    pub memo: Option<String>,
 }
 
 /// Invoice line item for API response
@@ -2205,6 +2211,16 @@ pub async fn get_invoice_detail(
         })
         .collect();
 
+    // Org-level invoice settings (PO number, billing address, memo)
+    let invoice_settings: (Option<String>, Option<String>, Option<String>) = sqlx::query_as(
+        "SELECT invoice_po_number, invoice_billing_address, invoice_memo FROM organizations WHERE id = $1",
+    )
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(|e| ApiError::Database(format!("Failed to fetch invoice settings: {}", e)))?
+    .unwrap_or_default();
+
     let payment_attempt_responses: Vec<PaymentAttemptResponse> = payment_attempts
         .into_iter()
         .map(|row| PaymentAttemptResponse {
@@ -2272,6 +2288,9 @@ pub async fn get_invoice_detail(
                 .unwrap_or_default()
         }),
         billing_reason: invoice.billing_reason,
+        po_number: invoice_settings.0,
+        billing_address: invoice_settings.1,
+        memo: invoice_settings.2,
     }))
 }
 
@@ -2778,3 +2797,758 @@ pub async fn get_grace_period_status(
         overdue_amount_cents: overdue_amount,
     }))
 }
+
+// =============================================================================
+// Billing Contacts
+// =============================================================================
+
+/// Maximum billing contacts per organization
+const MAX_BILLING_CONTACTS: i64 = 10;
+
+/// Billing contact info
+#[derive(Debug, Serialize)]
+pub struct BillingContact {
+    pub id: Uuid,
+    pub email: String,
+    pub name: Option<String>,
+    pub is_primary: bool,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+/// Request to add a billing contact
+#[derive(Debug, Deserialize)]
+pub struct CreateBillingContactRequest {
+    pub email: String,
+    pub name: Option<String>,
+    /// Primary contact's email becomes the Stripe customer email
+    #[serde(default)]
+    pub is_primary: bool,
+}
+
+/// Request to update a billing contact
+#[derive(Debug, Deserialize)]
+pub struct UpdateBillingContactRequest {
+    pub name: Option<String>,
+    pub is_primary: Option<bool>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct BillingContactRow {
+    id: Uuid,
+    email: String,
+    name: Option<String>,
+    is_primary: bool,
+    created_at: OffsetDateTime,
+}
+
+impl From<BillingContactRow> for BillingContact {
+    fn from(r: BillingContactRow) -> Self {
+        Self {
+            id: r.id,
+            email: r.email,
+            name: r.name,
+            is_primary: r.is_primary,
+            created_at: r.created_at,
+        }
+    }
+}
+
+/// Only org owners/admins may manage billing contacts
+fn require_org_billing_admin(auth_user: &AuthUser) -> Result<Uuid, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+    Ok(org_id)
+}
+
+/// Validates an email address format
+fn is_valid_email(email: &str) -> bool {
+    let parts: Vec<&str> = email.split('@').collect();
+    if parts.len() != 2 {
+        return false;
+    }
+    let (local, domain) = (parts[0], parts[1]);
+    !local.is_empty() && !domain.is_empty() && domain.contains('.')
+}
+
+/// Push the current invoice email to the Stripe customer (best-effort).
+///
+/// Uses the primary billing contact's email, falling back to the org owner
+/// when no primary contact exists. Orgs without a Stripe customer yet are
+/// skipped silently - the email is set when the customer is created.
+async fn sync_stripe_invoice_email(state: &AppState, org_id: Uuid) {
+    let Some(billing) = state.billing.as_ref() else {
+        return;
+    };
+
+    let email: Option<(String,)> = match sqlx::query_as(
+        r#"
+        SELECT email FROM billing_contacts WHERE org_id = $1 AND is_primary
+        UNION ALL
+        SELECT email FROM users WHERE org_id = $1 AND role = 'owner'
+        LIMIT 1
+        "#,
+    )
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await
+    {
+        Ok(row) => row,
+        Err(e) => {
+            tracing::error!(org_id = %org_id, error = %e, "Failed to resolve invoice email");
+            return;
+        }
+    };
+
+    let Some((email,)) = email else {
+        return;
+    };
+
+    match billing
+        .customer
+        .update_customer(org_id, Some(&email), None)
+        .await
+    {
+        Ok(_) => {
+            tracing::info!(org_id = %org_id, "Synced invoice email to Stripe customer");
+        }
+        Err(e) => {
+            // No Stripe customer yet is expected for free-tier orgs
+            tracing::debug!(org_id = %org_id, error = %e, "Skipped Stripe invoice email sync");
+        }
+    }
+}
+
+/// List the organization's billing contacts
+pub async fn list_billing_contacts(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<Vec<BillingContact>>, ApiError> {
+    let org_id = require_org_billing_admin(&auth_user)?;
+
+    let contacts: Vec<BillingContactRow> = sqlx::query_as(
+        r#"
+        SELECT id, email, name, is_primary, created_at
+        FROM billing_contacts
+        WHERE org_id = $1
+        ORDER BY is_primary DESC, created_at ASC
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(contacts.into_iter().map(Into::into).collect()))
+}
+
+/// Add a billing contact (owner/admin only)
+pub async fn create_billing_contact(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<CreateBillingContactRequest>,
+) -> Result<Json<BillingContact>, ApiError> {
+    let org_id = require_org_billing_admin(&auth_user)?;
+
+    let email = req.email.trim().to_lowercase();
+    if !is_valid_email(&email) {
+        return Err(ApiError::Validation("Invalid email address".to_string()));
+    }
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM billing_contacts WHERE org_id = $1")
+        .bind(org_id)
+        .fetch_one(&state.pool)
+        .await?;
+    if count >= MAX_BILLING_CONTACTS {
+        return Err(ApiError::Validation(format!(
+            "An organization can have at most {} billing contacts",
+            MAX_BILLING_CONTACTS
+        )));
+    }
+
+    let mut tx = state.pool.begin().await?;
+
+    if req.is_primary {
+        sqlx::query("UPDATE billing_contacts SET is_primary = false, updated_at = NOW() WHERE org_id = $1 AND is_primary")
+            .bind(org_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    let contact: BillingContactRow = sqlx::query_as(
+        r#"
+        INSERT INTO billing_contacts (org_id, email, name, is_primary, created_by)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, email, name, is_primary, created_at
+        "#,
+    )
+    .bind(org_id)
+    .bind(&email)
+    .bind(&req.name)
+    .bind(req.is_primary)
+    .bind(auth_user.user_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    if contact.is_primary {
+        sync_stripe_invoice_email(&state, org_id).await;
+    }
+
+    tracing::info!(
+        org_id = %org_id,
+        contact_id = %contact.id,
+        is_primary = contact.is_primary,
+        "Billing contact added"
+    );
+
+    Ok(Json(contact.into()))
+}
+
+/// Update a billing contact (owner/admin only)
+pub async fn update_billing_contact(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(contact_id): Path<Uuid>,
+    Json(req): Json<UpdateBillingContactRequest>,
+) -> Result<Json<BillingContact>, ApiError> {
+    let org_id = require_org_billing_admin(&auth_user)?;
+
+    let mut tx = state.pool.begin().await?;
+
+    if req.is_primary == Some(true) {
+        sqlx::query("UPDATE billing_contacts SET is_primary = false, updated_at = NOW() WHERE org_id = $1 AND is_primary")
+            .bind(org_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    let contact: Option<BillingContactRow> = sqlx::query_as(
+        r#"
+        UPDATE billing_contacts
+        SET name = COALESCE($3, name),
+            is_primary = COALESCE($4, is_primary),
+            updated_at = NOW()
+        WHERE id = $1 AND org_id = $2
+        RETURNING id, email, name, is_primary, created_at
+        "#,
+    )
+    .bind(contact_id)
+    .bind(org_id)
+    .bind(&req.name)
+    .bind(req.is_primary)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let contact = contact.ok_or(ApiError::NotFound)?;
+
+    tx.commit().await?;
+
+    if req.is_primary.is_some() {
+        sync_stripe_invoice_email(&state, org_id).await;
+    }
+
+    Ok(Json(contact.into()))
+}
+
+/// Remove a billing contact (owner/admin only)
+pub async fn delete_billing_contact(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(contact_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let org_id = require_org_billing_admin(&auth_user)?;
+
+    let deleted: Option<(bool,)> = sqlx::query_as(
+        "DELETE FROM billing_contacts WHERE id = $1 AND org_id = $2 RETURNING is_primary",
+    )
+    .bind(contact_id)
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    let (was_primary,) = deleted.ok_or(ApiError::NotFound)?;
+
+    // Removing the primary contact points Stripe back at the owner's email
+    if was_primary {
+        sync_stripe_invoice_email(&state, org_id).await;
+    }
+
+    tracing::info!(
+        org_id = %org_id,
+        contact_id = %contact_id,
+        "Billing contact removed"
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// =============================================================================
+// Invoice Settings
+// =============================================================================
+
+/// Stripe invoice custom field values may be up to 30 characters
+const MAX_PO_NUMBER_LEN: usize = 30;
+const MAX_BILLING_ADDRESS_LEN: usize = 500;
+const MAX_INVOICE_MEMO_LEN: usize = 500;
+
+/// Per-org invoice settings
+#[derive(Debug, Serialize)]
+pub struct InvoiceSettingsResponse {
+    pub po_number: Option<String>,
+    pub billing_address: Option<String>,
+    pub memo: Option<String>,
+}
+
+/// Request to update invoice settings; omitted fields are left unchanged,
+/// empty strings clear the value
+#[derive(Debug, Deserialize)]
+pub struct UpdateInvoiceSettingsRequest {
+    pub po_number: Option<String>,
+    pub billing_address: Option<String>,
+    pub memo: Option<String>,
+}
+
+/// Get the organization's invoice settings (owner/admin only)
+pub async fn get_invoice_settings(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<InvoiceSettingsResponse>, ApiError> {
+    let org_id = require_org_billing_admin(&auth_user)?;
+
+    let (po_number, billing_address, memo): (Option<String>, Option<String>, Option<String>) =
+        sqlx::query_as(
+            "SELECT invoice_po_number, invoice_billing_address, invoice_memo FROM organizations WHERE id = $1",
+        )
+        .bind(org_id)
+        .fetch_optional(&state.pool)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(InvoiceSettingsResponse {
+        po_number,
+        billing_address,
+        memo,
+    }))
+}
+
+/// Update the organization's invoice settings (owner/admin only)
+///
+/// Stored locally and pushed to the Stripe customer: the PO number renders
+/// as an invoice custom field, the memo as the invoice footer.
+pub async fn update_invoice_settings(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<UpdateInvoiceSettingsRequest>,
+) -> Result<Json<InvoiceSettingsResponse>, ApiError> {
+    let org_id = require_org_billing_admin(&auth_user)?;
+
+    if req
+        .po_number
+        .as_ref()
+        .is_some_and(|v| v.len() > MAX_PO_NUMBER_LEN)
+    {
+        return Err(ApiError::Validation(format!(
+            "PO number must be at most {} characters",
+            MAX_PO_NUMBER_LEN
+        )));
+    }
+    if req
+        .billing_address
+        .as_ref()
+        .is_some_and(|v| v.len() > MAX_BILLING_ADDRESS_LEN)
+    {
+        return Err(ApiError::Validation(format!(
+            "Billing address must be at most {} characters",
+            MAX_BILLING_ADDRESS_LEN
+        )));
+    }
+    if req
+        .memo
+        .as_ref()
+        .is_some_and(|v| v.len() > MAX_INVOICE_MEMO_LEN)
+    {
+        return Err(ApiError::Validation(format!(
+            "Memo must be at most {} characters",
+            MAX_INVOICE_MEMO_LEN
+        )));
+    }
+
+    // Omitted fields keep their value; empty strings clear it
+    let (po_number, billing_address, memo): (Option<String>, Option<String>, Option<String>) =
+        sqlx::query_as(
+            r#"
+            UPDATE organizations
+            SET invoice_po_number = NULLIF(COALESCE($2, invoice_po_number), ''),
+                invoice_billing_address = NULLIF(COALESCE($3, invoice_billing_address), ''),
+                invoice_memo = NULLIF(COALESCE($4, invoice_memo), ''),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING invoice_po_number, invoice_billing_address, invoice_memo
+            "#,
+        )
+        .bind(org_id)
+        .bind(&req.po_number)
+        .bind(&req.billing_address)
+        .bind(&req.memo)
+        .fetch_optional(&state.pool)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    // Push to Stripe (best-effort - free-tier orgs have no customer yet)
+    if let Some(billing) = state.billing.as_ref() {
+        if let Err(e) = billing
+            .customer
+            .update_invoice_settings(org_id, po_number.as_deref(), memo.as_deref())
+            .await
+        {
+            tracing::debug!(org_id = %org_id, error = %e, "Skipped Stripe invoice settings sync");
+        }
+    }
+
+    tracing::info!(org_id = %org_id, "Invoice settings updated");
+
+    Ok(Json(InvoiceSettingsResponse {
+        po_number,
+        billing_address,
+        memo,
+    }))
+}
+
+// =============================================================================
+// Billing Documents (generated PDF receipts)
+// =============================================================================
+
+/// Signed receipt URLs are valid for 5 minutes
+const DOCUMENT_URL_EXPIRY_SECS: u64 = 300;
+
+/// A generated receipt document
+#[derive(Debug, Serialize)]
+pub struct BillingDocument {
+    pub id: Uuid,
+    pub document_type: String,
+    pub title: String,
+    pub amount_cents: i64,
+    pub currency: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    /// Time-limited signed URL (absent when storage is unavailable)
+    pub download_url: Option<String>,
+    pub download_expires_in_secs: u64,
+}
+
+/// Short reference from a UUID (first segment, matches invoice fallback style)
+fn document_reference(prefix: &str, id: Uuid) -> String {
+    format!(
+        "{}-{}",
+        prefix,
+        id.to_string().split('-').next().unwrap_or("UNKNOWN")
+    )
+}
+
+/// List receipt documents for the org, generating any that are missing
+/// (owner/admin only)
+pub async fn list_billing_documents(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<Vec<BillingDocument>>, ApiError> {
+    let org_id = require_org_billing_admin(&auth_user)?;
+
+    generate_missing_receipts(&state, org_id).await?;
+
+    #[derive(sqlx::FromRow)]
+    struct DocumentRow {
+        id: Uuid,
+        document_type: String,
+        title: String,
+        amount_cents: i64,
+        currency: String,
+        storage_key: String,
+        created_at: OffsetDateTime,
+    }
+
+    let rows: Vec<DocumentRow> = sqlx::query_as(
+        r#"
+        SELECT id, document_type, title, amount_cents, currency, storage_key, created_at
+        FROM billing_documents
+        WHERE org_id = $1
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let mut documents = Vec::with_capacity(rows.len());
+    for row in rows {
+        let download_url = match state.storage.as_ref() {
+            Some(storage) => match storage
+                .signed_get_url(&row.storage_key, DOCUMENT_URL_EXPIRY_SECS)
+                .await
+            {
+                Ok(url) => Some(url),
+                Err(e) => {
+                    tracing::error!(
+                        document_id = %row.id,
+                        error = %e,
+                        "Failed to sign billing document URL"
+                    );
+                    None
+                }
+            },
+            None => None,
+        };
+
+        documents.push(BillingDocument {
+            id: row.id,
+            document_type: row.document_type,
+            title: row.title,
+            amount_cents: row.amount_cents,
+            currency: row.currency,
+            created_at: row.created_at,
+            download_url,
+            download_expires_in_secs: DOCUMENT_URL_EXPIRY_SECS,
+        });
+    }
+
+    Ok(Json(documents))
+}
+
+/// Render and store receipts for completed charges that have no document yet
+async fn generate_missing_receipts(state: &AppState, org_id: Uuid) -> Result<(), ApiError> {
+    let Some(storage) = state.storage.as_ref() else {
+        tracing::debug!(org_id = %org_id, "Storage unavailable, skipping receipt generation");
+        return Ok(());
+    };
+
+    let org_name: Option<(String,)> =
+        sqlx::query_as("SELECT name FROM organizations WHERE id = $1")
+            .bind(org_id)
+            .fetch_optional(&state.pool)
+            .await?;
+    let org_name = org_name.map(|(n,)| n).unwrap_or_default();
+
+    // Completed account credits (admin_refunds with refund_type = 'credit')
+    let credits: Vec<(Uuid, i32, String, String, String, OffsetDateTime)> = sqlx::query_as(
+        r#"
+        SELECT r.id, r.amount_cents, r.reason, r.old_tier, r.new_tier, r.created_at
+        FROM admin_refunds r
+        WHERE r.org_id = $1 AND r.status = 'completed' AND r.refund_type = 'credit'
+          AND NOT EXISTS (
+              SELECT 1 FROM billing_documents d
+              WHERE d.document_type = 'credit_receipt' AND d.source_id = r.id
+          )
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    for (source_id, amount_cents, reason, old_tier, new_tier, created_at) in credits {
+        let receipt = crate::receipts::Receipt {
+            title: "Account Credit Receipt".to_string(),
+            org_name: org_name.clone(),
+            reference: document_reference("CR", source_id),
+            issued_on: created_at.date().to_string(),
+            rows: vec![
+                ("Type".to_string(), "Account credit".to_string()),
+                ("Reason".to_string(), reason),
+                (
+                    "Plan change".to_string(),
+                    format!("{} to {}", old_tier, new_tier),
+                ),
+            ],
+            total_cents: amount_cents as i64,
+            currency: "usd".to_string(),
+        };
+        store_receipt(
+            state,
+            storage,
+            org_id,
+            "credit_receipt",
+            source_id,
+            &receipt,
+        )
+        .await?;
+    }
+
+    // Paid invoices recorded locally (no Stripe invoice behind them)
+    #[allow(clippy::type_complexity)]
+    let manual_invoices: Vec<(
+        Uuid,
+        Option<String>,
+        i32,
+        String,
+        Option<String>,
+        Option<OffsetDateTime>,
+        OffsetDateTime,
+    )> = sqlx::query_as(
+        r#"
+        SELECT i.id, i.invoice_number, i.amount_cents, i.currency, i.description,
+               i.paid_at, i.created_at
+        FROM invoices i
+        WHERE i.org_id = $1 AND i.stripe_invoice_id IS NULL AND i.status = 'paid'
+          AND NOT EXISTS (
+              SELECT 1 FROM billing_documents d
+              WHERE d.document_type = 'manual_invoice' AND d.source_id = i.id
+          )
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    for (source_id, invoice_number, amount_cents, currency, description, paid_at, created_at) in
+        manual_invoices
+    {
+        let reference = invoice_number.unwrap_or_else(|| document_reference("INV", source_id));
+        let receipt = crate::receipts::Receipt {
+            title: "Invoice Receipt".to_string(),
+            org_name: org_name.clone(),
+            reference,
+            issued_on: paid_at.unwrap_or(created_at).date().to_string(),
+            rows: vec![(
+                "Description".to_string(),
+                description.unwrap_or_else(|| "Manually recorded invoice".to_string()),
+            )],
+            total_cents: amount_cents as i64,
+            currency,
+        };
+        store_receipt(
+            state,
+            storage,
+            org_id,
+            "manual_invoice",
+            source_id,
+            &receipt,
+        )
+        .await?;
+    }
+
+    // Paid overage charges
+    #[allow(clippy::type_complexity)]
+    let overages: Vec<(
+        Uuid,
+        String,
+        i64,
+        i32,
+        i32,
+        OffsetDateTime,
+        OffsetDateTime,
+        Option<OffsetDateTime>,
+    )> = sqlx::query_as(
+        r#"
+        SELECT o.id, o.resource_type, o.overage_amount, o.rate_per_unit_cents,
+               o.total_charge_cents, o.billing_period_start, o.billing_period_end, o.paid_at
+        FROM overage_charges o
+        WHERE o.org_id = $1 AND o.status = 'paid'
+          AND NOT EXISTS (
+              SELECT 1 FROM billing_documents d
+              WHERE d.document_type = 'overage_receipt' AND d.source_id = o.id
+          )
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    for (
+        source_id,
+        resource_type,
+        overage_amount,
+        rate_per_unit_cents,
+        total_charge_cents,
+        period_start,
+        period_end,
+        paid_at,
+    ) in overages
+    {
+        let receipt = crate::receipts::Receipt {
+            title: "Overage Payment Receipt".to_string(),
+            org_name: org_name.clone(),
+            reference: document_reference("OV", source_id),
+            issued_on: paid_at.unwrap_or(period_end).date().to_string(),
+            rows: vec![
+                ("Resource".to_string(), resource_type),
+                ("Overage units".to_string(), overage_amount.to_string()),
+                (
+                    "Rate per unit".to_string(),
+                    crate::receipts::format_amount(rate_per_unit_cents as i64, "usd"),
+                ),
+                (
+                    "Billing period".to_string(),
+                    format!("{} to {}", period_start.date(), period_end.date()),
+                ),
+            ],
+            total_cents: total_charge_cents as i64,
+            currency: "usd".to_string(),
+        };
+        store_receipt(
+            state,
+            storage,
+            org_id,
+            "overage_receipt",
+            source_id,
+            &receipt,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Render a receipt, store the PDF, and record the document row
+async fn store_receipt(
+    state: &AppState,
+    storage: &plexmcp_shared::StorageBackend,
+    org_id: Uuid,
+    document_type: &str,
+    source_id: Uuid,
+    receipt: &crate::receipts::Receipt,
+) -> Result<(), ApiError> {
+    let document_id = Uuid::new_v4();
+    let storage_key = format!("billing-documents/{}/{}.pdf", org_id, document_id);
+
+    let pdf = crate::receipts::render_pdf(receipt);
+    if let Err(e) = storage.put(&storage_key, &pdf, "application/pdf").await {
+        // Leave the document ungenerated; the next listing retries
+        tracing::error!(
+            org_id = %org_id,
+            source_id = %source_id,
+            error = %e,
+            "Failed to store receipt PDF"
+        );
+        return Ok(());
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO billing_documents (
+            id, org_id, document_type, source_id, title, amount_cents, currency, storage_key
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (document_type, source_id) DO NOTHING
+        "#,
+    )
+    .bind(document_id)
+    .bind(org_id)
+    .bind(document_type)
+    .bind(source_id)
+    .bind(&receipt.title)
+    .bind(receipt.total_cents)
+    .bind(&receipt.currency)
+    .bind(&storage_key)
+    .execute(&state.pool)
+    .await?;
+
+    tracing::info!(
+        org_id = %org_id,
+        document_id = %document_id,
+        document_type = document_type,
+        "Generated receipt document"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,478 @@
+//! White-label brand configuration routes
+//!
+//! White-label tiers configure how the dashboard looks on their domain:
+//! logo, color palette, product name, support email, and a hidden
+//! "powered by" footer. Logos go through the shared storage backend and
+//! are served via signed URLs. The public host-resolution endpoint
+//! returns the branding for a domain so the frontend can theme itself
+//! before anyone logs in.
+
+use axum::{
+    body::Bytes,
+    extract::{Extension, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthUser,
+    error::{ApiError, ApiResult},
+    routing::HostResolveError,
+    state::AppState,
+};
+
+/// Tiers allowed to configure branding
+const WHITE_LABEL_TIERS: &[&str] = &["team", "enterprise"];
+
+/// Maximum logo size
+const MAX_LOGO_SIZE_BYTES: usize = 1024 * 1024;
+
+/// Accepted logo content types
+const LOGO_CONTENT_TYPES: &[&str] = &["image/png", "image/jpeg", "image/svg+xml", "image/webp"];
+
+/// Signed logo URLs are valid for an hour; the frontend refetches on load
+const LOGO_URL_EXPIRY_SECS: u64 = 3600;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+/// PATCH semantics: absent fields are left unchanged, empty strings clear
+#[derive(Debug, Deserialize)]
+pub struct UpdateBrandingRequest {
+    pub product_name: Option<String>,
+    pub support_email: Option<String>,
+    pub primary_color: Option<String>,
+    pub accent_color: Option<String>,
+    pub background_color: Option<String>,
+    pub hide_powered_by: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BrandingResponse {
+    pub product_name: Option<String>,
+    pub support_email: Option<String>,
+    pub primary_color: Option<String>,
+    pub accent_color: Option<String>,
+    pub background_color: Option<String>,
+    pub hide_powered_by: bool,
+    /// Time-limited signed URL for the uploaded logo
+    pub logo_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResolveHostQuery {
+    /// The host the frontend is being served on, e.g. "mcp.company.com"
+    pub host: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResolveHostResponse {
+    pub org_id: Uuid,
+    pub org_name: String,
+    pub branding: BrandingResponse,
+}
+
+#[derive(Debug, FromRow)]
+struct BrandingRow {
+    product_name: Option<String>,
+    support_email: Option<String>,
+    primary_color: Option<String>,
+    accent_color: Option<String>,
+    background_color: Option<String>,
+    logo_storage_key: Option<String>,
+    hide_powered_by: bool,
+}
+
+const BRANDING_COLUMNS: &str = "product_name, support_email, primary_color, accent_color, \
+     background_color, logo_storage_key, hide_powered_by";
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Branding mutations require a white-label tier
+async fn require_white_label_tier(pool: &sqlx::PgPool, org_id: Uuid) -> ApiResult<()> {
+    let tier: Option<String> = sqlx::query_scalar(
+        "SELECT COALESCE(subscription_tier, 'free') FROM organizations WHERE id = $1",
+    )
+    .bind(org_id)
+    .fetch_optional(pool)
+    .await?;
+
+    match tier.as_deref() {
+        Some(tier) if WHITE_LABEL_TIERS.contains(&tier) => Ok(()),
+        _ => Err(ApiError::BadRequest(format!(
+            "White-label branding requires one of these tiers: {}",
+            WHITE_LABEL_TIERS.join(", ")
+        ))),
+    }
+}
+
+async fn fetch_branding(pool: &sqlx::PgPool, org_id: Uuid) -> ApiResult<Option<BrandingRow>> {
+    let row: Option<BrandingRow> = sqlx::query_as(&format!(
+        "SELECT {} FROM org_branding WHERE org_id = $1",
+        BRANDING_COLUMNS
+    ))
+    .bind(org_id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row)
+}
+
+/// Build the API response, signing the logo URL when storage is available.
+/// A signing failure degrades to no logo rather than failing the read.
+async fn branding_response(state: &AppState, row: Option<BrandingRow>) -> BrandingResponse {
+    let Some(row) = row else {
+        return BrandingResponse {
+            product_name: None,
+            support_email: None,
+            primary_color: None,
+            accent_color: None,
+            background_color: None,
+            hide_powered_by: false,
+            logo_url: None,
+        };
+    };
+
+    let logo_url = match (&row.logo_storage_key, state.storage.as_ref()) {
+        (Some(key), Some(storage)) => match storage.signed_get_url(key, LOGO_URL_EXPIRY_SECS).await
+        {
+            Ok(url) => Some(url),
+            Err(e) => {
+                tracing::error!(key = %key, error = %e, "Failed to sign branding logo URL");
+                None
+            }
+        },
+        _ => None,
+    };
+
+    BrandingResponse {
+        product_name: row.product_name,
+        support_email: row.support_email,
+        primary_color: row.primary_color,
+        accent_color: row.accent_color,
+        background_color: row.background_color,
+        hide_powered_by: row.hide_powered_by,
+        logo_url,
+    }
+}
+
+/// Trim a patch value; empty strings clear the field
+fn normalize_field(value: String) -> Option<String> {
+    let trimmed = value.trim().to_string();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed)
+    }
+}
+
+/// Accept hex colors in the '#rrggbb' form only
+fn is_valid_hex_color(color: &str) -> bool {
+    let Some(digits) = color.strip_prefix('#') else {
+        return false;
+    };
+    digits.len() == 6 && digits.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn validate_color(field: &str, color: &Option<String>) -> ApiResult<()> {
+    if let Some(color) = color {
+        if !is_valid_hex_color(color) {
+            return Err(ApiError::Validation(format!(
+                "{} must be a hex color like #1a73e8",
+                field
+            )));
+        }
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// GET /org/branding - current brand configuration
+pub async fn get_branding(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<BrandingResponse>> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let row = fetch_branding(&state.pool, org_id).await?;
+    Ok(Json(branding_response(&state, row).await))
+}
+
+/// PATCH /org/branding - update brand configuration (white-label tiers only)
+pub async fn update_branding(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<UpdateBrandingRequest>,
+) -> ApiResult<Json<BrandingResponse>> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+    require_white_label_tier(&state.pool, org_id).await?;
+
+    // Merge the patch over the existing row, then write it back whole
+    let existing = fetch_branding(&state.pool, org_id).await?;
+
+    let mut product_name = existing.as_ref().and_then(|r| r.product_name.clone());
+    let mut support_email = existing.as_ref().and_then(|r| r.support_email.clone());
+    let mut primary_color = existing.as_ref().and_then(|r| r.primary_color.clone());
+    let mut accent_color = existing.as_ref().and_then(|r| r.accent_color.clone());
+    let mut background_color = existing.as_ref().and_then(|r| r.background_color.clone());
+    let mut hide_powered_by = existing
+        .as_ref()
+        .map(|r| r.hide_powered_by)
+        .unwrap_or(false);
+
+    if let Some(value) = req.product_name {
+        product_name = normalize_field(value);
+    }
+    if let Some(value) = req.support_email {
+        support_email = normalize_field(value);
+    }
+    if let Some(value) = req.primary_color {
+        primary_color = normalize_field(value);
+    }
+    if let Some(value) = req.accent_color {
+        accent_color = normalize_field(value);
+    }
+    if let Some(value) = req.background_color {
+        background_color = normalize_field(value);
+    }
+    if let Some(value) = req.hide_powered_by {
+        hide_powered_by = value;
+    }
+
+    if let Some(name) = &product_name {
+        if name.len() > 100 {
+            return Err(ApiError::Validation(
+                "product_name must be at most 100 characters".to_string(),
+            ));
+        }
+    }
+    if let Some(email) = &support_email {
+        if !email.contains('@') || email.len() > 255 {
+            return Err(ApiError::Validation(
+                "support_email must be a valid email address".to_string(),
+            ));
+        }
+    }
+    validate_color("primary_color", &primary_color)?;
+    validate_color("accent_color", &accent_color)?;
+    validate_color("background_color", &background_color)?;
+
+    let row: BrandingRow = sqlx::query_as(&format!(
+        r#"
+        INSERT INTO org_branding
+            (org_id, product_name, support_email, primary_color, accent_color,
+             background_color, hide_powered_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (org_id) DO UPDATE SET
+            product_name = EXCLUDED.product_name,
+            support_email = EXCLUDED.support_email,
+            primary_color = EXCLUDED.primary_color,
+            accent_color = EXCLUDED.accent_color,
+            background_color = EXCLUDED.background_color,
+            hide_powered_by = EXCLUDED.hide_powered_by,
+            updated_at = NOW()
+        RETURNING {}
+        "#,
+        BRANDING_COLUMNS
+    ))
+    .bind(org_id)
+    .bind(&product_name)
+    .bind(&support_email)
+    .bind(&primary_color)
+    .bind(&accent_color)
+    .bind(&background_color)
+    .bind(hide_powered_by)
+    .fetch_one(&state.pool)
+    .await?;
+
+    tracing::info!(org_id = %org_id, "Org branding updated");
+
+    Ok(Json(branding_response(&state, Some(row)).await))
+}
+
+/// POST /org/branding/logo - upload the brand logo (raw image body)
+pub async fn upload_logo(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ApiResult<Json<BrandingResponse>> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+    require_white_label_tier(&state.pool, org_id).await?;
+
+    let content_type = headers
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string();
+    if !LOGO_CONTENT_TYPES.contains(&content_type.as_str()) {
+        return Err(ApiError::Validation(format!(
+            "Logo must be one of these content types: {}",
+            LOGO_CONTENT_TYPES.join(", ")
+        )));
+    }
+
+    if body.is_empty() {
+        return Err(ApiError::BadRequest("Logo is empty".into()));
+    }
+    if body.len() > MAX_LOGO_SIZE_BYTES {
+        return Err(ApiError::Validation(format!(
+            "Logo exceeds the {} KB size limit",
+            MAX_LOGO_SIZE_BYTES / 1024
+        )));
+    }
+
+    let Some(storage) = state.storage.clone() else {
+        tracing::error!("Logo upload rejected: storage backend not available");
+        return Err(ApiError::ServiceUnavailable);
+    };
+
+    let storage_key = format!("branding/{}/logo-{}", org_id, Uuid::new_v4());
+
+    storage
+        .put(&storage_key, &body, &content_type)
+        .await
+        .map_err(|e| {
+            tracing::error!(key = %storage_key, error = %e, "Failed to store branding logo");
+            ApiError::Internal
+        })?;
+
+    let previous_key: Option<String> = sqlx::query_scalar(
+        r#"
+        INSERT INTO org_branding (org_id, logo_storage_key, logo_content_type)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (org_id) DO UPDATE SET
+            logo_storage_key = EXCLUDED.logo_storage_key,
+            logo_content_type = EXCLUDED.logo_content_type,
+            updated_at = NOW()
+        RETURNING (SELECT logo_storage_key FROM org_branding WHERE org_id = $1)
+        "#,
+    )
+    .bind(org_id)
+    .bind(&storage_key)
+    .bind(&content_type)
+    .fetch_one(&state.pool)
+    .await?;
+
+    // Best-effort cleanup of the replaced logo
+    if let Some(previous_key) = previous_key.filter(|k| k != &storage_key) {
+        if let Err(e) = storage.delete(&previous_key).await {
+            tracing::warn!(key = %previous_key, error = %e, "Failed to delete replaced logo");
+        }
+    }
+
+    tracing::info!(org_id = %org_id, size_bytes = body.len(), "Org logo uploaded");
+
+    let row = fetch_branding(&state.pool, org_id).await?;
+    Ok(Json(branding_response(&state, row).await))
+}
+
+/// DELETE /org/branding/logo - remove the brand logo
+pub async fn delete_logo(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<StatusCode> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let previous_key: Option<Option<String>> = sqlx::query_scalar(
+        r#"
+        UPDATE org_branding
+        SET logo_storage_key = NULL, logo_content_type = NULL, updated_at = NOW()
+        WHERE org_id = $1
+        RETURNING (SELECT logo_storage_key FROM org_branding WHERE org_id = $1)
+        "#,
+    )
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    if let (Some(Some(key)), Some(storage)) = (previous_key, state.storage.as_ref()) {
+        if let Err(e) = storage.delete(&key).await {
+            tracing::warn!(key = %key, error = %e, "Failed to delete removed logo");
+        }
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /public/resolve-host - resolve a dashboard host to its org and branding
+///
+/// Public so the frontend can theme itself before login. Hosts that do
+/// not belong to an org (including the platform's own hosts) return 404
+/// and the frontend falls back to the default theme.
+pub async fn resolve_host(
+    State(state): State<AppState>,
+    Query(query): Query<ResolveHostQuery>,
+) -> ApiResult<Json<ResolveHostResponse>> {
+    let resolved = match state.host_resolver.resolve(&query.host).await {
+        Ok(Some(resolved)) => resolved,
+        Ok(None) => return Err(ApiError::NotFound),
+        Err(HostResolveError::NotFound(_)) | Err(HostResolveError::ReservedSubdomain(_)) => {
+            return Err(ApiError::NotFound)
+        }
+        Err(HostResolveError::DatabaseError(e)) => {
+            tracing::error!(host = %query.host, error = %e, "Host resolution failed");
+            return Err(ApiError::Internal);
+        }
+    };
+
+    let org_name: Option<String> =
+        sqlx::query_scalar("SELECT name FROM organizations WHERE id = $1")
+            .bind(resolved.org_id)
+            .fetch_optional(&state.pool)
+            .await?;
+    let org_name = org_name.ok_or(ApiError::NotFound)?;
+
+    let row = fetch_branding(&state.pool, resolved.org_id).await?;
+    let branding = branding_response(&state, row).await;
+
+    Ok(Json(ResolveHostResponse {
+        org_id: resolved.org_id,
+        org_name,
+        branding,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_colors_are_validated() {
+        assert!(is_valid_hex_color("#1a73e8"));
+        assert!(is_valid_hex_color("#FFFFFF"));
+        assert!(!is_valid_hex_color("1a73e8"));
+        assert!(!is_valid_hex_color("#fff"));
+        assert!(!is_valid_hex_color("#1a73zz"));
+    }
+
+    #[test]
+    fn empty_patch_values_clear_fields() {
+        assert_eq!(
+            normalize_field("  Acme MCP  ".to_string()).as_deref(),
+            Some("Acme MCP")
+        );
+        assert_eq!(normalize_field("   ".to_string()), None);
+        assert_eq!(normalize_field(String::new()), None);
+    }
+}
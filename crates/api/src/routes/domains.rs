@@ -9,6 +9,7 @@ use axum::{
     Json,
 };
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "billing")]
 use sqlx::PgPool;
 use std::str::FromStr;
 use time::OffsetDateTime;
@@ -614,8 +615,8 @@ async fn check_addon_enabled(
     Ok(result)
 }
 
-/// Validate domain format
-fn is_valid_domain(domain: &str) -> bool {
+/// Validate domain format (also used by the sender-domain routes)
+pub(crate) fn is_valid_domain(domain: &str) -> bool {
     // Basic validation - should have at least one dot, no spaces, alphanumeric + hyphen
     if domain.is_empty() || domain.len() > 253 {
         return false;
@@ -642,7 +643,7 @@ fn is_valid_domain(domain: &str) -> bool {
 }
 
 /// Generate a secure verification token
-fn generate_verification_token() -> String {
+pub(crate) fn generate_verification_token() -> String {
     use rand::Rng;
     let mut rng = rand::thread_rng();
     let token: String = (0..32)
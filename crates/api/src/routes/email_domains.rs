@@ -0,0 +1,495 @@
+//! Per-org sender domain routes for white-label email
+//!
+//! White-label orgs can register their own domain for outbound ticket and
+//! invite emails. The handlers generate the DKIM/SPF records the org needs
+//! to publish, verify them over DNS (manually via the verify route; a
+//! background poll re-checks pending domains), and once verified the email
+//! module sends from the org's address (see
+//! [`crate::email::SecurityEmailService::for_org`]).
+
+use axum::{
+    extract::{Extension, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{auth::AuthUser, error::ApiError, state::AppState};
+
+/// How often the background poll re-checks unverified domains
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(900);
+
+/// Unverified domains re-checked per poll pass
+const POLL_BATCH_LIMIT: i64 = 20;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Database row for an org sender domain
+#[derive(Debug, sqlx::FromRow)]
+struct EmailDomainRow {
+    domain: String,
+    from_address: String,
+    dkim_selector: String,
+    status: String,
+    verification_attempts: i32,
+    last_verification_at: Option<OffsetDateTime>,
+    verified_at: Option<OffsetDateTime>,
+}
+
+/// One DNS record the org must publish
+#[derive(Debug, Serialize)]
+pub struct EmailDnsRecord {
+    pub record_type: String,
+    pub name: String,
+    pub value: String,
+    pub ttl: u32,
+}
+
+/// Sender domain configuration response
+#[derive(Debug, Serialize)]
+pub struct EmailDomainResponse {
+    pub domain: String,
+    pub from_address: String,
+    pub status: String,
+    pub verification_attempts: i32,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_verification_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub verified_at: Option<OffsetDateTime>,
+    /// DNS records to publish for DKIM/SPF
+    pub dns_records: Vec<EmailDnsRecord>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetEmailDomainRequest {
+    /// Sender domain, e.g. "acme.com"
+    pub domain: String,
+    /// From address on that domain, e.g. "support@acme.com"
+    pub from_address: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VerifyEmailDomainResponse {
+    pub domain: EmailDomainResponse,
+    pub verification_result: EmailVerificationResult,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EmailVerificationResult {
+    pub success: bool,
+    pub spf_valid: bool,
+    pub dkim_valid: bool,
+    pub message: String,
+}
+
+/// Platform-side DNS targets the org's records must point at
+struct SenderDnsTargets {
+    /// Host included in the org's SPF record
+    spf_include: String,
+    /// CNAME target for the DKIM selector record
+    dkim_target: String,
+}
+
+impl SenderDnsTargets {
+    /// Derive from the platform base domain, with env overrides for
+    /// deployments that relay through a different mail host
+    fn from_base_domain(base_domain: &str) -> Self {
+        Self {
+            spf_include: std::env::var("EMAIL_SPF_INCLUDE")
+                .unwrap_or_else(|_| format!("spf.{}", base_domain)),
+            dkim_target: std::env::var("EMAIL_DKIM_CNAME_TARGET")
+                .unwrap_or_else(|_| format!("dkim.{}", base_domain)),
+        }
+    }
+}
+
+/// Build the DNS records an org must publish for its sender domain
+fn dns_records_for(
+    domain: &str,
+    selector: &str,
+    targets: &SenderDnsTargets,
+) -> Vec<EmailDnsRecord> {
+    vec![
+        EmailDnsRecord {
+            record_type: "TXT".to_string(),
+            name: domain.to_string(),
+            value: format!("v=spf1 include:{} ~all", targets.spf_include),
+            ttl: 3600,
+        },
+        EmailDnsRecord {
+            record_type: "CNAME".to_string(),
+            name: format!("{}._domainkey.{}", selector, domain),
+            value: targets.dkim_target.clone(),
+            ttl: 3600,
+        },
+    ]
+}
+
+fn to_response(row: EmailDomainRow, targets: &SenderDnsTargets) -> EmailDomainResponse {
+    let dns_records = dns_records_for(&row.domain, &row.dkim_selector, targets);
+    EmailDomainResponse {
+        domain: row.domain,
+        from_address: row.from_address,
+        status: row.status,
+        verification_attempts: row.verification_attempts,
+        last_verification_at: row.last_verification_at,
+        verified_at: row.verified_at,
+        dns_records,
+    }
+}
+
+// ============================================================================
+// Route handlers
+// ============================================================================
+
+const SELECT_COLUMNS: &str = "domain, from_address, dkim_selector, status, \
+     verification_attempts, last_verification_at, verified_at";
+
+/// Get the org's sender domain configuration
+pub async fn get_email_domain(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<EmailDomainResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let row: EmailDomainRow = sqlx::query_as(&format!(
+        "SELECT {} FROM org_email_domains WHERE org_id = $1",
+        SELECT_COLUMNS
+    ))
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    let targets = SenderDnsTargets::from_base_domain(&state.config.base_domain);
+    Ok(Json(to_response(row, &targets)))
+}
+
+/// Register or replace the org's sender domain
+///
+/// Replacing the domain resets verification; emails keep going out from
+/// the platform address until the new domain verifies.
+pub async fn set_email_domain(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<SetEmailDomainRequest>,
+) -> Result<(StatusCode, Json<EmailDomainResponse>), ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    // Only owners and admins can change the sender domain
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let domain = req.domain.trim().to_lowercase();
+    if !crate::routes::domains::is_valid_domain(&domain) {
+        return Err(ApiError::Validation(
+            "Invalid domain format. Please enter a valid domain like 'yourcompany.com'".to_string(),
+        ));
+    }
+
+    let from_address = req.from_address.trim().to_lowercase();
+    if !is_valid_from_address(&from_address, &domain) {
+        return Err(ApiError::Validation(format!(
+            "from_address must be an address on {} (e.g. support@{})",
+            domain, domain
+        )));
+    }
+
+    let row: EmailDomainRow = sqlx::query_as(&format!(
+        r#"
+        INSERT INTO org_email_domains (org_id, domain, from_address)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (org_id) DO UPDATE SET
+            domain = EXCLUDED.domain,
+            from_address = EXCLUDED.from_address,
+            status = 'pending',
+            verification_attempts = 0,
+            last_verification_at = NULL,
+            verified_at = NULL,
+            updated_at = NOW()
+        RETURNING {}
+        "#,
+        SELECT_COLUMNS
+    ))
+    .bind(org_id)
+    .bind(&domain)
+    .bind(&from_address)
+    .fetch_one(&state.pool)
+    .await?;
+
+    let targets = SenderDnsTargets::from_base_domain(&state.config.base_domain);
+    Ok((StatusCode::CREATED, Json(to_response(row, &targets))))
+}
+
+/// Trigger DNS verification for the org's sender domain
+pub async fn verify_email_domain(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<VerifyEmailDomainResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let row: EmailDomainRow = sqlx::query_as(&format!(
+        "SELECT {} FROM org_email_domains WHERE org_id = $1",
+        SELECT_COLUMNS
+    ))
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    let targets = SenderDnsTargets::from_base_domain(&state.config.base_domain);
+    let (spf_valid, dkim_valid) =
+        verify_sender_records(&row.domain, &row.dkim_selector, &targets).await;
+    let success = spf_valid && dkim_valid;
+
+    let updated: EmailDomainRow = sqlx::query_as(&format!(
+        r#"
+        UPDATE org_email_domains
+        SET status = CASE WHEN $2 THEN 'verified' ELSE 'failed' END,
+            verification_attempts = verification_attempts + 1,
+            last_verification_at = NOW(),
+            verified_at = CASE WHEN $2 THEN NOW() ELSE verified_at END,
+            updated_at = NOW()
+        WHERE org_id = $1
+        RETURNING {}
+        "#,
+        SELECT_COLUMNS
+    ))
+    .bind(org_id)
+    .bind(success)
+    .fetch_one(&state.pool)
+    .await?;
+
+    let message = if success {
+        "DNS verification successful! Emails will now be sent from your domain.".to_string()
+    } else {
+        let mut issues = Vec::new();
+        if !spf_valid {
+            issues.push(format!(
+                "SPF record not found. Expected a TXT record on {} including {}",
+                row.domain, targets.spf_include
+            ));
+        }
+        if !dkim_valid {
+            issues.push(format!(
+                "DKIM record not found. Expected {}._domainkey.{} CNAME -> {}",
+                row.dkim_selector, row.domain, targets.dkim_target
+            ));
+        }
+        issues.join(". ")
+    };
+
+    Ok(Json(VerifyEmailDomainResponse {
+        domain: to_response(updated, &targets),
+        verification_result: EmailVerificationResult {
+            success,
+            spf_valid,
+            dkim_valid,
+            message,
+        },
+    }))
+}
+
+/// Remove the org's sender domain; emails revert to the platform address
+pub async fn delete_email_domain(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<StatusCode, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let result = sqlx::query("DELETE FROM org_email_domains WHERE org_id = $1")
+        .bind(org_id)
+        .execute(&state.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Verification
+// ============================================================================
+
+/// Validate that the from address is a plausible mailbox on the domain
+fn is_valid_from_address(from_address: &str, domain: &str) -> bool {
+    match from_address.split_once('@') {
+        Some((local, addr_domain)) => {
+            !local.is_empty()
+                && addr_domain == domain
+                && local
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '+'))
+        }
+        None => false,
+    }
+}
+
+/// Check the SPF TXT and DKIM CNAME records over DNS
+/// Returns (spf_valid, dkim_valid)
+async fn verify_sender_records(
+    domain: &str,
+    selector: &str,
+    targets: &SenderDnsTargets,
+) -> (bool, bool) {
+    use trust_dns_resolver::config::{ResolverConfig, ResolverOpts};
+    use trust_dns_resolver::proto::rr::RecordType;
+    use trust_dns_resolver::TokioAsyncResolver;
+
+    let resolver = TokioAsyncResolver::tokio(ResolverConfig::cloudflare(), ResolverOpts::default());
+
+    // SPF: a TXT record on the domain that includes our mail host
+    let include_directive = format!("include:{}", targets.spf_include);
+    let spf_valid = match resolver.lookup(domain, RecordType::TXT).await {
+        Ok(response) => response.iter().any(|record| {
+            if let Some(txt) = record.as_txt() {
+                let value: String = txt
+                    .txt_data()
+                    .iter()
+                    .map(|data| String::from_utf8_lossy(data))
+                    .collect();
+                value.starts_with("v=spf1") && value.contains(&include_directive)
+            } else {
+                false
+            }
+        }),
+        Err(_) => false,
+    };
+
+    // DKIM: the selector CNAME must point at the platform DKIM host
+    let dkim_name = format!("{}._domainkey.{}", selector, domain);
+    let dkim_valid = match resolver.lookup(&dkim_name, RecordType::CNAME).await {
+        Ok(response) => response.iter().any(|record| {
+            if let Some(cname) = record.as_cname() {
+                cname
+                    .to_string()
+                    .trim_end_matches('.')
+                    .eq_ignore_ascii_case(&targets.dkim_target)
+            } else {
+                false
+            }
+        }),
+        Err(_) => false,
+    };
+
+    (spf_valid, dkim_valid)
+}
+
+// ============================================================================
+// Background verification polling
+// ============================================================================
+
+/// Periodically re-verify unverified sender domains so orgs don't have to
+/// keep clicking verify while DNS propagates
+pub async fn verification_poll_task(pool: PgPool, base_domain: String) {
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        poll_pass(&pool, &base_domain).await;
+    }
+}
+
+async fn poll_pass(pool: &PgPool, base_domain: &str) {
+    let targets = SenderDnsTargets::from_base_domain(base_domain);
+
+    let rows: Vec<(Uuid, String, String)> = match sqlx::query_as(
+        r#"
+        SELECT org_id, domain, dkim_selector
+        FROM org_email_domains
+        WHERE status IN ('pending', 'failed')
+        ORDER BY last_verification_at ASC NULLS FIRST
+        LIMIT $1
+        "#,
+    )
+    .bind(POLL_BATCH_LIMIT)
+    .fetch_all(pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            tracing::error!(error = %e, "Sender domain poll failed to load domains");
+            return;
+        }
+    };
+
+    for (org_id, domain, selector) in rows {
+        let (spf_valid, dkim_valid) = verify_sender_records(&domain, &selector, &targets).await;
+        let success = spf_valid && dkim_valid;
+
+        let updated = sqlx::query(
+            r#"
+            UPDATE org_email_domains
+            SET status = CASE WHEN $2 THEN 'verified' ELSE 'failed' END,
+                verification_attempts = verification_attempts + 1,
+                last_verification_at = NOW(),
+                verified_at = CASE WHEN $2 THEN NOW() ELSE verified_at END,
+                updated_at = NOW()
+            WHERE org_id = $1
+            "#,
+        )
+        .bind(org_id)
+        .bind(success)
+        .execute(pool)
+        .await;
+
+        match updated {
+            Ok(_) if success => {
+                tracing::info!(org_id = %org_id, domain = %domain, "Sender domain verified");
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::error!(org_id = %org_id, error = %e, "Failed to record sender domain check");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dns_records_cover_spf_and_dkim() {
+        let targets = SenderDnsTargets {
+            spf_include: "spf.plexmcp.com".to_string(),
+            dkim_target: "dkim.plexmcp.com".to_string(),
+        };
+        let records = dns_records_for("acme.com", "plexmcp", &targets);
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].record_type, "TXT");
+        assert_eq!(records[0].value, "v=spf1 include:spf.plexmcp.com ~all");
+        assert_eq!(records[1].record_type, "CNAME");
+        assert_eq!(records[1].name, "plexmcp._domainkey.acme.com");
+        assert_eq!(records[1].value, "dkim.plexmcp.com");
+    }
+
+    #[test]
+    fn test_from_address_must_be_on_domain() {
+        assert!(is_valid_from_address("support@acme.com", "acme.com"));
+        assert!(is_valid_from_address(
+            "no-reply+tickets@acme.com",
+            "acme.com"
+        ));
+        assert!(!is_valid_from_address("support@other.com", "acme.com"));
+        assert!(!is_valid_from_address("@acme.com", "acme.com"));
+        assert!(!is_valid_from_address("support", "acme.com"));
+        assert!(!is_valid_from_address("sup port@acme.com", "acme.com"));
+    }
+}
@@ -0,0 +1,1079 @@
+//! Config-as-code GitHub sync for MCP definitions
+//!
+//! Orgs connect a GitHub repo whose JSON/YAML files under a configured
+//! path each define one MCP instance. A push webhook (verified via
+//! X-Hub-Signature-256) computes a diff against the org's current MCPs:
+//! orgs with auto_apply enabled apply it immediately, others review the
+//! run PR-style via the dashboard API before applying. When a GitHub
+//! token is configured, each push gets a `plexmcp/config-sync` commit
+//! status reflecting the sync outcome.
+
+use std::collections::BTreeSet;
+
+use axum::{
+    extract::{Extension, Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sqlx::FromRow;
+use subtle::ConstantTimeEq;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthUser,
+    error::{ApiError, ApiResult},
+    state::AppState,
+};
+
+use super::mcps::{
+    get_mcp_count, get_org_effective_limits, validate_auth_template, validate_cache_config,
+};
+
+/// Commit status context reported back to GitHub
+const STATUS_CONTEXT: &str = "plexmcp/config-sync";
+
+/// Definition files larger than this are rejected
+const MAX_DEFINITION_BYTES: usize = 64 * 1024;
+
+/// Runs returned by the review queue listing
+const RUNS_PAGE_SIZE: i64 = 50;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+/// Configure (or reconfigure) the GitHub sync connection. PUT semantics:
+/// every field is replaced; an absent `webhook_secret` generates a fresh
+/// one and an absent `github_token` clears any stored token.
+#[derive(Debug, Deserialize)]
+pub struct UpsertSyncConfigRequest {
+    /// Repository in "owner/name" form
+    pub repo: String,
+    /// Branch to sync from (default "main")
+    pub branch: Option<String>,
+    /// Repo path prefix containing definition files (default "mcps/")
+    pub path_prefix: Option<String>,
+    /// Apply pushes immediately instead of queueing them for review
+    pub auto_apply: Option<bool>,
+    /// Webhook secret (16-256 chars); omit to generate one
+    pub webhook_secret: Option<String>,
+    /// Token for private repos and commit status reporting; omit to clear
+    pub github_token: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncConfigResponse {
+    pub repo: String,
+    pub branch: String,
+    pub path_prefix: String,
+    pub auto_apply: bool,
+    pub enabled: bool,
+    /// URL to configure as the GitHub push webhook
+    pub webhook_url: String,
+    /// Only returned from PUT so the caller can configure GitHub
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_secret: Option<String>,
+    /// Whether a GitHub token is stored (the token itself is never returned)
+    pub token_set: bool,
+    pub last_synced_sha: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct SyncRunResponse {
+    pub id: Uuid,
+    pub commit_sha: String,
+    /// 'pending', 'applied', 'awaiting_review', 'failed', or 'rejected'
+    pub status: String,
+    /// Change set computed from the push
+    pub diff: serde_json::Value,
+    pub error: Option<String>,
+    pub reviewed_by: Option<Uuid>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub reviewed_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+/// One MCP definition file from the repo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpDefinition {
+    pub name: String,
+    pub mcp_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_active: Option<bool>,
+}
+
+/// One entry in a run's change set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DiffEntry {
+    /// 'create', 'update', or 'delete'
+    action: String,
+    /// MCP name the entry targets
+    name: String,
+    /// Repo path of the definition file
+    path: String,
+    /// Desired definition; absent for deletes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    definition: Option<McpDefinition>,
+}
+
+/// Stored sync connection for one org
+#[derive(Debug, Clone, FromRow)]
+struct SyncConfigRow {
+    org_id: Uuid,
+    repo: String,
+    branch: String,
+    path_prefix: String,
+    webhook_secret: String,
+    github_token: Option<String>,
+    auto_apply: bool,
+}
+
+// =============================================================================
+// GitHub Push Webhook
+// =============================================================================
+
+/// Relevant subset of the GitHub push event payload
+#[derive(Debug, Deserialize)]
+struct PushPayload {
+    #[serde(rename = "ref")]
+    git_ref: String,
+    before: String,
+    after: String,
+    #[serde(default)]
+    commits: Vec<PushCommit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PushCommit {
+    #[serde(default)]
+    added: Vec<String>,
+    #[serde(default)]
+    modified: Vec<String>,
+    #[serde(default)]
+    removed: Vec<String>,
+}
+
+/// Receive a GitHub push webhook for an org's sync connection
+///
+/// Verifies the HMAC signature, filters the push down to definition
+/// files on the configured branch, and processes the sync in the
+/// background so GitHub's delivery timeout is never hit.
+pub async fn github_webhook(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    headers: HeaderMap,
+    body: String,
+) -> ApiResult<StatusCode> {
+    let config: SyncConfigRow = sqlx::query_as(
+        r#"
+        SELECT org_id, repo, branch, path_prefix, webhook_secret, github_token, auto_apply
+        FROM github_sync_configs
+        WHERE org_id = $1 AND enabled = true
+        "#,
+    )
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    let signature = headers
+        .get("x-hub-signature-256")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    if !verify_signature(&config.webhook_secret, body.as_bytes(), signature) {
+        tracing::warn!(org_id = %org_id, "GitHub sync webhook signature mismatch");
+        return Err(ApiError::Unauthorized);
+    }
+
+    let event = headers
+        .get("x-github-event")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    match event {
+        "ping" => return Ok(StatusCode::OK),
+        "push" => {}
+        // Deliveries for events we don't handle are acknowledged, not errors
+        _ => return Ok(StatusCode::OK),
+    }
+
+    let payload: PushPayload = serde_json::from_str(&body)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid push payload: {}", e)))?;
+
+    if payload.git_ref != format!("refs/heads/{}", config.branch) {
+        return Ok(StatusCode::OK);
+    }
+
+    let (changed, removed) = collect_definition_paths(&payload.commits, &config.path_prefix);
+    if changed.is_empty() && removed.is_empty() {
+        return Ok(StatusCode::OK);
+    }
+
+    tracing::info!(
+        org_id = %org_id,
+        repo = %config.repo,
+        commit = %payload.after,
+        changed = changed.len(),
+        removed = removed.len(),
+        "GitHub sync push received"
+    );
+
+    tokio::spawn(process_push(state, config, payload, changed, removed));
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Verify a GitHub `X-Hub-Signature-256` header against the raw body
+fn verify_signature(secret: &str, body: &[u8], header: &str) -> bool {
+    let Some(provided) = header.strip_prefix("sha256=") else {
+        return false;
+    };
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .unwrap_or_else(|_| unreachable!("HMAC accepts keys of any length"));
+    mac.update(body);
+    let expected = hex::encode(mac.finalize().into_bytes());
+    // Constant-time comparison (SOC 2 CC6.1)
+    expected.as_bytes().ct_eq(provided.as_bytes()).into()
+}
+
+/// Split a push's commits into changed and removed definition paths,
+/// deduplicated and filtered to the configured prefix. A file that is
+/// both modified and removed within one push counts as removed only if
+/// no later commit re-adds it; GitHub lists each commit in order, so the
+/// last mention wins.
+fn collect_definition_paths(
+    commits: &[PushCommit],
+    path_prefix: &str,
+) -> (BTreeSet<String>, BTreeSet<String>) {
+    let mut changed = BTreeSet::new();
+    let mut removed = BTreeSet::new();
+
+    for commit in commits {
+        for path in commit.added.iter().chain(&commit.modified) {
+            if is_definition_path(path, path_prefix) {
+                removed.remove(path);
+                changed.insert(path.clone());
+            }
+        }
+        for path in &commit.removed {
+            if is_definition_path(path, path_prefix) {
+                changed.remove(path);
+                removed.insert(path.clone());
+            }
+        }
+    }
+
+    (changed, removed)
+}
+
+fn is_definition_path(path: &str, path_prefix: &str) -> bool {
+    path.starts_with(path_prefix)
+        && (path.ends_with(".json") || path.ends_with(".yaml") || path.ends_with(".yml"))
+}
+
+/// Process one push in the background: record the run, fetch and
+/// validate the definitions, compute the diff, then apply or queue it
+async fn process_push(
+    state: AppState,
+    config: SyncConfigRow,
+    payload: PushPayload,
+    changed: BTreeSet<String>,
+    removed: BTreeSet<String>,
+) {
+    let org_id = config.org_id;
+
+    let run_id: Uuid = match sqlx::query_scalar(
+        "INSERT INTO github_sync_runs (org_id, commit_sha) VALUES ($1, $2) RETURNING id",
+    )
+    .bind(org_id)
+    .bind(&payload.after)
+    .fetch_one(&state.pool)
+    .await
+    {
+        Ok(id) => id,
+        Err(e) => {
+            tracing::error!(org_id = %org_id, error = %e, "Failed to record GitHub sync run");
+            return;
+        }
+    };
+
+    report_commit_status(&config, &payload.after, "pending", "Sync in progress").await;
+
+    match compute_push_diff(&state, &config, &payload, &changed, &removed).await {
+        Ok(diff) if diff.is_empty() => {
+            finish_run(&state, &config, run_id, &payload.after, &diff, "applied").await;
+            report_commit_status(&config, &payload.after, "success", "No MCP changes").await;
+        }
+        Ok(diff) if config.auto_apply => match apply_diff(&state, org_id, &diff).await {
+            Ok(()) => {
+                finish_run(&state, &config, run_id, &payload.after, &diff, "applied").await;
+                report_commit_status(
+                    &config,
+                    &payload.after,
+                    "success",
+                    &describe_diff(&diff, "applied"),
+                )
+                .await;
+            }
+            Err(e) => {
+                fail_run(&state, run_id, &diff, &e.to_string()).await;
+                report_commit_status(&config, &payload.after, "failure", &e.to_string()).await;
+            }
+        },
+        Ok(diff) => {
+            finish_run(
+                &state,
+                &config,
+                run_id,
+                &payload.after,
+                &diff,
+                "awaiting_review",
+            )
+            .await;
+            report_commit_status(
+                &config,
+                &payload.after,
+                "pending",
+                &describe_diff(&diff, "awaiting review in the dashboard"),
+            )
+            .await;
+        }
+        Err(e) => {
+            fail_run(&state, run_id, &[], &e).await;
+            report_commit_status(&config, &payload.after, "failure", &e).await;
+        }
+    }
+}
+
+/// Fetch every touched definition file and turn the push into a change set
+async fn compute_push_diff(
+    state: &AppState,
+    config: &SyncConfigRow,
+    payload: &PushPayload,
+    changed: &BTreeSet<String>,
+    removed: &BTreeSet<String>,
+) -> Result<Vec<DiffEntry>, String> {
+    let client = github_client()?;
+
+    let existing_names: Vec<String> =
+        sqlx::query_scalar("SELECT name FROM mcp_instances WHERE org_id = $1")
+            .bind(config.org_id)
+            .fetch_all(&state.pool)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+    let existing: BTreeSet<&str> = existing_names.iter().map(String::as_str).collect();
+
+    let mut diff = Vec::new();
+    let mut errors = Vec::new();
+
+    for path in changed {
+        let content = fetch_repo_file(&client, config, path, &payload.after)
+            .await
+            .map_err(|e| format!("{}: {}", path, e))?;
+        match parse_definition(path, &content) {
+            Ok(definition) => {
+                let action = if existing.contains(definition.name.as_str()) {
+                    "update"
+                } else {
+                    "create"
+                };
+                diff.push(DiffEntry {
+                    action: action.to_string(),
+                    name: definition.name.clone(),
+                    path: path.clone(),
+                    definition: Some(definition),
+                });
+            }
+            Err(e) => errors.push(format!("{}: {}", path, e)),
+        }
+    }
+
+    // A removed file's definition only exists at the pre-push commit, so
+    // resolve the MCP it defined from the `before` sha
+    for path in removed {
+        match fetch_repo_file(&client, config, path, &payload.before).await {
+            Ok(content) => match parse_definition(path, &content) {
+                Ok(definition) if existing.contains(definition.name.as_str()) => {
+                    diff.push(DiffEntry {
+                        action: "delete".to_string(),
+                        name: definition.name,
+                        path: path.clone(),
+                        definition: None,
+                    });
+                }
+                // Never created, or already gone - nothing to delete
+                Ok(_) => {}
+                Err(e) => errors.push(format!("{}: {}", path, e)),
+            },
+            Err(e) => {
+                tracing::warn!(
+                    org_id = %config.org_id,
+                    path = %path,
+                    error = %e,
+                    "Could not resolve removed definition file; skipping delete"
+                );
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(format!("Validation failed: {}", errors.join("; ")));
+    }
+
+    Ok(diff)
+}
+
+/// Parse and validate one definition file (JSON or YAML by extension)
+fn parse_definition(path: &str, content: &str) -> Result<McpDefinition, String> {
+    if content.len() > MAX_DEFINITION_BYTES {
+        return Err(format!("definition exceeds {} bytes", MAX_DEFINITION_BYTES));
+    }
+
+    let definition: McpDefinition = if path.ends_with(".json") {
+        serde_json::from_str(content).map_err(|e| format!("invalid JSON: {}", e))?
+    } else {
+        serde_yaml::from_str(content).map_err(|e| format!("invalid YAML: {}", e))?
+    };
+
+    if definition.name.trim().is_empty() {
+        return Err("name cannot be empty".to_string());
+    }
+    if definition.mcp_type.trim().is_empty() {
+        return Err("mcp_type cannot be empty".to_string());
+    }
+    if let Some(ref config) = definition.config {
+        if !config.is_object() {
+            return Err("config must be an object".to_string());
+        }
+        validate_cache_config(config).map_err(|e| e.to_string())?;
+        validate_auth_template(config).map_err(|e| e.to_string())?;
+    }
+
+    Ok(definition)
+}
+
+/// Apply a change set to the org's MCP instances
+async fn apply_diff(state: &AppState, org_id: Uuid, diff: &[DiffEntry]) -> Result<(), ApiError> {
+    // Creates count toward the tier MCP limit just like dashboard creates
+    let creates = diff.iter().filter(|e| e.action == "create").count() as i64;
+    if creates > 0 {
+        let effective_limits = get_org_effective_limits(&state.pool, org_id).await?;
+        let current_count = get_mcp_count(&state.pool, org_id).await?;
+        if current_count + creates > i64::from(effective_limits.max_mcps) {
+            return Err(ApiError::QuotaExceeded(format!(
+                "Applying this sync would exceed the {} MCP limit",
+                effective_limits.max_mcps
+            )));
+        }
+    }
+
+    for entry in diff {
+        match (entry.action.as_str(), &entry.definition) {
+            ("create", Some(definition)) => {
+                let status = if definition.is_active.unwrap_or(true) {
+                    "active"
+                } else {
+                    "inactive"
+                };
+                sqlx::query(
+                    r#"
+                    INSERT INTO mcp_instances
+                        (id, org_id, name, mcp_type, description, config, status, health_status, created_at, updated_at)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, 'unknown', NOW(), NOW())
+                    "#,
+                )
+                .bind(Uuid::new_v4())
+                .bind(org_id)
+                .bind(definition.name.trim())
+                .bind(definition.mcp_type.trim())
+                .bind(&definition.description)
+                .bind(definition.config.clone().unwrap_or(serde_json::json!({})))
+                .bind(status)
+                .execute(&state.pool)
+                .await?;
+            }
+            ("update", Some(definition)) => {
+                sqlx::query(
+                    r#"
+                    UPDATE mcp_instances
+                    SET mcp_type = $3, description = $4, config = $5,
+                        status = CASE WHEN $6 THEN 'active' ELSE 'inactive' END,
+                        updated_at = NOW()
+                    WHERE org_id = $1 AND name = $2
+                    "#,
+                )
+                .bind(org_id)
+                .bind(&entry.name)
+                .bind(definition.mcp_type.trim())
+                .bind(&definition.description)
+                .bind(definition.config.clone().unwrap_or(serde_json::json!({})))
+                .bind(definition.is_active.unwrap_or(true))
+                .execute(&state.pool)
+                .await?;
+            }
+            ("delete", _) => {
+                sqlx::query("DELETE FROM mcp_instances WHERE org_id = $1 AND name = $2")
+                    .bind(org_id)
+                    .bind(&entry.name)
+                    .execute(&state.pool)
+                    .await?;
+            }
+            _ => {
+                tracing::warn!(action = %entry.action, "Skipping malformed sync diff entry");
+            }
+        }
+    }
+
+    state.tool_catalog.invalidate(org_id).await;
+
+    Ok(())
+}
+
+/// Record a run's final diff and status, advancing last_synced_sha for
+/// terminal applied runs
+async fn finish_run(
+    state: &AppState,
+    config: &SyncConfigRow,
+    run_id: Uuid,
+    commit_sha: &str,
+    diff: &[DiffEntry],
+    status: &str,
+) {
+    let diff_json = serde_json::to_value(diff).unwrap_or(serde_json::json!([]));
+    if let Err(e) = sqlx::query("UPDATE github_sync_runs SET status = $2, diff = $3 WHERE id = $1")
+        .bind(run_id)
+        .bind(status)
+        .bind(&diff_json)
+        .execute(&state.pool)
+        .await
+    {
+        tracing::error!(run_id = %run_id, error = %e, "Failed to update GitHub sync run");
+    }
+
+    if status == "applied" {
+        let _ = sqlx::query(
+            "UPDATE github_sync_configs SET last_synced_sha = $2, updated_at = NOW() WHERE org_id = $1",
+        )
+        .bind(config.org_id)
+        .bind(commit_sha)
+        .execute(&state.pool)
+        .await;
+    }
+}
+
+async fn fail_run(state: &AppState, run_id: Uuid, diff: &[DiffEntry], error: &str) {
+    let diff_json = serde_json::to_value(diff).unwrap_or(serde_json::json!([]));
+    if let Err(e) = sqlx::query(
+        "UPDATE github_sync_runs SET status = 'failed', diff = $2, error = $3 WHERE id = $1",
+    )
+    .bind(run_id)
+    .bind(&diff_json)
+    .bind(error)
+    .execute(&state.pool)
+    .await
+    {
+        tracing::error!(run_id = %run_id, error = %e, "Failed to mark GitHub sync run failed");
+    }
+}
+
+fn describe_diff(diff: &[DiffEntry], suffix: &str) -> String {
+    let creates = diff.iter().filter(|e| e.action == "create").count();
+    let updates = diff.iter().filter(|e| e.action == "update").count();
+    let deletes = diff.iter().filter(|e| e.action == "delete").count();
+    format!(
+        "{} to create, {} to update, {} to delete - {}",
+        creates, updates, deletes, suffix
+    )
+}
+
+// =============================================================================
+// GitHub API
+// =============================================================================
+
+fn github_client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .user_agent("PlexMCP-Config-Sync")
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))
+}
+
+/// Fetch one file's raw content from the repo at a specific commit
+async fn fetch_repo_file(
+    client: &reqwest::Client,
+    config: &SyncConfigRow,
+    path: &str,
+    git_ref: &str,
+) -> Result<String, String> {
+    let url = format!(
+        "https://api.github.com/repos/{}/contents/{}?ref={}",
+        config.repo, path, git_ref
+    );
+    let mut request = client
+        .get(&url)
+        .header("accept", "application/vnd.github.raw+json");
+    if let Some(ref token) = config.github_token {
+        request = request.bearer_auth(token);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("GitHub request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("GitHub returned {}", response.status()));
+    }
+    response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read GitHub response: {}", e))
+}
+
+/// Report a `plexmcp/config-sync` commit status; no-op without a token
+async fn report_commit_status(config: &SyncConfigRow, sha: &str, state: &str, description: &str) {
+    let Some(ref token) = config.github_token else {
+        return;
+    };
+    let client = match github_client() {
+        Ok(client) => client,
+        Err(_) => return,
+    };
+
+    // GitHub truncates descriptions over 140 chars; trim proactively
+    let description: String = description.chars().take(140).collect();
+    let url = format!(
+        "https://api.github.com/repos/{}/statuses/{}",
+        config.repo, sha
+    );
+    let result = client
+        .post(&url)
+        .bearer_auth(token)
+        .json(&serde_json::json!({
+            "state": state,
+            "description": description,
+            "context": STATUS_CONTEXT,
+        }))
+        .send()
+        .await;
+
+    match result {
+        Ok(response) if !response.status().is_success() => {
+            tracing::warn!(
+                repo = %config.repo,
+                sha = %sha,
+                status = %response.status(),
+                "GitHub commit status rejected"
+            );
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::warn!(repo = %config.repo, sha = %sha, error = %e, "GitHub commit status failed");
+        }
+    }
+}
+
+// =============================================================================
+// Dashboard Configuration & Review Queue
+// =============================================================================
+
+/// Get the org's GitHub sync connection (owner/admin only)
+pub async fn get_sync_config(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<SyncConfigResponse>> {
+    let org_id = require_org_admin(&auth_user)?;
+
+    let row: (
+        String,
+        String,
+        String,
+        bool,
+        bool,
+        Option<String>,
+        bool,
+        OffsetDateTime,
+    ) = sqlx::query_as(
+        r#"
+            SELECT repo, branch, path_prefix, auto_apply, enabled, last_synced_sha,
+                   github_token IS NOT NULL AS token_set, updated_at
+            FROM github_sync_configs
+            WHERE org_id = $1
+            "#,
+    )
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(SyncConfigResponse {
+        repo: row.0,
+        branch: row.1,
+        path_prefix: row.2,
+        auto_apply: row.3,
+        enabled: row.4,
+        webhook_url: webhook_url(&state, org_id),
+        webhook_secret: None,
+        token_set: row.6,
+        last_synced_sha: row.5,
+        updated_at: row.7,
+    }))
+}
+
+/// Create or replace the org's GitHub sync connection (owner/admin only)
+///
+/// Returns the webhook secret so the caller can configure the GitHub
+/// webhook; it is not retrievable afterwards.
+pub async fn upsert_sync_config(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<UpsertSyncConfigRequest>,
+) -> ApiResult<Json<SyncConfigResponse>> {
+    let org_id = require_org_admin(&auth_user)?;
+
+    let repo = req.repo.trim().to_string();
+    let valid_repo = {
+        let mut parts = repo.splitn(2, '/');
+        matches!(
+            (parts.next(), parts.next()),
+            (Some(owner), Some(name)) if !owner.is_empty() && !name.is_empty() && !name.contains('/')
+        )
+    };
+    if !valid_repo {
+        return Err(ApiError::Validation(
+            "repo must be in owner/name form".to_string(),
+        ));
+    }
+
+    let branch = req
+        .branch
+        .as_deref()
+        .map(str::trim)
+        .filter(|b| !b.is_empty())
+        .unwrap_or("main")
+        .to_string();
+
+    let path_prefix = req
+        .path_prefix
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .unwrap_or("mcps/")
+        .trim_start_matches('/')
+        .to_string();
+
+    let webhook_secret = match req.webhook_secret {
+        Some(secret) => {
+            if !(16..=256).contains(&secret.len()) {
+                return Err(ApiError::Validation(
+                    "webhook_secret must be between 16 and 256 characters".to_string(),
+                ));
+            }
+            secret
+        }
+        None => hex::encode(rand::random::<[u8; 32]>()),
+    };
+
+    let auto_apply = req.auto_apply.unwrap_or(false);
+
+    let (last_synced_sha, updated_at): (Option<String>, OffsetDateTime) = sqlx::query_as(
+        r#"
+        INSERT INTO github_sync_configs
+            (org_id, repo, branch, path_prefix, webhook_secret, github_token, auto_apply, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (org_id) DO UPDATE SET
+            repo = EXCLUDED.repo,
+            branch = EXCLUDED.branch,
+            path_prefix = EXCLUDED.path_prefix,
+            webhook_secret = EXCLUDED.webhook_secret,
+            github_token = EXCLUDED.github_token,
+            auto_apply = EXCLUDED.auto_apply,
+            enabled = true,
+            updated_at = NOW()
+        RETURNING last_synced_sha, updated_at
+        "#,
+    )
+    .bind(org_id)
+    .bind(&repo)
+    .bind(&branch)
+    .bind(&path_prefix)
+    .bind(&webhook_secret)
+    .bind(&req.github_token)
+    .bind(auto_apply)
+    .bind(auth_user.user_id)
+    .fetch_one(&state.pool)
+    .await?;
+
+    tracing::info!(org_id = %org_id, repo = %repo, auto_apply = auto_apply, "GitHub sync configured");
+
+    Ok(Json(SyncConfigResponse {
+        repo,
+        branch,
+        path_prefix,
+        auto_apply,
+        enabled: true,
+        webhook_url: webhook_url(&state, org_id),
+        webhook_secret: Some(webhook_secret),
+        token_set: req.github_token.is_some(),
+        last_synced_sha,
+        updated_at,
+    }))
+}
+
+/// Remove the org's GitHub sync connection (owner/admin only)
+pub async fn delete_sync_config(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<StatusCode> {
+    let org_id = require_org_admin(&auth_user)?;
+
+    let result = sqlx::query("DELETE FROM github_sync_configs WHERE org_id = $1")
+        .bind(org_id)
+        .execute(&state.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List recent sync runs, newest first
+pub async fn list_sync_runs(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<Vec<SyncRunResponse>>> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let runs: Vec<SyncRunResponse> = sqlx::query_as(
+        r#"
+        SELECT id, commit_sha, status, diff, error, reviewed_by, reviewed_at, created_at
+        FROM github_sync_runs
+        WHERE org_id = $1
+        ORDER BY created_at DESC
+        LIMIT $2
+        "#,
+    )
+    .bind(org_id)
+    .bind(RUNS_PAGE_SIZE)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(runs))
+}
+
+/// Approve a run awaiting review, applying its change set
+pub async fn approve_sync_run(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(run_id): Path<Uuid>,
+) -> ApiResult<Json<SyncRunResponse>> {
+    let org_id = require_org_admin(&auth_user)?;
+
+    let (commit_sha, diff_json) = take_reviewable_run(&state, org_id, run_id).await?;
+    let diff: Vec<DiffEntry> = serde_json::from_value(diff_json).map_err(|_| ApiError::Internal)?;
+
+    apply_diff(&state, org_id, &diff).await?;
+
+    let run: SyncRunResponse = sqlx::query_as(
+        r#"
+        UPDATE github_sync_runs
+        SET status = 'applied', reviewed_by = $2, reviewed_at = NOW()
+        WHERE id = $1
+        RETURNING id, commit_sha, status, diff, error, reviewed_by, reviewed_at, created_at
+        "#,
+    )
+    .bind(run_id)
+    .bind(auth_user.user_id)
+    .fetch_one(&state.pool)
+    .await?;
+
+    if let Some(config) = load_sync_config(&state, org_id).await? {
+        let _ = sqlx::query(
+            "UPDATE github_sync_configs SET last_synced_sha = $2, updated_at = NOW() WHERE org_id = $1",
+        )
+        .bind(org_id)
+        .bind(&commit_sha)
+        .execute(&state.pool)
+        .await;
+
+        let description = describe_diff(&diff, "applied after review");
+        tokio::spawn(async move {
+            report_commit_status(&config, &commit_sha, "success", &description).await;
+        });
+    }
+
+    tracing::info!(org_id = %org_id, run_id = %run_id, "GitHub sync run approved");
+
+    Ok(Json(run))
+}
+
+/// Reject a run awaiting review without applying it
+pub async fn reject_sync_run(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(run_id): Path<Uuid>,
+) -> ApiResult<Json<SyncRunResponse>> {
+    let org_id = require_org_admin(&auth_user)?;
+
+    let (commit_sha, _) = take_reviewable_run(&state, org_id, run_id).await?;
+
+    let run: SyncRunResponse = sqlx::query_as(
+        r#"
+        UPDATE github_sync_runs
+        SET status = 'rejected', reviewed_by = $2, reviewed_at = NOW()
+        WHERE id = $1
+        RETURNING id, commit_sha, status, diff, error, reviewed_by, reviewed_at, created_at
+        "#,
+    )
+    .bind(run_id)
+    .bind(auth_user.user_id)
+    .fetch_one(&state.pool)
+    .await?;
+
+    if let Some(config) = load_sync_config(&state, org_id).await? {
+        tokio::spawn(async move {
+            report_commit_status(
+                &config,
+                &commit_sha,
+                "failure",
+                "Changes rejected in review",
+            )
+            .await;
+        });
+    }
+
+    tracing::info!(org_id = %org_id, run_id = %run_id, "GitHub sync run rejected");
+
+    Ok(Json(run))
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+fn require_org_admin(auth_user: &AuthUser) -> Result<Uuid, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+    Ok(org_id)
+}
+
+fn webhook_url(state: &AppState, org_id: Uuid) -> String {
+    format!(
+        "{}/api/v1/webhooks/github/sync/{}",
+        state.config.public_url, org_id
+    )
+}
+
+/// Fetch a run that is awaiting review, returning its commit and diff
+async fn take_reviewable_run(
+    state: &AppState,
+    org_id: Uuid,
+    run_id: Uuid,
+) -> Result<(String, serde_json::Value), ApiError> {
+    let run: Option<(String, String, serde_json::Value)> = sqlx::query_as(
+        "SELECT commit_sha, status, diff FROM github_sync_runs WHERE id = $1 AND org_id = $2",
+    )
+    .bind(run_id)
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    let (commit_sha, status, diff) = run.ok_or(ApiError::NotFound)?;
+    if status != "awaiting_review" {
+        return Err(ApiError::Conflict(format!(
+            "Run is {} and can no longer be reviewed",
+            status
+        )));
+    }
+    Ok((commit_sha, diff))
+}
+
+async fn load_sync_config(
+    state: &AppState,
+    org_id: Uuid,
+) -> Result<Option<SyncConfigRow>, ApiError> {
+    let config: Option<SyncConfigRow> = sqlx::query_as(
+        r#"
+        SELECT org_id, repo, branch, path_prefix, webhook_secret, github_token, auto_apply
+        FROM github_sync_configs
+        WHERE org_id = $1
+        "#,
+    )
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await?;
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_signature() {
+        let secret = "a-webhook-secret";
+        let body = b"{\"ref\":\"refs/heads/main\"}";
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        let header = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+
+        assert!(verify_signature(secret, body, &header));
+        assert!(!verify_signature(secret, b"tampered", &header));
+        assert!(!verify_signature("wrong-secret", body, &header));
+        assert!(!verify_signature(secret, body, "sha1=deadbeef"));
+    }
+
+    #[test]
+    fn test_parse_definition_json_and_yaml() {
+        let json =
+            r#"{"name": "github", "mcp_type": "http", "config": {"url": "https://example.com"}}"#;
+        let parsed = parse_definition("mcps/github.json", json).unwrap();
+        assert_eq!(parsed.name, "github");
+        assert_eq!(parsed.mcp_type, "http");
+
+        let yaml = "name: jira\nmcp_type: http\ndescription: Issue tracker\n";
+        let parsed = parse_definition("mcps/jira.yaml", yaml).unwrap();
+        assert_eq!(parsed.name, "jira");
+        assert_eq!(parsed.description.as_deref(), Some("Issue tracker"));
+
+        assert!(parse_definition("mcps/bad.json", "not json").is_err());
+        assert!(parse_definition("mcps/empty.yaml", "name: ''\nmcp_type: http\n").is_err());
+    }
+
+    #[test]
+    fn test_collect_definition_paths_last_mention_wins() {
+        let commits = vec![
+            PushCommit {
+                added: vec!["mcps/a.json".to_string()],
+                modified: vec!["mcps/b.yaml".to_string(), "README.md".to_string()],
+                removed: vec![],
+            },
+            PushCommit {
+                added: vec![],
+                modified: vec![],
+                removed: vec!["mcps/a.json".to_string(), "mcps/c.yml".to_string()],
+            },
+        ];
+
+        let (changed, removed) = collect_definition_paths(&commits, "mcps/");
+        assert!(changed.contains("mcps/b.yaml"));
+        assert!(!changed.contains("mcps/a.json"));
+        assert!(!changed.contains("README.md"));
+        assert!(removed.contains("mcps/a.json"));
+        assert!(removed.contains("mcps/c.yml"));
+    }
+}
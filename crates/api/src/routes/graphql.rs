@@ -0,0 +1,408 @@
+//! GraphQL read surface for dashboard queries
+//!
+//! The dashboard otherwise chains many REST calls (org, usage, MCPs,
+//! keys, invoices, tickets); `POST /api/v1/graphql` lets it fetch one
+//! shaped document instead. The surface is read-only - mutations stay
+//! on the REST routes where validation and audit logging live. Per-MCP
+//! and per-ticket counts go through dataloaders so a query over N
+//! objects batches into one SQL statement instead of N.
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+use async_graphql::dataloader::{DataLoader, Loader};
+use async_graphql::{
+    ComplexObject, Context, EmptyMutation, EmptySubscription, Object, Schema, SimpleObject,
+};
+use axum::{extract::State, Extension, Json};
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{auth::AuthUser, error::ApiError, state::AppState};
+
+/// Default and maximum list sizes for collection fields
+const DEFAULT_LIMIT: i32 = 50;
+const MAX_LIMIT: i32 = 200;
+
+/// Window for the per-MCP failed health check count
+const MCP_ERROR_WINDOW_DAYS: i32 = 7;
+
+/// Org scope injected per request from the authenticated user
+#[derive(Clone, Copy)]
+struct OrgScope(Uuid);
+
+fn clamp_limit(limit: Option<i32>) -> i64 {
+    limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT) as i64
+}
+
+// =============================================================================
+// Dataloaders
+// =============================================================================
+
+/// Batches "failed health checks in the last 7 days" lookups per MCP
+struct McpErrorCountLoader {
+    pool: PgPool,
+}
+
+impl Loader<Uuid> for McpErrorCountLoader {
+    type Value = i64;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, i64>, Self::Error> {
+        let rows: Vec<(Uuid, i64)> = sqlx::query_as(
+            r#"
+            SELECT mcp_id, COUNT(*)
+            FROM mcp_test_history
+            WHERE mcp_id = ANY($1)
+              AND health_status != 'healthy'
+              AND tested_at >= NOW() - make_interval(days => $2)
+            GROUP BY mcp_id
+            "#,
+        )
+        .bind(keys)
+        .bind(MCP_ERROR_WINDOW_DAYS)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Arc::new)?;
+        Ok(rows.into_iter().collect())
+    }
+}
+
+/// Batches message-count lookups per ticket
+struct TicketMessageCountLoader {
+    pool: PgPool,
+}
+
+impl Loader<Uuid> for TicketMessageCountLoader {
+    type Value = i64;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, i64>, Self::Error> {
+        let rows: Vec<(Uuid, i64)> = sqlx::query_as(
+            r#"
+            SELECT ticket_id, COUNT(*)
+            FROM ticket_messages
+            WHERE ticket_id = ANY($1)
+            GROUP BY ticket_id
+            "#,
+        )
+        .bind(keys)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Arc::new)?;
+        Ok(rows.into_iter().collect())
+    }
+}
+
+// =============================================================================
+// Object Types
+// =============================================================================
+
+/// The caller's organization
+#[derive(SimpleObject, sqlx::FromRow)]
+struct Organization {
+    id: Uuid,
+    name: String,
+    slug: String,
+    subscription_tier: String,
+    created_at: OffsetDateTime,
+}
+
+/// One MCP instance
+#[derive(SimpleObject, sqlx::FromRow)]
+#[graphql(complex)]
+struct Mcp {
+    id: Uuid,
+    name: String,
+    mcp_type: String,
+    status: String,
+    health_status: String,
+    last_health_check_at: Option<OffsetDateTime>,
+    created_at: OffsetDateTime,
+}
+
+#[ComplexObject]
+impl Mcp {
+    /// Failed health checks in the last 7 days (dataloader-batched)
+    async fn recent_error_count(&self, ctx: &Context<'_>) -> async_graphql::Result<i64> {
+        let loader = ctx.data_unchecked::<DataLoader<McpErrorCountLoader>>();
+        Ok(loader.load_one(self.id).await?.unwrap_or(0))
+    }
+}
+
+/// One API key (metadata only; the key itself is never exposed)
+#[derive(SimpleObject, sqlx::FromRow)]
+struct ApiKey {
+    id: Uuid,
+    name: String,
+    key_prefix: String,
+    request_count: i64,
+    last_used_at: Option<OffsetDateTime>,
+    expires_at: Option<OffsetDateTime>,
+    created_at: OffsetDateTime,
+}
+
+/// Usage totals for one day
+#[derive(SimpleObject, sqlx::FromRow)]
+struct UsageDay {
+    day: time::Date,
+    requests: i64,
+    errors: i64,
+}
+
+/// Usage over a trailing window
+#[derive(SimpleObject)]
+struct Usage {
+    total_requests: i64,
+    total_tokens: i64,
+    total_errors: i64,
+    days: Vec<UsageDay>,
+}
+
+/// One invoice
+#[derive(SimpleObject, sqlx::FromRow)]
+struct Invoice {
+    id: Uuid,
+    amount_cents: i32,
+    currency: String,
+    status: String,
+    description: Option<String>,
+    paid_at: Option<OffsetDateTime>,
+    created_at: OffsetDateTime,
+}
+
+/// One support ticket
+#[derive(SimpleObject, sqlx::FromRow)]
+#[graphql(complex)]
+struct Ticket {
+    id: Uuid,
+    ticket_number: String,
+    subject: String,
+    status: String,
+    priority: String,
+    created_at: OffsetDateTime,
+}
+
+#[ComplexObject]
+impl Ticket {
+    /// Messages on this ticket (dataloader-batched)
+    async fn message_count(&self, ctx: &Context<'_>) -> async_graphql::Result<i64> {
+        let loader = ctx.data_unchecked::<DataLoader<TicketMessageCountLoader>>();
+        Ok(loader.load_one(self.id).await?.unwrap_or(0))
+    }
+}
+
+// =============================================================================
+// Query Root
+// =============================================================================
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// The caller's organization
+    async fn organization(&self, ctx: &Context<'_>) -> async_graphql::Result<Organization> {
+        let pool = ctx.data_unchecked::<PgPool>();
+        let OrgScope(org_id) = *ctx.data_unchecked::<OrgScope>();
+        let org = sqlx::query_as(
+            "SELECT id, name, slug, subscription_tier, created_at FROM organizations WHERE id = $1",
+        )
+        .bind(org_id)
+        .fetch_one(pool)
+        .await?;
+        Ok(org)
+    }
+
+    /// The org's MCP instances, newest first
+    async fn mcps(&self, ctx: &Context<'_>, limit: Option<i32>) -> async_graphql::Result<Vec<Mcp>> {
+        let pool = ctx.data_unchecked::<PgPool>();
+        let OrgScope(org_id) = *ctx.data_unchecked::<OrgScope>();
+        let mcps = sqlx::query_as(
+            r#"
+            SELECT id, name, mcp_type, status, health_status, last_health_check_at, created_at
+            FROM mcp_instances
+            WHERE org_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(org_id)
+        .bind(clamp_limit(limit))
+        .fetch_all(pool)
+        .await?;
+        Ok(mcps)
+    }
+
+    /// The org's API keys, newest first
+    async fn api_keys(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<i32>,
+    ) -> async_graphql::Result<Vec<ApiKey>> {
+        let pool = ctx.data_unchecked::<PgPool>();
+        let OrgScope(org_id) = *ctx.data_unchecked::<OrgScope>();
+        let keys = sqlx::query_as(
+            r#"
+            SELECT id, name, key_prefix, request_count, last_used_at, expires_at, created_at
+            FROM api_keys
+            WHERE org_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(org_id)
+        .bind(clamp_limit(limit))
+        .fetch_all(pool)
+        .await?;
+        Ok(keys)
+    }
+
+    /// Usage totals and daily series over a trailing window (default 30 days)
+    async fn usage(&self, ctx: &Context<'_>, days: Option<i32>) -> async_graphql::Result<Usage> {
+        let pool = ctx.data_unchecked::<PgPool>();
+        let OrgScope(org_id) = *ctx.data_unchecked::<OrgScope>();
+        let days = days.unwrap_or(30).clamp(1, 365);
+
+        let day_rows: Vec<UsageDay> = sqlx::query_as(
+            r#"
+            SELECT (period_hour AT TIME ZONE 'UTC')::date AS day,
+                   COALESCE(SUM(total_requests), 0)::BIGINT AS requests,
+                   COALESCE(SUM(total_errors), 0)::BIGINT AS errors
+            FROM usage_aggregates
+            WHERE org_id = $1 AND period_hour >= NOW() - make_interval(days => $2)
+            GROUP BY day
+            ORDER BY day
+            "#,
+        )
+        .bind(org_id)
+        .bind(days)
+        .fetch_all(pool)
+        .await?;
+
+        let (total_requests, total_tokens, total_errors): (i64, i64, i64) = sqlx::query_as(
+            r#"
+            SELECT COALESCE(SUM(total_requests), 0)::BIGINT,
+                   COALESCE(SUM(total_tokens), 0)::BIGINT,
+                   COALESCE(SUM(total_errors), 0)::BIGINT
+            FROM usage_aggregates
+            WHERE org_id = $1 AND period_hour >= NOW() - make_interval(days => $2)
+            "#,
+        )
+        .bind(org_id)
+        .bind(days)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(Usage {
+            total_requests,
+            total_tokens,
+            total_errors,
+            days: day_rows,
+        })
+    }
+
+    /// The org's invoices, newest first
+    async fn invoices(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<i32>,
+    ) -> async_graphql::Result<Vec<Invoice>> {
+        let pool = ctx.data_unchecked::<PgPool>();
+        let OrgScope(org_id) = *ctx.data_unchecked::<OrgScope>();
+        let invoices = sqlx::query_as(
+            r#"
+            SELECT id, amount_cents, currency, status, description, paid_at, created_at
+            FROM invoices
+            WHERE org_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(org_id)
+        .bind(clamp_limit(limit))
+        .fetch_all(pool)
+        .await?;
+        Ok(invoices)
+    }
+
+    /// The org's support tickets, newest first
+    async fn tickets(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<i32>,
+    ) -> async_graphql::Result<Vec<Ticket>> {
+        let pool = ctx.data_unchecked::<PgPool>();
+        let OrgScope(org_id) = *ctx.data_unchecked::<OrgScope>();
+        let tickets = sqlx::query_as(
+            r#"
+            SELECT id, ticket_number, subject, status::text AS status,
+                   priority::text AS priority, created_at
+            FROM support_tickets
+            WHERE organization_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(org_id)
+        .bind(clamp_limit(limit))
+        .fetch_all(pool)
+        .await?;
+        Ok(tickets)
+    }
+}
+
+type DashboardSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Schema is stateless; per-request data (pool, org scope, loaders) is
+/// attached to each request instead
+fn schema() -> &'static DashboardSchema {
+    static SCHEMA: OnceLock<DashboardSchema> = OnceLock::new();
+    SCHEMA.get_or_init(|| Schema::build(QueryRoot, EmptyMutation, EmptySubscription).finish())
+}
+
+/// POST /api/v1/graphql - execute a dashboard query
+pub async fn graphql_handler(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(request): Json<async_graphql::Request>,
+) -> Result<Json<async_graphql::Response>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let request = request
+        .data(state.pool.clone())
+        .data(OrgScope(org_id))
+        .data(DataLoader::new(
+            McpErrorCountLoader {
+                pool: state.pool.clone(),
+            },
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            TicketMessageCountLoader {
+                pool: state.pool.clone(),
+            },
+            tokio::spawn,
+        ));
+
+    Ok(Json(schema().execute(request).await))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_is_read_only() {
+        let sdl = schema().sdl();
+        assert!(sdl.contains("type Query"));
+        assert!(!sdl.contains("type Mutation"));
+    }
+
+    #[test]
+    fn test_clamp_limit() {
+        assert_eq!(clamp_limit(None), DEFAULT_LIMIT as i64);
+        assert_eq!(clamp_limit(Some(0)), 1);
+        assert_eq!(clamp_limit(Some(10_000)), MAX_LIMIT as i64);
+    }
+}
@@ -1,15 +1,81 @@
 //! Health check endpoints
+//!
+//! Probe semantics follow Kubernetes conventions so self-hosters can wire
+//! them straight into a Deployment:
+//!
+//! - `/health/live` - liveness: process is up, never checks dependencies
+//! - `/health/startup` - startup: 503 until initialization (migrations,
+//!   bootstrap) has completed, then 200 for the life of the process
+//! - `/health/ready` - readiness: 503 while draining or when the database
+//!   is unreachable
+//! - `POST /health/drain` - preStop hook: flips readiness to 503 so the
+//!   pod is removed from endpoints before SIGTERM arrives. Disabled unless
+//!   `ENABLE_DRAIN_ENDPOINT=true` since it is unauthenticated.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use axum::{extract::State, http::StatusCode, Json};
 use serde::Serialize;
 
 use crate::state::AppState;
 
+/// Shared probe state: startup completion and drain status
+#[derive(Clone)]
+pub struct ProbeState {
+    started: Arc<AtomicBool>,
+    draining: Arc<AtomicBool>,
+}
+
+impl ProbeState {
+    /// Create probe state in the "starting" phase
+    pub fn new() -> Self {
+        Self {
+            started: Arc::new(AtomicBool::new(false)),
+            draining: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Mark initialization complete (startup probe begins passing)
+    pub fn mark_started(&self) {
+        self.started.store(true, Ordering::Release);
+    }
+
+    /// Begin draining (readiness probe begins failing)
+    pub fn start_draining(&self) {
+        self.draining.store(true, Ordering::Release);
+    }
+
+    /// Whether initialization has completed
+    pub fn is_started(&self) -> bool {
+        self.started.load(Ordering::Acquire)
+    }
+
+    /// Whether the instance is draining ahead of shutdown
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Acquire)
+    }
+}
+
+impl Default for ProbeState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(Serialize)]
 pub struct HealthResponse {
     pub status: String,
     pub version: String,
     pub database: String,
+    /// Audit/usage records parked in the local disk buffer awaiting replay
+    /// (non-zero means the database was recently unreachable)
+    pub audit_buffer_depth: u64,
+}
+
+#[derive(Serialize)]
+pub struct DrainResponse {
+    pub draining: bool,
 }
 
 /// Health check endpoint
@@ -36,6 +102,7 @@ pub async fn health(State(state): State<AppState>) -> (StatusCode, Json<HealthRe
             },
             version: env!("CARGO_PKG_VERSION").to_string(),
             database: db_status,
+            audit_buffer_depth: state.audit_buffer.depth(),
         }),
     )
 }
@@ -45,10 +112,72 @@ pub async fn liveness() -> StatusCode {
     StatusCode::OK
 }
 
+/// Startup probe (503 until migrations and bootstrap have completed)
+pub async fn startup(State(state): State<AppState>) -> StatusCode {
+    if state.probes.is_started() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+}
+
 /// Readiness probe (checks if the service is ready to accept traffic)
 pub async fn readiness(State(state): State<AppState>) -> StatusCode {
+    // A draining instance must fail readiness regardless of database health
+    // so the load balancer stops routing to it
+    if state.probes.is_draining() {
+        return StatusCode::SERVICE_UNAVAILABLE;
+    }
+
     match sqlx::query("SELECT 1").execute(&state.pool).await {
         Ok(_) => StatusCode::OK,
         Err(_) => StatusCode::SERVICE_UNAVAILABLE,
     }
 }
+
+/// preStop drain hook: start failing readiness so the instance is removed
+/// from rotation before shutdown. Idempotent; returns 404 unless
+/// `ENABLE_DRAIN_ENDPOINT=true`.
+pub async fn drain(State(state): State<AppState>) -> (StatusCode, Json<DrainResponse>) {
+    if !state.config.drain_enabled {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(DrainResponse { draining: false }),
+        );
+    }
+
+    if !state.probes.is_draining() {
+        tracing::info!("Drain requested - readiness probe now failing");
+        state.probes.start_draining();
+    }
+
+    (StatusCode::OK, Json(DrainResponse { draining: true }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_probe_state_lifecycle() {
+        let probes = ProbeState::new();
+        assert!(!probes.is_started());
+        assert!(!probes.is_draining());
+
+        probes.mark_started();
+        assert!(probes.is_started());
+
+        probes.start_draining();
+        assert!(probes.is_draining());
+        // Started stays true through a drain
+        assert!(probes.is_started());
+    }
+
+    #[test]
+    fn test_probe_state_shared_across_clones() {
+        let probes = ProbeState::new();
+        let clone = probes.clone();
+        probes.start_draining();
+        assert!(clone.is_draining());
+    }
+}
@@ -27,6 +27,10 @@ use plexmcp_shared::types::{CustomLimits, EffectiveLimits, SubscriptionTier};
 // Invitation expiry in days
 const INVITATION_EXPIRY_DAYS: i64 = 7;
 
+// Custom expiry bounds for programmatic invitation links
+const MIN_LINK_EXPIRY_HOURS: i64 = 1;
+const MAX_LINK_EXPIRY_HOURS: i64 = 720; // 30 days
+
 // =============================================================================
 // Request/Response Types
 // =============================================================================
@@ -54,6 +58,30 @@ pub struct InvitationsListResponse {
     pub total: i64,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct CreateInvitationLinkRequest {
+    pub email: String,
+    pub role: String,
+    /// Hours until the link expires (1-720, default 168)
+    pub expires_in_hours: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InvitationLinkResponse {
+    pub id: Uuid,
+    pub email: String,
+    pub role: String,
+    /// Signed deep link for the invitee; embeds the invitation token
+    pub accept_url: String,
+    pub org_name: String,
+    /// Links are consumed on acceptance and cannot be reused
+    pub single_use: bool,
+    #[serde(with = "time::serde::rfc3339")]
+    pub expires_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ValidateInvitationQuery {
     pub token: String,
@@ -330,7 +358,8 @@ pub async fn create_invitation(
 
     // Send invitation email
     let accept_url = format!("{}/accept-invite?token={}", state.config.public_url, token);
-    let email_service = state.security_email.clone();
+    // Send from the org's verified sender domain when one is configured
+    let email_service = state.security_email.for_org(&state.pool, org_id).await;
     let to_email = email.clone();
     let org_name = org_info.0.clone();
     let inviter_name = inviter_info.0.clone();
@@ -349,6 +378,19 @@ pub async fn create_invitation(
             .await;
     });
 
+    // Fan out to org-registered outbound webhooks
+    plexmcp_shared::org_webhooks::emit(
+        &state.pool,
+        org_id,
+        "member.invited",
+        serde_json::json!({
+            "invitation_id": invitation_id,
+            "email": email,
+            "role": req.role,
+        }),
+    )
+    .await;
+
     let invitation: InvitationRow = sqlx::query_as(
         "SELECT id, org_id, email, role, token, invited_by, expires_at, accepted_at, created_at FROM invitations WHERE id = $1"
     )
@@ -368,6 +410,153 @@ pub async fn create_invitation(
     ))
 }
 
+/// Create a programmatic invitation link (API-key friendly)
+///
+/// Unlike `create_invitation` this sends no email; it returns the signed
+/// deep-link URL directly so enterprise portals can embed "Join our org"
+/// flows in their own onboarding. The link presets the role, is
+/// single-use (consumed on acceptance), and supports a custom expiry.
+/// Re-posting for an email with a pending invitation rotates the token
+/// and returns a fresh link instead of failing.
+pub async fn create_invitation_link(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<CreateInvitationLinkRequest>,
+) -> ApiResult<(StatusCode, Json<InvitationLinkResponse>)> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    // API keys are org-level credentials; JWT callers need owner/admin
+    if !["api_key", "owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    // Validate role preset
+    let valid_roles = ["admin", "member", "viewer"];
+    if !valid_roles.contains(&req.role.as_str()) {
+        return Err(ApiError::Validation(format!(
+            "Invalid role. Must be one of: {}",
+            valid_roles.join(", ")
+        )));
+    }
+
+    // Admins can't mint admin invitations (API keys act as the org)
+    if auth_user.role == "admin" && req.role == "admin" {
+        return Err(ApiError::Forbidden);
+    }
+
+    let email = req.email.to_lowercase().trim().to_string();
+    if !is_valid_email(&email) {
+        return Err(ApiError::Validation("Invalid email format".to_string()));
+    }
+
+    let expiry_hours = req.expires_in_hours.unwrap_or(INVITATION_EXPIRY_DAYS * 24);
+    if !(MIN_LINK_EXPIRY_HOURS..=MAX_LINK_EXPIRY_HOURS).contains(&expiry_hours) {
+        return Err(ApiError::Validation(format!(
+            "expires_in_hours must be between {} and {}",
+            MIN_LINK_EXPIRY_HOURS, MAX_LINK_EXPIRY_HOURS
+        )));
+    }
+
+    // Check if user already exists in this org
+    let exists_user: Option<(bool,)> =
+        sqlx::query_as("SELECT EXISTS(SELECT 1 FROM users WHERE email = $1 AND org_id = $2)")
+            .bind(&email)
+            .bind(org_id)
+            .fetch_optional(&state.pool)
+            .await?;
+
+    if exists_user.map(|r| r.0).unwrap_or(false) {
+        return Err(ApiError::Validation(
+            "User already exists in this organization".to_string(),
+        ));
+    }
+
+    // Reuse a pending invitation for this email if one exists; otherwise
+    // enforce the team member limit before creating a new one
+    let pending: Option<(Uuid,)> = sqlx::query_as(
+        "SELECT id FROM invitations WHERE email = $1 AND org_id = $2 AND accepted_at IS NULL",
+    )
+    .bind(&email)
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    if pending.is_none() {
+        let effective_limits = get_org_effective_limits(&state.pool, org_id).await?;
+        let max_members = effective_limits.max_team_members;
+
+        if max_members != u32::MAX {
+            let active_count: (i64,) = sqlx::query_as(
+                "SELECT COUNT(*) FROM organization_members WHERE org_id = $1 AND status = 'active'",
+            )
+            .bind(org_id)
+            .fetch_one(&state.pool)
+            .await?;
+
+            let pending_invites: (i64,) = sqlx::query_as(
+                "SELECT COUNT(*) FROM invitations WHERE org_id = $1 AND accepted_at IS NULL AND expires_at > NOW()",
+            )
+            .bind(org_id)
+            .fetch_one(&state.pool)
+            .await?;
+
+            if active_count.0 + pending_invites.0 >= max_members as i64 {
+                return Err(ApiError::Validation(format!(
+                    "Team member limit reached ({}/{}). Contact support to increase your limit.",
+                    active_count.0 + pending_invites.0,
+                    max_members
+                )));
+            }
+        }
+    }
+
+    let invitation_id = pending.map(|p| p.0).unwrap_or_else(Uuid::new_v4);
+    let token = generate_invitation_token(invitation_id, &state.config.api_key_hmac_secret);
+    let expires_at = OffsetDateTime::now_utc() + time::Duration::hours(expiry_hours);
+
+    let (created_at,): (OffsetDateTime,) = sqlx::query_as(
+        r#"
+        INSERT INTO invitations (id, org_id, email, role, token, invited_by, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (id) DO UPDATE SET
+            role = EXCLUDED.role,
+            token = EXCLUDED.token,
+            expires_at = EXCLUDED.expires_at
+        RETURNING created_at
+        "#,
+    )
+    .bind(invitation_id)
+    .bind(org_id)
+    .bind(&email)
+    .bind(&req.role)
+    .bind(&token)
+    .bind(auth_user.user_id)
+    .bind(expires_at)
+    .fetch_one(&state.pool)
+    .await?;
+
+    let org_info: (String,) = sqlx::query_as("SELECT name FROM organizations WHERE id = $1")
+        .bind(org_id)
+        .fetch_one(&state.pool)
+        .await?;
+
+    let accept_url = format!("{}/accept-invite?token={}", state.config.public_url, token);
+
+    Ok((
+        StatusCode::CREATED,
+        Json(InvitationLinkResponse {
+            id: invitation_id,
+            email,
+            role: req.role,
+            accept_url,
+            org_name: org_info.0,
+            single_use: true,
+            expires_at,
+            created_at,
+        }),
+    ))
+}
+
 /// List all pending invitations for the organization
 pub async fn list_invitations(
     State(state): State<AppState>,
@@ -482,7 +671,7 @@ pub async fn resend_invitation(
 
     // Send invitation email
     let accept_url = format!("{}/accept-invite?token={}", state.config.public_url, token);
-    let email_service = state.security_email.clone();
+    let email_service = state.security_email.for_org(&state.pool, org_id).await;
     let to_email = invitation.email.clone();
     let org_name = org_info.0;
     let role = invitation.role.clone();
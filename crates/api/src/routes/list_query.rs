@@ -0,0 +1,256 @@
+//! Shared pagination, filtering and sorting conventions for list endpoints
+//!
+//! Larger orgs page through hundreds of MCPs, keys and members, so the
+//! SDK-facing list endpoints all speak the same dialect: `page` /
+//! `per_page` (capped at 100), `sort` against a per-endpoint whitelist,
+//! `order` (`asc`/`desc`), plus endpoint-specific filters. Responses use
+//! the shared [`PaginatedResponse`] envelope so clients get a total count
+//! with every page.
+//!
+//! [`ListQuery`] assembles the WHERE clause from typed filters - column
+//! names are `&'static str` supplied by the handler and values are always
+//! bound, so user input never reaches the SQL text.
+//!
+//! [`PaginatedResponse`]: plexmcp_shared::types::PaginatedResponse
+
+use serde::Deserialize;
+use sqlx::{postgres::PgRow, FromRow, PgPool, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use crate::error::ApiError;
+
+/// Default and maximum page sizes shared by all list endpoints
+const DEFAULT_PER_PAGE: i64 = 50;
+const MAX_PER_PAGE: i64 = 100;
+
+/// Common pagination and sorting params, flattened into each endpoint's
+/// query struct
+#[derive(Debug, Default, Deserialize)]
+pub struct ListParams {
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+    /// Sort key, validated against the endpoint's whitelist
+    pub sort: Option<String>,
+    /// "asc" or "desc" (default desc - newest/most recent first)
+    pub order: Option<String>,
+}
+
+impl ListParams {
+    pub fn page(&self) -> i64 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn per_page(&self) -> i64 {
+        self.per_page
+            .unwrap_or(DEFAULT_PER_PAGE)
+            .clamp(1, MAX_PER_PAGE)
+    }
+
+    pub fn offset(&self) -> i64 {
+        (self.page() - 1) * self.per_page()
+    }
+
+    /// Resolve `sort`/`order` into an ORDER BY expression
+    ///
+    /// `allowed` maps sort keys to SQL expressions; the first entry is the
+    /// default. Unknown keys or orders are a validation error rather than
+    /// a silent fallback so SDK typos surface immediately.
+    pub fn order_by(&self, allowed: &[(&str, &str)]) -> Result<String, ApiError> {
+        let expression = match &self.sort {
+            Some(key) => allowed
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, sql)| *sql)
+                .ok_or_else(|| {
+                    ApiError::Validation(format!(
+                        "Unknown sort key '{}'; valid keys are {}",
+                        key,
+                        allowed
+                            .iter()
+                            .map(|(name, _)| *name)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ))
+                })?,
+            None => allowed[0].1,
+        };
+
+        let direction = match self.order.as_deref() {
+            Some("asc") => "ASC",
+            Some("desc") | None => "DESC",
+            Some(other) => {
+                return Err(ApiError::Validation(format!(
+                    "Unknown order '{}'; use asc or desc",
+                    other
+                )))
+            }
+        };
+
+        // NULLS LAST keeps never-used/never-seen rows at the end in both
+        // directions (matters for last_used_at-style columns)
+        Ok(format!("{} {} NULLS LAST", expression, direction))
+    }
+}
+
+/// A filter condition; values are always bound parameters
+enum Filter {
+    /// `column = value`
+    EqText(&'static str, String),
+    /// `column = value` for UUID columns
+    EqUuid(&'static str, Uuid),
+    /// `column ILIKE '%value%'`
+    Contains(&'static str, String),
+    /// `column ? value` - JSONB array membership (e.g. `config->'tags'`)
+    JsonHas(&'static str, String),
+}
+
+/// Builds the count and page queries for one list request
+pub struct ListQuery {
+    table: &'static str,
+    filters: Vec<Filter>,
+}
+
+impl ListQuery {
+    pub fn new(table: &'static str) -> Self {
+        Self {
+            table,
+            filters: Vec::new(),
+        }
+    }
+
+    /// Require a UUID column to equal a value (org scoping, creator scoping)
+    pub fn eq_uuid(mut self, column: &'static str, value: Uuid) -> Self {
+        self.filters.push(Filter::EqUuid(column, value));
+        self
+    }
+
+    /// Require a text column to equal a value, when the filter is present
+    pub fn eq_text(mut self, column: &'static str, value: Option<String>) -> Self {
+        if let Some(value) = value {
+            self.filters.push(Filter::EqText(column, value));
+        }
+        self
+    }
+
+    /// Require a text column to contain a substring (case-insensitive),
+    /// when the filter is present
+    pub fn contains(mut self, column: &'static str, value: Option<String>) -> Self {
+        if let Some(value) = value {
+            self.filters.push(Filter::Contains(column, value));
+        }
+        self
+    }
+
+    /// Require a JSONB array expression to contain a string element, when
+    /// the filter is present
+    pub fn json_has(mut self, expression: &'static str, value: Option<String>) -> Self {
+        if let Some(value) = value {
+            self.filters.push(Filter::JsonHas(expression, value));
+        }
+        self
+    }
+
+    fn push_where(&self, qb: &mut QueryBuilder<'_, Postgres>) {
+        qb.push(" WHERE 1=1");
+        for filter in &self.filters {
+            qb.push(" AND ");
+            match filter {
+                Filter::EqText(column, value) => {
+                    qb.push(*column).push(" = ").push_bind(value.clone());
+                }
+                Filter::EqUuid(column, value) => {
+                    qb.push(*column).push(" = ").push_bind(*value);
+                }
+                Filter::Contains(column, value) => {
+                    qb.push(*column)
+                        .push(" ILIKE '%' || ")
+                        .push_bind(value.clone())
+                        .push(" || '%'");
+                }
+                Filter::JsonHas(expression, value) => {
+                    qb.push(*expression).push(" ? ").push_bind(value.clone());
+                }
+            }
+        }
+    }
+
+    /// Run the count and page queries, returning the rows plus the total
+    /// matching the filters (ignoring pagination)
+    ///
+    /// `order_by` must come from [`ListParams::order_by`] - it is spliced
+    /// into the SQL text, never from raw user input.
+    pub async fn fetch_page<T>(
+        &self,
+        pool: &PgPool,
+        columns: &str,
+        order_by: &str,
+        params: &ListParams,
+    ) -> Result<(Vec<T>, i64), sqlx::Error>
+    where
+        T: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+    {
+        let total: i64 = {
+            let mut qb = QueryBuilder::new(format!("SELECT COUNT(*) FROM {}", self.table));
+            self.push_where(&mut qb);
+            qb.build_query_scalar().fetch_one(pool).await?
+        };
+
+        let mut qb = QueryBuilder::new(format!("SELECT {} FROM {}", columns, self.table));
+        self.push_where(&mut qb);
+        qb.push(format!(" ORDER BY {}", order_by));
+        qb.push(" LIMIT ")
+            .push_bind(params.per_page())
+            .push(" OFFSET ")
+            .push_bind(params.offset());
+
+        let rows = qb.build_query_as::<T>().fetch_all(pool).await?;
+        Ok((rows, total))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SORT_KEYS: &[(&str, &str)] = &[("created_at", "created_at"), ("name", "name")];
+
+    #[test]
+    fn test_pagination_defaults_and_caps() {
+        let params = ListParams::default();
+        assert_eq!(params.page(), 1);
+        assert_eq!(params.per_page(), DEFAULT_PER_PAGE);
+        assert_eq!(params.offset(), 0);
+
+        let params = ListParams {
+            page: Some(3),
+            per_page: Some(500),
+            ..Default::default()
+        };
+        assert_eq!(params.per_page(), MAX_PER_PAGE);
+        assert_eq!(params.offset(), 2 * MAX_PER_PAGE);
+    }
+
+    #[test]
+    fn test_order_by_whitelist() {
+        let params = ListParams {
+            sort: Some("name".to_string()),
+            order: Some("asc".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(params.order_by(SORT_KEYS).unwrap(), "name ASC NULLS LAST");
+
+        // Default sort is the first whitelist entry, descending
+        let params = ListParams::default();
+        assert_eq!(
+            params.order_by(SORT_KEYS).unwrap(),
+            "created_at DESC NULLS LAST"
+        );
+
+        // Unknown keys error instead of silently falling back
+        let params = ListParams {
+            sort: Some("secret_column".to_string()),
+            ..Default::default()
+        };
+        assert!(params.order_by(SORT_KEYS).is_err());
+    }
+}
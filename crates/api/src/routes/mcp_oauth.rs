@@ -0,0 +1,356 @@
+//! OAuth connection routes for upstream MCPs
+//!
+//! Orgs connect an MCP to an OAuth provider in two steps: `connect` stores
+//! the provider details plus a CSRF state and returns the authorization
+//! URL; the dashboard relays the provider redirect to `callback`, which
+//! exchanges the code and stores the tokens in the vault
+//! (`mcp_oauth_credentials`). Secrets are never returned - `status` only
+//! reports expiry and scope.
+
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    auth::totp::{decrypt_secret, encrypt_secret, parse_encryption_key},
+    auth::AuthUser,
+    error::ApiError,
+    mcp::oauth,
+    state::AppState,
+};
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct ConnectOAuthRequest {
+    /// Provider authorization page, e.g. https://github.com/login/oauth/authorize
+    pub authorization_endpoint: String,
+    /// Provider token endpoint used for code exchange and refresh
+    pub token_endpoint: String,
+    pub client_id: String,
+    /// Omitted for public clients (PKCE-only providers)
+    pub client_secret: Option<String>,
+    /// Space-separated scopes to request
+    pub scope: Option<String>,
+    /// Where the provider sends the user back (dashboard callback page)
+    pub redirect_uri: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConnectOAuthResponse {
+    /// Send the user here to authorize
+    pub authorization_url: String,
+    pub state: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OAuthCallbackQuery {
+    pub code: String,
+    pub state: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OAuthStatusResponse {
+    pub connected: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub expires_at: Option<OffsetDateTime>,
+    #[serde(
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub connected_at: Option<OffsetDateTime>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Start the authorization-code flow for an MCP
+///
+/// Stores provider details plus a single-use CSRF state (10 minute TTL)
+/// and returns the URL to send the user to.
+pub async fn connect_mcp_oauth(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(mcp_id): Path<Uuid>,
+    Json(req): Json<ConnectOAuthRequest>,
+) -> Result<Json<ConnectOAuthResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    verify_mcp_ownership(&state, mcp_id, org_id).await?;
+
+    for (field, value) in [
+        ("authorization_endpoint", &req.authorization_endpoint),
+        ("token_endpoint", &req.token_endpoint),
+        ("redirect_uri", &req.redirect_uri),
+    ] {
+        if !value.starts_with("https://") && !value.starts_with("http://") {
+            return Err(ApiError::Validation(format!(
+                "{} must be an http(s) URL",
+                field
+            )));
+        }
+    }
+    if req.client_id.trim().is_empty() {
+        return Err(ApiError::Validation("client_id is required".to_string()));
+    }
+
+    let encryption_key = load_encryption_key(&state)?;
+    let secret = req
+        .client_secret
+        .as_deref()
+        .map(|s| encrypt_secret(s, &encryption_key))
+        .transpose()
+        .map_err(|e| {
+            tracing::error!("Failed to encrypt OAuth client secret: {}", e);
+            ApiError::Internal
+        })?;
+
+    // Single-use CSRF token echoed back by the provider
+    let state_token = hex::encode(rand::random::<[u8; 32]>());
+
+    // Opportunistic cleanup of stale flows (10 minute TTL)
+    sqlx::query("DELETE FROM mcp_oauth_states WHERE expires_at < NOW()")
+        .execute(&state.pool)
+        .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO mcp_oauth_states (
+            state, org_id, mcp_id, token_endpoint, client_id,
+            client_secret_encrypted, client_secret_nonce,
+            redirect_uri, scope, created_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        "#,
+    )
+    .bind(&state_token)
+    .bind(org_id)
+    .bind(mcp_id)
+    .bind(&req.token_endpoint)
+    .bind(&req.client_id)
+    .bind(secret.as_ref().map(|(e, _)| e))
+    .bind(secret.as_ref().map(|(_, n)| n))
+    .bind(&req.redirect_uri)
+    .bind(&req.scope)
+    .bind(auth_user.user_id)
+    .execute(&state.pool)
+    .await?;
+
+    let mut params = vec![
+        ("response_type", "code"),
+        ("client_id", req.client_id.as_str()),
+        ("redirect_uri", req.redirect_uri.as_str()),
+        ("state", state_token.as_str()),
+    ];
+    if let Some(scope) = req.scope.as_deref() {
+        params.push(("scope", scope));
+    }
+    let authorization_url = reqwest::Url::parse_with_params(&req.authorization_endpoint, &params)
+        .map_err(|_| ApiError::Validation("authorization_endpoint is not a valid URL".to_string()))?
+        .to_string();
+
+    Ok(Json(ConnectOAuthResponse {
+        authorization_url,
+        state: state_token,
+    }))
+}
+
+/// Complete the flow: exchange the code and store tokens in the vault
+pub async fn oauth_callback(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(mcp_id): Path<Uuid>,
+    Query(query): Query<OAuthCallbackQuery>,
+) -> Result<Json<OAuthStatusResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    #[derive(sqlx::FromRow)]
+    struct StateRow {
+        token_endpoint: String,
+        client_id: String,
+        client_secret_encrypted: Option<String>,
+        client_secret_nonce: Option<String>,
+        redirect_uri: String,
+    }
+
+    // Consume the state - single use, bound to both the org and the MCP
+    let row: Option<StateRow> = sqlx::query_as(
+        r#"
+        DELETE FROM mcp_oauth_states
+        WHERE state = $1 AND org_id = $2 AND mcp_id = $3 AND expires_at > NOW()
+        RETURNING token_endpoint, client_id, client_secret_encrypted,
+                  client_secret_nonce, redirect_uri
+        "#,
+    )
+    .bind(&query.state)
+    .bind(org_id)
+    .bind(mcp_id)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    let Some(row) = row else {
+        return Err(ApiError::Validation(
+            "Unknown or expired OAuth state - restart the connect flow".to_string(),
+        ));
+    };
+
+    let encryption_key = load_encryption_key(&state)?;
+    let client_secret = match (&row.client_secret_encrypted, &row.client_secret_nonce) {
+        (Some(enc), Some(nonce)) => {
+            Some(decrypt_secret(enc, nonce, &encryption_key).map_err(|e| {
+                tracing::error!("Failed to decrypt OAuth client secret: {}", e);
+                ApiError::Internal
+            })?)
+        }
+        _ => None,
+    };
+
+    let tokens = oauth::exchange_code(
+        &row.token_endpoint,
+        &row.client_id,
+        client_secret.as_deref(),
+        &query.code,
+        &row.redirect_uri,
+    )
+    .await
+    .map_err(|e| ApiError::Validation(format!("OAuth code exchange failed: {}", e)))?;
+
+    oauth::store_credentials(
+        &state.pool,
+        &encryption_key,
+        org_id,
+        mcp_id,
+        &row.token_endpoint,
+        &row.client_id,
+        client_secret.as_deref(),
+        &tokens,
+        auth_user.user_id,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!(mcp_id = %mcp_id, "Failed to store OAuth credentials: {}", e);
+        ApiError::Internal
+    })?;
+
+    tracing::info!(
+        org_id = %org_id,
+        mcp_id = %mcp_id,
+        "Upstream MCP connected via OAuth"
+    );
+
+    get_oauth_status(State(state), Extension(auth_user), Path(mcp_id)).await
+}
+
+/// Report connection state without exposing any token material
+pub async fn get_oauth_status(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(mcp_id): Path<Uuid>,
+) -> Result<Json<OAuthStatusResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    verify_mcp_ownership(&state, mcp_id, org_id).await?;
+
+    let row: Option<(
+        String,
+        Option<String>,
+        Option<OffsetDateTime>,
+        OffsetDateTime,
+    )> = sqlx::query_as(
+        r#"
+            SELECT token_type, scope, expires_at, created_at
+            FROM mcp_oauth_credentials
+            WHERE mcp_id = $1 AND org_id = $2
+            "#,
+    )
+    .bind(mcp_id)
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    Ok(Json(match row {
+        Some((token_type, scope, expires_at, created_at)) => OAuthStatusResponse {
+            connected: true,
+            token_type: Some(token_type),
+            scope,
+            expires_at,
+            connected_at: Some(created_at),
+        },
+        None => OAuthStatusResponse {
+            connected: false,
+            token_type: None,
+            scope: None,
+            expires_at: None,
+            connected_at: None,
+        },
+    }))
+}
+
+/// Drop stored OAuth credentials for an MCP
+///
+/// The MCP keeps proxying with whatever auth its config carries; with
+/// `auth_type: "oauth"` and no vault entry, upstream calls will fail
+/// until reconnected.
+pub async fn disconnect_mcp_oauth(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(mcp_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let result = sqlx::query("DELETE FROM mcp_oauth_credentials WHERE mcp_id = $1 AND org_id = $2")
+        .bind(mcp_id)
+        .bind(org_id)
+        .execute(&state.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Confirm the MCP exists and belongs to the caller's org
+async fn verify_mcp_ownership(
+    state: &AppState,
+    mcp_id: Uuid,
+    org_id: Uuid,
+) -> Result<(), ApiError> {
+    let exists: Option<(Uuid,)> =
+        sqlx::query_as("SELECT id FROM mcp_instances WHERE id = $1 AND org_id = $2")
+            .bind(mcp_id)
+            .bind(org_id)
+            .fetch_optional(&state.pool)
+            .await?;
+
+    if exists.is_none() {
+        return Err(ApiError::NotFound);
+    }
+    Ok(())
+}
+
+/// Parse TOTP_ENCRYPTION_KEY for vault encryption
+fn load_encryption_key(state: &AppState) -> Result<[u8; 32], ApiError> {
+    parse_encryption_key(&state.config.totp_encryption_key).map_err(|e| {
+        tracing::error!("TOTP_ENCRYPTION_KEY not usable for OAuth encryption: {}", e);
+        ApiError::Internal
+    })
+}
@@ -19,10 +19,12 @@ use axum::{
 use futures::stream;
 #[cfg(feature = "billing")]
 use plexmcp_billing::UsageEvent;
+#[cfg(feature = "billing")]
 use plexmcp_shared::SubscriptionTier;
 use std::convert::Infallible;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+#[cfg(feature = "billing")]
 use time::OffsetDateTime;
 use uuid::Uuid;
 
@@ -39,11 +41,45 @@ use crate::state::AppState;
 ///
 /// Accepts JSON-RPC 2.0 requests and routes them to configured upstream MCPs.
 /// Supports host-based routing (subdomain/custom domain) with API key authentication.
+///
+/// Each request runs under a trace context - continued from an incoming
+/// `traceparent` header or started fresh per the configured sample rate -
+/// so auth, handler dispatch and upstream calls show up as one trace in
+/// the collector.
 pub async fn handle_mcp_request(
     State(state): State<AppState>,
     headers: HeaderMap,
     body: Bytes,
 ) -> Response {
+    let trace_ctx = headers
+        .get("traceparent")
+        .and_then(|v| v.to_str().ok())
+        .and_then(crate::otel::TraceContext::from_traceparent)
+        .unwrap_or_else(|| crate::otel::TraceContext::new_root(state.config.otel_sample_rate));
+    let request_start = std::time::SystemTime::now();
+
+    let response = crate::otel::CURRENT_TRACE
+        .scope(trace_ctx.clone(), process_mcp_request(state, headers, body))
+        .await;
+
+    if trace_ctx.sampled {
+        crate::otel::record_span(crate::otel::SpanRecord::finish(
+            &trace_ctx,
+            "mcp.request",
+            request_start,
+            vec![("http.status_code", response.status().as_u16().to_string())],
+            !response.status().is_success(),
+        ));
+    }
+
+    response
+}
+
+/// The actual proxy pipeline: host resolution, auth, quota checks,
+/// dispatch and response shaping
+async fn process_mcp_request(state: AppState, headers: HeaderMap, body: Bytes) -> Response {
+    let auth_start = std::time::SystemTime::now();
+
     // 1. Extract Host header for routing
     let host = headers
         .get(header::HOST)
@@ -108,24 +144,26 @@ pub async fn handle_mcp_request(
     // 4. Validate API key and get the org_id plus MCP access control settings
     let ip_address = extract_ip_from_headers(&headers);
     let user_agent = extract_user_agent(&headers);
-    let api_key_validation = match validate_api_key(&state, &api_key, ip_address, user_agent).await
-    {
-        Ok(validation) => validation,
-        Err(e) => {
-            return error_response(
-                None,
-                JsonRpcError {
-                    code: -32002,
-                    message: e,
-                    data: None,
-                },
-                StatusCode::UNAUTHORIZED,
-            );
-        }
-    };
+    let origin = extract_origin(&headers);
+    let api_key_validation =
+        match validate_api_key(&state, &api_key, ip_address, origin, user_agent).await {
+            Ok(validation) => validation,
+            Err(e) => {
+                return error_response(
+                    None,
+                    JsonRpcError {
+                        code: -32002,
+                        message: e,
+                        data: None,
+                    },
+                    StatusCode::UNAUTHORIZED,
+                );
+            }
+        };
 
-    // 4.5. Check rate limit for this API key
-    match state
+    // 4.5. Check rate limit for this API key; the result is kept so the
+    // final response carries X-RateLimit-* headers
+    let rate_limit_result = match state
         .rate_limiter
         .check_api_key(
             api_key_validation.org_id,
@@ -141,24 +179,23 @@ pub async fn handle_mcp_request(
                 "Rate limit exceeded"
             );
 
-            return error_response(
+            let response = error_response(
                 None,
                 JsonRpcError {
                     code: -32029, // Custom rate limit error code
                     message: "Rate limit exceeded".to_string(),
                     data: Some(serde_json::json!({
                         "retry_after_seconds": result.retry_after_seconds,
-                        "limit_rpm": api_key_validation.rate_limit_rpm,
+                        "limit_rpm": result.limit,
                         "remaining_minute": result.remaining_minute,
                         "reset_at": result.reset_at.unix_timestamp(),
                     })),
                 },
                 StatusCode::TOO_MANY_REQUESTS,
             );
+            return attach_rate_limit_headers(response, Some(&result));
         }
-        Ok(_) => {
-            // Rate limit check passed
-        }
+        Ok(result) => Some(result),
         Err(e) => {
             // Fail-open: log error but allow request (availability > strict enforcement)
             tracing::error!(
@@ -166,8 +203,9 @@ pub async fn handle_mcp_request(
                 error = %e,
                 "Rate limit check failed, allowing request"
             );
+            None
         }
-    }
+    };
 
     // 5. Verify API key belongs to the resolved org (if host-based routing was used)
     let org_id = if let Some(ref resolved) = resolved_org {
@@ -188,8 +226,18 @@ pub async fn handle_mcp_request(
         api_key_validation.org_id
     };
 
+    // Auth phase complete: host resolution, key validation, rate limit, org match
+    crate::otel::record_child_span(
+        "mcp.auth",
+        auth_start,
+        vec![("org.id", org_id.to_string())],
+        false,
+    );
+
     // 6. Check monthly usage limit (Free tier blocks when over limit) - only when billing feature is enabled
     #[cfg(feature = "billing")]
+    let usage_warning: Option<serde_json::Value>;
+    #[cfg(feature = "billing")]
     {
         let limit_check = match check_monthly_limit(&state, org_id).await {
             Ok(check) => check,
@@ -263,6 +311,16 @@ pub async fn handle_mcp_request(
                 StatusCode::TOO_MANY_REQUESTS,
             );
         }
+
+        // Build an in-band warning when the org is near its quota so
+        // developers see it in tool output before requests start failing.
+        // Suppressed per key (suppress_usage_warnings) or globally
+        // (USAGE_WARNING_THRESHOLD_PERCENT=0).
+        usage_warning = build_usage_warning(
+            &state,
+            &limit_check,
+            api_key_validation.suppress_usage_warnings,
+        );
     }
 
     // 7. Check if org is paused due to spend cap (only when billing feature is enabled)
@@ -341,6 +399,46 @@ pub async fn handle_mcp_request(
         );
     }
 
+    // Per-org method allowlist (enterprise lockdown): orgs can restrict
+    // the proxy to a subset of methods; blocked attempts are audit logged
+    if let Some(allowlist) = crate::mcp::method_allowlist::load(&state.pool, org_id).await {
+        if !crate::mcp::method_allowlist::is_allowed(&allowlist, &request.method) {
+            log_mcp_request(
+                state.pool.clone(),
+                state.audit_buffer.clone(),
+                McpRequestLog {
+                    organization_id: Some(org_id),
+                    mcp_server_name: "none".to_string(),
+                    endpoint_path: request.method.clone(),
+                    http_status_code: 403,
+                    api_key_id: Some(api_key_validation.api_key_id),
+                    error_message: Some("Method not allowed by organization policy".to_string()),
+                    error_code: Some("method_not_allowed".to_string()),
+                    metadata: Some(serde_json::json!({
+                        "blocked_by": "method_allowlist",
+                    })),
+                    ..Default::default()
+                },
+            );
+            return error_response(
+                request.id,
+                JsonRpcError {
+                    code: -32031, // Custom method-not-allowed code
+                    message: format!(
+                        "Method '{}' is not allowed by your organization's method allowlist",
+                        request.method
+                    ),
+                    data: Some(serde_json::json!({
+                        "reason": "method_not_allowed",
+                        "method": request.method,
+                        "allowed_methods": allowlist,
+                    })),
+                },
+                StatusCode::FORBIDDEN,
+            );
+        }
+    }
+
     // Check if this is a streaming request (for future SSE support)
     let wants_stream = headers
         .get(header::ACCEPT)
@@ -351,6 +449,76 @@ pub async fn handle_mcp_request(
     // Start timing for latency tracking
     let start_time = Instant::now();
 
+    // Session context methods (context/*) are served locally from the
+    // per-API-key KV store; they never reach an upstream MCP but still
+    // count toward usage like any other proxied request
+    if crate::mcp::session_context::is_context_method(&request.method) {
+        let response = crate::mcp::session_context::handle_method(
+            &state,
+            org_id,
+            api_key_validation.api_key_id,
+            &request,
+        )
+        .await;
+        let tracked_response = McpTrackedResponse::without_mcps(response);
+        let latency_ms = start_time.elapsed().as_millis() as i32;
+        log_request(
+            &state,
+            &api_key,
+            org_id,
+            &request,
+            &tracked_response,
+            latency_ms,
+        )
+        .await;
+        return attach_rate_limit_headers(
+            json_response(tracked_response.response),
+            rate_limit_result.as_ref(),
+        );
+    }
+
+    // Duplicate detection: identical method+params re-sent by the same
+    // key inside a short window are counted and rolled up for the
+    // diagnostics endpoint; when the target MCP opted in to
+    // `dedup.serve_cached` the previous tools/call response is replayed
+    // instead of hitting the upstream again
+    let dedup_observation = state
+        .request_dedup
+        .observe(api_key_validation.api_key_id, &request)
+        .await;
+    if dedup_observation.prior_count > 0 {
+        record_duplicate_request(
+            &state,
+            org_id,
+            api_key_validation.api_key_id,
+            &request,
+            &dedup_observation.request_hash,
+        );
+
+        if let Some(mut cached) = dedup_observation.cached_response.clone() {
+            if request.method == "tools/call"
+                && duplicate_replay_enabled(&state, org_id, &request).await
+            {
+                cached.id = request.id.clone();
+                let tracked_response = McpTrackedResponse::without_mcps(cached);
+                let latency_ms = start_time.elapsed().as_millis() as i32;
+                log_request(
+                    &state,
+                    &api_key,
+                    org_id,
+                    &request,
+                    &tracked_response,
+                    latency_ms,
+                )
+                .await;
+                return attach_rate_limit_headers(
+                    json_response(tracked_response.response),
+                    rate_limit_result.as_ref(),
+                );
+            }
+        }
+    }
+
     // Create handler and process request with MCP filtering
     // Returns McpTrackedResponse which includes which MCPs were accessed
     // Uses shared MCP client for HTTP session caching across requests
@@ -358,10 +526,94 @@ pub async fn handle_mcp_request(
         state.pool.clone(),
         Arc::new(state.config.clone()),
         state.mcp_client.clone(),
-    );
-    let tracked_response = handler
-        .handle_request_filtered(org_id, request.clone(), mcp_filter)
-        .await;
+    )
+    .with_moderation(state.moderation.clone())
+    .with_tool_cache(state.tool_cache.clone());
+
+    // Streaming clients get aggregation fan-outs chunk by chunk: each
+    // upstream's result is emitted as a `partial` event the moment it
+    // arrives, so slow MCPs don't block fast ones. Usage is logged once
+    // the final aggregated result has been assembled.
+    if wants_stream {
+        if let Some(aggregation) = handler
+            .handle_aggregation_streaming(org_id, request.clone(), &mcp_filter)
+            .await
+        {
+            let log_state = state.clone();
+            tokio::spawn(async move {
+                if let Ok(tracked_response) = aggregation.completion.await {
+                    let latency_ms = start_time.elapsed().as_millis() as i32;
+                    log_request(
+                        &log_state,
+                        &api_key,
+                        org_id,
+                        &request,
+                        &tracked_response,
+                        latency_ms,
+                    )
+                    .await;
+                }
+            });
+            return attach_rate_limit_headers(
+                partial_stream_response(aggregation.events),
+                rate_limit_result.as_ref(),
+            );
+        }
+    }
+
+    // Dispatch under a child span so upstream calls nest beneath it
+    let handle_ctx = crate::otel::current_context().map(|ctx| ctx.child());
+    let handle_start = std::time::SystemTime::now();
+    let tracked_response = match handle_ctx.clone() {
+        Some(ctx) => {
+            crate::otel::CURRENT_TRACE
+                .scope(
+                    ctx,
+                    handler.handle_request_filtered(org_id, request.clone(), mcp_filter),
+                )
+                .await
+        }
+        None => {
+            handler
+                .handle_request_filtered(org_id, request.clone(), mcp_filter)
+                .await
+        }
+    };
+    if let Some(ctx) = handle_ctx {
+        if ctx.sampled {
+            crate::otel::record_span(crate::otel::SpanRecord::finish(
+                &ctx,
+                "mcp.handle",
+                handle_start,
+                vec![("rpc.method", request.method.clone())],
+                tracked_response.response.error.is_some(),
+            ));
+        }
+    }
+
+    // Append the quota warning to `_meta` on successful responses
+    #[cfg(feature = "billing")]
+    let tracked_response = match usage_warning {
+        Some(warning) => {
+            let mut tracked = tracked_response;
+            attach_usage_warning(&mut tracked.response, warning);
+            tracked
+        }
+        None => tracked_response,
+    };
+
+    // Keep successful tools/call responses around so duplicates arriving
+    // within the dedup window can be replayed
+    if request.method == "tools/call" {
+        state
+            .request_dedup
+            .store_response(
+                api_key_validation.api_key_id,
+                &dedup_observation.request_hash,
+                &tracked_response.response,
+            )
+            .await;
+    }
 
     // Calculate latency
     let latency_ms = start_time.elapsed().as_millis() as i32;
@@ -378,15 +630,372 @@ pub async fn handle_mcp_request(
     )
     .await;
 
-    if wants_stream {
+    let mut response = if wants_stream {
         // Return SSE stream
         stream_response(tracked_response.response)
     } else {
         // Return JSON response
         json_response(tracked_response.response)
+    };
+    // Identity and accessed upstreams for the access log middleware
+    response
+        .extensions_mut()
+        .insert(crate::access_log::AccessContext {
+            org_id: Some(org_id),
+            api_key_id: Some(api_key_validation.api_key_id),
+            user_id: None,
+            upstream_mcps: tracked_response
+                .accessed_mcp_ids
+                .iter()
+                .map(|id| id.to_string())
+                .collect(),
+        });
+    attach_rate_limit_headers(response, rate_limit_result.as_ref())
+}
+
+/// Handle persistent streaming connections on GET /mcp
+///
+/// Two transports hang off the same route:
+/// - `Upgrade: websocket` - bidirectional JSON-RPC over a WebSocket; requests
+///   are answered in-band and server-initiated events arrive as
+///   `notifications/message` notifications
+/// - `Accept: text/event-stream` - an SSE stream of server-initiated events
+///   (the session ID is returned in the `Mcp-Session-Id` header and the
+///   initial `session` event); requests still go through POST /mcp
+///
+/// Authentication matches POST /mcp: host-based org resolution plus an
+/// X-API-Key that must belong to the resolved org.
+pub async fn handle_mcp_stream(
+    State(state): State<AppState>,
+    ws: Option<axum::extract::WebSocketUpgrade>,
+    headers: HeaderMap,
+) -> Response {
+    let (org_id, validation, api_key) = match authorize_stream_connection(&state, &headers).await {
+        Ok(authorized) => authorized,
+        Err(response) => return response,
+    };
+
+    let mcp_filter = McpFilter {
+        mode: validation.mcp_access_mode.clone(),
+        allowed_ids: validation.allowed_mcp_ids.clone(),
+    };
+
+    // WebSocket upgrade takes precedence over SSE
+    if let Some(ws) = ws {
+        return ws.on_upgrade(move |socket| {
+            mcp_websocket_session(state, socket, org_id, validation, mcp_filter, api_key)
+        });
+    }
+
+    let wants_sse = headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.contains("text/event-stream"))
+        .unwrap_or(false);
+    if !wants_sse {
+        return error_response(
+            None,
+            JsonRpcError::invalid_request(
+                "GET /mcp requires a WebSocket upgrade or Accept: text/event-stream",
+            ),
+            StatusCode::BAD_REQUEST,
+        );
+    }
+
+    // Register the session and stream events until the client disconnects;
+    // dead sessions are pruned on the next delivery attempt
+    let (session_id, rx) = state.mcp_streams.open(org_id).await;
+    tracing::info!(org_id = %org_id, session_id = %session_id, "MCP SSE session opened");
+
+    use futures::StreamExt;
+    let initial = stream::once(async move {
+        Ok::<_, Infallible>(
+            Event::default()
+                .event("session")
+                .data(serde_json::json!({ "session_id": session_id }).to_string()),
+        )
+    });
+    let events = initial.chain(stream_from_channel(rx));
+
+    let mut response = Sse::new(events)
+        .keep_alive(
+            axum::response::sse::KeepAlive::new()
+                .interval(Duration::from_secs(15))
+                .event(
+                    Event::default()
+                        .event("heartbeat")
+                        .data("{\"type\":\"heartbeat\"}"),
+                ),
+        )
+        .into_response();
+    if let Ok(value) = session_id.to_string().parse() {
+        response.headers_mut().insert("Mcp-Session-Id", value);
+    }
+    response
+}
+
+/// Run one WebSocket session: answer JSON-RPC requests in-band and forward
+/// server-initiated events as notifications
+async fn mcp_websocket_session(
+    state: AppState,
+    mut socket: axum::extract::ws::WebSocket,
+    org_id: Uuid,
+    validation: ApiKeyValidation,
+    mcp_filter: McpFilter,
+    api_key: String,
+) {
+    use axum::extract::ws::Message;
+    use futures::StreamExt;
+
+    let (session_id, mut rx) = state.mcp_streams.open(org_id).await;
+    tracing::info!(org_id = %org_id, session_id = %session_id, "MCP WebSocket session opened");
+
+    let handler = McpProxyHandler::new(
+        state.pool.clone(),
+        Arc::new(state.config.clone()),
+        state.mcp_client.clone(),
+    )
+    .with_moderation(state.moderation.clone())
+    .with_tool_cache(state.tool_cache.clone());
+
+    loop {
+        tokio::select! {
+            event = rx.recv() => {
+                let Some(event) = event else { break };
+                let payload = event.to_jsonrpc_notification().to_string();
+                if socket.send(Message::Text(payload)).await.is_err() {
+                    break;
+                }
+            }
+            message = socket.next() => {
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(_)) => continue, // Ping/Pong/Binary
+                    Some(Err(e)) => {
+                        tracing::debug!(session_id = %session_id, error = %e, "WebSocket receive error");
+                        break;
+                    }
+                };
+
+                let response = handle_ws_message(
+                    &state,
+                    &handler,
+                    org_id,
+                    &validation,
+                    &mcp_filter,
+                    &api_key,
+                    &text,
+                )
+                .await;
+                if let Some(response) = response {
+                    let payload = serde_json::to_string(&response).unwrap_or_default();
+                    if socket.send(Message::Text(payload)).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    state.mcp_streams.close(session_id).await;
+    tracing::info!(org_id = %org_id, session_id = %session_id, "MCP WebSocket session closed");
+}
+
+/// Process one inbound WebSocket frame as a JSON-RPC request
+///
+/// Returns None for notifications (no response expected per JSON-RPC).
+/// Rate limits and usage quotas apply per message, mirroring POST /mcp.
+async fn handle_ws_message(
+    state: &AppState,
+    handler: &McpProxyHandler,
+    org_id: Uuid,
+    validation: &ApiKeyValidation,
+    mcp_filter: &McpFilter,
+    api_key: &str,
+    text: &str,
+) -> Option<JsonRpcResponse> {
+    let request: JsonRpcRequest = match serde_json::from_str(text) {
+        Ok(req) => req,
+        Err(e) => {
+            return Some(JsonRpcResponse::error(
+                None,
+                JsonRpcError::parse_error(format!("Invalid JSON: {}", e)),
+            ));
+        }
+    };
+    if request.jsonrpc != "2.0" {
+        return Some(JsonRpcResponse::error(
+            request.id,
+            JsonRpcError::invalid_request("Invalid JSON-RPC version, expected 2.0"),
+        ));
+    }
+
+    // Per-message rate limit (same limiter as POST; fail-open on errors)
+    if let Ok(result) = state
+        .rate_limiter
+        .check_api_key(org_id, validation.api_key_id, validation.rate_limit_rpm)
+        .await
+    {
+        if !result.allowed {
+            return Some(JsonRpcResponse::error(
+                request.id,
+                JsonRpcError {
+                    code: -32029,
+                    message: "Rate limit exceeded".to_string(),
+                    data: Some(serde_json::json!({
+                        "retry_after_seconds": result.retry_after_seconds,
+                        "limit_rpm": validation.rate_limit_rpm,
+                    })),
+                },
+            ));
+        }
+    }
+
+    // Monthly quota applies per message too (Free tier blocks when over)
+    #[cfg(feature = "billing")]
+    {
+        if let Ok(check) = check_monthly_limit(state, org_id).await {
+            if !check.allowed {
+                return Some(JsonRpcResponse::error(
+                    request.id,
+                    JsonRpcError {
+                        code: -32029,
+                        message: "Monthly request limit exceeded. Upgrade your plan to continue."
+                            .to_string(),
+                        data: None,
+                    },
+                ));
+            }
+        }
+    }
+
+    let is_notification = request.id.is_none();
+    let start_time = Instant::now();
+    let tracked_response = handler
+        .handle_request_filtered(org_id, request.clone(), mcp_filter.clone())
+        .await;
+    let latency_ms = start_time.elapsed().as_millis() as i32;
+
+    log_request(
+        state,
+        api_key,
+        org_id,
+        &request,
+        &tracked_response,
+        latency_ms,
+    )
+    .await;
+
+    if is_notification {
+        None
+    } else {
+        Some(tracked_response.response)
     }
 }
 
+/// Resolve the org from the Host header and validate the API key for a
+/// streaming connection (same checks as steps 1-5 of POST /mcp)
+async fn authorize_stream_connection(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<(Uuid, ApiKeyValidation, String), Response> {
+    let host = headers
+        .get(header::HOST)
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or("");
+
+    let resolved_org = match state.host_resolver.resolve(host).await {
+        Ok(resolved) => resolved,
+        Err(HostResolveError::NotFound(host)) => {
+            return Err(error_response(
+                None,
+                JsonRpcError {
+                    code: -32001,
+                    message: format!("Unknown host: {}", host),
+                    data: None,
+                },
+                StatusCode::NOT_FOUND,
+            ));
+        }
+        Err(HostResolveError::ReservedSubdomain(subdomain)) => {
+            return Err(error_response(
+                None,
+                JsonRpcError {
+                    code: -32001,
+                    message: format!("Reserved subdomain: {}", subdomain),
+                    data: None,
+                },
+                StatusCode::FORBIDDEN,
+            ));
+        }
+        Err(HostResolveError::DatabaseError(e)) => {
+            tracing::error!("Host resolution database error: {}", e);
+            return Err(error_response(
+                None,
+                JsonRpcError {
+                    code: -32003,
+                    message: "Internal server error".to_string(),
+                    data: None,
+                },
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ));
+        }
+    };
+
+    let api_key = match extract_api_key(headers) {
+        Some(key) => key,
+        None => {
+            return Err(error_response(
+                None,
+                JsonRpcError {
+                    code: -32001,
+                    message: "Missing X-API-Key header".to_string(),
+                    data: None,
+                },
+                StatusCode::UNAUTHORIZED,
+            ));
+        }
+    };
+
+    let ip_address = extract_ip_from_headers(headers);
+    let user_agent = extract_user_agent(headers);
+    let origin = extract_origin(headers);
+    let validation = match validate_api_key(state, &api_key, ip_address, origin, user_agent).await {
+        Ok(validation) => validation,
+        Err(e) => {
+            return Err(error_response(
+                None,
+                JsonRpcError {
+                    code: -32002,
+                    message: e,
+                    data: None,
+                },
+                StatusCode::UNAUTHORIZED,
+            ));
+        }
+    };
+
+    let org_id = if let Some(ref resolved) = resolved_org {
+        if validation.org_id != resolved.org_id {
+            return Err(error_response(
+                None,
+                JsonRpcError {
+                    code: -32004,
+                    message: "API key does not belong to this organization".to_string(),
+                    data: None,
+                },
+                StatusCode::FORBIDDEN,
+            ));
+        }
+        resolved.org_id
+    } else {
+        validation.org_id
+    };
+
+    Ok((org_id, validation, api_key))
+}
+
 /// Extract API key from headers
 fn extract_api_key(headers: &HeaderMap) -> Option<String> {
     // Try X-API-Key header first
@@ -415,6 +1024,9 @@ struct ApiKeyValidation {
     mcp_access_mode: String,
     allowed_mcp_ids: Option<Vec<Uuid>>,
     rate_limit_rpm: u32,
+    /// Whether in-band quota warnings are suppressed for this key
+    #[cfg_attr(not(feature = "billing"), allow(dead_code))]
+    suppress_usage_warnings: bool,
 }
 
 /// MCP filter settings for access control
@@ -524,11 +1136,78 @@ async fn check_monthly_limit(state: &AppState, org_id: Uuid) -> Result<MonthlyLi
     })
 }
 
+/// Build the in-band quota warning when the org is at or above the
+/// configured threshold (only available with billing feature)
+///
+/// Returns `None` when warnings are disabled, suppressed for this key, the
+/// tier is unlimited, or usage is below the threshold.
+#[cfg(feature = "billing")]
+fn build_usage_warning(
+    state: &AppState,
+    limit_check: &MonthlyLimitCheck,
+    suppressed_for_key: bool,
+) -> Option<serde_json::Value> {
+    let threshold = state.config.usage_warning_threshold_percent;
+    if threshold == 0 || suppressed_for_key {
+        return None;
+    }
+    if limit_check.limit == 0 || limit_check.limit == u64::MAX {
+        return None;
+    }
+
+    let percent_used = limit_check.current_usage.max(0) as f64 / limit_check.limit as f64 * 100.0;
+    if percent_used < threshold as f64 {
+        return None;
+    }
+
+    let resets_at = limit_check
+        .resets_at
+        .format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_else(|_| "unknown".to_string());
+
+    Some(serde_json::json!({
+        "level": "warning",
+        "message": format!(
+            "Your organization has used {:.0}% of its monthly request quota ({} of {}).",
+            percent_used,
+            format_number(limit_check.current_usage.max(0) as u64),
+            format_number(limit_check.limit),
+        ),
+        "current_usage": limit_check.current_usage,
+        "limit": limit_check.limit,
+        "percent_used": percent_used,
+        "resets_at": resets_at,
+        "suppress_hint": "Set suppress_usage_warnings on this API key to opt out",
+    }))
+}
+
+/// Append the quota warning under `result._meta` (MCP metadata convention)
+///
+/// Error responses and non-object results are left untouched so the
+/// warning never breaks protocol-level consumers.
+#[cfg(feature = "billing")]
+fn attach_usage_warning(response: &mut JsonRpcResponse, warning: serde_json::Value) {
+    if let Some(serde_json::Value::Object(result)) = response.result.as_mut() {
+        let meta = result
+            .entry("_meta")
+            .or_insert_with(|| serde_json::json!({}));
+        if let Some(meta) = meta.as_object_mut() {
+            meta.insert("plexmcp/usage_warning".to_string(), warning);
+        }
+    }
+}
+
 /// Validate API key and return the org_id plus MCP access control settings
+///
+/// Besides format/signature and status checks, this enforces the key's
+/// network restrictions: when `allowed_cidrs` or `allowed_origins` is set,
+/// requests from outside those ranges/origins are rejected and recorded as
+/// suspicious activity.
 async fn validate_api_key(
     state: &AppState,
     api_key: &str,
     ip_address: Option<String>,
+    origin: Option<String>,
     user_agent: Option<String>,
 ) -> Result<ApiKeyValidation, String> {
     // Extract key prefix for safe logging (first 12 chars)
@@ -568,12 +1247,21 @@ async fn validate_api_key(
         mcp_access_mode: String,
         allowed_mcp_ids: Option<Vec<Uuid>>,
         rate_limit_rpm: i32,
+        suppress_usage_warnings: bool,
+        key_age_basis: time::OffsetDateTime,
+        max_api_key_age_days: Option<i32>,
+        allowed_cidrs: Option<Vec<String>>,
+        allowed_origins: Option<Vec<String>>,
     }
 
     let result: Option<ApiKeyRow> = sqlx::query_as(
         r#"
         SELECT ak.id, ak.org_id, ak.status, ak.expires_at, o.status as org_status,
-               ak.mcp_access_mode, ak.allowed_mcp_ids, ak.rate_limit_rpm
+               ak.mcp_access_mode, ak.allowed_mcp_ids, ak.rate_limit_rpm,
+               ak.suppress_usage_warnings,
+               COALESCE(ak.last_rotated_at, ak.created_at) AS key_age_basis,
+               o.max_api_key_age_days,
+               ak.allowed_cidrs, ak.allowed_origins
         FROM api_keys ak
         JOIN organizations o ON ak.org_id = o.id
         WHERE ak.key_hash = $1
@@ -624,6 +1312,86 @@ async fn validate_api_key(
                 }
             }
 
+            // Check the org's rotation policy: keys past the maximum age are
+            // treated as expired until they are rotated
+            if let Some(max_age_days) = row.max_api_key_age_days {
+                let max_age = time::Duration::days(i64::from(max_age_days));
+                if row.key_age_basis + max_age < time::OffsetDateTime::now_utc() {
+                    log_mcp_auth_failure(
+                        state.pool.clone(),
+                        key_prefix.clone(),
+                        "key_max_age_exceeded".to_string(),
+                        ip_address.clone(),
+                        user_agent.clone(),
+                    );
+                    return Err(format!(
+                        "API key exceeds the organization's maximum key age of {} days and must be rotated",
+                        max_age_days
+                    ));
+                }
+            }
+
+            // Enforce the key's IP allowlist; a restricted key with no
+            // resolvable source IP is rejected rather than waved through
+            if let Some(cidrs) = row.allowed_cidrs.as_deref().filter(|c| !c.is_empty()) {
+                let allowed = ip_address
+                    .as_deref()
+                    .is_some_and(|ip| crate::auth::ApiKeyManager::ip_allowed(ip, cidrs));
+                if !allowed {
+                    log_mcp_auth_failure(
+                        state.pool.clone(),
+                        key_prefix.clone(),
+                        "ip_not_in_allowlist".to_string(),
+                        ip_address.clone(),
+                        user_agent.clone(),
+                    );
+                    let _ = crate::alerting::trigger_suspicious_activity_alert(
+                        &state.alert_service,
+                        None,
+                        Some(row.org_id),
+                        "api_key_ip_restriction_violation",
+                        serde_json::json!({
+                            "api_key_id": row.id,
+                            "key_prefix": key_prefix,
+                            "source_ip": ip_address,
+                        }),
+                        ip_address.as_deref(),
+                    )
+                    .await;
+                    return Err("API key is not allowed from this IP address".to_string());
+                }
+            }
+
+            // Enforce the key's origin allowlist the same way
+            if let Some(origins) = row.allowed_origins.as_deref().filter(|o| !o.is_empty()) {
+                let allowed = origin
+                    .as_deref()
+                    .is_some_and(|o| crate::auth::ApiKeyManager::origin_allowed(o, origins));
+                if !allowed {
+                    log_mcp_auth_failure(
+                        state.pool.clone(),
+                        key_prefix.clone(),
+                        "origin_not_in_allowlist".to_string(),
+                        ip_address.clone(),
+                        user_agent.clone(),
+                    );
+                    let _ = crate::alerting::trigger_suspicious_activity_alert(
+                        &state.alert_service,
+                        None,
+                        Some(row.org_id),
+                        "api_key_origin_restriction_violation",
+                        serde_json::json!({
+                            "api_key_id": row.id,
+                            "key_prefix": key_prefix,
+                            "origin": origin,
+                        }),
+                        ip_address.as_deref(),
+                    )
+                    .await;
+                    return Err("API key is not allowed from this origin".to_string());
+                }
+            }
+
             // Check if MCP access is disabled
             if row.mcp_access_mode == "none" {
                 log_mcp_auth_failure(
@@ -642,6 +1410,7 @@ async fn validate_api_key(
                 mcp_access_mode: row.mcp_access_mode,
                 allowed_mcp_ids: row.allowed_mcp_ids,
                 rate_limit_rpm: row.rate_limit_rpm.max(0) as u32,
+                suppress_usage_warnings: row.suppress_usage_warnings,
             })
         }
         None => {
@@ -657,6 +1426,95 @@ async fn validate_api_key(
     }
 }
 
+/// Largest JSON-RPC payload captured for replay (serialized params size).
+/// Oversized requests are still logged, just without a stored payload, so
+/// they show up in the console as non-replayable rather than bloating the
+/// audit table.
+const MAX_REPLAY_PAYLOAD_BYTES: usize = 64 * 1024;
+
+/// Roll a detected duplicate up into `mcp_duplicate_requests`
+///
+/// Spawned so it never adds latency to the request path; failures are
+/// logged and dropped (diagnostics data is best-effort).
+fn record_duplicate_request(
+    state: &AppState,
+    org_id: Uuid,
+    api_key_id: Uuid,
+    request: &JsonRpcRequest,
+    request_hash: &str,
+) {
+    let tool_name: Option<String> = if request.method == "tools/call" {
+        request
+            .params
+            .as_ref()
+            .and_then(|p| p.get("name"))
+            .and_then(|n| n.as_str())
+            .map(String::from)
+    } else {
+        None
+    };
+    let pool = state.pool.clone();
+    let method = request.method.clone();
+    let request_hash = request_hash.to_string();
+
+    tokio::spawn(async move {
+        let result = sqlx::query(
+            "INSERT INTO mcp_duplicate_requests \
+             (org_id, api_key_id, method, tool_name, request_hash, duplicate_count, last_seen_at) \
+             VALUES ($1, $2, $3, $4, $5, 1, NOW()) \
+             ON CONFLICT (api_key_id, request_hash, day) \
+             DO UPDATE SET duplicate_count = mcp_duplicate_requests.duplicate_count + 1, \
+                           last_seen_at = NOW()",
+        )
+        .bind(org_id)
+        .bind(api_key_id)
+        .bind(&method)
+        .bind(&tool_name)
+        .bind(&request_hash)
+        .execute(&pool)
+        .await;
+
+        if let Err(e) = result {
+            tracing::warn!("Failed to record duplicate request: {}", e);
+        }
+    });
+}
+
+/// Whether the MCP targeted by a duplicate tools/call opted in to having
+/// cached responses replayed (`dedup.serve_cached` in its config)
+///
+/// Only consulted on duplicate hits, so the config lookup stays off the
+/// common request path.
+async fn duplicate_replay_enabled(
+    state: &AppState,
+    org_id: Uuid,
+    request: &JsonRpcRequest,
+) -> bool {
+    // Tool names are prefixed `{mcp_name}:{tool_name}`
+    let mcp_name = match request
+        .params
+        .as_ref()
+        .and_then(|p| p.get("name"))
+        .and_then(|n| n.as_str())
+        .and_then(|n| n.split(':').next())
+    {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => return false,
+    };
+
+    let config: Option<(serde_json::Value,)> =
+        sqlx::query_as("SELECT config FROM mcp_instances WHERE org_id = $1 AND name = $2")
+            .bind(org_id)
+            .bind(&mcp_name)
+            .fetch_optional(&state.pool)
+            .await
+            .unwrap_or(None);
+
+    config
+        .map(|(c,)| crate::mcp::dedup::serve_cached_enabled(&c))
+        .unwrap_or(false)
+}
+
 /// Log the MCP request for usage tracking and billing
 ///
 /// Records the request in `mcp_proxy_logs` for debugging and creates usage
@@ -748,24 +1606,21 @@ async fn log_request(
     // Per-MCP analytics are tracked separately via mcp_instance_id, but request_count is always 1 total
     #[cfg(feature = "billing")]
     if let Some(billing) = &state.billing {
-        if tracked_response.accessed_mcp_ids.is_empty() {
+        let events: Vec<UsageEvent> = if tracked_response.accessed_mcp_ids.is_empty() {
             // No MCPs accessed (e.g., initialize, errors, non-tool methods)
-            let _ = billing
-                .usage
-                .record_event(UsageEvent {
-                    org_id,
-                    api_key_id: Some(api_key_id),
-                    mcp_instance_id: None,
-                    request_count: 1,
-                    token_count: 0,
-                    error_count: if is_error { 1 } else { 0 },
-                    latency_ms: Some(latency_ms),
-                })
-                .await;
+            vec![UsageEvent {
+                org_id,
+                api_key_id: Some(api_key_id),
+                mcp_instance_id: None,
+                request_count: 1,
+                token_count: 0,
+                error_count: if is_error { 1 } else { 0 },
+                latency_ms: Some(latency_ms),
+            }]
         } else {
             // One or more MCPs accessed - create separate event per MCP
             // This ensures accurate per-MCP usage tracking for billing
-            let events: Vec<UsageEvent> = tracked_response
+            tracked_response
                 .accessed_mcp_ids
                 .iter()
                 .map(|&mcp_id| UsageEvent {
@@ -777,9 +1632,29 @@ async fn log_request(
                     error_count: if is_error { 1 } else { 0 },
                     latency_ms: Some(latency_ms),
                 })
-                .collect();
+                .collect()
+        };
 
-            let _ = billing.usage.record_events(events).await;
+        if let Err(e) = billing.usage.record_events(events.clone()).await {
+            // Database unavailable: park the events in the disk buffer so
+            // usage isn't lost while Postgres is down
+            tracing::warn!(org_id = %org_id, error = %e, "Usage write failed, buffering to disk");
+            let recorded_at_unix = OffsetDateTime::now_utc().unix_timestamp();
+            for event in events {
+                state
+                    .audit_buffer
+                    .buffer_usage_event(crate::mcp::audit_buffer::BufferedUsageEvent {
+                        org_id: event.org_id,
+                        api_key_id: event.api_key_id,
+                        mcp_instance_id: event.mcp_instance_id,
+                        request_count: event.request_count,
+                        token_count: event.token_count,
+                        error_count: event.error_count,
+                        latency_ms: event.latency_ms,
+                        recorded_at_unix,
+                    })
+                    .await;
+            }
         }
     }
 
@@ -809,6 +1684,39 @@ async fn log_request(
         "all".to_string()
     };
 
+    // Capture the original payload for the replay console unless the params
+    // are oversized (the entry is then logged as non-replayable)
+    let params_size = request
+        .params
+        .as_ref()
+        .map(|p| serde_json::to_vec(p).map(|v| v.len()).unwrap_or(usize::MAX))
+        .unwrap_or(0);
+    let request_payload = if params_size <= MAX_REPLAY_PAYLOAD_BYTES {
+        Some(serde_json::json!({
+            "method": request.method,
+            "params": request.params,
+        }))
+    } else {
+        None
+    };
+
+    // Opt-in payload capture for debugging: store a redacted copy of the
+    // full request/response pair alongside the audit entry
+    let capture = crate::mcp::payload_capture::load(&state.pool, org_id).await;
+    let captured_payload = if capture.enabled {
+        let mut captured = serde_json::json!({
+            "request": {
+                "method": request.method,
+                "params": request.params,
+            },
+            "response": serde_json::to_value(&tracked_response.response).unwrap_or(serde_json::Value::Null),
+        });
+        crate::mcp::payload_capture::redact(&mut captured, &capture.redact_paths);
+        Some(captured)
+    } else {
+        None
+    };
+
     let audit_log = McpRequestLog {
         request_id: Uuid::new_v4(),
         user_id, // Already Option<Uuid> from created_by column
@@ -843,9 +1751,31 @@ async fn log_request(
             "resource_uri": resource_uri,
             "accessed_mcp_count": tracked_response.accessed_mcp_ids.len(),
         })),
+        request_payload,
+        captured_payload,
     };
 
-    log_mcp_request(state.pool.clone(), audit_log);
+    log_mcp_request(state.pool.clone(), state.audit_buffer.clone(), audit_log);
+
+    // Truncations bump the per-MCP cap-hit counter for scraping, and
+    // repeated ones fire a security alert (threshold and window configured
+    // in alert_configurations)
+    if let Some(error) = &tracked_response.response.error {
+        if error.code == JsonRpcError::RESPONSE_TOO_LARGE {
+            for &mcp_id in &tracked_response.accessed_mcp_ids {
+                crate::metrics::registry()
+                    .record_response_truncation(mcp_id)
+                    .await;
+                let _ = crate::alerting::trigger_upstream_truncation_alert(
+                    &state.alert_service,
+                    Some(org_id),
+                    mcp_id,
+                    &request.method,
+                )
+                .await;
+            }
+        }
+    }
 }
 
 /// Create a JSON response
@@ -872,6 +1802,39 @@ fn error_response(id: Option<JsonRpcId>, error: JsonRpcError, status: StatusCode
         .into_response()
 }
 
+/// Attach standard rate limit headers to a proxy response
+///
+/// Emits `X-RateLimit-Limit`, `X-RateLimit-Remaining`, and
+/// `X-RateLimit-Reset` on every response the limiter evaluated, plus
+/// `Retry-After` when the request was rejected. No-op when the limiter
+/// failed open.
+fn attach_rate_limit_headers(
+    mut response: Response,
+    result: Option<&plexmcp_shared::RateLimitResult2>,
+) -> Response {
+    let Some(result) = result else {
+        return response;
+    };
+
+    let headers = response.headers_mut();
+    let mut insert = |name: &'static str, value: String| {
+        if let Ok(value) = axum::http::HeaderValue::from_str(&value) {
+            headers.insert(name, value);
+        }
+    };
+    insert("x-ratelimit-limit", result.limit.to_string());
+    insert("x-ratelimit-remaining", result.remaining_minute.to_string());
+    insert(
+        "x-ratelimit-reset",
+        result.reset_at.unix_timestamp().to_string(),
+    );
+    if let Some(retry_after) = result.retry_after_seconds {
+        insert("retry-after", retry_after.to_string());
+    }
+
+    response
+}
+
 /// Create an SSE streaming response
 /// Stream response as Server-Sent Events
 ///
@@ -902,11 +1865,28 @@ fn stream_response(response: JsonRpcResponse) -> Response {
         .into_response()
 }
 
+/// Stream a fan-out aggregation as Server-Sent Events
+///
+/// Emits one `partial` event per upstream MCP as its result arrives,
+/// then a `result` event with the fully aggregated response.
+fn partial_stream_response(rx: tokio::sync::mpsc::Receiver<McpStreamEvent>) -> Response {
+    Sse::new(stream_from_channel(rx))
+        .keep_alive(
+            axum::response::sse::KeepAlive::new()
+                .interval(Duration::from_secs(15))
+                .event(
+                    Event::default()
+                        .event("heartbeat")
+                        .data("{\"type\":\"heartbeat\"}"),
+                ),
+        )
+        .into_response()
+}
+
 /// Create an SSE stream from a channel receiver
 ///
-/// This can be used to stream partial results as they arrive from different MCPs.
-/// Currently not used, but provides foundation for future streaming aggregation.
-#[allow(dead_code)]
+/// Used by the persistent GET /mcp SSE transport to deliver session events,
+/// and available for future streaming aggregation.
 fn stream_from_channel(
     rx: tokio::sync::mpsc::Receiver<McpStreamEvent>,
 ) -> impl futures::Stream<Item = Result<Event, Infallible>> {
@@ -921,6 +1901,7 @@ fn stream_from_channel(
 }
 
 /// Format a number with thousands separators (e.g., 1000 -> "1,000")
+#[cfg(feature = "billing")]
 fn format_number(n: u64) -> String {
     let s = n.to_string();
     let mut result = String::new();
@@ -994,6 +1975,20 @@ fn extract_ip_from_headers(headers: &HeaderMap) -> Option<String> {
     None
 }
 
+/// Extract the request origin for allowed-origin enforcement
+///
+/// Prefers the Origin header; falls back to the scheme://host[:port]
+/// portion of the Referer for clients that only send the latter.
+fn extract_origin(headers: &HeaderMap) -> Option<String> {
+    if let Some(origin) = headers.get(header::ORIGIN).and_then(|v| v.to_str().ok()) {
+        return Some(origin.to_string());
+    }
+    let referer = headers.get(header::REFERER).and_then(|v| v.to_str().ok())?;
+    let (scheme, rest) = referer.split_once("://")?;
+    let host = rest.split('/').next()?;
+    Some(format!("{}://{}", scheme, host))
+}
+
 /// Extract user agent from request headers
 fn extract_user_agent(headers: &HeaderMap) -> Option<String> {
     headers
@@ -1028,4 +2023,34 @@ mod tests {
         let headers = HeaderMap::new();
         assert_eq!(extract_api_key(&headers), None);
     }
+
+    #[test]
+    fn test_extract_origin_prefers_origin_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ORIGIN, "https://app.example.com".parse().unwrap());
+        headers.insert(
+            header::REFERER,
+            "https://other.example.com/page".parse().unwrap(),
+        );
+        assert_eq!(
+            extract_origin(&headers),
+            Some("https://app.example.com".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_origin_from_referer() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::REFERER,
+            "https://app.example.com:8443/dashboard/keys"
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(
+            extract_origin(&headers),
+            Some("https://app.example.com:8443".to_string())
+        );
+        assert_eq!(extract_origin(&HeaderMap::new()), None);
+    }
 }
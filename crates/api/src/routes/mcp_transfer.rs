@@ -0,0 +1,483 @@
+//! Bulk MCP configuration import/export
+//!
+//! Serializes all of an org's MCP definitions to a portable JSON or YAML
+//! document - the same definition shape the GitHub config sync consumes -
+//! and imports such a document back with upsert-by-name semantics. This
+//! enables migration between orgs and infrastructure-as-code workflows
+//! without wiring up a repo webhook.
+//!
+//! Secrets are redacted on export. On import, a redacted value is carried
+//! over from the org's existing MCP of the same name; a definition that
+//! would *create* an MCP with redacted secrets is skipped instead, since
+//! there is nothing to carry the secret over from.
+
+use axum::{
+    extract::{Extension, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{auth::AuthUser, error::ApiError, state::AppState};
+
+use super::github_sync::McpDefinition;
+use super::mcps::{
+    get_mcp_count, get_org_effective_limits, redact_sensitive_config, validate_auth_template,
+    validate_cache_config,
+};
+
+/// Document format version; bumped on breaking shape changes
+const EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// Import documents larger than this are rejected
+const MAX_IMPORT_BYTES: usize = 1024 * 1024;
+
+/// Maximum definitions accepted in one import
+const MAX_IMPORT_MCPS: usize = 200;
+
+/// Placeholder written by export for secret config values
+const REDACTED: &str = "[REDACTED]";
+
+/// Portable export document
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportDocument {
+    pub version: u32,
+    #[serde(with = "time::serde::rfc3339")]
+    pub exported_at: OffsetDateTime,
+    pub mcps: Vec<McpDefinition>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    /// "json" (default) or "yaml"
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportQuery {
+    /// Validate and diff without writing anything
+    pub dry_run: Option<bool>,
+}
+
+/// One definition the import could not apply
+#[derive(Debug, Serialize)]
+pub struct SkippedEntry {
+    pub name: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportResponse {
+    pub dry_run: bool,
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub skipped: Vec<SkippedEntry>,
+}
+
+/// Existing MCP row used for upsert matching and secret carry-over
+#[derive(sqlx::FromRow)]
+struct ExistingMcpRow {
+    name: String,
+    mcp_type: String,
+    description: Option<String>,
+    config: serde_json::Value,
+    status: String,
+}
+
+/// Export all of the org's MCP definitions as a portable document
+///
+/// Org owners/admins only: the document contains upstream endpoints even
+/// though secret values are redacted.
+pub async fn export_mcps(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let rows: Vec<ExistingMcpRow> = sqlx::query_as(
+        r#"
+        SELECT name, mcp_type, description, config, status
+        FROM mcp_instances
+        WHERE org_id = $1
+        ORDER BY name
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let mcps = rows
+        .into_iter()
+        .map(|row| McpDefinition {
+            name: row.name,
+            mcp_type: row.mcp_type,
+            description: row.description,
+            config: Some(redact_sensitive_config(row.config)),
+            is_active: Some(row.status == "active"),
+        })
+        .collect();
+
+    let document = ExportDocument {
+        version: EXPORT_FORMAT_VERSION,
+        exported_at: OffsetDateTime::now_utc(),
+        mcps,
+    };
+
+    match query.format.as_deref().unwrap_or("json") {
+        "json" => Ok(Json(document).into_response()),
+        "yaml" => {
+            let body = serde_yaml::to_string(&document).map_err(|e| {
+                tracing::error!(error = %e, "Export YAML serialization failed");
+                ApiError::Internal
+            })?;
+            Ok((
+                StatusCode::OK,
+                [(header::CONTENT_TYPE, "application/yaml")],
+                body,
+            )
+                .into_response())
+        }
+        other => Err(ApiError::Validation(format!(
+            "Unknown format '{}'. Supported: json, yaml",
+            other
+        ))),
+    }
+}
+
+/// Import an exported document, upserting MCPs by name
+///
+/// Creates definitions the org doesn't have and updates those it does;
+/// never deletes. Pass `?dry_run=true` to get the would-be change set
+/// without writing. Org owners/admins only.
+pub async fn import_mcps(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<ImportQuery>,
+    body: String,
+) -> Result<Json<ImportResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let document = parse_document(&body)?;
+    let dry_run = query.dry_run.unwrap_or(false);
+
+    // Load existing MCPs once for upsert matching and secret carry-over
+    let existing: Vec<ExistingMcpRow> = sqlx::query_as(
+        "SELECT name, mcp_type, description, config, status FROM mcp_instances WHERE org_id = $1",
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let mut created = Vec::new();
+    let mut updated = Vec::new();
+    let mut skipped = Vec::new();
+    // (name, mcp_type, description, config, is_active, is_create)
+    let mut to_apply: Vec<(
+        String,
+        String,
+        Option<String>,
+        serde_json::Value,
+        bool,
+        bool,
+    )> = Vec::new();
+
+    let mut seen = std::collections::BTreeSet::new();
+    for definition in &document.mcps {
+        let name = definition.name.trim().to_string();
+        if let Err(reason) = validate_definition(definition) {
+            skipped.push(SkippedEntry { name, reason });
+            continue;
+        }
+        if !seen.insert(name.clone()) {
+            skipped.push(SkippedEntry {
+                name,
+                reason: "duplicate name in document".to_string(),
+            });
+            continue;
+        }
+
+        let mut config = definition.config.clone().unwrap_or(serde_json::json!({}));
+        let current = existing.iter().find(|row| row.name == name);
+
+        match current {
+            Some(row) => {
+                carry_over_redacted(&mut config, &row.config);
+                updated.push(name.clone());
+                to_apply.push((
+                    name,
+                    definition.mcp_type.trim().to_string(),
+                    definition.description.clone(),
+                    config,
+                    definition.is_active.unwrap_or(true),
+                    false,
+                ));
+            }
+            None => {
+                if contains_redacted(&config) {
+                    skipped.push(SkippedEntry {
+                        name,
+                        reason: "config contains redacted secrets and no existing MCP to carry them over from".to_string(),
+                    });
+                    continue;
+                }
+                created.push(name.clone());
+                to_apply.push((
+                    name,
+                    definition.mcp_type.trim().to_string(),
+                    definition.description.clone(),
+                    config,
+                    definition.is_active.unwrap_or(true),
+                    true,
+                ));
+            }
+        }
+    }
+
+    // Creates count toward the tier MCP limit just like dashboard creates
+    if !created.is_empty() {
+        let effective_limits = get_org_effective_limits(&state.pool, org_id).await?;
+        let current_count = get_mcp_count(&state.pool, org_id).await?;
+        if current_count + created.len() as i64 > i64::from(effective_limits.max_mcps) {
+            return Err(ApiError::QuotaExceeded(format!(
+                "Importing {} new MCPs would exceed the {} MCP limit",
+                created.len(),
+                effective_limits.max_mcps
+            )));
+        }
+    }
+
+    if !dry_run {
+        for (name, mcp_type, description, config, is_active, is_create) in &to_apply {
+            if *is_create {
+                sqlx::query(
+                    r#"
+                    INSERT INTO mcp_instances
+                        (id, org_id, name, mcp_type, description, config, status, health_status, created_at, updated_at)
+                    VALUES ($1, $2, $3, $4, $5, $6, CASE WHEN $7 THEN 'active' ELSE 'inactive' END, 'unknown', NOW(), NOW())
+                    "#,
+                )
+                .bind(Uuid::new_v4())
+                .bind(org_id)
+                .bind(name)
+                .bind(mcp_type)
+                .bind(description)
+                .bind(config)
+                .bind(is_active)
+                .execute(&state.pool)
+                .await?;
+            } else {
+                sqlx::query(
+                    r#"
+                    UPDATE mcp_instances
+                    SET mcp_type = $3, description = $4, config = $5,
+                        status = CASE WHEN $6 THEN 'active' ELSE 'inactive' END,
+                        updated_at = NOW()
+                    WHERE org_id = $1 AND name = $2
+                    "#,
+                )
+                .bind(org_id)
+                .bind(name)
+                .bind(mcp_type)
+                .bind(description)
+                .bind(config)
+                .bind(is_active)
+                .execute(&state.pool)
+                .await?;
+            }
+        }
+
+        if !to_apply.is_empty() {
+            state.tool_catalog.invalidate(org_id).await;
+        }
+    }
+
+    Ok(Json(ImportResponse {
+        dry_run,
+        created,
+        updated,
+        skipped,
+    }))
+}
+
+/// Parse an import document from JSON or YAML (detected by leading syntax)
+fn parse_document(body: &str) -> Result<ExportDocument, ApiError> {
+    if body.len() > MAX_IMPORT_BYTES {
+        return Err(ApiError::Validation(format!(
+            "Import document exceeds {} bytes",
+            MAX_IMPORT_BYTES
+        )));
+    }
+
+    let document: ExportDocument = if body.trim_start().starts_with('{') {
+        serde_json::from_str(body)
+            .map_err(|e| ApiError::Validation(format!("Invalid JSON document: {}", e)))?
+    } else {
+        serde_yaml::from_str(body)
+            .map_err(|e| ApiError::Validation(format!("Invalid YAML document: {}", e)))?
+    };
+
+    if document.version != EXPORT_FORMAT_VERSION {
+        return Err(ApiError::Validation(format!(
+            "Unsupported document version {} (expected {})",
+            document.version, EXPORT_FORMAT_VERSION
+        )));
+    }
+    if document.mcps.len() > MAX_IMPORT_MCPS {
+        return Err(ApiError::Validation(format!(
+            "Import document contains {} MCPs (maximum {})",
+            document.mcps.len(),
+            MAX_IMPORT_MCPS
+        )));
+    }
+
+    Ok(document)
+}
+
+/// Per-definition validation, mirroring the GitHub sync rules
+fn validate_definition(definition: &McpDefinition) -> Result<(), String> {
+    if definition.name.trim().is_empty() {
+        return Err("name cannot be empty".to_string());
+    }
+    if definition.mcp_type.trim().is_empty() {
+        return Err("mcp_type cannot be empty".to_string());
+    }
+    if let Some(ref config) = definition.config {
+        if !config.is_object() {
+            return Err("config must be an object".to_string());
+        }
+        validate_cache_config(config).map_err(|e| e.to_string())?;
+        validate_auth_template(config).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Whether any config value is the export redaction placeholder
+///
+/// Checks top-level values plus one level of nesting (the `credentials`
+/// object used by templated auth headers).
+fn contains_redacted(config: &serde_json::Value) -> bool {
+    config
+        .as_object()
+        .map(|obj| {
+            obj.values().any(|v| {
+                v.as_str() == Some(REDACTED)
+                    || v.as_object().is_some_and(|nested| {
+                        nested.values().any(|nv| nv.as_str() == Some(REDACTED))
+                    })
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// Replace redacted values with the existing MCP's stored values, so a
+/// round-tripped export doesn't wipe secrets on import
+fn carry_over_redacted(config: &mut serde_json::Value, existing: &serde_json::Value) {
+    let Some(obj) = config.as_object_mut() else {
+        return;
+    };
+    let Some(existing_obj) = existing.as_object() else {
+        return;
+    };
+    for (key, value) in obj.iter_mut() {
+        if value.as_str() == Some(REDACTED) {
+            if let Some(current) = existing_obj.get(key) {
+                *value = current.clone();
+            }
+        } else if let Some(nested) = value.as_object_mut() {
+            // One level of nesting covers the `credentials` slot object
+            let existing_nested = existing_obj.get(key).and_then(|v| v.as_object());
+            for (nested_key, nested_value) in nested.iter_mut() {
+                if nested_value.as_str() == Some(REDACTED) {
+                    if let Some(current) = existing_nested.and_then(|e| e.get(nested_key)) {
+                        *nested_value = current.clone();
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_document_json_and_yaml() {
+        let json = r#"{"version": 1, "exported_at": "2026-01-01T00:00:00Z", "mcps": [{"name": "github", "mcp_type": "http"}]}"#;
+        let parsed = parse_document(json).unwrap();
+        assert_eq!(parsed.mcps.len(), 1);
+        assert_eq!(parsed.mcps[0].name, "github");
+
+        let yaml = "version: 1\nexported_at: 2026-01-01T00:00:00Z\nmcps:\n  - name: jira\n    mcp_type: http\n";
+        let parsed = parse_document(yaml).unwrap();
+        assert_eq!(parsed.mcps[0].name, "jira");
+    }
+
+    #[test]
+    fn test_parse_document_rejects_wrong_version() {
+        let json = r#"{"version": 2, "exported_at": "2026-01-01T00:00:00Z", "mcps": []}"#;
+        let err = parse_document(json).unwrap_err();
+        assert!(matches!(err, ApiError::Validation(_)));
+    }
+
+    #[test]
+    fn test_contains_redacted() {
+        assert!(contains_redacted(&serde_json::json!({
+            "endpoint_url": "https://example.com",
+            "api_key": "[REDACTED]",
+        })));
+        assert!(!contains_redacted(&serde_json::json!({
+            "endpoint_url": "https://example.com",
+        })));
+    }
+
+    #[test]
+    fn test_carry_over_redacted_preserves_secrets() {
+        let mut incoming = serde_json::json!({
+            "endpoint_url": "https://new.example.com",
+            "api_key": "[REDACTED]",
+        });
+        let existing = serde_json::json!({
+            "endpoint_url": "https://old.example.com",
+            "api_key": "sk-live-secret",
+        });
+        carry_over_redacted(&mut incoming, &existing);
+        assert_eq!(incoming["api_key"], "sk-live-secret");
+        // Non-redacted fields keep the incoming value
+        assert_eq!(incoming["endpoint_url"], "https://new.example.com");
+    }
+
+    #[test]
+    fn test_carry_over_redacted_nested_credentials() {
+        let mut incoming = serde_json::json!({
+            "auth_type": "headers",
+            "auth_headers": { "X-Api-Key": "{{credentials.api_key}}" },
+            "credentials": { "api_key": "[REDACTED]" },
+        });
+        let existing = serde_json::json!({
+            "credentials": { "api_key": "real-secret" },
+        });
+        assert!(contains_redacted(&incoming));
+        carry_over_redacted(&mut incoming, &existing);
+        assert_eq!(incoming["credentials"]["api_key"], "real-secret");
+    }
+
+    #[test]
+    fn test_carry_over_redacted_without_existing_value() {
+        let mut incoming = serde_json::json!({"api_key": "[REDACTED]"});
+        let existing = serde_json::json!({});
+        carry_over_redacted(&mut incoming, &existing);
+        // Nothing to carry over - placeholder stays and the caller decides
+        assert_eq!(incoming["api_key"], "[REDACTED]");
+    }
+}
@@ -1,6 +1,5 @@
 //! MCP instance management API routes
 
-use std::sync::Arc;
 use std::time::Instant;
 
 use axum::{
@@ -8,18 +7,16 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use plexmcp_shared::{CreateMcpRequest, McpInstance, SubscriptionTier};
+use plexmcp_shared::{CreateMcpRequest, McpInstance, PaginatedResponse, SubscriptionTier};
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
+use super::list_query::{ListParams, ListQuery};
 use crate::{
     auth::AuthUser,
     error::ApiError,
-    mcp::{
-        client::McpClient,
-        types::{McpAuth, McpTransport},
-    },
+    mcp::types::{McpAuth, McpTransport},
     state::AppState,
 };
 
@@ -52,6 +49,9 @@ pub struct McpResponse {
     // Timeout configuration
     pub request_timeout_ms: i32,
     pub partial_timeout_ms: Option<i32>,
+    /// Live adaptive timeout state (populated on the detail endpoint only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adaptive_timeout: Option<crate::mcp::adaptive_timeout::AdaptiveTimeoutSnapshot>,
 }
 
 impl From<McpInstance> for McpResponse {
@@ -85,12 +85,13 @@ impl From<McpInstance> for McpResponse {
             // Timeout configuration
             request_timeout_ms: mcp.request_timeout_ms,
             partial_timeout_ms: mcp.partial_timeout_ms,
+            adaptive_timeout: None,
         }
     }
 }
 
 /// Redact sensitive fields from config JSON
-fn redact_sensitive_config(mut config: serde_json::Value) -> serde_json::Value {
+pub(crate) fn redact_sensitive_config(mut config: serde_json::Value) -> serde_json::Value {
     if let Some(obj) = config.as_object_mut() {
         // List of sensitive keys to redact
         let sensitive_keys = ["api_key", "password", "secret", "token", "auth_token"];
@@ -100,6 +101,14 @@ fn redact_sensitive_config(mut config: serde_json::Value) -> serde_json::Value {
                 obj.insert((*key).to_string(), serde_json::json!("[REDACTED]"));
             }
         }
+
+        // Templated auth credential slots are all secrets (the templates
+        // themselves only reference slots and stay visible)
+        if let Some(credentials) = obj.get_mut("credentials").and_then(|v| v.as_object_mut()) {
+            for value in credentials.values_mut() {
+                *value = serde_json::json!("[REDACTED]");
+            }
+        }
     }
     config
 }
@@ -109,8 +118,12 @@ fn redact_sensitive_config(mut config: serde_json::Value) -> serde_json::Value {
 pub struct ListMcpsQuery {
     pub status: Option<String>,
     pub mcp_type: Option<String>,
-    pub page: Option<i64>,
-    pub per_page: Option<i64>,
+    /// Case-insensitive substring match on the MCP name
+    pub name: Option<String>,
+    /// Matches MCPs whose `config.tags` array contains this tag
+    pub tag: Option<String>,
+    #[serde(flatten)]
+    pub list: ListParams,
 }
 
 /// Update MCP request
@@ -130,6 +143,36 @@ pub struct UpdateMcpStatusRequest {
     pub status: String,
 }
 
+/// Set cost center request (null clears the tag)
+#[derive(Debug, Deserialize)]
+pub struct SetCostCenterRequest {
+    pub cost_center: Option<String>,
+}
+
+/// Query params for the client config generator
+#[derive(Debug, Deserialize)]
+pub struct ClientConfigQuery {
+    /// Target client: "claude-desktop", "cursor" or "vscode"
+    pub format: String,
+    /// API key to reference; the secret itself is not recoverable, so the
+    /// rendered block embeds a placeholder either way
+    pub key_id: Option<Uuid>,
+}
+
+/// Rendered client config block
+#[derive(Debug, Serialize)]
+pub struct ClientConfigResponse {
+    pub format: String,
+    /// The org's MCP proxy endpoint (custom-domain aware)
+    pub endpoint: String,
+    /// Where the block belongs on the client's machine
+    pub config_file: String,
+    /// Prefix of the selected key, to identify which secret to paste over
+    /// the placeholder
+    pub key_prefix: Option<String>,
+    pub config: serde_json::Value,
+}
+
 /// Health check response
 #[derive(Debug, Serialize)]
 pub struct HealthCheckResponse {
@@ -148,6 +191,9 @@ pub struct HealthCheckDetails {
     pub tools_count: Option<usize>,
     pub resources_count: Option<usize>,
     pub latency_ms: u64,
+    /// Whether a cached session/process existed before the test, so cold and
+    /// warm latencies can be compared in the history
+    pub connection_warm: bool,
     pub error: Option<String>,
     // Full tool/resource data (for storing in database)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -168,6 +214,9 @@ pub struct TestHistoryEntry {
     pub tools_count: Option<i32>,
     pub resources_count: Option<i32>,
     pub latency_ms: i32,
+    /// Whether the connection was warm when the test ran (None for rows
+    /// recorded before this was tracked)
+    pub connection_warm: Option<bool>,
     pub error_message: Option<String>,
     pub tested_at: OffsetDateTime,
     pub tested_by: Option<Uuid>,
@@ -185,6 +234,7 @@ pub struct TestHistoryResponse {
     pub tools_count: Option<i32>,
     pub resources_count: Option<i32>,
     pub latency_ms: i32,
+    pub connection_warm: Option<bool>,
     pub error_message: Option<String>,
     pub tested_at: String,
     pub tested_by: Option<String>,
@@ -202,6 +252,7 @@ impl From<TestHistoryEntry> for TestHistoryResponse {
             tools_count: entry.tools_count,
             resources_count: entry.resources_count,
             latency_ms: entry.latency_ms,
+            connection_warm: entry.connection_warm,
             error_message: entry.error_message,
             tested_at: format_datetime(entry.tested_at),
             tested_by: entry.tested_by.map(|id| id.to_string()),
@@ -247,101 +298,45 @@ pub struct BatchTestResponse {
     pub tested_at: String,
 }
 
+/// Columns selected for MCP list/detail rows
+const MCP_COLUMNS: &str =
+    "id, org_id, name, mcp_type, description, config, status, health_status, \
+     last_health_check_at, created_at, updated_at, \
+     protocol_version, server_name, server_version, tools_count, resources_count, last_latency_ms, \
+     tools_json, resources_json, request_timeout_ms, partial_timeout_ms";
+
+/// Sort keys accepted by the MCP listing; first entry is the default
+const MCP_SORT_KEYS: &[(&str, &str)] = &[
+    ("created_at", "created_at"),
+    ("name", "name"),
+    ("updated_at", "updated_at"),
+    ("last_health_check", "last_health_check_at"),
+];
+
 /// List all MCP instances for the organization
 pub async fn list_mcps(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
     Query(query): Query<ListMcpsQuery>,
-) -> Result<Json<Vec<McpResponse>>, ApiError> {
+) -> Result<Json<PaginatedResponse<McpResponse>>, ApiError> {
     let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
-    let page = query.page.unwrap_or(1).max(1);
-    let per_page = query.per_page.unwrap_or(50).min(100);
-    let offset = (page - 1) * per_page;
-
-    let mcps: Vec<McpInstance> = match (&query.status, &query.mcp_type) {
-        (Some(status), Some(mcp_type)) => {
-            sqlx::query_as(
-                r#"
-                SELECT id, org_id, name, mcp_type, description, config, status, health_status,
-                       last_health_check_at, created_at, updated_at,
-                       protocol_version, server_name, server_version, tools_count, resources_count, last_latency_ms,
-                       tools_json, resources_json, request_timeout_ms, partial_timeout_ms
-                FROM mcp_instances
-                WHERE org_id = $1 AND status = $2 AND mcp_type = $3
-                ORDER BY created_at DESC
-                LIMIT $4 OFFSET $5
-                "#
-            )
-            .bind(org_id)
-            .bind(status)
-            .bind(mcp_type)
-            .bind(per_page)
-            .bind(offset)
-            .fetch_all(&state.pool)
-            .await?
-        }
-        (Some(status), None) => {
-            sqlx::query_as(
-                r#"
-                SELECT id, org_id, name, mcp_type, description, config, status, health_status,
-                       last_health_check_at, created_at, updated_at,
-                       protocol_version, server_name, server_version, tools_count, resources_count, last_latency_ms,
-                       tools_json, resources_json, request_timeout_ms, partial_timeout_ms
-                FROM mcp_instances
-                WHERE org_id = $1 AND status = $2
-                ORDER BY created_at DESC
-                LIMIT $3 OFFSET $4
-                "#
-            )
-            .bind(org_id)
-            .bind(status)
-            .bind(per_page)
-            .bind(offset)
-            .fetch_all(&state.pool)
-            .await?
-        }
-        (None, Some(mcp_type)) => {
-            sqlx::query_as(
-                r#"
-                SELECT id, org_id, name, mcp_type, description, config, status, health_status,
-                       last_health_check_at, created_at, updated_at,
-                       protocol_version, server_name, server_version, tools_count, resources_count, last_latency_ms,
-                       tools_json, resources_json, request_timeout_ms, partial_timeout_ms
-                FROM mcp_instances
-                WHERE org_id = $1 AND mcp_type = $2
-                ORDER BY created_at DESC
-                LIMIT $3 OFFSET $4
-                "#
-            )
-            .bind(org_id)
-            .bind(mcp_type)
-            .bind(per_page)
-            .bind(offset)
-            .fetch_all(&state.pool)
-            .await?
-        }
-        (None, None) => {
-            sqlx::query_as(
-                r#"
-                SELECT id, org_id, name, mcp_type, description, config, status, health_status,
-                       last_health_check_at, created_at, updated_at,
-                       protocol_version, server_name, server_version, tools_count, resources_count, last_latency_ms,
-                       tools_json, resources_json, request_timeout_ms, partial_timeout_ms
-                FROM mcp_instances
-                WHERE org_id = $1
-                ORDER BY created_at DESC
-                LIMIT $2 OFFSET $3
-                "#
-            )
-            .bind(org_id)
-            .bind(per_page)
-            .bind(offset)
-            .fetch_all(&state.pool)
-            .await?
-        }
-    };
+    let order_by = query.list.order_by(MCP_SORT_KEYS)?;
+
+    let (mcps, total): (Vec<McpInstance>, i64) = ListQuery::new("mcp_instances")
+        .eq_uuid("org_id", org_id)
+        .eq_text("status", query.status)
+        .eq_text("mcp_type", query.mcp_type)
+        .contains("name", query.name)
+        .json_has("config->'tags'", query.tag)
+        .fetch_page(&state.pool, MCP_COLUMNS, &order_by, &query.list)
+        .await?;
 
-    Ok(Json(mcps.into_iter().map(McpResponse::from).collect()))
+    Ok(Json(PaginatedResponse::new(
+        mcps.into_iter().map(McpResponse::from).collect(),
+        total,
+        query.list.page(),
+        query.list.per_page(),
+    )))
 }
 
 /// Create a new MCP instance
@@ -374,6 +369,20 @@ pub async fn create_mcp(
     }
 
     let config = req.config.unwrap_or(serde_json::json!({}));
+
+    // Keep-warm is tier-gated (Pro and above)
+    if config_requests_keep_warm(&config) {
+        let tier = get_org_tier(&state.pool, org_id).await?;
+        if !tier.keep_warm_allowed() {
+            return Err(ApiError::Validation(
+                "Keep-warm connections require a Pro plan or higher".to_string(),
+            ));
+        }
+    }
+
+    validate_cache_config(&config)?;
+    validate_auth_template(&config)?;
+
     let id = Uuid::new_v4();
     let now = OffsetDateTime::now_utc();
 
@@ -405,9 +414,87 @@ pub async fn create_mcp(
     .fetch_one(&state.pool)
     .await?;
 
+    prewarm_if_requested(&state, &mcp, org_id);
+
+    state.tool_catalog.invalidate(org_id).await;
+
     Ok((StatusCode::CREATED, Json(McpResponse::from(mcp))))
 }
 
+/// Validate the optional `cache` object in an MCP config
+///
+/// The tool result cache is opt-in; when present, `ttl_seconds` must be a
+/// sensible bound and the tool allowlist must be an array of strings.
+pub(crate) fn validate_cache_config(config: &serde_json::Value) -> Result<(), ApiError> {
+    let Some(cache) = config.get("cache") else {
+        return Ok(());
+    };
+    if !cache.is_object() {
+        return Err(ApiError::Validation("cache must be an object".to_string()));
+    }
+    let ttl = cache
+        .get("ttl_seconds")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| {
+            ApiError::Validation("cache.ttl_seconds must be a positive integer".to_string())
+        })?;
+    if !(1..=86400).contains(&ttl) {
+        return Err(ApiError::Validation(
+            "cache.ttl_seconds must be between 1 and 86400 seconds".to_string(),
+        ));
+    }
+    if let Some(tools) = cache.get("tools") {
+        let valid = tools
+            .as_array()
+            .is_some_and(|arr| arr.iter().all(|t| t.is_string()));
+        if !valid {
+            return Err(ApiError::Validation(
+                "cache.tools must be an array of tool names".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Validate templated auth headers at save time
+///
+/// Render failures (bad preset name, missing credential slot, invalid
+/// header name) surface in the dashboard here instead of silently
+/// degrading to unauthenticated upstream calls at request time.
+pub(crate) fn validate_auth_template(config: &serde_json::Value) -> Result<(), ApiError> {
+    let templated = config.get("auth_type").and_then(|v| v.as_str()) == Some("headers")
+        || config.get("auth_preset").is_some();
+    if templated {
+        crate::mcp::auth_template::render_auth_headers(config)
+            .map_err(|e| ApiError::Validation(format!("Invalid auth headers: {}", e)))?;
+    }
+    Ok(())
+}
+
+/// Whether a config opts this MCP into keep-warm connection pings
+fn config_requests_keep_warm(config: &serde_json::Value) -> bool {
+    config
+        .get("keep_warm")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Pre-establish the connection in the background when an MCP was saved
+/// with keep-warm enabled, so the first real request is already warm
+fn prewarm_if_requested(state: &AppState, mcp: &McpInstance, org_id: Uuid) {
+    if !config_requests_keep_warm(&mcp.config) {
+        return;
+    }
+    let Some(transport) = parse_transport(&mcp.mcp_type, &mcp.config, org_id) else {
+        return;
+    };
+    let client = state.mcp_client.clone();
+    let mcp_id = mcp.id.to_string();
+    tokio::spawn(async move {
+        client.warm_connection(&transport, &mcp_id).await;
+    });
+}
+
 /// Get a specific MCP instance
 pub async fn get_mcp(
     State(state): State<AppState>,
@@ -431,7 +518,10 @@ pub async fn get_mcp(
     .await?
     .ok_or(ApiError::NotFound)?;
 
-    Ok(Json(McpResponse::from(mcp)))
+    let mut response = McpResponse::from(mcp);
+    response.adaptive_timeout = state.mcp_client.adaptive_timeout_snapshot(mcp_id).await;
+
+    Ok(Json(response))
 }
 
 /// Update an MCP instance
@@ -488,6 +578,20 @@ pub async fn update_mcp(
     let description = req.description.as_ref().or(existing.description.as_ref());
     let config = req.config.as_ref().unwrap_or(&existing.config);
 
+    // Keep-warm is tier-gated (Pro and above); only check when the new
+    // config turns it on
+    if config_requests_keep_warm(config) && !config_requests_keep_warm(&existing.config) {
+        let tier = get_org_tier(&state.pool, org_id).await?;
+        if !tier.keep_warm_allowed() {
+            return Err(ApiError::Validation(
+                "Keep-warm connections require a Pro plan or higher".to_string(),
+            ));
+        }
+    }
+
+    validate_cache_config(config)?;
+    validate_auth_template(config)?;
+
     // Convert is_active boolean to status string
     let status = match req.is_active {
         Some(true) => "active",
@@ -524,9 +628,47 @@ pub async fn update_mcp(
     .fetch_one(&state.pool)
     .await?;
 
+    prewarm_if_requested(&state, &mcp, org_id);
+
+    // Config changes can alter endpoints or cache rules; drop any cached
+    // tool results so stale entries don't outlive the old settings
+    if req.config.is_some() {
+        state.tool_cache.invalidate_mcp(org_id, mcp_id).await;
+    }
+
+    state.tool_catalog.invalidate(org_id).await;
+
     Ok(Json(McpResponse::from(mcp)))
 }
 
+/// Manually flush cached tool results for an MCP
+///
+/// Lets org members force-invalidate the opt-in tools/call result cache
+/// (e.g. after out-of-band changes upstream) without waiting for the TTL.
+pub async fn invalidate_mcp_cache(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(mcp_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    // Verify MCP exists and belongs to org
+    let exists: Option<(Uuid,)> =
+        sqlx::query_as("SELECT id FROM mcp_instances WHERE id = $1 AND org_id = $2")
+            .bind(mcp_id)
+            .bind(org_id)
+            .fetch_optional(&state.pool)
+            .await?;
+    if exists.is_none() {
+        return Err(ApiError::NotFound);
+    }
+
+    let invalidated = state.tool_cache.invalidate_mcp(org_id, mcp_id).await;
+
+    Ok(Json(serde_json::json!({
+        "invalidated": invalidated,
+    })))
+}
+
 /// Delete an MCP instance
 pub async fn delete_mcp(
     State(state): State<AppState>,
@@ -544,6 +686,8 @@ pub async fn delete_mcp(
         return Err(ApiError::NotFound);
     }
 
+    state.tool_catalog.invalidate(org_id).await;
+
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -582,9 +726,121 @@ pub async fn update_mcp_status(
     .await?
     .ok_or(ApiError::NotFound)?;
 
+    state.tool_catalog.invalidate(org_id).await;
+
     Ok(Json(McpResponse::from(mcp)))
 }
 
+/// Render a ready-to-paste client config block for the org's proxy endpoint
+///
+/// The endpoint prefers the org's custom domain, then subdomains (see
+/// `HostResolver::preferred_host`). API key secrets are only shown at
+/// creation, so the block embeds a `<YOUR_API_KEY>` placeholder; pass
+/// `key_id` to get the key's prefix back for identification.
+pub async fn get_client_config(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<ClientConfigQuery>,
+) -> Result<Json<ClientConfigResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let key_prefix = match query.key_id {
+        Some(key_id) => {
+            let row: Option<(String,)> =
+                sqlx::query_as("SELECT key_prefix FROM api_keys WHERE id = $1 AND org_id = $2")
+                    .bind(key_id)
+                    .bind(org_id)
+                    .fetch_optional(&state.pool)
+                    .await?;
+            Some(row.ok_or(ApiError::NotFound)?.0)
+        }
+        None => None,
+    };
+
+    let endpoint = super::onboarding::proxy_endpoint(&state, org_id).await?;
+
+    const KEY_PLACEHOLDER: &str = "<YOUR_API_KEY>";
+    let (config_file, config) = match query.format.as_str() {
+        "claude-desktop" => (
+            "claude_desktop_config.json",
+            super::onboarding::claude_desktop_config(&endpoint, KEY_PLACEHOLDER),
+        ),
+        "cursor" => (
+            ".cursor/mcp.json",
+            super::onboarding::cursor_config(&endpoint, KEY_PLACEHOLDER),
+        ),
+        "vscode" => (
+            ".vscode/mcp.json",
+            super::onboarding::vscode_config(&endpoint, KEY_PLACEHOLDER),
+        ),
+        other => {
+            return Err(ApiError::Validation(format!(
+                "Unknown format '{}'. Supported: claude-desktop, cursor, vscode",
+                other
+            )));
+        }
+    };
+
+    Ok(Json(ClientConfigResponse {
+        format: query.format,
+        endpoint,
+        config_file: config_file.to_string(),
+        key_prefix,
+        config,
+    }))
+}
+
+/// Set or clear an MCP instance's cost center tag
+///
+/// Cost centers feed the chargeback reports under `/usage/by-tag`.
+/// Org owners/admins only.
+pub async fn set_mcp_cost_center(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(mcp_id): Path<Uuid>,
+    Json(req): Json<SetCostCenterRequest>,
+) -> Result<StatusCode, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let cost_center = validate_cost_center(req.cost_center)?;
+
+    let result = sqlx::query(
+        "UPDATE mcp_instances SET cost_center = $3, updated_at = NOW() WHERE id = $1 AND org_id = $2",
+    )
+    .bind(mcp_id)
+    .bind(org_id)
+    .bind(cost_center)
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Normalize and validate a cost center tag (None clears it)
+pub(crate) fn validate_cost_center(
+    cost_center: Option<String>,
+) -> Result<Option<String>, ApiError> {
+    match cost_center {
+        None => Ok(None),
+        Some(raw) => {
+            let trimmed = raw.trim();
+            if trimmed.is_empty() || trimmed.len() > 100 {
+                return Err(ApiError::Validation(
+                    "Cost center must be between 1 and 100 characters".to_string(),
+                ));
+            }
+            Ok(Some(trimmed.to_string()))
+        }
+    }
+}
+
 /// Trigger a health check for an MCP instance
 ///
 /// Performs a real MCP connection test:
@@ -621,7 +877,7 @@ pub async fn trigger_health_check(
     let start = Instant::now();
 
     // Parse transport from config
-    let transport = match parse_transport(&mcp.mcp_type, &mcp.config) {
+    let transport = match parse_transport(&mcp.mcp_type, &mcp.config, org_id) {
         Some(t) => t,
         None => {
             let details = HealthCheckDetails {
@@ -631,6 +887,7 @@ pub async fn trigger_health_check(
                 tools_count: None,
                 resources_count: None,
                 latency_ms: start.elapsed().as_millis() as u64,
+                connection_warm: false,
                 error: Some("Invalid MCP configuration: missing endpoint_url".to_string()),
                 tools_json: None,
                 resources_json: None,
@@ -655,10 +912,15 @@ pub async fn trigger_health_check(
         }
     };
 
-    // Create MCP client and test connection
-    let client = Arc::new(McpClient::new());
+    // Use the shared MCP client so tunneled MCPs are tested through their
+    // tunnel (and session/tunnel caches are reused)
+    let client = state.mcp_client.clone();
     let mcp_id_str = mcp_id.to_string();
 
+    // Capture whether this test hits a warm connection before the handshake
+    // establishes one
+    let connection_warm = client.is_connection_warm(&transport, &mcp_id_str).await;
+
     // Test 1: Initialize handshake
     let init_result = client.initialize(&transport, &mcp_id_str).await;
 
@@ -691,6 +953,7 @@ pub async fn trigger_health_check(
                             tools_count: Some(tools.len()),
                             resources_count,
                             latency_ms,
+                            connection_warm,
                             error: None,
                             tools_json,
                             resources_json,
@@ -708,6 +971,7 @@ pub async fn trigger_health_check(
                             tools_count: None,
                             resources_count: None,
                             latency_ms,
+                            connection_warm,
                             error: Some(format!("Failed to list tools: {}", e)),
                             tools_json: None,
                             resources_json: None,
@@ -729,6 +993,7 @@ pub async fn trigger_health_check(
                     tools_count: None,
                     resources_count: None,
                     latency_ms,
+                    connection_warm,
                     error: Some(error_message),
                     tools_json: None,
                     resources_json: None,
@@ -775,8 +1040,8 @@ pub async fn trigger_health_check(
         r#"
         INSERT INTO mcp_test_history (
             mcp_id, org_id, health_status, protocol_version, server_name, server_version,
-            tools_count, resources_count, latency_ms, error_message, tested_at, tested_by
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            tools_count, resources_count, latency_ms, connection_warm, error_message, tested_at, tested_by
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
         "#,
     )
     .bind(mcp_id)
@@ -788,6 +1053,7 @@ pub async fn trigger_health_check(
     .bind(details.tools_count.map(|c| c as i32))
     .bind(details.resources_count.map(|c| c as i32))
     .bind(details.latency_ms as i32)
+    .bind(details.connection_warm)
     .bind(&details.error)
     .bind(now)
     .bind(None::<uuid::Uuid>) // Use NULL for tested_by to avoid FK issues with auth.users vs public.users
@@ -797,6 +1063,57 @@ pub async fn trigger_health_check(
         tracing::error!("Failed to save test history for MCP {}: {}", mcp_id, e);
     }
 
+    // Manual checks feed the same health webhook as the automatic sweep
+    if health_status != mcp.health_status && mcp.health_status != "unknown" {
+        crate::mcp::health_webhooks::notify(
+            &state.pool,
+            crate::mcp::health_webhooks::McpWebhookEvent {
+                event: "mcp.health_changed",
+                mcp_id,
+                from: mcp.health_status.clone(),
+                to: health_status.clone(),
+                evidence: serde_json::json!({
+                    "source": "manual",
+                    "latency_ms": details.latency_ms,
+                    "tools_count": details.tools_count,
+                    "connection_warm": details.connection_warm,
+                    "error": details.error,
+                }),
+            },
+        );
+        plexmcp_shared::org_webhooks::emit(
+            &state.pool,
+            org_id,
+            "mcp.status_changed",
+            serde_json::json!({
+                "mcp_id": mcp_id,
+                "from": mcp.health_status.clone(),
+                "to": health_status.clone(),
+            }),
+        )
+        .await;
+    }
+
+    // Manual checks also feed differential tools/list change detection,
+    // comparing against the snapshot this check replaced
+    if let (Some(previous_names), Some(current_names)) = (
+        crate::mcp::tool_changes::tool_names(mcp.tools_json.as_ref()),
+        crate::mcp::tool_changes::tool_names(details.tools_json.as_ref()),
+    ) {
+        if let Some(change) = crate::mcp::tool_changes::detect(&previous_names, &current_names) {
+            crate::mcp::tool_changes::notify(
+                &state.pool,
+                &state.mcp_streams,
+                &state.tool_catalog,
+                org_id,
+                mcp_id,
+                &mcp.name,
+                change,
+            )
+            .await;
+        }
+    }
+
     Ok(Json(HealthCheckResponse {
         mcp_id,
         health_status,
@@ -806,7 +1123,11 @@ pub async fn trigger_health_check(
 }
 
 /// Parse transport configuration from MCP config
-fn parse_transport(mcp_type: &str, config: &serde_json::Value) -> Option<McpTransport> {
+pub(crate) fn parse_transport(
+    mcp_type: &str,
+    config: &serde_json::Value,
+    org_id: Uuid,
+) -> Option<McpTransport> {
     // Support both "endpoint_url" and "url" keys for backwards compatibility
     let endpoint_url = config
         .get("endpoint_url")
@@ -815,9 +1136,39 @@ fn parse_transport(mcp_type: &str, config: &serde_json::Value) -> Option<McpTran
         .map(String::from)?;
     let auth = parse_auth(config);
 
+    // Optional SSH tunnel block; org_id is injected so key lookups stay
+    // scoped to the owning organization
+    let tunnel = config.get("ssh_tunnel").and_then(|v| {
+        let mut t: crate::mcp::ssh_tunnel::SshTunnelConfig =
+            serde_json::from_value(v.clone()).ok()?;
+        t.org_id = Some(org_id);
+        Some(t)
+    });
+
+    // Optional per-MCP outbound proxy
+    let proxy_url = config
+        .get("proxy_url")
+        .and_then(|v| v.as_str())
+        .map(String::from);
+
+    // Optional per-MCP cap on upstream response size
+    let max_response_bytes = config.get("max_response_bytes").and_then(|v| v.as_u64());
+
     match mcp_type {
-        "http" => Some(McpTransport::Http { endpoint_url, auth }),
-        "sse" | "websocket" => Some(McpTransport::Sse { endpoint_url, auth }),
+        "http" => Some(McpTransport::Http {
+            endpoint_url,
+            auth,
+            tunnel,
+            proxy_url,
+            max_response_bytes,
+        }),
+        "sse" | "websocket" => Some(McpTransport::Sse {
+            endpoint_url,
+            auth,
+            tunnel,
+            proxy_url,
+            max_response_bytes,
+        }),
         "stdio" => {
             let command = config.get("command")?.as_str()?.to_string();
             let args = config
@@ -838,11 +1189,27 @@ fn parse_transport(mcp_type: &str, config: &serde_json::Value) -> Option<McpTran
                         .collect()
                 })
                 .unwrap_or_default();
-            Some(McpTransport::Stdio { command, args, env })
+            // Optional resource-limit sandbox for the managed process
+            let sandbox = config
+                .get("sandbox")
+                .and_then(|s| serde_json::from_value(s.clone()).ok());
+            Some(McpTransport::Stdio {
+                command,
+                args,
+                env,
+                max_response_bytes,
+                sandbox,
+            })
         }
         _ => {
             // Default to HTTP if type is unknown but we have an endpoint
-            Some(McpTransport::Http { endpoint_url, auth })
+            Some(McpTransport::Http {
+                endpoint_url,
+                auth,
+                tunnel,
+                proxy_url,
+                max_response_bytes,
+            })
         }
     }
 }
@@ -943,6 +1310,34 @@ fn parse_auth(config: &serde_json::Value) -> McpAuth {
                 .to_string();
             McpAuth::Basic { username, password }
         }
+        // Templated headers with credential slots, or a provider preset
+        // (see crate::mcp::auth_template)
+        "headers" => match crate::mcp::auth_template::render_auth_headers(config) {
+            Ok(headers) => McpAuth::Headers { headers },
+            Err(e) => {
+                tracing::warn!(error = %e, "Invalid auth header template - connecting unauthenticated");
+                McpAuth::None
+            }
+        },
+        // Signed or static query parameters appended to the request URL
+        "query" => {
+            let params: Vec<(String, String)> = config
+                .get("query_params")
+                .and_then(|v| v.as_object())
+                .map(|obj| {
+                    obj.iter()
+                        .filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_string())))
+                        .collect()
+                })
+                .unwrap_or_default();
+            if params.is_empty() {
+                McpAuth::None
+            } else {
+                McpAuth::Query { params }
+            }
+        }
+        // "cookie" and "oauth" need the jar/vault, which the connection
+        // test can't reach - the test runs unauthenticated for those
         _ => McpAuth::None,
     }
 }
@@ -1027,11 +1422,12 @@ pub async fn update_mcp_config(
     .await?
     .ok_or(ApiError::NotFound)?;
 
+    state.tool_catalog.invalidate(org_id).await;
+
     Ok(Json(result.0))
 }
 
 /// Get organization subscription tier
-#[allow(dead_code)] // Reserved for tier-based feature gating
 async fn get_org_tier(pool: &sqlx::PgPool, org_id: Uuid) -> Result<SubscriptionTier, ApiError> {
     let result: Option<(String,)> =
         sqlx::query_as("SELECT subscription_tier FROM organizations WHERE id = $1")
@@ -1057,7 +1453,7 @@ struct OrgLimitData {
 }
 
 /// Get organization's effective limits (tier + custom overrides)
-async fn get_org_effective_limits(
+pub(crate) async fn get_org_effective_limits(
     pool: &sqlx::PgPool,
     org_id: Uuid,
 ) -> Result<plexmcp_shared::types::EffectiveLimits, ApiError> {
@@ -1089,7 +1485,7 @@ async fn get_org_effective_limits(
 }
 
 /// Get current MCP count for organization
-async fn get_mcp_count(pool: &sqlx::PgPool, org_id: Uuid) -> Result<i64, ApiError> {
+pub(crate) async fn get_mcp_count(pool: &sqlx::PgPool, org_id: Uuid) -> Result<i64, ApiError> {
     let result: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM mcp_instances WHERE org_id = $1")
         .bind(org_id)
         .fetch_one(pool)
@@ -1132,7 +1528,7 @@ pub async fn get_test_history(
     let history: Vec<TestHistoryEntry> = sqlx::query_as(
         r#"
         SELECT id, mcp_id, health_status, protocol_version, server_name, server_version,
-               tools_count, resources_count, latency_ms, error_message, tested_at, tested_by
+               tools_count, resources_count, latency_ms, connection_warm, error_message, tested_at, tested_by
         FROM mcp_test_history
         WHERE mcp_id = $1 AND org_id = $2
         ORDER BY tested_at DESC
@@ -1217,6 +1613,23 @@ pub async fn validate_config(
         McpAuth::Bearer { .. } => "Bearer token configured",
         McpAuth::ApiKey { header, .. } => &format!("API key configured (header: {})", header),
         McpAuth::Basic { .. } => "Basic auth configured",
+        McpAuth::Headers { headers } => &format!(
+            "Templated auth headers configured ({})",
+            headers
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        McpAuth::Query { params } => &format!(
+            "Query parameter auth configured ({})",
+            params
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        McpAuth::Cookie { .. } => "Session cookie auth configured",
         McpAuth::None => "No authentication configured",
     };
     validations.push(ValidationCheck {
@@ -1226,7 +1639,29 @@ pub async fn validate_config(
         latency_ms: None,
     });
 
-    // Check 3: Endpoint reachability (only if URL is valid)
+    // Check 3: Timeout/retry policy block (only when one is configured)
+    if mcp.config.get("retry_policy").is_some() {
+        use crate::mcp::retry_policy::RetryPolicy;
+        let problems = RetryPolicy::validate_block(&mcp.config);
+        if problems.is_empty() {
+            let policy = RetryPolicy::from_config(&mcp.config).unwrap_or_default();
+            validations.push(ValidationCheck {
+                check: "retry_policy".to_string(),
+                passed: true,
+                message: format!("Retry policy configured: {}", policy.describe()),
+                latency_ms: None,
+            });
+        } else {
+            validations.push(ValidationCheck {
+                check: "retry_policy".to_string(),
+                passed: false,
+                message: problems.join("; "),
+                latency_ms: None,
+            });
+        }
+    }
+
+    // Check 4: Endpoint reachability (only if URL is valid)
     if url_valid {
         if let Some(url) = endpoint_url {
             let start = Instant::now();
@@ -1316,31 +1751,31 @@ pub async fn test_all_mcps(
     .await?;
 
     let mut results = Vec::new();
-    let client = Arc::new(McpClient::new());
+    let client = state.mcp_client.clone();
 
     for mcp in mcps {
         let start = Instant::now();
         let mcp_id_str = mcp.id.to_string();
 
-        let (health_status, tools_count, error) = match parse_transport(&mcp.mcp_type, &mcp.config)
-        {
-            Some(transport) => match client.initialize(&transport, &mcp_id_str).await {
-                Ok(_init) => match client.get_tools(&transport, &mcp_id_str).await {
-                    Ok(tools) => ("healthy".to_string(), Some(tools.len()), None),
-                    Err(e) => (
-                        "unhealthy".to_string(),
-                        None,
-                        Some(format!("Failed to list tools: {}", e)),
-                    ),
+        let (health_status, tools_count, error) =
+            match parse_transport(&mcp.mcp_type, &mcp.config, org_id) {
+                Some(transport) => match client.initialize(&transport, &mcp_id_str).await {
+                    Ok(_init) => match client.get_tools(&transport, &mcp_id_str).await {
+                        Ok(tools) => ("healthy".to_string(), Some(tools.len()), None),
+                        Err(e) => (
+                            "unhealthy".to_string(),
+                            None,
+                            Some(format!("Failed to list tools: {}", e)),
+                        ),
+                    },
+                    Err(e) => ("unhealthy".to_string(), None, Some(format_mcp_error(&e))),
                 },
-                Err(e) => ("unhealthy".to_string(), None, Some(format_mcp_error(&e))),
-            },
-            None => (
-                "unhealthy".to_string(),
-                None,
-                Some("Invalid configuration".to_string()),
-            ),
-        };
+                None => (
+                    "unhealthy".to_string(),
+                    None,
+                    Some("Invalid configuration".to_string()),
+                ),
+            };
 
         let latency_ms = start.elapsed().as_millis() as u64;
 
@@ -1405,3 +1840,1133 @@ pub async fn test_all_mcps(
         tested_at: format_datetime(now),
     }))
 }
+
+// =============================================================================
+// Aggregated Tool Catalog
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct ListToolsQuery {
+    /// Case-insensitive text search over tool names and descriptions
+    pub q: Option<String>,
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ToolCatalogResponse {
+    pub tools: Vec<crate::mcp::tool_catalog::CatalogTool>,
+    /// Total matching tools before pagination
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+    /// Upstreams that failed during aggregation (the catalog is partial)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<crate::mcp::types::McpError>,
+    /// Whether this response was served from the catalog cache
+    pub cached: bool,
+}
+
+/// List the merged tool catalog aggregated across all of the org's MCPs
+///
+/// Runs one tools/list aggregation pass (cached briefly per org) instead
+/// of making clients discover namespaced tools MCP by MCP. Supports text
+/// search over names/descriptions and pagination.
+pub async fn list_org_tools(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<ListToolsQuery>,
+) -> Result<Json<ToolCatalogResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query.per_page.unwrap_or(50).clamp(1, 200);
+
+    let (catalog, cached) = match state.tool_catalog.get(org_id).await {
+        Some(catalog) => (catalog, true),
+        None => {
+            let handler = crate::mcp::handlers::McpProxyHandler::new(
+                state.pool.clone(),
+                std::sync::Arc::new(state.config.clone()),
+                state.mcp_client.clone(),
+            );
+            let request = crate::mcp::types::JsonRpcRequest {
+                jsonrpc: "2.0".to_string(),
+                id: Some(crate::mcp::types::JsonRpcId::Number(1)),
+                method: "tools/list".to_string(),
+                params: None,
+            };
+
+            let tracked = handler.handle_request(org_id, request).await;
+            if let Some(error) = tracked.response.error {
+                tracing::error!(
+                    org_id = %org_id,
+                    error = %error.message,
+                    "Tool catalog aggregation failed"
+                );
+                return Err(ApiError::Internal);
+            }
+
+            let result: crate::mcp::types::AggregatedToolsListResult = match tracked.response.result
+            {
+                Some(value) => serde_json::from_value(value).map_err(|e| {
+                    tracing::error!(org_id = %org_id, error = %e, "Invalid tools/list result");
+                    ApiError::Internal
+                })?,
+                None => crate::mcp::types::AggregatedToolsListResult {
+                    tools: vec![],
+                    errors: vec![],
+                    next_cursor: None,
+                    meta: None,
+                },
+            };
+
+            let catalog =
+                crate::mcp::tool_catalog::Catalog::from_aggregated(result.tools, result.errors);
+            (state.tool_catalog.insert(org_id, catalog).await, false)
+        }
+    };
+
+    // Case-insensitive substring search over names and descriptions
+    let needle = query
+        .q
+        .as_deref()
+        .map(str::trim)
+        .filter(|q| !q.is_empty())
+        .map(str::to_lowercase);
+
+    let matching: Vec<&crate::mcp::tool_catalog::CatalogTool> = catalog
+        .tools
+        .iter()
+        .filter(|tool| match &needle {
+            Some(needle) => {
+                tool.name.to_lowercase().contains(needle)
+                    || tool
+                        .description
+                        .as_deref()
+                        .map(|d| d.to_lowercase().contains(needle))
+                        .unwrap_or(false)
+            }
+            None => true,
+        })
+        .collect();
+
+    let total = matching.len() as i64;
+    let offset = ((page - 1) * per_page) as usize;
+    let tools: Vec<crate::mcp::tool_catalog::CatalogTool> = matching
+        .into_iter()
+        .skip(offset)
+        .take(per_page as usize)
+        .cloned()
+        .collect();
+
+    Ok(Json(ToolCatalogResponse {
+        tools,
+        total,
+        page,
+        per_page,
+        errors: catalog.errors.clone(),
+        cached,
+    }))
+}
+
+// =============================================================================
+// Circuit Breaker Tuning
+// =============================================================================
+
+/// Update circuit breaker settings request; omitted fields keep their
+/// current value, `reset: true` drops the override entirely
+#[derive(Debug, Deserialize)]
+pub struct UpdateCircuitRequest {
+    pub failure_threshold: Option<u32>,
+    pub min_backoff_ms: Option<u64>,
+    pub max_backoff_ms: Option<u64>,
+    /// Clear the per-MCP override and return to platform defaults
+    pub reset: Option<bool>,
+}
+
+/// Circuit breaker status response
+#[derive(Debug, Serialize)]
+pub struct McpCircuitResponse {
+    pub mcp_id: Uuid,
+    pub circuit: crate::mcp::circuit_breaker::CircuitBreakerSnapshot,
+}
+
+/// Get the live circuit breaker state for an MCP
+///
+/// Reports the current circuit state (closed/open/half-open), consecutive
+/// failure count, open transitions, and the effective threshold/backoff
+/// configuration.
+pub async fn get_mcp_circuit(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(mcp_id): Path<Uuid>,
+) -> Result<Json<McpCircuitResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let (config,): (serde_json::Value,) =
+        sqlx::query_as("SELECT config FROM mcp_instances WHERE id = $1 AND org_id = $2")
+            .bind(mcp_id)
+            .bind(org_id)
+            .fetch_optional(&state.pool)
+            .await?
+            .ok_or(ApiError::NotFound)?;
+
+    // Sync the persisted override first so the snapshot reflects it even
+    // if the proxy hasn't loaded this MCP since the process started
+    state
+        .mcp_client
+        .apply_circuit_override(
+            mcp_id,
+            crate::mcp::circuit_breaker::CircuitBreakerConfig::from_config(&config),
+        )
+        .await;
+
+    Ok(Json(McpCircuitResponse {
+        mcp_id,
+        circuit: state.mcp_client.circuit_snapshot(mcp_id).await,
+    }))
+}
+
+/// Update circuit breaker settings for an MCP
+///
+/// The override is persisted in the MCP's config JSON (under
+/// `circuit_breaker`) and applied to the live breaker immediately.
+pub async fn update_mcp_circuit(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(mcp_id): Path<Uuid>,
+    Json(req): Json<UpdateCircuitRequest>,
+) -> Result<Json<McpCircuitResponse>, ApiError> {
+    use crate::mcp::circuit_breaker::CircuitBreakerConfig;
+    use std::time::Duration;
+
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let (config,): (serde_json::Value,) =
+        sqlx::query_as("SELECT config FROM mcp_instances WHERE id = $1 AND org_id = $2")
+            .bind(mcp_id)
+            .bind(org_id)
+            .fetch_optional(&state.pool)
+            .await?
+            .ok_or(ApiError::NotFound)?;
+
+    let override_config = if req.reset.unwrap_or(false) {
+        None
+    } else {
+        // Merge the request into the existing override (or the platform
+        // defaults when no override exists yet)
+        let mut merged = CircuitBreakerConfig::from_config(&config).unwrap_or_default();
+        if let Some(threshold) = req.failure_threshold {
+            merged.failure_threshold = threshold;
+        }
+        if let Some(ms) = req.min_backoff_ms {
+            merged.min_backoff = Duration::from_millis(ms);
+        }
+        if let Some(ms) = req.max_backoff_ms {
+            merged.max_backoff = Duration::from_millis(ms);
+        }
+
+        if !(1..=100).contains(&merged.failure_threshold) {
+            return Err(ApiError::Validation(
+                "failure_threshold must be between 1 and 100".to_string(),
+            ));
+        }
+        if !(100..=600_000).contains(&(merged.min_backoff.as_millis() as u64)) {
+            return Err(ApiError::Validation(
+                "min_backoff_ms must be between 100 and 600000".to_string(),
+            ));
+        }
+        if merged.max_backoff < merged.min_backoff || merged.max_backoff > Duration::from_secs(3600)
+        {
+            return Err(ApiError::Validation(
+                "max_backoff_ms must be between min_backoff_ms and 3600000".to_string(),
+            ));
+        }
+        Some(merged)
+    };
+
+    // Persist into the config JSON so the override survives restarts
+    match &override_config {
+        Some(c) => {
+            sqlx::query(
+                r#"
+                UPDATE mcp_instances
+                SET config = jsonb_set(config, '{circuit_breaker}', $3::jsonb),
+                    updated_at = NOW()
+                WHERE id = $1 AND org_id = $2
+                "#,
+            )
+            .bind(mcp_id)
+            .bind(org_id)
+            .bind(serde_json::json!({
+                "failure_threshold": c.failure_threshold,
+                "min_backoff_ms": c.min_backoff.as_millis() as u64,
+                "max_backoff_ms": c.max_backoff.as_millis() as u64,
+            }))
+            .execute(&state.pool)
+            .await?;
+        }
+        None => {
+            sqlx::query(
+                r#"
+                UPDATE mcp_instances
+                SET config = config - 'circuit_breaker', updated_at = NOW()
+                WHERE id = $1 AND org_id = $2
+                "#,
+            )
+            .bind(mcp_id)
+            .bind(org_id)
+            .execute(&state.pool)
+            .await?;
+        }
+    }
+
+    state
+        .mcp_client
+        .apply_circuit_override(mcp_id, override_config)
+        .await;
+
+    Ok(Json(McpCircuitResponse {
+        mcp_id,
+        circuit: state.mcp_client.circuit_snapshot(mcp_id).await,
+    }))
+}
+
+// =============================================================================
+// Health Webhooks
+// =============================================================================
+
+/// Configure the health webhook for an MCP; a missing secret is generated
+/// server-side and returned once in the response
+#[derive(Debug, Deserialize)]
+pub struct UpsertHealthWebhookRequest {
+    pub url: String,
+    /// HMAC signing secret; generated when omitted
+    pub secret: Option<String>,
+    /// Re-enable a webhook that delivery failures disabled
+    pub enabled: Option<bool>,
+}
+
+/// Health webhook status response; the secret only appears in the PUT
+/// response that created or rotated it
+#[derive(Debug, Serialize)]
+pub struct McpHealthWebhookResponse {
+    pub mcp_id: Uuid,
+    pub url: String,
+    pub enabled: bool,
+    pub disabled_reason: Option<String>,
+    pub consecutive_failures: i32,
+    pub last_success_at: Option<String>,
+    pub last_failure_at: Option<String>,
+    pub secret_set: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct HealthWebhookRow {
+    url: String,
+    enabled: bool,
+    disabled_reason: Option<String>,
+    consecutive_failures: i32,
+    last_success_at: Option<OffsetDateTime>,
+    last_failure_at: Option<OffsetDateTime>,
+}
+
+impl HealthWebhookRow {
+    fn into_response(self, mcp_id: Uuid, secret: Option<String>) -> McpHealthWebhookResponse {
+        McpHealthWebhookResponse {
+            mcp_id,
+            url: self.url,
+            enabled: self.enabled,
+            disabled_reason: self.disabled_reason,
+            consecutive_failures: self.consecutive_failures,
+            last_success_at: self.last_success_at.map(format_datetime),
+            last_failure_at: self.last_failure_at.map(format_datetime),
+            secret_set: true,
+            secret,
+        }
+    }
+}
+
+/// Verify the MCP belongs to the caller's org
+async fn require_org_mcp(state: &AppState, mcp_id: Uuid, org_id: Uuid) -> Result<(), ApiError> {
+    sqlx::query_scalar::<_, Uuid>("SELECT id FROM mcp_instances WHERE id = $1 AND org_id = $2")
+        .bind(mcp_id)
+        .bind(org_id)
+        .fetch_optional(&state.pool)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    Ok(())
+}
+
+/// Get the health webhook configured for an MCP
+pub async fn get_mcp_health_webhook(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(mcp_id): Path<Uuid>,
+) -> Result<Json<McpHealthWebhookResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    require_org_mcp(&state, mcp_id, org_id).await?;
+
+    let row: HealthWebhookRow = sqlx::query_as(
+        r#"
+        SELECT url, enabled, disabled_reason, consecutive_failures,
+               last_success_at, last_failure_at
+        FROM mcp_health_webhooks
+        WHERE mcp_id = $1
+        "#,
+    )
+    .bind(mcp_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(row.into_response(mcp_id, None)))
+}
+
+/// Create or replace the health webhook for an MCP
+///
+/// The webhook receives signed callbacks whenever the MCP's health flips
+/// or its circuit breaker opens/closes. Setting a new config resets the
+/// failure counters and re-enables a webhook that was disabled by
+/// delivery failures (unless `enabled: false` is passed explicitly).
+pub async fn upsert_mcp_health_webhook(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(mcp_id): Path<Uuid>,
+    Json(req): Json<UpsertHealthWebhookRequest>,
+) -> Result<Json<McpHealthWebhookResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    require_org_mcp(&state, mcp_id, org_id).await?;
+
+    let url = req.url.trim().to_string();
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        return Err(ApiError::Validation(
+            "Webhook URL must start with http:// or https://".to_string(),
+        ));
+    }
+    if url.len() > 2048 {
+        return Err(ApiError::Validation(
+            "Webhook URL must be at most 2048 characters".to_string(),
+        ));
+    }
+
+    let secret = match req.secret {
+        Some(secret) => {
+            let secret = secret.trim().to_string();
+            if secret.len() < 16 || secret.len() > 256 {
+                return Err(ApiError::Validation(
+                    "Webhook secret must be between 16 and 256 characters".to_string(),
+                ));
+            }
+            secret
+        }
+        None => hex::encode(rand::random::<[u8; 32]>()),
+    };
+    let enabled = req.enabled.unwrap_or(true);
+
+    let row: HealthWebhookRow = sqlx::query_as(
+        r#"
+        INSERT INTO mcp_health_webhooks (org_id, mcp_id, url, secret, enabled)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (mcp_id) DO UPDATE SET
+            url = EXCLUDED.url,
+            -- keep the outgoing secret for the verify-sample helper
+            previous_secret = CASE
+                WHEN mcp_health_webhooks.secret IS DISTINCT FROM EXCLUDED.secret
+                THEN mcp_health_webhooks.secret
+                ELSE mcp_health_webhooks.previous_secret
+            END,
+            secret_rotated_at = CASE
+                WHEN mcp_health_webhooks.secret IS DISTINCT FROM EXCLUDED.secret
+                THEN NOW()
+                ELSE mcp_health_webhooks.secret_rotated_at
+            END,
+            secret = EXCLUDED.secret,
+            enabled = EXCLUDED.enabled,
+            disabled_reason = NULL,
+            consecutive_failures = 0,
+            updated_at = NOW()
+        RETURNING url, enabled, disabled_reason, consecutive_failures,
+                  last_success_at, last_failure_at
+        "#,
+    )
+    .bind(org_id)
+    .bind(mcp_id)
+    .bind(&url)
+    .bind(&secret)
+    .bind(enabled)
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok(Json(row.into_response(mcp_id, Some(secret))))
+}
+
+/// Remove the health webhook for an MCP
+pub async fn delete_mcp_health_webhook(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(mcp_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    require_org_mcp(&state, mcp_id, org_id).await?;
+
+    let result = sqlx::query("DELETE FROM mcp_health_webhooks WHERE mcp_id = $1")
+        .bind(mcp_id)
+        .execute(&state.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Replay request body: which logged request to re-send
+#[derive(Debug, Deserialize)]
+pub struct ReplayMcpRequest {
+    pub request_id: Uuid,
+}
+
+/// Outcome of the original logged request
+///
+/// Response bodies are not stored in the audit log, so the replay diff
+/// compares the logged outcome fields (status, error, latency) rather than
+/// full payloads.
+#[derive(Debug, Serialize)]
+pub struct ReplayOriginal {
+    pub request_id: Uuid,
+    pub method: String,
+    pub logged_at: String,
+    pub http_status_code: i32,
+    pub latency_ms: Option<i32>,
+    pub error_code: Option<String>,
+    pub error_message: Option<String>,
+}
+
+/// Outcome of the replayed request, including the fresh response body
+#[derive(Debug, Serialize)]
+pub struct ReplayOutcome {
+    pub response: crate::mcp::types::JsonRpcResponse,
+    pub http_status_code: i32,
+    pub latency_ms: i32,
+    pub error_code: Option<String>,
+    pub error_message: Option<String>,
+}
+
+/// Field-level comparison between the logged outcome and the replay
+#[derive(Debug, Serialize)]
+pub struct ReplayDiff {
+    pub status_changed: bool,
+    pub error_code_changed: bool,
+    pub error_message_changed: bool,
+    /// Replay latency minus original latency (negative = replay was faster)
+    pub latency_delta_ms: Option<i32>,
+}
+
+/// Replay response: original outcome, fresh outcome, and the diff
+#[derive(Debug, Serialize)]
+pub struct ReplayMcpResponse {
+    pub original: ReplayOriginal,
+    pub replay: ReplayOutcome,
+    pub diff: ReplayDiff,
+}
+
+/// Re-send a previously logged MCP request against the upstream
+///
+/// Debugging console endpoint: looks up the logged JSON-RPC payload by
+/// `request_id`, dispatches it through the proxy handler restricted to the
+/// target MCP, and returns the fresh response alongside a diff of the
+/// outcome. Useful for reproducing upstream regressions without leaving
+/// the dashboard.
+///
+/// Replays go straight to the handler - they are not billed, rate limited,
+/// or written back to the audit log as client traffic. Org owners/admins
+/// only, since a replayed `tools/call` re-executes the tool upstream.
+pub async fn replay_mcp_request(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(mcp_id): Path<Uuid>,
+    Json(req): Json<ReplayMcpRequest>,
+) -> Result<Json<ReplayMcpResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    // The target MCP must belong to the org and be active
+    let status: String =
+        sqlx::query_scalar("SELECT status FROM mcp_instances WHERE id = $1 AND org_id = $2")
+            .bind(mcp_id)
+            .bind(org_id)
+            .fetch_optional(&state.pool)
+            .await?
+            .ok_or(ApiError::NotFound)?;
+
+    if status != "active" {
+        return Err(ApiError::Validation(format!(
+            "MCP is not active (status: {})",
+            status
+        )));
+    }
+
+    #[derive(sqlx::FromRow)]
+    struct LoggedRequestRow {
+        endpoint_path: String,
+        http_status_code: i32,
+        latency_ms: Option<i32>,
+        error_code: Option<String>,
+        error_message: Option<String>,
+        request_payload: Option<serde_json::Value>,
+        created_at: OffsetDateTime,
+    }
+
+    let row: LoggedRequestRow = sqlx::query_as(
+        r#"
+        SELECT endpoint_path, http_status_code, latency_ms, error_code,
+               error_message, request_payload, created_at
+        FROM mcp_request_log
+        WHERE request_id = $1 AND organization_id = $2
+        "#,
+    )
+    .bind(req.request_id)
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    let payload = row.request_payload.as_ref().ok_or_else(|| {
+        ApiError::Validation(
+            "Request is not replayable: no payload was captured (it predates replay support or exceeded the capture size limit)".to_string(),
+        )
+    })?;
+
+    let method = payload
+        .get("method")
+        .and_then(|m| m.as_str())
+        .unwrap_or(&row.endpoint_path)
+        .to_string();
+
+    let replay_request = crate::mcp::types::JsonRpcRequest {
+        jsonrpc: "2.0".to_string(),
+        id: Some(crate::mcp::types::JsonRpcId::String(format!(
+            "replay-{}",
+            req.request_id
+        ))),
+        method: method.clone(),
+        params: payload.get("params").filter(|p| !p.is_null()).cloned(),
+    };
+
+    // Dispatch restricted to the target MCP so the replay can't fan out
+    let handler = crate::mcp::McpProxyHandler::new(
+        state.pool.clone(),
+        std::sync::Arc::new(state.config.clone()),
+        state.mcp_client.clone(),
+    )
+    .with_moderation(state.moderation.clone())
+    .with_tool_cache(state.tool_cache.clone());
+
+    let filter = super::mcp_proxy::McpFilter {
+        mode: "selected".to_string(),
+        allowed_ids: Some(vec![mcp_id]),
+    };
+
+    let start = Instant::now();
+    let tracked = handler
+        .handle_request_filtered(org_id, replay_request, filter)
+        .await;
+    let replay_latency_ms = start.elapsed().as_millis() as i32;
+
+    let replay_status = if tracked.response.error.is_some() {
+        400
+    } else {
+        200
+    };
+    let replay_error_code = tracked
+        .response
+        .error
+        .as_ref()
+        .map(|e| format!("{}", e.code));
+    let replay_error_message = tracked.response.error.as_ref().map(|e| e.message.clone());
+
+    let diff = ReplayDiff {
+        status_changed: replay_status != row.http_status_code,
+        error_code_changed: replay_error_code != row.error_code,
+        error_message_changed: replay_error_message != row.error_message,
+        latency_delta_ms: row.latency_ms.map(|orig| replay_latency_ms - orig),
+    };
+
+    Ok(Json(ReplayMcpResponse {
+        original: ReplayOriginal {
+            request_id: req.request_id,
+            method,
+            logged_at: format_datetime(row.created_at),
+            http_status_code: row.http_status_code,
+            latency_ms: row.latency_ms,
+            error_code: row.error_code,
+            error_message: row.error_message,
+        },
+        replay: ReplayOutcome {
+            response: tracked.response,
+            http_status_code: replay_status,
+            latency_ms: replay_latency_ms,
+            error_code: replay_error_code,
+            error_message: replay_error_message,
+        },
+        diff,
+    }))
+}
+
+/// Captured payload for a logged MCP request
+#[derive(Debug, Serialize)]
+pub struct McpLogPayloadResponse {
+    pub request_id: Uuid,
+    pub logged_at: String,
+    /// Redacted `{"request", "response"}` pair captured at proxy time
+    pub payload: serde_json::Value,
+}
+
+/// Fetch the captured request/response payload for a logged MCP request
+///
+/// Only populated for orgs that enabled payload capture (see
+/// `/org/security/payload-capture`); the stored copy was redacted at
+/// write time, so secrets matched by the org's rules never appear here.
+/// Org owners/admins only, matching the replay console.
+pub async fn get_mcp_log_payload(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(log_id): Path<Uuid>,
+) -> Result<Json<McpLogPayloadResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    #[derive(sqlx::FromRow)]
+    struct CapturedRow {
+        captured_payload: Option<serde_json::Value>,
+        created_at: OffsetDateTime,
+    }
+
+    let row: CapturedRow = sqlx::query_as(
+        r#"
+        SELECT captured_payload, created_at
+        FROM mcp_request_log
+        WHERE request_id = $1 AND organization_id = $2
+        "#,
+    )
+    .bind(log_id)
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    let payload = row.captured_payload.ok_or_else(|| {
+        ApiError::Validation(
+            "No payload was captured for this request (payload capture was disabled when it was logged, or the retention window elapsed)".to_string(),
+        )
+    })?;
+
+    Ok(Json(McpLogPayloadResponse {
+        request_id: log_id,
+        logged_at: format_datetime(row.created_at),
+        payload,
+    }))
+}
+
+// ============ Managed Stdio Processes ============
+
+/// Managed stdio process state for an MCP
+#[derive(Debug, Serialize)]
+pub struct McpProcessResponse {
+    pub mcp_id: Uuid,
+    pub running: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+}
+
+/// Recent stderr output from a managed stdio process
+#[derive(Debug, Serialize)]
+pub struct McpProcessLogsResponse {
+    pub mcp_id: Uuid,
+    pub lines: Vec<crate::mcp::client::StdioLogLine>,
+}
+
+/// Load the stdio transport for an MCP, verifying org ownership
+///
+/// Shared by the process lifecycle handlers; rejects MCPs that don't use
+/// the stdio transport so HTTP MCPs can't be "started".
+async fn fetch_stdio_transport(
+    state: &AppState,
+    org_id: Uuid,
+    mcp_id: Uuid,
+) -> Result<McpTransport, ApiError> {
+    let (mcp_type, config): (String, serde_json::Value) =
+        sqlx::query_as("SELECT mcp_type, config FROM mcp_instances WHERE id = $1 AND org_id = $2")
+            .bind(mcp_id)
+            .bind(org_id)
+            .fetch_optional(&state.pool)
+            .await?
+            .ok_or(ApiError::NotFound)?;
+
+    if mcp_type != "stdio" {
+        return Err(ApiError::Validation(format!(
+            "MCP does not use the stdio transport (type: {})",
+            mcp_type
+        )));
+    }
+
+    parse_transport(&mcp_type, &config, org_id)
+        .ok_or_else(|| ApiError::Validation("MCP config is missing a valid command".to_string()))
+}
+
+/// Get the managed process state for a stdio MCP
+pub async fn get_mcp_process(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(mcp_id): Path<Uuid>,
+) -> Result<Json<McpProcessResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    fetch_stdio_transport(&state, org_id, mcp_id).await?;
+
+    let status = state
+        .mcp_client
+        .stdio_process_status(&mcp_id.to_string())
+        .await;
+    Ok(Json(McpProcessResponse {
+        mcp_id,
+        running: status.running,
+        pid: status.pid,
+    }))
+}
+
+/// Start the managed process for a stdio MCP
+///
+/// No-op (reporting the live state) when the process is already running.
+/// Org owners/admins only - starting a process executes the org-configured
+/// command on the host.
+pub async fn start_mcp_process(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(mcp_id): Path<Uuid>,
+) -> Result<Json<McpProcessResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+    let transport = fetch_stdio_transport(&state, org_id, mcp_id).await?;
+
+    let status = state
+        .mcp_client
+        .start_stdio_process(&mcp_id.to_string(), &transport)
+        .await
+        .map_err(|e| ApiError::Validation(format!("Failed to start process: {}", e)))?;
+    Ok(Json(McpProcessResponse {
+        mcp_id,
+        running: status.running,
+        pid: status.pid,
+    }))
+}
+
+/// Stop the managed process for a stdio MCP
+pub async fn stop_mcp_process(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(mcp_id): Path<Uuid>,
+) -> Result<Json<McpProcessResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+    fetch_stdio_transport(&state, org_id, mcp_id).await?;
+
+    state
+        .mcp_client
+        .stop_stdio_process(&mcp_id.to_string())
+        .await;
+    Ok(Json(McpProcessResponse {
+        mcp_id,
+        running: false,
+        pid: None,
+    }))
+}
+
+/// Restart the managed process for a stdio MCP
+///
+/// Stop-then-start with the current config, so config edits take effect.
+pub async fn restart_mcp_process(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(mcp_id): Path<Uuid>,
+) -> Result<Json<McpProcessResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+    let transport = fetch_stdio_transport(&state, org_id, mcp_id).await?;
+
+    let key = mcp_id.to_string();
+    state.mcp_client.stop_stdio_process(&key).await;
+    let status = state
+        .mcp_client
+        .start_stdio_process(&key, &transport)
+        .await
+        .map_err(|e| ApiError::Validation(format!("Failed to restart process: {}", e)))?;
+    Ok(Json(McpProcessResponse {
+        mcp_id,
+        running: status.running,
+        pid: status.pid,
+    }))
+}
+
+/// Recent stderr output from a stdio MCP's managed process
+///
+/// The buffer is kept after exit, so this shows why a process died.
+pub async fn get_mcp_process_logs(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(mcp_id): Path<Uuid>,
+) -> Result<Json<McpProcessLogsResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    fetch_stdio_transport(&state, org_id, mcp_id).await?;
+
+    let lines = state
+        .mcp_client
+        .stdio_process_logs(&mcp_id.to_string())
+        .await;
+    Ok(Json(McpProcessLogsResponse { mcp_id, lines }))
+}
+
+// ============ Webhook Debugging ============
+
+/// Request to check a signature the customer received against their secret
+#[derive(Debug, Deserialize)]
+pub struct VerifyWebhookSampleRequest {
+    /// MCP whose webhook secret should be checked
+    pub mcp_id: Uuid,
+    /// Exact request body as received, byte for byte
+    pub payload: String,
+    /// Value of the X-PlexMCP-Signature header as received
+    pub signature: String,
+}
+
+/// Outcome of a signature verification attempt
+#[derive(Debug, Serialize)]
+pub struct VerifyWebhookSampleResponse {
+    /// True when the signature matches the current secret
+    pub valid: bool,
+    /// "current" or "previous" when a secret produced this signature
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched_secret: Option<String>,
+    /// What the signature should be for this payload and the current secret
+    pub expected_signature: String,
+    /// Common-mistake diagnosis (missing prefix, hex case, whitespace)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+/// Check whether a provided signature matches a secret, diagnosing common
+/// formatting mistakes. Returns Some(hint) on a match; the hint is None
+/// when the signature was exactly right.
+fn match_webhook_signature(secret: &str, payload: &str, provided: &str) -> Option<Option<String>> {
+    let expected = crate::mcp::health_webhooks::sign_payload(secret, payload);
+    if provided == expected {
+        return Some(None);
+    }
+
+    let trimmed = provided.trim();
+    let (had_prefix, hex_part) = match trimmed.strip_prefix("sha256=") {
+        Some(rest) => (true, rest),
+        None => (false, trimmed),
+    };
+    let expected_hex = expected.trim_start_matches("sha256=");
+    if hex_part.eq_ignore_ascii_case(expected_hex) {
+        let hint = if !had_prefix {
+            "Signature digest is correct but the 'sha256=' prefix is missing"
+        } else if hex_part != expected_hex {
+            "Signature digest is correct but hex-encoded in uppercase; compare case-insensitively or emit lowercase"
+        } else {
+            "Signature is correct but carries surrounding whitespace"
+        };
+        return Some(Some(hint.to_string()));
+    }
+    None
+}
+
+/// Check a received webhook signature against the MCP's secrets
+///
+/// Debugging aid for receiver implementations: reports whether the
+/// signature validates, whether it was produced with the current or the
+/// previous (pre-rotation) secret, and what the signature should have
+/// been. The payload must be passed exactly as received - re-serialized
+/// JSON will not match.
+pub async fn verify_webhook_sample(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<VerifyWebhookSampleRequest>,
+) -> Result<Json<VerifyWebhookSampleResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    require_org_mcp(&state, req.mcp_id, org_id).await?;
+
+    let (secret, previous_secret): (String, Option<String>) =
+        sqlx::query_as("SELECT secret, previous_secret FROM mcp_health_webhooks WHERE mcp_id = $1")
+            .bind(req.mcp_id)
+            .fetch_optional(&state.pool)
+            .await?
+            .ok_or(ApiError::NotFound)?;
+
+    let expected_signature = crate::mcp::health_webhooks::sign_payload(&secret, &req.payload);
+
+    if let Some(hint) = match_webhook_signature(&secret, &req.payload, &req.signature) {
+        return Ok(Json(VerifyWebhookSampleResponse {
+            valid: true,
+            matched_secret: Some("current".to_string()),
+            expected_signature,
+            hint,
+        }));
+    }
+
+    if let Some(previous) = previous_secret.as_deref() {
+        if let Some(hint) = match_webhook_signature(previous, &req.payload, &req.signature) {
+            return Ok(Json(VerifyWebhookSampleResponse {
+                valid: false,
+                matched_secret: Some("previous".to_string()),
+                expected_signature,
+                hint: Some(hint.unwrap_or_else(|| {
+                    "Signature was produced with the previous secret; the webhook secret has been rotated since"
+                        .to_string()
+                })),
+            }));
+        }
+    }
+
+    Ok(Json(VerifyWebhookSampleResponse {
+        valid: false,
+        matched_secret: None,
+        expected_signature,
+        hint: Some(
+            "Signature matches neither the current nor the previous secret. Verify you are signing the exact bytes of the request body."
+                .to_string(),
+        ),
+    }))
+}
+
+/// Outcome of a webhook test delivery
+#[derive(Debug, Serialize)]
+pub struct WebhookTestResponse {
+    pub mcp_id: Uuid,
+    pub delivered: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Send a signed `mcp.webhook_test` event to the MCP's webhook
+///
+/// Exercises the receiver end-to-end with the production signing path;
+/// delivery counters update the same as for real events.
+pub async fn test_mcp_health_webhook(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(mcp_id): Path<Uuid>,
+) -> Result<Json<WebhookTestResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    require_org_mcp(&state, mcp_id, org_id).await?;
+
+    // Distinguish "no webhook" from a failed delivery up front
+    let enabled: bool =
+        sqlx::query_scalar("SELECT enabled FROM mcp_health_webhooks WHERE mcp_id = $1")
+            .bind(mcp_id)
+            .fetch_optional(&state.pool)
+            .await?
+            .ok_or(ApiError::NotFound)?;
+    if !enabled {
+        return Err(ApiError::Validation(
+            "Webhook is disabled; re-enable it before testing".to_string(),
+        ));
+    }
+
+    let result =
+        crate::mcp::health_webhooks::send_test(&state.pool, mcp_id, auth_user.user_id).await;
+
+    Ok(Json(WebhookTestResponse {
+        mcp_id,
+        delivered: result.is_ok(),
+        error: result.err(),
+    }))
+}
+
+// ============ Duplicate Request Diagnostics ============
+
+#[derive(sqlx::FromRow)]
+struct DuplicateOffenderRow {
+    api_key_id: Uuid,
+    key_name: Option<String>,
+    method: String,
+    tool_name: Option<String>,
+    total_duplicates: i64,
+    last_seen_at: OffsetDateTime,
+}
+
+/// One API key + request combination that keeps getting re-sent
+#[derive(Debug, Serialize)]
+pub struct DuplicateOffender {
+    pub api_key_id: Uuid,
+    /// Key name, when the key still exists
+    pub key_name: Option<String>,
+    pub method: String,
+    /// Prefixed tool name for tools/call duplicates
+    pub tool_name: Option<String>,
+    /// Duplicates detected across the window (first send not counted)
+    pub total_duplicates: i64,
+    pub last_seen_at: String,
+}
+
+impl From<DuplicateOffenderRow> for DuplicateOffender {
+    fn from(row: DuplicateOffenderRow) -> Self {
+        Self {
+            api_key_id: row.api_key_id,
+            key_name: row.key_name,
+            method: row.method,
+            tool_name: row.tool_name,
+            total_duplicates: row.total_duplicates,
+            last_seen_at: format_datetime(row.last_seen_at),
+        }
+    }
+}
+
+/// List the API key + request combinations most often re-sent
+///
+/// Surfaces the rollups written by the proxy's duplicate detector over
+/// the last 7 days so customers can track down buggy clients that keep
+/// re-sending the same call. Only hashes are stored, so the diagnostics
+/// show the method and tool name, never request bodies.
+pub async fn list_duplicate_offenders(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<Vec<DuplicateOffender>>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let offenders: Vec<DuplicateOffenderRow> = sqlx::query_as(
+        r#"
+        SELECT d.api_key_id, ak.name AS key_name, d.method, d.tool_name,
+               SUM(d.duplicate_count)::BIGINT AS total_duplicates,
+               MAX(d.last_seen_at) AS last_seen_at
+        FROM mcp_duplicate_requests d
+        LEFT JOIN api_keys ak ON ak.id = d.api_key_id
+        WHERE d.org_id = $1 AND d.day >= CURRENT_DATE - 6
+        GROUP BY d.api_key_id, ak.name, d.method, d.tool_name
+        ORDER BY total_duplicates DESC
+        LIMIT 20
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(
+        offenders.into_iter().map(DuplicateOffender::from).collect(),
+    ))
+}
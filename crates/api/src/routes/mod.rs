@@ -6,26 +6,46 @@ pub mod admin;
 pub mod admin_legacy; // Legacy monolithic admin.rs (being refactored into admin/ module)
 pub mod analytics_tracking;
 pub mod api_keys;
+pub mod attachments;
 pub mod audit;
 pub mod auth;
+pub mod badges;
 #[cfg(feature = "billing")]
 pub mod billing;
+pub mod branding;
 pub mod domains;
+pub mod email_domains;
 pub mod gdpr;
+pub mod github_sync;
+pub mod graphql;
 pub mod health;
 pub mod identities;
 pub mod invitations;
+pub mod list_query;
+pub mod mcp_oauth;
 pub mod mcp_proxy;
+pub mod mcp_transfer;
 pub mod mcps;
+pub mod moderation;
 pub mod notifications;
+pub mod onboarding;
+pub mod org_config;
+pub mod org_security;
 pub mod organizations;
 pub mod pin;
 pub mod public;
+pub mod recommendations;
+pub mod scim;
+pub mod spam;
+pub mod ssh_keys;
+pub mod status;
 pub mod support;
+pub mod support_email;
 pub mod two_factor;
 #[cfg(feature = "billing")]
 pub mod usage;
 pub mod users;
+pub mod webhooks;
 
 use axum::{
     extract::DefaultBodyLimit,
@@ -61,13 +81,27 @@ pub fn create_router(state: AppState) -> Router {
     let health_routes = Router::new()
         .route("/health", get(health::health))
         .route("/health/live", get(health::liveness))
-        .route("/health/ready", get(health::readiness));
+        .route("/health/startup", get(health::startup))
+        .route("/health/ready", get(health::readiness))
+        .route("/health/drain", post(health::drain))
+        // Prometheus scrape endpoint; 404s unless METRICS_TOKEN is set
+        .route("/metrics", get(crate::metrics::metrics_handler));
 
     // Public API routes (no auth required) - under /api/v1
+    #[cfg_attr(not(feature = "billing"), allow(unused_mut))]
     let mut public_api_routes = Router::new()
         .route("/auth/register", post(auth::register))
         .route("/auth/login", post(auth::login))
         .route("/auth/login/2fa", post(auth::login_2fa))
+        // Passkey 2FA completion (paired with the temp token from login)
+        .route(
+            "/auth/login/2fa/webauthn",
+            post(auth::login_2fa_webauthn_begin),
+        )
+        .route(
+            "/auth/login/2fa/webauthn/complete",
+            post(auth::login_2fa_webauthn_complete),
+        )
         .route("/auth/refresh", post(auth::refresh))
         .route("/auth/forgot-password", post(auth::forgot_password))
         .route("/auth/reset-password", post(auth::reset_password))
@@ -88,6 +122,8 @@ pub fn create_router(state: AppState) -> Router {
             post(audit::oauth_session_created),
         )
         .route("/audit/session-event", post(audit::session_event))
+        // Org audit trail (listing + CSV/JSONL compliance export)
+        .route("/audit/logs", get(audit::list_org_audit_logs))
         // PIN reset (public - user forgot their PIN)
         .route("/pin/forgot", post(pin::forgot_pin))
         .route("/pin/reset", post(pin::reset_pin))
@@ -108,6 +144,24 @@ pub fn create_router(state: AppState) -> Router {
             "/public/enterprise-inquiry",
             post(public::submit_enterprise_inquiry),
         )
+        // Egress IPs for customer firewall allowlisting
+        .route("/public/egress-ips", get(public::get_egress_ips))
+        // Email unsubscribe (public - signed token, RFC 8058 one-click POST)
+        .route(
+            "/public/unsubscribe",
+            get(public::unsubscribe_page).post(public::unsubscribe_one_click),
+        )
+        // Embeddable SVG badges (public, org opt-in via settings)
+        .route(
+            "/public/badges/:org_slug/:mcp_id/uptime.svg",
+            get(badges::uptime_badge),
+        )
+        .route(
+            "/public/badges/:org_slug/:mcp_id/latency.svg",
+            get(badges::latency_badge),
+        )
+        // Host-to-org resolution with branding (frontend theming per domain)
+        .route("/public/resolve-host", get(branding::resolve_host))
         // Website analytics collection (public with optional auth for admin exclusion)
         .route(
             "/analytics/collect",
@@ -115,6 +169,25 @@ pub fn create_router(state: AppState) -> Router {
                 auth_state.clone(),
                 optional_auth,
             )),
+        )
+        // GitHub config-as-code push webhook (public, uses signature verification)
+        .route(
+            "/webhooks/github/sync/:org_id",
+            post(github_sync::github_webhook),
+        )
+        // Inbound support email webhook (public, uses signature verification)
+        .route("/support/email/inbound", post(support_email::inbound_email))
+        // CSAT survey submission (public, uses signed token from close emails)
+        .route(
+            "/support/tickets/:ticket_id/csat",
+            post(support::submit_csat),
+        )
+        // Platform status page (public) with email subscriptions
+        .route("/public/status", get(status::get_status))
+        .route("/public/status/subscribe", post(status::subscribe_status))
+        .route(
+            "/public/status/unsubscribe",
+            get(status::unsubscribe_status),
         );
 
     // Stripe webhook (public, uses signature verification) - only when billing feature is enabled AND runtime config allows
@@ -124,6 +197,7 @@ pub fn create_router(state: AppState) -> Router {
     }
 
     // Protected API routes (auth required) - under /api/v1
+    #[cfg_attr(not(feature = "billing"), allow(unused_mut))]
     let mut protected_api_routes = Router::new()
         // Auth routes
         .route("/auth/me", get(auth::me))
@@ -142,11 +216,84 @@ pub fn create_router(state: AppState) -> Router {
         .route("/org", patch(organizations::update_org))
         .route("/org", delete(organizations::delete_org))
         .route("/org/stats", get(organizations::get_org_stats))
+        // White-label sender domain (DKIM/SPF) configuration
+        .route("/org/email-domain", get(email_domains::get_email_domain))
+        .route("/org/email-domain", put(email_domains::set_email_domain))
+        .route(
+            "/org/email-domain",
+            delete(email_domains::delete_email_domain),
+        )
+        .route(
+            "/org/email-domain/verify",
+            post(email_domains::verify_email_domain),
+        )
+        // White-label brand configuration (white-label tiers)
+        .route("/org/branding", get(branding::get_branding))
+        .route("/org/branding", patch(branding::update_branding))
+        .route("/org/branding/logo", post(branding::upload_logo))
+        .route("/org/branding/logo", delete(branding::delete_logo))
+        // SCIM provisioning token management (enterprise orgs)
+        .route("/org/scim/tokens", get(scim::list_scim_tokens))
+        .route("/org/scim/tokens", post(scim::create_scim_token))
+        .route(
+            "/org/scim/tokens/:token_id",
+            delete(scim::revoke_scim_token),
+        )
+        // Config-as-code GitHub sync for MCP definitions
+        // Declarative config reconciliation (Terraform-style, with dry_run)
+        .route("/org/config", put(org_config::put_org_config))
+        .route("/org/github-sync", get(github_sync::get_sync_config))
+        .route("/org/github-sync", put(github_sync::upsert_sync_config))
+        .route("/org/github-sync", delete(github_sync::delete_sync_config))
+        .route("/org/github-sync/runs", get(github_sync::list_sync_runs))
+        .route(
+            "/org/github-sync/runs/:run_id/approve",
+            post(github_sync::approve_sync_run),
+        )
+        .route(
+            "/org/github-sync/runs/:run_id/reject",
+            post(github_sync::reject_sync_run),
+        )
+        // API key rotation policy and compliance report
+        .route(
+            "/org/security/key-policy",
+            get(org_security::get_key_policy),
+        )
+        .route(
+            "/org/security/key-policy",
+            put(org_security::update_key_policy),
+        )
+        // JSON-RPC method allowlist enforced at the proxy entry point
+        .route(
+            "/org/security/method-allowlist",
+            get(org_security::get_method_allowlist),
+        )
+        .route(
+            "/org/security/method-allowlist",
+            put(org_security::update_method_allowlist),
+        )
+        // Opt-in payload capture with redaction and retention
+        .route(
+            "/org/security/payload-capture",
+            get(org_security::get_payload_capture),
+        )
+        .route(
+            "/org/security/payload-capture",
+            put(org_security::update_payload_capture),
+        )
+        .route(
+            "/org/security/key-report",
+            get(org_security::get_key_report),
+        )
         .route("/org/subscription", get(organizations::get_subscription))
         .route(
             "/org/subdomain/check",
             post(organizations::check_subdomain_availability),
         )
+        .route(
+            "/org/subdomain/history",
+            get(organizations::get_subdomain_history),
+        )
         // User routes
         .route("/users", get(users::list_users))
         .route("/users", post(users::invite_user))
@@ -156,27 +303,146 @@ pub fn create_router(state: AppState) -> Router {
         // API Key routes (legacy flat routes)
         .route("/api-keys", get(api_keys::list_api_keys))
         .route("/api-keys", post(api_keys::create_api_key))
+        .route("/api-keys/bulk", post(api_keys::bulk_create_api_keys))
+        .route(
+            "/api-keys/bulk/revoke",
+            post(api_keys::bulk_revoke_api_keys),
+        )
         .route("/api-keys/:key_id", get(api_keys::get_api_key))
         .route("/api-keys/:key_id", patch(api_keys::update_api_key))
         .route("/api-keys/:key_id", delete(api_keys::delete_api_key))
         .route("/api-keys/:key_id/rotate", post(api_keys::rotate_api_key))
+        .route(
+            "/api-keys/:key_id/rotation/cutoff",
+            post(api_keys::cutoff_api_key_rotation),
+        )
         .route("/api-keys/:key_id/usage", get(api_keys::get_api_key_usage))
+        .route(
+            "/api-keys/:key_id/cost-center",
+            put(api_keys::set_api_key_cost_center),
+        )
+        // Usage-based recommendations (caching, error rates, quota pressure)
+        .route(
+            "/recommendations",
+            get(recommendations::get_recommendations),
+        )
+        .route(
+            "/recommendations/:key/dismiss",
+            post(recommendations::dismiss_recommendation),
+        )
+        .route(
+            "/recommendations/:key/dismiss",
+            delete(recommendations::undismiss_recommendation),
+        )
+        // Guided onboarding (quickstart MCP + scoped key + config snippets)
+        .route("/onboarding/catalog", get(onboarding::list_catalog))
+        .route("/onboarding/quickstart", post(onboarding::quickstart))
+        .route("/onboarding/progress", get(onboarding::get_progress))
         // MCP routes (legacy flat routes)
         .route("/mcps", get(mcps::list_mcps))
         .route("/mcps", post(mcps::create_mcp))
         .route("/mcps/test-all", post(mcps::test_all_mcps)) // Must be before :mcp_id routes
+        .route("/mcps/export", get(mcp_transfer::export_mcps))
+        .route("/mcps/import", post(mcp_transfer::import_mcps))
+        .route("/mcps/client-config", get(mcps::get_client_config))
+        .route("/mcps/tools", get(mcps::list_org_tools))
+        // SSH keys for tunneled MCPs (must be before :mcp_id routes)
+        .route("/mcps/ssh-keys", get(ssh_keys::list_ssh_keys))
+        .route("/mcps/ssh-keys", post(ssh_keys::create_ssh_key))
+        .route("/mcps/ssh-keys/:key_id", delete(ssh_keys::delete_ssh_key))
         .route("/mcps/:mcp_id", get(mcps::get_mcp))
         .route("/mcps/:mcp_id", patch(mcps::update_mcp))
         .route("/mcps/:mcp_id", delete(mcps::delete_mcp))
         .route("/mcps/:mcp_id/status", patch(mcps::update_mcp_status))
+        .route("/mcps/:mcp_id/circuit", get(mcps::get_mcp_circuit))
+        .route("/mcps/:mcp_id/circuit", patch(mcps::update_mcp_circuit))
+        .route(
+            "/mcps/:mcp_id/cache/invalidate",
+            post(mcps::invalidate_mcp_cache),
+        )
+        .route("/mcps/:mcp_id/cost-center", put(mcps::set_mcp_cost_center))
         .route(
             "/mcps/:mcp_id/health-check",
             post(mcps::trigger_health_check),
         )
+        .route(
+            "/mcps/:mcp_id/health-webhook",
+            get(mcps::get_mcp_health_webhook),
+        )
+        .route(
+            "/mcps/:mcp_id/health-webhook",
+            put(mcps::upsert_mcp_health_webhook),
+        )
+        .route(
+            "/mcps/:mcp_id/health-webhook",
+            delete(mcps::delete_mcp_health_webhook),
+        )
+        .route(
+            "/mcps/:mcp_id/health-webhook/test",
+            post(mcps::test_mcp_health_webhook),
+        )
+        .route("/webhooks/verify-sample", post(mcps::verify_webhook_sample))
+        // Duplicate request diagnostics (top offenders from the proxy's dedup tracker)
+        .route(
+            "/mcps/diagnostics/duplicate-requests",
+            get(mcps::list_duplicate_offenders),
+        )
+        // GraphQL read surface for the dashboard
+        .route("/graphql", post(graphql::graphql_handler))
+        // Outbound org event webhooks
+        .route(
+            "/webhooks",
+            get(webhooks::list_webhooks).post(webhooks::create_webhook),
+        )
+        .route(
+            "/webhooks/:webhook_id",
+            patch(webhooks::update_webhook).delete(webhooks::delete_webhook),
+        )
+        .route(
+            "/webhooks/:webhook_id/deliveries",
+            get(webhooks::list_webhook_deliveries),
+        )
+        .route("/mcps/:mcp_id/replay", post(mcps::replay_mcp_request))
+        .route("/mcp/logs/:log_id/payload", get(mcps::get_mcp_log_payload))
+        // Managed stdio process lifecycle
+        .route("/mcps/:mcp_id/process", get(mcps::get_mcp_process))
+        .route("/mcps/:mcp_id/process/start", post(mcps::start_mcp_process))
+        .route("/mcps/:mcp_id/process/stop", post(mcps::stop_mcp_process))
+        .route(
+            "/mcps/:mcp_id/process/restart",
+            post(mcps::restart_mcp_process),
+        )
+        .route(
+            "/mcps/:mcp_id/process/logs",
+            get(mcps::get_mcp_process_logs),
+        )
         .route("/mcps/:mcp_id/test-history", get(mcps::get_test_history))
         .route("/mcps/:mcp_id/validate", post(mcps::validate_config))
         .route("/mcps/:mcp_id/config", get(mcps::get_mcp_config))
         .route("/mcps/:mcp_id/config", put(mcps::update_mcp_config))
+        // OAuth connections for upstream MCPs (token vault)
+        .route("/mcps/:mcp_id/oauth", get(mcp_oauth::get_oauth_status))
+        .route(
+            "/mcps/:mcp_id/oauth",
+            delete(mcp_oauth::disconnect_mcp_oauth),
+        )
+        .route(
+            "/mcps/:mcp_id/oauth/connect",
+            post(mcp_oauth::connect_mcp_oauth),
+        )
+        .route(
+            "/mcps/:mcp_id/oauth/callback",
+            get(mcp_oauth::oauth_callback),
+        )
+        // Content moderation rules (org admin only, checked in handlers)
+        .route("/moderation/rules", get(moderation::list_rules))
+        .route("/moderation/rules", post(moderation::create_rule))
+        .route("/moderation/rules/:rule_id", patch(moderation::update_rule))
+        .route(
+            "/moderation/rules/:rule_id",
+            delete(moderation::delete_rule),
+        )
+        .route("/moderation/decisions", get(moderation::list_decisions))
         // PIN-protected key management routes
         .route("/pin/status", get(pin::get_pin_status))
         .route("/pin", post(pin::set_pin))
@@ -204,6 +470,31 @@ pub fn create_router(state: AppState) -> Router {
             "/2fa/devices/:device_id",
             delete(two_factor::revoke_trusted_device),
         )
+        // WebAuthn / passkey routes
+        .route(
+            "/2fa/webauthn/register",
+            post(two_factor::begin_webauthn_registration),
+        )
+        .route(
+            "/2fa/webauthn/register/complete",
+            post(two_factor::complete_webauthn_registration),
+        )
+        .route(
+            "/2fa/webauthn/authenticate",
+            post(two_factor::begin_webauthn_authentication),
+        )
+        .route(
+            "/2fa/webauthn/authenticate/complete",
+            post(two_factor::complete_webauthn_authentication),
+        )
+        .route(
+            "/2fa/webauthn/credentials",
+            get(two_factor::list_webauthn_credentials),
+        )
+        .route(
+            "/2fa/webauthn/credentials/:credential_id",
+            delete(two_factor::delete_webauthn_credential),
+        )
         // Custom domain routes
         .route("/domains", get(domains::list_domains))
         .route("/domains", post(domains::create_domain))
@@ -243,9 +534,37 @@ pub fn create_router(state: AppState) -> Router {
             "/support/tickets/:ticket_id/close",
             post(support::close_ticket),
         )
+        // Async org support history export (compliance)
+        .route("/support/export", post(support::create_support_export))
+        .route(
+            "/support/export/:export_id",
+            get(support::get_support_export),
+        )
+        // Programmatic support API (API keys with support:write scope)
+        .route("/support/api/tickets", post(support::api_create_ticket))
+        .route(
+            "/support/api/tickets/:ticket_id/messages",
+            post(support::api_reply_to_ticket),
+        )
+        .route(
+            "/support/tickets/:ticket_id/attachments",
+            get(attachments::list_attachments),
+        )
+        .route(
+            "/support/tickets/:ticket_id/attachments",
+            post(attachments::upload_attachment),
+        )
+        .route(
+            "/support/tickets/:ticket_id/attachments/:attachment_id/download",
+            get(attachments::download_attachment),
+        )
         // Team invitation routes
         .route("/invitations", get(invitations::list_invitations))
         .route("/invitations", post(invitations::create_invitation))
+        .route(
+            "/invitations/links",
+            post(invitations::create_invitation_link),
+        )
         .route(
             "/invitations/:invitation_id/resend",
             post(invitations::resend_invitation),
@@ -283,14 +602,143 @@ pub fn create_router(state: AppState) -> Router {
             "/admin/users/:user_id/api-keys/:key_id",
             delete(admin::revoke_user_api_key),
         )
+        // Admin API token routes (superadmin only, checked in handlers)
+        .route("/admin/alerts", get(admin::alerts::list_alerts))
+        .route(
+            "/admin/alerts/config",
+            get(admin::alerts::list_alert_configs),
+        )
+        .route(
+            "/admin/alerts/config/:alert_type",
+            patch(admin::alerts::update_alert_config),
+        )
+        .route(
+            "/admin/alerts/:alert_id/acknowledge",
+            post(admin::alerts::acknowledge_alert),
+        )
+        .route(
+            "/admin/alerts/:alert_id/resolve",
+            post(admin::alerts::resolve_alert),
+        )
+        .route("/admin/approvals", get(admin::approvals::list_approvals))
+        .route(
+            "/admin/approvals/:approval_id/approve",
+            post(admin::approvals::approve_approval),
+        )
+        .route(
+            "/admin/approvals/:approval_id/reject",
+            post(admin::approvals::reject_approval),
+        )
+        .route("/admin/tokens", get(admin::tokens::list_admin_tokens))
+        .route("/admin/tokens", post(admin::tokens::create_admin_token))
+        .route(
+            "/admin/tokens/:token_id",
+            delete(admin::tokens::revoke_admin_token),
+        )
+        .route(
+            "/admin/tokens/:token_id/usage",
+            get(admin::tokens::get_admin_token_usage),
+        )
         // Admin overages toggle routes
         .route("/admin/orgs/:org_id/overages", get(admin::get_org_overages))
         .route(
             "/admin/orgs/:org_id/overages",
             put(admin::toggle_org_overages),
         )
+        .route(
+            "/admin/orgs/:org_id/rate-limits",
+            get(admin::rate_limits::get_org_rate_limits),
+        )
+        .route(
+            "/admin/orgs/:org_id/rate-limits",
+            put(admin::rate_limits::put_org_rate_limits),
+        )
+        // Temporary rate limit exemptions (multiplier or bypass, with expiry)
+        .route(
+            "/admin/orgs/:org_id/rate-limit-exemptions",
+            get(admin::rate_limits::list_rate_limit_exemptions)
+                .post(admin::rate_limits::create_rate_limit_exemption),
+        )
+        .route(
+            "/admin/rate-limit-exemptions/:exemption_id",
+            delete(admin::rate_limits::revoke_rate_limit_exemption),
+        )
+        // Reserved subdomain management (baseline list + runtime additions)
+        .route(
+            "/admin/reserved-subdomains",
+            get(admin::reserved_subdomains::list_reserved_subdomains)
+                .post(admin::reserved_subdomains::create_reserved_subdomain),
+        )
+        .route(
+            "/admin/reserved-subdomains/:subdomain",
+            delete(admin::reserved_subdomains::delete_reserved_subdomain),
+        )
+        // Support ticket routing rules
+        .route(
+            "/admin/support/rules",
+            get(admin::support_rules::list_rules).post(admin::support_rules::create_rule),
+        )
+        .route(
+            "/admin/support/rules/:rule_id",
+            put(admin::support_rules::update_rule).delete(admin::support_rules::delete_rule),
+        )
+        // Platform incidents (feed the public status page)
+        .route(
+            "/admin/incidents",
+            get(admin::incidents::list_incidents).post(admin::incidents::create_incident),
+        )
+        .route(
+            "/admin/incidents/:incident_id/updates",
+            post(admin::incidents::post_incident_update),
+        )
+        // API deprecation registry
+        .route(
+            "/admin/deprecations",
+            get(admin::deprecations::list_deprecations)
+                .post(admin::deprecations::create_deprecation),
+        )
+        .route(
+            "/admin/deprecations/:deprecation_id",
+            patch(admin::deprecations::update_deprecation)
+                .delete(admin::deprecations::delete_deprecation),
+        )
+        .route(
+            "/admin/deprecations/:deprecation_id/usage",
+            get(admin::deprecations::deprecation_usage_report),
+        )
+        // Platform read-only (maintenance) mode toggle
+        .route("/admin/system/read-only", get(admin::system::get_read_only))
+        .route(
+            "/admin/system/auth-cache",
+            get(admin::system::get_auth_cache_metrics),
+        )
+        .route("/admin/system/read-only", put(admin::system::put_read_only))
+        // Unified background scheduler: job status and manual triggers
+        .route("/admin/scheduler", get(admin::scheduler::get_scheduler))
+        .route(
+            "/admin/scheduler/:job_name/run",
+            post(admin::scheduler::trigger_scheduler_job),
+        )
         // Admin MCP proxy logs route
         .route("/admin/mcp/logs", get(admin::get_mcp_logs))
+        // Admin org benchmarking route
+        .route(
+            "/admin/analytics/orgs/benchmark",
+            get(admin::analytics::benchmark_orgs),
+        )
+        // Admin product metrics routes (activation, WAU, churn)
+        .route(
+            "/admin/analytics/product",
+            get(admin::product_metrics::get_product_metrics),
+        )
+        .route(
+            "/admin/analytics/product/export",
+            get(admin::product_metrics::export_product_metrics),
+        )
+        .route(
+            "/admin/analytics/product/refresh",
+            post(admin::product_metrics::refresh_product_metrics),
+        )
         // Admin support ticket routes
         .route("/admin/support/tickets", get(support::admin_list_tickets))
         .route("/admin/support/stats", get(support::admin_get_ticket_stats))
@@ -341,6 +789,32 @@ pub fn create_router(state: AppState) -> Router {
             "/admin/support/sla/rules/:rule_id",
             patch(support::admin_update_sla_rule),
         )
+        // Spam quarantine review
+        .route(
+            "/admin/support/spam/quarantine",
+            get(spam::admin_list_quarantine),
+        )
+        .route(
+            "/admin/support/spam/quarantine/:entry_id/approve",
+            post(spam::admin_approve_quarantine),
+        )
+        .route(
+            "/admin/support/spam/quarantine/:entry_id/discard",
+            post(spam::admin_discard_quarantine),
+        )
+        // Lifecycle policy management (inactivity reminders / auto-close)
+        .route(
+            "/admin/support/lifecycle/policies",
+            get(support::admin_list_lifecycle_policies),
+        )
+        .route(
+            "/admin/support/lifecycle/policies",
+            post(support::admin_create_lifecycle_policy),
+        )
+        .route(
+            "/admin/support/lifecycle/policies/:policy_id",
+            patch(support::admin_update_lifecycle_policy),
+        )
         // Template management
         .route(
             "/admin/support/templates",
@@ -358,6 +832,32 @@ pub fn create_router(state: AppState) -> Router {
             "/admin/support/templates/:template_id",
             delete(support::admin_delete_template),
         )
+        // Ticket tag registry and per-ticket tagging
+        .route(
+            "/admin/support/tags",
+            get(support::admin_list_tags).post(support::admin_create_tag),
+        )
+        .route(
+            "/admin/support/tags/:tag_name",
+            delete(support::admin_delete_tag),
+        )
+        .route(
+            "/admin/support/tickets/:ticket_id/tags",
+            post(support::admin_add_ticket_tag),
+        )
+        .route(
+            "/admin/support/tickets/:ticket_id/tags/:tag",
+            delete(support::admin_remove_ticket_tag),
+        )
+        // Per-admin saved ticket filters (one-click dashboard views)
+        .route(
+            "/admin/support/filters",
+            get(support::admin_list_saved_filters).post(support::admin_save_filter),
+        )
+        .route(
+            "/admin/support/filters/:filter_id",
+            delete(support::admin_delete_saved_filter),
+        )
         // Admin website analytics routes
         .route(
             "/admin/analytics/website/realtime",
@@ -399,6 +899,14 @@ pub fn create_router(state: AppState) -> Router {
             "/admin/analytics/website/events/details",
             get(analytics_tracking::get_event_details),
         )
+        .route(
+            "/admin/analytics/website/heatmap/scroll",
+            get(analytics_tracking::get_scroll_distribution),
+        )
+        .route(
+            "/admin/analytics/website/heatmap/clicks",
+            get(analytics_tracking::get_click_density),
+        )
         .route(
             "/admin/analytics/website/goals",
             get(analytics_tracking::list_goals),
@@ -415,6 +923,10 @@ pub fn create_router(state: AppState) -> Router {
             "/admin/analytics/website/goals/:goal_id",
             delete(analytics_tracking::delete_goal),
         )
+        .route(
+            "/admin/analytics/website/goals/:goal_id/test",
+            post(analytics_tracking::test_goal),
+        )
         .route(
             "/admin/analytics/website/settings",
             get(analytics_tracking::get_settings),
@@ -496,6 +1008,28 @@ pub fn create_router(state: AppState) -> Router {
                 "/billing/subscription/downgrade",
                 delete(billing::cancel_scheduled_downgrade),
             )
+            // Billing contact routes (invoice recipients without dashboard access)
+            .route("/billing/contacts", get(billing::list_billing_contacts))
+            .route("/billing/contacts", post(billing::create_billing_contact))
+            .route(
+                "/billing/contacts/:contact_id",
+                patch(billing::update_billing_contact),
+            )
+            .route(
+                "/billing/contacts/:contact_id",
+                delete(billing::delete_billing_contact),
+            )
+            // Billing document routes (generated PDF receipts)
+            .route("/billing/documents", get(billing::list_billing_documents))
+            // Invoice settings routes (PO number, billing address, memo)
+            .route(
+                "/billing/invoice-settings",
+                get(billing::get_invoice_settings),
+            )
+            .route(
+                "/billing/invoice-settings",
+                patch(billing::update_invoice_settings),
+            )
             // Invoice routes (database-backed with line items)
             .route("/billing/invoices", get(billing::list_invoices))
             .route("/billing/invoices/sync", post(billing::sync_invoices))
@@ -530,7 +1064,18 @@ pub fn create_router(state: AppState) -> Router {
             .route("/usage/summary", get(usage::get_usage_summary))
             .route("/usage/by-api-key", get(usage::get_usage_by_api_key))
             .route("/usage/by-mcp", get(usage::get_usage_by_mcp))
+            .route("/usage/by-tag", get(usage::get_usage_by_tag))
+            .route(
+                "/usage/chargeback/export",
+                get(usage::export_chargeback_csv),
+            )
             .route("/usage/hourly", get(usage::get_hourly_usage))
+            .route("/usage/calendar", get(usage::get_usage_calendar))
+            // Customer usage alerts ("notify me at 80% of included calls")
+            .route("/usage/alerts", get(usage::list_usage_alerts))
+            .route("/usage/alerts", post(usage::create_usage_alert))
+            .route("/usage/alerts/:alert_id", patch(usage::update_usage_alert))
+            .route("/usage/alerts/:alert_id", delete(usage::delete_usage_alert))
             .route("/usage/check-limit", get(usage::check_usage_limit))
             .route("/usage/limits", get(usage::get_effective_limits))
             .route("/usage/errors", get(usage::get_recent_errors))
@@ -546,14 +1091,29 @@ pub fn create_router(state: AppState) -> Router {
             .route(
                 "/admin/billing/debug/:org_id",
                 get(admin::debug_org_billing),
+            )
+            // Admin revenue analytics routes (requires billing feature)
+            .route(
+                "/admin/analytics/revenue",
+                get(admin::revenue::get_revenue_analytics),
+            )
+            .route(
+                "/admin/analytics/revenue/refresh",
+                post(admin::revenue::refresh_revenue_analytics),
             );
     }
 
-    // Apply auth middleware to protected routes
-    let protected_api_routes = protected_api_routes.layer(middleware::from_fn_with_state(
-        auth_state.clone(),
-        require_auth,
-    ));
+    // Apply auth middleware to protected routes; the deprecation layer
+    // sits inside auth so it can attribute usage to the caller's org
+    let protected_api_routes = protected_api_routes
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            crate::deprecation::deprecation_headers,
+        ))
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            require_auth,
+        ));
 
     // WebSocket routes (auth handled in handler via query parameter)
     let websocket_routes = Router::new().route("/ws/support", get(ws_handler));
@@ -568,15 +1128,54 @@ pub fn create_router(state: AppState) -> Router {
     // This is the main proxy endpoint that forwards requests to upstream MCPs
     let mcp_routes = Router::new()
         .route("/mcp", post(mcp_proxy::handle_mcp_request))
+        .route("/mcp", get(mcp_proxy::handle_mcp_stream))
         .layer(DefaultBodyLimit::max(
             state.config.mcp_max_request_body_bytes,
         ));
 
+    // SCIM 2.0 provisioning routes (org-issued bearer token auth, not JWT)
+    // Identity providers call these directly to manage team members
+    let scim_routes = Router::new()
+        .route(
+            "/scim/v2/ServiceProviderConfig",
+            get(scim::service_provider_config),
+        )
+        .route(
+            "/scim/v2/Users",
+            get(scim::list_users).post(scim::create_user),
+        )
+        .route(
+            "/scim/v2/Users/:user_id",
+            get(scim::get_user)
+                .put(scim::replace_user)
+                .patch(scim::patch_user)
+                .delete(scim::delete_user),
+        )
+        .route("/scim/v2/Groups", get(scim::list_groups))
+        .route(
+            "/scim/v2/Groups/:group_id",
+            get(scim::get_group).patch(scim::patch_group),
+        );
+
     // Combine all routes
     Router::new()
         .merge(health_routes)
         .merge(mcp_routes) // MCP proxy at root level
+        .merge(scim_routes) // SCIM provisioning at root level
         .nest("/api/v1", api_v1_routes)
+        // Read-only maintenance mode: rejects mutating requests with 503
+        // while the toggle is on (see crate::read_only for route classes)
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            crate::read_only::read_only_guard,
+        ))
+        // Request count/latency collection for the /metrics endpoint;
+        // wraps the guard above so its 503 rejections are counted too
+        .layer(middleware::from_fn(crate::metrics::track_http_metrics))
+        // Structured access log (no-op without ACCESS_LOG_SINK)
+        .layer(middleware::from_fn(
+            crate::access_log::access_log_middleware,
+        ))
         // SOC 2 CC6.1: Global request body size limit to prevent DoS via large payloads
         // MCP routes have their own 1MB limit which takes precedence
         .layer(DefaultBodyLimit::max(10 * 1024 * 1024)) // 10MB global limit
@@ -0,0 +1,327 @@
+//! Content moderation rule management routes
+//!
+//! Org owners/admins define regex rules evaluated on the MCP proxy path
+//! (see [`crate::mcp::moderation`]). Rule mutations invalidate the engine's
+//! per-org cache so changes take effect within one request.
+
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{auth::AuthUser, error::ApiError, state::AppState};
+
+const VALID_ACTIONS: &[&str] = &["deny", "redact"];
+const VALID_DIRECTIONS: &[&str] = &["request", "response", "both"];
+
+/// Upper bound on pattern length to keep rule evaluation cheap
+const MAX_PATTERN_LEN: usize = 1024;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, sqlx::FromRow)]
+struct RuleRow {
+    pub id: Uuid,
+    pub name: String,
+    pub pattern: String,
+    pub action: String,
+    pub direction: String,
+    pub bypass_tools: Vec<String>,
+    pub enabled: bool,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModerationRule {
+    pub id: Uuid,
+    pub name: String,
+    pub pattern: String,
+    pub action: String,
+    pub direction: String,
+    pub bypass_tools: Vec<String>,
+    pub enabled: bool,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+impl From<RuleRow> for ModerationRule {
+    fn from(row: RuleRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            pattern: row.pattern,
+            action: row.action,
+            direction: row.direction,
+            bypass_tools: row.bypass_tools,
+            enabled: row.enabled,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateRuleRequest {
+    pub name: String,
+    pub pattern: String,
+    pub action: String,
+    /// "request", "response" or "both" (default)
+    #[serde(default)]
+    pub direction: Option<String>,
+    /// Prefixed tool names exempt from this rule
+    #[serde(default)]
+    pub bypass_tools: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateRuleRequest {
+    pub pattern: Option<String>,
+    pub action: Option<String>,
+    pub direction: Option<String>,
+    pub bypass_tools: Option<Vec<String>>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct DecisionRow {
+    pub id: Uuid,
+    pub rule_name: String,
+    pub tool_name: String,
+    pub direction: String,
+    pub action: String,
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModerationDecision {
+    pub id: Uuid,
+    pub rule_name: String,
+    pub tool_name: String,
+    pub direction: String,
+    pub action: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListDecisionsQuery {
+    /// Max rows to return (default 100, capped at 1000)
+    pub limit: Option<i64>,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Only org owners/admins may manage moderation rules
+fn require_org_admin(auth_user: &AuthUser) -> Result<Uuid, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+    Ok(org_id)
+}
+
+/// Validate a rule pattern compiles and stays within size limits
+fn validate_pattern(pattern: &str) -> Result<(), ApiError> {
+    if pattern.is_empty() || pattern.len() > MAX_PATTERN_LEN {
+        return Err(ApiError::Validation(format!(
+            "Pattern must be between 1 and {} characters",
+            MAX_PATTERN_LEN
+        )));
+    }
+    regex::Regex::new(pattern)
+        .map_err(|e| ApiError::Validation(format!("Invalid regex pattern: {}", e)))?;
+    Ok(())
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// List the organization's moderation rules
+pub async fn list_rules(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<Vec<ModerationRule>>, ApiError> {
+    let org_id = require_org_admin(&auth_user)?;
+
+    let rows: Vec<RuleRow> = sqlx::query_as(
+        "SELECT id, name, pattern, action, direction, bypass_tools, enabled, created_at, updated_at \
+         FROM moderation_rules WHERE org_id = $1 ORDER BY created_at",
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(rows.into_iter().map(ModerationRule::from).collect()))
+}
+
+/// Create a moderation rule
+pub async fn create_rule(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<CreateRuleRequest>,
+) -> Result<(StatusCode, Json<ModerationRule>), ApiError> {
+    let org_id = require_org_admin(&auth_user)?;
+
+    let name = req.name.trim();
+    if name.is_empty() || name.len() > 255 {
+        return Err(ApiError::Validation(
+            "Rule name must be between 1 and 255 characters".to_string(),
+        ));
+    }
+    validate_pattern(&req.pattern)?;
+    if !VALID_ACTIONS.contains(&req.action.as_str()) {
+        return Err(ApiError::Validation(
+            "Action must be 'deny' or 'redact'".to_string(),
+        ));
+    }
+    let direction = req.direction.as_deref().unwrap_or("both");
+    if !VALID_DIRECTIONS.contains(&direction) {
+        return Err(ApiError::Validation(
+            "Direction must be 'request', 'response' or 'both'".to_string(),
+        ));
+    }
+
+    let row: RuleRow = sqlx::query_as(
+        r#"
+        INSERT INTO moderation_rules (org_id, name, pattern, action, direction, bypass_tools, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id, name, pattern, action, direction, bypass_tools, enabled, created_at, updated_at
+        "#,
+    )
+    .bind(org_id)
+    .bind(name)
+    .bind(&req.pattern)
+    .bind(&req.action)
+    .bind(direction)
+    .bind(&req.bypass_tools)
+    .bind(auth_user.user_id)
+    .fetch_one(&state.pool)
+    .await?;
+
+    state.moderation.invalidate(org_id).await;
+
+    Ok((StatusCode::CREATED, Json(ModerationRule::from(row))))
+}
+
+/// Update a moderation rule (partial)
+pub async fn update_rule(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(rule_id): Path<Uuid>,
+    Json(req): Json<UpdateRuleRequest>,
+) -> Result<Json<ModerationRule>, ApiError> {
+    let org_id = require_org_admin(&auth_user)?;
+
+    if let Some(pattern) = &req.pattern {
+        validate_pattern(pattern)?;
+    }
+    if let Some(action) = &req.action {
+        if !VALID_ACTIONS.contains(&action.as_str()) {
+            return Err(ApiError::Validation(
+                "Action must be 'deny' or 'redact'".to_string(),
+            ));
+        }
+    }
+    if let Some(direction) = &req.direction {
+        if !VALID_DIRECTIONS.contains(&direction.as_str()) {
+            return Err(ApiError::Validation(
+                "Direction must be 'request', 'response' or 'both'".to_string(),
+            ));
+        }
+    }
+
+    let row: Option<RuleRow> = sqlx::query_as(
+        r#"
+        UPDATE moderation_rules SET
+            pattern = COALESCE($3, pattern),
+            action = COALESCE($4, action),
+            direction = COALESCE($5, direction),
+            bypass_tools = COALESCE($6, bypass_tools),
+            enabled = COALESCE($7, enabled),
+            updated_at = NOW()
+        WHERE id = $1 AND org_id = $2
+        RETURNING id, name, pattern, action, direction, bypass_tools, enabled, created_at, updated_at
+        "#,
+    )
+    .bind(rule_id)
+    .bind(org_id)
+    .bind(&req.pattern)
+    .bind(&req.action)
+    .bind(&req.direction)
+    .bind(&req.bypass_tools)
+    .bind(req.enabled)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    let row = row.ok_or(ApiError::NotFound)?;
+    state.moderation.invalidate(org_id).await;
+
+    Ok(Json(ModerationRule::from(row)))
+}
+
+/// Delete a moderation rule
+pub async fn delete_rule(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(rule_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let org_id = require_org_admin(&auth_user)?;
+
+    let result = sqlx::query("DELETE FROM moderation_rules WHERE id = $1 AND org_id = $2")
+        .bind(rule_id)
+        .bind(org_id)
+        .execute(&state.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    state.moderation.invalidate(org_id).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List recent moderation decisions (most recent first)
+pub async fn list_decisions(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<ListDecisionsQuery>,
+) -> Result<Json<Vec<ModerationDecision>>, ApiError> {
+    let org_id = require_org_admin(&auth_user)?;
+    let limit = query.limit.unwrap_or(100).clamp(1, 1000);
+
+    let rows: Vec<DecisionRow> = sqlx::query_as(
+        "SELECT id, rule_name, tool_name, direction, action, created_at \
+         FROM moderation_decisions WHERE org_id = $1 ORDER BY created_at DESC LIMIT $2",
+    )
+    .bind(org_id)
+    .bind(limit)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(
+        rows.into_iter()
+            .map(|row| ModerationDecision {
+                id: row.id,
+                rule_name: row.rule_name,
+                tool_name: row.tool_name,
+                direction: row.direction,
+                action: row.action,
+                created_at: row.created_at,
+            })
+            .collect(),
+    ))
+}
@@ -249,6 +249,40 @@ pub async fn update_notification_preferences(
     .execute(&state.pool)
     .await?;
 
+    // Re-enabling a category also clears any address-level suppression
+    // left behind by an unsubscribe link, which would otherwise keep
+    // blocking sends regardless of the preference
+    let reenabled: Vec<crate::email_suppression::EmailCategory> = [
+        (
+            req.marketing_emails,
+            crate::email_suppression::EmailCategory::Marketing,
+        ),
+        (
+            req.weekly_digest,
+            crate::email_suppression::EmailCategory::Digest,
+        ),
+        (
+            req.usage_alerts,
+            crate::email_suppression::EmailCategory::Usage,
+        ),
+    ]
+    .into_iter()
+    .filter(|(requested, _)| *requested == Some(true))
+    .map(|(_, category)| category)
+    .collect();
+
+    if !reenabled.is_empty() {
+        let email: Option<(String,)> = sqlx::query_as("SELECT email FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(&state.pool)
+            .await?;
+        if let Some((email,)) = email {
+            for category in reenabled {
+                crate::email_suppression::unsuppress(&state.pool, &email, category).await?;
+            }
+        }
+    }
+
     tracing::info!(
         user_id = %user_id,
         "Notification preferences updated"
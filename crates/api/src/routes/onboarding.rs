@@ -0,0 +1,404 @@
+//! Guided onboarding routes
+//!
+//! `POST /onboarding/quickstart` gets a new org from zero to a working MCP
+//! call in one request: it creates a starter MCP from the built-in catalog,
+//! issues an API key scoped to just that MCP, and returns ready-to-paste
+//! client config snippets. Completed steps are recorded in
+//! `onboarding_progress` for activation analytics.
+
+use axum::{
+    extract::{Extension, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{auth::AuthUser, error::ApiError, state::AppState};
+
+// ============================================================================
+// Starter catalog
+// ============================================================================
+
+/// A starter MCP template (credential-free reference servers only)
+#[derive(Debug, Serialize)]
+pub struct CatalogTemplate {
+    pub slug: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+const QUICKSTART_CATALOG: &[CatalogTemplate] = &[
+    CatalogTemplate {
+        slug: "memory",
+        name: "Memory",
+        description: "Knowledge-graph memory server - persists facts across conversations",
+    },
+    CatalogTemplate {
+        slug: "fetch",
+        name: "Fetch",
+        description: "Fetches web pages and converts them for LLM consumption",
+    },
+    CatalogTemplate {
+        slug: "everything",
+        name: "Everything",
+        description: "Reference server exercising every MCP feature - useful for testing",
+    },
+];
+
+/// Stdio launch config for a catalog template
+fn template_config(slug: &str) -> Option<serde_json::Value> {
+    let (command, args): (&str, &[&str]) = match slug {
+        "memory" => ("npx", &["-y", "@modelcontextprotocol/server-memory"]),
+        "fetch" => ("uvx", &["mcp-server-fetch"]),
+        "everything" => ("npx", &["-y", "@modelcontextprotocol/server-everything"]),
+        _ => return None,
+    };
+    Some(serde_json::json!({ "command": command, "args": args }))
+}
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct QuickstartRequest {
+    /// Catalog template slug (defaults to "memory")
+    #[serde(default)]
+    pub template: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QuickstartResponse {
+    pub mcp_id: Uuid,
+    pub mcp_name: String,
+    pub template: String,
+    pub api_key_id: Uuid,
+    /// Full API key - only shown once, like key creation
+    pub api_key: String,
+    pub key_prefix: String,
+    /// The org's MCP proxy endpoint
+    pub endpoint: String,
+    pub snippets: ConfigSnippets,
+}
+
+/// Ready-to-paste client configuration blocks
+#[derive(Debug, Serialize)]
+pub struct ConfigSnippets {
+    /// Claude Desktop `claude_desktop_config.json` (stdio via mcp-remote)
+    pub claude_desktop: serde_json::Value,
+    /// Cursor `.cursor/mcp.json`
+    pub cursor: serde_json::Value,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct ProgressRow {
+    step: String,
+    completed_at: OffsetDateTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OnboardingStep {
+    pub step: String,
+    pub completed_at: String,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// List the starter MCP catalog
+pub async fn list_catalog() -> Json<&'static [CatalogTemplate]> {
+    Json(QUICKSTART_CATALOG)
+}
+
+/// One-request quickstart: starter MCP + scoped API key + config snippets
+///
+/// Idempotent per template: re-running reuses the existing quickstart MCP
+/// but always issues a fresh key (the previous secret is unrecoverable).
+pub async fn quickstart(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<QuickstartRequest>,
+) -> Result<(StatusCode, Json<QuickstartResponse>), ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    // Viewers cannot create MCPs or API keys
+    if auth_user.role.as_str() == "viewer" {
+        return Err(ApiError::Forbidden);
+    }
+
+    let slug = req.template.as_deref().unwrap_or("memory");
+    let template = QUICKSTART_CATALOG
+        .iter()
+        .find(|t| t.slug == slug)
+        .ok_or_else(|| {
+            ApiError::Validation(format!(
+                "Unknown template '{}'. Available: {}",
+                slug,
+                QUICKSTART_CATALOG
+                    .iter()
+                    .map(|t| t.slug)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })?;
+    let config = template_config(slug).ok_or(ApiError::Internal)?;
+
+    let mcp_name = format!("quickstart-{}", slug);
+
+    // Reuse an existing quickstart MCP so re-runs don't eat the MCP quota
+    let existing: Option<(Uuid,)> =
+        sqlx::query_as("SELECT id FROM mcp_instances WHERE org_id = $1 AND name = $2")
+            .bind(org_id)
+            .bind(&mcp_name)
+            .fetch_optional(&state.pool)
+            .await?;
+
+    let mcp_id = match existing {
+        Some((id,)) => id,
+        None => {
+            let id = Uuid::new_v4();
+            let now = OffsetDateTime::now_utc();
+            sqlx::query(
+                r#"
+                INSERT INTO mcp_instances (id, org_id, name, mcp_type, description, config, status, health_status, created_at, updated_at)
+                VALUES ($1, $2, $3, 'stdio', $4, $5, 'active', 'unknown', $6, $6)
+                "#,
+            )
+            .bind(id)
+            .bind(org_id)
+            .bind(&mcp_name)
+            .bind(template.description)
+            .bind(&config)
+            .bind(now)
+            .execute(&state.pool)
+            .await?;
+            id
+        }
+    };
+    record_step(&state.pool, org_id, auth_user.user_id, "mcp_created").await;
+
+    // Issue a key scoped to just the quickstart MCP
+    let (full_key, key_hash, key_prefix) = state
+        .api_key_manager
+        .generate_key()
+        .map_err(|_| ApiError::Internal)?;
+
+    let key_id = Uuid::new_v4();
+    sqlx::query(
+        r#"
+        INSERT INTO api_keys (id, org_id, name, key_hash, key_prefix, scopes, rate_limit_rpm, created_by, mcp_access_mode, allowed_mcp_ids)
+        VALUES ($1, $2, $3, $4, $5, '[]'::jsonb, 60, $6, 'selected', $7)
+        "#,
+    )
+    .bind(key_id)
+    .bind(org_id)
+    .bind(format!("quickstart-{}", slug))
+    .bind(&key_hash)
+    .bind(&key_prefix)
+    .bind(auth_user.user_id)
+    .bind(vec![mcp_id])
+    .execute(&state.pool)
+    .await?;
+    record_step(&state.pool, org_id, auth_user.user_id, "api_key_created").await;
+    record_step(
+        &state.pool,
+        org_id,
+        auth_user.user_id,
+        "quickstart_completed",
+    )
+    .await;
+
+    let endpoint = proxy_endpoint(&state, org_id).await?;
+    let snippets = build_snippets(&endpoint, &full_key);
+
+    Ok((
+        StatusCode::CREATED,
+        Json(QuickstartResponse {
+            mcp_id,
+            mcp_name,
+            template: slug.to_string(),
+            api_key_id: key_id,
+            api_key: full_key,
+            key_prefix,
+            endpoint,
+            snippets,
+        }),
+    ))
+}
+
+/// List completed onboarding steps for the org
+pub async fn get_progress(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<Vec<OnboardingStep>>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let rows: Vec<ProgressRow> = sqlx::query_as(
+        "SELECT step, completed_at FROM onboarding_progress WHERE org_id = $1 ORDER BY completed_at",
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(
+        rows.into_iter()
+            .map(|row| OnboardingStep {
+                step: row.step,
+                completed_at: row
+                    .completed_at
+                    .format(&time::format_description::well_known::Rfc3339)
+                    .unwrap_or_else(|_| row.completed_at.to_string()),
+            })
+            .collect(),
+    ))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Record a completed onboarding step (best effort, idempotent)
+async fn record_step(pool: &sqlx::PgPool, org_id: Uuid, user_id: Option<Uuid>, step: &str) {
+    let result = sqlx::query(
+        "INSERT INTO onboarding_progress (org_id, step, completed_by) VALUES ($1, $2, $3) \
+         ON CONFLICT (org_id, step) DO NOTHING",
+    )
+    .bind(org_id)
+    .bind(step)
+    .bind(user_id)
+    .execute(pool)
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!(org_id = %org_id, step = %step, "Failed to record onboarding step: {}", e);
+    }
+}
+
+/// Build the org's MCP proxy endpoint URL
+///
+/// Prefers the org's best public host (custom domain, then subdomains, via
+/// [`crate::routing::HostResolver::preferred_host`]); falls back to the
+/// deployment's public URL (legacy API-key-only routing).
+pub(crate) async fn proxy_endpoint(state: &AppState, org_id: Uuid) -> Result<String, ApiError> {
+    let host = state
+        .host_resolver
+        .preferred_host(org_id)
+        .await
+        .map_err(|e| {
+            tracing::error!(org_id = %org_id, "Failed to resolve preferred host: {}", e);
+            ApiError::Internal
+        })?;
+
+    Ok(match host {
+        Some(host) if state.config.base_domain != "localhost" => {
+            format!("https://{}/mcp", host)
+        }
+        _ => format!("{}/mcp", state.config.public_url.trim_end_matches('/')),
+    })
+}
+
+/// Render the per-client config snippets
+fn build_snippets(endpoint: &str, api_key: &str) -> ConfigSnippets {
+    ConfigSnippets {
+        claude_desktop: claude_desktop_config(endpoint, api_key),
+        cursor: cursor_config(endpoint, api_key),
+    }
+}
+
+/// Claude Desktop `claude_desktop_config.json` block
+///
+/// Claude Desktop only speaks stdio, so the snippet bridges to the HTTP
+/// proxy via mcp-remote.
+pub(crate) fn claude_desktop_config(endpoint: &str, api_key: &str) -> serde_json::Value {
+    serde_json::json!({
+        "mcpServers": {
+            "plexmcp": {
+                "command": "npx",
+                "args": [
+                    "-y",
+                    "mcp-remote",
+                    endpoint,
+                    "--header",
+                    format!("X-API-Key: {}", api_key),
+                ]
+            }
+        }
+    })
+}
+
+/// Cursor `.cursor/mcp.json` block
+pub(crate) fn cursor_config(endpoint: &str, api_key: &str) -> serde_json::Value {
+    serde_json::json!({
+        "mcpServers": {
+            "plexmcp": {
+                "url": endpoint,
+                "headers": { "X-API-Key": api_key }
+            }
+        }
+    })
+}
+
+/// VS Code `.vscode/mcp.json` block
+pub(crate) fn vscode_config(endpoint: &str, api_key: &str) -> serde_json::Value {
+    serde_json::json!({
+        "servers": {
+            "plexmcp": {
+                "type": "http",
+                "url": endpoint,
+                "headers": { "X-API-Key": api_key }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_catalog_template_has_a_config() {
+        for template in QUICKSTART_CATALOG {
+            assert!(
+                template_config(template.slug).is_some(),
+                "missing config for template '{}'",
+                template.slug
+            );
+        }
+        assert!(template_config("does-not-exist").is_none());
+    }
+
+    #[test]
+    fn test_snippets_embed_endpoint_and_key() {
+        let snippets = build_snippets("https://acme.plexmcp.com/mcp", "pmcp_secret");
+        let claude = serde_json::to_string(&snippets.claude_desktop).unwrap();
+        assert!(claude.contains("https://acme.plexmcp.com/mcp"));
+        assert!(claude.contains("pmcp_secret"));
+
+        assert_eq!(
+            snippets.cursor["mcpServers"]["plexmcp"]["url"],
+            "https://acme.plexmcp.com/mcp"
+        );
+        assert_eq!(
+            snippets.cursor["mcpServers"]["plexmcp"]["headers"]["X-API-Key"],
+            "pmcp_secret"
+        );
+    }
+
+    #[test]
+    fn test_vscode_config_shape() {
+        let config = vscode_config("https://acme.plexmcp.com/mcp", "<YOUR_API_KEY>");
+        assert_eq!(config["servers"]["plexmcp"]["type"], "http");
+        assert_eq!(
+            config["servers"]["plexmcp"]["url"],
+            "https://acme.plexmcp.com/mcp"
+        );
+        assert_eq!(
+            config["servers"]["plexmcp"]["headers"]["X-API-Key"],
+            "<YOUR_API_KEY>"
+        );
+    }
+}
@@ -0,0 +1,699 @@
+//! Declarative org configuration
+//!
+//! PUT /api/v1/org/config accepts a full spec of the org's MCPs, API key
+//! metadata, custom domains, and notification preferences and reconciles
+//! it against current state, Terraform-style. Each section is optional:
+//! an omitted section is left untouched, while a present one is
+//! authoritative - resources missing from it are deleted. The response
+//! is the computed plan of creations, updates, and deletions; with
+//! `dry_run` the plan is returned without being applied, so callers can
+//! review exactly what a spec would change.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use axum::{
+    extract::{Extension, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthUser,
+    error::{ApiError, ApiResult},
+    state::AppState,
+};
+
+use super::domains::{generate_verification_token, is_valid_domain};
+use super::github_sync::McpDefinition;
+use super::mcps::{
+    get_mcp_count, get_org_effective_limits, validate_auth_template, validate_cache_config,
+};
+
+/// Same bounds the rate limit override table enforces
+const MAX_KEY_RPM: u32 = 100_000;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct OrgConfigRequest {
+    /// Compute and return the plan without applying it
+    #[serde(default)]
+    pub dry_run: bool,
+    pub config: OrgConfigSpec,
+}
+
+/// The declarative spec. Every section is optional; only sections that
+/// are present are reconciled.
+#[derive(Debug, Deserialize)]
+pub struct OrgConfigSpec {
+    /// Desired MCP instances, keyed by name (same shape as GitHub sync
+    /// definition files)
+    pub mcps: Option<Vec<McpDefinition>>,
+    /// Metadata for existing API keys, keyed by name. Keys cannot be
+    /// created here (secret material is only issued by the dashboard);
+    /// keys absent from the list are deleted.
+    pub api_keys: Option<Vec<ApiKeySpec>>,
+    /// Custom domains for the calling user
+    pub domains: Option<Vec<String>>,
+    /// Notification preferences for the calling user
+    pub notifications: Option<NotificationSpec>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiKeySpec {
+    pub name: String,
+    /// Omit to keep the key's current scopes
+    pub scopes: Option<Vec<String>>,
+    /// Omit to keep the key's current limit
+    pub rate_limit_rpm: Option<u32>,
+}
+
+/// Omitted fields keep their current value; security alerts are always
+/// on and not configurable (matching the notifications API)
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct NotificationSpec {
+    pub email_alerts: Option<bool>,
+    pub weekly_digest: Option<bool>,
+    pub usage_alerts: Option<bool>,
+    pub api_error_notifications: Option<bool>,
+    pub marketing_emails: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrgConfigResponse {
+    pub dry_run: bool,
+    /// Whether the plan was applied (false for dry runs and empty plans)
+    pub applied: bool,
+    pub plan: Vec<PlanEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlanEntry {
+    /// "mcp", "api_key", "domain", or "notification_preferences"
+    pub resource: String,
+    /// "create", "update", or "delete"
+    pub action: String,
+    pub name: String,
+    /// Human-readable summary of what changes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+// =============================================================================
+// Reconciliation Plan
+// =============================================================================
+
+/// One reconciliation step; carries everything apply needs so the plan
+/// shown to the caller and the work done are the same by construction
+enum Op {
+    McpCreate(McpDefinition),
+    McpUpdate(McpDefinition),
+    McpDelete(String),
+    ApiKeyUpdate {
+        id: Uuid,
+        name: String,
+        scopes: Option<serde_json::Value>,
+        rate_limit_rpm: Option<i32>,
+    },
+    ApiKeyDelete {
+        id: Uuid,
+        name: String,
+    },
+    DomainCreate(String),
+    DomainDelete {
+        id: Uuid,
+        domain: String,
+    },
+    PrefsUpdate {
+        changed: Vec<&'static str>,
+        email_alerts: bool,
+        weekly_digest: bool,
+        usage_alerts: bool,
+        api_error_notifications: bool,
+        marketing_emails: bool,
+    },
+}
+
+impl Op {
+    fn plan_entry(&self) -> PlanEntry {
+        let (resource, action, name, detail) = match self {
+            Op::McpCreate(d) => ("mcp", "create", d.name.clone(), None),
+            Op::McpUpdate(d) => ("mcp", "update", d.name.clone(), None),
+            Op::McpDelete(name) => ("mcp", "delete", name.clone(), None),
+            Op::ApiKeyUpdate {
+                name,
+                scopes,
+                rate_limit_rpm,
+                ..
+            } => {
+                let mut changes = Vec::new();
+                if scopes.is_some() {
+                    changes.push("scopes");
+                }
+                if rate_limit_rpm.is_some() {
+                    changes.push("rate_limit_rpm");
+                }
+                ("api_key", "update", name.clone(), Some(changes.join(", ")))
+            }
+            Op::ApiKeyDelete { name, .. } => ("api_key", "delete", name.clone(), None),
+            Op::DomainCreate(domain) => ("domain", "create", domain.clone(), None),
+            Op::DomainDelete { domain, .. } => ("domain", "delete", domain.clone(), None),
+            Op::PrefsUpdate { changed, .. } => (
+                "notification_preferences",
+                "update",
+                "notifications".to_string(),
+                Some(changed.join(", ")),
+            ),
+        };
+        PlanEntry {
+            resource: resource.to_string(),
+            action: action.to_string(),
+            name,
+            detail,
+        }
+    }
+}
+
+// =============================================================================
+// Handler
+// =============================================================================
+
+/// Reconcile the org against a declarative config spec
+pub async fn put_org_config(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<OrgConfigRequest>,
+) -> ApiResult<Json<OrgConfigResponse>> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+    let user_id = auth_user.user_id.ok_or(ApiError::Unauthorized)?;
+
+    let mut ops = Vec::new();
+
+    if let Some(ref mcps) = req.config.mcps {
+        plan_mcps(&state, org_id, mcps, &mut ops).await?;
+    }
+    if let Some(ref api_keys) = req.config.api_keys {
+        plan_api_keys(&state, org_id, api_keys, &mut ops).await?;
+    }
+    if let Some(ref domains) = req.config.domains {
+        plan_domains(&state, user_id, domains, &mut ops).await?;
+    }
+    if let Some(ref notifications) = req.config.notifications {
+        plan_notifications(&state, user_id, notifications, &mut ops).await?;
+    }
+
+    let plan: Vec<PlanEntry> = ops.iter().map(Op::plan_entry).collect();
+
+    if req.dry_run || ops.is_empty() {
+        return Ok(Json(OrgConfigResponse {
+            dry_run: req.dry_run,
+            applied: false,
+            plan,
+        }));
+    }
+
+    apply_ops(&state, org_id, user_id, &ops).await?;
+
+    tracing::info!(
+        org_id = %org_id,
+        user_id = %user_id,
+        changes = plan.len(),
+        "Declarative org config applied"
+    );
+
+    Ok(Json(OrgConfigResponse {
+        dry_run: false,
+        applied: true,
+        plan,
+    }))
+}
+
+// =============================================================================
+// Planning
+// =============================================================================
+
+/// Diff desired MCPs against the org's current instances
+async fn plan_mcps(
+    state: &AppState,
+    org_id: Uuid,
+    desired: &[McpDefinition],
+    ops: &mut Vec<Op>,
+) -> Result<(), ApiError> {
+    let mut seen = BTreeSet::new();
+    for definition in desired {
+        let name = definition.name.trim();
+        if name.is_empty() {
+            return Err(ApiError::Validation("MCP name cannot be empty".to_string()));
+        }
+        if !seen.insert(name.to_string()) {
+            return Err(ApiError::Validation(format!(
+                "Duplicate MCP name in spec: {}",
+                name
+            )));
+        }
+        if definition.mcp_type.trim().is_empty() {
+            return Err(ApiError::Validation(format!(
+                "MCP {}: mcp_type cannot be empty",
+                name
+            )));
+        }
+        if let Some(ref config) = definition.config {
+            if !config.is_object() {
+                return Err(ApiError::Validation(format!(
+                    "MCP {}: config must be an object",
+                    name
+                )));
+            }
+            validate_cache_config(config)?;
+            validate_auth_template(config)?;
+        }
+    }
+
+    let existing: Vec<(String, String, Option<String>, serde_json::Value, String)> =
+        sqlx::query_as(
+            "SELECT name, mcp_type, description, config, status FROM mcp_instances WHERE org_id = $1",
+        )
+        .bind(org_id)
+        .fetch_all(&state.pool)
+        .await?;
+    let current: BTreeMap<&str, &(String, String, Option<String>, serde_json::Value, String)> =
+        existing.iter().map(|row| (row.0.as_str(), row)).collect();
+
+    let mut creates = 0_i64;
+    for definition in desired {
+        match current.get(definition.name.trim()) {
+            None => {
+                creates += 1;
+                ops.push(Op::McpCreate(definition.clone()));
+            }
+            Some((_, mcp_type, description, config, status)) => {
+                let desired_config = definition.config.clone().unwrap_or(serde_json::json!({}));
+                let desired_status = if definition.is_active.unwrap_or(true) {
+                    "active"
+                } else {
+                    "inactive"
+                };
+                let unchanged = mcp_type == definition.mcp_type.trim()
+                    && *description == definition.description
+                    && *config == desired_config
+                    && (status == desired_status
+                        // Statuses beyond active/inactive (e.g. 'error')
+                        // are operational, not declarative; leave them
+                        || (desired_status == "active" && status != "inactive"));
+                if !unchanged {
+                    ops.push(Op::McpUpdate(definition.clone()));
+                }
+            }
+        }
+    }
+
+    for (name, _, _, _, _) in &existing {
+        if !seen.contains(name) {
+            ops.push(Op::McpDelete(name.clone()));
+        }
+    }
+
+    // Creates count toward the tier MCP limit just like dashboard creates
+    if creates > 0 {
+        let effective_limits = get_org_effective_limits(&state.pool, org_id).await?;
+        let current_count = get_mcp_count(&state.pool, org_id).await?;
+        let deletes = ops
+            .iter()
+            .filter(|op| matches!(op, Op::McpDelete(_)))
+            .count() as i64;
+        if current_count - deletes + creates > i64::from(effective_limits.max_mcps) {
+            return Err(ApiError::QuotaExceeded(format!(
+                "Applying this config would exceed the {} MCP limit",
+                effective_limits.max_mcps
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Diff desired API key metadata against the org's current keys
+///
+/// Keys are matched by name, so every existing key must have a unique
+/// name before this section can be managed declaratively.
+async fn plan_api_keys(
+    state: &AppState,
+    org_id: Uuid,
+    desired: &[ApiKeySpec],
+    ops: &mut Vec<Op>,
+) -> Result<(), ApiError> {
+    let mut seen = BTreeSet::new();
+    for spec in desired {
+        let name = spec.name.trim();
+        if name.is_empty() {
+            return Err(ApiError::Validation(
+                "API key name cannot be empty".to_string(),
+            ));
+        }
+        if !seen.insert(name.to_string()) {
+            return Err(ApiError::Validation(format!(
+                "Duplicate API key name in spec: {}",
+                name
+            )));
+        }
+        if let Some(rpm) = spec.rate_limit_rpm {
+            if !(1..=MAX_KEY_RPM).contains(&rpm) {
+                return Err(ApiError::Validation(format!(
+                    "API key {}: rate_limit_rpm must be between 1 and {}",
+                    name, MAX_KEY_RPM
+                )));
+            }
+        }
+    }
+
+    let existing: Vec<(Uuid, String, serde_json::Value, i32)> =
+        sqlx::query_as("SELECT id, name, scopes, rate_limit_rpm FROM api_keys WHERE org_id = $1")
+            .bind(org_id)
+            .fetch_all(&state.pool)
+            .await?;
+
+    let mut by_name: BTreeMap<&str, &(Uuid, String, serde_json::Value, i32)> = BTreeMap::new();
+    for row in &existing {
+        if by_name.insert(row.1.as_str(), row).is_some() {
+            return Err(ApiError::Validation(format!(
+                "Multiple API keys are named \"{}\"; rename them before managing keys declaratively",
+                row.1
+            )));
+        }
+    }
+
+    for spec in desired {
+        let Some((id, name, current_scopes, current_rpm)) = by_name.get(spec.name.trim()).copied()
+        else {
+            // Issuing secret material is deliberately out of scope here
+            return Err(ApiError::Validation(format!(
+                "API key \"{}\" does not exist; keys must be created via the API keys endpoint first",
+                spec.name.trim()
+            )));
+        };
+
+        let new_scopes = spec.scopes.as_ref().and_then(|scopes| {
+            let desired_json = serde_json::json!(scopes);
+            (*current_scopes != desired_json).then_some(desired_json)
+        });
+        let new_rpm = spec
+            .rate_limit_rpm
+            .map(|rpm| rpm as i32)
+            .filter(|rpm| rpm != current_rpm);
+
+        if new_scopes.is_some() || new_rpm.is_some() {
+            ops.push(Op::ApiKeyUpdate {
+                id: *id,
+                name: name.clone(),
+                scopes: new_scopes,
+                rate_limit_rpm: new_rpm,
+            });
+        }
+    }
+
+    for (id, name, _, _) in &existing {
+        if !seen.contains(name) {
+            ops.push(Op::ApiKeyDelete {
+                id: *id,
+                name: name.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Diff desired custom domains against the user's current ones
+async fn plan_domains(
+    state: &AppState,
+    user_id: Uuid,
+    desired: &[String],
+    ops: &mut Vec<Op>,
+) -> Result<(), ApiError> {
+    let mut wanted = BTreeSet::new();
+    for raw in desired {
+        let domain = raw.trim().to_lowercase();
+        if !is_valid_domain(&domain) {
+            return Err(ApiError::Validation(format!(
+                "Invalid domain format: {}",
+                raw
+            )));
+        }
+        wanted.insert(domain);
+    }
+
+    let existing: Vec<(Uuid, String)> =
+        sqlx::query_as("SELECT id, domain FROM custom_domains WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_all(&state.pool)
+            .await?;
+    let current: BTreeSet<&str> = existing.iter().map(|(_, d)| d.as_str()).collect();
+
+    for domain in &wanted {
+        if !current.contains(domain.as_str()) {
+            // New domains must not be registered elsewhere either
+            let taken: Option<(Uuid,)> =
+                sqlx::query_as("SELECT id FROM custom_domains WHERE domain = $1")
+                    .bind(domain)
+                    .fetch_optional(&state.pool)
+                    .await?;
+            if taken.is_some() {
+                return Err(ApiError::Conflict(format!(
+                    "Domain {} is already registered",
+                    domain
+                )));
+            }
+            ops.push(Op::DomainCreate(domain.clone()));
+        }
+    }
+
+    for (id, domain) in &existing {
+        if !wanted.contains(domain) {
+            ops.push(Op::DomainDelete {
+                id: *id,
+                domain: domain.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Diff desired notification preferences against the user's current ones
+async fn plan_notifications(
+    state: &AppState,
+    user_id: Uuid,
+    spec: &NotificationSpec,
+    ops: &mut Vec<Op>,
+) -> Result<(), ApiError> {
+    let current: Option<(bool, bool, bool, bool, bool)> = sqlx::query_as(
+        r#"
+        SELECT email_alerts, weekly_digest, usage_alerts,
+               api_error_notifications, marketing_emails
+        FROM user_notification_preferences
+        WHERE user_id = $1
+        "#,
+    )
+    .bind(user_id)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    // Same defaults the notifications API reports for users with no row
+    let (email_alerts, weekly_digest, usage_alerts, api_error_notifications, marketing_emails) =
+        current.unwrap_or((true, false, true, true, false));
+
+    let mut changed = Vec::new();
+    let desired_email = spec.email_alerts.unwrap_or(email_alerts);
+    if desired_email != email_alerts {
+        changed.push("email_alerts");
+    }
+    let desired_weekly = spec.weekly_digest.unwrap_or(weekly_digest);
+    if desired_weekly != weekly_digest {
+        changed.push("weekly_digest");
+    }
+    let desired_usage = spec.usage_alerts.unwrap_or(usage_alerts);
+    if desired_usage != usage_alerts {
+        changed.push("usage_alerts");
+    }
+    let desired_api_errors = spec
+        .api_error_notifications
+        .unwrap_or(api_error_notifications);
+    if desired_api_errors != api_error_notifications {
+        changed.push("api_error_notifications");
+    }
+    let desired_marketing = spec.marketing_emails.unwrap_or(marketing_emails);
+    if desired_marketing != marketing_emails {
+        changed.push("marketing_emails");
+    }
+
+    if !changed.is_empty() {
+        ops.push(Op::PrefsUpdate {
+            changed,
+            email_alerts: desired_email,
+            weekly_digest: desired_weekly,
+            usage_alerts: desired_usage,
+            api_error_notifications: desired_api_errors,
+            marketing_emails: desired_marketing,
+        });
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+// Apply
+// =============================================================================
+
+async fn apply_ops(
+    state: &AppState,
+    org_id: Uuid,
+    user_id: Uuid,
+    ops: &[Op],
+) -> Result<(), ApiError> {
+    let mut mcps_changed = false;
+
+    for op in ops {
+        match op {
+            Op::McpCreate(definition) => {
+                mcps_changed = true;
+                let status = if definition.is_active.unwrap_or(true) {
+                    "active"
+                } else {
+                    "inactive"
+                };
+                sqlx::query(
+                    r#"
+                    INSERT INTO mcp_instances
+                        (id, org_id, name, mcp_type, description, config, status, health_status, created_at, updated_at)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, 'unknown', NOW(), NOW())
+                    "#,
+                )
+                .bind(Uuid::new_v4())
+                .bind(org_id)
+                .bind(definition.name.trim())
+                .bind(definition.mcp_type.trim())
+                .bind(&definition.description)
+                .bind(definition.config.clone().unwrap_or(serde_json::json!({})))
+                .bind(status)
+                .execute(&state.pool)
+                .await?;
+            }
+            Op::McpUpdate(definition) => {
+                mcps_changed = true;
+                sqlx::query(
+                    r#"
+                    UPDATE mcp_instances
+                    SET mcp_type = $3, description = $4, config = $5,
+                        status = CASE WHEN $6 THEN 'active' ELSE 'inactive' END,
+                        updated_at = NOW()
+                    WHERE org_id = $1 AND name = $2
+                    "#,
+                )
+                .bind(org_id)
+                .bind(definition.name.trim())
+                .bind(definition.mcp_type.trim())
+                .bind(&definition.description)
+                .bind(definition.config.clone().unwrap_or(serde_json::json!({})))
+                .bind(definition.is_active.unwrap_or(true))
+                .execute(&state.pool)
+                .await?;
+            }
+            Op::McpDelete(name) => {
+                mcps_changed = true;
+                sqlx::query("DELETE FROM mcp_instances WHERE org_id = $1 AND name = $2")
+                    .bind(org_id)
+                    .bind(name)
+                    .execute(&state.pool)
+                    .await?;
+            }
+            Op::ApiKeyUpdate {
+                id,
+                scopes,
+                rate_limit_rpm,
+                ..
+            } => {
+                sqlx::query(
+                    r#"
+                    UPDATE api_keys
+                    SET scopes = COALESCE($2, scopes),
+                        rate_limit_rpm = COALESCE($3, rate_limit_rpm)
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(id)
+                .bind(scopes)
+                .bind(rate_limit_rpm)
+                .execute(&state.pool)
+                .await?;
+            }
+            Op::ApiKeyDelete { id, .. } => {
+                sqlx::query("DELETE FROM api_keys WHERE id = $1")
+                    .bind(id)
+                    .execute(&state.pool)
+                    .await?;
+            }
+            Op::DomainCreate(domain) => {
+                sqlx::query(
+                    "INSERT INTO custom_domains (user_id, domain, verification_token) VALUES ($1, $2, $3)",
+                )
+                .bind(user_id)
+                .bind(domain)
+                .bind(generate_verification_token())
+                .execute(&state.pool)
+                .await?;
+            }
+            Op::DomainDelete { id, .. } => {
+                sqlx::query("DELETE FROM custom_domains WHERE id = $1 AND user_id = $2")
+                    .bind(id)
+                    .bind(user_id)
+                    .execute(&state.pool)
+                    .await?;
+            }
+            Op::PrefsUpdate {
+                email_alerts,
+                weekly_digest,
+                usage_alerts,
+                api_error_notifications,
+                marketing_emails,
+                ..
+            } => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO user_notification_preferences (
+                        user_id, email_alerts, weekly_digest, usage_alerts,
+                        security_alerts, api_error_notifications, marketing_emails
+                    )
+                    VALUES ($1, $2, $3, $4, true, $5, $6)
+                    ON CONFLICT (user_id) DO UPDATE SET
+                        email_alerts = EXCLUDED.email_alerts,
+                        weekly_digest = EXCLUDED.weekly_digest,
+                        usage_alerts = EXCLUDED.usage_alerts,
+                        api_error_notifications = EXCLUDED.api_error_notifications,
+                        marketing_emails = EXCLUDED.marketing_emails,
+                        updated_at = NOW()
+                    "#,
+                )
+                .bind(user_id)
+                .bind(email_alerts)
+                .bind(weekly_digest)
+                .bind(usage_alerts)
+                .bind(api_error_notifications)
+                .bind(marketing_emails)
+                .execute(&state.pool)
+                .await?;
+            }
+        }
+    }
+
+    if mcps_changed {
+        state.tool_catalog.invalidate(org_id).await;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,484 @@
+//! Org security policy routes: API key rotation policy, key age report,
+//! the JSON-RPC method allowlist and payload capture configuration
+//!
+//! Orgs can force periodic key rotation by setting `max_api_key_age_days`.
+//! Keys older than the policy (measured from the last rotation, or creation
+//! if never rotated) are rejected by the proxy until rotated; the worker
+//! emails reminders at 30/7/1 days before a key's effective expiry. The
+//! key report gives security teams a compliance view of all keys by age.
+//!
+//! The method allowlist restricts which JSON-RPC methods the proxy will
+//! accept for the org (see crate::mcp::method_allowlist for enforcement).
+//! Payload capture opts the org into storing redacted request/response
+//! copies for debugging (see crate::mcp::payload_capture).
+
+use axum::{
+    extract::{Extension, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthUser,
+    error::{ApiError, ApiResult},
+    state::AppState,
+};
+
+/// Valid range for the rotation policy, matching the column CHECK constraint
+const MIN_KEY_AGE_DAYS: i32 = 1;
+const MAX_KEY_AGE_DAYS: i32 = 3650;
+
+/// Keys within this many days of expiry are flagged as due for rotation
+const DUE_SOON_DAYS: i64 = 30;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateKeyPolicyRequest {
+    /// Maximum key age in days (1-3650); null clears the policy
+    pub max_api_key_age_days: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct KeyPolicyResponse {
+    /// Maximum key age in days; None when no rotation policy is set
+    pub max_api_key_age_days: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateMethodAllowlistRequest {
+    /// Allowed method names or `namespace/*` wildcards; null or empty
+    /// clears the restriction
+    pub methods: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MethodAllowlistResponse {
+    /// Allowed methods; None when the org is unrestricted
+    pub methods: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdatePayloadCaptureRequest {
+    pub enabled: bool,
+    /// Days to retain captured payloads (1-90); defaults to 7
+    pub retention_days: Option<i64>,
+    /// Redaction rules as `$.`-prefixed dotted paths, `*` wildcards allowed
+    #[serde(default)]
+    pub redact_paths: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct KeyReportResponse {
+    pub max_api_key_age_days: Option<i32>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub generated_at: OffsetDateTime,
+    pub total_keys: i64,
+    /// Keys counted into age buckets (0-29, 30-89, 90-179, 180+ days)
+    pub keys_by_age: Vec<AgeBucketCount>,
+    /// All keys, oldest first
+    pub keys: Vec<KeyReportEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AgeBucketCount {
+    pub bucket: &'static str,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct KeyReportEntry {
+    pub id: Uuid,
+    pub name: String,
+    pub key_prefix: String,
+    /// Email of the key creator; None if the user was deleted
+    pub created_by_email: Option<String>,
+    /// Days since the key secret was created or last rotated
+    pub age_days: i64,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_used_at: Option<OffsetDateTime>,
+    /// Earliest of the key's explicit expiry and the policy cutoff
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub effective_expires_at: Option<OffsetDateTime>,
+    /// Rotation status: 'ok', 'due_soon' (within 30 days), or 'expired'
+    pub rotation_status: &'static str,
+}
+
+/// Raw key row; age and status are derived in Rust so the 30/7/1-day
+/// semantics stay in one place
+#[derive(FromRow)]
+struct KeyReportRow {
+    id: Uuid,
+    name: String,
+    key_prefix: String,
+    created_by_email: Option<String>,
+    key_age_basis: OffsetDateTime,
+    expires_at: Option<OffsetDateTime>,
+    last_used_at: Option<OffsetDateTime>,
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// Get the org's API key rotation policy
+pub async fn get_key_policy(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<KeyPolicyResponse>> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let max_api_key_age_days: Option<i32> =
+        sqlx::query_scalar("SELECT max_api_key_age_days FROM organizations WHERE id = $1")
+            .bind(org_id)
+            .fetch_optional(&state.pool)
+            .await?
+            .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(KeyPolicyResponse {
+        max_api_key_age_days,
+    }))
+}
+
+/// Set or clear the org's API key rotation policy (owner/admin only)
+pub async fn update_key_policy(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<UpdateKeyPolicyRequest>,
+) -> ApiResult<Json<KeyPolicyResponse>> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    if let Some(days) = req.max_api_key_age_days {
+        if !(MIN_KEY_AGE_DAYS..=MAX_KEY_AGE_DAYS).contains(&days) {
+            return Err(ApiError::Validation(format!(
+                "max_api_key_age_days must be between {} and {}",
+                MIN_KEY_AGE_DAYS, MAX_KEY_AGE_DAYS
+            )));
+        }
+    }
+
+    sqlx::query("UPDATE organizations SET max_api_key_age_days = $1 WHERE id = $2")
+        .bind(req.max_api_key_age_days)
+        .bind(org_id)
+        .execute(&state.pool)
+        .await?;
+
+    tracing::info!(
+        org_id = %org_id,
+        max_api_key_age_days = ?req.max_api_key_age_days,
+        "API key rotation policy updated"
+    );
+
+    Ok(Json(KeyPolicyResponse {
+        max_api_key_age_days: req.max_api_key_age_days,
+    }))
+}
+
+/// Get the org's JSON-RPC method allowlist; null means unrestricted
+pub async fn get_method_allowlist(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<MethodAllowlistResponse>> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    Ok(Json(MethodAllowlistResponse {
+        methods: crate::mcp::method_allowlist::load(&state.pool, org_id).await,
+    }))
+}
+
+/// Set or clear the org's JSON-RPC method allowlist (owner/admin only)
+///
+/// Entries must be known proxy methods or `namespace/*` wildcards; an
+/// empty or null list removes the restriction.
+pub async fn update_method_allowlist(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<UpdateMethodAllowlistRequest>,
+) -> ApiResult<Json<MethodAllowlistResponse>> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let methods = req.methods.filter(|m| !m.is_empty());
+
+    if let Some(ref methods) = methods {
+        for entry in methods {
+            if !crate::mcp::method_allowlist::is_valid_entry(entry) {
+                return Err(ApiError::Validation(format!(
+                    "Unknown method '{}'; valid entries are {} or namespace wildcards like tools/*",
+                    entry,
+                    crate::mcp::method_allowlist::KNOWN_METHODS.join(", ")
+                )));
+            }
+        }
+    }
+
+    match methods {
+        Some(ref methods) => {
+            sqlx::query(
+                "UPDATE organizations \
+                 SET settings = jsonb_set(COALESCE(settings, '{}'::jsonb), '{mcp_method_allowlist}', $1::jsonb), \
+                     updated_at = NOW() \
+                 WHERE id = $2",
+            )
+            .bind(serde_json::to_value(methods).unwrap_or_default())
+            .bind(org_id)
+            .execute(&state.pool)
+            .await?;
+        }
+        None => {
+            sqlx::query(
+                "UPDATE organizations \
+                 SET settings = COALESCE(settings, '{}'::jsonb) - $1, updated_at = NOW() \
+                 WHERE id = $2",
+            )
+            .bind(crate::mcp::method_allowlist::SETTINGS_KEY)
+            .bind(org_id)
+            .execute(&state.pool)
+            .await?;
+        }
+    }
+
+    tracing::info!(
+        org_id = %org_id,
+        methods = ?methods,
+        "JSON-RPC method allowlist updated"
+    );
+
+    Ok(Json(MethodAllowlistResponse { methods }))
+}
+
+/// Get the org's payload capture configuration (owner/admin only)
+pub async fn get_payload_capture(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<crate::mcp::payload_capture::PayloadCaptureConfig>> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    Ok(Json(
+        crate::mcp::payload_capture::load(&state.pool, org_id).await,
+    ))
+}
+
+/// Update the org's payload capture configuration (owner/admin only)
+///
+/// Redaction rules are applied at write time, so tightening them does not
+/// re-redact already-captured payloads; shortening the retention window
+/// takes effect on the next retention sweep.
+pub async fn update_payload_capture(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<UpdatePayloadCaptureRequest>,
+) -> ApiResult<Json<crate::mcp::payload_capture::PayloadCaptureConfig>> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let retention_days = req.retention_days.unwrap_or(7);
+    if !(crate::mcp::payload_capture::MIN_RETENTION_DAYS
+        ..=crate::mcp::payload_capture::MAX_RETENTION_DAYS)
+        .contains(&retention_days)
+    {
+        return Err(ApiError::Validation(format!(
+            "retention_days must be between {} and {}",
+            crate::mcp::payload_capture::MIN_RETENTION_DAYS,
+            crate::mcp::payload_capture::MAX_RETENTION_DAYS
+        )));
+    }
+
+    for path in &req.redact_paths {
+        if !crate::mcp::payload_capture::is_valid_path(path) {
+            return Err(ApiError::Validation(format!(
+                "Invalid redaction path '{}'; paths must look like $.params.arguments.password (use * to match any key)",
+                path
+            )));
+        }
+    }
+
+    let config = crate::mcp::payload_capture::PayloadCaptureConfig {
+        enabled: req.enabled,
+        retention_days,
+        redact_paths: req.redact_paths,
+    };
+
+    sqlx::query(
+        "UPDATE organizations \
+         SET settings = jsonb_set(COALESCE(settings, '{}'::jsonb), '{payload_capture}', $1::jsonb), \
+             updated_at = NOW() \
+         WHERE id = $2",
+    )
+    .bind(serde_json::to_value(&config).unwrap_or_default())
+    .bind(org_id)
+    .execute(&state.pool)
+    .await?;
+
+    tracing::info!(
+        org_id = %org_id,
+        enabled = config.enabled,
+        retention_days = config.retention_days,
+        redact_path_count = config.redact_paths.len(),
+        "Payload capture configuration updated"
+    );
+
+    Ok(Json(config))
+}
+
+/// Compliance report of the org's API keys by age (owner/admin only)
+pub async fn get_key_report(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<KeyReportResponse>> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let max_api_key_age_days: Option<i32> =
+        sqlx::query_scalar("SELECT max_api_key_age_days FROM organizations WHERE id = $1")
+            .bind(org_id)
+            .fetch_optional(&state.pool)
+            .await?
+            .ok_or(ApiError::NotFound)?;
+
+    let rows: Vec<KeyReportRow> = sqlx::query_as(
+        r#"
+        SELECT ak.id, ak.name, ak.key_prefix,
+               u.email AS created_by_email,
+               COALESCE(ak.last_rotated_at, ak.created_at) AS key_age_basis,
+               ak.expires_at, ak.last_used_at
+        FROM api_keys ak
+        LEFT JOIN users u ON u.id = ak.created_by
+        WHERE ak.org_id = $1 AND ak.status = 'active'
+        ORDER BY key_age_basis ASC
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let now = OffsetDateTime::now_utc();
+    let mut bucket_counts = [0i64; AGE_BUCKETS.len()];
+    let keys: Vec<KeyReportEntry> = rows
+        .into_iter()
+        .map(|row| {
+            let age_days = (now - row.key_age_basis).whole_days().max(0);
+            bucket_counts[age_bucket_index(age_days)] += 1;
+
+            let effective_expires_at =
+                effective_expiry(row.key_age_basis, row.expires_at, max_api_key_age_days);
+            let rotation_status = match effective_expires_at {
+                Some(expiry) if expiry <= now => "expired",
+                Some(expiry) if expiry <= now + time::Duration::days(DUE_SOON_DAYS) => "due_soon",
+                _ => "ok",
+            };
+
+            KeyReportEntry {
+                id: row.id,
+                name: row.name,
+                key_prefix: row.key_prefix,
+                created_by_email: row.created_by_email,
+                age_days,
+                last_used_at: row.last_used_at,
+                effective_expires_at,
+                rotation_status,
+            }
+        })
+        .collect();
+
+    let keys_by_age = AGE_BUCKETS
+        .iter()
+        .zip(bucket_counts)
+        .map(|(bucket, count)| AgeBucketCount { bucket, count })
+        .collect();
+
+    Ok(Json(KeyReportResponse {
+        max_api_key_age_days,
+        generated_at: now,
+        total_keys: keys.len() as i64,
+        keys_by_age,
+        keys,
+    }))
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Age bucket labels for the compliance report, youngest first
+const AGE_BUCKETS: [&str; 4] = ["0-29", "30-89", "90-179", "180+"];
+
+fn age_bucket_index(age_days: i64) -> usize {
+    match age_days {
+        0..=29 => 0,
+        30..=89 => 1,
+        90..=179 => 2,
+        _ => 3,
+    }
+}
+
+/// Earliest of the key's explicit expiry and the rotation policy cutoff;
+/// None when the key never expires and no policy is set
+fn effective_expiry(
+    key_age_basis: OffsetDateTime,
+    expires_at: Option<OffsetDateTime>,
+    max_age_days: Option<i32>,
+) -> Option<OffsetDateTime> {
+    let policy_cutoff =
+        max_age_days.map(|days| key_age_basis + time::Duration::days(i64::from(days)));
+    match (expires_at, policy_cutoff) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (Some(a), None) => Some(a),
+        (None, Some(b)) => Some(b),
+        (None, None) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_age_bucket_index() {
+        assert_eq!(age_bucket_index(0), 0);
+        assert_eq!(age_bucket_index(29), 0);
+        assert_eq!(age_bucket_index(30), 1);
+        assert_eq!(age_bucket_index(90), 2);
+        assert_eq!(age_bucket_index(180), 3);
+        assert_eq!(age_bucket_index(5000), 3);
+    }
+
+    #[test]
+    fn test_effective_expiry_takes_earliest() {
+        let basis = OffsetDateTime::now_utc();
+        let explicit = basis + time::Duration::days(10);
+
+        // Policy cutoff (90 days) is later than the explicit expiry
+        let effective = effective_expiry(basis, Some(explicit), Some(90));
+        assert_eq!(effective, Some(explicit));
+
+        // Policy cutoff (5 days) is earlier than the explicit expiry
+        let effective = effective_expiry(basis, Some(explicit), Some(5));
+        assert_eq!(effective, Some(basis + time::Duration::days(5)));
+
+        // No expiry and no policy
+        assert_eq!(effective_expiry(basis, None, None), None);
+    }
+}
@@ -13,10 +13,16 @@ use uuid::Uuid;
 use crate::{
     auth::AuthUser,
     error::{ApiError, ApiResult},
-    routing::RESERVED_SUBDOMAINS,
+    routing::{is_reserved_subdomain, SUBDOMAIN_REDIRECT_GRACE_DAYS},
     state::AppState,
 };
 
+/// How often an org may change its custom subdomain
+///
+/// Changes churn client configs and leave grace-period redirects behind,
+/// so they're limited to one per cooldown window.
+const SUBDOMAIN_CHANGE_COOLDOWN_DAYS: i64 = 30;
+
 // =============================================================================
 // Request/Response Types
 // =============================================================================
@@ -229,25 +235,35 @@ pub async fn update_org(
 
     // Update custom_subdomain if provided (paid tiers only)
     if let Some(ref custom_subdomain) = req.custom_subdomain {
-        // Get current tier
-        let tier: (String,) =
-            sqlx::query_as("SELECT subscription_tier FROM organizations WHERE id = $1")
-                .bind(org_id)
-                .fetch_one(&state.pool)
-                .await?;
+        // Get current tier and subdomain
+        let (tier, current_subdomain): (String, Option<String>) = sqlx::query_as(
+            "SELECT subscription_tier, custom_subdomain FROM organizations WHERE id = $1",
+        )
+        .bind(org_id)
+        .fetch_one(&state.pool)
+        .await?;
 
         // Check tier access (pro, team, enterprise only)
-        let tier_lower = tier.0.to_lowercase();
+        let tier_lower = tier.to_lowercase();
         if !["pro", "team", "enterprise"].contains(&tier_lower.as_str()) {
             return Err(ApiError::Forbidden);
         }
 
         // Allow clearing subdomain with empty string
         if custom_subdomain.is_empty() {
-            sqlx::query("UPDATE organizations SET custom_subdomain = NULL, updated_at = NOW() WHERE id = $1")
-                .bind(org_id)
-                .execute(&state.pool)
+            // No-op clears don't burn the cooldown or create history
+            if let Some(ref old_subdomain) = current_subdomain {
+                enforce_subdomain_cooldown(&state, org_id).await?;
+
+                apply_subdomain_change(
+                    &state,
+                    org_id,
+                    auth_user.user_id,
+                    Some(old_subdomain),
+                    None,
+                )
                 .await?;
+            }
         } else {
             // Normalize to lowercase
             let subdomain = custom_subdomain.trim().to_lowercase();
@@ -255,35 +271,50 @@ pub async fn update_org(
             // Validate format
             validate_custom_subdomain(&subdomain)?;
 
-            // Check reserved
-            if is_reserved_subdomain(&subdomain) {
-                return Err(ApiError::Validation(
-                    "This subdomain is reserved and cannot be used".to_string(),
-                ));
-            }
-
-            // Check uniqueness (against both custom_subdomain AND auto_subdomain)
-            let existing: Option<(Uuid,)> = sqlx::query_as(
-                "SELECT id FROM organizations WHERE (custom_subdomain = $1 OR auto_subdomain = $1) AND id != $2"
-            )
-            .bind(&subdomain)
-            .bind(org_id)
-            .fetch_optional(&state.pool)
-            .await?;
+            // Setting the same subdomain again is a no-op
+            if current_subdomain.as_deref() != Some(subdomain.as_str()) {
+                // Check reserved (baseline list + admin-managed table)
+                if is_reserved_subdomain(&state.pool, &subdomain).await? {
+                    return Err(ApiError::Validation(
+                        "This subdomain is reserved and cannot be used".to_string(),
+                    ));
+                }
+
+                enforce_subdomain_cooldown(&state, org_id).await?;
+
+                // Check uniqueness (against both custom_subdomain AND auto_subdomain)
+                let existing: Option<(Uuid,)> = sqlx::query_as(
+                    "SELECT id FROM organizations WHERE (custom_subdomain = $1 OR auto_subdomain = $1) AND id != $2"
+                )
+                .bind(&subdomain)
+                .bind(org_id)
+                .fetch_optional(&state.pool)
+                .await?;
 
-            if existing.is_some() {
-                return Err(ApiError::Conflict(
-                    "This subdomain is already taken".to_string(),
-                ));
+                if existing.is_some() {
+                    return Err(ApiError::Conflict(
+                        "This subdomain is already taken".to_string(),
+                    ));
+                }
+
+                // A subdomain released by another org keeps redirecting to
+                // them for the grace period and can't be claimed yet
+                if subdomain_in_grace_period(&state, &subdomain, org_id).await? {
+                    return Err(ApiError::Conflict(
+                        "This subdomain was recently released and is temporarily unavailable"
+                            .to_string(),
+                    ));
+                }
+
+                apply_subdomain_change(
+                    &state,
+                    org_id,
+                    auth_user.user_id,
+                    current_subdomain.as_deref(),
+                    Some(&subdomain),
+                )
+                .await?;
             }
-
-            sqlx::query(
-                "UPDATE organizations SET custom_subdomain = $1, updated_at = NOW() WHERE id = $2",
-            )
-            .bind(&subdomain)
-            .bind(org_id)
-            .execute(&state.pool)
-            .await?;
         }
     }
 
@@ -727,8 +758,101 @@ fn validate_custom_subdomain(subdomain: &str) -> Result<(), ApiError> {
     Ok(())
 }
 
-fn is_reserved_subdomain(subdomain: &str) -> bool {
-    RESERVED_SUBDOMAINS.contains(&subdomain)
+/// Reject the change if the org already changed its subdomain within the
+/// cooldown window
+async fn enforce_subdomain_cooldown(state: &AppState, org_id: Uuid) -> Result<(), ApiError> {
+    let last_change: Option<(OffsetDateTime,)> = sqlx::query_as(
+        "SELECT changed_at FROM org_subdomain_history WHERE org_id = $1 ORDER BY changed_at DESC LIMIT 1",
+    )
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    if let Some((changed_at,)) = last_change {
+        let next_allowed = changed_at + time::Duration::days(SUBDOMAIN_CHANGE_COOLDOWN_DAYS);
+        if OffsetDateTime::now_utc() < next_allowed {
+            let next_allowed_str = next_allowed
+                .format(&time::format_description::well_known::Rfc3339)
+                .unwrap_or_else(|_| next_allowed.to_string());
+            return Err(ApiError::TooManyRequests(format!(
+                "Subdomain can only be changed once every {} days; next change allowed after {}",
+                SUBDOMAIN_CHANGE_COOLDOWN_DAYS, next_allowed_str
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check whether another org released this subdomain within the redirect
+/// grace period (during which it still resolves to them)
+async fn subdomain_in_grace_period(
+    state: &AppState,
+    subdomain: &str,
+    org_id: Uuid,
+) -> Result<bool, ApiError> {
+    let redirecting: Option<(Uuid,)> = sqlx::query_as(
+        r#"
+        SELECT org_id FROM org_subdomain_history
+        WHERE old_subdomain = $1
+          AND org_id != $2
+          AND changed_at > NOW() - make_interval(days => $3)
+        LIMIT 1
+        "#,
+    )
+    .bind(subdomain)
+    .bind(org_id)
+    .bind(SUBDOMAIN_REDIRECT_GRACE_DAYS)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    Ok(redirecting.is_some())
+}
+
+/// Apply a subdomain change: update the org, record history, and drop any
+/// cached host resolutions for the org
+async fn apply_subdomain_change(
+    state: &AppState,
+    org_id: Uuid,
+    changed_by: Option<Uuid>,
+    old_subdomain: Option<&str>,
+    new_subdomain: Option<&str>,
+) -> Result<(), ApiError> {
+    let mut tx = state.pool.begin().await?;
+
+    sqlx::query("UPDATE organizations SET custom_subdomain = $1, updated_at = NOW() WHERE id = $2")
+        .bind(new_subdomain)
+        .bind(org_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO org_subdomain_history (org_id, old_subdomain, new_subdomain, changed_by)
+        VALUES ($1, $2, $3, $4)
+        "#,
+    )
+    .bind(org_id)
+    .bind(old_subdomain)
+    .bind(new_subdomain)
+    .bind(changed_by)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    // The old host must stop resolving (beyond the grace redirect) and the
+    // new one must start immediately
+    state.host_resolver.invalidate_org(org_id);
+
+    tracing::info!(
+        org_id = %org_id,
+        old_subdomain = ?old_subdomain,
+        new_subdomain = ?new_subdomain,
+        "Custom subdomain changed"
+    );
+
+    Ok(())
 }
 
 // =============================================================================
@@ -762,8 +886,8 @@ pub async fn check_subdomain_availability(
         }));
     }
 
-    // Check reserved
-    if is_reserved_subdomain(&subdomain) {
+    // Check reserved (baseline list + admin-managed table)
+    if is_reserved_subdomain(&state.pool, &subdomain).await? {
         return Ok(Json(CheckSubdomainResponse {
             available: false,
             reason: Some("This subdomain is reserved".to_string()),
@@ -786,8 +910,70 @@ pub async fn check_subdomain_availability(
         }));
     }
 
+    // Check grace-period redirects left by another org
+    if subdomain_in_grace_period(&state, &subdomain, org_id).await? {
+        return Ok(Json(CheckSubdomainResponse {
+            available: false,
+            reason: Some(
+                "This subdomain was recently released and is temporarily unavailable".to_string(),
+            ),
+        }));
+    }
+
     Ok(Json(CheckSubdomainResponse {
         available: true,
         reason: None,
     }))
 }
+
+// =============================================================================
+// Subdomain Change History
+// =============================================================================
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct SubdomainHistoryEntry {
+    pub old_subdomain: Option<String>,
+    pub new_subdomain: Option<String>,
+    pub changed_at: OffsetDateTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SubdomainHistoryResponse {
+    pub history: Vec<SubdomainHistoryEntry>,
+    /// When the next subdomain change is allowed; None means right away
+    pub next_change_allowed_at: Option<OffsetDateTime>,
+}
+
+/// List the org's custom subdomain changes, most recent first
+///
+/// Includes when the next change is allowed under the cooldown rule, so
+/// the dashboard can explain a rejected change up front.
+pub async fn get_subdomain_history(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<SubdomainHistoryResponse>> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let history: Vec<SubdomainHistoryEntry> = sqlx::query_as(
+        r#"
+        SELECT old_subdomain, new_subdomain, changed_at
+        FROM org_subdomain_history
+        WHERE org_id = $1
+        ORDER BY changed_at DESC
+        LIMIT 50
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let next_change_allowed_at = history
+        .first()
+        .map(|entry| entry.changed_at + time::Duration::days(SUBDOMAIN_CHANGE_COOLDOWN_DAYS))
+        .filter(|next_allowed| *next_allowed > OffsetDateTime::now_utc());
+
+    Ok(Json(SubdomainHistoryResponse {
+        history,
+        next_change_allowed_at,
+    }))
+}
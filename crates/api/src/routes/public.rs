@@ -10,6 +10,7 @@ use uuid::Uuid;
 
 use crate::{
     error::{ApiError, ApiResult},
+    spam,
     state::AppState,
 };
 
@@ -35,6 +36,15 @@ pub struct EnterpriseInquiryResponse {
     pub message: String,
 }
 
+#[derive(Debug, Serialize)]
+pub struct EgressIpsResponse {
+    /// Static IPs upstream MCP traffic originates from (for firewall
+    /// allowlisting). Empty when the deployment has no static egress.
+    pub egress_ips: Vec<String>,
+    /// Whether upstream calls are routed through an outbound proxy
+    pub proxy_configured: bool,
+}
+
 // =============================================================================
 // Database Row Types
 // =============================================================================
@@ -69,6 +79,18 @@ fn is_valid_company_size(size: &str) -> bool {
 // Handlers
 // =============================================================================
 
+/// Current egress IPs for upstream MCP traffic (no auth required)
+///
+/// Customers allowlist these IPs so the proxy can reach MCPs behind their
+/// firewalls. The list comes from EGRESS_IPS; operators should keep it in
+/// sync with their NAT gateway / outbound proxy addresses.
+pub async fn get_egress_ips(State(state): State<AppState>) -> Json<EgressIpsResponse> {
+    Json(EgressIpsResponse {
+        egress_ips: state.config.egress_ips.clone(),
+        proxy_configured: state.config.outbound_proxy_url.is_some(),
+    })
+}
+
 /// Submit an enterprise sales inquiry (no auth required)
 ///
 /// Creates a support ticket with category 'enterprise_inquiry' that will be
@@ -132,6 +154,68 @@ pub async fn submit_enterprise_inquiry(
     // Create the subject line
     let subject = format!("Enterprise Inquiry from {}", company_name);
 
+    // Spam scoring: quarantine or discard instead of creating a ticket.
+    // Either way the caller gets the normal success response so bots
+    // can't probe the filter.
+    if state.config.spam_quarantine_threshold > 0 {
+        let mut verdict = spam::score_submission(&subject, use_case);
+
+        if let Some(api_url) = &state.config.spam_check_api_url {
+            if let Some(external) =
+                spam::external_score(&state.http_client, api_url, &subject, use_case, &work_email)
+                    .await
+            {
+                verdict.score += external;
+                verdict.signals.push(format!("external_api:{}", external));
+            }
+        }
+
+        if verdict.score >= state.config.spam_quarantine_threshold {
+            let auto_discard = verdict.score >= state.config.spam_discard_threshold;
+            let payload = serde_json::json!({
+                "content": content,
+                "contact_name": company_name,
+                "contact_email": work_email,
+                "contact_company": company_name,
+                "company_size": req.company_size,
+            });
+
+            let quarantine_id: Uuid = sqlx::query_scalar(
+                r#"
+                INSERT INTO spam_quarantine
+                    (source, contact_email, subject, payload, score, signals, status, auto_discarded)
+                VALUES ('enterprise_inquiry', $1, $2, $3, $4, $5, $6, $7)
+                RETURNING id
+                "#,
+            )
+            .bind(&work_email)
+            .bind(&subject)
+            .bind(&payload)
+            .bind(verdict.score)
+            .bind(&verdict.signals)
+            .bind(if auto_discard { "discarded" } else { "quarantined" })
+            .bind(auto_discard)
+            .fetch_one(&state.pool)
+            .await?;
+
+            tracing::warn!(
+                quarantine_id = %quarantine_id,
+                score = verdict.score,
+                signals = ?verdict.signals,
+                auto_discarded = auto_discard,
+                email = %work_email,
+                "Enterprise inquiry flagged as spam"
+            );
+
+            // Decoy reference in the same shape as a real ticket number
+            return Ok(Json(EnterpriseInquiryResponse {
+                success: true,
+                ticket_number: format!("PLX-{:05}", quarantine_id.as_fields().0 % 100_000),
+                message: "Thank you for your interest! Our enterprise team will contact you within 1 business day.".into(),
+            }));
+        }
+    }
+
     // Insert the support ticket (no user_id or organization_id since this is public)
     // ticket_number is auto-generated by database trigger
     // Store contact info so we can identify the exact person who submitted the inquiry
@@ -170,6 +254,12 @@ pub async fn submit_enterprise_inquiry(
     .execute(&state.pool)
     .await?;
 
+    tokio::spawn(crate::support_rules::run_rules(
+        state.clone(),
+        ticket.id,
+        crate::support_rules::RuleTrigger::TicketCreated,
+    ));
+
     tracing::info!(
         ticket_id = %ticket.id,
         ticket_number = %ticket.ticket_number,
@@ -184,3 +274,109 @@ pub async fn submit_enterprise_inquiry(
         message: "Thank you for your interest! Our enterprise team will contact you within 1 business day.".into(),
     }))
 }
+
+// =============================================================================
+// Email Unsubscribe (RFC 8058 one-click)
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct UnsubscribeQuery {
+    pub token: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnsubscribeResponse {
+    pub success: bool,
+    pub category: String,
+    pub message: String,
+}
+
+/// Validate the token and record the opt-out
+///
+/// Suppression is keyed by address, so it works without login and for
+/// addresses without an account; when the address does have accounts,
+/// the matching notification preference is synced too so the dashboard
+/// reflects the opt-out.
+async fn process_unsubscribe(
+    state: &AppState,
+    token: &str,
+) -> ApiResult<(String, crate::email_suppression::EmailCategory)> {
+    let secret = state.security_email.unsubscribe_secret();
+    if secret.is_empty() {
+        return Err(ApiError::BadRequest("Invalid unsubscribe link".into()));
+    }
+
+    let (email, category) = crate::email_suppression::verify_unsubscribe_token(token, secret)
+        .ok_or_else(|| ApiError::BadRequest("Invalid unsubscribe link".into()))?;
+
+    crate::email_suppression::suppress(&state.pool, &email, category, "unsubscribe_link").await?;
+
+    // Mirror the opt-out into notification preferences where a matching
+    // toggle exists
+    use crate::email_suppression::EmailCategory;
+    let pref_column = match category {
+        EmailCategory::Marketing => Some("marketing_emails"),
+        EmailCategory::Digest => Some("weekly_digest"),
+        EmailCategory::Usage => Some("usage_alerts"),
+        _ => None,
+    };
+    if let Some(column) = pref_column {
+        sqlx::query(&format!(
+            "UPDATE user_notification_preferences SET {} = FALSE \
+             WHERE user_id IN (SELECT id FROM users WHERE email = $1)",
+            column
+        ))
+        .bind(&email)
+        .execute(&state.pool)
+        .await?;
+    }
+
+    tracing::info!(
+        category = %category.as_str(),
+        "Email unsubscribe processed"
+    );
+
+    Ok((email, category))
+}
+
+/// Unsubscribe landing page (link clicked in an email client)
+///
+/// `GET /public/unsubscribe?token=...` - performs the opt-out and shows a
+/// plain confirmation page. No login required.
+pub async fn unsubscribe_page(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<UnsubscribeQuery>,
+) -> ApiResult<axum::response::Html<String>> {
+    let (_, category) = process_unsubscribe(&state, &query.token).await?;
+
+    Ok(axum::response::Html(format!(
+        r#"<!DOCTYPE html>
+<html>
+<head><title>Unsubscribed</title></head>
+<body style="font-family: sans-serif; max-width: 480px; margin: 80px auto; text-align: center;">
+  <h1>You're unsubscribed</h1>
+  <p>You will no longer receive {}.</p>
+  <p style="color: #999; font-size: 13px;">You can resubscribe at any time from your notification preferences.</p>
+</body>
+</html>"#,
+        category.display_name()
+    )))
+}
+
+/// One-click unsubscribe (RFC 8058)
+///
+/// `POST /public/unsubscribe?token=...` - mail clients send this for the
+/// List-Unsubscribe-Post header without any user interaction beyond the
+/// unsubscribe button.
+pub async fn unsubscribe_one_click(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<UnsubscribeQuery>,
+) -> ApiResult<Json<UnsubscribeResponse>> {
+    let (_, category) = process_unsubscribe(&state, &query.token).await?;
+
+    Ok(Json(UnsubscribeResponse {
+        success: true,
+        category: category.as_str().to_string(),
+        message: format!("Unsubscribed from {}", category.display_name()),
+    }))
+}
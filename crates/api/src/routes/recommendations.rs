@@ -0,0 +1,326 @@
+//! Usage-based recommendations
+//!
+//! Analyzes an org's recent proxy traffic and billing usage and suggests
+//! concrete actions: enable result caching for chatty MCPs, investigate
+//! MCPs with elevated error rates or latency, upgrade the tier when the
+//! quota is nearly used up. Recommendations are computed on request from
+//! `mcp_proxy_logs`; nothing is stored except dismissals, which hide a
+//! finding until the dismissal ages out.
+
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Serialize;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{auth::AuthUser, error::ApiError, state::AppState};
+
+/// How far back proxy logs are analyzed
+const ANALYSIS_WINDOW_DAYS: i64 = 7;
+/// Minimum requests before error-rate / latency findings are trusted
+const MIN_REQUESTS_FOR_SIGNAL: i64 = 50;
+/// Minimum successful tool calls before caching is suggested
+const CACHE_MIN_CALLS: i64 = 200;
+/// Calls-per-distinct-tool ratio that marks traffic as repetitive
+const CACHE_REPEAT_FACTOR: i64 = 20;
+/// Error percentage that triggers a warning
+const ERROR_RATE_WARN_PCT: f64 = 5.0;
+/// Average latency that triggers a finding
+const HIGH_LATENCY_MS: f64 = 2000.0;
+/// Quota share that triggers the tier suggestion
+#[cfg(feature = "billing")]
+const QUOTA_WARN_PCT: f64 = 80.0;
+/// Dismissed findings re-surface after this many days
+const DISMISSAL_TTL_DAYS: i64 = 30;
+
+// =============================================================================
+// Response Types
+// =============================================================================
+
+/// One actionable recommendation
+#[derive(Debug, Serialize)]
+pub struct Recommendation {
+    /// Stable key for dismiss tracking (e.g. `enable_caching:<mcp_id>`)
+    pub key: String,
+    /// Machine-readable kind: enable_caching, high_error_rate,
+    /// high_latency, quota_approaching
+    pub kind: String,
+    /// info or warning
+    pub severity: String,
+    pub title: String,
+    pub description: String,
+    pub mcp_id: Option<Uuid>,
+    pub mcp_name: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecommendationsResponse {
+    pub recommendations: Vec<Recommendation>,
+    /// Findings hidden by an active dismissal
+    pub dismissed_count: usize,
+    pub analysis_window_days: i64,
+}
+
+// =============================================================================
+// Database Row Types
+// =============================================================================
+
+#[derive(Debug, FromRow)]
+struct CacheCandidateRow {
+    mcp_id: Uuid,
+    mcp_name: String,
+    calls: i64,
+    distinct_tools: i64,
+}
+
+#[derive(Debug, FromRow)]
+struct McpHealthRow {
+    mcp_id: Uuid,
+    mcp_name: String,
+    requests: i64,
+    errors: i64,
+    avg_latency_ms: Option<f64>,
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// Get usage-based recommendations for the org
+pub async fn get_recommendations(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<RecommendationsResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let since = OffsetDateTime::now_utc() - time::Duration::days(ANALYSIS_WINDOW_DAYS);
+    let mut recommendations = Vec::new();
+
+    // Repetitive tool traffic against MCPs that never opted into the
+    // result cache
+    let cache_candidates: Vec<CacheCandidateRow> = sqlx::query_as(
+        r#"
+        SELECT m.id as mcp_id, m.name as mcp_name,
+               COUNT(*) as calls,
+               COUNT(DISTINCT pl.tool_name) as distinct_tools
+        FROM mcp_proxy_logs pl
+        JOIN api_keys ak ON pl.api_key_id = ak.id
+        JOIN mcp_instances m ON pl.mcp_id = m.id
+        WHERE ak.org_id = $1
+          AND pl.method = 'tools/call'
+          AND pl.status = 'success'
+          AND pl.created_at >= $2
+          AND m.config->'cache' IS NULL
+        GROUP BY m.id, m.name
+        HAVING COUNT(*) >= $3 AND COUNT(*) >= COUNT(DISTINCT pl.tool_name) * $4
+        ORDER BY COUNT(*) DESC
+        "#,
+    )
+    .bind(org_id)
+    .bind(since)
+    .bind(CACHE_MIN_CALLS)
+    .bind(CACHE_REPEAT_FACTOR)
+    .fetch_all(&state.pool)
+    .await?;
+
+    for row in cache_candidates {
+        recommendations.push(Recommendation {
+            key: format!("enable_caching:{}", row.mcp_id),
+            kind: "enable_caching".to_string(),
+            severity: "info".to_string(),
+            title: format!("Enable result caching for {}", row.mcp_name),
+            description: format!(
+                "{} received {} tool calls across only {} distinct tools in the last {} days. \
+                 Adding a `cache` block to its config could serve repeated calls from cache \
+                 and cut upstream load.",
+                row.mcp_name, row.calls, row.distinct_tools, ANALYSIS_WINDOW_DAYS
+            ),
+            mcp_id: Some(row.mcp_id),
+            mcp_name: Some(row.mcp_name),
+        });
+    }
+
+    // Error rates and latency per MCP
+    let health: Vec<McpHealthRow> = sqlx::query_as(
+        r#"
+        SELECT m.id as mcp_id, m.name as mcp_name,
+               COUNT(*) as requests,
+               COUNT(*) FILTER (WHERE pl.status <> 'success') as errors,
+               AVG(pl.latency_ms)::double precision as avg_latency_ms
+        FROM mcp_proxy_logs pl
+        JOIN api_keys ak ON pl.api_key_id = ak.id
+        JOIN mcp_instances m ON pl.mcp_id = m.id
+        WHERE ak.org_id = $1
+          AND pl.created_at >= $2
+        GROUP BY m.id, m.name
+        HAVING COUNT(*) >= $3
+        ORDER BY COUNT(*) DESC
+        "#,
+    )
+    .bind(org_id)
+    .bind(since)
+    .bind(MIN_REQUESTS_FOR_SIGNAL)
+    .fetch_all(&state.pool)
+    .await?;
+
+    for row in health {
+        let error_pct = 100.0 * row.errors as f64 / row.requests as f64;
+        if error_pct >= ERROR_RATE_WARN_PCT {
+            recommendations.push(Recommendation {
+                key: format!("high_error_rate:{}", row.mcp_id),
+                kind: "high_error_rate".to_string(),
+                severity: "warning".to_string(),
+                title: format!("{} has a {:.0}% error rate", row.mcp_name, error_pct),
+                description: format!(
+                    "{} of {}'s {} requests failed in the last {} days. \
+                     Check its credentials, upstream availability, and recent error messages.",
+                    row.errors, row.mcp_name, row.requests, ANALYSIS_WINDOW_DAYS
+                ),
+                mcp_id: Some(row.mcp_id),
+                mcp_name: Some(row.mcp_name.clone()),
+            });
+        }
+
+        if let Some(avg_latency) = row.avg_latency_ms {
+            if avg_latency >= HIGH_LATENCY_MS {
+                recommendations.push(Recommendation {
+                    key: format!("high_latency:{}", row.mcp_id),
+                    kind: "high_latency".to_string(),
+                    severity: "info".to_string(),
+                    title: format!("{} is slow to respond", row.mcp_name),
+                    description: format!(
+                        "{} averaged {:.0}ms per request over the last {} days. \
+                         Consider caching, a closer deployment region, or checking the \
+                         upstream server's load.",
+                        row.mcp_name, avg_latency, ANALYSIS_WINDOW_DAYS
+                    ),
+                    mcp_id: Some(row.mcp_id),
+                    mcp_name: Some(row.mcp_name),
+                });
+            }
+        }
+    }
+
+    // Quota pressure (needs the billing service; skipped when disabled)
+    #[cfg(feature = "billing")]
+    if let Some(billing) = state.billing.as_ref() {
+        if let Ok(usage) = billing.usage.get_billing_period_usage(org_id).await {
+            if usage.requests_limit != u64::MAX && usage.percentage_used >= QUOTA_WARN_PCT {
+                if let Some(next_tier) = next_tier(usage.tier) {
+                    recommendations.push(Recommendation {
+                        key: format!("quota_approaching:{}", usage.tier),
+                        kind: "quota_approaching".to_string(),
+                        severity: "warning".to_string(),
+                        title: format!(
+                            "You've used {:.0}% of your monthly requests",
+                            usage.percentage_used
+                        ),
+                        description: format!(
+                            "{} of {} included requests are used this billing period. \
+                             Consider upgrading to the {} tier to avoid throttling or overage.",
+                            usage.requests_used, usage.requests_limit, next_tier
+                        ),
+                        mcp_id: None,
+                        mcp_name: None,
+                    });
+                }
+            }
+        }
+    }
+
+    // Hide findings with an active dismissal
+    let dismissed: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT recommendation_key FROM recommendation_dismissals
+        WHERE org_id = $1 AND dismissed_at > NOW() - make_interval(days => $2)
+        "#,
+    )
+    .bind(org_id)
+    .bind(DISMISSAL_TTL_DAYS as i32)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let before = recommendations.len();
+    recommendations.retain(|r| !dismissed.contains(&r.key));
+    let dismissed_count = before - recommendations.len();
+
+    Ok(Json(RecommendationsResponse {
+        recommendations,
+        dismissed_count,
+        analysis_window_days: ANALYSIS_WINDOW_DAYS,
+    }))
+}
+
+/// Dismiss a recommendation by key
+///
+/// Dismissals are keyed so the same finding stays hidden while new
+/// findings (other MCPs, other kinds) still surface. After
+/// [`DISMISSAL_TTL_DAYS`] the finding re-appears if it still holds.
+pub async fn dismiss_recommendation(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(key): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    if key.is_empty() || key.len() > 200 {
+        return Err(ApiError::Validation(
+            "recommendation key must be between 1 and 200 characters".to_string(),
+        ));
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO recommendation_dismissals (org_id, recommendation_key, dismissed_by)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (org_id, recommendation_key)
+        DO UPDATE SET dismissed_at = NOW(), dismissed_by = EXCLUDED.dismissed_by
+        "#,
+    )
+    .bind(org_id)
+    .bind(&key)
+    .bind(auth_user.user_id)
+    .execute(&state.pool)
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Restore a dismissed recommendation
+pub async fn undismiss_recommendation(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(key): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let result = sqlx::query(
+        "DELETE FROM recommendation_dismissals WHERE org_id = $1 AND recommendation_key = $2",
+    )
+    .bind(org_id)
+    .bind(&key)
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// The next tier up, for quota suggestions (None once there is nowhere
+/// to go)
+#[cfg(feature = "billing")]
+fn next_tier(tier: plexmcp_shared::SubscriptionTier) -> Option<&'static str> {
+    use plexmcp_shared::SubscriptionTier;
+    match tier {
+        SubscriptionTier::Free | SubscriptionTier::Starter => Some("Pro"),
+        SubscriptionTier::Pro => Some("Team"),
+        SubscriptionTier::Team => Some("Enterprise"),
+        SubscriptionTier::Enterprise => None,
+    }
+}
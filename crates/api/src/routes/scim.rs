@@ -0,0 +1,1195 @@
+//! SCIM 2.0 provisioning for enterprise orgs
+//!
+//! Identity providers (Okta, Azure AD) manage team members through the
+//! /scim/v2 router: Users map onto the users table plus
+//! organization_members, Groups are read-mostly views of the four org
+//! roles. Requests authenticate with an org-issued bearer token (see
+//! scim_tokens), managed by owners/admins under /org/scim/tokens.
+//! Deprovisioning is a soft deactivation - the membership is suspended,
+//! nothing the member created is destroyed.
+
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    auth::{generate_impossible_hash, AuthUser},
+    error::{ApiError, ApiResult},
+    state::AppState,
+};
+
+const USER_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:User";
+const GROUP_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:Group";
+const LIST_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:ListResponse";
+const ERROR_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:Error";
+
+/// Default and maximum page size for list responses
+const DEFAULT_PAGE_SIZE: i64 = 100;
+const MAX_PAGE_SIZE: i64 = 200;
+
+/// Org roles exposed as SCIM groups: (group id, displayName, role)
+const ROLE_GROUPS: &[(&str, &str, &str)] = &[
+    ("owners", "Owners", "owner"),
+    ("administrators", "Administrators", "admin"),
+    ("members", "Members", "member"),
+    ("viewers", "Viewers", "viewer"),
+];
+
+/// Suspension reason recorded when an IdP deprovisions a member
+const SCIM_SUSPENSION_REASON: &str = "scim_deprovisioned";
+
+// =============================================================================
+// SCIM Error Responses
+// =============================================================================
+
+/// Error surfaced to the identity provider in the SCIM error schema
+/// (RFC 7644 section 3.12) rather than our normal API error shape
+#[derive(Debug)]
+pub struct ScimError {
+    status: StatusCode,
+    detail: String,
+}
+
+impl ScimError {
+    fn unauthorized() -> Self {
+        Self {
+            status: StatusCode::UNAUTHORIZED,
+            detail: "Invalid or missing bearer token".to_string(),
+        }
+    }
+
+    fn not_found() -> Self {
+        Self {
+            status: StatusCode::NOT_FOUND,
+            detail: "Resource not found".to_string(),
+        }
+    }
+
+    fn bad_request(detail: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            detail: detail.into(),
+        }
+    }
+
+    fn conflict(detail: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::CONFLICT,
+            detail: detail.into(),
+        }
+    }
+
+    fn internal() -> Self {
+        Self {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            detail: "Internal server error".to_string(),
+        }
+    }
+}
+
+impl From<sqlx::Error> for ScimError {
+    fn from(e: sqlx::Error) -> Self {
+        tracing::error!(error = %e, "Database error in SCIM handler");
+        Self {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            detail: "Internal server error".to_string(),
+        }
+    }
+}
+
+impl IntoResponse for ScimError {
+    fn into_response(self) -> Response {
+        let body = json!({
+            "schemas": [ERROR_SCHEMA],
+            "status": self.status.as_u16().to_string(),
+            "detail": self.detail,
+        });
+        (self.status, Json(body)).into_response()
+    }
+}
+
+// =============================================================================
+// Bearer-Token Authentication
+// =============================================================================
+
+/// Org resolved from a valid SCIM bearer token
+struct ScimContext {
+    org_id: Uuid,
+    token_id: Uuid,
+}
+
+/// Authenticate a /scim/v2 request from its Authorization header
+async fn authenticate(state: &AppState, headers: &HeaderMap) -> Result<ScimContext, ScimError> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .ok_or_else(ScimError::unauthorized)?;
+
+    // Reject forged tokens before touching the database
+    if !state
+        .api_key_manager
+        .validate_scim_token(token)
+        .unwrap_or(false)
+    {
+        return Err(ScimError::unauthorized());
+    }
+
+    let token_hash = state.api_key_manager.hash_key(token);
+
+    let row: Option<(Uuid, Uuid)> = sqlx::query_as(
+        "SELECT id, org_id FROM scim_tokens WHERE token_hash = $1 AND revoked_at IS NULL",
+    )
+    .bind(&token_hash)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    let (token_id, org_id) = row.ok_or_else(ScimError::unauthorized)?;
+
+    // Usage bookkeeping - fire and forget
+    let pool = state.pool.clone();
+    tokio::spawn(async move {
+        let _ = sqlx::query(
+            "UPDATE scim_tokens SET last_used_at = NOW(), request_count = request_count + 1 WHERE id = $1",
+        )
+        .bind(token_id)
+        .execute(&pool)
+        .await;
+    });
+
+    Ok(ScimContext { org_id, token_id })
+}
+
+// =============================================================================
+// SCIM Resource Types
+// =============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct ScimMeta {
+    #[serde(rename = "resourceType")]
+    pub resource_type: &'static str,
+    #[serde(
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub created: Option<OffsetDateTime>,
+    #[serde(
+        rename = "lastModified",
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_modified: Option<OffsetDateTime>,
+    pub location: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScimEmail {
+    pub value: String,
+    pub primary: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScimRoleRef {
+    pub value: String,
+    pub primary: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScimUser {
+    pub schemas: Vec<&'static str>,
+    pub id: Uuid,
+    #[serde(rename = "externalId", skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    #[serde(rename = "userName")]
+    pub user_name: String,
+    pub active: bool,
+    pub emails: Vec<ScimEmail>,
+    pub roles: Vec<ScimRoleRef>,
+    pub meta: ScimMeta,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScimMemberRef {
+    pub value: Uuid,
+    pub display: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScimGroup {
+    pub schemas: Vec<&'static str>,
+    pub id: String,
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    pub members: Vec<ScimMemberRef>,
+    pub meta: ScimMeta,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScimListResponse<T: Serialize> {
+    pub schemas: Vec<&'static str>,
+    #[serde(rename = "totalResults")]
+    pub total_results: i64,
+    #[serde(rename = "startIndex")]
+    pub start_index: i64,
+    #[serde(rename = "itemsPerPage")]
+    pub items_per_page: i64,
+    #[serde(rename = "Resources")]
+    pub resources: Vec<T>,
+}
+
+// =============================================================================
+// Request Types
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct ScimRoleValue {
+    pub value: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimUserPayload {
+    pub user_name: Option<String>,
+    pub external_id: Option<String>,
+    /// Azure AD sends booleans as the strings "True"/"False"
+    pub active: Option<Value>,
+    pub roles: Option<Vec<ScimRoleValue>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScimPatchRequest {
+    #[serde(rename = "Operations")]
+    pub operations: Vec<ScimPatchOp>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScimPatchOp {
+    pub op: String,
+    pub path: Option<String>,
+    pub value: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScimListQuery {
+    pub filter: Option<String>,
+    #[serde(rename = "startIndex")]
+    pub start_index: Option<i64>,
+    pub count: Option<i64>,
+}
+
+// =============================================================================
+// Database Row Types
+// =============================================================================
+
+#[derive(Debug, FromRow)]
+struct ScimUserRow {
+    id: Uuid,
+    email: String,
+    role: String,
+    created_at: OffsetDateTime,
+    updated_at: OffsetDateTime,
+    member_status: Option<String>,
+    scim_external_id: Option<String>,
+}
+
+const USER_COLUMNS: &str = "u.id, u.email, u.role, u.created_at, u.updated_at, \
+     om.status AS member_status, om.scim_external_id";
+const USER_FROM: &str =
+    "FROM users u LEFT JOIN organization_members om ON om.user_id = u.id AND om.org_id = u.org_id";
+
+impl ScimUserRow {
+    fn into_resource(self) -> ScimUser {
+        // Suspended members are inactive; a missing membership row
+        // (pre-dating organization_members) counts as active
+        let active = !matches!(self.member_status.as_deref(), Some("suspended"));
+        ScimUser {
+            schemas: vec![USER_SCHEMA],
+            id: self.id,
+            external_id: self.scim_external_id,
+            user_name: self.email.clone(),
+            active,
+            emails: vec![ScimEmail {
+                value: self.email,
+                primary: true,
+            }],
+            roles: vec![ScimRoleRef {
+                value: self.role,
+                primary: true,
+            }],
+            meta: ScimMeta {
+                resource_type: "User",
+                created: Some(self.created_at),
+                last_modified: Some(self.updated_at),
+                location: format!("/scim/v2/Users/{}", self.id),
+            },
+        }
+    }
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Parse a simple `attribute eq "value"` SCIM filter; returns the
+/// attribute lowercased and the unquoted value
+fn parse_eq_filter(filter: &str) -> Option<(String, String)> {
+    let (attr, value) = filter.split_once(" eq ")?;
+    let attr = attr.trim().to_lowercase();
+    let value = value.trim().trim_matches('"').to_string();
+    if attr.is_empty() || value.is_empty() {
+        return None;
+    }
+    Some((attr, value))
+}
+
+/// Interpret a SCIM active value; IdPs send true, "True" or "False"
+fn parse_scim_bool(value: &Value) -> Option<bool> {
+    match value {
+        Value::Bool(b) => Some(*b),
+        Value::String(s) => match s.to_lowercase().as_str() {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Map the first entry of a SCIM roles array onto an org role
+fn role_from_payload(roles: &[ScimRoleValue]) -> Result<Option<String>, ScimError> {
+    let Some(first) = roles.first() else {
+        return Ok(None);
+    };
+    let role = first.value.trim().to_lowercase();
+    if !["owner", "admin", "member", "viewer"].contains(&role.as_str()) {
+        return Err(ScimError::bad_request(format!(
+            "Unknown role: {}. Must be one of: owner, admin, member, viewer",
+            first.value
+        )));
+    }
+    Ok(Some(role))
+}
+
+async fn fetch_user(
+    pool: &sqlx::PgPool,
+    org_id: Uuid,
+    user_id: Uuid,
+) -> Result<ScimUserRow, ScimError> {
+    let row: Option<ScimUserRow> = sqlx::query_as(&format!(
+        "SELECT {} {} WHERE u.org_id = $1 AND u.id = $2",
+        USER_COLUMNS, USER_FROM
+    ))
+    .bind(org_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    row.ok_or_else(ScimError::not_found)
+}
+
+/// Suspend or reactivate a membership, creating the row if the user
+/// pre-dates organization_members
+async fn set_member_active(
+    pool: &sqlx::PgPool,
+    org_id: Uuid,
+    user_id: Uuid,
+    role: &str,
+    active: bool,
+) -> Result<(), ScimError> {
+    if active {
+        sqlx::query(
+            r#"
+            INSERT INTO organization_members (id, org_id, user_id, role, created_at, status)
+            VALUES ($1, $2, $3, $4, NOW(), 'active')
+            ON CONFLICT (org_id, user_id) DO UPDATE
+            SET status = 'active', suspended_at = NULL, suspended_reason = NULL
+            "#,
+        )
+    } else {
+        sqlx::query(
+            r#"
+            INSERT INTO organization_members
+                (id, org_id, user_id, role, created_at, status, suspended_at, suspended_reason)
+            VALUES ($1, $2, $3, $4, NOW(), 'suspended', NOW(), 'scim_deprovisioned')
+            ON CONFLICT (org_id, user_id) DO UPDATE
+            SET status = 'suspended', suspended_at = NOW(),
+                suspended_reason = 'scim_deprovisioned'
+            "#,
+        )
+    }
+    .bind(Uuid::new_v4())
+    .bind(org_id)
+    .bind(user_id)
+    .bind(role)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Change a member's role, refusing to demote the only owner
+async fn update_role(
+    pool: &sqlx::PgPool,
+    org_id: Uuid,
+    user_id: Uuid,
+    new_role: &str,
+) -> Result<(), ScimError> {
+    let current: Option<String> =
+        sqlx::query_scalar("SELECT role FROM users WHERE id = $1 AND org_id = $2")
+            .bind(user_id)
+            .bind(org_id)
+            .fetch_optional(pool)
+            .await?;
+
+    let current = current.ok_or_else(ScimError::not_found)?;
+    if current == new_role {
+        return Ok(());
+    }
+
+    if current == "owner" {
+        let owner_count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE org_id = $1 AND role = 'owner'")
+                .bind(org_id)
+                .fetch_one(pool)
+                .await?;
+        if owner_count <= 1 {
+            return Err(ScimError::bad_request("Cannot demote the only owner"));
+        }
+    }
+
+    sqlx::query("UPDATE users SET role = $1, updated_at = NOW() WHERE id = $2 AND org_id = $3")
+        .bind(new_role)
+        .bind(user_id)
+        .bind(org_id)
+        .execute(pool)
+        .await?;
+    sqlx::query("UPDATE organization_members SET role = $1 WHERE org_id = $2 AND user_id = $3")
+        .bind(new_role)
+        .bind(org_id)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+fn group_by_id(group_id: &str) -> Option<(&'static str, &'static str, &'static str)> {
+    ROLE_GROUPS
+        .iter()
+        .find(|(id, _, _)| *id == group_id)
+        .copied()
+}
+
+async fn group_resource(
+    pool: &sqlx::PgPool,
+    org_id: Uuid,
+    group: (&'static str, &'static str, &'static str),
+) -> Result<ScimGroup, ScimError> {
+    let (id, display_name, role) = group;
+
+    let members: Vec<(Uuid, String)> = sqlx::query_as(
+        "SELECT id, email FROM users WHERE org_id = $1 AND role = $2 ORDER BY email ASC",
+    )
+    .bind(org_id)
+    .bind(role)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(ScimGroup {
+        schemas: vec![GROUP_SCHEMA],
+        id: id.to_string(),
+        display_name: display_name.to_string(),
+        members: members
+            .into_iter()
+            .map(|(value, display)| ScimMemberRef { value, display })
+            .collect(),
+        meta: ScimMeta {
+            resource_type: "Group",
+            created: None,
+            last_modified: None,
+            location: format!("/scim/v2/Groups/{}", id),
+        },
+    })
+}
+
+// =============================================================================
+// SCIM Handlers (bearer-token auth)
+// =============================================================================
+
+/// GET /scim/v2/ServiceProviderConfig - advertised capabilities
+pub async fn service_provider_config(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<Value>, ScimError> {
+    authenticate(&state, &headers).await?;
+
+    Ok(Json(json!({
+        "schemas": ["urn:ietf:params:scim:schemas:core:2.0:ServiceProviderConfig"],
+        "patch": { "supported": true },
+        "bulk": { "supported": false, "maxOperations": 0, "maxPayloadSize": 0 },
+        "filter": { "supported": true, "maxResults": MAX_PAGE_SIZE },
+        "changePassword": { "supported": false },
+        "sort": { "supported": false },
+        "etag": { "supported": false },
+        "authenticationSchemes": [{
+            "type": "oauthbearertoken",
+            "name": "OAuth Bearer Token",
+            "description": "Org-issued SCIM provisioning token"
+        }]
+    })))
+}
+
+/// GET /scim/v2/Users - list users, with `userName eq` / `externalId eq` filters
+pub async fn list_users(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<ScimListQuery>,
+) -> Result<Json<ScimListResponse<ScimUser>>, ScimError> {
+    let ctx = authenticate(&state, &headers).await?;
+
+    let start_index = query.start_index.unwrap_or(1).max(1);
+    let count = query
+        .count
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(0, MAX_PAGE_SIZE);
+    let offset = start_index - 1;
+
+    // Okta filters on userName during provisioning; Azure AD on externalId
+    let filter_clause = match query.filter.as_deref() {
+        None => None,
+        Some(filter) => match parse_eq_filter(filter) {
+            Some((attr, value)) if attr == "username" => {
+                Some(("AND LOWER(u.email) = LOWER($4)", value))
+            }
+            Some((attr, value)) if attr == "externalid" => {
+                Some(("AND om.scim_external_id = $4", value))
+            }
+            _ => {
+                return Err(ScimError::bad_request(
+                    "Only userName eq and externalId eq filters are supported",
+                ))
+            }
+        },
+    };
+
+    let (total_results, rows): (i64, Vec<ScimUserRow>) = match &filter_clause {
+        Some((clause, value)) => {
+            let total = sqlx::query_scalar(&format!(
+                "SELECT COUNT(*) {} WHERE u.org_id = $1 {}",
+                USER_FROM,
+                clause.replace("$4", "$2")
+            ))
+            .bind(ctx.org_id)
+            .bind(value)
+            .fetch_one(&state.pool)
+            .await?;
+
+            let rows = sqlx::query_as(&format!(
+                "SELECT {} {} WHERE u.org_id = $1 {} ORDER BY u.created_at ASC LIMIT $2 OFFSET $3",
+                USER_COLUMNS, USER_FROM, clause
+            ))
+            .bind(ctx.org_id)
+            .bind(count)
+            .bind(offset)
+            .bind(value)
+            .fetch_all(&state.pool)
+            .await?;
+
+            (total, rows)
+        }
+        None => {
+            let total = sqlx::query_scalar("SELECT COUNT(*) FROM users u WHERE u.org_id = $1")
+                .bind(ctx.org_id)
+                .fetch_one(&state.pool)
+                .await?;
+
+            let rows = sqlx::query_as(&format!(
+                "SELECT {} {} WHERE u.org_id = $1 ORDER BY u.created_at ASC LIMIT $2 OFFSET $3",
+                USER_COLUMNS, USER_FROM
+            ))
+            .bind(ctx.org_id)
+            .bind(count)
+            .bind(offset)
+            .fetch_all(&state.pool)
+            .await?;
+
+            (total, rows)
+        }
+    };
+
+    let resources: Vec<ScimUser> = rows.into_iter().map(ScimUserRow::into_resource).collect();
+
+    Ok(Json(ScimListResponse {
+        schemas: vec![LIST_SCHEMA],
+        total_results,
+        start_index,
+        items_per_page: resources.len() as i64,
+        resources,
+    }))
+}
+
+/// GET /scim/v2/Users/:user_id
+pub async fn get_user(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<ScimUser>, ScimError> {
+    let ctx = authenticate(&state, &headers).await?;
+    let row = fetch_user(&state.pool, ctx.org_id, user_id).await?;
+    Ok(Json(row.into_resource()))
+}
+
+/// POST /scim/v2/Users - provision a new member
+pub async fn create_user(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<ScimUserPayload>,
+) -> Result<(StatusCode, Json<ScimUser>), ScimError> {
+    let ctx = authenticate(&state, &headers).await?;
+
+    let email = payload
+        .user_name
+        .as_deref()
+        .map(|u| u.trim().to_lowercase())
+        .filter(|u| u.contains('@'))
+        .ok_or_else(|| ScimError::bad_request("userName must be an email address"))?;
+
+    let role = payload
+        .roles
+        .as_deref()
+        .map(role_from_payload)
+        .transpose()?
+        .flatten()
+        .unwrap_or_else(|| "member".to_string());
+
+    // Emails are globally unique; per SCIM the IdP resolves a 409 by
+    // filtering for the user and updating it instead
+    let existing: Option<Uuid> = sqlx::query_scalar("SELECT id FROM users WHERE email = $1")
+        .bind(&email)
+        .fetch_optional(&state.pool)
+        .await?;
+    if existing.is_some() {
+        return Err(ScimError::conflict(format!(
+            "User {} already exists",
+            email
+        )));
+    }
+
+    // SCIM-provisioned users authenticate through their IdP (SSO), never
+    // with a local password
+    let password_hash = generate_impossible_hash().map_err(|_| ScimError::internal())?;
+
+    let user_id = Uuid::new_v4();
+    let mut tx = state.pool.begin().await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO users (id, org_id, email, password_hash, role, email_verified)
+        VALUES ($1, $2, $3, $4, $5, TRUE)
+        "#,
+    )
+    .bind(user_id)
+    .bind(ctx.org_id)
+    .bind(&email)
+    .bind(&password_hash)
+    .bind(&role)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO organization_members
+            (id, org_id, user_id, role, created_at, status, scim_external_id)
+        VALUES ($1, $2, $3, $4, NOW(), 'active', $5)
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(ctx.org_id)
+    .bind(user_id)
+    .bind(&role)
+    .bind(&payload.external_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    tracing::info!(
+        org_id = %ctx.org_id,
+        user_id = %user_id,
+        token_id = %ctx.token_id,
+        role = %role,
+        "User provisioned via SCIM"
+    );
+
+    let row = fetch_user(&state.pool, ctx.org_id, user_id).await?;
+    Ok((StatusCode::CREATED, Json(row.into_resource())))
+}
+
+/// PUT /scim/v2/Users/:user_id - replace active state, role and externalId
+pub async fn replace_user(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(user_id): Path<Uuid>,
+    Json(payload): Json<ScimUserPayload>,
+) -> Result<Json<ScimUser>, ScimError> {
+    let ctx = authenticate(&state, &headers).await?;
+    let row = fetch_user(&state.pool, ctx.org_id, user_id).await?;
+
+    if let Some(role) = payload
+        .roles
+        .as_deref()
+        .map(role_from_payload)
+        .transpose()?
+        .flatten()
+    {
+        update_role(&state.pool, ctx.org_id, user_id, &role).await?;
+    }
+
+    if let Some(external_id) = &payload.external_id {
+        sqlx::query(
+            "UPDATE organization_members SET scim_external_id = $1 WHERE org_id = $2 AND user_id = $3",
+        )
+        .bind(external_id)
+        .bind(ctx.org_id)
+        .bind(user_id)
+        .execute(&state.pool)
+        .await?;
+    }
+
+    if let Some(active) = payload.active.as_ref().and_then(parse_scim_bool) {
+        set_member_active(&state.pool, ctx.org_id, user_id, &row.role, active).await?;
+    }
+
+    let row = fetch_user(&state.pool, ctx.org_id, user_id).await?;
+    Ok(Json(row.into_resource()))
+}
+
+/// PATCH /scim/v2/Users/:user_id - partial update (active, roles, externalId)
+pub async fn patch_user(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(user_id): Path<Uuid>,
+    Json(payload): Json<ScimPatchRequest>,
+) -> Result<Json<ScimUser>, ScimError> {
+    let ctx = authenticate(&state, &headers).await?;
+    let row = fetch_user(&state.pool, ctx.org_id, user_id).await?;
+
+    for op in &payload.operations {
+        let op_name = op.op.to_lowercase();
+        if !matches!(op_name.as_str(), "add" | "replace" | "remove") {
+            return Err(ScimError::bad_request(format!("Unsupported op: {}", op.op)));
+        }
+
+        match op.path.as_deref().map(str::to_lowercase).as_deref() {
+            Some("active") => {
+                let active = op
+                    .value
+                    .as_ref()
+                    .and_then(parse_scim_bool)
+                    .ok_or_else(|| ScimError::bad_request("active must be a boolean"))?;
+                set_member_active(&state.pool, ctx.org_id, user_id, &row.role, active).await?;
+            }
+            Some("externalid") => {
+                let external_id = if op_name == "remove" {
+                    None
+                } else {
+                    op.value.as_ref().and_then(|v| v.as_str()).map(String::from)
+                };
+                sqlx::query(
+                    "UPDATE organization_members SET scim_external_id = $1 WHERE org_id = $2 AND user_id = $3",
+                )
+                .bind(external_id)
+                .bind(ctx.org_id)
+                .bind(user_id)
+                .execute(&state.pool)
+                .await?;
+            }
+            Some("roles") => {
+                let roles: Vec<ScimRoleValue> = op
+                    .value
+                    .clone()
+                    .map(serde_json::from_value)
+                    .transpose()
+                    .map_err(|_| ScimError::bad_request("Invalid roles value"))?
+                    .unwrap_or_default();
+                if let Some(role) = role_from_payload(&roles)? {
+                    update_role(&state.pool, ctx.org_id, user_id, &role).await?;
+                }
+            }
+            None => {
+                // Okta sends replace without a path and the attributes
+                // as a value object
+                let Some(Value::Object(attrs)) = &op.value else {
+                    return Err(ScimError::bad_request("Operation value must be an object"));
+                };
+                if let Some(active) = attrs.get("active").and_then(parse_scim_bool) {
+                    set_member_active(&state.pool, ctx.org_id, user_id, &row.role, active).await?;
+                }
+                if let Some(external_id) = attrs.get("externalId").and_then(|v| v.as_str()) {
+                    sqlx::query(
+                        "UPDATE organization_members SET scim_external_id = $1 WHERE org_id = $2 AND user_id = $3",
+                    )
+                    .bind(external_id)
+                    .bind(ctx.org_id)
+                    .bind(user_id)
+                    .execute(&state.pool)
+                    .await?;
+                }
+            }
+            // Attributes we don't map (displayName, name.*) are ignored
+            Some(_) => {}
+        }
+    }
+
+    let row = fetch_user(&state.pool, ctx.org_id, user_id).await?;
+    Ok(Json(row.into_resource()))
+}
+
+/// DELETE /scim/v2/Users/:user_id - soft-deactivate the membership
+pub async fn delete_user(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(user_id): Path<Uuid>,
+) -> Result<StatusCode, ScimError> {
+    let ctx = authenticate(&state, &headers).await?;
+    let row = fetch_user(&state.pool, ctx.org_id, user_id).await?;
+
+    set_member_active(&state.pool, ctx.org_id, user_id, &row.role, false).await?;
+
+    tracing::info!(
+        org_id = %ctx.org_id,
+        user_id = %user_id,
+        token_id = %ctx.token_id,
+        reason = SCIM_SUSPENSION_REASON,
+        "User deprovisioned via SCIM"
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /scim/v2/Groups - the four org roles as groups
+pub async fn list_groups(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ScimListResponse<ScimGroup>>, ScimError> {
+    let ctx = authenticate(&state, &headers).await?;
+
+    let mut resources = Vec::with_capacity(ROLE_GROUPS.len());
+    for group in ROLE_GROUPS {
+        resources.push(group_resource(&state.pool, ctx.org_id, *group).await?);
+    }
+
+    Ok(Json(ScimListResponse {
+        schemas: vec![LIST_SCHEMA],
+        total_results: resources.len() as i64,
+        start_index: 1,
+        items_per_page: resources.len() as i64,
+        resources,
+    }))
+}
+
+/// GET /scim/v2/Groups/:group_id
+pub async fn get_group(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(group_id): Path<String>,
+) -> Result<Json<ScimGroup>, ScimError> {
+    let ctx = authenticate(&state, &headers).await?;
+    let group = group_by_id(&group_id).ok_or_else(ScimError::not_found)?;
+    Ok(Json(group_resource(&state.pool, ctx.org_id, group).await?))
+}
+
+/// PATCH /scim/v2/Groups/:group_id - group membership drives role mapping:
+/// adding a member assigns the group's role, removing one demotes back to
+/// member (removal from Members itself is a no-op)
+pub async fn patch_group(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(group_id): Path<String>,
+    Json(payload): Json<ScimPatchRequest>,
+) -> Result<Json<ScimGroup>, ScimError> {
+    let ctx = authenticate(&state, &headers).await?;
+    let group = group_by_id(&group_id).ok_or_else(ScimError::not_found)?;
+    let (_, _, group_role) = group;
+
+    for op in &payload.operations {
+        match op.op.to_lowercase().as_str() {
+            "add" | "replace" => {
+                for member_id in member_ids_from_value(op.value.as_ref())? {
+                    update_role(&state.pool, ctx.org_id, member_id, group_role).await?;
+                }
+            }
+            "remove" => {
+                if group_role == "member" {
+                    continue;
+                }
+                let ids = match op.path.as_deref().and_then(member_id_from_path) {
+                    Some(id) => vec![id],
+                    None => member_ids_from_value(op.value.as_ref())?,
+                };
+                for member_id in ids {
+                    let row = fetch_user(&state.pool, ctx.org_id, member_id).await?;
+                    if row.role == group_role {
+                        update_role(&state.pool, ctx.org_id, member_id, "member").await?;
+                    }
+                }
+            }
+            other => {
+                return Err(ScimError::bad_request(format!("Unsupported op: {}", other)));
+            }
+        }
+    }
+
+    Ok(Json(group_resource(&state.pool, ctx.org_id, group).await?))
+}
+
+/// Extract member UUIDs from a patch value like [{"value": "<uuid>"}]
+fn member_ids_from_value(value: Option<&Value>) -> Result<Vec<Uuid>, ScimError> {
+    let Some(Value::Array(entries)) = value else {
+        return Err(ScimError::bad_request(
+            "members value must be an array of {value} objects",
+        ));
+    };
+    entries
+        .iter()
+        .map(|entry| {
+            entry
+                .get("value")
+                .and_then(|v| v.as_str())
+                .and_then(|s| Uuid::parse_str(s).ok())
+                .ok_or_else(|| ScimError::bad_request("Invalid member value"))
+        })
+        .collect()
+}
+
+/// Extract the UUID from a remove path like `members[value eq "<uuid>"]`
+fn member_id_from_path(path: &str) -> Option<Uuid> {
+    let start = path.find('"')? + 1;
+    let end = path.rfind('"')?;
+    Uuid::parse_str(path.get(start..end)?).ok()
+}
+
+// =============================================================================
+// Token Management (JWT auth, owner/admin only)
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct CreateScimTokenRequest {
+    /// Human-readable purpose, e.g. "Okta provisioning"
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScimTokenResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub token_prefix: String,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_used_at: Option<OffsetDateTime>,
+    pub request_count: i64,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub revoked_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreatedScimTokenResponse {
+    /// The full token - shown once, never retrievable again
+    pub token: String,
+    #[serde(flatten)]
+    pub details: ScimTokenResponse,
+}
+
+#[derive(Debug, FromRow)]
+struct ScimTokenRow {
+    id: Uuid,
+    name: String,
+    token_prefix: String,
+    last_used_at: Option<OffsetDateTime>,
+    request_count: i64,
+    revoked_at: Option<OffsetDateTime>,
+    created_at: OffsetDateTime,
+}
+
+impl From<ScimTokenRow> for ScimTokenResponse {
+    fn from(row: ScimTokenRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            token_prefix: row.token_prefix,
+            last_used_at: row.last_used_at,
+            request_count: row.request_count,
+            revoked_at: row.revoked_at,
+            created_at: row.created_at,
+        }
+    }
+}
+
+/// GET /org/scim/tokens - list the org's SCIM tokens
+pub async fn list_scim_tokens(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<Vec<ScimTokenResponse>>> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let rows: Vec<ScimTokenRow> = sqlx::query_as(
+        r#"
+        SELECT id, name, token_prefix, last_used_at, request_count, revoked_at, created_at
+        FROM scim_tokens
+        WHERE org_id = $1
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(rows.into_iter().map(Into::into).collect()))
+}
+
+/// POST /org/scim/tokens - issue a SCIM provisioning token (enterprise only)
+///
+/// The full token is returned once in the response and never retrievable
+/// again; only its hash is stored.
+pub async fn create_scim_token(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<CreateScimTokenRequest>,
+) -> ApiResult<Json<CreatedScimTokenResponse>> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let tier: Option<String> = sqlx::query_scalar(
+        "SELECT COALESCE(subscription_tier, 'free') FROM organizations WHERE id = $1",
+    )
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    if tier.as_deref() != Some("enterprise") {
+        return Err(ApiError::BadRequest(
+            "SCIM provisioning requires the enterprise tier".to_string(),
+        ));
+    }
+
+    let name = payload.name.trim();
+    if name.is_empty() || name.len() > 100 {
+        return Err(ApiError::Validation(
+            "Token name must be 1-100 characters".to_string(),
+        ));
+    }
+
+    let (full_token, token_hash, token_prefix) =
+        state.api_key_manager.generate_scim_token().map_err(|e| {
+            tracing::error!(error = ?e, "Failed to generate SCIM token");
+            ApiError::Internal
+        })?;
+
+    let row: ScimTokenRow = sqlx::query_as(
+        r#"
+        INSERT INTO scim_tokens (org_id, name, token_hash, token_prefix, created_by)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, name, token_prefix, last_used_at, request_count, revoked_at, created_at
+        "#,
+    )
+    .bind(org_id)
+    .bind(name)
+    .bind(&token_hash)
+    .bind(&token_prefix)
+    .bind(auth_user.user_id)
+    .fetch_one(&state.pool)
+    .await?;
+
+    tracing::info!(
+        token_id = %row.id,
+        org_id = %org_id,
+        "SCIM provisioning token issued"
+    );
+
+    Ok(Json(CreatedScimTokenResponse {
+        token: full_token,
+        details: row.into(),
+    }))
+}
+
+/// DELETE /org/scim/tokens/:token_id - revoke a SCIM token
+pub async fn revoke_scim_token(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(token_id): Path<Uuid>,
+) -> ApiResult<Json<ScimTokenResponse>> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let row: Option<ScimTokenRow> = sqlx::query_as(
+        r#"
+        UPDATE scim_tokens
+        SET revoked_at = NOW(), revoked_by = $3
+        WHERE id = $1 AND org_id = $2 AND revoked_at IS NULL
+        RETURNING id, name, token_prefix, last_used_at, request_count, revoked_at, created_at
+        "#,
+    )
+    .bind(token_id)
+    .bind(org_id)
+    .bind(auth_user.user_id)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    let row = row.ok_or(ApiError::NotFound)?;
+
+    tracing::info!(
+        token_id = %token_id,
+        org_id = %org_id,
+        "SCIM provisioning token revoked"
+    );
+
+    Ok(Json(row.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eq_filter_parses_quoted_values() {
+        let (attr, value) = parse_eq_filter(r#"userName eq "jane@example.com""#).unwrap();
+        assert_eq!(attr, "username");
+        assert_eq!(value, "jane@example.com");
+
+        assert!(parse_eq_filter("userName co \"jane\"").is_none());
+    }
+
+    #[test]
+    fn scim_bool_accepts_azure_string_booleans() {
+        assert_eq!(parse_scim_bool(&json!(true)), Some(true));
+        assert_eq!(parse_scim_bool(&json!("False")), Some(false));
+        assert_eq!(parse_scim_bool(&json!("yes")), None);
+    }
+
+    #[test]
+    fn group_remove_path_extracts_member_id() {
+        let id = Uuid::new_v4();
+        let path = format!("members[value eq \"{}\"]", id);
+        assert_eq!(member_id_from_path(&path), Some(id));
+        assert_eq!(member_id_from_path("members"), None);
+    }
+}
@@ -0,0 +1,364 @@
+//! Spam quarantine review routes (platform admin)
+//!
+//! Public submissions that score above the quarantine threshold land in
+//! `spam_quarantine` (see [`crate::spam`]) instead of creating tickets.
+//! Admins review entries here: approving one creates the ticket from the
+//! stored payload, discarding records the decision. Both actions are
+//! written to the admin audit log.
+
+use axum::{
+    extract::{Extension, Path, Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    audit_constants::{admin_action, event_type, severity, target_type},
+    auth::AuthUser,
+    error::{ApiError, ApiResult},
+    state::AppState,
+};
+
+use super::admin::shared::require_platform_admin;
+
+// =============================================================================
+// Types
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct QuarantineListQuery {
+    /// Filter by status (default: quarantined)
+    pub status: Option<String>,
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QuarantineEntry {
+    pub id: Uuid,
+    pub source: String,
+    pub contact_email: Option<String>,
+    pub subject: String,
+    pub payload: serde_json::Value,
+    pub score: i32,
+    pub signals: Vec<String>,
+    pub status: String,
+    pub auto_discarded: bool,
+    pub reviewed_by: Option<Uuid>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub reviewed_at: Option<OffsetDateTime>,
+    pub ticket_id: Option<Uuid>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QuarantineListResponse {
+    pub entries: Vec<QuarantineEntry>,
+    pub total: i64,
+    pub page: i64,
+    pub limit: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QuarantineReviewResponse {
+    pub id: Uuid,
+    pub status: String,
+    /// Set when approval created a ticket
+    pub ticket_number: Option<String>,
+}
+
+#[derive(Debug, FromRow)]
+struct QuarantineRow {
+    id: Uuid,
+    source: String,
+    contact_email: Option<String>,
+    subject: String,
+    payload: serde_json::Value,
+    score: i32,
+    signals: Vec<String>,
+    status: String,
+    auto_discarded: bool,
+    reviewed_by: Option<Uuid>,
+    reviewed_at: Option<OffsetDateTime>,
+    ticket_id: Option<Uuid>,
+    created_at: OffsetDateTime,
+}
+
+impl From<QuarantineRow> for QuarantineEntry {
+    fn from(r: QuarantineRow) -> Self {
+        Self {
+            id: r.id,
+            source: r.source,
+            contact_email: r.contact_email,
+            subject: r.subject,
+            payload: r.payload,
+            score: r.score,
+            signals: r.signals,
+            status: r.status,
+            auto_discarded: r.auto_discarded,
+            reviewed_by: r.reviewed_by,
+            reviewed_at: r.reviewed_at,
+            ticket_id: r.ticket_id,
+            created_at: r.created_at,
+        }
+    }
+}
+
+const VALID_STATUSES: &[&str] = &["quarantined", "approved", "discarded"];
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// List quarantined submissions (admin)
+pub async fn admin_list_quarantine(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<QuarantineListQuery>,
+) -> ApiResult<Json<QuarantineListResponse>> {
+    require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    let status = query.status.as_deref().unwrap_or("quarantined");
+    if !VALID_STATUSES.contains(&status) {
+        return Err(ApiError::Validation(format!(
+            "status must be one of: {}",
+            VALID_STATUSES.join(", ")
+        )));
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+    let offset = (page - 1) * limit;
+
+    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM spam_quarantine WHERE status = $1")
+        .bind(status)
+        .fetch_one(&state.pool)
+        .await?;
+
+    let entries: Vec<QuarantineRow> = sqlx::query_as(
+        r#"
+        SELECT id, source, contact_email, subject, payload, score, signals,
+               status, auto_discarded, reviewed_by, reviewed_at, ticket_id, created_at
+        FROM spam_quarantine
+        WHERE status = $1
+        ORDER BY created_at DESC
+        LIMIT $2 OFFSET $3
+        "#,
+    )
+    .bind(status)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(QuarantineListResponse {
+        entries: entries.into_iter().map(Into::into).collect(),
+        total,
+        page,
+        limit,
+    }))
+}
+
+/// Approve a quarantined submission, creating the ticket it was held from (admin)
+pub async fn admin_approve_quarantine(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(entry_id): Path<Uuid>,
+) -> ApiResult<Json<QuarantineReviewResponse>> {
+    let admin_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    let entry: QuarantineRow = sqlx::query_as(
+        r#"
+        SELECT id, source, contact_email, subject, payload, score, signals,
+               status, auto_discarded, reviewed_by, reviewed_at, ticket_id, created_at
+        FROM spam_quarantine
+        WHERE id = $1
+        "#,
+    )
+    .bind(entry_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    if entry.status != "quarantined" {
+        return Err(ApiError::Conflict(format!(
+            "Entry has already been {}",
+            entry.status
+        )));
+    }
+
+    let category = match entry.source.as_str() {
+        "enterprise_inquiry" => "enterprise_inquiry",
+        _ => "general",
+    };
+
+    #[derive(FromRow)]
+    struct TicketRow {
+        id: Uuid,
+        ticket_number: String,
+    }
+
+    let ticket: TicketRow = sqlx::query_as(
+        r#"
+        INSERT INTO support_tickets (
+            organization_id, user_id, subject, category, priority,
+            contact_name, contact_email, contact_company
+        )
+        VALUES (NULL, NULL, $1, $2::ticket_category, 'high'::ticket_priority, $3, $4, $5)
+        RETURNING id, ticket_number
+        "#,
+    )
+    .bind(&entry.subject)
+    .bind(category)
+    .bind(entry.payload.get("contact_name").and_then(|v| v.as_str()))
+    .bind(&entry.contact_email)
+    .bind(
+        entry
+            .payload
+            .get("contact_company")
+            .and_then(|v| v.as_str()),
+    )
+    .fetch_one(&state.pool)
+    .await?;
+
+    let content = entry
+        .payload
+        .get("content")
+        .and_then(|v| v.as_str())
+        .unwrap_or(&entry.subject);
+
+    sqlx::query(
+        r#"
+        INSERT INTO ticket_messages (ticket_id, sender_id, is_admin_reply, content)
+        VALUES ($1, NULL, false, $2)
+        "#,
+    )
+    .bind(ticket.id)
+    .bind(content)
+    .execute(&state.pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE spam_quarantine
+        SET status = 'approved', reviewed_by = $2, reviewed_at = NOW(), ticket_id = $3
+        WHERE id = $1
+        "#,
+    )
+    .bind(entry_id)
+    .bind(admin_id)
+    .bind(ticket.id)
+    .execute(&state.pool)
+    .await?;
+
+    log_review_audit(
+        &state,
+        admin_id,
+        admin_action::SPAM_QUARANTINE_APPROVED,
+        &entry,
+        Some(&ticket.ticket_number),
+    )
+    .await;
+
+    tracing::info!(
+        entry_id = %entry_id,
+        ticket_number = %ticket.ticket_number,
+        admin_id = %admin_id,
+        "Quarantined submission approved"
+    );
+
+    Ok(Json(QuarantineReviewResponse {
+        id: entry_id,
+        status: "approved".to_string(),
+        ticket_number: Some(ticket.ticket_number),
+    }))
+}
+
+/// Discard a quarantined submission (admin)
+pub async fn admin_discard_quarantine(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(entry_id): Path<Uuid>,
+) -> ApiResult<Json<QuarantineReviewResponse>> {
+    let admin_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    let entry: QuarantineRow = sqlx::query_as(
+        r#"
+        UPDATE spam_quarantine
+        SET status = 'discarded', reviewed_by = $2, reviewed_at = NOW()
+        WHERE id = $1 AND status = 'quarantined'
+        RETURNING id, source, contact_email, subject, payload, score, signals,
+                  status, auto_discarded, reviewed_by, reviewed_at, ticket_id, created_at
+        "#,
+    )
+    .bind(entry_id)
+    .bind(admin_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    log_review_audit(
+        &state,
+        admin_id,
+        admin_action::SPAM_QUARANTINE_DISCARDED,
+        &entry,
+        None,
+    )
+    .await;
+
+    tracing::info!(
+        entry_id = %entry_id,
+        admin_id = %admin_id,
+        "Quarantined submission discarded"
+    );
+
+    Ok(Json(QuarantineReviewResponse {
+        id: entry_id,
+        status: "discarded".to_string(),
+        ticket_number: None,
+    }))
+}
+
+/// Write a review decision to the admin audit log (best-effort)
+async fn log_review_audit(
+    state: &AppState,
+    admin_id: Uuid,
+    action: &str,
+    entry: &QuarantineRow,
+    ticket_number: Option<&str>,
+) {
+    let details = serde_json::json!({
+        "source": entry.source,
+        "subject": entry.subject,
+        "contact_email": entry.contact_email,
+        "score": entry.score,
+        "signals": entry.signals,
+        "ticket_number": ticket_number,
+    });
+
+    if let Err(e) = sqlx::query(
+        r#"
+        INSERT INTO admin_audit_log (
+            admin_user_id, action, target_type, target_id, details,
+            event_type, severity
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(admin_id)
+    .bind(action)
+    .bind(target_type::SPAM_QUARANTINE)
+    .bind(entry.id)
+    .bind(&details)
+    .bind(event_type::DATA_MODIFICATION)
+    .bind(severity::INFO)
+    .execute(&state.pool)
+    .await
+    {
+        tracing::warn!("Failed to log quarantine review audit: {}", e);
+    }
+}
@@ -0,0 +1,167 @@
+//! SSH key routes for tunneled MCP connections
+//!
+//! Organizations upload a private key once, then reference it by ID from an
+//! MCP's `ssh_tunnel` config block. Key material is AES-GCM encrypted with
+//! `TOTP_ENCRYPTION_KEY` before storage and is never returned by the API -
+//! only the SHA-256 fingerprint is exposed.
+
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    auth::totp::{encrypt_secret, parse_encryption_key},
+    auth::AuthUser,
+    error::ApiError,
+    state::AppState,
+};
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Database row for an SSH key (key material columns excluded)
+#[derive(Debug, sqlx::FromRow)]
+struct SshKeyRow {
+    pub id: Uuid,
+    pub name: String,
+    pub fingerprint: String,
+    pub created_at: OffsetDateTime,
+}
+
+/// API response for an SSH key - never includes key material
+#[derive(Debug, Serialize)]
+pub struct SshKey {
+    pub id: Uuid,
+    pub name: String,
+    pub fingerprint: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+impl From<SshKeyRow> for SshKey {
+    fn from(row: SshKeyRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            fingerprint: row.fingerprint,
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSshKeyRequest {
+    pub name: String,
+    /// PEM-encoded private key (e.g. OpenSSH or PKCS#8)
+    pub private_key: String,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// List the organization's SSH keys (fingerprints only)
+pub async fn list_ssh_keys(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<Vec<SshKey>>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let rows: Vec<SshKeyRow> = sqlx::query_as(
+        "SELECT id, name, fingerprint, created_at FROM mcp_ssh_keys \
+         WHERE org_id = $1 ORDER BY name",
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(rows.into_iter().map(SshKey::from).collect()))
+}
+
+/// Upload a new SSH private key
+pub async fn create_ssh_key(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<CreateSshKeyRequest>,
+) -> Result<(StatusCode, Json<SshKey>), ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let name = req.name.trim();
+    if name.is_empty() || name.len() > 255 {
+        return Err(ApiError::Validation(
+            "Key name must be between 1 and 255 characters".to_string(),
+        ));
+    }
+
+    let private_key = req.private_key.trim();
+    if !private_key.contains("PRIVATE KEY") {
+        return Err(ApiError::Validation(
+            "private_key must be a PEM-encoded private key".to_string(),
+        ));
+    }
+
+    // Encrypt with the same envelope used for 2FA secrets
+    let key = parse_encryption_key(&state.config.totp_encryption_key).map_err(|e| {
+        tracing::error!(
+            "TOTP_ENCRYPTION_KEY not usable for SSH key encryption: {}",
+            e
+        );
+        ApiError::Internal
+    })?;
+    let (encrypted, nonce) = encrypt_secret(private_key, &key).map_err(|e| {
+        tracing::error!("Failed to encrypt SSH key: {}", e);
+        ApiError::Internal
+    })?;
+
+    let fingerprint = hex::encode(Sha256::digest(private_key.as_bytes()));
+
+    let row: SshKeyRow = sqlx::query_as(
+        r#"
+        INSERT INTO mcp_ssh_keys (org_id, name, private_key_encrypted, private_key_nonce, fingerprint, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, name, fingerprint, created_at
+        "#,
+    )
+    .bind(org_id)
+    .bind(name)
+    .bind(&encrypted)
+    .bind(&nonce)
+    .bind(&fingerprint)
+    .bind(auth_user.user_id)
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(SshKey::from(row))))
+}
+
+/// Delete an SSH key
+///
+/// MCPs whose `ssh_tunnel` config still references the key will fail to
+/// connect until they are updated; existing tunnels keep running until they
+/// drop.
+pub async fn delete_ssh_key(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(key_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let result = sqlx::query("DELETE FROM mcp_ssh_keys WHERE id = $1 AND org_id = $2")
+        .bind(key_id)
+        .bind(org_id)
+        .execute(&state.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,382 @@
+//! Public platform status page
+//!
+//! Customers read platform incidents from `/public/status` and can
+//! subscribe by email to be notified on every incident update. Incidents
+//! are created and updated by platform admins in
+//! [`super::admin::incidents`]; the response doubles as the dashboard's
+//! banner source - the most severe active incident is surfaced as a
+//! ready-to-render banner object.
+
+use axum::{extract::State, Json};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sqlx::FromRow;
+use subtle::ConstantTimeEq;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    error::{ApiError, ApiResult},
+    state::AppState,
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Resolved incidents stay on the status page this long
+const RESOLVED_VISIBILITY_DAYS: i64 = 7;
+
+// =============================================================================
+// Response Types
+// =============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct PublicStatusResponse {
+    /// operational, degraded, partial_outage, or major_outage
+    pub status: String,
+    /// Banner for dashboards, derived from the most severe active incident
+    pub banner: Option<StatusBanner>,
+    pub active_incidents: Vec<PublicIncident>,
+    /// Incidents resolved within the last [`RESOLVED_VISIBILITY_DAYS`] days
+    pub recently_resolved: Vec<PublicIncident>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusBanner {
+    pub message: String,
+    pub severity: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicIncident {
+    pub id: Uuid,
+    pub title: String,
+    pub severity: String,
+    pub status: String,
+    pub affected_components: Vec<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub started_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub resolved_at: Option<OffsetDateTime>,
+    /// Timeline, newest first
+    pub updates: Vec<PublicIncidentUpdate>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicIncidentUpdate {
+    pub status: String,
+    pub message: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SubscribeRequest {
+    pub email: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SubscribeResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+// =============================================================================
+// Database Row Types
+// =============================================================================
+
+#[derive(Debug, FromRow)]
+struct IncidentRow {
+    id: Uuid,
+    title: String,
+    severity: String,
+    status: String,
+    affected_components: Vec<String>,
+    started_at: OffsetDateTime,
+    resolved_at: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, FromRow)]
+struct UpdateRow {
+    incident_id: Uuid,
+    status: String,
+    message: String,
+    created_at: OffsetDateTime,
+}
+
+// =============================================================================
+// Signed Unsubscribe Tokens
+// =============================================================================
+
+/// Generate a signed status unsubscribe token for an address
+///
+/// Format: `base64url(email).signature_hex_16_chars`, no expiry -
+/// unsubscribe links in old notifications must keep working.
+pub(crate) fn status_unsubscribe_token(email: &str, secret: &str) -> String {
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use base64::Engine;
+
+    let signature = sign_email(email, secret);
+    format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(email.as_bytes()),
+        hex::encode(&signature[..8])
+    )
+}
+
+/// Validate a status unsubscribe token and extract the address
+fn verify_status_unsubscribe_token(token: &str, secret: &str) -> Option<String> {
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use base64::Engine;
+
+    let (email_b64, signature_hex) = token.split_once('.')?;
+    let email = String::from_utf8(URL_SAFE_NO_PAD.decode(email_b64).ok()?).ok()?;
+
+    let expected = hex::encode(&sign_email(&email, secret)[..8]);
+    if !bool::from(expected.as_bytes().ct_eq(signature_hex.as_bytes())) {
+        return None;
+    }
+    Some(email)
+}
+
+fn sign_email(email: &str, secret: &str) -> Vec<u8> {
+    #[allow(clippy::expect_used)] // HMAC accepts keys of any size; this cannot fail
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(b"status-unsubscribe:");
+    mac.update(email.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// Current platform status with active and recent incidents (no auth)
+pub async fn get_status(State(state): State<AppState>) -> ApiResult<Json<PublicStatusResponse>> {
+    let active: Vec<IncidentRow> = sqlx::query_as(
+        r#"
+        SELECT id, title, severity, status, affected_components, started_at, resolved_at
+        FROM platform_incidents
+        WHERE status != 'resolved'
+        ORDER BY started_at DESC
+        "#,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let resolved: Vec<IncidentRow> = sqlx::query_as(
+        r#"
+        SELECT id, title, severity, status, affected_components, started_at, resolved_at
+        FROM platform_incidents
+        WHERE status = 'resolved'
+          AND resolved_at >= NOW() - make_interval(days => $1)
+        ORDER BY resolved_at DESC
+        LIMIT 20
+        "#,
+    )
+    .bind(RESOLVED_VISIBILITY_DAYS as i32)
+    .fetch_all(&state.pool)
+    .await?;
+
+    // One query for all timelines instead of one per incident
+    let ids: Vec<Uuid> = active.iter().chain(&resolved).map(|i| i.id).collect();
+    let mut updates: Vec<UpdateRow> = if ids.is_empty() {
+        Vec::new()
+    } else {
+        sqlx::query_as(
+            r#"
+            SELECT incident_id, status, message, created_at
+            FROM platform_incident_updates
+            WHERE incident_id = ANY($1)
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(&ids)
+        .fetch_all(&state.pool)
+        .await?
+    };
+
+    let overall = overall_status(&active);
+    let banner = active
+        .iter()
+        .max_by_key(|i| severity_rank(&i.severity))
+        .map(|i| StatusBanner {
+            message: i.title.clone(),
+            severity: i.severity.clone(),
+        });
+
+    let build = |rows: Vec<IncidentRow>, updates: &mut Vec<UpdateRow>| -> Vec<PublicIncident> {
+        rows.into_iter()
+            .map(|row| {
+                let timeline = updates
+                    .iter()
+                    .filter(|u| u.incident_id == row.id)
+                    .map(|u| PublicIncidentUpdate {
+                        status: u.status.clone(),
+                        message: u.message.clone(),
+                        created_at: u.created_at,
+                    })
+                    .collect();
+                PublicIncident {
+                    id: row.id,
+                    title: row.title,
+                    severity: row.severity,
+                    status: row.status,
+                    affected_components: row.affected_components,
+                    started_at: row.started_at,
+                    resolved_at: row.resolved_at,
+                    updates: timeline,
+                }
+            })
+            .collect()
+    };
+
+    Ok(Json(PublicStatusResponse {
+        status: overall,
+        banner,
+        active_incidents: build(active, &mut updates),
+        recently_resolved: build(resolved, &mut updates),
+    }))
+}
+
+/// Subscribe an email address to incident update notifications (no auth)
+pub async fn subscribe_status(
+    State(state): State<AppState>,
+    Json(req): Json<SubscribeRequest>,
+) -> ApiResult<Json<SubscribeResponse>> {
+    let email = req.email.trim().to_lowercase();
+    if !is_valid_email(&email) {
+        return Err(ApiError::BadRequest(
+            "Please enter a valid email address".into(),
+        ));
+    }
+
+    // Idempotent: re-subscribing an existing address succeeds quietly
+    sqlx::query(
+        "INSERT INTO status_subscribers (email) VALUES ($1) ON CONFLICT (email) DO NOTHING",
+    )
+    .bind(&email)
+    .execute(&state.pool)
+    .await?;
+
+    tracing::info!("Status page subscription added");
+
+    Ok(Json(SubscribeResponse {
+        success: true,
+        message: "You're subscribed to status updates.".into(),
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StatusUnsubscribeQuery {
+    pub token: String,
+}
+
+/// Unsubscribe landing page (link clicked in a notification email)
+pub async fn unsubscribe_status(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<StatusUnsubscribeQuery>,
+) -> ApiResult<axum::response::Html<String>> {
+    let secret = state.security_email.unsubscribe_secret();
+    if secret.is_empty() {
+        return Err(ApiError::BadRequest("Invalid unsubscribe link".into()));
+    }
+
+    let email = verify_status_unsubscribe_token(&query.token, secret)
+        .ok_or_else(|| ApiError::BadRequest("Invalid unsubscribe link".into()))?;
+
+    sqlx::query("DELETE FROM status_subscribers WHERE email = $1")
+        .bind(&email)
+        .execute(&state.pool)
+        .await?;
+
+    tracing::info!("Status page subscription removed");
+
+    Ok(axum::response::Html(
+        r#"<!DOCTYPE html>
+<html>
+<head><title>Unsubscribed</title></head>
+<body style="font-family: sans-serif; max-width: 480px; margin: 80px auto; text-align: center;">
+  <h1>You're unsubscribed</h1>
+  <p>You will no longer receive platform status updates.</p>
+</body>
+</html>"#
+            .to_string(),
+    ))
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Numeric rank so the worst active severity wins
+fn severity_rank(severity: &str) -> u8 {
+    match severity {
+        "critical" => 3,
+        "major" => 2,
+        "minor" => 1,
+        _ => 0,
+    }
+}
+
+/// Overall status derived from the worst active incident
+fn overall_status(active: &[IncidentRow]) -> String {
+    let worst = active
+        .iter()
+        .map(|i| severity_rank(&i.severity))
+        .max()
+        .unwrap_or(0);
+    match worst {
+        3 => "major_outage",
+        2 => "partial_outage",
+        1 => "degraded",
+        _ => "operational",
+    }
+    .to_string()
+}
+
+/// Basic email validation: content on both sides of @, dotted domain
+fn is_valid_email(email: &str) -> bool {
+    let parts: Vec<&str> = email.split('@').collect();
+    if parts.len() != 2 {
+        return false;
+    }
+    let (local, domain) = (parts[0], parts[1]);
+    !local.is_empty() && !domain.is_empty() && domain.contains('.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsubscribe_token_round_trip() {
+        let token = status_unsubscribe_token("user@example.com", "secret");
+        assert_eq!(
+            verify_status_unsubscribe_token(&token, "secret").as_deref(),
+            Some("user@example.com")
+        );
+        assert!(verify_status_unsubscribe_token(&token, "other").is_none());
+        assert!(verify_status_unsubscribe_token("garbage", "secret").is_none());
+    }
+
+    #[test]
+    fn overall_status_tracks_worst_severity() {
+        let incident = |severity: &str| IncidentRow {
+            id: Uuid::new_v4(),
+            title: "t".into(),
+            severity: severity.into(),
+            status: "investigating".into(),
+            affected_components: vec![],
+            started_at: OffsetDateTime::now_utc(),
+            resolved_at: None,
+        };
+        assert_eq!(overall_status(&[]), "operational");
+        assert_eq!(overall_status(&[incident("minor")]), "degraded");
+        assert_eq!(
+            overall_status(&[incident("minor"), incident("critical")]),
+            "major_outage"
+        );
+    }
+}
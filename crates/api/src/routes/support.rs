@@ -5,6 +5,7 @@
 
 use axum::{
     extract::{Extension, Path, Query, State},
+    http::HeaderMap,
     Json,
 };
 use serde::{Deserialize, Serialize};
@@ -123,6 +124,11 @@ pub struct SupportTicket {
     pub source: Option<String>,
     pub original_email_from: Option<String>,
     pub original_email_to: Option<String>,
+
+    /// Signed CSAT survey token; only set on the close response so the
+    /// frontend can offer the survey immediately
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub csat_token: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -195,6 +201,8 @@ impl From<TicketRow> for SupportTicket {
             source: row.source,
             original_email_from: row.original_email_from,
             original_email_to: row.original_email_to,
+
+            csat_token: None,
         }
     }
 }
@@ -227,7 +235,7 @@ impl From<MessageRow> for TicketMessage {
 // =============================================================================
 
 /// Resolve the actual user_id and organization_id for the authenticated user.
-async fn resolve_user_context(
+pub(super) async fn resolve_user_context(
     pool: &PgPool,
     auth_user: &AuthUser,
 ) -> Result<(Uuid, Option<Uuid>), ApiError> {
@@ -367,6 +375,13 @@ pub async fn create_ticket(
     .execute(&state.pool)
     .await?;
 
+    // Routing rules run in the background; a broken rule must not fail creation
+    tokio::spawn(crate::support_rules::run_rules(
+        state.clone(),
+        ticket_id,
+        crate::support_rules::RuleTrigger::TicketCreated,
+    ));
+
     tracing::info!(
         ticket_id = %ticket_id,
         ticket_number = %ticket.ticket_number,
@@ -621,6 +636,27 @@ pub async fn reply_to_ticket(
         return Err(ApiError::NotFound);
     }
 
+    // Auto-closed tickets can only be reopened within the reopen window
+    let expired_reopen: Option<(OffsetDateTime,)> = sqlx::query_as(
+        r#"
+        SELECT reopen_deadline
+        FROM support_tickets
+        WHERE id = $1
+          AND status = 'closed'::ticket_status
+          AND auto_closed_at IS NOT NULL
+          AND reopen_deadline < NOW()
+        "#,
+    )
+    .bind(ticket_id)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    if expired_reopen.is_some() {
+        return Err(ApiError::BadRequest(
+            "This ticket was closed due to inactivity and can no longer be reopened. Please open a new ticket.".into(),
+        ));
+    }
+
     // Insert the message
     let message: MessageRow = sqlx::query_as(
         r#"
@@ -635,11 +671,13 @@ pub async fn reply_to_ticket(
     .fetch_one(&state.pool)
     .await?;
 
-    // Update ticket status to awaiting_response if it was resolved
+    // Update ticket status to awaiting_response if it was resolved,
+    // clearing any lifecycle automation markers on reopen
     sqlx::query(
         r#"
         UPDATE support_tickets
-        SET status = 'open'::ticket_status, updated_at = NOW()
+        SET status = 'open'::ticket_status, updated_at = NOW(),
+            inactivity_reminder_sent_at = NULL, auto_closed_at = NULL, reopen_deadline = NULL
         WHERE id = $1 AND status IN ('resolved'::ticket_status, 'closed'::ticket_status)
         "#,
     )
@@ -669,12 +707,33 @@ pub async fn reply_to_ticket(
         )
         .await;
 
+    tokio::spawn(crate::support_rules::run_rules(
+        state.clone(),
+        ticket_id,
+        crate::support_rules::RuleTrigger::CustomerReply,
+    ));
+
     tracing::info!(
         ticket_id = %ticket_id,
         user_id = %user_id,
         "User replied to support ticket"
     );
 
+    // Fan out to org-registered outbound webhooks
+    if let Some(org_id) = org_id {
+        plexmcp_shared::org_webhooks::emit(
+            &state.pool,
+            org_id,
+            "ticket.replied",
+            serde_json::json!({
+                "ticket_id": ticket_id,
+                "message_id": message.id,
+                "author": "customer",
+            }),
+        )
+        .await;
+    }
+
     Ok(Json(message.into()))
 }
 
@@ -695,7 +754,8 @@ pub async fn close_ticket(
             WHERE id = $1 AND organization_id = $2
             RETURNING id, ticket_number, organization_id, user_id, subject,
                       category::text, status::text, priority::text, assigned_to,
-                      created_at, updated_at, resolved_at, closed_at
+                      created_at, updated_at, resolved_at, closed_at,
+                      source, original_email_from, original_email_to
             "#,
         )
         .bind(ticket_id)
@@ -710,7 +770,8 @@ pub async fn close_ticket(
             WHERE id = $1 AND user_id = $2
             RETURNING id, ticket_number, organization_id, user_id, subject,
                       category::text, status::text, priority::text, assigned_to,
-                      created_at, updated_at, resolved_at, closed_at
+                      created_at, updated_at, resolved_at, closed_at,
+                      source, original_email_from, original_email_to
             "#,
         )
         .bind(ticket_id)
@@ -742,7 +803,117 @@ pub async fn close_ticket(
         "Support ticket closed by user"
     );
 
-    Ok(Json(ticket.into()))
+    // Hand the caller a survey token so the frontend can ask for a
+    // rating right away
+    let mut ticket: SupportTicket = ticket.into();
+    let csat_secret = state.security_email.csat_secret();
+    if !csat_secret.is_empty() {
+        ticket.csat_token = Some(crate::csat::survey_token(ticket_id, csat_secret));
+    }
+
+    Ok(Json(ticket))
+}
+
+// =============================================================================
+// CSAT Surveys
+// =============================================================================
+
+/// Longest accepted free-text comment on a survey response
+const MAX_CSAT_COMMENT_LENGTH: usize = 2000;
+
+#[derive(Debug, Deserialize)]
+pub struct SubmitCsatRequest {
+    /// Signed token from the close response or survey email link
+    pub token: String,
+    /// 1 (very dissatisfied) to 5 (very satisfied)
+    pub rating: i16,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SubmitCsatResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+/// Submit a satisfaction rating for a resolved/closed ticket (no auth)
+///
+/// The signed token gates access instead of a session - survey links in
+/// close emails must work for contacts without an account. One response
+/// per ticket; repeat submissions are rejected.
+pub async fn submit_csat(
+    State(state): State<AppState>,
+    Path(ticket_id): Path<Uuid>,
+    Json(req): Json<SubmitCsatRequest>,
+) -> ApiResult<Json<SubmitCsatResponse>> {
+    let secret = state.security_email.csat_secret();
+    if secret.is_empty() || !crate::csat::verify_survey_token(ticket_id, &req.token, secret) {
+        return Err(ApiError::BadRequest("Invalid survey link".into()));
+    }
+
+    if !(1..=5).contains(&req.rating) {
+        return Err(ApiError::BadRequest(
+            "Rating must be between 1 and 5".into(),
+        ));
+    }
+
+    let comment = match req.comment.as_deref().map(str::trim) {
+        Some("") | None => None,
+        Some(c) if c.chars().count() > MAX_CSAT_COMMENT_LENGTH => {
+            return Err(ApiError::BadRequest(format!(
+                "Comment is too long (max {} characters)",
+                MAX_CSAT_COMMENT_LENGTH
+            )));
+        }
+        Some(c) => Some(c),
+    };
+
+    // Surveys only make sense once the ticket is done
+    let ticket: Option<(String, Option<Uuid>)> =
+        sqlx::query_as("SELECT status::text, assigned_to FROM support_tickets WHERE id = $1")
+            .bind(ticket_id)
+            .fetch_optional(&state.pool)
+            .await?;
+
+    let (status, assigned_to) = ticket.ok_or(ApiError::NotFound)?;
+    if status != "resolved" && status != "closed" {
+        return Err(ApiError::BadRequest(
+            "Surveys are only available after a ticket is resolved or closed".into(),
+        ));
+    }
+
+    let inserted: Option<Uuid> = sqlx::query_scalar(
+        r#"
+        INSERT INTO csat_responses (ticket_id, rating, comment, rated_staff_id)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (ticket_id) DO NOTHING
+        RETURNING id
+        "#,
+    )
+    .bind(ticket_id)
+    .bind(req.rating)
+    .bind(comment)
+    .bind(assigned_to)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    if inserted.is_none() {
+        return Err(ApiError::Conflict(
+            "A rating was already submitted for this ticket".into(),
+        ));
+    }
+
+    tracing::info!(
+        ticket_id = %ticket_id,
+        rating = req.rating,
+        has_comment = comment.is_some(),
+        "CSAT response submitted"
+    );
+
+    Ok(Json(SubmitCsatResponse {
+        success: true,
+        message: "Thanks for your feedback!".into(),
+    }))
 }
 
 // =============================================================================
@@ -758,6 +929,10 @@ pub struct AdminListTicketsQuery {
     pub category: Option<String>,
     pub assigned_to: Option<Uuid>,
     pub search: Option<String>,
+    /// Only tickets carrying this tag (registry name)
+    pub tag: Option<String>,
+    /// When true, only tickets with no assignee
+    pub unassigned: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -766,6 +941,8 @@ pub struct AdminTicketResponse {
     pub ticket_number: String,
     pub organization_id: Option<Uuid>,
     pub organization_name: Option<String>,
+    /// Subscription tier of the ticket's org; None for orgless tickets
+    pub organization_tier: Option<String>,
     pub user_id: Option<Uuid>,
     pub user_email: Option<String>,
     pub contact_name: Option<String>,
@@ -777,6 +954,7 @@ pub struct AdminTicketResponse {
     pub priority: String,
     pub assigned_to: Option<Uuid>,
     pub assigned_to_email: Option<String>,
+    pub tags: Vec<String>,
     pub message_count: i64,
     #[serde(with = "time::serde::rfc3339")]
     pub created_at: OffsetDateTime,
@@ -836,6 +1014,7 @@ struct AdminTicketRow {
     ticket_number: String,
     organization_id: Option<Uuid>,
     organization_name: Option<String>,
+    organization_tier: Option<String>,
     user_id: Option<Uuid>,
     user_email: Option<String>,
     contact_name: Option<String>,
@@ -847,6 +1026,7 @@ struct AdminTicketRow {
     priority: String,
     assigned_to: Option<Uuid>,
     assigned_to_email: Option<String>,
+    tags: Vec<String>,
     message_count: i64,
     created_at: OffsetDateTime,
     updated_at: OffsetDateTime,
@@ -866,6 +1046,7 @@ impl From<AdminTicketRow> for AdminTicketResponse {
             ticket_number: row.ticket_number,
             organization_id: row.organization_id,
             organization_name: row.organization_name,
+            organization_tier: row.organization_tier,
             user_id: row.user_id,
             user_email: row.user_email,
             contact_name: row.contact_name,
@@ -877,6 +1058,7 @@ impl From<AdminTicketRow> for AdminTicketResponse {
             priority: row.priority,
             assigned_to: row.assigned_to,
             assigned_to_email: row.assigned_to_email,
+            tags: row.tags,
             message_count: row.message_count,
             created_at: row.created_at,
             updated_at: row.updated_at,
@@ -986,6 +1168,7 @@ pub async fn admin_list_tickets(
                 t.ticket_number,
                 t.organization_id,
                 o.name as organization_name,
+                o.subscription_tier as organization_tier,
                 t.user_id,
                 u.email as user_email,
                 t.contact_name,
@@ -1002,6 +1185,7 @@ pub async fn admin_list_tickets(
                 t.updated_at,
                 t.resolved_at,
                 t.closed_at,
+                t.tags,
                 t.source,
                 t.original_email_from,
                 t.original_email_to
@@ -1015,6 +1199,8 @@ pub async fn admin_list_tickets(
                 AND ($5::text IS NULL OR t.category::text = $5)
                 AND ($6::uuid IS NULL OR t.assigned_to = $6)
                 AND ($7::text IS NULL OR t.subject ILIKE '%' || $7 || '%' OR t.ticket_number ILIKE '%' || $7 || '%')
+                AND ($8::text IS NULL OR t.tags @> ARRAY[$8])
+                AND ($9::bool IS NOT TRUE OR t.assigned_to IS NULL)
             ORDER BY
                 CASE t.priority
                     WHEN 'urgent' THEN 1
@@ -1022,6 +1208,12 @@ pub async fn admin_list_tickets(
                     WHEN 'medium' THEN 3
                     WHEN 'low' THEN 4
                 END,
+                CASE COALESCE(o.subscription_tier, 'free')
+                    WHEN 'enterprise' THEN 1
+                    WHEN 'team' THEN 2
+                    WHEN 'pro' THEN 3
+                    ELSE 4
+                END,
                 t.created_at DESC
             LIMIT $1 OFFSET $2
             "#,
@@ -1033,6 +1225,8 @@ pub async fn admin_list_tickets(
         .bind(&query.category)
         .bind(query.assigned_to)
         .bind(&query.search)
+        .bind(&query.tag)
+        .bind(query.unassigned)
         .fetch_all(&state.pool)
         .await?
     } else if !assigned_emails.is_empty() {
@@ -1044,6 +1238,7 @@ pub async fn admin_list_tickets(
                 t.ticket_number,
                 t.organization_id,
                 o.name as organization_name,
+                o.subscription_tier as organization_tier,
                 t.user_id,
                 u.email as user_email,
                 t.contact_name,
@@ -1060,6 +1255,7 @@ pub async fn admin_list_tickets(
                 t.updated_at,
                 t.resolved_at,
                 t.closed_at,
+                t.tags,
                 t.source,
                 t.original_email_from,
                 t.original_email_to
@@ -1068,12 +1264,14 @@ pub async fn admin_list_tickets(
             LEFT JOIN users u ON u.id = t.user_id
             LEFT JOIN users a ON a.id = t.assigned_to
             WHERE 1=1
-                AND (COALESCE(t.source, 'web') != 'email' OR t.original_email_to = ANY($8))
+                AND (COALESCE(t.source, 'web') != 'email' OR t.original_email_to = ANY($10))
                 AND ($3::text IS NULL OR t.status::text = $3)
                 AND ($4::text IS NULL OR t.priority::text = $4)
                 AND ($5::text IS NULL OR t.category::text = $5)
                 AND ($6::uuid IS NULL OR t.assigned_to = $6)
                 AND ($7::text IS NULL OR t.subject ILIKE '%' || $7 || '%' OR t.ticket_number ILIKE '%' || $7 || '%')
+                AND ($8::text IS NULL OR t.tags @> ARRAY[$8])
+                AND ($9::bool IS NOT TRUE OR t.assigned_to IS NULL)
             ORDER BY
                 CASE t.priority
                     WHEN 'urgent' THEN 1
@@ -1081,6 +1279,12 @@ pub async fn admin_list_tickets(
                     WHEN 'medium' THEN 3
                     WHEN 'low' THEN 4
                 END,
+                CASE COALESCE(o.subscription_tier, 'free')
+                    WHEN 'enterprise' THEN 1
+                    WHEN 'team' THEN 2
+                    WHEN 'pro' THEN 3
+                    ELSE 4
+                END,
                 t.created_at DESC
             LIMIT $1 OFFSET $2
             "#,
@@ -1092,6 +1296,8 @@ pub async fn admin_list_tickets(
         .bind(&query.category)
         .bind(query.assigned_to)
         .bind(&query.search)
+        .bind(&query.tag)
+        .bind(query.unassigned)
         .bind(&assigned_emails)
         .fetch_all(&state.pool)
         .await?
@@ -1104,6 +1310,7 @@ pub async fn admin_list_tickets(
                 t.ticket_number,
                 t.organization_id,
                 o.name as organization_name,
+                o.subscription_tier as organization_tier,
                 t.user_id,
                 u.email as user_email,
                 t.contact_name,
@@ -1120,6 +1327,7 @@ pub async fn admin_list_tickets(
                 t.updated_at,
                 t.resolved_at,
                 t.closed_at,
+                t.tags,
                 t.source,
                 t.original_email_from,
                 t.original_email_to
@@ -1134,6 +1342,8 @@ pub async fn admin_list_tickets(
                 AND ($5::text IS NULL OR t.category::text = $5)
                 AND ($6::uuid IS NULL OR t.assigned_to = $6)
                 AND ($7::text IS NULL OR t.subject ILIKE '%' || $7 || '%' OR t.ticket_number ILIKE '%' || $7 || '%')
+                AND ($8::text IS NULL OR t.tags @> ARRAY[$8])
+                AND ($9::bool IS NOT TRUE OR t.assigned_to IS NULL)
             ORDER BY
                 CASE t.priority
                     WHEN 'urgent' THEN 1
@@ -1141,6 +1351,12 @@ pub async fn admin_list_tickets(
                     WHEN 'medium' THEN 3
                     WHEN 'low' THEN 4
                 END,
+                CASE COALESCE(o.subscription_tier, 'free')
+                    WHEN 'enterprise' THEN 1
+                    WHEN 'team' THEN 2
+                    WHEN 'pro' THEN 3
+                    ELSE 4
+                END,
                 t.created_at DESC
             LIMIT $1 OFFSET $2
             "#,
@@ -1152,6 +1368,8 @@ pub async fn admin_list_tickets(
         .bind(&query.category)
         .bind(query.assigned_to)
         .bind(&query.search)
+        .bind(&query.tag)
+        .bind(query.unassigned)
         .fetch_all(&state.pool)
         .await?
     };
@@ -1168,6 +1386,8 @@ pub async fn admin_list_tickets(
                 AND ($3::text IS NULL OR t.category::text = $3)
                 AND ($4::uuid IS NULL OR t.assigned_to = $4)
                 AND ($5::text IS NULL OR t.subject ILIKE '%' || $5 || '%' OR t.ticket_number ILIKE '%' || $5 || '%')
+                AND ($6::text IS NULL OR t.tags @> ARRAY[$6])
+                AND ($7::bool IS NOT TRUE OR t.assigned_to IS NULL)
             "#,
         )
         .bind(&query.status)
@@ -1175,6 +1395,8 @@ pub async fn admin_list_tickets(
         .bind(&query.category)
         .bind(query.assigned_to)
         .bind(&query.search)
+        .bind(&query.tag)
+        .bind(query.unassigned)
         .fetch_one(&state.pool)
         .await?
     } else if !assigned_emails.is_empty() {
@@ -1183,12 +1405,14 @@ pub async fn admin_list_tickets(
             SELECT COUNT(*)
             FROM support_tickets t
             WHERE 1=1
-                AND (COALESCE(t.source, 'web') != 'email' OR t.original_email_to = ANY($6))
+                AND (COALESCE(t.source, 'web') != 'email' OR t.original_email_to = ANY($8))
                 AND ($1::text IS NULL OR t.status::text = $1)
                 AND ($2::text IS NULL OR t.priority::text = $2)
                 AND ($3::text IS NULL OR t.category::text = $3)
                 AND ($4::uuid IS NULL OR t.assigned_to = $4)
                 AND ($5::text IS NULL OR t.subject ILIKE '%' || $5 || '%' OR t.ticket_number ILIKE '%' || $5 || '%')
+                AND ($6::text IS NULL OR t.tags @> ARRAY[$6])
+                AND ($7::bool IS NOT TRUE OR t.assigned_to IS NULL)
             "#,
         )
         .bind(&query.status)
@@ -1196,6 +1420,8 @@ pub async fn admin_list_tickets(
         .bind(&query.category)
         .bind(query.assigned_to)
         .bind(&query.search)
+        .bind(&query.tag)
+        .bind(query.unassigned)
         .bind(&assigned_emails)
         .fetch_one(&state.pool)
         .await?
@@ -1211,6 +1437,8 @@ pub async fn admin_list_tickets(
                 AND ($3::text IS NULL OR t.category::text = $3)
                 AND ($4::uuid IS NULL OR t.assigned_to = $4)
                 AND ($5::text IS NULL OR t.subject ILIKE '%' || $5 || '%' OR t.ticket_number ILIKE '%' || $5 || '%')
+                AND ($6::text IS NULL OR t.tags @> ARRAY[$6])
+                AND ($7::bool IS NOT TRUE OR t.assigned_to IS NULL)
             "#,
         )
         .bind(&query.status)
@@ -1218,6 +1446,8 @@ pub async fn admin_list_tickets(
         .bind(&query.category)
         .bind(query.assigned_to)
         .bind(&query.search)
+        .bind(&query.tag)
+        .bind(query.unassigned)
         .fetch_one(&state.pool)
         .await?
     };
@@ -1316,6 +1546,7 @@ pub async fn admin_update_ticket(
         RETURNING
             id, ticket_number, organization_id,
             (SELECT name FROM organizations WHERE id = organization_id) as organization_name,
+            (SELECT subscription_tier FROM organizations WHERE id = organization_id) as organization_tier,
             user_id,
             (SELECT email FROM users WHERE id = support_tickets.user_id) as user_email,
             contact_name,
@@ -1324,8 +1555,10 @@ pub async fn admin_update_ticket(
             subject, category::text, status::text, priority::text,
             assigned_to,
             (SELECT email FROM users WHERE id = assigned_to) as assigned_to_email,
+            tags,
             (SELECT COUNT(*) FROM ticket_messages WHERE ticket_id = support_tickets.id) as message_count,
-            created_at, updated_at, resolved_at, closed_at
+            created_at, updated_at, resolved_at, closed_at,
+            source, original_email_from, original_email_to
         "#,
     )
     .bind(ticket_id)
@@ -1501,6 +1734,26 @@ pub async fn admin_reply_to_ticket(
         tracing::warn!("Failed to log ticket reply audit: {}", e);
     }
 
+    // Fan out to org-registered outbound webhooks
+    let ticket_org: Option<Option<Uuid>> =
+        sqlx::query_scalar("SELECT organization_id FROM support_tickets WHERE id = $1")
+            .bind(ticket_id)
+            .fetch_optional(&state.pool)
+            .await?;
+    if let Some(Some(org_id)) = ticket_org {
+        plexmcp_shared::org_webhooks::emit(
+            &state.pool,
+            org_id,
+            "ticket.replied",
+            serde_json::json!({
+                "ticket_id": ticket_id,
+                "message_id": message.id,
+                "author": "staff",
+            }),
+        )
+        .await;
+    }
+
     Ok(Json(message.into()))
 }
 
@@ -1557,6 +1810,7 @@ pub async fn admin_assign_ticket(
         RETURNING
             id, ticket_number, organization_id,
             (SELECT name FROM organizations WHERE id = organization_id) as organization_name,
+            (SELECT subscription_tier FROM organizations WHERE id = organization_id) as organization_tier,
             user_id,
             (SELECT email FROM users WHERE id = support_tickets.user_id) as user_email,
             contact_name,
@@ -1565,8 +1819,10 @@ pub async fn admin_assign_ticket(
             subject, category::text, status::text, priority::text,
             assigned_to,
             (SELECT email FROM users WHERE id = assigned_to) as assigned_to_email,
+            tags,
             (SELECT COUNT(*) FROM ticket_messages WHERE ticket_id = support_tickets.id) as message_count,
-            created_at, updated_at, resolved_at, closed_at
+            created_at, updated_at, resolved_at, closed_at,
+            source, original_email_from, original_email_to
         "#,
     )
     .bind(ticket_id)
@@ -1665,6 +1921,11 @@ pub struct AdminTicketStatsEnhanced {
     pub sla_breached: i64,
     pub first_response_met_pct: Option<f64>,
     pub resolution_met_pct: Option<f64>,
+    // CSAT metrics
+    pub csat_responses: i64,
+    pub csat_avg_rating: Option<f64>,
+    /// Percentage of responses rating 4 or 5
+    pub csat_satisfaction_pct: Option<f64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -1677,6 +1938,9 @@ pub struct StaffWorkload {
     pub urgent_tickets: i64,
     pub avg_response_time_hours: Option<f64>,
     pub load_status: String,
+    // CSAT breakdown (responses attributed via rated_staff_id)
+    pub csat_responses: i64,
+    pub csat_avg_rating: Option<f64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -1691,6 +1955,8 @@ pub struct SlaRule {
     pub name: String,
     pub priority: String,
     pub category: Option<String>,
+    /// Subscription tier the rule is scoped to; None = all tiers
+    pub tier: Option<String>,
     pub first_response_hours: i32,
     pub resolution_hours: i32,
     pub business_hours_only: bool,
@@ -1704,6 +1970,8 @@ pub struct CreateSlaRuleRequest {
     pub name: String,
     pub priority: String,
     pub category: Option<String>,
+    /// Scope the rule to a subscription tier (free/pro/team/enterprise)
+    pub tier: Option<String>,
     pub first_response_hours: i32,
     pub resolution_hours: i32,
     pub business_hours_only: Option<bool>,
@@ -1718,6 +1986,36 @@ pub struct UpdateSlaRuleRequest {
     pub is_active: Option<bool>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct LifecyclePolicy {
+    pub id: Uuid,
+    pub org_id: Option<Uuid>,
+    pub category: Option<String>,
+    pub reminder_after_days: i32,
+    pub close_after_days: i32,
+    pub reopen_window_days: i32,
+    pub is_active: bool,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateLifecyclePolicyRequest {
+    pub org_id: Option<Uuid>,
+    pub category: Option<String>,
+    pub reminder_after_days: i32,
+    pub close_after_days: i32,
+    pub reopen_window_days: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateLifecyclePolicyRequest {
+    pub reminder_after_days: Option<i32>,
+    pub close_after_days: Option<i32>,
+    pub reopen_window_days: Option<i32>,
+    pub is_active: Option<bool>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct TicketTemplate {
     pub id: Uuid,
@@ -1812,6 +2110,7 @@ struct SlaRuleRow {
     name: String,
     priority: String,
     category: Option<String>,
+    tier: Option<String>,
     first_response_hours: i32,
     resolution_hours: i32,
     business_hours_only: bool,
@@ -1819,6 +2118,33 @@ struct SlaRuleRow {
     created_at: OffsetDateTime,
 }
 
+#[derive(Debug, FromRow)]
+struct LifecyclePolicyRow {
+    id: Uuid,
+    org_id: Option<Uuid>,
+    category: Option<String>,
+    reminder_after_days: i32,
+    close_after_days: i32,
+    reopen_window_days: i32,
+    is_active: bool,
+    created_at: OffsetDateTime,
+}
+
+impl From<LifecyclePolicyRow> for LifecyclePolicy {
+    fn from(r: LifecyclePolicyRow) -> Self {
+        Self {
+            id: r.id,
+            org_id: r.org_id,
+            category: r.category,
+            reminder_after_days: r.reminder_after_days,
+            close_after_days: r.close_after_days,
+            reopen_window_days: r.reopen_window_days,
+            is_active: r.is_active,
+            created_at: r.created_at,
+        }
+    }
+}
+
 #[derive(Debug, FromRow)]
 struct TemplateRow {
     id: Uuid,
@@ -1853,6 +2179,8 @@ struct WorkloadRow {
     open_tickets: i64,
     urgent_tickets: i64,
     avg_response_time_hours: Option<f64>,
+    csat_responses: i64,
+    csat_avg_rating: Option<f64>,
 }
 
 // =============================================================================
@@ -1868,7 +2196,7 @@ pub async fn admin_get_ticket_stats_enhanced(
 
     // Get all stats in one query
     #[allow(clippy::type_complexity)]
-    let stats: (i64, i64, i64, i64, i64, i64, i64, Option<f64>, i64, i64, Option<f64>, Option<f64>) = sqlx::query_as(
+    let stats: (i64, i64, i64, i64, i64, i64, i64, Option<f64>, i64, i64, Option<f64>, Option<f64>, i64, Option<f64>, Option<f64>) = sqlx::query_as(
         r#"
         SELECT
             COUNT(*) as total_tickets,
@@ -1895,7 +2223,12 @@ pub async fn admin_get_ticket_stats_enhanced(
                 NULLIF(COUNT(*) FILTER (WHERE first_response_at IS NOT NULL)::float, 0) as first_response_met_pct,
             -- Resolution SLA met percentage
             100.0 * COUNT(*) FILTER (WHERE resolved_at IS NOT NULL AND resolution_breached = false)::float /
-                NULLIF(COUNT(*) FILTER (WHERE resolved_at IS NOT NULL)::float, 0) as resolution_met_pct
+                NULLIF(COUNT(*) FILTER (WHERE resolved_at IS NOT NULL)::float, 0) as resolution_met_pct,
+            -- CSAT metrics
+            (SELECT COUNT(*) FROM csat_responses) as csat_responses,
+            (SELECT AVG(rating)::double precision FROM csat_responses) as csat_avg_rating,
+            (SELECT 100.0 * COUNT(*) FILTER (WHERE rating >= 4)::float / NULLIF(COUNT(*)::float, 0)
+             FROM csat_responses) as csat_satisfaction_pct
         FROM support_tickets
         "#,
     )
@@ -1915,6 +2248,9 @@ pub async fn admin_get_ticket_stats_enhanced(
         sla_breached: stats.9,
         first_response_met_pct: stats.10,
         resolution_met_pct: stats.11,
+        csat_responses: stats.12,
+        csat_avg_rating: stats.13,
+        csat_satisfaction_pct: stats.14,
     }))
 }
 
@@ -1942,7 +2278,9 @@ pub async fn admin_get_workload(
                 CASE WHEN tm.created_at IS NOT NULL
                 THEN EXTRACT(EPOCH FROM (tm.created_at - t.created_at)) / 3600
                 END
-            )::double precision as avg_response_time_hours
+            )::double precision as avg_response_time_hours,
+            cs.csat_responses,
+            cs.csat_avg_rating
         FROM users u
         LEFT JOIN support_tickets t ON t.assigned_to = u.id
         LEFT JOIN LATERAL (
@@ -1950,8 +2288,14 @@ pub async fn admin_get_workload(
             FROM ticket_messages
             WHERE ticket_id = t.id AND is_admin_reply = true
         ) tm ON true
+        LEFT JOIN LATERAL (
+            SELECT COUNT(*) as csat_responses,
+                   AVG(rating)::double precision as csat_avg_rating
+            FROM csat_responses
+            WHERE rated_staff_id = u.id
+        ) cs ON true
         WHERE u.platform_role IN ('admin', 'superadmin', 'staff')
-        GROUP BY u.id, u.email
+        GROUP BY u.id, u.email, cs.csat_responses, cs.csat_avg_rating
         ORDER BY assigned_tickets DESC
         "#,
     )
@@ -1978,6 +2322,8 @@ pub async fn admin_get_workload(
                 urgent_tickets: w.urgent_tickets,
                 avg_response_time_hours: w.avg_response_time_hours,
                 load_status,
+                csat_responses: w.csat_responses,
+                csat_avg_rating: w.csat_avg_rating,
             }
         })
         .collect();
@@ -2036,7 +2382,7 @@ pub async fn admin_list_sla_rules(
 
     let rules: Vec<SlaRuleRow> = sqlx::query_as(
         r#"
-        SELECT id, name, priority::text, category::text, first_response_hours,
+        SELECT id, name, priority::text, category::text, tier, first_response_hours,
                resolution_hours, business_hours_only, is_active, created_at
         FROM sla_rules
         ORDER BY
@@ -2059,6 +2405,7 @@ pub async fn admin_list_sla_rules(
                 name: r.name,
                 priority: r.priority,
                 category: r.category,
+                tier: r.tier,
                 first_response_hours: r.first_response_hours,
                 resolution_hours: r.resolution_hours,
                 business_hours_only: r.business_hours_only,
@@ -2077,17 +2424,27 @@ pub async fn admin_create_sla_rule(
 ) -> ApiResult<Json<SlaRule>> {
     require_platform_admin(&state.pool, &auth_user, true).await?;
 
+    if let Some(tier) = &req.tier {
+        if !["free", "pro", "team", "enterprise"].contains(&tier.as_str()) {
+            return Err(ApiError::Validation(format!(
+                "Unknown subscription tier: {}",
+                tier
+            )));
+        }
+    }
+
     let rule: SlaRuleRow = sqlx::query_as(
         r#"
-        INSERT INTO sla_rules (name, priority, category, first_response_hours, resolution_hours, business_hours_only)
-        VALUES ($1, $2::ticket_priority, $3::ticket_category, $4, $5, $6)
-        RETURNING id, name, priority::text, category::text, first_response_hours,
+        INSERT INTO sla_rules (name, priority, category, tier, first_response_hours, resolution_hours, business_hours_only)
+        VALUES ($1, $2::ticket_priority, $3::ticket_category, $4, $5, $6, $7)
+        RETURNING id, name, priority::text, category::text, tier, first_response_hours,
                   resolution_hours, business_hours_only, is_active, created_at
         "#,
     )
     .bind(&req.name)
     .bind(&req.priority)
     .bind(&req.category)
+    .bind(&req.tier)
     .bind(req.first_response_hours)
     .bind(req.resolution_hours)
     .bind(req.business_hours_only.unwrap_or(true))
@@ -2099,6 +2456,7 @@ pub async fn admin_create_sla_rule(
         name: rule.name,
         priority: rule.priority,
         category: rule.category,
+        tier: rule.tier,
         first_response_hours: rule.first_response_hours,
         resolution_hours: rule.resolution_hours,
         business_hours_only: rule.business_hours_only,
@@ -2126,7 +2484,7 @@ pub async fn admin_update_sla_rule(
             is_active = COALESCE($6, is_active),
             updated_at = NOW()
         WHERE id = $1
-        RETURNING id, name, priority::text, category::text, first_response_hours,
+        RETURNING id, name, priority::text, category::text, tier, first_response_hours,
                   resolution_hours, business_hours_only, is_active, created_at
         "#,
     )
@@ -2145,6 +2503,7 @@ pub async fn admin_update_sla_rule(
         name: rule.name,
         priority: rule.priority,
         category: rule.category,
+        tier: rule.tier,
         first_response_hours: rule.first_response_hours,
         resolution_hours: rule.resolution_hours,
         business_hours_only: rule.business_hours_only,
@@ -2153,6 +2512,117 @@ pub async fn admin_update_sla_rule(
     }))
 }
 
+// =============================================================================
+// Lifecycle Policy CRUD
+// =============================================================================
+
+fn validate_lifecycle_days(days: i32, field: &str) -> ApiResult<()> {
+    if days <= 0 {
+        return Err(ApiError::Validation(format!(
+            "{} must be a positive number of days",
+            field
+        )));
+    }
+    Ok(())
+}
+
+/// List ticket lifecycle policies (admin)
+pub async fn admin_list_lifecycle_policies(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<Vec<LifecyclePolicy>>> {
+    require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    let policies: Vec<LifecyclePolicyRow> = sqlx::query_as(
+        r#"
+        SELECT id, org_id, category::text, reminder_after_days, close_after_days,
+               reopen_window_days, is_active, created_at
+        FROM ticket_lifecycle_policies
+        ORDER BY org_id NULLS FIRST, category NULLS FIRST
+        "#,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(policies.into_iter().map(Into::into).collect()))
+}
+
+/// Create ticket lifecycle policy override (admin)
+pub async fn admin_create_lifecycle_policy(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<CreateLifecyclePolicyRequest>,
+) -> ApiResult<Json<LifecyclePolicy>> {
+    require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    validate_lifecycle_days(req.reminder_after_days, "reminder_after_days")?;
+    validate_lifecycle_days(req.close_after_days, "close_after_days")?;
+    validate_lifecycle_days(req.reopen_window_days, "reopen_window_days")?;
+
+    let policy: LifecyclePolicyRow = sqlx::query_as(
+        r#"
+        INSERT INTO ticket_lifecycle_policies
+            (org_id, category, reminder_after_days, close_after_days, reopen_window_days)
+        VALUES ($1, $2::ticket_category, $3, $4, $5)
+        RETURNING id, org_id, category::text, reminder_after_days, close_after_days,
+                  reopen_window_days, is_active, created_at
+        "#,
+    )
+    .bind(req.org_id)
+    .bind(&req.category)
+    .bind(req.reminder_after_days)
+    .bind(req.close_after_days)
+    .bind(req.reopen_window_days)
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok(Json(policy.into()))
+}
+
+/// Update ticket lifecycle policy (admin)
+pub async fn admin_update_lifecycle_policy(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(policy_id): Path<Uuid>,
+    Json(req): Json<UpdateLifecyclePolicyRequest>,
+) -> ApiResult<Json<LifecyclePolicy>> {
+    require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    if let Some(days) = req.reminder_after_days {
+        validate_lifecycle_days(days, "reminder_after_days")?;
+    }
+    if let Some(days) = req.close_after_days {
+        validate_lifecycle_days(days, "close_after_days")?;
+    }
+    if let Some(days) = req.reopen_window_days {
+        validate_lifecycle_days(days, "reopen_window_days")?;
+    }
+
+    let policy: LifecyclePolicyRow = sqlx::query_as(
+        r#"
+        UPDATE ticket_lifecycle_policies SET
+            reminder_after_days = COALESCE($2, reminder_after_days),
+            close_after_days = COALESCE($3, close_after_days),
+            reopen_window_days = COALESCE($4, reopen_window_days),
+            is_active = COALESCE($5, is_active),
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING id, org_id, category::text, reminder_after_days, close_after_days,
+                  reopen_window_days, is_active, created_at
+        "#,
+    )
+    .bind(policy_id)
+    .bind(req.reminder_after_days)
+    .bind(req.close_after_days)
+    .bind(req.reopen_window_days)
+    .bind(req.is_active)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(policy.into()))
+}
+
 // =============================================================================
 // Template CRUD
 // =============================================================================
@@ -2556,29 +3026,30 @@ pub async fn admin_reply_with_internal(
         .bind(ticket_id)
         .execute(&state.pool)
         .await?;
+    }
 
-        // Broadcast new message to WebSocket subscribers (only for non-internal messages)
-        state
-            .ws_state
-            .rooms
-            .broadcast(
-                &ticket_id,
-                ServerEvent::NewMessage {
+    // Broadcast new message to WebSocket subscribers. The room manager
+    // filters internal notes so only staff-level connections receive them.
+    state
+        .ws_state
+        .rooms
+        .broadcast(
+            &ticket_id,
+            ServerEvent::NewMessage {
+                ticket_id,
+                message: TicketMessageEvent {
+                    id: message.id,
                     ticket_id,
-                    message: TicketMessageEvent {
-                        id: message.id,
-                        ticket_id,
-                        sender_id: message.sender_id,
-                        sender_name: None, // Client can resolve via sender_id
-                        is_admin_reply: message.is_admin_reply,
-                        is_internal: false, // Only non-internal messages are broadcast
-                        content: message.content.clone(),
-                        created_at: message.created_at,
-                    },
+                    sender_id: message.sender_id,
+                    sender_name: None, // Client can resolve via sender_id
+                    is_admin_reply: message.is_admin_reply,
+                    is_internal: req.is_internal,
+                    content: message.content.clone(),
+                    created_at: message.created_at,
                 },
-            )
-            .await;
-    }
+            },
+        )
+        .await;
 
     // Send email reply for email-sourced tickets (Day 3)
     // Only send if: 1) ticket source is 'email' AND 2) message is not internal
@@ -2938,3 +3409,1215 @@ async fn store_outbound_email_metadata(
 
     Ok(())
 }
+
+// =============================================================================
+// Programmatic API (API-key authenticated, scoped support:write)
+// =============================================================================
+
+/// Scope an API key must carry to use the programmatic support endpoints
+const SUPPORT_WRITE_SCOPE: &str = "support:write";
+
+/// Resolve the org behind an API key and enforce the support:write scope
+///
+/// These endpoints are machine-only: JWT sessions should use the regular
+/// ticket routes so replies are attributed to a user.
+async fn require_support_write(
+    state: &AppState,
+    auth_user: &AuthUser,
+) -> Result<(Uuid, Uuid), ApiError> {
+    let AuthMethod::ApiKey { key_id } = auth_user.auth_method else {
+        return Err(ApiError::Forbidden);
+    };
+    let org_id = auth_user.org_id.ok_or(ApiError::Forbidden)?;
+
+    let scopes: serde_json::Value = sqlx::query_scalar("SELECT scopes FROM api_keys WHERE id = $1")
+        .bind(key_id)
+        .fetch_one(&state.pool)
+        .await?;
+    let scopes: Vec<String> = serde_json::from_value(scopes).unwrap_or_default();
+
+    if !scopes.iter().any(|s| s == SUPPORT_WRITE_SCOPE) {
+        tracing::warn!(
+            api_key_id = %key_id,
+            "Programmatic support call denied: key lacks support:write scope"
+        );
+        return Err(ApiError::Forbidden);
+    }
+
+    Ok((org_id, key_id))
+}
+
+/// Notify staff that a machine-created ticket arrived
+///
+/// Pushes a TicketCreated event to every connected staff WebSocket and
+/// fires the support Slack webhook when one is configured. Both paths are
+/// best-effort; the ticket is already committed.
+async fn notify_staff_ticket_created(state: &AppState, ticket: &SupportTicket) {
+    let event = ServerEvent::TicketCreated {
+        ticket_id: ticket.id,
+        ticket_number: ticket.ticket_number.clone(),
+        organization_id: ticket.organization_id,
+        subject: ticket.subject.clone(),
+        priority: ticket.priority.clone(),
+        source: ticket.source.clone().unwrap_or_else(|| "api".to_string()),
+    };
+
+    let connections = state.ws_state.connections.read().await;
+    for conn in connections.values() {
+        if conn.staff_level >= crate::websocket::connection::StaffLevel::Staff {
+            let _ = conn.send(event.clone());
+        }
+    }
+    drop(connections);
+
+    if let Some(webhook_url) = state.support_webhook_url.clone() {
+        let payload = serde_json::json!({
+            "text": format!(
+                ":ticket: *New API-created ticket {}*\n*Subject:* {}\n*Priority:* {}",
+                ticket.ticket_number, ticket.subject, ticket.priority
+            ),
+        });
+        tokio::spawn(async move {
+            let client = reqwest::Client::new();
+            match client.post(&webhook_url).json(&payload).send().await {
+                Ok(resp) if !resp.status().is_success() => {
+                    tracing::error!(
+                        status = %resp.status(),
+                        "Support Slack webhook returned an error"
+                    );
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "Failed to send support Slack webhook");
+                }
+                _ => {}
+            }
+        });
+    }
+}
+
+/// Create a ticket on behalf of the org (machine caller)
+///
+/// The ticket carries no user attribution: `user_id` is NULL and `source`
+/// is 'api', so staff can tell automation traffic from human reports.
+pub async fn api_create_ticket(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<CreateTicketRequest>,
+) -> ApiResult<Json<SupportTicket>> {
+    let (org_id, api_key_id) = require_support_write(&state, &auth_user).await?;
+
+    // Same per-org budget as the dashboard endpoint - automation shares it
+    match state.rate_limiter.check_ticket_creation(org_id).await {
+        Ok(result) if !result.allowed => {
+            tracing::warn!(org_id = %org_id, "api_create_ticket: Rate limit exceeded for organization");
+            let retry_after = result.retry_after_seconds.unwrap_or(60);
+            return Err(ApiError::TooManyRequests(format!(
+                "Too many support tickets created. Please try again in {} seconds.",
+                retry_after
+            )));
+        }
+        Err(e) => {
+            tracing::error!(error = ?e, "api_create_ticket: Rate limit check failed, allowing request");
+        }
+        _ => {}
+    }
+
+    const MAX_SUBJECT_LENGTH: usize = 500;
+    const MAX_CONTENT_LENGTH: usize = 50_000;
+
+    if req.subject.trim().is_empty() {
+        return Err(ApiError::BadRequest("Subject cannot be empty".into()));
+    }
+    if req.subject.len() > MAX_SUBJECT_LENGTH {
+        return Err(ApiError::BadRequest(format!(
+            "Subject too long (max {} characters)",
+            MAX_SUBJECT_LENGTH
+        )));
+    }
+    if req.content.trim().is_empty() {
+        return Err(ApiError::BadRequest("Content cannot be empty".into()));
+    }
+    if req.content.len() > MAX_CONTENT_LENGTH {
+        return Err(ApiError::BadRequest(format!(
+            "Content too long (max {} characters)",
+            MAX_CONTENT_LENGTH
+        )));
+    }
+
+    let ticket: TicketRow = sqlx::query_as(
+        r#"
+        INSERT INTO support_tickets (organization_id, user_id, subject, category, priority, source)
+        VALUES ($1, NULL, $2, $3::ticket_category, $4::ticket_priority, 'api')
+        RETURNING id, ticket_number, organization_id, user_id, subject,
+                  category::text, status::text, priority::text, assigned_to,
+                  created_at, updated_at, resolved_at, closed_at,
+                  source, original_email_from, original_email_to
+        "#,
+    )
+    .bind(org_id)
+    .bind(&req.subject)
+    .bind(req.category.as_str())
+    .bind(req.priority.as_str())
+    .fetch_one(&state.pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO ticket_messages (ticket_id, sender_id, is_admin_reply, content)
+        VALUES ($1, NULL, false, $2)
+        "#,
+    )
+    .bind(ticket.id)
+    .bind(&req.content)
+    .execute(&state.pool)
+    .await?;
+
+    let ticket: SupportTicket = ticket.into();
+    notify_staff_ticket_created(&state, &ticket).await;
+
+    tracing::info!(
+        ticket_id = %ticket.id,
+        ticket_number = %ticket.ticket_number,
+        org_id = %org_id,
+        api_key_id = %api_key_id,
+        "Support ticket created via API"
+    );
+
+    Ok(Json(ticket))
+}
+
+/// Reply to an org ticket as the org (machine caller)
+pub async fn api_reply_to_ticket(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(ticket_id): Path<Uuid>,
+    Json(req): Json<ReplyToTicketRequest>,
+) -> ApiResult<Json<TicketMessage>> {
+    let (org_id, api_key_id) = require_support_write(&state, &auth_user).await?;
+
+    if req.content.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Message content cannot be empty".into(),
+        ));
+    }
+
+    let ticket_exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM support_tickets WHERE id = $1 AND organization_id = $2)",
+    )
+    .bind(ticket_id)
+    .bind(org_id)
+    .fetch_one(&state.pool)
+    .await?;
+
+    if !ticket_exists {
+        return Err(ApiError::NotFound);
+    }
+
+    // Auto-closed tickets can only be reopened within the reopen window
+    let expired_reopen: Option<(OffsetDateTime,)> = sqlx::query_as(
+        r#"
+        SELECT reopen_deadline
+        FROM support_tickets
+        WHERE id = $1
+          AND status = 'closed'::ticket_status
+          AND auto_closed_at IS NOT NULL
+          AND reopen_deadline < NOW()
+        "#,
+    )
+    .bind(ticket_id)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    if expired_reopen.is_some() {
+        return Err(ApiError::BadRequest(
+            "This ticket was closed due to inactivity and can no longer be reopened. Please open a new ticket.".into(),
+        ));
+    }
+
+    let message: MessageRow = sqlx::query_as(
+        r#"
+        INSERT INTO ticket_messages (ticket_id, sender_id, is_admin_reply, content)
+        VALUES ($1, NULL, false, $2)
+        RETURNING id, ticket_id, sender_id, is_admin_reply, content, created_at
+        "#,
+    )
+    .bind(ticket_id)
+    .bind(&req.content)
+    .fetch_one(&state.pool)
+    .await?;
+
+    // Reopen resolved/closed tickets, clearing lifecycle automation markers
+    sqlx::query(
+        r#"
+        UPDATE support_tickets
+        SET status = 'open'::ticket_status, updated_at = NOW(),
+            inactivity_reminder_sent_at = NULL, auto_closed_at = NULL, reopen_deadline = NULL
+        WHERE id = $1 AND status IN ('resolved'::ticket_status, 'closed'::ticket_status)
+        "#,
+    )
+    .bind(ticket_id)
+    .execute(&state.pool)
+    .await?;
+
+    // Broadcast to WebSocket subscribers; sender_id None marks automation
+    state
+        .ws_state
+        .rooms
+        .broadcast(
+            &ticket_id,
+            ServerEvent::NewMessage {
+                ticket_id,
+                message: TicketMessageEvent {
+                    id: message.id,
+                    ticket_id,
+                    sender_id: message.sender_id,
+                    sender_name: None,
+                    is_admin_reply: message.is_admin_reply,
+                    is_internal: false,
+                    content: message.content.clone(),
+                    created_at: message.created_at,
+                },
+            },
+        )
+        .await;
+
+    tracing::info!(
+        ticket_id = %ticket_id,
+        org_id = %org_id,
+        api_key_id = %api_key_id,
+        "API reply added to support ticket"
+    );
+
+    Ok(Json(message.into()))
+}
+
+// =============================================================================
+// Org Support History Export
+// =============================================================================
+
+/// Signed export download URLs are valid for 15 minutes
+const EXPORT_URL_EXPIRY_SECS: u64 = 900;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSupportExportRequest {
+    /// "json" or "csv"
+    #[serde(default = "default_export_format")]
+    pub format: String,
+}
+
+fn default_export_format() -> String {
+    "json".to_string()
+}
+
+#[derive(Debug, Serialize)]
+pub struct SupportExportResponse {
+    pub id: Uuid,
+    pub format: String,
+    pub status: String,
+    pub ticket_count: Option<i32>,
+    pub message_count: Option<i32>,
+    pub attachment_count: Option<i32>,
+    pub error: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub completed_at: Option<OffsetDateTime>,
+    /// Time-limited download URL, present once the export completed
+    pub download_url: Option<String>,
+}
+
+#[derive(Debug, FromRow)]
+struct SupportExportRow {
+    id: Uuid,
+    format: String,
+    status: String,
+    storage_key: Option<String>,
+    ticket_count: Option<i32>,
+    message_count: Option<i32>,
+    attachment_count: Option<i32>,
+    error: Option<String>,
+    created_at: OffsetDateTime,
+    completed_at: Option<OffsetDateTime>,
+}
+
+impl SupportExportRow {
+    fn into_response(self, download_url: Option<String>) -> SupportExportResponse {
+        SupportExportResponse {
+            id: self.id,
+            format: self.format,
+            status: self.status,
+            ticket_count: self.ticket_count,
+            message_count: self.message_count,
+            attachment_count: self.attachment_count,
+            error: self.error,
+            created_at: self.created_at,
+            completed_at: self.completed_at,
+            download_url,
+        }
+    }
+}
+
+/// Attachment manifest entry - metadata only, the files themselves are
+/// downloaded per ticket through the attachments API
+#[derive(Debug, Serialize, FromRow)]
+struct ExportAttachmentEntry {
+    id: Uuid,
+    ticket_id: Uuid,
+    filename: String,
+    content_type: String,
+    size_bytes: i64,
+    scan_status: String,
+    #[serde(with = "time::serde::rfc3339")]
+    created_at: OffsetDateTime,
+}
+
+/// One ticket with its customer-visible conversation and attachment manifest
+#[derive(Debug, Serialize)]
+struct ExportedTicketEntry {
+    #[serde(flatten)]
+    ticket: SupportTicket,
+    messages: Vec<TicketMessage>,
+    attachments: Vec<ExportAttachmentEntry>,
+}
+
+/// Request an async export of the org's full support history
+///
+/// `POST /support/export` - org owners/admins only. Exports every ticket
+/// with its messages (internal notes excluded) and an attachments
+/// manifest as JSON or CSV. The export runs in the background; poll
+/// `GET /support/export/:export_id` for the signed download URL.
+pub async fn create_support_export(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    headers: HeaderMap,
+    Json(req): Json<CreateSupportExportRequest>,
+) -> ApiResult<Json<SupportExportResponse>> {
+    let user_id = auth_user.user_id.ok_or(ApiError::Unauthorized)?;
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let format = req.format.to_lowercase();
+    if !["json", "csv"].contains(&format.as_str()) {
+        return Err(ApiError::Validation(
+            "format must be \"json\" or \"csv\"".to_string(),
+        ));
+    }
+
+    if state.storage.is_none() {
+        tracing::error!("Support export rejected: storage backend not available");
+        return Err(ApiError::Internal);
+    }
+
+    // One export at a time per org keeps the background load bounded
+    let in_flight: Option<(Uuid,)> = sqlx::query_as(
+        "SELECT id FROM support_exports WHERE org_id = $1 AND status IN ('pending', 'processing')",
+    )
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await?;
+    if in_flight.is_some() {
+        return Err(ApiError::Conflict(
+            "An export is already in progress for this organization".to_string(),
+        ));
+    }
+
+    let export: SupportExportRow = sqlx::query_as(
+        r#"
+        INSERT INTO support_exports (org_id, requested_by, format)
+        VALUES ($1, $2, $3)
+        RETURNING id, format, status, storage_key, ticket_count, message_count,
+                  attachment_count, error, created_at, completed_at
+        "#,
+    )
+    .bind(org_id)
+    .bind(user_id)
+    .bind(&format)
+    .fetch_one(&state.pool)
+    .await?;
+
+    // Compliance: data exports land in the org audit trail
+    let (ip_address, user_agent) = crate::routes::auth::extract_auth_audit_context(&headers);
+    let _ = crate::routes::auth::log_auth_event(
+        &state.pool,
+        Some(user_id),
+        "support_export_requested",
+        auth_user.email.clone(),
+        Some(serde_json::json!({
+            "export_id": export.id,
+            "org_id": org_id,
+            "format": format,
+        })),
+        event_type::ADMIN_ACTION,
+        severity::WARNING,
+        ip_address,
+        user_agent,
+        true,
+        None,
+        None,
+    )
+    .await;
+
+    let export_id = export.id;
+    let job_state = state.clone();
+    let job_format = format.clone();
+    tokio::spawn(async move {
+        run_support_export(job_state, export_id, org_id, &job_format).await;
+    });
+
+    tracing::info!(
+        export_id = %export_id,
+        org_id = %org_id,
+        user_id = %user_id,
+        format = %format,
+        "Support history export requested"
+    );
+
+    Ok(Json(export.into_response(None)))
+}
+
+/// Get the status of a support history export
+///
+/// Returns a time-limited signed download URL once the export completed.
+pub async fn get_support_export(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(export_id): Path<Uuid>,
+) -> ApiResult<Json<SupportExportResponse>> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let export: SupportExportRow = sqlx::query_as(
+        r#"
+        SELECT id, format, status, storage_key, ticket_count, message_count,
+               attachment_count, error, created_at, completed_at
+        FROM support_exports
+        WHERE id = $1 AND org_id = $2
+        "#,
+    )
+    .bind(export_id)
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    let download_url = match (&export.status, &export.storage_key, &state.storage) {
+        (status, Some(storage_key), Some(storage)) if status == "completed" => Some(
+            storage
+                .signed_get_url(storage_key, EXPORT_URL_EXPIRY_SECS)
+                .await
+                .map_err(|e| {
+                    tracing::error!(key = %storage_key, error = %e, "Failed to sign export URL");
+                    ApiError::Internal
+                })?,
+        ),
+        _ => None,
+    };
+
+    Ok(Json(export.into_response(download_url)))
+}
+
+/// Background job: gather the org's support history, serialize it, and
+/// upload it to the storage backend
+async fn run_support_export(state: AppState, export_id: Uuid, org_id: Uuid, format: &str) {
+    let _ = sqlx::query("UPDATE support_exports SET status = 'processing' WHERE id = $1")
+        .bind(export_id)
+        .execute(&state.pool)
+        .await;
+
+    match build_and_store_export(&state, export_id, org_id, format).await {
+        Ok((storage_key, ticket_count, message_count, attachment_count)) => {
+            let _ = sqlx::query(
+                r#"
+                UPDATE support_exports
+                SET status = 'completed', storage_key = $1, ticket_count = $2,
+                    message_count = $3, attachment_count = $4, completed_at = NOW()
+                WHERE id = $5
+                "#,
+            )
+            .bind(&storage_key)
+            .bind(ticket_count)
+            .bind(message_count)
+            .bind(attachment_count)
+            .bind(export_id)
+            .execute(&state.pool)
+            .await;
+
+            tracing::info!(
+                export_id = %export_id,
+                org_id = %org_id,
+                tickets = ticket_count,
+                messages = message_count,
+                attachments = attachment_count,
+                "Support history export completed"
+            );
+        }
+        Err(e) => {
+            tracing::error!(
+                export_id = %export_id,
+                org_id = %org_id,
+                error = %e,
+                "Support history export failed"
+            );
+            let _ = sqlx::query(
+                "UPDATE support_exports SET status = 'failed', error = $1, completed_at = NOW() WHERE id = $2",
+            )
+            .bind(e.to_string())
+            .bind(export_id)
+            .execute(&state.pool)
+            .await;
+        }
+    }
+}
+
+async fn build_and_store_export(
+    state: &AppState,
+    export_id: Uuid,
+    org_id: Uuid,
+    format: &str,
+) -> Result<(String, i32, i32, i32), Box<dyn std::error::Error + Send + Sync>> {
+    let tickets: Vec<TicketRow> = sqlx::query_as(
+        r#"
+        SELECT id, ticket_number, organization_id, user_id, subject,
+               category::text, status::text, priority::text, assigned_to,
+               created_at, updated_at, resolved_at, closed_at,
+               source, original_email_from, original_email_to
+        FROM support_tickets
+        WHERE organization_id = $1
+        ORDER BY created_at ASC
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    // Customer-visible conversation only - internal staff notes stay out
+    let messages: Vec<MessageRow> = sqlx::query_as(
+        r#"
+        SELECT m.id, m.ticket_id, m.sender_id, m.is_admin_reply, m.content, m.created_at
+        FROM ticket_messages m
+        JOIN support_tickets t ON t.id = m.ticket_id
+        WHERE t.organization_id = $1 AND NOT COALESCE(m.is_internal, FALSE)
+        ORDER BY m.created_at ASC
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let attachments: Vec<ExportAttachmentEntry> = sqlx::query_as(
+        r#"
+        SELECT a.id, a.ticket_id, a.filename, a.content_type, a.size_bytes,
+               a.scan_status, a.created_at
+        FROM ticket_attachments a
+        JOIN support_tickets t ON t.id = a.ticket_id
+        WHERE t.organization_id = $1
+        ORDER BY a.created_at ASC
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let ticket_count = tickets.len() as i32;
+    let message_count = messages.len() as i32;
+    let attachment_count = attachments.len() as i32;
+
+    let (body, content_type, extension) = if format == "csv" {
+        (
+            render_export_csv(&tickets, &messages, &attachments),
+            "text/csv; charset=utf-8",
+            "csv",
+        )
+    } else {
+        (
+            render_export_json(export_id, org_id, tickets, messages, attachments)?,
+            "application/json",
+            "json",
+        )
+    };
+
+    let storage = state
+        .storage
+        .as_ref()
+        .ok_or("storage backend not available")?;
+    let storage_key = format!("exports/support/{}/{}.{}", org_id, export_id, extension);
+    storage
+        .put(&storage_key, body.as_bytes(), content_type)
+        .await?;
+
+    Ok((storage_key, ticket_count, message_count, attachment_count))
+}
+
+fn render_export_json(
+    export_id: Uuid,
+    org_id: Uuid,
+    tickets: Vec<TicketRow>,
+    messages: Vec<MessageRow>,
+    attachments: Vec<ExportAttachmentEntry>,
+) -> Result<String, serde_json::Error> {
+    use std::collections::HashMap;
+
+    let mut messages_by_ticket: HashMap<Uuid, Vec<TicketMessage>> = HashMap::new();
+    for message in messages {
+        messages_by_ticket
+            .entry(message.ticket_id)
+            .or_default()
+            .push(message.into());
+    }
+
+    let mut attachments_by_ticket: HashMap<Uuid, Vec<ExportAttachmentEntry>> = HashMap::new();
+    for attachment in attachments {
+        attachments_by_ticket
+            .entry(attachment.ticket_id)
+            .or_default()
+            .push(attachment);
+    }
+
+    let entries: Vec<ExportedTicketEntry> = tickets
+        .into_iter()
+        .map(|row| {
+            let ticket_id = row.id;
+            ExportedTicketEntry {
+                ticket: row.into(),
+                messages: messages_by_ticket.remove(&ticket_id).unwrap_or_default(),
+                attachments: attachments_by_ticket.remove(&ticket_id).unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    let document = serde_json::json!({
+        "export_id": export_id,
+        "org_id": org_id,
+        "exported_at": OffsetDateTime::now_utc()
+            .format(&time::format_description::well_known::Rfc3339)
+            .ok(),
+        "tickets": entries,
+    });
+
+    serde_json::to_string_pretty(&document)
+}
+
+/// Flat single-file CSV: one row per ticket, message, and attachment,
+/// distinguished by record_type, with empty cells where a column doesn't
+/// apply to the record
+fn render_export_csv(
+    tickets: &[TicketRow],
+    messages: &[MessageRow],
+    attachments: &[ExportAttachmentEntry],
+) -> String {
+    use std::collections::HashMap;
+
+    let ticket_numbers: HashMap<Uuid, &str> = tickets
+        .iter()
+        .map(|t| (t.id, t.ticket_number.as_str()))
+        .collect();
+
+    let mut csv = String::from(
+        "record_type,ticket_number,id,subject,category,status,priority,\
+         sender_id,is_admin_reply,content,filename,content_type,size_bytes,\
+         scan_status,created_at\n",
+    );
+
+    for ticket in tickets {
+        csv.push_str(&format!(
+            "ticket,{},{},{},{},{},{},,,,,,,,{}\n",
+            csv_field(&ticket.ticket_number),
+            ticket.id,
+            csv_field(&ticket.subject),
+            ticket.category,
+            ticket.status,
+            ticket.priority,
+            format_rfc3339(ticket.created_at),
+        ));
+    }
+
+    for message in messages {
+        let ticket_number = ticket_numbers
+            .get(&message.ticket_id)
+            .copied()
+            .unwrap_or("");
+        csv.push_str(&format!(
+            "message,{},{},,,,,{},{},{},,,,,{}\n",
+            csv_field(ticket_number),
+            message.id,
+            message
+                .sender_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            message.is_admin_reply,
+            csv_field(&message.content),
+            format_rfc3339(message.created_at),
+        ));
+    }
+
+    for attachment in attachments {
+        let ticket_number = ticket_numbers
+            .get(&attachment.ticket_id)
+            .copied()
+            .unwrap_or("");
+        csv.push_str(&format!(
+            "attachment,{},{},,,,,,,,{},{},{},{},{}\n",
+            csv_field(ticket_number),
+            attachment.id,
+            csv_field(&attachment.filename),
+            csv_field(&attachment.content_type),
+            attachment.size_bytes,
+            attachment.scan_status,
+            format_rfc3339(attachment.created_at),
+        ));
+    }
+
+    csv
+}
+
+/// CSV-escape a field (quote when it contains separators or quotes)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn format_rfc3339(dt: OffsetDateTime) -> String {
+    dt.format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_else(|_| dt.to_string())
+}
+
+// =============================================================================
+// Ticket Tags & Saved Filters
+// =============================================================================
+
+/// Tag names are lowercase slugs so they read cleanly in query strings
+fn is_valid_tag_name(name: &str) -> bool {
+    (1..=50).contains(&name.len())
+        && name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+fn is_valid_tag_color(color: &str) -> bool {
+    color.len() == 7 && color.starts_with('#') && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct SupportTagResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub color: String,
+    /// Tickets currently carrying this tag
+    pub ticket_count: i64,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTagRequest {
+    pub name: String,
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TicketTagRequest {
+    pub tag: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TicketTagsResponse {
+    pub ticket_id: Uuid,
+    pub tags: Vec<String>,
+}
+
+/// Saved filter definition - the same knobs [`AdminListTicketsQuery`]
+/// accepts, minus paging. `deny_unknown_fields` keeps stored filters
+/// replayable against the list endpoint.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SavedFilterDefinition {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assigned_to: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unassigned: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SaveFilterRequest {
+    pub name: String,
+    pub filters: SavedFilterDefinition,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct SavedFilterResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub filters: serde_json::Value,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+/// Audit log insert for tag operations; failures are logged, not fatal
+async fn record_tag_audit(
+    pool: &PgPool,
+    admin_id: Uuid,
+    action: &str,
+    target_id: Uuid,
+    details: serde_json::Value,
+) {
+    if let Err(e) = sqlx::query(
+        r#"
+        INSERT INTO admin_audit_log (
+            admin_user_id, action, target_type, target_id, details,
+            event_type, severity
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(admin_id)
+    .bind(action)
+    .bind(target_type::TICKET)
+    .bind(target_id)
+    .bind(&details)
+    .bind(event_type::DATA_MODIFICATION)
+    .bind(severity::INFO)
+    .execute(pool)
+    .await
+    {
+        tracing::warn!("Failed to log tag audit: {}", e);
+    }
+}
+
+/// List the tag registry with usage counts (admin)
+pub async fn admin_list_tags(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<Vec<SupportTagResponse>>> {
+    require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    let tags: Vec<SupportTagResponse> = sqlx::query_as(
+        r#"
+        SELECT
+            st.id, st.name, st.color, st.created_at,
+            (SELECT COUNT(*) FROM support_tickets t WHERE t.tags @> ARRAY[st.name]) as ticket_count
+        FROM support_tags st
+        ORDER BY st.name
+        "#,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(tags))
+}
+
+/// Create a tag in the registry (admin)
+pub async fn admin_create_tag(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<CreateTagRequest>,
+) -> ApiResult<Json<SupportTagResponse>> {
+    let admin_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    let name = req.name.trim().to_lowercase();
+    if !is_valid_tag_name(&name) {
+        return Err(ApiError::BadRequest(
+            "Tag names must be 1-50 lowercase letters, digits, hyphens, or underscores".into(),
+        ));
+    }
+    if let Some(ref color) = req.color {
+        if !is_valid_tag_color(color) {
+            return Err(ApiError::BadRequest(
+                "Tag color must be a hex value like #6366f1".into(),
+            ));
+        }
+    }
+
+    let tag: Option<SupportTagResponse> = sqlx::query_as(
+        r#"
+        INSERT INTO support_tags (name, color, created_by)
+        VALUES ($1, COALESCE($2, '#6366f1'), $3)
+        ON CONFLICT (name) DO NOTHING
+        RETURNING id, name, color, created_at, 0::bigint as ticket_count
+        "#,
+    )
+    .bind(&name)
+    .bind(&req.color)
+    .bind(admin_id)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    let tag = tag.ok_or_else(|| ApiError::Conflict(format!("Tag '{}' already exists", name)))?;
+
+    record_tag_audit(
+        &state.pool,
+        admin_id,
+        admin_action::SUPPORT_TAG_REGISTRY_CHANGED,
+        tag.id,
+        serde_json::json!({ "operation": "created", "name": name }),
+    )
+    .await;
+
+    Ok(Json(tag))
+}
+
+/// Delete a tag from the registry and strip it from all tickets (admin)
+pub async fn admin_delete_tag(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(tag_name): Path<String>,
+) -> ApiResult<axum::http::StatusCode> {
+    let admin_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    let deleted: Option<(Uuid,)> =
+        sqlx::query_as("DELETE FROM support_tags WHERE name = $1 RETURNING id")
+            .bind(&tag_name)
+            .fetch_optional(&state.pool)
+            .await?;
+
+    let (tag_id,) = deleted.ok_or(ApiError::NotFound)?;
+
+    // Strip the tag from tickets so the list filter stays consistent
+    let stripped = sqlx::query(
+        "UPDATE support_tickets SET tags = array_remove(tags, $1) WHERE tags @> ARRAY[$1]",
+    )
+    .bind(&tag_name)
+    .execute(&state.pool)
+    .await?;
+
+    record_tag_audit(
+        &state.pool,
+        admin_id,
+        admin_action::SUPPORT_TAG_REGISTRY_CHANGED,
+        tag_id,
+        serde_json::json!({
+            "operation": "deleted",
+            "name": tag_name,
+            "tickets_stripped": stripped.rows_affected(),
+        }),
+    )
+    .await;
+
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
+
+/// Add a registry tag to a ticket (admin)
+pub async fn admin_add_ticket_tag(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(ticket_id): Path<Uuid>,
+    Json(req): Json<TicketTagRequest>,
+) -> ApiResult<Json<TicketTagsResponse>> {
+    let admin_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    let tag = req.tag.trim().to_lowercase();
+    let known: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM support_tags WHERE name = $1)")
+            .bind(&tag)
+            .fetch_one(&state.pool)
+            .await?;
+    if !known {
+        return Err(ApiError::BadRequest(format!(
+            "Unknown tag '{}'; create it in the registry first",
+            tag
+        )));
+    }
+
+    let tags: Option<(Vec<String>,)> = sqlx::query_as(
+        r#"
+        UPDATE support_tickets
+        SET tags = ARRAY(SELECT DISTINCT unnest(tags || ARRAY[$2]) ORDER BY 1),
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING tags
+        "#,
+    )
+    .bind(ticket_id)
+    .bind(&tag)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    let (tags,) = tags.ok_or(ApiError::NotFound)?;
+
+    record_tag_audit(
+        &state.pool,
+        admin_id,
+        admin_action::TICKET_TAGS_CHANGED,
+        ticket_id,
+        serde_json::json!({ "operation": "added", "tag": tag }),
+    )
+    .await;
+
+    Ok(Json(TicketTagsResponse { ticket_id, tags }))
+}
+
+/// Remove a tag from a ticket (admin)
+pub async fn admin_remove_ticket_tag(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((ticket_id, tag)): Path<(Uuid, String)>,
+) -> ApiResult<Json<TicketTagsResponse>> {
+    let admin_id = require_platform_admin(&state.pool, &auth_user, true).await?;
+
+    let tags: Option<(Vec<String>,)> = sqlx::query_as(
+        r#"
+        UPDATE support_tickets
+        SET tags = array_remove(tags, $2), updated_at = NOW()
+        WHERE id = $1
+        RETURNING tags
+        "#,
+    )
+    .bind(ticket_id)
+    .bind(&tag)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    let (tags,) = tags.ok_or(ApiError::NotFound)?;
+
+    record_tag_audit(
+        &state.pool,
+        admin_id,
+        admin_action::TICKET_TAGS_CHANGED,
+        ticket_id,
+        serde_json::json!({ "operation": "removed", "tag": tag }),
+    )
+    .await;
+
+    Ok(Json(TicketTagsResponse { ticket_id, tags }))
+}
+
+/// List the calling admin's saved ticket filters
+pub async fn admin_list_saved_filters(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<Vec<SavedFilterResponse>>> {
+    let user_id = require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    let filters: Vec<SavedFilterResponse> = sqlx::query_as(
+        r#"
+        SELECT id, name, filters, created_at, updated_at
+        FROM admin_saved_filters
+        WHERE admin_user_id = $1
+        ORDER BY name
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(filters))
+}
+
+/// Create or update a saved filter by name (personal, so staff can save too)
+pub async fn admin_save_filter(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<SaveFilterRequest>,
+) -> ApiResult<Json<SavedFilterResponse>> {
+    let user_id = require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    let name = req.name.trim().to_string();
+    if name.is_empty() || name.len() > 100 {
+        return Err(ApiError::BadRequest(
+            "Filter name must be 1-100 characters".into(),
+        ));
+    }
+
+    // Validate enum-valued fields so one-click views never 400 on replay
+    if let Some(ref status) = req.filters.status {
+        let valid = [
+            "open",
+            "in_progress",
+            "awaiting_response",
+            "resolved",
+            "closed",
+        ];
+        if !valid.contains(&status.as_str()) {
+            return Err(ApiError::BadRequest(format!("Invalid status: {}", status)));
+        }
+    }
+    if let Some(ref priority) = req.filters.priority {
+        if !["low", "medium", "high", "urgent"].contains(&priority.as_str()) {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid priority: {}",
+                priority
+            )));
+        }
+    }
+    if let Some(ref category) = req.filters.category {
+        let valid = [
+            "general",
+            "billing",
+            "technical",
+            "feature_request",
+            "bug_report",
+            "enterprise_inquiry",
+        ];
+        if !valid.contains(&category.as_str()) {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid category: {}",
+                category
+            )));
+        }
+    }
+
+    let filters_json = serde_json::to_value(&req.filters).map_err(|_| ApiError::Internal)?;
+
+    let saved: SavedFilterResponse = sqlx::query_as(
+        r#"
+        INSERT INTO admin_saved_filters (admin_user_id, name, filters)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (admin_user_id, name)
+        DO UPDATE SET filters = EXCLUDED.filters, updated_at = NOW()
+        RETURNING id, name, filters, created_at, updated_at
+        "#,
+    )
+    .bind(user_id)
+    .bind(&name)
+    .bind(&filters_json)
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok(Json(saved))
+}
+
+/// Delete one of the calling admin's saved filters
+pub async fn admin_delete_saved_filter(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(filter_id): Path<Uuid>,
+) -> ApiResult<axum::http::StatusCode> {
+    let user_id = require_platform_admin(&state.pool, &auth_user, false).await?;
+
+    let result =
+        sqlx::query("DELETE FROM admin_saved_filters WHERE id = $1 AND admin_user_id = $2")
+            .bind(filter_id)
+            .bind(user_id)
+            .execute(&state.pool)
+            .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,1026 @@
+//! Inbound support email ingestion
+//!
+//! Public webhook that turns inbound email into support tickets. Replies
+//! are threaded back to their ticket via the RFC 5322 Message-ID chain
+//! recorded in `ticket_email_metadata` (with the ticket number in the
+//! subject as a fallback); anything unthreadable opens a new ticket.
+//! Payloads from Resend's inbound webhook (Svix-signed) and SES
+//! notifications are both accepted — SES deliveries are expected to be
+//! bridged through a forwarder that signs requests with the same Svix
+//! scheme, since this endpoint does not speak SNS subscription
+//! handshakes. Attachment extraction is supported for structured
+//! (Resend) payloads; raw-MIME SES attachments are not unpacked.
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+use uuid::Uuid;
+
+use crate::{
+    error::{ApiError, ApiResult},
+    spam,
+    state::AppState,
+};
+
+/// Inbound message bodies are truncated to the same limit as dashboard
+/// ticket content
+const MAX_CONTENT_LENGTH: usize = 50_000;
+
+/// Subjects are truncated to the dashboard limit
+const MAX_SUBJECT_LENGTH: usize = 500;
+
+/// Attachments beyond this count are dropped with a warning
+const MAX_INBOUND_ATTACHMENTS: usize = 5;
+
+/// Per-attachment size cap, matching dashboard uploads
+const MAX_ATTACHMENT_SIZE_BYTES: usize = 10 * 1024 * 1024;
+
+// =============================================================================
+// Payload Normalization
+// =============================================================================
+
+/// Provider-agnostic view of one inbound email
+#[derive(Debug)]
+struct InboundEmail {
+    /// RFC 5322 Message-ID, used for idempotency and threading
+    message_id: String,
+    /// In-Reply-To plus References, newest first
+    references: Vec<String>,
+    from: String,
+    to: Vec<String>,
+    subject: String,
+    text: Option<String>,
+    html: Option<String>,
+    attachments: Vec<InboundAttachment>,
+}
+
+#[derive(Debug)]
+struct InboundAttachment {
+    filename: String,
+    content_type: String,
+    content_base64: String,
+}
+
+/// Parse a webhook body into the normalized shape, detecting the provider
+/// from the payload structure. Returns None for unrecognized payloads.
+fn parse_inbound_email(body: &str) -> Option<InboundEmail> {
+    let value: serde_json::Value = serde_json::from_str(body).ok()?;
+
+    // SNS envelope: the actual SES notification is a JSON string in Message
+    if value.get("Type").and_then(|t| t.as_str()) == Some("Notification") {
+        let inner: serde_json::Value =
+            serde_json::from_str(value.get("Message")?.as_str()?).ok()?;
+        return parse_ses_notification(&inner);
+    }
+
+    // Bare SES notification (forwarder already unwrapped the envelope)
+    if value.get("mail").is_some() {
+        return parse_ses_notification(&value);
+    }
+
+    // Resend inbound event: {"type": "email.received", "data": {...}}
+    if value
+        .get("type")
+        .and_then(|t| t.as_str())
+        .is_some_and(|t| t.ends_with("received"))
+    {
+        return parse_resend_data(value.get("data")?);
+    }
+
+    None
+}
+
+/// Look up a header by name (case-insensitive) in either of the shapes
+/// providers use: an array of `{name, value}` objects or a flat map
+fn header_value(headers: Option<&serde_json::Value>, name: &str) -> Option<String> {
+    let headers = headers?;
+    if let Some(list) = headers.as_array() {
+        for entry in list {
+            let entry_name = entry.get("name").and_then(|n| n.as_str())?;
+            if entry_name.eq_ignore_ascii_case(name) {
+                return entry
+                    .get("value")
+                    .and_then(|v| v.as_str())
+                    .map(String::from);
+            }
+        }
+        return None;
+    }
+    let map = headers.as_object()?;
+    map.iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .and_then(|(_, v)| v.as_str())
+        .map(String::from)
+}
+
+/// Collect In-Reply-To plus every Message-ID in References
+fn collect_references(headers: Option<&serde_json::Value>) -> Vec<String> {
+    let mut refs = Vec::new();
+    if let Some(in_reply_to) = header_value(headers, "In-Reply-To") {
+        refs.push(in_reply_to.trim().to_string());
+    }
+    if let Some(references) = header_value(headers, "References") {
+        for id in references.split_whitespace().rev() {
+            let id = id.to_string();
+            if !refs.contains(&id) {
+                refs.push(id);
+            }
+        }
+    }
+    refs
+}
+
+fn parse_resend_data(data: &serde_json::Value) -> Option<InboundEmail> {
+    let headers = data.get("headers");
+    let message_id = data
+        .get("message_id")
+        .and_then(|m| m.as_str())
+        .map(String::from)
+        .or_else(|| header_value(headers, "Message-ID"))?;
+
+    let from = data.get("from").and_then(|f| f.as_str())?.to_string();
+    let to = match data.get("to") {
+        Some(serde_json::Value::Array(list)) => list
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(String::from)
+            .collect(),
+        Some(serde_json::Value::String(addr)) => vec![addr.clone()],
+        _ => Vec::new(),
+    };
+
+    let attachments = data
+        .get("attachments")
+        .and_then(|a| a.as_array())
+        .map(|list| {
+            list.iter()
+                .filter_map(|a| {
+                    Some(InboundAttachment {
+                        filename: a.get("filename").and_then(|f| f.as_str())?.to_string(),
+                        content_type: a
+                            .get("content_type")
+                            .and_then(|c| c.as_str())
+                            .unwrap_or("application/octet-stream")
+                            .to_string(),
+                        content_base64: a.get("content").and_then(|c| c.as_str())?.to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(InboundEmail {
+        message_id,
+        references: collect_references(headers),
+        from,
+        to,
+        subject: data
+            .get("subject")
+            .and_then(|s| s.as_str())
+            .unwrap_or("")
+            .to_string(),
+        text: data.get("text").and_then(|t| t.as_str()).map(String::from),
+        html: data.get("html").and_then(|h| h.as_str()).map(String::from),
+        attachments,
+    })
+}
+
+fn parse_ses_notification(inner: &serde_json::Value) -> Option<InboundEmail> {
+    let mail = inner.get("mail")?;
+    let common = mail.get("commonHeaders");
+    let headers = mail.get("headers");
+
+    // commonHeaders.messageId carries the RFC 5322 form with angle
+    // brackets; mail.messageId is the bare SES receipt id
+    let message_id = common
+        .and_then(|c| c.get("messageId"))
+        .and_then(|m| m.as_str())
+        .map(String::from)
+        .or_else(|| header_value(headers, "Message-ID"))
+        .or_else(|| {
+            mail.get("messageId")
+                .and_then(|m| m.as_str())
+                .map(|id| format!("<{}@email.amazonses.com>", id))
+        })?;
+
+    let from = common
+        .and_then(|c| c.get("from"))
+        .and_then(|f| f.as_array())
+        .and_then(|list| list.first())
+        .and_then(|f| f.as_str())?
+        .to_string();
+    let to = common
+        .and_then(|c| c.get("to"))
+        .and_then(|t| t.as_array())
+        .map(|list| {
+            list.iter()
+                .filter_map(|v| v.as_str())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Receipt actions that include content deliver the raw MIME message
+    // base64-encoded; best-effort extract the text/plain part
+    let text = inner
+        .get("content")
+        .and_then(|c| c.as_str())
+        .and_then(|raw| BASE64.decode(raw).ok())
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .and_then(|mime| extract_plain_text(&mime));
+
+    Some(InboundEmail {
+        message_id,
+        references: collect_references(headers),
+        from,
+        to,
+        subject: common
+            .and_then(|c| c.get("subject"))
+            .and_then(|s| s.as_str())
+            .unwrap_or("")
+            .to_string(),
+        text,
+        html: None,
+        attachments: Vec::new(),
+    })
+}
+
+// =============================================================================
+// MIME / Content Helpers
+// =============================================================================
+
+/// Best-effort text/plain extraction from a raw MIME message. Handles
+/// single-part messages and one level of multipart with base64 or
+/// quoted-printable transfer encoding; anything more exotic returns None
+/// and the caller falls back to a placeholder.
+fn extract_plain_text(raw: &str) -> Option<String> {
+    let (headers, body) = split_headers(raw)?;
+
+    let boundary = headers
+        .to_lowercase()
+        .contains("multipart/")
+        .then(|| extract_boundary(&headers))
+        .flatten();
+
+    let Some(boundary) = boundary else {
+        // Single-part: assume text unless the headers say otherwise
+        if headers.to_lowercase().contains("content-type: text/html") {
+            return Some(strip_html(&decode_transfer_encoding(&headers, body)));
+        }
+        return Some(decode_transfer_encoding(&headers, body));
+    };
+
+    let delimiter = format!("--{}", boundary);
+    for part in body.split(&delimiter) {
+        let Some((part_headers, part_body)) = split_headers(part.trim_start_matches(['\r', '\n']))
+        else {
+            continue;
+        };
+        if part_headers.to_lowercase().contains("text/plain") {
+            return Some(decode_transfer_encoding(&part_headers, part_body));
+        }
+    }
+    None
+}
+
+/// Split a MIME entity into its header block and body
+fn split_headers(raw: &str) -> Option<(String, &str)> {
+    let idx = raw
+        .find("\r\n\r\n")
+        .map(|i| (i, 4))
+        .or_else(|| raw.find("\n\n").map(|i| (i, 2)))?;
+    Some((raw[..idx.0].to_string(), &raw[idx.0 + idx.1..]))
+}
+
+/// Pull the boundary parameter out of a Content-Type header block
+fn extract_boundary(headers: &str) -> Option<String> {
+    // ASCII lowering keeps byte offsets aligned with the original, and
+    // boundaries themselves are case-sensitive so we slice the original
+    let lower: String = headers.chars().map(|c| c.to_ascii_lowercase()).collect();
+    let start = lower.find("boundary=")? + "boundary=".len();
+    let rest = &headers[start..];
+    let boundary = if let Some(stripped) = rest.strip_prefix('"') {
+        stripped.split('"').next()?
+    } else {
+        rest.split([';', '\r', '\n', ' ']).next()?
+    };
+    (!boundary.is_empty()).then(|| boundary.to_string())
+}
+
+/// Decode base64 or quoted-printable bodies per Content-Transfer-Encoding
+fn decode_transfer_encoding(headers: &str, body: &str) -> String {
+    let lower = headers.to_lowercase();
+    if lower.contains("content-transfer-encoding: base64") {
+        let compact: String = body.chars().filter(|c| !c.is_whitespace()).collect();
+        if let Ok(bytes) = BASE64.decode(compact.as_bytes()) {
+            if let Ok(text) = String::from_utf8(bytes) {
+                return text;
+            }
+        }
+        return body.trim().to_string();
+    }
+    if lower.contains("content-transfer-encoding: quoted-printable") {
+        return decode_quoted_printable(body);
+    }
+    body.trim().to_string()
+}
+
+/// Minimal quoted-printable decoder: soft line breaks and =XX escapes.
+/// Decodes to bytes so multi-byte UTF-8 escapes come out intact
+fn decode_quoted_printable(body: &str) -> String {
+    let bytes = body.as_bytes();
+    let mut out: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'=' {
+            out.push(bytes[i]);
+            i += 1;
+        } else if bytes.get(i + 1) == Some(&b'\r') && bytes.get(i + 2) == Some(&b'\n') {
+            // Soft line break
+            i += 3;
+        } else if bytes.get(i + 1) == Some(&b'\n') {
+            i += 2;
+        } else if i + 2 < bytes.len()
+            && bytes[i + 1].is_ascii_hexdigit()
+            && bytes[i + 2].is_ascii_hexdigit()
+        {
+            let hi = (bytes[i + 1] as char).to_digit(16).unwrap_or(0) as u8;
+            let lo = (bytes[i + 2] as char).to_digit(16).unwrap_or(0) as u8;
+            out.push((hi << 4) | lo);
+            i += 3;
+        } else {
+            out.push(b'=');
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&out).trim().to_string()
+}
+
+/// Strip tags from an HTML body for tickets that arrive with no plain
+/// text alternative. Not a sanitizer - the result is stored as plain text
+fn strip_html(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    let decoded = out
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'");
+    // Collapse runs of blank lines left behind by block elements
+    let mut lines: Vec<&str> = Vec::new();
+    for line in decoded.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() && lines.last().is_none_or(|l| l.is_empty()) {
+            continue;
+        }
+        lines.push(if trimmed.is_empty() { "" } else { trimmed });
+    }
+    lines.join("\n").trim().to_string()
+}
+
+/// Find a ticket number like PLX-00042 anywhere in the subject
+fn extract_ticket_number(subject: &str) -> Option<String> {
+    let mut search_from = 0;
+    while let Some(pos) = subject[search_from..].find("PLX-") {
+        let start = search_from + pos;
+        let digits: &str = subject[start + 4..]
+            .split(|c: char| !c.is_ascii_digit())
+            .next()
+            .unwrap_or("");
+        if digits.len() >= 5 {
+            return Some(format!("PLX-{}", digits));
+        }
+        search_from = start + 4;
+        if search_from >= subject.len() {
+            break;
+        }
+    }
+    None
+}
+
+/// Truncate on a char boundary
+fn truncate_content(content: &str, max: usize) -> String {
+    if content.len() <= max {
+        return content.to_string();
+    }
+    let mut end = max;
+    while !content.is_char_boundary(end) {
+        end -= 1;
+    }
+    content[..end].to_string()
+}
+
+/// Pull the bare address out of "Display Name <addr@example.com>"
+fn extract_address(from: &str) -> String {
+    if let (Some(start), Some(end)) = (from.rfind('<'), from.rfind('>')) {
+        if start < end {
+            return from[start + 1..end].trim().to_lowercase();
+        }
+    }
+    from.trim().to_lowercase()
+}
+
+// =============================================================================
+// Signature Verification
+// =============================================================================
+
+/// Verify the Svix-style signature Resend signs inbound webhooks with:
+/// HMAC-SHA256 over `{svix-id}.{svix-timestamp}.{body}` keyed by the
+/// base64 secret (after the `whsec_` prefix), compared against each
+/// space-separated `v1,<base64>` candidate in svix-signature
+fn verify_svix_signature(secret: &str, headers: &HeaderMap, body: &[u8]) -> bool {
+    let Some(msg_id) = headers.get("svix-id").and_then(|v| v.to_str().ok()) else {
+        return false;
+    };
+    let Some(timestamp) = headers.get("svix-timestamp").and_then(|v| v.to_str().ok()) else {
+        return false;
+    };
+    let Some(signatures) = headers.get("svix-signature").and_then(|v| v.to_str().ok()) else {
+        return false;
+    };
+
+    let key = secret.strip_prefix("whsec_").unwrap_or(secret);
+    let key = BASE64
+        .decode(key.as_bytes())
+        .unwrap_or_else(|_| key.as_bytes().to_vec());
+
+    #[allow(clippy::expect_used)]
+    let mut mac = Hmac::<Sha256>::new_from_slice(&key).expect("HMAC can take key of any size");
+    mac.update(msg_id.as_bytes());
+    mac.update(b".");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    let expected = BASE64.encode(mac.finalize().into_bytes());
+
+    signatures
+        .split_whitespace()
+        .filter_map(|candidate| candidate.strip_prefix("v1,"))
+        .any(|candidate| {
+            // Constant-time comparison (SOC 2 CC6.1)
+            expected.as_bytes().ct_eq(candidate.as_bytes()).into()
+        })
+}
+
+// =============================================================================
+// Handler
+// =============================================================================
+
+/// Receive an inbound support email webhook
+///
+/// Idempotent on the email's Message-ID: redeliveries are acknowledged
+/// without creating duplicate messages. Always returns 200 for accepted
+/// mail (including spam-quarantined mail) so the provider does not
+/// retry.
+pub async fn inbound_email(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: String,
+) -> ApiResult<StatusCode> {
+    let secret = &state.config.resend_webhook_secret;
+    if secret.is_empty() {
+        tracing::warn!("Inbound email webhook received but RESEND_WEBHOOK_SECRET is not set");
+        return Err(ApiError::ServiceUnavailable);
+    }
+    if !verify_svix_signature(secret, &headers, body.as_bytes()) {
+        tracing::warn!("Inbound email webhook signature mismatch");
+        return Err(ApiError::Unauthorized);
+    }
+
+    let Some(email) = parse_inbound_email(&body) else {
+        return Err(ApiError::BadRequest(
+            "Unrecognized inbound email payload".into(),
+        ));
+    };
+
+    // Idempotency: a redelivered webhook carries the same Message-ID
+    let already_seen: Option<Uuid> = sqlx::query_scalar(
+        "SELECT ticket_id FROM ticket_email_metadata WHERE email_message_id = $1",
+    )
+    .bind(&email.message_id)
+    .fetch_optional(&state.pool)
+    .await?;
+    if let Some(ticket_id) = already_seen {
+        tracing::debug!(
+            ticket_id = %ticket_id,
+            email_message_id = %email.message_id,
+            "Duplicate inbound email delivery ignored"
+        );
+        return Ok(StatusCode::OK);
+    }
+
+    let from_address = extract_address(&email.from);
+    let subject = truncate_content(email.subject.trim(), MAX_SUBJECT_LENGTH);
+    let content = match (&email.text, &email.html) {
+        (Some(text), _) if !text.trim().is_empty() => text.trim().to_string(),
+        (_, Some(html)) if !html.trim().is_empty() => strip_html(html),
+        _ => "(no text content)".to_string(),
+    };
+    let content = truncate_content(&content, MAX_CONTENT_LENGTH);
+
+    // Thread by Message-ID chain first, ticket number in the subject second
+    let mut ticket_id: Option<Uuid> = None;
+    if !email.references.is_empty() {
+        ticket_id = sqlx::query_scalar(
+            r#"
+            SELECT ticket_id FROM ticket_email_metadata
+            WHERE email_message_id = ANY($1)
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(&email.references)
+        .fetch_optional(&state.pool)
+        .await?;
+    }
+    if ticket_id.is_none() {
+        if let Some(number) = extract_ticket_number(&subject) {
+            ticket_id =
+                sqlx::query_scalar("SELECT id FROM support_tickets WHERE ticket_number = $1")
+                    .bind(&number)
+                    .fetch_optional(&state.pool)
+                    .await?;
+        }
+    }
+
+    let sender_id: Option<Uuid> = sqlx::query_scalar("SELECT id FROM users WHERE email = $1")
+        .bind(&from_address)
+        .fetch_optional(&state.pool)
+        .await?;
+
+    let (ticket_id, ticket_number, message_id, created) = match ticket_id {
+        Some(ticket_id) => {
+            let message_id: Uuid = sqlx::query_scalar(
+                r#"
+                INSERT INTO ticket_messages (ticket_id, sender_id, is_admin_reply, content)
+                VALUES ($1, $2, false, $3)
+                RETURNING id
+                "#,
+            )
+            .bind(ticket_id)
+            .bind(sender_id)
+            .bind(&content)
+            .fetch_one(&state.pool)
+            .await?;
+
+            // A customer reply reopens tickets parked on their response
+            let ticket_number: String = sqlx::query_scalar(
+                r#"
+                UPDATE support_tickets
+                SET status = CASE
+                        WHEN status IN ('awaiting_response', 'resolved') THEN 'open'::ticket_status
+                        ELSE status
+                    END,
+                    updated_at = NOW()
+                WHERE id = $1
+                RETURNING ticket_number
+                "#,
+            )
+            .bind(ticket_id)
+            .fetch_one(&state.pool)
+            .await?;
+
+            (ticket_id, ticket_number, message_id, false)
+        }
+        None => {
+            // New tickets from unknown senders go through the same spam
+            // filter as the public inquiry form; quarantined mail is still
+            // acknowledged so the provider does not retry it
+            if state.config.spam_quarantine_threshold > 0 {
+                let verdict = spam::score_submission(&subject, &content);
+                if verdict.score >= state.config.spam_quarantine_threshold {
+                    quarantine_inbound_email(
+                        &state,
+                        &email,
+                        &from_address,
+                        &subject,
+                        &content,
+                        verdict,
+                    )
+                    .await?;
+                    return Ok(StatusCode::OK);
+                }
+            }
+
+            let org_id: Option<Uuid> = match sender_id {
+                Some(user_id) => {
+                    sqlx::query_scalar(
+                        "SELECT org_id FROM organization_members WHERE user_id = $1 LIMIT 1",
+                    )
+                    .bind(user_id)
+                    .fetch_optional(&state.pool)
+                    .await?
+                }
+                None => None,
+            };
+
+            let ticket_subject = if subject.is_empty() {
+                "(no subject)".to_string()
+            } else {
+                subject.clone()
+            };
+
+            let (ticket_id, ticket_number): (Uuid, String) = sqlx::query_as(
+                r#"
+                INSERT INTO support_tickets (
+                    organization_id, user_id, subject, category, priority,
+                    source, original_email_from, original_email_to, contact_email
+                )
+                VALUES ($1, $2, $3, 'general'::ticket_category, 'medium'::ticket_priority,
+                        'email', $4, $5, $4)
+                RETURNING id, ticket_number
+                "#,
+            )
+            .bind(org_id)
+            .bind(sender_id)
+            .bind(&ticket_subject)
+            .bind(&from_address)
+            .bind(email.to.first().map(|s| s.as_str()))
+            .fetch_one(&state.pool)
+            .await?;
+
+            let message_id: Uuid = sqlx::query_scalar(
+                r#"
+                INSERT INTO ticket_messages (ticket_id, sender_id, is_admin_reply, content)
+                VALUES ($1, $2, false, $3)
+                RETURNING id
+                "#,
+            )
+            .bind(ticket_id)
+            .bind(sender_id)
+            .bind(&content)
+            .fetch_one(&state.pool)
+            .await?;
+
+            (ticket_id, ticket_number, message_id, true)
+        }
+    };
+
+    let attachment_count = store_inbound_attachments(&state, ticket_id, &email.attachments).await;
+
+    sqlx::query(
+        r#"
+        INSERT INTO ticket_email_metadata (
+            ticket_id, message_id, email_message_id, in_reply_to,
+            direction, from_address, to_addresses, subject,
+            has_attachments, attachment_count
+        )
+        VALUES ($1, $2, $3, $4, 'inbound', $5, $6, $7, $8, $9)
+        "#,
+    )
+    .bind(ticket_id)
+    .bind(message_id)
+    .bind(&email.message_id)
+    .bind(email.references.first().map(|s| s.as_str()))
+    .bind(&from_address)
+    .bind(&email.to)
+    .bind(&subject)
+    .bind(attachment_count > 0)
+    .bind(attachment_count)
+    .execute(&state.pool)
+    .await?;
+
+    let trigger = if created {
+        crate::support_rules::RuleTrigger::TicketCreated
+    } else {
+        crate::support_rules::RuleTrigger::CustomerReply
+    };
+    tokio::spawn(crate::support_rules::run_rules(
+        state.clone(),
+        ticket_id,
+        trigger,
+    ));
+
+    tracing::info!(
+        ticket_id = %ticket_id,
+        ticket_number = %ticket_number,
+        from = %from_address,
+        created = created,
+        attachments = attachment_count,
+        "Inbound email ingested"
+    );
+
+    Ok(StatusCode::OK)
+}
+
+/// Quarantine an inbound email the spam filter flagged, mirroring the
+/// enterprise inquiry flow
+async fn quarantine_inbound_email(
+    state: &AppState,
+    email: &InboundEmail,
+    from_address: &str,
+    subject: &str,
+    content: &str,
+    verdict: spam::SpamVerdict,
+) -> ApiResult<()> {
+    let auto_discard = verdict.score >= state.config.spam_discard_threshold;
+    let payload = serde_json::json!({
+        "content": content,
+        "contact_email": from_address,
+        "to": email.to,
+        "email_message_id": email.message_id,
+    });
+
+    let quarantine_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO spam_quarantine
+            (source, contact_email, subject, payload, score, signals, status, auto_discarded)
+        VALUES ('email', $1, $2, $3, $4, $5, $6, $7)
+        RETURNING id
+        "#,
+    )
+    .bind(from_address)
+    .bind(subject)
+    .bind(&payload)
+    .bind(verdict.score)
+    .bind(&verdict.signals)
+    .bind(if auto_discard {
+        "discarded"
+    } else {
+        "quarantined"
+    })
+    .bind(auto_discard)
+    .fetch_one(&state.pool)
+    .await?;
+
+    tracing::warn!(
+        quarantine_id = %quarantine_id,
+        score = verdict.score,
+        signals = ?verdict.signals,
+        auto_discarded = auto_discard,
+        email = %from_address,
+        "Inbound email flagged as spam"
+    );
+    Ok(())
+}
+
+/// Decode and store structured attachments, returning how many were
+/// persisted. Failures are logged and skipped - a bad attachment should
+/// not lose the message text
+async fn store_inbound_attachments(
+    state: &AppState,
+    ticket_id: Uuid,
+    attachments: &[InboundAttachment],
+) -> i32 {
+    if attachments.is_empty() {
+        return 0;
+    }
+    let Some(storage) = state.storage.clone() else {
+        tracing::warn!(
+            ticket_id = %ticket_id,
+            count = attachments.len(),
+            "Dropping inbound attachments: storage backend not available"
+        );
+        return 0;
+    };
+
+    if attachments.len() > MAX_INBOUND_ATTACHMENTS {
+        tracing::warn!(
+            ticket_id = %ticket_id,
+            count = attachments.len(),
+            "Inbound email has too many attachments; keeping the first {}",
+            MAX_INBOUND_ATTACHMENTS
+        );
+    }
+
+    let mut stored = 0;
+    for attachment in attachments.iter().take(MAX_INBOUND_ATTACHMENTS) {
+        let Ok(data) = BASE64.decode(attachment.content_base64.as_bytes()) else {
+            tracing::warn!(
+                ticket_id = %ticket_id,
+                filename = %attachment.filename,
+                "Skipping inbound attachment with invalid base64 content"
+            );
+            continue;
+        };
+        if data.is_empty() || data.len() > MAX_ATTACHMENT_SIZE_BYTES {
+            tracing::warn!(
+                ticket_id = %ticket_id,
+                filename = %attachment.filename,
+                size = data.len(),
+                "Skipping inbound attachment outside the size limit"
+            );
+            continue;
+        }
+
+        let attachment_id = Uuid::new_v4();
+        // Keep the key free of path tricks from attacker-controlled names
+        let safe_filename: String = attachment
+            .filename
+            .chars()
+            .map(|c| if c == '/' || c == '\\' { '_' } else { c })
+            .collect();
+        let storage_key = format!(
+            "attachments/{}/{}/{}",
+            ticket_id, attachment_id, safe_filename
+        );
+
+        if let Err(e) = storage
+            .put(&storage_key, &data, &attachment.content_type)
+            .await
+        {
+            tracing::error!(
+                key = %storage_key,
+                error = %e,
+                "Failed to store inbound attachment"
+            );
+            continue;
+        }
+
+        let scanner_enabled = state.virus_scanner.is_enabled();
+        let (initial_status, scan_engine) = if scanner_enabled {
+            ("pending", None)
+        } else {
+            ("clean", Some(state.virus_scanner.name()))
+        };
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO ticket_attachments (
+                id, ticket_id, uploaded_by, filename, content_type, size_bytes,
+                storage_key, scan_status, scan_engine, scanned_at
+            )
+            VALUES ($1, $2, NULL, $3, $4, $5, $6, $7, $8,
+                    CASE WHEN $7 = 'clean' THEN NOW() END)
+            "#,
+        )
+        .bind(attachment_id)
+        .bind(ticket_id)
+        .bind(&safe_filename)
+        .bind(&attachment.content_type)
+        .bind(data.len() as i64)
+        .bind(&storage_key)
+        .bind(initial_status)
+        .bind(scan_engine)
+        .execute(&state.pool)
+        .await;
+        if let Err(e) = inserted {
+            tracing::error!(
+                ticket_id = %ticket_id,
+                filename = %safe_filename,
+                error = %e,
+                "Failed to record inbound attachment"
+            );
+            continue;
+        }
+
+        if scanner_enabled {
+            let state_for_scan = state.clone();
+            let filename = safe_filename.clone();
+            let content_type = attachment.content_type.clone();
+            let data = Bytes::from(data);
+            tokio::spawn(async move {
+                super::attachments::run_scan(
+                    state_for_scan,
+                    attachment_id,
+                    ticket_id,
+                    filename,
+                    content_type,
+                    data,
+                )
+                .await;
+            });
+        }
+
+        stored += 1;
+    }
+    stored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_svix_signature() {
+        let secret = format!("whsec_{}", BASE64.encode(b"test-signing-key"));
+        let body = b"{\"type\":\"email.received\"}";
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"test-signing-key").unwrap();
+        mac.update(b"msg_1.1700000000.");
+        mac.update(body);
+        let sig = BASE64.encode(mac.finalize().into_bytes());
+
+        let mut headers = HeaderMap::new();
+        headers.insert("svix-id", "msg_1".parse().unwrap());
+        headers.insert("svix-timestamp", "1700000000".parse().unwrap());
+        headers.insert("svix-signature", format!("v1,{}", sig).parse().unwrap());
+        assert!(verify_svix_signature(&secret, &headers, body));
+        assert!(!verify_svix_signature(&secret, &headers, b"tampered"));
+
+        headers.insert("svix-signature", "v1,bm90LXRoZS1zaWc=".parse().unwrap());
+        assert!(!verify_svix_signature(&secret, &headers, body));
+    }
+
+    #[test]
+    fn test_parse_resend_payload() {
+        let body = serde_json::json!({
+            "type": "email.received",
+            "data": {
+                "from": "Jordan <jordan@example.com>",
+                "to": ["support@plexmcp.com"],
+                "subject": "Proxy keeps timing out",
+                "text": "It started this morning.",
+                "headers": [
+                    {"name": "Message-ID", "value": "<abc@mail.example.com>"},
+                    {"name": "In-Reply-To", "value": "<parent@plexmcp.com>"}
+                ],
+                "attachments": [
+                    {"filename": "log.txt", "content_type": "text/plain", "content": "aGVsbG8="}
+                ]
+            }
+        })
+        .to_string();
+
+        let email = parse_inbound_email(&body).unwrap();
+        assert_eq!(email.message_id, "<abc@mail.example.com>");
+        assert_eq!(email.references, vec!["<parent@plexmcp.com>"]);
+        assert_eq!(extract_address(&email.from), "jordan@example.com");
+        assert_eq!(email.subject, "Proxy keeps timing out");
+        assert_eq!(email.attachments.len(), 1);
+        assert_eq!(email.attachments[0].filename, "log.txt");
+    }
+
+    #[test]
+    fn test_parse_ses_sns_payload() {
+        let notification = serde_json::json!({
+            "notificationType": "Received",
+            "mail": {
+                "messageId": "ses-receipt-id",
+                "commonHeaders": {
+                    "from": ["jordan@example.com"],
+                    "to": ["support@plexmcp.com"],
+                    "subject": "Re: [PLX-00042] Proxy keeps timing out",
+                    "messageId": "<def@mail.example.com>"
+                },
+                "headers": [
+                    {"name": "In-Reply-To", "value": "<abc@mail.example.com>"}
+                ]
+            },
+            "content": BASE64.encode(
+                "Content-Type: text/plain\r\n\r\nStill broken after the restart.\r\n"
+            )
+        })
+        .to_string();
+        let body = serde_json::json!({
+            "Type": "Notification",
+            "Message": notification,
+        })
+        .to_string();
+
+        let email = parse_inbound_email(&body).unwrap();
+        assert_eq!(email.message_id, "<def@mail.example.com>");
+        assert_eq!(email.references, vec!["<abc@mail.example.com>"]);
+        assert_eq!(
+            email.text.as_deref(),
+            Some("Still broken after the restart.")
+        );
+        assert_eq!(
+            extract_ticket_number(&email.subject).as_deref(),
+            Some("PLX-00042")
+        );
+    }
+
+    #[test]
+    fn test_extract_plain_text_multipart() {
+        let mime = "Content-Type: multipart/alternative; boundary=\"b1\"\r\n\r\n\
+                    --b1\r\nContent-Type: text/html\r\n\r\n<p>hi</p>\r\n\
+                    --b1\r\nContent-Type: text/plain\r\n\
+                    Content-Transfer-Encoding: quoted-printable\r\n\r\n\
+                    caf=C3=A9 time=\r\n still one line\r\n--b1--";
+        let text = extract_plain_text(mime).unwrap();
+        assert!(text.starts_with("café time still one line"));
+    }
+
+    #[test]
+    fn test_extract_ticket_number() {
+        assert_eq!(
+            extract_ticket_number("Re: [PLX-00042] broken").as_deref(),
+            Some("PLX-00042")
+        );
+        assert_eq!(extract_ticket_number("PLX-123 too short"), None);
+        assert_eq!(extract_ticket_number("no reference here"), None);
+    }
+
+    #[test]
+    fn test_strip_html() {
+        let text = strip_html("<div><p>Hello &amp; goodbye</p>\n\n\n<p>Second</p></div>");
+        assert_eq!(text, "Hello & goodbye\n\nSecond");
+    }
+}
@@ -18,7 +18,7 @@ use uuid::Uuid;
 
 use crate::{
     audit_constants::{auth_event, event_type, severity},
-    auth::{totp, AuthUser},
+    auth::{totp, webauthn, AuthUser},
     error::{ApiError, ApiResult},
     state::AppState,
 };
@@ -42,6 +42,8 @@ pub struct TwoFactorStatusResponse {
     pub locked_until: Option<OffsetDateTime>,
     /// Number of unused backup codes remaining
     pub backup_codes_remaining: i64,
+    /// Number of registered WebAuthn passkeys
+    pub passkeys_registered: i64,
 }
 
 /// Response for beginning 2FA setup
@@ -287,6 +289,7 @@ pub async fn get_2fa_status(
 
     let row = get_2fa_record(&state, user_id).await?;
     let now = OffsetDateTime::now_utc();
+    let passkeys_registered = webauthn::count_passkeys(&state.pool, user_id).await?;
 
     match row {
         Some(r) => {
@@ -303,6 +306,7 @@ pub async fn get_2fa_status(
                 is_locked,
                 locked_until: if is_locked { r.locked_until } else { None },
                 backup_codes_remaining,
+                passkeys_registered,
             }))
         }
         None => Ok(Json(TwoFactorStatusResponse {
@@ -311,6 +315,7 @@ pub async fn get_2fa_status(
             is_locked: false,
             locked_until: None,
             backup_codes_remaining: 0,
+            passkeys_registered,
         })),
     }
 }
@@ -952,3 +957,244 @@ pub async fn trust_device(
 
     Ok(device_token)
 }
+
+// =============================================================================
+// WebAuthn / Passkey Types
+// =============================================================================
+
+/// Request to complete a passkey registration ceremony
+#[derive(Debug, Deserialize)]
+pub struct WebauthnRegisterCompleteRequest {
+    /// User-facing nickname for the passkey ("MacBook Touch ID")
+    pub name: Option<String>,
+    /// Attestation response from `navigator.credentials.create`
+    pub credential: webauthn_rs::prelude::RegisterPublicKeyCredential,
+}
+
+/// Request to complete a passkey authentication ceremony
+#[derive(Debug, Deserialize)]
+pub struct WebauthnAuthenticateCompleteRequest {
+    /// Assertion response from `navigator.credentials.get`
+    pub credential: webauthn_rs::prelude::PublicKeyCredential,
+}
+
+/// A registered passkey
+#[derive(Debug, Serialize)]
+pub struct WebauthnCredentialResponse {
+    pub id: Uuid,
+    pub name: String,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_used_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+/// Response for listing registered passkeys
+#[derive(Debug, Serialize)]
+pub struct WebauthnCredentialsListResponse {
+    pub credentials: Vec<WebauthnCredentialResponse>,
+}
+
+#[derive(Debug, FromRow)]
+struct WebauthnCredentialRow {
+    id: Uuid,
+    name: String,
+    last_used_at: Option<OffsetDateTime>,
+    created_at: OffsetDateTime,
+}
+
+// =============================================================================
+// WebAuthn / Passkey Handlers
+// =============================================================================
+
+/// Begin registering a new passkey
+///
+/// POST /api/v1/2fa/webauthn/register
+pub async fn begin_webauthn_registration(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<webauthn_rs::prelude::CreationChallengeResponse>> {
+    let user_id = auth_user.user_id.ok_or(ApiError::Unauthorized)?;
+    let email = auth_user.email.as_deref().ok_or(ApiError::Unauthorized)?;
+
+    let challenge =
+        webauthn::begin_registration(&state.pool, &state.config, user_id, email).await?;
+
+    Ok(Json(challenge))
+}
+
+/// Complete a passkey registration
+///
+/// POST /api/v1/2fa/webauthn/register/complete
+pub async fn complete_webauthn_registration(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    headers: HeaderMap,
+    Json(req): Json<WebauthnRegisterCompleteRequest>,
+) -> ApiResult<Json<WebauthnCredentialResponse>> {
+    let user_id = auth_user.user_id.ok_or(ApiError::Unauthorized)?;
+    let email = auth_user.email.as_deref().ok_or(ApiError::Unauthorized)?;
+
+    let credential_id = webauthn::finish_registration(
+        &state.pool,
+        &state.config,
+        user_id,
+        req.name.as_deref(),
+        &req.credential,
+    )
+    .await?;
+
+    let row: WebauthnCredentialRow = sqlx::query_as(
+        "SELECT id, name, last_used_at, created_at FROM user_webauthn_credentials WHERE id = $1",
+    )
+    .bind(credential_id)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(|e| ApiError::Database(e.to_string()))?;
+
+    tracing::info!(user_id = %user_id, credential_id = %credential_id, "Passkey registered");
+
+    // Log registration (new authentication factor is noteworthy)
+    let (ip_address, user_agent) = extract_auth_audit_context(&headers);
+    log_auth_event(
+        &state.pool,
+        Some(user_id),
+        auth_event::PASSKEY_REGISTERED,
+        Some(email.to_string()),
+        Some(serde_json::json!({"name": row.name})),
+        event_type::AUTHENTICATION,
+        severity::WARNING,
+        ip_address,
+        user_agent,
+        true,
+        None,
+        Some("webauthn".to_string()),
+    )
+    .await?;
+
+    Ok(Json(WebauthnCredentialResponse {
+        id: row.id,
+        name: row.name,
+        last_used_at: row.last_used_at,
+        created_at: row.created_at,
+    }))
+}
+
+/// Begin a passkey authentication challenge (re-auth verification)
+///
+/// POST /api/v1/2fa/webauthn/authenticate
+pub async fn begin_webauthn_authentication(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<webauthn_rs::prelude::RequestChallengeResponse>> {
+    let user_id = auth_user.user_id.ok_or(ApiError::Unauthorized)?;
+
+    let challenge = webauthn::begin_authentication(&state.pool, &state.config, user_id).await?;
+
+    Ok(Json(challenge))
+}
+
+/// Complete a passkey authentication challenge
+///
+/// POST /api/v1/2fa/webauthn/authenticate/complete
+pub async fn complete_webauthn_authentication(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<WebauthnAuthenticateCompleteRequest>,
+) -> ApiResult<Json<TwoFactorVerifyResponse>> {
+    let user_id = auth_user.user_id.ok_or(ApiError::Unauthorized)?;
+
+    webauthn::finish_authentication(&state.pool, &state.config, user_id, &req.credential).await?;
+
+    Ok(Json(TwoFactorVerifyResponse {
+        valid: true,
+        remaining_attempts: None,
+        is_locked: false,
+    }))
+}
+
+/// List registered passkeys for the current user
+///
+/// GET /api/v1/2fa/webauthn/credentials
+pub async fn list_webauthn_credentials(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> ApiResult<Json<WebauthnCredentialsListResponse>> {
+    let user_id = auth_user.user_id.ok_or(ApiError::Unauthorized)?;
+
+    let rows: Vec<WebauthnCredentialRow> = sqlx::query_as(
+        "SELECT id, name, last_used_at, created_at FROM user_webauthn_credentials \
+         WHERE user_id = $1 ORDER BY created_at",
+    )
+    .bind(user_id)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| ApiError::Database(e.to_string()))?;
+
+    Ok(Json(WebauthnCredentialsListResponse {
+        credentials: rows
+            .into_iter()
+            .map(|r| WebauthnCredentialResponse {
+                id: r.id,
+                name: r.name,
+                last_used_at: r.last_used_at,
+                created_at: r.created_at,
+            })
+            .collect(),
+    }))
+}
+
+/// Remove a registered passkey
+///
+/// DELETE /api/v1/2fa/webauthn/credentials/:credential_id
+pub async fn delete_webauthn_credential(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    headers: HeaderMap,
+    axum::extract::Path(credential_id): axum::extract::Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    let user_id = auth_user.user_id.ok_or(ApiError::Unauthorized)?;
+    let email = auth_user.email.as_deref().ok_or(ApiError::Unauthorized)?;
+
+    let deleted: Option<(String,)> = sqlx::query_as(
+        "DELETE FROM user_webauthn_credentials WHERE id = $1 AND user_id = $2 RETURNING name",
+    )
+    .bind(credential_id)
+    .bind(user_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(|e| ApiError::Database(e.to_string()))?;
+
+    let (name,) = deleted.ok_or(ApiError::NotFound)?;
+
+    tracing::info!(user_id = %user_id, credential_id = %credential_id, "Passkey removed");
+
+    // Log removal (CRITICAL when it was the last 2FA factor)
+    let has_totp = get_2fa_record(&state, user_id)
+        .await?
+        .map(|r| r.is_enabled)
+        .unwrap_or(false);
+    let has_remaining = webauthn::has_passkeys(&state.pool, user_id).await?;
+    let (ip_address, user_agent) = extract_auth_audit_context(&headers);
+    log_auth_event(
+        &state.pool,
+        Some(user_id),
+        auth_event::PASSKEY_REMOVED,
+        Some(email.to_string()),
+        Some(serde_json::json!({"name": name})),
+        event_type::AUTHENTICATION,
+        if has_totp || has_remaining {
+            severity::WARNING
+        } else {
+            severity::CRITICAL
+        },
+        ip_address,
+        user_agent,
+        true,
+        None,
+        Some("webauthn".to_string()),
+    )
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
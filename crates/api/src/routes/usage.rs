@@ -1,7 +1,9 @@
 //! Usage tracking API routes
 
 use axum::{
-    extract::{Extension, Query, State},
+    extract::{Extension, Path, Query, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
     Json,
 };
 use serde::{Deserialize, Serialize};
@@ -62,6 +64,37 @@ pub struct McpUsageItem {
     pub avg_latency_ms: Option<i32>,
 }
 
+/// Cost center usage breakdown with allocated overage cost
+#[derive(Debug, Serialize)]
+pub struct TagUsageItem {
+    pub cost_center: String,
+    pub request_count: i64,
+    pub token_count: i64,
+    pub error_count: i64,
+    /// Share of the org's overage cost allocated to this tag, in cents
+    pub estimated_overage_cents: i64,
+}
+
+/// Usage-by-tag response
+#[derive(Debug, Serialize)]
+pub struct UsageByTagResponse {
+    pub org_id: Uuid,
+    pub tier: String,
+    pub period_start: String,
+    pub period_end: String,
+    /// Requests above the tier's included allowance for this period
+    pub overage_requests: i64,
+    pub overage_rate_per_1k_cents: Option<i32>,
+    pub tags: Vec<TagUsageItem>,
+}
+
+/// Query params for the chargeback export
+#[derive(Debug, Deserialize)]
+pub struct ChargebackQuery {
+    /// Month to export as `YYYY-MM` (defaults to the previous month)
+    pub month: Option<String>,
+}
+
 /// Hourly usage data point
 #[derive(Debug, Serialize)]
 pub struct HourlyUsageItem {
@@ -71,6 +104,38 @@ pub struct HourlyUsageItem {
     pub errors: i64,
 }
 
+/// Query parameters for the usage heat calendar
+#[derive(Debug, Deserialize)]
+pub struct UsageCalendarQuery {
+    /// How many months to cover, counting the current one (default 6, max 12)
+    pub months: Option<i32>,
+    /// IANA timezone for day bucketing (default UTC)
+    pub timezone: Option<String>,
+}
+
+/// One day cell in the usage heat calendar
+#[derive(Debug, Serialize)]
+pub struct UsageCalendarDay {
+    /// Local calendar date, YYYY-MM-DD
+    pub date: String,
+    pub requests: i64,
+    pub errors: i64,
+    pub error_rate: f64,
+    /// Heat intensity 1-4 relative to the org's busiest day in the window
+    pub level: u8,
+}
+
+/// GitHub-style usage heat calendar
+#[derive(Debug, Serialize)]
+pub struct UsageCalendarResponse {
+    pub timezone: String,
+    pub months: i32,
+    /// Busiest day's request count; the dashboard can reuse it for legends
+    pub max_requests: i64,
+    /// Days with traffic only - absent dates render as empty cells
+    pub days: Vec<UsageCalendarDay>,
+}
+
 /// Get current billing period usage
 pub async fn get_billing_usage(
     State(state): State<AppState>,
@@ -284,6 +349,230 @@ pub async fn get_usage_by_mcp(
     ))
 }
 
+/// Get usage and allocated overage cost by cost center tag
+///
+/// Aggregates usage for the period by the cost center on the MCP instance
+/// (falling back to the API key's tag, then `untagged`), and splits the
+/// org's estimated overage cost across tags in proportion to their request
+/// share so internal departments can be billed.
+pub async fn get_usage_by_tag(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<UsagePeriodQuery>,
+) -> Result<Json<UsageByTagResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let billing = state.billing.as_ref().ok_or(ApiError::ServiceUnavailable)?;
+
+    // Default to current month
+    let now = OffsetDateTime::now_utc();
+    let default_start = now
+        .replace_day(1)
+        .map_err(|e| ApiError::Database(format!("Failed to set start date: {}", e)))?
+        .replace_time(time::Time::MIDNIGHT);
+
+    let start = query
+        .start
+        .as_ref()
+        .and_then(|s| {
+            time::OffsetDateTime::parse(s, &time::format_description::well_known::Rfc3339).ok()
+        })
+        .unwrap_or(default_start);
+    let end = query
+        .end
+        .as_ref()
+        .and_then(|s| {
+            time::OffsetDateTime::parse(s, &time::format_description::well_known::Rfc3339).ok()
+        })
+        .unwrap_or(now);
+
+    let breakdown = billing
+        .usage
+        .get_usage_by_tag(org_id, start, end)
+        .await
+        .map_err(|e| ApiError::Database(format!("Failed to get usage by tag: {}", e)))?;
+
+    let tier = get_org_tier(&state.pool, org_id).await?;
+    let (tags, overage_requests) = allocate_overage(breakdown, tier);
+
+    Ok(Json(UsageByTagResponse {
+        org_id,
+        tier: tier.to_string(),
+        period_start: format_datetime(start),
+        period_end: format_datetime(end),
+        overage_requests,
+        overage_rate_per_1k_cents: tier.overage_rate_per_1k_cents(),
+        tags,
+    }))
+}
+
+/// Export the monthly chargeback report as CSV
+///
+/// Same aggregation as `/usage/by-tag`, snapped to a calendar month
+/// (`?month=YYYY-MM`, defaults to the previous month) for handing to
+/// finance.
+pub async fn export_chargeback_csv(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<ChargebackQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let billing = state.billing.as_ref().ok_or(ApiError::ServiceUnavailable)?;
+
+    let (start, end, label) = resolve_chargeback_month(query.month.as_deref())?;
+
+    let breakdown = billing
+        .usage
+        .get_usage_by_tag(org_id, start, end)
+        .await
+        .map_err(|e| ApiError::Database(format!("Failed to get usage by tag: {}", e)))?;
+
+    let tier = get_org_tier(&state.pool, org_id).await?;
+    let (tags, _) = allocate_overage(breakdown, tier);
+
+    let mut csv =
+        String::from("cost_center,request_count,token_count,error_count,estimated_overage_cents\n");
+    for tag in &tags {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            csv_field(&tag.cost_center),
+            tag.request_count,
+            tag.token_count,
+            tag.error_count,
+            tag.estimated_overage_cents
+        ));
+    }
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"chargeback-{}.csv\"", label),
+            ),
+        ],
+        csv,
+    ))
+}
+
+/// Look up the org's subscription tier
+async fn get_org_tier(
+    pool: &sqlx::PgPool,
+    org_id: Uuid,
+) -> Result<plexmcp_shared::SubscriptionTier, ApiError> {
+    let result: Option<(String,)> = sqlx::query_as(
+        "SELECT COALESCE(subscription_tier, 'free') FROM organizations WHERE id = $1",
+    )
+    .bind(org_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(result
+        .and_then(|(t,)| t.parse().ok())
+        .unwrap_or(plexmcp_shared::SubscriptionTier::Free))
+}
+
+/// Split the org's estimated overage cost across tags by request share
+///
+/// Returns the per-tag items plus the org-wide overage request count.
+/// Tiers without metered overage (or periods under the included allowance)
+/// allocate zero everywhere.
+fn allocate_overage(
+    breakdown: Vec<plexmcp_billing::usage::TagUsageBreakdown>,
+    tier: plexmcp_shared::SubscriptionTier,
+) -> (Vec<TagUsageItem>, i64) {
+    let total_requests: i64 = breakdown.iter().map(|t| t.request_count).sum();
+    let included = tier.monthly_requests();
+    let overage_requests = if included == u64::MAX {
+        0
+    } else {
+        (total_requests.max(0) as u64).saturating_sub(included) as i64
+    };
+    let rate = tier.overage_rate_per_1k_cents();
+
+    let tags = breakdown
+        .into_iter()
+        .map(|item| {
+            let estimated_overage_cents = match rate {
+                Some(rate) if overage_requests > 0 && total_requests > 0 => {
+                    let share = item.request_count as f64 / total_requests as f64;
+                    (share * overage_requests as f64 / 1000.0 * rate as f64).round() as i64
+                }
+                _ => 0,
+            };
+            TagUsageItem {
+                cost_center: item.cost_center,
+                request_count: item.request_count,
+                token_count: item.token_count,
+                error_count: item.error_count,
+                estimated_overage_cents,
+            }
+        })
+        .collect();
+
+    (tags, overage_requests)
+}
+
+/// Resolve a `YYYY-MM` month into a [start, end) range and filename label
+///
+/// Defaults to the previous calendar month when not specified.
+fn resolve_chargeback_month(
+    month: Option<&str>,
+) -> Result<(OffsetDateTime, OffsetDateTime, String), ApiError> {
+    let (year, month_num) = match month {
+        Some(raw) => {
+            let (y, m) = raw
+                .split_once('-')
+                .ok_or_else(|| ApiError::Validation("Month must be YYYY-MM".to_string()))?;
+            let year: i32 = y
+                .parse()
+                .map_err(|_| ApiError::Validation("Month must be YYYY-MM".to_string()))?;
+            let month_num: u8 = m
+                .parse()
+                .map_err(|_| ApiError::Validation("Month must be YYYY-MM".to_string()))?;
+            if !(1..=12).contains(&month_num) {
+                return Err(ApiError::Validation("Month must be YYYY-MM".to_string()));
+            }
+            (year, month_num)
+        }
+        None => {
+            let now = OffsetDateTime::now_utc();
+            if now.month() == time::Month::January {
+                (now.year() - 1, 12)
+            } else {
+                (now.year(), now.month() as u8 - 1)
+            }
+        }
+    };
+
+    let month_enum = time::Month::try_from(month_num)
+        .map_err(|_| ApiError::Validation("Month must be YYYY-MM".to_string()))?;
+    let start = time::Date::from_calendar_date(year, month_enum, 1)
+        .map_err(|e| ApiError::Validation(format!("Invalid month: {}", e)))?
+        .midnight()
+        .assume_utc();
+    let end = if month_num == 12 {
+        time::Date::from_calendar_date(year + 1, time::Month::January, 1)
+    } else {
+        time::Date::from_calendar_date(year, month_enum.next(), 1)
+    }
+    .map_err(|e| ApiError::Validation(format!("Invalid month: {}", e)))?
+    .midnight()
+    .assume_utc();
+
+    Ok((start, end, format!("{:04}-{:02}", year, month_num)))
+}
+
+/// Quote a CSV field if it contains separators or quotes
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 /// Get hourly usage data for charts
 pub async fn get_hourly_usage(
     State(state): State<AppState>,
@@ -330,6 +619,71 @@ pub async fn get_hourly_usage(
     ))
 }
 
+/// Get per-day usage for the past N months as a heat calendar
+///
+/// Days are bucketed in the requested timezone so the calendar lines up
+/// with the org's working days instead of UTC. Intensity levels are
+/// relative to the busiest day in the window.
+pub async fn get_usage_calendar(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<UsageCalendarQuery>,
+) -> Result<Json<UsageCalendarResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let billing = state.billing.as_ref().ok_or(ApiError::ServiceUnavailable)?;
+
+    let months = query.months.unwrap_or(6);
+    if !(1..=12).contains(&months) {
+        return Err(ApiError::Validation(
+            "months must be between 1 and 12".to_string(),
+        ));
+    }
+    let timezone = query.timezone.as_deref().unwrap_or("UTC");
+
+    let daily = billing
+        .usage
+        .get_daily_usage(org_id, timezone, months)
+        .await
+        .map_err(|e| match e {
+            plexmcp_billing::error::BillingError::InvalidInput(msg) => ApiError::Validation(msg),
+            e => ApiError::Database(format!("Failed to get usage calendar: {}", e)),
+        })?;
+
+    let max_requests = daily.iter().map(|d| d.requests).max().unwrap_or(0);
+    let days = daily
+        .into_iter()
+        .map(|d| {
+            // 1-4 relative to the busiest day, so sparse orgs still get a
+            // readable gradient
+            let level = if max_requests == 0 {
+                1
+            } else {
+                (((d.requests * 4) + max_requests - 1) / max_requests).clamp(1, 4) as u8
+            };
+            let error_rate = if d.requests > 0 {
+                d.errors as f64 / d.requests as f64
+            } else {
+                0.0
+            };
+            UsageCalendarDay {
+                date: d.day.to_string(),
+                requests: d.requests,
+                errors: d.errors,
+                error_rate,
+                level,
+            }
+        })
+        .collect();
+
+    Ok(Json(UsageCalendarResponse {
+        timezone: timezone.to_string(),
+        months,
+        max_requests,
+        days,
+    }))
+}
+
 /// Check if current usage is within limits
 pub async fn check_usage_limit(
     State(state): State<AppState>,
@@ -799,3 +1153,294 @@ pub async fn get_latency_distribution(
         total_requests,
     }))
 }
+
+// ============ Customer Usage Alerts ============
+
+/// One org-defined usage alert
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct UsageAlertResponse {
+    pub id: Uuid,
+    pub name: String,
+    /// Fires when billing-period requests reach this share of the quota
+    pub threshold_percent: i32,
+    pub notify_email: bool,
+    pub webhook_url: Option<String>,
+    pub enabled: bool,
+    pub cooldown_hours: i32,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_triggered_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateUsageAlertRequest {
+    pub name: String,
+    pub threshold_percent: i32,
+    /// Email the alert creator when the threshold is reached (default true)
+    pub notify_email: Option<bool>,
+    pub webhook_url: Option<String>,
+    /// Hours before the same alert may fire again within one billing
+    /// period (default 24, max 168)
+    pub cooldown_hours: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateUsageAlertRequest {
+    pub name: Option<String>,
+    pub threshold_percent: Option<i32>,
+    pub notify_email: Option<bool>,
+    /// Empty string clears the webhook
+    pub webhook_url: Option<String>,
+    pub cooldown_hours: Option<i32>,
+    pub enabled: Option<bool>,
+}
+
+/// Cap on alerts per org - enough for tiered thresholds (50/80/90/100)
+const MAX_USAGE_ALERTS_PER_ORG: i64 = 20;
+
+fn validate_usage_alert_name(name: &str) -> Result<(), ApiError> {
+    if name.trim().is_empty() || name.len() > 100 {
+        return Err(ApiError::Validation(
+            "name must be between 1 and 100 characters".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_usage_alert_threshold(threshold: i32) -> Result<(), ApiError> {
+    if !(1..=100).contains(&threshold) {
+        return Err(ApiError::Validation(
+            "threshold_percent must be between 1 and 100".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_usage_alert_webhook(url: &str) -> Result<(), ApiError> {
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        return Err(ApiError::Validation(
+            "webhook_url must start with http:// or https://".to_string(),
+        ));
+    }
+    if url.len() > 2000 {
+        return Err(ApiError::Validation(
+            "webhook_url must be at most 2000 characters".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_usage_alert_cooldown(hours: i32) -> Result<(), ApiError> {
+    if !(1..=168).contains(&hours) {
+        return Err(ApiError::Validation(
+            "cooldown_hours must be between 1 and 168".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// List the org's usage alerts
+pub async fn list_usage_alerts(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<Vec<UsageAlertResponse>>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let alerts: Vec<UsageAlertResponse> = sqlx::query_as(
+        r#"
+        SELECT id, name, threshold_percent, notify_email, webhook_url,
+               enabled, cooldown_hours, last_triggered_at, created_at
+        FROM usage_alerts
+        WHERE org_id = $1
+        ORDER BY threshold_percent, created_at
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(alerts))
+}
+
+/// Create a usage alert
+///
+/// The threshold is evaluated by the worker against the billing period's
+/// metered requests. At least one delivery channel (email or webhook)
+/// must be active.
+pub async fn create_usage_alert(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<CreateUsageAlertRequest>,
+) -> Result<(StatusCode, Json<UsageAlertResponse>), ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    validate_usage_alert_name(&req.name)?;
+    validate_usage_alert_threshold(req.threshold_percent)?;
+    let notify_email = req.notify_email.unwrap_or(true);
+    let webhook_url = match req.webhook_url.as_deref() {
+        Some(url) if !url.is_empty() => {
+            validate_usage_alert_webhook(url)?;
+            Some(url.to_string())
+        }
+        _ => None,
+    };
+    if !notify_email && webhook_url.is_none() {
+        return Err(ApiError::Validation(
+            "At least one delivery channel (notify_email or webhook_url) is required".to_string(),
+        ));
+    }
+    let cooldown_hours = req.cooldown_hours.unwrap_or(24);
+    validate_usage_alert_cooldown(cooldown_hours)?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM usage_alerts WHERE org_id = $1")
+        .bind(org_id)
+        .fetch_one(&state.pool)
+        .await?;
+    if count >= MAX_USAGE_ALERTS_PER_ORG {
+        return Err(ApiError::Validation(format!(
+            "At most {} usage alerts per organization",
+            MAX_USAGE_ALERTS_PER_ORG
+        )));
+    }
+
+    let alert: UsageAlertResponse = sqlx::query_as(
+        r#"
+        INSERT INTO usage_alerts (org_id, name, threshold_percent, notify_email,
+                                  webhook_url, cooldown_hours, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id, name, threshold_percent, notify_email, webhook_url,
+                  enabled, cooldown_hours, last_triggered_at, created_at
+        "#,
+    )
+    .bind(org_id)
+    .bind(req.name.trim())
+    .bind(req.threshold_percent)
+    .bind(notify_email)
+    .bind(&webhook_url)
+    .bind(cooldown_hours)
+    .bind(auth_user.user_id)
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(alert)))
+}
+
+/// Update a usage alert
+///
+/// Changing the threshold re-arms the alert so the new level can fire in
+/// the current billing period.
+pub async fn update_usage_alert(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(alert_id): Path<Uuid>,
+    Json(req): Json<UpdateUsageAlertRequest>,
+) -> Result<Json<UsageAlertResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let existing: Option<(String, i32, bool, Option<String>, i32, bool)> = sqlx::query_as(
+        r#"
+        SELECT name, threshold_percent, notify_email, webhook_url, cooldown_hours, enabled
+        FROM usage_alerts
+        WHERE id = $1 AND org_id = $2
+        "#,
+    )
+    .bind(alert_id)
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await?;
+    let (mut name, mut threshold, mut notify_email, mut webhook_url, mut cooldown, mut enabled) =
+        existing.ok_or(ApiError::NotFound)?;
+
+    if let Some(ref new_name) = req.name {
+        validate_usage_alert_name(new_name)?;
+        name = new_name.trim().to_string();
+    }
+    let threshold_changed = req
+        .threshold_percent
+        .map(|t| t != threshold)
+        .unwrap_or(false);
+    if let Some(new_threshold) = req.threshold_percent {
+        validate_usage_alert_threshold(new_threshold)?;
+        threshold = new_threshold;
+    }
+    if let Some(new_notify) = req.notify_email {
+        notify_email = new_notify;
+    }
+    if let Some(ref new_url) = req.webhook_url {
+        if new_url.is_empty() {
+            webhook_url = None;
+        } else {
+            validate_usage_alert_webhook(new_url)?;
+            webhook_url = Some(new_url.clone());
+        }
+    }
+    if let Some(new_cooldown) = req.cooldown_hours {
+        validate_usage_alert_cooldown(new_cooldown)?;
+        cooldown = new_cooldown;
+    }
+    if let Some(new_enabled) = req.enabled {
+        enabled = new_enabled;
+    }
+    if !notify_email && webhook_url.is_none() {
+        return Err(ApiError::Validation(
+            "At least one delivery channel (notify_email or webhook_url) is required".to_string(),
+        ));
+    }
+
+    let alert: UsageAlertResponse = sqlx::query_as(
+        r#"
+        UPDATE usage_alerts
+        SET name = $3, threshold_percent = $4, notify_email = $5, webhook_url = $6,
+            cooldown_hours = $7, enabled = $8,
+            last_triggered_at = CASE WHEN $9 THEN NULL ELSE last_triggered_at END,
+            last_triggered_period_start = CASE WHEN $9 THEN NULL ELSE last_triggered_period_start END,
+            updated_at = NOW()
+        WHERE id = $1 AND org_id = $2
+        RETURNING id, name, threshold_percent, notify_email, webhook_url,
+                  enabled, cooldown_hours, last_triggered_at, created_at
+        "#,
+    )
+    .bind(alert_id)
+    .bind(org_id)
+    .bind(&name)
+    .bind(threshold)
+    .bind(notify_email)
+    .bind(&webhook_url)
+    .bind(cooldown)
+    .bind(enabled)
+    .bind(threshold_changed)
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok(Json(alert))
+}
+
+/// Delete a usage alert
+pub async fn delete_usage_alert(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(alert_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let result = sqlx::query("DELETE FROM usage_alerts WHERE id = $1 AND org_id = $2")
+        .bind(alert_id)
+        .bind(org_id)
+        .execute(&state.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,15 +1,17 @@
 //! User management routes
 
 use axum::{
-    extract::{Extension, Path, State},
+    extract::{Extension, Path, Query, State},
     http::StatusCode,
     Json,
 };
+use plexmcp_shared::PaginatedResponse;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
+use super::list_query::{ListParams, ListQuery};
 use crate::{
     auth::AuthUser,
     error::{ApiError, ApiResult},
@@ -31,10 +33,14 @@ pub struct UpdateUserRequest {
     pub role: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct UserListResponse {
-    pub users: Vec<UserSummary>,
-    pub total: i64,
+/// Query params for listing org members
+#[derive(Debug, Deserialize)]
+pub struct ListUsersQuery {
+    pub role: Option<String>,
+    /// Case-insensitive substring match on the email address
+    pub email: Option<String>,
+    #[serde(flatten)]
+    pub list: ListParams,
 }
 
 #[derive(Debug, Serialize)]
@@ -89,11 +95,20 @@ struct UserWithJoinedRow {
 // Handlers
 // =============================================================================
 
+/// Sort keys accepted by the member listing; first entry is the default
+const USER_SORT_KEYS: &[(&str, &str)] = &[
+    ("joined_at", "om.created_at"),
+    ("created_at", "u.created_at"),
+    ("email", "u.email"),
+    ("last_login", "u.last_login_at"),
+];
+
 /// List all users in the organization
 pub async fn list_users(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
-) -> ApiResult<Json<UserListResponse>> {
+    Query(query): Query<ListUsersQuery>,
+) -> ApiResult<Json<PaginatedResponse<UserSummary>>> {
     let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
 
     // Only owners and admins can list users
@@ -101,29 +116,24 @@ pub async fn list_users(
         return Err(ApiError::Forbidden);
     }
 
+    let order_by = query.list.order_by(USER_SORT_KEYS)?;
+
     // Join with organization_members to get the joined_at date
-    let users: Vec<UserWithJoinedRow> = sqlx::query_as(
-        r#"
-        SELECT
-            u.id,
-            u.email,
-            u.role,
-            u.email_verified,
-            u.last_login_at,
-            u.created_at,
-            om.created_at as joined_at
-        FROM users u
-        LEFT JOIN organization_members om ON om.user_id = u.id AND om.org_id = $1
-        WHERE u.org_id = $1
-        ORDER BY om.created_at DESC NULLS LAST
-        "#,
+    let (users, total): (Vec<UserWithJoinedRow>, i64) = ListQuery::new(
+        "users u LEFT JOIN organization_members om ON om.user_id = u.id AND om.org_id = u.org_id",
+    )
+    .eq_uuid("u.org_id", org_id)
+    .eq_text("u.role", query.role)
+    .contains("u.email", query.email)
+    .fetch_page(
+        &state.pool,
+        "u.id, u.email, u.role, u.email_verified, u.last_login_at, u.created_at, \
+         om.created_at as joined_at",
+        &order_by,
+        &query.list,
     )
-    .bind(org_id)
-    .fetch_all(&state.pool)
     .await?;
 
-    let total = users.len() as i64;
-
     let user_summaries: Vec<UserSummary> = users
         .into_iter()
         .map(|u| UserSummary {
@@ -137,10 +147,12 @@ pub async fn list_users(
         })
         .collect();
 
-    Ok(Json(UserListResponse {
-        users: user_summaries,
+    Ok(Json(PaginatedResponse::new(
+        user_summaries,
         total,
-    }))
+        query.list.page(),
+        query.list.per_page(),
+    )))
 }
 
 /// Get a specific user by ID
@@ -288,6 +300,13 @@ pub async fn update_user(
             .bind(user_id)
             .execute(&state.pool)
             .await?;
+
+        // Drop cached token verifications so the new role applies on the
+        // target user's next request
+        state
+            .token_cache
+            .invalidate_user(&user_id.to_string(), Some(&target_user.email))
+            .await;
     }
 
     // Fetch updated user
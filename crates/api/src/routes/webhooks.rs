@@ -0,0 +1,352 @@
+//! Outbound org webhook management
+//!
+//! CRUD for org-registered webhook endpoints subscribed to platform
+//! events (see [`plexmcp_shared::org_webhooks::SUPPORTED_EVENTS`]), plus
+//! a per-endpoint delivery log for debugging. Event fan-out and signed
+//! delivery with retries live in `plexmcp_shared::org_webhooks`; the
+//! worker drains the delivery queue every minute.
+
+use axum::{
+    extract::{Extension, Path, Query, State},
+    Json,
+};
+use plexmcp_shared::org_webhooks::SUPPORTED_EVENTS;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{auth::AuthUser, error::ApiError, state::AppState};
+
+/// Maximum webhook endpoints per org
+const MAX_ENDPOINTS_PER_ORG: i64 = 10;
+
+/// Deliveries returned per log request
+const DELIVERY_LOG_LIMIT: i64 = 50;
+
+/// Webhook endpoint response; the secret only appears in the POST
+/// response that created it
+#[derive(Debug, Serialize)]
+pub struct WebhookEndpointResponse {
+    pub id: Uuid,
+    pub url: String,
+    pub description: Option<String>,
+    pub events: Vec<String>,
+    pub enabled: bool,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct WebhookEndpointRow {
+    id: Uuid,
+    url: String,
+    description: Option<String>,
+    events: Vec<String>,
+    enabled: bool,
+    created_at: OffsetDateTime,
+    updated_at: OffsetDateTime,
+}
+
+impl WebhookEndpointRow {
+    fn into_response(self, secret: Option<String>) -> WebhookEndpointResponse {
+        WebhookEndpointResponse {
+            id: self.id,
+            url: self.url,
+            description: self.description,
+            events: self.events,
+            enabled: self.enabled,
+            created_at: format_datetime(self.created_at),
+            updated_at: format_datetime(self.updated_at),
+            secret,
+        }
+    }
+}
+
+fn format_datetime(dt: OffsetDateTime) -> String {
+    dt.format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_else(|_| dt.to_string())
+}
+
+fn validate_url(url: &str) -> Result<(), ApiError> {
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(ApiError::Validation(
+            "Webhook URL must start with http:// or https://".to_string(),
+        ));
+    }
+    if url.len() > 2000 {
+        return Err(ApiError::Validation(
+            "Webhook URL must be at most 2000 characters".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Check the subscription list against the supported events, rejecting
+/// unknown types so typos fail loudly instead of never firing
+fn validate_events(events: &[String]) -> Result<(), ApiError> {
+    if events.is_empty() {
+        return Err(ApiError::Validation(
+            "Subscribe to at least one event".to_string(),
+        ));
+    }
+    for event in events {
+        if !SUPPORTED_EVENTS.contains(&event.as_str()) {
+            return Err(ApiError::Validation(format!(
+                "Unknown event type '{}'; supported: {}",
+                event,
+                SUPPORTED_EVENTS.join(", ")
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// GET /api/v1/webhooks - list the org's webhook endpoints
+pub async fn list_webhooks(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<Vec<WebhookEndpointResponse>>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    let rows: Vec<WebhookEndpointRow> = sqlx::query_as(
+        r#"
+        SELECT id, url, description, events, enabled, created_at, updated_at
+        FROM org_webhook_endpoints
+        WHERE org_id = $1
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(
+        rows.into_iter().map(|r| r.into_response(None)).collect(),
+    ))
+}
+
+/// Register a new webhook endpoint
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    pub description: Option<String>,
+    /// Event types to subscribe to
+    pub events: Vec<String>,
+}
+
+/// POST /api/v1/webhooks - register an endpoint (admin only)
+///
+/// The signing secret is generated server-side and returned once in
+/// this response; store it before navigating away.
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<CreateWebhookRequest>,
+) -> Result<Json<WebhookEndpointResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    validate_url(&req.url)?;
+    validate_events(&req.events)?;
+
+    let count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM org_webhook_endpoints WHERE org_id = $1")
+            .bind(org_id)
+            .fetch_one(&state.pool)
+            .await?;
+    if count >= MAX_ENDPOINTS_PER_ORG {
+        return Err(ApiError::Validation(format!(
+            "Maximum of {} webhook endpoints per organization",
+            MAX_ENDPOINTS_PER_ORG
+        )));
+    }
+
+    let secret = format!("whsec_{}", hex::encode(rand::random::<[u8; 32]>()));
+
+    let row: WebhookEndpointRow = sqlx::query_as(
+        r#"
+        INSERT INTO org_webhook_endpoints (org_id, url, secret, description, events, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, url, description, events, enabled, created_at, updated_at
+        "#,
+    )
+    .bind(org_id)
+    .bind(&req.url)
+    .bind(&secret)
+    .bind(&req.description)
+    .bind(&req.events)
+    .bind(auth_user.user_id)
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok(Json(row.into_response(Some(secret))))
+}
+
+/// Update a webhook endpoint; omitted fields are unchanged
+#[derive(Debug, Deserialize)]
+pub struct UpdateWebhookRequest {
+    pub url: Option<String>,
+    pub description: Option<String>,
+    pub events: Option<Vec<String>>,
+    pub enabled: Option<bool>,
+}
+
+/// PATCH /api/v1/webhooks/:webhook_id - update an endpoint (admin only)
+pub async fn update_webhook(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(webhook_id): Path<Uuid>,
+    Json(req): Json<UpdateWebhookRequest>,
+) -> Result<Json<WebhookEndpointResponse>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    if let Some(url) = req.url.as_deref() {
+        validate_url(url)?;
+    }
+    if let Some(events) = req.events.as_deref() {
+        validate_events(events)?;
+    }
+
+    let row: WebhookEndpointRow = sqlx::query_as(
+        r#"
+        UPDATE org_webhook_endpoints
+        SET url = COALESCE($3, url),
+            description = COALESCE($4, description),
+            events = COALESCE($5, events),
+            enabled = COALESCE($6, enabled),
+            updated_at = NOW()
+        WHERE id = $1 AND org_id = $2
+        RETURNING id, url, description, events, enabled, created_at, updated_at
+        "#,
+    )
+    .bind(webhook_id)
+    .bind(org_id)
+    .bind(&req.url)
+    .bind(&req.description)
+    .bind(&req.events)
+    .bind(req.enabled)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(row.into_response(None)))
+}
+
+/// DELETE /api/v1/webhooks/:webhook_id - remove an endpoint (admin only)
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+    if !["owner", "admin"].contains(&auth_user.role.as_str()) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let result = sqlx::query("DELETE FROM org_webhook_endpoints WHERE id = $1 AND org_id = $2")
+        .bind(webhook_id)
+        .bind(org_id)
+        .execute(&state.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    Ok(Json(serde_json::json!({ "deleted": true })))
+}
+
+/// Filters for the delivery log
+#[derive(Debug, Deserialize)]
+pub struct DeliveryLogQuery {
+    /// Restrict to one delivery status (pending, delivered, failed)
+    pub status: Option<String>,
+}
+
+/// One delivery attempt record for debugging
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct WebhookDeliveryResponse {
+    pub id: Uuid,
+    pub event_type: String,
+    pub status: String,
+    pub attempts: i32,
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    #[serde(serialize_with = "serialize_optional_rfc3339")]
+    pub next_attempt_at: Option<OffsetDateTime>,
+    #[serde(serialize_with = "serialize_optional_rfc3339")]
+    pub delivered_at: Option<OffsetDateTime>,
+    #[serde(serialize_with = "serialize_rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+fn serialize_rfc3339<S: serde::Serializer>(
+    dt: &OffsetDateTime,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format_datetime(*dt))
+}
+
+fn serialize_optional_rfc3339<S: serde::Serializer>(
+    dt: &Option<OffsetDateTime>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match dt {
+        Some(dt) => serializer.serialize_str(&format_datetime(*dt)),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// GET /api/v1/webhooks/:webhook_id/deliveries - recent delivery log
+pub async fn list_webhook_deliveries(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(webhook_id): Path<Uuid>,
+    Query(query): Query<DeliveryLogQuery>,
+) -> Result<Json<Vec<WebhookDeliveryResponse>>, ApiError> {
+    let org_id = auth_user.org_id.ok_or(ApiError::NoOrganization)?;
+
+    if let Some(status) = query.status.as_deref() {
+        if !["pending", "delivered", "failed"].contains(&status) {
+            return Err(ApiError::Validation(
+                "status must be one of: pending, delivered, failed".to_string(),
+            ));
+        }
+    }
+
+    // Scope the endpoint to the org before exposing its deliveries
+    sqlx::query_scalar::<_, Uuid>(
+        "SELECT id FROM org_webhook_endpoints WHERE id = $1 AND org_id = $2",
+    )
+    .bind(webhook_id)
+    .bind(org_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    let deliveries: Vec<WebhookDeliveryResponse> = sqlx::query_as(
+        r#"
+        SELECT id, event_type, status, attempts, response_status, last_error,
+               CASE WHEN status = 'pending' THEN next_attempt_at END AS next_attempt_at,
+               delivered_at, created_at
+        FROM org_webhook_deliveries
+        WHERE endpoint_id = $1 AND ($2::text IS NULL OR status = $2)
+        ORDER BY created_at DESC
+        LIMIT $3
+        "#,
+    )
+    .bind(webhook_id)
+    .bind(&query.status)
+    .bind(DELIVERY_LOG_LIMIT)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(deliveries))
+}
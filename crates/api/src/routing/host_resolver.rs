@@ -12,7 +12,11 @@ use uuid::Uuid;
 
 use super::DomainCache;
 
-/// Reserved subdomains that cannot be used by organizations
+/// Baseline reserved subdomains that cannot be used by organizations
+///
+/// This list is compiled in and can never be removed. Platform admins can
+/// reserve additional subdomains at runtime via the reserved_subdomains
+/// table; [`is_reserved_subdomain`] checks both.
 pub const RESERVED_SUBDOMAINS: &[&str] = &[
     "api",
     "www",
@@ -38,6 +42,32 @@ pub const RESERVED_SUBDOMAINS: &[&str] = &[
     "demo",
 ];
 
+/// How long a released custom subdomain keeps resolving to its old org
+///
+/// After an org changes or clears its custom subdomain, the old name
+/// keeps routing to the org for this many days so in-flight client
+/// configs don't break immediately. The name cannot be claimed by
+/// another org until the grace period ends.
+pub const SUBDOMAIN_REDIRECT_GRACE_DAYS: i32 = 30;
+
+/// Check whether a subdomain is reserved (baseline list or admin-managed)
+///
+/// Checks the compiled-in [`RESERVED_SUBDOMAINS`] baseline first, then
+/// the reserved_subdomains table for entries admins added at runtime.
+pub async fn is_reserved_subdomain(pool: &PgPool, subdomain: &str) -> Result<bool, sqlx::Error> {
+    if RESERVED_SUBDOMAINS.contains(&subdomain) {
+        return Ok(true);
+    }
+
+    let managed: Option<(String,)> =
+        sqlx::query_as("SELECT subdomain FROM reserved_subdomains WHERE subdomain = $1")
+            .bind(subdomain)
+            .fetch_optional(pool)
+            .await?;
+
+    Ok(managed.is_some())
+}
+
 /// Result of resolving a host to an organization
 #[derive(Debug, Clone)]
 pub struct ResolvedOrg {
@@ -56,6 +86,8 @@ pub enum ResolutionType {
     CustomSubdomain,
     /// Matched custom domain (e.g., mcp.company.com)
     CustomDomain,
+    /// Matched a recently released custom subdomain still in its grace period
+    GraceRedirect,
 }
 
 /// Host resolver with caching
@@ -117,8 +149,11 @@ impl HostResolver {
         if host.ends_with(&base_suffix) {
             let subdomain = host.strip_suffix(&base_suffix).unwrap_or(&host);
 
-            // Check for reserved subdomains
-            if RESERVED_SUBDOMAINS.contains(&subdomain) {
+            // Check for reserved subdomains (baseline + admin-managed)
+            if is_reserved_subdomain(&self.pool, subdomain)
+                .await
+                .map_err(|e| HostResolveError::DatabaseError(e.to_string()))?
+            {
                 self.cache.set(&host, None);
                 return Err(HostResolveError::ReservedSubdomain(subdomain.to_string()));
             }
@@ -137,6 +172,12 @@ impl HostResolver {
                 return Ok(Some(resolved));
             }
 
+            // Recently released subdomain still in its grace period
+            if let Some(resolved) = self.resolve_released_subdomain(subdomain).await? {
+                self.cache.set(&host, Some(resolved.org_id));
+                return Ok(Some(resolved));
+            }
+
             // Subdomain not found
             self.cache.set(&host, None);
             return Err(HostResolveError::NotFound(host.to_string()));
@@ -201,6 +242,44 @@ impl HostResolver {
         }))
     }
 
+    /// Resolve a recently released custom subdomain to its previous org
+    ///
+    /// After an org changes or clears its custom subdomain, the old name
+    /// keeps routing to the org for [`SUBDOMAIN_REDIRECT_GRACE_DAYS`] so
+    /// existing client configs have time to move to the new host.
+    async fn resolve_released_subdomain(
+        &self,
+        subdomain: &str,
+    ) -> Result<Option<ResolvedOrg>, HostResolveError> {
+        #[derive(sqlx::FromRow)]
+        struct HistoryRow {
+            org_id: Uuid,
+        }
+
+        let result: Option<HistoryRow> = sqlx::query_as(
+            r#"
+            SELECT h.org_id
+            FROM org_subdomain_history h
+            JOIN organizations o ON o.id = h.org_id
+            WHERE h.old_subdomain = $1
+              AND h.changed_at > NOW() - make_interval(days => $2)
+              AND o.status = 'active'
+            ORDER BY h.changed_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(subdomain)
+        .bind(SUBDOMAIN_REDIRECT_GRACE_DAYS)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| HostResolveError::DatabaseError(e.to_string()))?;
+
+        Ok(result.map(|row| ResolvedOrg {
+            org_id: row.org_id,
+            resolution_type: ResolutionType::GraceRedirect,
+        }))
+    }
+
     /// Resolve a custom domain to an org
     async fn resolve_custom_domain(
         &self,
@@ -234,6 +313,47 @@ impl HostResolver {
         }))
     }
 
+    /// Best public host for an org's proxy endpoint (reverse of [`resolve`](Self::resolve))
+    ///
+    /// Prefers an active custom domain, then the custom subdomain, then the
+    /// auto-assigned subdomain. Returns `None` when the org has none of
+    /// them (legacy API-host routing).
+    pub async fn preferred_host(&self, org_id: Uuid) -> Result<Option<String>, HostResolveError> {
+        // Active custom domain first
+        let domain: Option<(String,)> = sqlx::query_as(
+            r#"
+            SELECT cd.domain
+            FROM custom_domains cd
+            JOIN users u ON cd.user_id = u.id
+            WHERE u.org_id = $1
+              AND cd.verification_status = 'active'
+              AND cd.ssl_status = 'active'
+            ORDER BY cd.verified_at DESC NULLS LAST
+            LIMIT 1
+            "#,
+        )
+        .bind(org_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| HostResolveError::DatabaseError(e.to_string()))?;
+
+        if let Some((domain,)) = domain {
+            return Ok(Some(domain));
+        }
+
+        let subdomains: Option<(Option<String>, Option<String>)> = sqlx::query_as(
+            "SELECT custom_subdomain, auto_subdomain FROM organizations WHERE id = $1",
+        )
+        .bind(org_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| HostResolveError::DatabaseError(e.to_string()))?;
+
+        Ok(subdomains
+            .and_then(|(custom, auto)| custom.or(auto))
+            .map(|sub| format!("{}.{}", sub, self.base_domain)))
+    }
+
     /// Invalidate cache for a specific host
     pub fn invalidate_host(&self, host: &str) {
         let host = normalize_host(host);
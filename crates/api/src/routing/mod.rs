@@ -11,5 +11,6 @@ mod host_resolver;
 
 pub use cache::DomainCache;
 pub use host_resolver::{
-    HostResolveError, HostResolver, ResolutionType, ResolvedOrg, RESERVED_SUBDOMAINS,
+    is_reserved_subdomain, HostResolveError, HostResolver, ResolutionType, ResolvedOrg,
+    RESERVED_SUBDOMAINS, SUBDOMAIN_REDIRECT_GRACE_DAYS,
 };
@@ -0,0 +1,393 @@
+//! Unified background scheduler
+//!
+//! Central registry for periodic platform work (health sweeps, retention
+//! purges, rollups). Jobs are registered at startup with a cron expression;
+//! a single tick loop computes due slots and claims them through the
+//! `scheduler_jobs` table, so each slot runs on exactly one instance even
+//! with multiple API replicas. Run outcomes (status, error, duration) are
+//! recorded for `GET /admin/scheduler`, and platform admins can trigger a
+//! job out of schedule via `POST /admin/scheduler/:job_name/run`.
+//!
+//! Per-instance loops (in-memory cache cleanup, disk buffer replay, rate
+//! limit override reload) intentionally stay as plain intervals in
+//! `AppState::new` - their work is local to one process and must run
+//! everywhere.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use croner::Cron;
+use serde::Serialize;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// How often the tick loop checks for due jobs
+pub const TICK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long a slot claim protects a running job before another instance may
+/// re-claim it (guards against instances dying mid-run)
+const LOCK_TTL_SECS: i32 = 600;
+
+type JobFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+type JobFn = Arc<dyn Fn() -> JobFuture + Send + Sync>;
+
+/// A registered periodic job
+struct JobDefinition {
+    name: &'static str,
+    description: &'static str,
+    cron_expr: &'static str,
+    cron: Cron,
+    run: JobFn,
+}
+
+/// Per-job status for the admin endpoint
+#[derive(Debug, Serialize)]
+pub struct JobStatus {
+    pub name: String,
+    pub description: String,
+    pub cron: String,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_run_at: Option<OffsetDateTime>,
+    /// "success" or "error" from the most recent completed run
+    pub last_status: Option<String>,
+    pub last_error: Option<String>,
+    pub last_duration_ms: Option<i32>,
+    /// Whether an instance currently holds the job's lock
+    pub running: bool,
+    pub locked_by: Option<String>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub next_run_at: Option<OffsetDateTime>,
+}
+
+/// Outcome of a manual trigger request
+pub enum TriggerOutcome {
+    /// Job claimed and started in the background
+    Started,
+    /// Another instance (or a scheduled run) currently holds the lock
+    AlreadyRunning,
+    /// No job registered under that name
+    UnknownJob,
+}
+
+/// Central scheduler: job registry plus the tick loop
+pub struct Scheduler {
+    pool: PgPool,
+    /// Identifies this instance in `scheduler_jobs.locked_by`
+    instance_id: String,
+    jobs: Vec<JobDefinition>,
+}
+
+impl Scheduler {
+    pub fn new(pool: PgPool) -> Self {
+        // Hostname (pod/machine name) plus a short suffix so restarts are
+        // distinguishable in the lock column
+        let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "api".to_string());
+        let suffix = Uuid::new_v4().simple().to_string();
+        Self {
+            pool,
+            instance_id: format!("{}-{}", host, &suffix[..8]),
+            jobs: Vec::new(),
+        }
+    }
+
+    /// Register a job under a cron expression (standard 5-field, UTC;
+    /// seconds field optional). Invalid expressions are a programming error:
+    /// logged and skipped rather than crashing startup.
+    pub fn register<F, Fut>(
+        &mut self,
+        name: &'static str,
+        description: &'static str,
+        cron_expr: &'static str,
+        f: F,
+    ) where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        let cron = match Cron::new(cron_expr).with_seconds_optional().parse() {
+            Ok(cron) => cron,
+            Err(e) => {
+                tracing::error!(
+                    job = name,
+                    cron = cron_expr,
+                    error = %e,
+                    "Invalid cron expression - job not registered"
+                );
+                return;
+            }
+        };
+
+        self.jobs.push(JobDefinition {
+            name,
+            description,
+            cron_expr,
+            cron,
+            run: Arc::new(move || Box::pin(f()) as JobFuture),
+        });
+    }
+
+    /// Start the tick loop (call once after all jobs are registered)
+    pub fn spawn(self: &Arc<Self>) {
+        let scheduler = self.clone();
+        tokio::spawn(async move {
+            scheduler.ensure_rows().await;
+
+            let mut next_runs: Vec<Option<DateTime<Utc>>> = scheduler
+                .jobs
+                .iter()
+                .map(|job| job.cron.find_next_occurrence(&Utc::now(), false).ok())
+                .collect();
+
+            let mut interval = tokio::time::interval(TICK_INTERVAL);
+            loop {
+                interval.tick().await;
+                let now = Utc::now();
+                for (i, job) in scheduler.jobs.iter().enumerate() {
+                    let Some(due) = next_runs[i] else { continue };
+                    if due > now {
+                        continue;
+                    }
+                    // Advance before running so a slow job doesn't make us
+                    // re-attempt the same slot next tick
+                    next_runs[i] = job.cron.find_next_occurrence(&now, false).ok();
+                    if scheduler.claim_slot(job.name, due).await {
+                        scheduler.run_job(job).await;
+                    }
+                }
+            }
+        });
+    }
+
+    /// Seed `scheduler_jobs` rows for registered jobs (idempotent)
+    async fn ensure_rows(&self) {
+        for job in &self.jobs {
+            let result =
+                sqlx::query("INSERT INTO scheduler_jobs (job_name) VALUES ($1) ON CONFLICT (job_name) DO NOTHING")
+                    .bind(job.name)
+                    .execute(&self.pool)
+                    .await;
+            if let Err(e) = result {
+                tracing::error!(job = job.name, error = %e, "Failed to seed scheduler job row");
+            }
+        }
+    }
+
+    /// Try to claim a cron slot. The WHERE clause makes this a
+    /// cross-instance mutex: only one instance can move
+    /// `last_scheduled_for` forward to the slot, and expired locks from
+    /// crashed instances are re-claimable.
+    async fn claim_slot(&self, job_name: &str, slot: DateTime<Utc>) -> bool {
+        let slot = match OffsetDateTime::from_unix_timestamp(slot.timestamp()) {
+            Ok(t) => t,
+            Err(_) => return false,
+        };
+
+        let result = sqlx::query(
+            r#"
+            UPDATE scheduler_jobs
+            SET last_scheduled_for = $2,
+                locked_by = $3,
+                locked_until = NOW() + make_interval(secs => $4),
+                updated_at = NOW()
+            WHERE job_name = $1
+              AND (last_scheduled_for IS NULL OR last_scheduled_for < $2)
+              AND (locked_until IS NULL OR locked_until < NOW())
+            "#,
+        )
+        .bind(job_name)
+        .bind(slot)
+        .bind(&self.instance_id)
+        .bind(LOCK_TTL_SECS as f64)
+        .execute(&self.pool)
+        .await;
+
+        match result {
+            Ok(r) => r.rows_affected() == 1,
+            Err(e) => {
+                tracing::error!(job = job_name, error = %e, "Scheduler slot claim failed");
+                false
+            }
+        }
+    }
+
+    /// Try to claim the job lock outside the cron schedule (manual trigger)
+    async fn claim_manual(&self, job_name: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE scheduler_jobs
+            SET locked_by = $2,
+                locked_until = NOW() + make_interval(secs => $3),
+                updated_at = NOW()
+            WHERE job_name = $1
+              AND (locked_until IS NULL OR locked_until < NOW())
+            "#,
+        )
+        .bind(job_name)
+        .bind(&self.instance_id)
+        .bind(LOCK_TTL_SECS as f64)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Run a claimed job and record the outcome (releases the lock)
+    async fn run_job(&self, job: &JobDefinition) {
+        tracing::info!(job = job.name, "Scheduler job starting");
+        let start = Instant::now();
+        let result = (job.run)().await;
+        let duration_ms = start.elapsed().as_millis() as i32;
+
+        let (status, error) = match &result {
+            Ok(()) => {
+                tracing::info!(job = job.name, duration_ms, "Scheduler job completed");
+                ("success", None)
+            }
+            Err(e) => {
+                tracing::error!(job = job.name, duration_ms, error = %e, "Scheduler job failed");
+                ("error", Some(e.clone()))
+            }
+        };
+
+        let record = sqlx::query(
+            r#"
+            UPDATE scheduler_jobs
+            SET last_run_at = NOW(),
+                last_status = $2,
+                last_error = $3,
+                last_duration_ms = $4,
+                locked_by = NULL,
+                locked_until = NULL,
+                updated_at = NOW()
+            WHERE job_name = $1
+            "#,
+        )
+        .bind(job.name)
+        .bind(status)
+        .bind(error)
+        .bind(duration_ms)
+        .execute(&self.pool)
+        .await;
+
+        if let Err(e) = record {
+            tracing::error!(job = job.name, error = %e, "Failed to record scheduler job outcome");
+        }
+    }
+
+    /// Manually trigger a job (admin endpoint). Takes the same lock as
+    /// scheduled runs, so a manual trigger never overlaps one.
+    pub async fn trigger(self: &Arc<Self>, job_name: &str) -> Result<TriggerOutcome, sqlx::Error> {
+        let Some(index) = self.jobs.iter().position(|j| j.name == job_name) else {
+            return Ok(TriggerOutcome::UnknownJob);
+        };
+
+        if !self.claim_manual(job_name).await? {
+            return Ok(TriggerOutcome::AlreadyRunning);
+        }
+
+        let scheduler = self.clone();
+        tokio::spawn(async move {
+            scheduler.run_job(&scheduler.jobs[index]).await;
+        });
+
+        Ok(TriggerOutcome::Started)
+    }
+
+    /// Per-job status for `GET /admin/scheduler` (registration order)
+    pub async fn status(&self) -> Result<Vec<JobStatus>, sqlx::Error> {
+        #[derive(sqlx::FromRow)]
+        struct JobRow {
+            job_name: String,
+            last_run_at: Option<OffsetDateTime>,
+            last_status: Option<String>,
+            last_error: Option<String>,
+            last_duration_ms: Option<i32>,
+            locked_by: Option<String>,
+            locked_until: Option<OffsetDateTime>,
+        }
+
+        let rows: Vec<JobRow> = sqlx::query_as(
+            r#"
+            SELECT job_name, last_run_at, last_status, last_error,
+                   last_duration_ms, locked_by, locked_until
+            FROM scheduler_jobs
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let now = OffsetDateTime::now_utc();
+        let statuses = self
+            .jobs
+            .iter()
+            .map(|job| {
+                let row = rows.iter().find(|r| r.job_name == job.name);
+                let running = row
+                    .and_then(|r| r.locked_until)
+                    .map(|until| until > now)
+                    .unwrap_or(false);
+                let next_run_at = job
+                    .cron
+                    .find_next_occurrence(&Utc::now(), false)
+                    .ok()
+                    .and_then(|dt| OffsetDateTime::from_unix_timestamp(dt.timestamp()).ok());
+                JobStatus {
+                    name: job.name.to_string(),
+                    description: job.description.to_string(),
+                    cron: job.cron_expr.to_string(),
+                    last_run_at: row.and_then(|r| r.last_run_at),
+                    last_status: row.and_then(|r| r.last_status.clone()),
+                    last_error: row.and_then(|r| r.last_error.clone()),
+                    last_duration_ms: row.and_then(|r| r.last_duration_ms),
+                    running,
+                    locked_by: if running {
+                        row.and_then(|r| r.locked_by.clone())
+                    } else {
+                        None
+                    },
+                    next_run_at,
+                }
+            })
+            .collect();
+
+        Ok(statuses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_register_valid_cron() {
+        let pool = PgPool::connect_lazy("postgres://localhost/test").unwrap();
+        let mut scheduler = Scheduler::new(pool);
+        scheduler.register("test_job", "Test", "*/5 * * * *", || async { Ok(()) });
+        assert_eq!(scheduler.jobs.len(), 1);
+        assert_eq!(scheduler.jobs[0].name, "test_job");
+    }
+
+    #[tokio::test]
+    async fn test_register_invalid_cron_skipped() {
+        let pool = PgPool::connect_lazy("postgres://localhost/test").unwrap();
+        let mut scheduler = Scheduler::new(pool);
+        scheduler.register("bad_job", "Test", "not a cron", || async { Ok(()) });
+        assert!(scheduler.jobs.is_empty());
+    }
+
+    #[test]
+    fn test_next_occurrence_advances() {
+        let cron = Cron::new("*/5 * * * *")
+            .with_seconds_optional()
+            .parse()
+            .unwrap();
+        let now = Utc::now();
+        let next = cron.find_next_occurrence(&now, false).unwrap();
+        assert!(next > now);
+        // At most 5 minutes out
+        assert!((next - now).num_seconds() <= 300);
+    }
+}
@@ -0,0 +1,181 @@
+//! Spam scoring for public submissions
+//!
+//! Scores the enterprise-inquiry form (and inbound support email, where
+//! enabled) before a ticket is created. The score combines keyword
+//! heuristics, link density, and formatting signals; an optional
+//! external API can contribute on top. Thresholds in [`crate::Config`]
+//! decide whether a submission is quarantined for review or discarded
+//! outright — see the `spam_quarantine` table and admin review routes.
+
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Keyword heuristics with their score contributions (matched
+/// case-insensitively against subject + body)
+const SPAM_KEYWORDS: &[(&str, i32)] = &[
+    ("backlink", 20),
+    ("guest post", 20),
+    ("link building", 20),
+    ("guaranteed ranking", 25),
+    ("seo services", 20),
+    ("seo expert", 20),
+    ("web traffic", 15),
+    ("investment opportunity", 25),
+    ("make money", 20),
+    ("work from home", 15),
+    ("limited time offer", 20),
+    ("100% free", 20),
+    ("click here", 15),
+    ("casino", 25),
+    ("loan approval", 25),
+    ("viagra", 40),
+];
+
+/// Timeout for the optional external spam-check API
+const EXTERNAL_API_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Result of scoring a submission
+#[derive(Debug, Clone)]
+pub struct SpamVerdict {
+    /// Aggregate spam score (higher = more likely spam)
+    pub score: i32,
+    /// Heuristics that contributed, for the quarantine audit record
+    pub signals: Vec<String>,
+}
+
+/// Score a submission with the built-in heuristics
+pub fn score_submission(subject: &str, body: &str) -> SpamVerdict {
+    let mut score = 0;
+    let mut signals = Vec::new();
+
+    let text = format!("{} {}", subject, body).to_lowercase();
+
+    for (keyword, weight) in SPAM_KEYWORDS {
+        if text.contains(keyword) {
+            score += weight;
+            signals.push(format!("keyword:{}", keyword));
+        }
+    }
+
+    // Link density: legitimate inquiries rarely carry many URLs
+    let link_count = text.matches("http://").count() + text.matches("https://").count();
+    if link_count >= 5 {
+        score += 30;
+        signals.push(format!("link_count:{}", link_count));
+    } else if link_count >= 2 && body.len() < 400 {
+        score += 15;
+        signals.push(format!("link_density:{}", link_count));
+    }
+
+    // Very short body whose main content is a link
+    if link_count >= 1 && body.trim().len() < 80 {
+        score += 15;
+        signals.push("short_body_with_link".to_string());
+    }
+
+    // Shouting subject lines
+    let alpha: Vec<char> = subject.chars().filter(|c| c.is_alphabetic()).collect();
+    if alpha.len() >= 12 {
+        let upper = alpha.iter().filter(|c| c.is_uppercase()).count();
+        if upper * 10 > alpha.len() * 6 {
+            score += 15;
+            signals.push("excessive_caps".to_string());
+        }
+    }
+
+    SpamVerdict { score, signals }
+}
+
+/// Ask the optional external spam-check API for an additional score
+///
+/// The API is POSTed `{"subject", "content", "email"}` and must respond
+/// with `{"score": <number>}`. Fails open: any error returns `None` so
+/// an outage never blocks legitimate submissions.
+pub async fn external_score(
+    client: &reqwest::Client,
+    api_url: &str,
+    subject: &str,
+    content: &str,
+    email: &str,
+) -> Option<i32> {
+    #[derive(Deserialize)]
+    struct ExternalResponse {
+        score: i32,
+    }
+
+    let response = client
+        .post(api_url)
+        .timeout(EXTERNAL_API_TIMEOUT)
+        .json(&serde_json::json!({
+            "subject": subject,
+            "content": content,
+            "email": email,
+        }))
+        .send()
+        .await;
+
+    match response {
+        Ok(resp) if resp.status().is_success() => match resp.json::<ExternalResponse>().await {
+            Ok(body) => Some(body.score),
+            Err(e) => {
+                tracing::warn!(error = %e, "Spam-check API returned unparseable body");
+                None
+            }
+        },
+        Ok(resp) => {
+            tracing::warn!(status = %resp.status(), "Spam-check API returned error status");
+            None
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "Spam-check API request failed");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_submission_scores_zero() {
+        let verdict = score_submission(
+            "Enterprise Inquiry from Acme",
+            "We are evaluating MCP gateways for our platform team of 40 engineers \
+             and would like to discuss enterprise pricing and SSO requirements.",
+        );
+        assert_eq!(verdict.score, 0);
+        assert!(verdict.signals.is_empty());
+    }
+
+    #[test]
+    fn test_keyword_heuristics() {
+        let verdict = score_submission(
+            "SEO services for your website",
+            "We offer guest post and link building packages to boost your web traffic.",
+        );
+        assert!(verdict.score >= 55);
+        assert!(verdict.signals.iter().any(|s| s == "keyword:guest post"));
+        assert!(verdict.signals.iter().any(|s| s == "keyword:link building"));
+    }
+
+    #[test]
+    fn test_link_density() {
+        let many_links = "https://a.com https://b.com https://c.com https://d.com https://e.com";
+        let verdict = score_submission("Check these out", many_links);
+        assert!(verdict.signals.iter().any(|s| s.starts_with("link_count:")));
+
+        let short_with_link = "Visit https://spam.example now";
+        let verdict = score_submission("Hello", short_with_link);
+        assert!(verdict.signals.iter().any(|s| s == "short_body_with_link"));
+    }
+
+    #[test]
+    fn test_excessive_caps() {
+        let verdict = score_submission(
+            "AMAZING BUSINESS OPPORTUNITY FOR YOU",
+            "A longer body that is otherwise perfectly ordinary prose without any links.",
+        );
+        assert!(verdict.signals.iter().any(|s| s == "excessive_caps"));
+    }
+}
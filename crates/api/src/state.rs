@@ -11,6 +11,7 @@ use crate::{
     config::Config,
     email::SecurityEmailService,
     flyio::FlyClient,
+    routes::health::ProbeState,
     routing::HostResolver,
     websocket::WebSocketState,
 };
@@ -43,9 +44,38 @@ pub struct AppState {
     /// Security alerting service for real-time threat detection
     pub alert_service: AlertService,
     /// Cache for Supabase token verification results to prevent rate limiting
-    pub(crate) token_cache: TokenCache,
+    pub(crate) token_cache: Arc<TokenCache>,
     /// Track in-flight Supabase verification requests for request coalescing
     pub(crate) in_flight_requests: InFlightRequests,
+    /// Kubernetes-style startup/readiness probe state
+    pub probes: ProbeState,
+    /// Org content moderation rules for proxied MCP traffic
+    pub moderation: Arc<crate::mcp::moderation::ModerationEngine>,
+    /// Opt-in tools/call result cache shared across proxy requests
+    pub tool_cache: Arc<crate::mcp::tool_cache::ToolCallCache>,
+    /// Duplicate request tracker for client misbehaviour diagnostics
+    pub request_dedup: Arc<crate::mcp::dedup::RequestDedup>,
+    /// Per-org cache of the merged upstream tool catalog (GET /mcps/tools)
+    pub tool_catalog: Arc<crate::mcp::tool_catalog::ToolCatalogCache>,
+    /// Open SSE/WebSocket sessions on the MCP proxy endpoint
+    pub mcp_streams: Arc<crate::mcp::streaming::StreamingSessionManager>,
+    /// Object storage backend for ticket attachments (None if misconfigured)
+    pub storage: Option<Arc<plexmcp_shared::StorageBackend>>,
+    /// Virus scanner for uploaded attachments
+    pub virus_scanner: crate::virus_scan::ScannerBackend,
+    /// Slack webhook for staff notifications about machine-created tickets
+    /// (SLACK_SUPPORT_WEBHOOK_URL)
+    pub support_webhook_url: Option<String>,
+    /// Compiled analytics goal matchers evaluated at ingestion
+    pub goal_engine: Arc<crate::goals::GoalEngine>,
+    /// Platform read-only (maintenance) mode flags
+    pub read_only: Arc<crate::read_only::ReadOnlyMode>,
+    /// Disk-backed fallback buffer for audit/usage writes during DB outages
+    pub audit_buffer: Arc<crate::mcp::audit_buffer::AuditWriteBuffer>,
+    /// Unified cron scheduler for cross-instance periodic jobs
+    pub scheduler: Arc<crate::scheduler::Scheduler>,
+    /// Deprecated-route registry served as response headers
+    pub deprecations: Arc<crate::deprecation::DeprecationRegistry>,
 }
 
 /// Load MaxMind GeoLite2-City database from disk
@@ -130,7 +160,7 @@ impl AppState {
         }
 
         // Initialize security email service
-        let security_email = SecurityEmailService::from_env();
+        let security_email = SecurityEmailService::from_env().with_pool(pool.clone());
         if security_email.is_enabled() {
             tracing::info!("Security email notifications enabled");
         } else {
@@ -165,21 +195,155 @@ impl AppState {
             tracing::warn!("Location tracking disabled (GeoIP database not available)");
         }
 
-        // Initialize rate limiter from shared crate (always available, no billing dependency)
-        let rate_limiter = RateLimiter::new_in_memory();
-        tracing::info!("Rate limiter initialized");
+        // Initialize rate limiter from shared crate (always available, no
+        // billing dependency). With REDIS_URL set, counters live in Redis so
+        // limits hold across all instances; per-check Redis errors degrade
+        // to local enforcement. Without it each instance enforces in memory.
+        let rate_limiter = if std::env::var("REDIS_URL").is_ok() {
+            match RateLimiter::new_redis(&config.redis_url) {
+                Ok(limiter) => {
+                    tracing::info!("Rate limiter initialized (Redis-backed, distributed)");
+                    limiter
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        error = %e,
+                        "Invalid REDIS_URL, falling back to in-memory rate limiter"
+                    );
+                    RateLimiter::new_in_memory()
+                }
+            }
+        } else {
+            tracing::info!("Rate limiter initialized (in-memory, per-instance)");
+            RateLimiter::new_in_memory()
+        };
+
+        // Hot-reload admin rate limit overrides (per-org / per-API-key);
+        // admin PUTs also push the table immediately, this keeps other
+        // instances in sync
+        let limiter_for_reload = rate_limiter.clone();
+        let pool_for_reload = pool.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                match plexmcp_shared::rate_limit::load_overrides(&pool_for_reload).await {
+                    Ok(overrides) => limiter_for_reload.set_overrides(overrides).await,
+                    Err(e) => {
+                        tracing::error!(error = %e, "Failed to reload rate limit overrides")
+                    }
+                }
+            }
+        });
+
+        // Deprecated-route registry, reloaded every minute like the rate
+        // limit overrides so admin changes reach every instance
+        let deprecations = Arc::new(crate::deprecation::DeprecationRegistry::new());
+        let deprecations_for_reload = deprecations.clone();
+        let pool_for_deprecations = pool.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                match crate::deprecation::load_rules(&pool_for_deprecations).await {
+                    Ok(rules) => deprecations_for_reload.set_rules(rules),
+                    Err(e) => {
+                        tracing::error!(error = %e, "Failed to reload API deprecations")
+                    }
+                }
+            }
+        });
+
+        // Initialize shared MCP client for HTTP session caching, with SSH
+        // tunnel support when the encryption key is usable
+        let mcp_client = match crate::auth::totp::parse_encryption_key(&config.totp_encryption_key)
+        {
+            Ok(key) => {
+                let tunnels = Arc::new(crate::mcp::ssh_tunnel::SshTunnelManager::new(
+                    pool.clone(),
+                    key,
+                ));
+                tracing::info!("Shared MCP client initialized with SSH tunnel support");
+                crate::mcp::client::McpClient::with_tunnels(tunnels)
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "SSH tunnel support disabled (TOTP_ENCRYPTION_KEY not usable: {})",
+                    e
+                );
+                crate::mcp::client::McpClient::new()
+            }
+        };
+        // Re-login hook: expired cookie sessions drop their jar so the
+        // next request logs in fresh
+        let mcp_client = mcp_client.with_session_store(pool.clone());
+        // Route upstream calls through the deployment-wide egress proxy if set
+        let mcp_client = Arc::new(match &config.outbound_proxy_url {
+            Some(proxy_url) => mcp_client.with_outbound_proxy(proxy_url),
+            None => mcp_client,
+        });
+
+        // Opt-in tools/call result cache for the proxy path
+        let tool_cache = Arc::new(crate::mcp::tool_cache::ToolCallCache::new());
 
-        // Initialize shared MCP client for HTTP session caching
-        let mcp_client = Arc::new(crate::mcp::client::McpClient::new());
-        tracing::info!("Shared MCP client initialized");
+        // Duplicate request tracker for the proxy path
+        let request_dedup = Arc::new(crate::mcp::dedup::RequestDedup::new());
 
-        // Start session cleanup task (runs every 5 minutes)
+        // Per-org merged tool catalog cache (GET /mcps/tools)
+        let tool_catalog = Arc::new(crate::mcp::tool_catalog::ToolCatalogCache::new());
+
+        // Session registry for persistent SSE/WebSocket proxy connections
+        let mcp_streams = Arc::new(crate::mcp::streaming::StreamingSessionManager::new());
+
+        // Start session cleanup task (runs every 5 minutes); also evicts
+        // expired tool cache and duplicate tracker entries
         let client_for_cleanup = mcp_client.clone();
+        let cache_for_cleanup = tool_cache.clone();
+        let dedup_for_cleanup = request_dedup.clone();
         tokio::spawn(async move {
             let mut interval = tokio::time::interval(std::time::Duration::from_secs(300));
             loop {
                 interval.tick().await;
                 client_for_cleanup.cleanup_stale_sessions().await;
+                cache_for_cleanup.purge_expired().await;
+                dedup_for_cleanup.purge_expired().await;
+            }
+        });
+
+        // Keep-warm sweep for low-latency MCPs (tier-gated per org)
+        let client_for_warm = mcp_client.clone();
+        let pool_for_warm = pool.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(crate::mcp::keep_warm::KEEP_WARM_INTERVAL);
+            loop {
+                interval.tick().await;
+                crate::mcp::keep_warm::keep_warm_sweep(&pool_for_warm, &client_for_warm).await;
+            }
+        });
+
+        // Forward circuit breaker open/close transitions to the per-MCP
+        // health webhooks (the breaker manager has no pool access)
+        let (circuit_tx, mut circuit_rx) = tokio::sync::mpsc::unbounded_channel();
+        let client_for_transitions = mcp_client.clone();
+        let pool_for_transitions = pool.clone();
+        tokio::spawn(async move {
+            client_for_transitions
+                .set_circuit_transition_listener(circuit_tx)
+                .await;
+            while let Some(transition) = circuit_rx.recv().await {
+                crate::mcp::health_webhooks::notify(
+                    &pool_for_transitions,
+                    crate::mcp::health_webhooks::McpWebhookEvent {
+                        event: "mcp.circuit_changed",
+                        mcp_id: transition.mcp_id,
+                        from: transition.from.to_string(),
+                        to: transition.to.to_string(),
+                        evidence: serde_json::json!({
+                            "consecutive_failures": transition.consecutive_failures,
+                            "backoff_ms": transition.backoff_ms,
+                        }),
+                    },
+                );
             }
         });
 
@@ -192,8 +356,131 @@ impl AppState {
             tracing::warn!("Security alerting service initialized without Slack (missing SLACK_SECURITY_WEBHOOK_URL)");
         }
 
+        // Unified scheduler for cross-instance periodic work: jobs claim
+        // their cron slot through scheduler_jobs so each slot runs on
+        // exactly one instance (see crate::scheduler). Per-instance loops
+        // above stay as plain intervals - their work is local to this
+        // process.
+        let mut scheduler = crate::scheduler::Scheduler::new(pool.clone());
+
+        // Automatic health monitoring for active MCPs (flap alerts go
+        // through the alert service, so this registers after it exists).
+        // Previously an inline interval on every instance; as a scheduler
+        // job each sweep runs platform-wide exactly once.
+        let client_for_monitor = mcp_client.clone();
+        let pool_for_monitor = pool.clone();
+        let alerts_for_monitor = alert_service.clone();
+        let streams_for_monitor = mcp_streams.clone();
+        let catalog_for_monitor = tool_catalog.clone();
+        scheduler.register(
+            "mcp_health_monitor",
+            "Connection health sweep for active MCP instances",
+            "*/5 * * * *",
+            move || {
+                let pool = pool_for_monitor.clone();
+                let client = client_for_monitor.clone();
+                let alerts = alerts_for_monitor.clone();
+                let streams = streams_for_monitor.clone();
+                let catalog = catalog_for_monitor.clone();
+                async move {
+                    crate::mcp::health_monitor::health_monitor_sweep(
+                        &pool, &client, &alerts, &streams, &catalog,
+                    )
+                    .await;
+                    Ok(())
+                }
+            },
+        );
+
+        // Bring disabled alert rules back once their re-enable timer
+        // elapses, so a temporary silence can't become permanent
+        let pool_for_re_enable = pool.clone();
+        scheduler.register(
+            "alert_config_re_enable",
+            "Re-enable alert rules whose disable timer elapsed",
+            "* * * * *",
+            move || {
+                let pool = pool_for_re_enable.clone();
+                async move {
+                    let result = sqlx::query(
+                        r#"
+                        UPDATE alert_configurations
+                        SET enabled = TRUE, re_enable_at = NULL,
+                            disabled_reason = NULL, updated_at = NOW()
+                        WHERE enabled = FALSE AND re_enable_at IS NOT NULL
+                          AND re_enable_at <= NOW()
+                        "#,
+                    )
+                    .execute(&pool)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                    if result.rows_affected() > 0 {
+                        tracing::info!(
+                            count = result.rows_affected(),
+                            "Re-enabled alert rules after disable timer elapsed"
+                        );
+                    }
+                    Ok(())
+                }
+            },
+        );
+
+        // Clear captured MCP payloads once their org's retention window
+        // elapses (see mcp::payload_capture)
+        let pool_for_capture_sweep = pool.clone();
+        scheduler.register(
+            "payload_capture_retention",
+            "Clear captured MCP payloads past their org's retention window",
+            "0 * * * *",
+            move || {
+                let pool = pool_for_capture_sweep.clone();
+                async move {
+                    let cleared = crate::mcp::payload_capture::run_retention_sweep(&pool)
+                        .await
+                        .map_err(|e| e.to_string())?;
+                    if cleared > 0 {
+                        tracing::info!(count = cleared, "Cleared expired captured MCP payloads");
+                    }
+                    Ok(())
+                }
+            },
+        );
+
+        let scheduler = Arc::new(scheduler);
+        scheduler.spawn();
+
+        // Distributed tracing export (no-op without OTEL_EXPORTER_OTLP_ENDPOINT)
+        crate::otel::init(config.otel_endpoint.as_deref());
+
+        // Structured access logging (no-op without ACCESS_LOG_SINK)
+        crate::access_log::init(&config);
+
+        // Staff Slack channel for machine-created support tickets (optional)
+        let support_webhook_url = std::env::var("SLACK_SUPPORT_WEBHOOK_URL").ok();
+
+        // Analytics goal matchers (compiled + cached off the collect path)
+        let goal_engine = Arc::new(crate::goals::GoalEngine::new(pool.clone()));
+
+        // Read-only maintenance mode, seeded from READ_ONLY_MODE
+        let read_only = Arc::new(crate::read_only::ReadOnlyMode::from_env());
+
+        // Disk buffer for audit/usage writes that fail while Postgres is
+        // down; the recovery loop replays it once the database is back
+        let audit_buffer = Arc::new(crate::mcp::audit_buffer::AuditWriteBuffer::new(
+            &config.audit_buffer_dir,
+        ));
+        let buffer_for_replay = audit_buffer.clone();
+        let pool_for_replay = pool.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(crate::mcp::audit_buffer::REPLAY_INTERVAL);
+            loop {
+                interval.tick().await;
+                buffer_for_replay.replay(&pool_for_replay).await;
+            }
+        });
+
         // Initialize token cache for Supabase verification (prevents rate limiting)
-        let token_cache = Arc::new(tokio::sync::RwLock::new(std::collections::HashMap::new()));
+        let token_cache = Arc::new(TokenCache::default());
         tracing::info!("Supabase token cache initialized");
 
         // Initialize in-flight requests map for request coalescing
@@ -201,6 +488,36 @@ impl AppState {
             Arc::new(tokio::sync::Mutex::new(std::collections::HashMap::new()));
         tracing::info!("Request coalescing initialized for Supabase verification");
 
+        // Probe state starts in the "starting" phase; main() marks it
+        // started once migrations and bootstrap have completed
+        let probes = ProbeState::new();
+
+        // Content moderation engine for the proxy path
+        let moderation = Arc::new(crate::mcp::moderation::ModerationEngine::from_env(
+            pool.clone(),
+        ));
+        tracing::info!("Content moderation engine initialized");
+
+        // Object storage for attachments (local filesystem by default)
+        let storage = match plexmcp_shared::StorageBackend::from_config(&config.storage) {
+            Ok(backend) => {
+                tracing::info!("Storage backend initialized: {}", backend.backend_name());
+                Some(Arc::new(backend))
+            }
+            Err(e) => {
+                tracing::warn!("Storage backend not available, attachments disabled: {}", e);
+                None
+            }
+        };
+
+        // Virus scanner for uploaded attachments
+        let virus_scanner = crate::virus_scan::ScannerBackend::from_env();
+        if virus_scanner.is_enabled() {
+            tracing::info!("Virus scanning enabled via {}", virus_scanner.name());
+        } else {
+            tracing::warn!("Virus scanning not configured (missing VIRUS_SCAN_PROVIDER) - uploads are marked clean");
+        }
+
         Self {
             pool,
             config,
@@ -219,6 +536,20 @@ impl AppState {
             alert_service,
             token_cache,
             in_flight_requests,
+            probes,
+            moderation,
+            tool_cache,
+            request_dedup,
+            tool_catalog,
+            mcp_streams,
+            storage,
+            virus_scanner,
+            support_webhook_url,
+            goal_engine,
+            read_only,
+            audit_buffer,
+            scheduler,
+            deprecations,
         }
     }
 
@@ -0,0 +1,551 @@
+//! Support ticket routing rules engine
+//!
+//! Walks the admin-defined rules in `support_routing_rules` whenever a
+//! ticket is created or a customer replies (dashboard, public form, or
+//! inbound email). A rule's conditions all have to hold (AND across
+//! condition types, any-of within each list); its actions then run in a
+//! fixed order: assign, set priority, tag, canned response. Rules are
+//! evaluated by position and a matching rule can stop the walk.
+//!
+//! The engine runs as a spawned background task after the triggering
+//! request has committed - a broken rule must never fail ticket
+//! creation, so failures here are logged and swallowed.
+
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::state::AppState;
+
+/// Valid `ticket_category` values for the category condition
+pub const VALID_CATEGORIES: [&str; 6] = [
+    "general",
+    "billing",
+    "technical",
+    "feature_request",
+    "bug_report",
+    "enterprise_inquiry",
+];
+
+/// Valid `ticket_priority` values for the set_priority action
+pub const VALID_PRIORITIES: [&str; 4] = ["low", "medium", "high", "urgent"];
+
+/// Canned responses are capped at the dashboard message limit
+const MAX_TEMPLATE_LENGTH: usize = 50_000;
+
+/// Tags are short labels, not prose
+const MAX_TAG_LENGTH: usize = 50;
+
+// =============================================================================
+// Rule Schema
+// =============================================================================
+
+/// Which event a rule run was triggered by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleTrigger {
+    TicketCreated,
+    CustomerReply,
+}
+
+impl RuleTrigger {
+    fn matches_applies_to(self, applies_to: &str) -> bool {
+        match self {
+            RuleTrigger::TicketCreated => applies_to != "customer_reply",
+            RuleTrigger::CustomerReply => applies_to != "ticket_created",
+        }
+    }
+}
+
+/// Conditions block of a rule. Every present condition must hold;
+/// within a list, any entry matching is enough. An empty block matches
+/// every ticket.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleConditions {
+    /// Ticket category is one of these
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<Vec<String>>,
+    /// Subject or message text contains one of these (case-insensitive)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<Vec<String>>,
+    /// The ticket org's subscription tier is one of these; tickets
+    /// without an org never match when this is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org_tiers: Option<Vec<String>>,
+}
+
+/// Actions block of a rule. At least one action must be set.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleActions {
+    /// Assign the ticket to this staff user (only if still unassigned)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assign_to: Option<Uuid>,
+    /// Override the ticket priority
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub set_priority: Option<String>,
+    /// Append these tags (deduplicated)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub add_tags: Option<Vec<String>>,
+    /// Post a canned staff response; supports {{ticket_number}} and
+    /// {{subject}} placeholders. At most one template is posted per run
+    /// even when several rules match.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_template: Option<String>,
+}
+
+/// Validate a rule's JSONB blocks at CRUD time so the engine never sees
+/// malformed rules. Returns a human-readable reason on failure.
+pub fn validate_rule(
+    conditions: &serde_json::Value,
+    actions: &serde_json::Value,
+) -> Result<(), String> {
+    let conditions: RuleConditions = serde_json::from_value(conditions.clone())
+        .map_err(|e| format!("Invalid conditions: {}", e))?;
+    let actions: RuleActions =
+        serde_json::from_value(actions.clone()).map_err(|e| format!("Invalid actions: {}", e))?;
+
+    if let Some(categories) = &conditions.category {
+        if categories.is_empty() {
+            return Err("category condition must list at least one category".into());
+        }
+        for category in categories {
+            if !VALID_CATEGORIES.contains(&category.as_str()) {
+                return Err(format!("Unknown ticket category: {}", category));
+            }
+        }
+    }
+    if let Some(keywords) = &conditions.keywords {
+        if keywords.is_empty() || keywords.iter().any(|k| k.trim().is_empty()) {
+            return Err("keywords condition must list non-empty keywords".into());
+        }
+    }
+    if let Some(tiers) = &conditions.org_tiers {
+        if tiers.is_empty() || tiers.iter().any(|t| t.trim().is_empty()) {
+            return Err("org_tiers condition must list non-empty tiers".into());
+        }
+    }
+
+    if let Some(priority) = &actions.set_priority {
+        if !VALID_PRIORITIES.contains(&priority.as_str()) {
+            return Err(format!("Unknown ticket priority: {}", priority));
+        }
+    }
+    if let Some(tags) = &actions.add_tags {
+        if tags.is_empty() {
+            return Err("add_tags action must list at least one tag".into());
+        }
+        for tag in tags {
+            if tag.trim().is_empty() || tag.len() > MAX_TAG_LENGTH {
+                return Err(format!("Tags must be 1-{} characters", MAX_TAG_LENGTH));
+            }
+        }
+    }
+    if let Some(template) = &actions.reply_template {
+        if template.trim().is_empty() {
+            return Err("reply_template must not be empty".into());
+        }
+        if template.len() > MAX_TEMPLATE_LENGTH {
+            return Err(format!(
+                "reply_template must be at most {} characters",
+                MAX_TEMPLATE_LENGTH
+            ));
+        }
+    }
+
+    if actions.assign_to.is_none()
+        && actions.set_priority.is_none()
+        && actions.add_tags.is_none()
+        && actions.reply_template.is_none()
+    {
+        return Err("A rule needs at least one action".into());
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+// Matching
+// =============================================================================
+
+/// The facts about a ticket the conditions are evaluated against
+#[derive(Debug)]
+struct TicketFacts {
+    category: String,
+    subject: String,
+    /// The triggering message text (initial message or latest customer reply)
+    content: String,
+    org_tier: Option<String>,
+}
+
+fn rule_matches(conditions: &RuleConditions, facts: &TicketFacts) -> bool {
+    if let Some(categories) = &conditions.category {
+        if !categories.iter().any(|c| c == &facts.category) {
+            return false;
+        }
+    }
+    if let Some(keywords) = &conditions.keywords {
+        let haystack = format!("{} {}", facts.subject, facts.content).to_lowercase();
+        if !keywords
+            .iter()
+            .any(|k| haystack.contains(&k.trim().to_lowercase()))
+        {
+            return false;
+        }
+    }
+    if let Some(tiers) = &conditions.org_tiers {
+        match &facts.org_tier {
+            Some(tier) => {
+                if !tiers.iter().any(|t| t == tier) {
+                    return false;
+                }
+            }
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Fill the placeholders a canned response may use
+fn render_template(template: &str, ticket_number: &str, subject: &str) -> String {
+    template
+        .replace("{{ticket_number}}", ticket_number)
+        .replace("{{subject}}", subject)
+}
+
+// =============================================================================
+// Execution
+// =============================================================================
+
+/// Run the routing rules for a ticket. Spawn this after the triggering
+/// write has committed; failures are logged, never propagated.
+pub async fn run_rules(state: AppState, ticket_id: Uuid, trigger: RuleTrigger) {
+    if let Err(e) = apply_rules(&state, ticket_id, trigger).await {
+        tracing::error!(
+            ticket_id = %ticket_id,
+            error = %e,
+            "Support routing rules run failed"
+        );
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct RuleRow {
+    id: Uuid,
+    name: String,
+    applies_to: String,
+    conditions: serde_json::Value,
+    actions: serde_json::Value,
+    stop_processing: bool,
+}
+
+async fn apply_rules(
+    state: &AppState,
+    ticket_id: Uuid,
+    trigger: RuleTrigger,
+) -> Result<(), sqlx::Error> {
+    let facts = load_facts(&state.pool, ticket_id, trigger).await?;
+    let Some((facts, ticket_number)) = facts else {
+        tracing::warn!(ticket_id = %ticket_id, "Routing rules skipped: ticket not found");
+        return Ok(());
+    };
+
+    let rules: Vec<RuleRow> = sqlx::query_as(
+        r#"
+        SELECT id, name, applies_to, conditions, actions, stop_processing
+        FROM support_routing_rules
+        WHERE enabled
+        ORDER BY position, created_at
+        "#,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let mut replied = false;
+    for rule in rules {
+        if !trigger.matches_applies_to(&rule.applies_to) {
+            continue;
+        }
+
+        // Rules are validated at CRUD time; a row that no longer parses
+        // (e.g. hand-edited) is skipped, not fatal
+        let (Ok(conditions), Ok(actions)) = (
+            serde_json::from_value::<RuleConditions>(rule.conditions),
+            serde_json::from_value::<RuleActions>(rule.actions),
+        ) else {
+            tracing::warn!(rule_id = %rule.id, "Skipping unparseable routing rule");
+            continue;
+        };
+
+        if !rule_matches(&conditions, &facts) {
+            continue;
+        }
+
+        tracing::info!(
+            ticket_id = %ticket_id,
+            rule_id = %rule.id,
+            rule_name = %rule.name,
+            "Routing rule matched"
+        );
+
+        if let Some(assignee) = actions.assign_to {
+            sqlx::query(
+                r#"
+                UPDATE support_tickets
+                SET assigned_to = $2, updated_at = NOW()
+                WHERE id = $1 AND assigned_to IS NULL
+                "#,
+            )
+            .bind(ticket_id)
+            .bind(assignee)
+            .execute(&state.pool)
+            .await?;
+        }
+
+        if let Some(priority) = &actions.set_priority {
+            sqlx::query(
+                r#"
+                UPDATE support_tickets
+                SET priority = $2::ticket_priority, updated_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(ticket_id)
+            .bind(priority)
+            .execute(&state.pool)
+            .await?;
+        }
+
+        if let Some(tags) = &actions.add_tags {
+            sqlx::query(
+                r#"
+                UPDATE support_tickets
+                SET tags = ARRAY(SELECT DISTINCT unnest(tags || $2::text[])), updated_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(ticket_id)
+            .bind(tags)
+            .execute(&state.pool)
+            .await?;
+        }
+
+        if let Some(template) = &actions.reply_template {
+            // One canned response per run, no matter how many rules carry one
+            if !replied {
+                replied = true;
+                let content = render_template(template, &ticket_number, &facts.subject);
+                sqlx::query(
+                    r#"
+                    INSERT INTO ticket_messages (ticket_id, sender_id, is_admin_reply, content)
+                    VALUES ($1, NULL, true, $2)
+                    "#,
+                )
+                .bind(ticket_id)
+                .bind(&content)
+                .execute(&state.pool)
+                .await?;
+            }
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE support_routing_rules
+            SET match_count = match_count + 1, last_matched_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(rule.id)
+        .execute(&state.pool)
+        .await?;
+
+        if rule.stop_processing {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Assemble the facts the conditions run against: ticket fields, the
+/// org's tier, and the triggering message text
+async fn load_facts(
+    pool: &PgPool,
+    ticket_id: Uuid,
+    trigger: RuleTrigger,
+) -> Result<Option<(TicketFacts, String)>, sqlx::Error> {
+    #[derive(sqlx::FromRow)]
+    struct FactsRow {
+        ticket_number: String,
+        category: String,
+        subject: String,
+        org_tier: Option<String>,
+    }
+
+    let row: Option<FactsRow> = sqlx::query_as(
+        r#"
+        SELECT t.ticket_number, t.category::text AS category, t.subject,
+               o.subscription_tier AS org_tier
+        FROM support_tickets t
+        LEFT JOIN organizations o ON o.id = t.organization_id
+        WHERE t.id = $1
+        "#,
+    )
+    .bind(ticket_id)
+    .fetch_optional(pool)
+    .await?;
+    let Some(row) = row else {
+        return Ok(None);
+    };
+
+    let content: Option<String> = match trigger {
+        RuleTrigger::TicketCreated => {
+            sqlx::query_scalar(
+                r#"
+                SELECT content FROM ticket_messages
+                WHERE ticket_id = $1 AND NOT is_admin_reply
+                ORDER BY created_at ASC
+                LIMIT 1
+                "#,
+            )
+            .bind(ticket_id)
+            .fetch_optional(pool)
+            .await?
+        }
+        RuleTrigger::CustomerReply => {
+            sqlx::query_scalar(
+                r#"
+                SELECT content FROM ticket_messages
+                WHERE ticket_id = $1 AND NOT is_admin_reply
+                ORDER BY created_at DESC
+                LIMIT 1
+                "#,
+            )
+            .bind(ticket_id)
+            .fetch_optional(pool)
+            .await?
+        }
+    };
+
+    Ok(Some((
+        TicketFacts {
+            category: row.category,
+            subject: row.subject,
+            content: content.unwrap_or_default(),
+            org_tier: row.org_tier,
+        },
+        row.ticket_number,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn facts() -> TicketFacts {
+        TicketFacts {
+            category: "technical".to_string(),
+            subject: "Proxy timeout on every request".to_string(),
+            content: "Our MCP calls started failing this morning.".to_string(),
+            org_tier: Some("pro".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_empty_conditions_match_everything() {
+        assert!(rule_matches(&RuleConditions::default(), &facts()));
+    }
+
+    #[test]
+    fn test_category_condition() {
+        let conditions = RuleConditions {
+            category: Some(vec!["billing".into(), "technical".into()]),
+            ..Default::default()
+        };
+        assert!(rule_matches(&conditions, &facts()));
+
+        let conditions = RuleConditions {
+            category: Some(vec!["billing".into()]),
+            ..Default::default()
+        };
+        assert!(!rule_matches(&conditions, &facts()));
+    }
+
+    #[test]
+    fn test_keyword_condition_is_case_insensitive() {
+        let conditions = RuleConditions {
+            keywords: Some(vec!["TIMEOUT".into()]),
+            ..Default::default()
+        };
+        assert!(rule_matches(&conditions, &facts()));
+
+        let conditions = RuleConditions {
+            keywords: Some(vec!["refund".into()]),
+            ..Default::default()
+        };
+        assert!(!rule_matches(&conditions, &facts()));
+    }
+
+    #[test]
+    fn test_org_tier_condition_requires_an_org() {
+        let conditions = RuleConditions {
+            org_tiers: Some(vec!["pro".into(), "enterprise".into()]),
+            ..Default::default()
+        };
+        assert!(rule_matches(&conditions, &facts()));
+
+        let mut orgless = facts();
+        orgless.org_tier = None;
+        assert!(!rule_matches(&conditions, &orgless));
+    }
+
+    #[test]
+    fn test_conditions_combine_with_and() {
+        let conditions = RuleConditions {
+            category: Some(vec!["technical".into()]),
+            keywords: Some(vec!["refund".into()]),
+            ..Default::default()
+        };
+        assert!(!rule_matches(&conditions, &facts()));
+    }
+
+    #[test]
+    fn test_render_template() {
+        let rendered = render_template(
+            "Re {{ticket_number}}: we received \"{{subject}}\"",
+            "PLX-00042",
+            "Proxy timeout",
+        );
+        assert_eq!(rendered, "Re PLX-00042: we received \"Proxy timeout\"");
+    }
+
+    #[test]
+    fn test_validate_rule() {
+        let ok = validate_rule(
+            &serde_json::json!({"category": ["technical"], "keywords": ["timeout"]}),
+            &serde_json::json!({"set_priority": "high", "add_tags": ["latency"]}),
+        );
+        assert!(ok.is_ok());
+
+        // No actions
+        assert!(validate_rule(&serde_json::json!({}), &serde_json::json!({})).is_err());
+        // Unknown category
+        assert!(validate_rule(
+            &serde_json::json!({"category": ["nonsense"]}),
+            &serde_json::json!({"set_priority": "high"}),
+        )
+        .is_err());
+        // Unknown priority
+        assert!(validate_rule(
+            &serde_json::json!({}),
+            &serde_json::json!({"set_priority": "asap"}),
+        )
+        .is_err());
+        // Unknown field is rejected, not ignored
+        assert!(validate_rule(
+            &serde_json::json!({"categories": ["technical"]}),
+            &serde_json::json!({"set_priority": "high"}),
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,328 @@
+//! Virus scanning for uploaded attachments
+//!
+//! Mirrors the [`crate::email_provider`] backend pattern: concrete scanners
+//! (ClamAV over its TCP INSTREAM protocol, or a generic cloud scanning API)
+//! sit behind a [`ScannerBackend`] enum selected via `VIRUS_SCAN_PROVIDER`.
+//! When no scanner is configured the backend is `Disabled` and uploads are
+//! marked clean immediately - self-hosted installs without ClamAV keep
+//! working, hosted deployments opt in.
+//!
+//! Scans run asynchronously after upload; the attachment row carries the
+//! scan status and downloads are blocked until the verdict is `clean`.
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// Result of scanning a single file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanVerdict {
+    /// No threats found
+    Clean,
+    /// Malware detected
+    Infected {
+        /// Signature name reported by the scanner
+        signature: String,
+    },
+    /// The scan itself failed (scanner unreachable, protocol error)
+    Failed {
+        /// Error detail for the attachment record
+        reason: String,
+    },
+}
+
+// =============================================================================
+// ClamAV (clamd INSTREAM protocol)
+// =============================================================================
+
+/// ClamAV daemon scanner speaking the clamd TCP protocol
+#[derive(Clone)]
+pub struct ClamAvScanner {
+    addr: String,
+}
+
+/// Chunk size for INSTREAM uploads (clamd default StreamMaxLength is far larger)
+const INSTREAM_CHUNK_SIZE: usize = 8192;
+
+impl ClamAvScanner {
+    /// Create a scanner targeting the given `host:port`
+    pub fn new(addr: String) -> Self {
+        Self { addr }
+    }
+
+    /// Scanner name for logging
+    pub fn name(&self) -> &'static str {
+        "clamav"
+    }
+
+    /// Scan a byte buffer via the clamd `zINSTREAM` command
+    pub async fn scan(&self, data: &[u8]) -> ScanVerdict {
+        match self.scan_inner(data).await {
+            Ok(verdict) => verdict,
+            Err(reason) => {
+                tracing::error!(addr = %self.addr, reason = %reason, "ClamAV scan failed");
+                ScanVerdict::Failed { reason }
+            }
+        }
+    }
+
+    async fn scan_inner(&self, data: &[u8]) -> Result<ScanVerdict, String> {
+        let mut stream = TcpStream::connect(&self.addr)
+            .await
+            .map_err(|e| format!("connect to clamd at {}: {}", self.addr, e))?;
+
+        // INSTREAM: null-terminated command, then length-prefixed chunks,
+        // terminated by a zero-length chunk
+        stream
+            .write_all(b"zINSTREAM\0")
+            .await
+            .map_err(|e| format!("send INSTREAM command: {}", e))?;
+
+        for chunk in data.chunks(INSTREAM_CHUNK_SIZE) {
+            stream
+                .write_all(&(chunk.len() as u32).to_be_bytes())
+                .await
+                .map_err(|e| format!("send chunk length: {}", e))?;
+            stream
+                .write_all(chunk)
+                .await
+                .map_err(|e| format!("send chunk: {}", e))?;
+        }
+        stream
+            .write_all(&0u32.to_be_bytes())
+            .await
+            .map_err(|e| format!("send stream terminator: {}", e))?;
+
+        let mut response = Vec::new();
+        stream
+            .read_to_end(&mut response)
+            .await
+            .map_err(|e| format!("read clamd response: {}", e))?;
+
+        let response = String::from_utf8_lossy(&response);
+        parse_clamd_response(&response)
+    }
+}
+
+/// Parse a clamd scan response line into a verdict
+///
+/// Responses look like `stream: OK`, `stream: Eicar-Signature FOUND` or
+/// `INSTREAM size limit exceeded. ERROR`, null- or newline-terminated.
+fn parse_clamd_response(response: &str) -> Result<ScanVerdict, String> {
+    let line = response.trim_end_matches(['\0', '\n']).trim();
+
+    if let Some(rest) = line.strip_suffix("FOUND") {
+        let signature = rest
+            .trim_end()
+            .strip_prefix("stream:")
+            .unwrap_or(rest)
+            .trim()
+            .to_string();
+        return Ok(ScanVerdict::Infected { signature });
+    }
+    if line.ends_with("OK") {
+        return Ok(ScanVerdict::Clean);
+    }
+    if line.ends_with("ERROR") {
+        return Err(format!("clamd error: {}", line));
+    }
+    Err(format!("unexpected clamd response: {}", line))
+}
+
+// =============================================================================
+// Cloud scanning API (HTTP)
+// =============================================================================
+
+/// Generic cloud scanner: POSTs the file bytes and expects a JSON verdict
+/// of the form `{"status": "clean" | "infected", "signature": "..."}`
+#[derive(Clone)]
+pub struct CloudScanner {
+    api_url: String,
+    api_key: Option<String>,
+    client: reqwest::Client,
+}
+
+impl CloudScanner {
+    /// Create a scanner targeting the given API endpoint
+    pub fn new(api_url: String, api_key: Option<String>) -> Self {
+        Self {
+            api_url,
+            api_key,
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(60))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Scanner name for logging
+    pub fn name(&self) -> &'static str {
+        "cloud"
+    }
+
+    /// Submit the file bytes for scanning
+    pub async fn scan(&self, data: &[u8], content_type: &str) -> ScanVerdict {
+        let mut request = self
+            .client
+            .post(&self.api_url)
+            .header("Content-Type", content_type)
+            .body(data.to_vec());
+
+        if let Some(ref key) = self.api_key {
+            request = request.header("Authorization", format!("Bearer {}", key));
+        }
+
+        let response = match request.send().await {
+            Ok(resp) => resp,
+            Err(e) => {
+                tracing::error!(error = %e, "Cloud virus scan request failed");
+                return ScanVerdict::Failed {
+                    reason: format!("scan API request failed: {}", e),
+                };
+            }
+        };
+
+        if !response.status().is_success() {
+            let status = response.status();
+            tracing::error!(status = %status, "Cloud virus scan API returned error");
+            return ScanVerdict::Failed {
+                reason: format!("scan API returned HTTP {}", status),
+            };
+        }
+
+        let body: serde_json::Value = match response.json().await {
+            Ok(body) => body,
+            Err(e) => {
+                return ScanVerdict::Failed {
+                    reason: format!("invalid scan API response: {}", e),
+                }
+            }
+        };
+
+        match body.get("status").and_then(|s| s.as_str()) {
+            Some("clean") => ScanVerdict::Clean,
+            Some("infected") => ScanVerdict::Infected {
+                signature: body
+                    .get("signature")
+                    .and_then(|s| s.as_str())
+                    .unwrap_or("unknown")
+                    .to_string(),
+            },
+            other => ScanVerdict::Failed {
+                reason: format!("unexpected scan API status: {:?}", other),
+            },
+        }
+    }
+}
+
+// =============================================================================
+// Backend selection
+// =============================================================================
+
+/// Configured scanner backend, selected via `VIRUS_SCAN_PROVIDER`
+#[derive(Clone)]
+pub enum ScannerBackend {
+    ClamAv(ClamAvScanner),
+    Cloud(CloudScanner),
+    /// No scanner configured - uploads are marked clean without scanning
+    Disabled,
+}
+
+impl ScannerBackend {
+    /// Select a backend from environment variables.
+    ///
+    /// `VIRUS_SCAN_PROVIDER=clamav` uses `CLAMAV_ADDR` (default
+    /// `127.0.0.1:3310`); `VIRUS_SCAN_PROVIDER=cloud` requires
+    /// `VIRUS_SCAN_API_URL` and optionally `VIRUS_SCAN_API_KEY`. Anything
+    /// else (including unset) disables scanning.
+    pub fn from_env() -> Self {
+        let provider = std::env::var("VIRUS_SCAN_PROVIDER")
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match provider.as_str() {
+            "clamav" => {
+                let addr =
+                    std::env::var("CLAMAV_ADDR").unwrap_or_else(|_| "127.0.0.1:3310".to_string());
+                ScannerBackend::ClamAv(ClamAvScanner::new(addr))
+            }
+            "cloud" => {
+                let api_url = std::env::var("VIRUS_SCAN_API_URL").unwrap_or_default();
+                if api_url.is_empty() {
+                    tracing::warn!(
+                        "VIRUS_SCAN_PROVIDER=cloud but VIRUS_SCAN_API_URL is not configured"
+                    );
+                    ScannerBackend::Disabled
+                } else {
+                    let api_key = std::env::var("VIRUS_SCAN_API_KEY")
+                        .ok()
+                        .filter(|k| !k.is_empty());
+                    ScannerBackend::Cloud(CloudScanner::new(api_url, api_key))
+                }
+            }
+            _ => ScannerBackend::Disabled,
+        }
+    }
+
+    /// Whether a scanner is configured
+    pub fn is_enabled(&self) -> bool {
+        !matches!(self, ScannerBackend::Disabled)
+    }
+
+    /// Scanner name for logging and the attachment record
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScannerBackend::ClamAv(s) => s.name(),
+            ScannerBackend::Cloud(s) => s.name(),
+            ScannerBackend::Disabled => "disabled",
+        }
+    }
+
+    /// Scan a file through the configured scanner
+    pub async fn scan(&self, data: &[u8], content_type: &str) -> ScanVerdict {
+        match self {
+            ScannerBackend::ClamAv(s) => s.scan(data).await,
+            ScannerBackend::Cloud(s) => s.scan(data, content_type).await,
+            ScannerBackend::Disabled => ScanVerdict::Clean,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_clamd_clean() {
+        assert_eq!(
+            parse_clamd_response("stream: OK\0").unwrap(),
+            ScanVerdict::Clean
+        );
+        assert_eq!(
+            parse_clamd_response("stream: OK\n").unwrap(),
+            ScanVerdict::Clean
+        );
+    }
+
+    #[test]
+    fn test_parse_clamd_infected() {
+        assert_eq!(
+            parse_clamd_response("stream: Eicar-Signature FOUND\0").unwrap(),
+            ScanVerdict::Infected {
+                signature: "Eicar-Signature".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_clamd_error() {
+        let err = parse_clamd_response("INSTREAM size limit exceeded. ERROR\0").unwrap_err();
+        assert!(err.contains("size limit exceeded"));
+    }
+
+    #[test]
+    fn test_parse_clamd_garbage() {
+        assert!(parse_clamd_response("").is_err());
+        assert!(parse_clamd_response("not a clamd response").is_err());
+    }
+}
@@ -0,0 +1,263 @@
+//! Realtime analytics feed for the admin dashboard
+//!
+//! Streams live visitor counts, active pages, and goal conversions over the
+//! existing WebSocket backplane so the dashboard doesn't have to poll
+//! `GET /admin/analytics/website/realtime`. Subscriptions are gated to
+//! platform admins at the event handler; a single background task polls
+//! the analytics tables while at least one subscriber is connected and
+//! broadcasts a throttled snapshot, skipping ticks where nothing changed.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use sqlx::PgPool;
+
+use super::connection::Connection;
+use super::events::{AnalyticsActivePage, AnalyticsConversion, ServerEvent};
+
+/// How often the feed polls the analytics tables while subscribed
+const BROADCAST_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Maximum number of active pages included in each snapshot
+const ACTIVE_PAGES_LIMIT: i64 = 10;
+
+/// Maximum number of recent conversions included in each snapshot
+const RECENT_CONVERSIONS_LIMIT: i64 = 10;
+
+/// One polled snapshot of the realtime analytics tables
+#[derive(Debug, Clone, PartialEq)]
+struct Snapshot {
+    active_visitors: i64,
+    active_pages: Vec<AnalyticsActivePage>,
+    recent_conversions: Vec<AnalyticsConversion>,
+}
+
+impl Snapshot {
+    fn into_event(self) -> ServerEvent {
+        ServerEvent::AnalyticsUpdate {
+            active_visitors: self.active_visitors,
+            active_pages: self.active_pages,
+            recent_conversions: self.recent_conversions,
+        }
+    }
+}
+
+/// Realtime analytics pub/sub for admin dashboard connections
+pub struct AnalyticsFeed {
+    /// Subscribed connections indexed by session_id
+    subscribers: Arc<RwLock<HashMap<Uuid, Arc<Connection>>>>,
+
+    /// Whether the polling task is currently running
+    running: Arc<AtomicBool>,
+}
+
+impl AnalyticsFeed {
+    pub fn new() -> Self {
+        Self {
+            subscribers: Arc::new(RwLock::new(HashMap::new())),
+            running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Subscribe a connection to the analytics feed
+    ///
+    /// Sends an immediate snapshot to the new subscriber and starts the
+    /// polling task if it isn't already running. Permission gating happens
+    /// in the event handler before this is called.
+    pub async fn subscribe(&self, conn: Arc<Connection>, pool: PgPool) {
+        // Immediate snapshot so the dashboard doesn't wait a full tick
+        match poll_snapshot(&pool).await {
+            Ok(snapshot) => {
+                let _ = conn.send(snapshot.into_event());
+            }
+            Err(e) => {
+                tracing::error!(error = ?e, "Failed to build initial analytics snapshot");
+            }
+        }
+
+        {
+            let mut subs = self.subscribers.write().await;
+            subs.insert(conn.session_id, conn);
+        }
+
+        // First subscriber starts the polling task
+        if !self.running.swap(true, Ordering::SeqCst) {
+            let subscribers = Arc::clone(&self.subscribers);
+            let running = Arc::clone(&self.running);
+            tokio::spawn(async move {
+                poll_loop(pool, subscribers, running).await;
+            });
+        }
+    }
+
+    /// Unsubscribe a connection from the analytics feed
+    pub async fn unsubscribe(&self, session_id: &Uuid) {
+        let mut subs = self.subscribers.write().await;
+        subs.remove(session_id);
+    }
+
+    /// Number of current subscribers
+    pub async fn subscriber_count(&self) -> usize {
+        let subs = self.subscribers.read().await;
+        subs.len()
+    }
+}
+
+impl Default for AnalyticsFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Poll the analytics tables and broadcast snapshots until all
+/// subscribers are gone
+async fn poll_loop(
+    pool: PgPool,
+    subscribers: Arc<RwLock<HashMap<Uuid, Arc<Connection>>>>,
+    running: Arc<AtomicBool>,
+) {
+    tracing::debug!("Analytics feed polling started");
+    let mut interval = tokio::time::interval(BROADCAST_INTERVAL);
+    // First tick fires immediately; subscribers already got a snapshot
+    interval.tick().await;
+    let mut last_snapshot: Option<Snapshot> = None;
+
+    loop {
+        interval.tick().await;
+
+        {
+            let subs = subscribers.read().await;
+            if subs.is_empty() {
+                break;
+            }
+        }
+
+        let snapshot = match poll_snapshot(&pool).await {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                tracing::error!(error = ?e, "Failed to poll analytics snapshot");
+                continue;
+            }
+        };
+
+        // Throttle: don't wake every dashboard when nothing changed
+        if last_snapshot.as_ref() == Some(&snapshot) {
+            continue;
+        }
+        last_snapshot = Some(snapshot.clone());
+
+        let event = snapshot.into_event();
+        let mut closed = Vec::new();
+        {
+            let subs = subscribers.read().await;
+            for (session_id, conn) in subs.iter() {
+                if conn.send(event.clone()).is_err() {
+                    closed.push(*session_id);
+                }
+            }
+        }
+        if !closed.is_empty() {
+            let mut subs = subscribers.write().await;
+            for session_id in closed {
+                subs.remove(&session_id);
+            }
+        }
+    }
+
+    running.store(false, Ordering::SeqCst);
+    tracing::debug!("Analytics feed polling stopped (no subscribers)");
+}
+
+/// Build one snapshot from the realtime analytics tables
+async fn poll_snapshot(pool: &PgPool) -> Result<Snapshot, sqlx::Error> {
+    // Expire stale realtime sessions first, same as get_realtime
+    sqlx::query("SELECT cleanup_analytics_realtime()")
+        .execute(pool)
+        .await
+        .ok();
+
+    let active_visitors: i64 =
+        sqlx::query_scalar("SELECT COUNT(*)::bigint FROM analytics_realtime")
+            .fetch_one(pool)
+            .await?;
+
+    let active_pages: Vec<AnalyticsActivePage> = sqlx::query_as(
+        r#"
+        SELECT current_page as path, COUNT(*)::bigint as visitors
+        FROM analytics_realtime
+        WHERE current_page IS NOT NULL
+        GROUP BY current_page
+        ORDER BY visitors DESC, current_page
+        LIMIT $1
+        "#,
+    )
+    .bind(ACTIVE_PAGES_LIMIT)
+    .fetch_all(pool)
+    .await?;
+
+    let recent_conversions: Vec<AnalyticsConversion> = sqlx::query_as(
+        r#"
+        SELECT c.id, c.goal_id, g.name as goal_name, c.value_cents, c.created_at
+        FROM analytics_conversions c
+        JOIN analytics_goals g ON g.id = c.goal_id
+        WHERE c.created_at >= NOW() - interval '5 minutes'
+        ORDER BY c.created_at DESC
+        LIMIT $1
+        "#,
+    )
+    .bind(RECENT_CONVERSIONS_LIMIT)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(Snapshot {
+        active_visitors,
+        active_pages,
+        recent_conversions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::connection::StaffLevel;
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn test_subscribe_and_unsubscribe_tracking() {
+        let feed = AnalyticsFeed::new();
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let conn = Arc::new(Connection::new(Uuid::new_v4(), StaffLevel::Admin, tx));
+        let session_id = conn.session_id;
+
+        // Insert directly: subscribe() needs a live pool for the initial snapshot
+        feed.subscribers.write().await.insert(session_id, conn);
+        assert_eq!(feed.subscriber_count().await, 1);
+
+        feed.unsubscribe(&session_id).await;
+        assert_eq!(feed.subscriber_count().await, 0);
+    }
+
+    #[test]
+    fn test_unchanged_snapshots_compare_equal() {
+        let a = Snapshot {
+            active_visitors: 3,
+            active_pages: vec![AnalyticsActivePage {
+                path: "/pricing".to_string(),
+                visitors: 2,
+            }],
+            recent_conversions: vec![],
+        };
+        let b = a.clone();
+        assert_eq!(a, b);
+
+        let c = Snapshot {
+            active_visitors: 4,
+            ..a.clone()
+        };
+        assert_ne!(a, c);
+    }
+}
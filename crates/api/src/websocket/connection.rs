@@ -9,6 +9,32 @@ use uuid::Uuid;
 
 use super::events::ServerEvent;
 
+/// Staff level of an authenticated WebSocket connection
+///
+/// Derived from `users.platform_role` at connect time and used for
+/// per-event permission filtering when broadcasting to ticket rooms.
+/// Levels are ordered: `User < Staff < Admin`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StaffLevel {
+    /// Regular user (no platform role)
+    User,
+    /// Support staff (`platform_role = 'staff'`)
+    Staff,
+    /// Platform admin (`platform_role = 'admin'` or `'superadmin'`)
+    Admin,
+}
+
+impl StaffLevel {
+    /// Map a `users.platform_role` value to a staff level
+    pub fn from_platform_role(role: Option<&str>) -> Self {
+        match role {
+            Some("admin") | Some("superadmin") => Self::Admin,
+            Some("staff") => Self::Staff,
+            _ => Self::User,
+        }
+    }
+}
+
 /// Represents an active WebSocket connection
 #[derive(Debug)]
 pub struct Connection {
@@ -18,6 +44,9 @@ pub struct Connection {
     /// Authenticated user ID
     pub user_id: Uuid,
 
+    /// Staff level for event permission filtering
+    pub staff_level: StaffLevel,
+
     /// Channel to send events to this connection
     pub sender: mpsc::UnboundedSender<ServerEvent>,
 
@@ -27,10 +56,15 @@ pub struct Connection {
 
 impl Connection {
     /// Create a new connection
-    pub fn new(user_id: Uuid, sender: mpsc::UnboundedSender<ServerEvent>) -> Self {
+    pub fn new(
+        user_id: Uuid,
+        staff_level: StaffLevel,
+        sender: mpsc::UnboundedSender<ServerEvent>,
+    ) -> Self {
         Self {
             session_id: Uuid::new_v4(),
             user_id,
+            staff_level,
             sender,
             subscriptions: Arc::new(RwLock::new(HashSet::new())),
         }
@@ -83,10 +117,38 @@ impl Connection {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_staff_level_from_platform_role() {
+        assert_eq!(
+            StaffLevel::from_platform_role(Some("admin")),
+            StaffLevel::Admin
+        );
+        assert_eq!(
+            StaffLevel::from_platform_role(Some("superadmin")),
+            StaffLevel::Admin
+        );
+        assert_eq!(
+            StaffLevel::from_platform_role(Some("staff")),
+            StaffLevel::Staff
+        );
+        assert_eq!(StaffLevel::from_platform_role(None), StaffLevel::User);
+        assert_eq!(
+            StaffLevel::from_platform_role(Some("other")),
+            StaffLevel::User
+        );
+    }
+
+    #[test]
+    fn test_staff_level_ordering() {
+        assert!(StaffLevel::User < StaffLevel::Staff);
+        assert!(StaffLevel::Staff < StaffLevel::Admin);
+        assert!(StaffLevel::Admin >= StaffLevel::Staff);
+    }
+
     #[tokio::test]
     async fn test_connection_subscription() {
         let (tx, _rx) = mpsc::unbounded_channel();
-        let conn = Connection::new(Uuid::new_v4(), tx);
+        let conn = Connection::new(Uuid::new_v4(), StaffLevel::User, tx);
         let ticket_id = Uuid::new_v4();
 
         // Initially not subscribed
@@ -104,7 +166,7 @@ mod tests {
     #[tokio::test]
     async fn test_multiple_subscriptions() {
         let (tx, _rx) = mpsc::unbounded_channel();
-        let conn = Connection::new(Uuid::new_v4(), tx);
+        let conn = Connection::new(Uuid::new_v4(), StaffLevel::User, tx);
 
         let ticket1 = Uuid::new_v4();
         let ticket2 = Uuid::new_v4();
@@ -36,10 +36,23 @@ pub enum ClientEvent {
     /// Leave ticket view
     LeaveTicketView { ticket_id: Uuid },
 
-    /// Set user presence status (admin only)
+    /// Set user presence status
     SetPresence {
         status: String, // "online" | "away" | "offline"
     },
+
+    /// Subscribe to the realtime analytics feed (platform admins only)
+    SubscribeAnalytics,
+
+    /// Unsubscribe from the realtime analytics feed
+    UnsubscribeAnalytics,
+
+    /// Subscribe to the caller's org event feed (MCP status changes,
+    /// usage threshold crossings, billing events)
+    SubscribeOrgEvents,
+
+    /// Unsubscribe from the org event feed
+    UnsubscribeOrgEvents,
 }
 
 // =============================================================================
@@ -86,6 +99,9 @@ pub enum ServerEvent {
     },
 
     /// Batch presence data (sent on initial connection)
+    ///
+    /// Staff connections receive all online/away users; customer
+    /// connections only receive staff so they see agent availability.
     PresenceBatch { users: Vec<UserPresence> },
 
     /// Ticket viewers list updated
@@ -94,6 +110,34 @@ pub enum ServerEvent {
         viewers: Vec<TicketViewer>,
     },
 
+    /// New ticket created (sent to staff connections, not ticket rooms)
+    TicketCreated {
+        ticket_id: Uuid,
+        ticket_number: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        organization_id: Option<Uuid>,
+        subject: String,
+        priority: String,
+        /// How the ticket arrived: "web", "email" or "api"
+        source: String,
+    },
+
+    /// Org-scoped realtime event (sent to org event feed subscribers)
+    ///
+    /// `event` follows the org webhook naming convention, e.g.
+    /// "mcp.status_changed", "usage.threshold_reached", "billing.invoice_paid"
+    OrgEvent {
+        event: String,
+        data: serde_json::Value,
+    },
+
+    /// Realtime analytics snapshot (sent to admin dashboard subscribers)
+    AnalyticsUpdate {
+        active_visitors: i64,
+        active_pages: Vec<AnalyticsActivePage>,
+        recent_conversions: Vec<AnalyticsConversion>,
+    },
+
     /// Heartbeat response
     Pong,
 
@@ -134,6 +178,25 @@ pub struct TicketViewer {
     pub started_viewing_at: OffsetDateTime,
 }
 
+/// Page currently being viewed, with concurrent visitor count
+#[derive(Debug, Serialize, Clone, PartialEq, sqlx::FromRow)]
+pub struct AnalyticsActivePage {
+    pub path: String,
+    pub visitors: i64,
+}
+
+/// Goal conversion recorded in the last few minutes
+#[derive(Debug, Serialize, Clone, PartialEq, sqlx::FromRow)]
+pub struct AnalyticsConversion {
+    pub id: Uuid,
+    pub goal_id: Uuid,
+    pub goal_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_cents: Option<i32>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
 /// User presence data
 #[derive(Debug, Serialize, Clone)]
 pub struct UserPresence {
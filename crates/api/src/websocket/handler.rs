@@ -20,7 +20,7 @@ use uuid::Uuid;
 use crate::state::AppState;
 
 use super::{
-    connection::Connection,
+    connection::{Connection, StaffLevel},
     events::{ClientEvent, ServerEvent, TicketViewer, UserPresence},
     state::WebSocketState,
 };
@@ -89,21 +89,41 @@ pub async fn ws_handler(
         }
     };
 
-    tracing::info!(user_id = %user_id, "WebSocket connection upgrade requested");
+    // Resolve staff level for per-event permission filtering in ticket rooms
+    let staff_level = match sqlx::query_scalar::<_, Option<String>>(
+        "SELECT platform_role::TEXT FROM users WHERE id = $1",
+    )
+    .bind(user_id)
+    .fetch_optional(&app_state.pool)
+    .await
+    {
+        Ok(role) => StaffLevel::from_platform_role(role.flatten().as_deref()),
+        Err(e) => {
+            tracing::error!(error = ?e, user_id = %user_id, "WebSocket auth: failed to fetch platform role");
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    tracing::info!(user_id = %user_id, staff_level = ?staff_level, "WebSocket connection upgrade requested");
 
     // Upgrade the connection
-    Ok(ws.on_upgrade(move |socket| handle_socket(socket, user_id, app_state)))
+    Ok(ws.on_upgrade(move |socket| handle_socket(socket, user_id, staff_level, app_state)))
 }
 
 /// Handle individual WebSocket connection
-async fn handle_socket(socket: WebSocket, user_id: Uuid, app_state: AppState) {
+async fn handle_socket(
+    socket: WebSocket,
+    user_id: Uuid,
+    staff_level: StaffLevel,
+    app_state: AppState,
+) {
     let (mut sender, mut receiver) = socket.split();
 
     // Create channel for sending events to this connection
     let (tx, mut rx) = mpsc::unbounded_channel::<ServerEvent>();
 
     // Create connection
-    let conn = Connection::new(user_id, tx);
+    let conn = Connection::new(user_id, staff_level, tx);
     let ws_state = app_state.ws_state.clone();
     let conn = ws_state.add_connection(conn).await;
     let session_id = conn.session_id;
@@ -116,8 +136,14 @@ async fn handle_socket(socket: WebSocket, user_id: Uuid, app_state: AppState) {
         tracing::error!(error = ?e, user_id = %user_id, "Failed to update user presence");
     }
 
-    // Send initial presence batch to newly connected client
-    match get_all_user_presence(&app_state.pool).await {
+    // Send initial presence batch to newly connected client. Staff see
+    // everyone; customer connections only see agent availability.
+    let presence_batch = if staff_level >= StaffLevel::Staff {
+        get_all_user_presence(&app_state.pool).await
+    } else {
+        get_staff_presence(&app_state.pool).await
+    };
+    match presence_batch {
         Ok(presence_list) => {
             let _ = conn.send(ServerEvent::PresenceBatch {
                 users: presence_list,
@@ -129,7 +155,7 @@ async fn handle_socket(socket: WebSocket, user_id: Uuid, app_state: AppState) {
     }
 
     // Broadcast presence update to all other clients
-    broadcast_presence_to_all(&ws_state, user_id, "online", None).await;
+    broadcast_presence_to_all(&ws_state, user_id, staff_level, "online", None).await;
 
     // Spawn task to send messages to client
     let send_task = tokio::spawn(async move {
@@ -199,7 +225,7 @@ async fn handle_socket(socket: WebSocket, user_id: Uuid, app_state: AppState) {
     }
 
     // Broadcast offline status to all clients
-    broadcast_presence_to_all(&ws_state, user_id, "offline", None).await;
+    broadcast_presence_to_all(&ws_state, user_id, staff_level, "offline", None).await;
 
     send_task.abort();
 }
@@ -363,10 +389,75 @@ async fn handle_client_event(
             }
 
             // Broadcast to all clients
-            broadcast_presence_to_all(&ws_state, conn.user_id, &status, None).await;
+            broadcast_presence_to_all(&ws_state, conn.user_id, conn.staff_level, &status, None)
+                .await;
 
             tracing::info!(user_id = %conn.user_id, status = %status, "User presence updated");
         }
+
+        SubscribeAnalytics => {
+            // Realtime analytics are platform-admin only
+            if conn.staff_level < StaffLevel::Admin {
+                let _ = conn.send(ServerEvent::Error {
+                    message: "Access denied to analytics feed".to_string(),
+                });
+                return;
+            }
+
+            ws_state
+                .analytics
+                .subscribe(Arc::clone(&conn), app_state.pool.clone())
+                .await;
+            tracing::debug!(
+                session_id = %conn.session_id,
+                "Subscribed to analytics feed"
+            );
+        }
+
+        UnsubscribeAnalytics => {
+            ws_state.analytics.unsubscribe(&conn.session_id).await;
+        }
+
+        SubscribeOrgEvents => {
+            // Scope the feed to the caller's own org
+            let org_id = match sqlx::query_scalar::<_, Option<Uuid>>(
+                "SELECT org_id FROM users WHERE id = $1",
+            )
+            .bind(conn.user_id)
+            .fetch_optional(&app_state.pool)
+            .await
+            {
+                Ok(org_id) => org_id.flatten(),
+                Err(e) => {
+                    tracing::error!(error = ?e, "Failed to resolve org for event feed");
+                    let _ = conn.send(ServerEvent::Error {
+                        message: "Failed to verify access".to_string(),
+                    });
+                    return;
+                }
+            };
+
+            let Some(org_id) = org_id else {
+                let _ = conn.send(ServerEvent::Error {
+                    message: "No organization to subscribe to".to_string(),
+                });
+                return;
+            };
+
+            ws_state
+                .org_events
+                .subscribe(Arc::clone(&conn), org_id, app_state.pool.clone())
+                .await;
+            tracing::debug!(
+                session_id = %conn.session_id,
+                org_id = %org_id,
+                "Subscribed to org event feed"
+            );
+        }
+
+        UnsubscribeOrgEvents => {
+            ws_state.org_events.unsubscribe(&conn.session_id).await;
+        }
     }
 }
 
@@ -386,8 +477,10 @@ async fn verify_ticket_access(
           SELECT 1 FROM support_tickets t
           WHERE t.id = $1
             AND (
-              -- User belongs to ticket's organization
-              t.organization_id IN (SELECT org_id FROM users WHERE id = $2)
+              -- User owns the ticket (covers tickets raised without an org)
+              t.user_id = $2
+              -- Or user belongs to ticket's organization
+              OR t.organization_id IN (SELECT org_id FROM users WHERE id = $2)
               -- Or user is platform admin/staff
               OR EXISTS(
                 SELECT 1 FROM users
@@ -574,10 +667,50 @@ async fn get_all_user_presence(pool: &PgPool) -> Result<Vec<UserPresence>, sqlx:
         .collect())
 }
 
-/// Broadcast presence update to all connected clients
+/// Get presence data for platform staff only
+///
+/// Used for customer connections: end users see agent availability on their
+/// tickets but not the presence of other customers.
+async fn get_staff_presence(pool: &PgPool) -> Result<Vec<UserPresence>, sqlx::Error> {
+    #[derive(sqlx::FromRow)]
+    struct PresenceRow {
+        user_id: Uuid,
+        online_status: String,
+        last_activity_at: time::OffsetDateTime,
+    }
+
+    let rows = sqlx::query_as::<_, PresenceRow>(
+        r#"
+        SELECT p.user_id, p.online_status, p.last_activity_at
+        FROM user_presence p
+        JOIN users u ON u.id = p.user_id
+        WHERE p.online_status IN ('online', 'away')
+          AND u.platform_role IN ('admin', 'superadmin', 'staff')
+        ORDER BY p.last_activity_at DESC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| UserPresence {
+            user_id: r.user_id,
+            online_status: r.online_status,
+            last_activity_at: r.last_activity_at.to_string(),
+        })
+        .collect())
+}
+
+/// Broadcast presence update to connected clients
+///
+/// Staff and admin connections receive every update; customer connections
+/// only receive updates about staff, so end users see agent availability
+/// without the presence of unrelated users leaking across organizations.
 async fn broadcast_presence_to_all(
     ws_state: &WebSocketState,
     user_id: Uuid,
+    user_staff_level: StaffLevel,
     status: &str,
     last_activity_at: Option<String>,
 ) {
@@ -587,9 +720,11 @@ async fn broadcast_presence_to_all(
         last_activity_at,
     };
 
-    // Broadcast to all connections
     let connections = ws_state.connections.read().await;
     for conn in connections.values() {
+        if conn.staff_level < StaffLevel::Staff && user_staff_level < StaffLevel::Staff {
+            continue;
+        }
         let _ = conn.send(event.clone());
     }
 }
@@ -1,7 +1,7 @@
 //! WebSocket support for real-time features
 //!
 //! Provides WebSocket infrastructure for support ticket real-time updates including:
-//! - User presence tracking (online/offline/away)
+//! - User presence tracking (online/offline/away); customers only see staff presence
 //! - Ticket viewer tracking (who's viewing which tickets)
 //! - Typing indicators (who's typing in which tickets)
 //! - Real-time message delivery
@@ -14,9 +14,11 @@
 //! - **Handler**: Axum WebSocket route handler
 //! - **Events**: Type-safe event definitions for client/server communication
 
+pub mod analytics;
 pub mod connection;
 pub mod events;
 pub mod handler;
+pub mod org_events;
 pub mod room;
 pub mod state;
 
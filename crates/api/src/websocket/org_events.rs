@@ -0,0 +1,365 @@
+//! Org-scoped realtime event feed
+//!
+//! Broadcasts MCP health status changes, usage alert threshold crossings,
+//! and billing events to subscribed connections of the same org, so
+//! dashboards update live instead of polling `/mcps` and `/usage`.
+//! Modeled on the analytics feed: a single background task polls while at
+//! least one subscriber is connected. The sources are tables the API and
+//! worker already write (`mcp_instances`, `usage_alerts`,
+//! `billing_events`), so events raised in either process reach connected
+//! dashboards; each event is delivered only to its org's subscribers.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::Value;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::connection::Connection;
+use super::events::ServerEvent;
+
+/// How often the feed polls its source tables while subscribed
+const BROADCAST_INTERVAL: Duration = Duration::from_secs(5);
+
+/// One subscribed connection and the org whose events it receives
+struct Subscriber {
+    org_id: Uuid,
+    conn: Arc<Connection>,
+}
+
+/// Org event pub/sub for dashboard connections
+pub struct OrgEventFeed {
+    /// Subscribed connections indexed by session_id
+    subscribers: Arc<RwLock<HashMap<Uuid, Subscriber>>>,
+
+    /// Whether the polling task is currently running
+    running: Arc<AtomicBool>,
+}
+
+impl OrgEventFeed {
+    pub fn new() -> Self {
+        Self {
+            subscribers: Arc::new(RwLock::new(HashMap::new())),
+            running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Subscribe a connection to its org's event feed
+    ///
+    /// Org resolution (and therefore scoping) happens in the event handler
+    /// before this is called. The first subscriber starts the polling task.
+    pub async fn subscribe(&self, conn: Arc<Connection>, org_id: Uuid, pool: PgPool) {
+        {
+            let mut subs = self.subscribers.write().await;
+            subs.insert(conn.session_id, Subscriber { org_id, conn });
+        }
+
+        if !self.running.swap(true, Ordering::SeqCst) {
+            let subscribers = Arc::clone(&self.subscribers);
+            let running = Arc::clone(&self.running);
+            tokio::spawn(async move {
+                poll_loop(pool, subscribers, running).await;
+            });
+        }
+    }
+
+    /// Unsubscribe a connection from the org event feed
+    pub async fn unsubscribe(&self, session_id: &Uuid) {
+        let mut subs = self.subscribers.write().await;
+        subs.remove(session_id);
+    }
+
+    /// Number of current subscribers
+    pub async fn subscriber_count(&self) -> usize {
+        let subs = self.subscribers.read().await;
+        subs.len()
+    }
+}
+
+impl Default for OrgEventFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Poll the source tables and deliver org-scoped events until all
+/// subscribers are gone
+async fn poll_loop(
+    pool: PgPool,
+    subscribers: Arc<RwLock<HashMap<Uuid, Subscriber>>>,
+    running: Arc<AtomicBool>,
+) {
+    tracing::debug!("Org event feed polling started");
+    let mut interval = tokio::time::interval(BROADCAST_INTERVAL);
+
+    // The watermark uses the database clock so application clock skew
+    // can't skip or replay rows; only events after subscription flow
+    let mut watermark: OffsetDateTime =
+        match sqlx::query_scalar("SELECT NOW()").fetch_one(&pool).await {
+            Ok(now) => now,
+            Err(e) => {
+                tracing::error!(error = ?e, "Org event feed failed to initialize watermark");
+                running.store(false, Ordering::SeqCst);
+                return;
+            }
+        };
+
+    // Last observed health per MCP; populated on the first tick so a new
+    // feed doesn't replay every instance's current status as a change
+    let mut health_snapshot: HashMap<Uuid, String> = HashMap::new();
+    let mut first_tick = true;
+
+    loop {
+        interval.tick().await;
+
+        let org_ids: Vec<Uuid> = {
+            let subs = subscribers.read().await;
+            if subs.is_empty() {
+                break;
+            }
+            let mut orgs: Vec<Uuid> = subs.values().map(|s| s.org_id).collect();
+            orgs.sort();
+            orgs.dedup();
+            orgs
+        };
+
+        let mut events: Vec<(Uuid, ServerEvent)> = Vec::new();
+
+        match poll_health_changes(&pool, &org_ids, &mut health_snapshot, first_tick).await {
+            Ok(mut changes) => events.append(&mut changes),
+            Err(e) => tracing::error!(error = ?e, "Org event feed failed to poll MCP health"),
+        }
+        first_tick = false;
+
+        match poll_usage_crossings(&pool, &org_ids, watermark).await {
+            Ok((mut crossings, advanced)) => {
+                events.append(&mut crossings);
+                watermark = watermark.max(advanced);
+            }
+            Err(e) => tracing::error!(error = ?e, "Org event feed failed to poll usage alerts"),
+        }
+
+        match poll_billing_events(&pool, &org_ids, watermark).await {
+            Ok((mut billing, advanced)) => {
+                events.append(&mut billing);
+                watermark = watermark.max(advanced);
+            }
+            Err(e) => tracing::error!(error = ?e, "Org event feed failed to poll billing events"),
+        }
+
+        if events.is_empty() {
+            continue;
+        }
+
+        let mut closed = Vec::new();
+        {
+            let subs = subscribers.read().await;
+            for (org_id, event) in &events {
+                for (session_id, sub) in subs.iter() {
+                    if sub.org_id != *org_id {
+                        continue;
+                    }
+                    if sub.conn.send(event.clone()).is_err() {
+                        closed.push(*session_id);
+                    }
+                }
+            }
+        }
+        if !closed.is_empty() {
+            let mut subs = subscribers.write().await;
+            for session_id in closed {
+                subs.remove(&session_id);
+            }
+        }
+    }
+
+    running.store(false, Ordering::SeqCst);
+    tracing::debug!("Org event feed polling stopped (no subscribers)");
+}
+
+/// Diff current MCP health against the previous tick's snapshot
+///
+/// The first tick only establishes the baseline; transitions are emitted
+/// from the second tick on. Instances that disappear (deleted or paused)
+/// simply drop out of the snapshot without an event.
+async fn poll_health_changes(
+    pool: &PgPool,
+    org_ids: &[Uuid],
+    snapshot: &mut HashMap<Uuid, String>,
+    first_tick: bool,
+) -> Result<Vec<(Uuid, ServerEvent)>, sqlx::Error> {
+    #[derive(sqlx::FromRow)]
+    struct HealthRow {
+        id: Uuid,
+        org_id: Uuid,
+        name: String,
+        health_status: String,
+    }
+
+    let rows: Vec<HealthRow> = sqlx::query_as(
+        r#"
+        SELECT id, org_id, name, COALESCE(health_status, 'unknown') as health_status
+        FROM mcp_instances
+        WHERE org_id = ANY($1) AND status = 'active'
+        "#,
+    )
+    .bind(org_ids)
+    .fetch_all(pool)
+    .await?;
+
+    let mut events = Vec::new();
+    let mut current = HashMap::with_capacity(rows.len());
+    for row in rows {
+        if let Some(previous) = snapshot.get(&row.id) {
+            if !first_tick && previous != &row.health_status {
+                events.push((
+                    row.org_id,
+                    ServerEvent::OrgEvent {
+                        event: "mcp.status_changed".to_string(),
+                        data: serde_json::json!({
+                            "mcp_id": row.id,
+                            "mcp_name": row.name,
+                            "from": previous,
+                            "to": row.health_status,
+                        }),
+                    },
+                ));
+            }
+        }
+        current.insert(row.id, row.health_status);
+    }
+    *snapshot = current;
+
+    Ok(events)
+}
+
+/// Pick up usage alerts the worker has fired since the watermark
+async fn poll_usage_crossings(
+    pool: &PgPool,
+    org_ids: &[Uuid],
+    watermark: OffsetDateTime,
+) -> Result<(Vec<(Uuid, ServerEvent)>, OffsetDateTime), sqlx::Error> {
+    #[derive(sqlx::FromRow)]
+    struct CrossingRow {
+        id: Uuid,
+        org_id: Uuid,
+        name: String,
+        threshold_percent: i32,
+        last_triggered_at: OffsetDateTime,
+    }
+
+    let rows: Vec<CrossingRow> = sqlx::query_as(
+        r#"
+        SELECT id, org_id, name, threshold_percent, last_triggered_at
+        FROM usage_alerts
+        WHERE org_id = ANY($1) AND last_triggered_at > $2
+        ORDER BY last_triggered_at
+        "#,
+    )
+    .bind(org_ids)
+    .bind(watermark)
+    .fetch_all(pool)
+    .await?;
+
+    let mut advanced = watermark;
+    let events = rows
+        .into_iter()
+        .map(|row| {
+            advanced = advanced.max(row.last_triggered_at);
+            (
+                row.org_id,
+                ServerEvent::OrgEvent {
+                    event: "usage.threshold_reached".to_string(),
+                    data: serde_json::json!({
+                        "alert_id": row.id,
+                        "alert_name": row.name,
+                        "threshold_percent": row.threshold_percent,
+                    }),
+                },
+            )
+        })
+        .collect();
+
+    Ok((events, advanced))
+}
+
+/// Pick up billing events logged since the watermark
+async fn poll_billing_events(
+    pool: &PgPool,
+    org_ids: &[Uuid],
+    watermark: OffsetDateTime,
+) -> Result<(Vec<(Uuid, ServerEvent)>, OffsetDateTime), sqlx::Error> {
+    #[derive(sqlx::FromRow)]
+    struct BillingRow {
+        org_id: Uuid,
+        event_type: String,
+        event_data: Option<Value>,
+        created_at: OffsetDateTime,
+    }
+
+    let rows: Vec<BillingRow> = sqlx::query_as(
+        r#"
+        SELECT org_id, event_type, event_data, created_at
+        FROM billing_events
+        WHERE org_id = ANY($1) AND created_at > $2
+        ORDER BY created_at
+        "#,
+    )
+    .bind(org_ids)
+    .bind(watermark)
+    .fetch_all(pool)
+    .await?;
+
+    let mut advanced = watermark;
+    let events = rows
+        .into_iter()
+        .map(|row| {
+            advanced = advanced.max(row.created_at);
+            (
+                row.org_id,
+                ServerEvent::OrgEvent {
+                    // Stored types are SCREAMING_SNAKE (e.g. INVOICE_PAID);
+                    // event names follow the webhook convention instead
+                    event: format!("billing.{}", row.event_type.to_lowercase()),
+                    data: row.event_data.unwrap_or_else(|| serde_json::json!({})),
+                },
+            )
+        })
+        .collect();
+
+    Ok((events, advanced))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::connection::StaffLevel;
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn test_subscribe_and_unsubscribe_tracking() {
+        let feed = OrgEventFeed::new();
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let conn = Arc::new(Connection::new(Uuid::new_v4(), StaffLevel::User, tx));
+        let session_id = conn.session_id;
+
+        // Insert directly: subscribe() spawns the polling task, which
+        // needs a live pool
+        feed.subscribers.write().await.insert(
+            session_id,
+            Subscriber {
+                org_id: Uuid::new_v4(),
+                conn,
+            },
+        );
+        assert_eq!(feed.subscriber_count().await, 1);
+
+        feed.unsubscribe(&session_id).await;
+        assert_eq!(feed.subscriber_count().await, 0);
+    }
+}
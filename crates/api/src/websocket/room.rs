@@ -7,9 +7,50 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
-use super::connection::Connection;
+use super::connection::{Connection, StaffLevel};
 use super::events::ServerEvent;
 
+/// Filter an event for a recipient's staff level
+///
+/// Applied per-connection at broadcast time:
+/// - Internal note messages are only delivered to staff and admins
+/// - Assignment changes are only delivered to admins; for other
+///   recipients the event is stripped to its status/priority fields,
+///   or dropped entirely if it carried nothing else
+/// - All other events pass through unchanged
+///
+/// Returns `None` when the recipient should not receive the event.
+fn filter_event_for(event: &ServerEvent, staff_level: StaffLevel) -> Option<ServerEvent> {
+    match event {
+        ServerEvent::NewMessage { message, .. } if message.is_internal => {
+            if staff_level >= StaffLevel::Staff {
+                Some(event.clone())
+            } else {
+                None
+            }
+        }
+        ServerEvent::TicketUpdated {
+            ticket_id,
+            status,
+            priority,
+            assigned_to: Some(_),
+        } if staff_level < StaffLevel::Admin => {
+            if status.is_some() || priority.is_some() {
+                Some(ServerEvent::TicketUpdated {
+                    ticket_id: *ticket_id,
+                    status: status.clone(),
+                    priority: priority.clone(),
+                    assigned_to: None,
+                })
+            } else {
+                // Assignment-only update: nothing left to deliver
+                None
+            }
+        }
+        _ => Some(event.clone()),
+    }
+}
+
 /// Manages ticket "rooms" for broadcasting events
 pub struct RoomManager {
     /// Map of ticket_id -> list of connections
@@ -67,6 +108,8 @@ impl RoomManager {
 
     /// Broadcast an event to all connections in a ticket room
     ///
+    /// Events are permission-filtered per recipient (see [`filter_event_for`]):
+    /// internal notes only reach staff, assignment details only reach admins.
     /// Silently ignores send errors (closed connections will be cleaned up)
     pub async fn broadcast(&self, ticket_id: &Uuid, event: ServerEvent) {
         let rooms = self.rooms.read().await;
@@ -75,7 +118,10 @@ impl RoomManager {
             let mut failed_count = 0;
 
             for conn in conns {
-                match conn.send(event.clone()) {
+                let Some(filtered) = filter_event_for(&event, conn.staff_level) else {
+                    continue;
+                };
+                match conn.send(filtered) {
                     Ok(()) => success_count += 1,
                     Err(_) => {
                         failed_count += 1;
@@ -158,7 +204,7 @@ mod tests {
         let ticket_id = Uuid::new_v4();
 
         let (tx, _rx) = mpsc::unbounded_channel();
-        let conn = Arc::new(Connection::new(Uuid::new_v4(), tx));
+        let conn = Arc::new(Connection::new(Uuid::new_v4(), StaffLevel::User, tx));
 
         // Initially room doesn't exist
         assert_eq!(room_manager.get_room_size(&ticket_id).await, 0);
@@ -180,8 +226,8 @@ mod tests {
         let (tx1, mut rx1) = mpsc::unbounded_channel();
         let (tx2, mut rx2) = mpsc::unbounded_channel();
 
-        let conn1 = Arc::new(Connection::new(Uuid::new_v4(), tx1));
-        let conn2 = Arc::new(Connection::new(Uuid::new_v4(), tx2));
+        let conn1 = Arc::new(Connection::new(Uuid::new_v4(), StaffLevel::User, tx1));
+        let conn2 = Arc::new(Connection::new(Uuid::new_v4(), StaffLevel::User, tx2));
 
         room_manager.join(ticket_id, conn1).await;
         room_manager.join(ticket_id, conn2).await;
@@ -202,7 +248,7 @@ mod tests {
         let ticket2 = Uuid::new_v4();
 
         let (tx, _rx) = mpsc::unbounded_channel();
-        let conn = Arc::new(Connection::new(Uuid::new_v4(), tx));
+        let conn = Arc::new(Connection::new(Uuid::new_v4(), StaffLevel::User, tx));
 
         room_manager.join(ticket1, Arc::clone(&conn)).await;
         room_manager.join(ticket2, Arc::clone(&conn)).await;
@@ -214,4 +260,141 @@ mod tests {
 
         assert_eq!(room_manager.get_room_count().await, 0);
     }
+
+    fn message_event(ticket_id: Uuid, is_internal: bool) -> ServerEvent {
+        ServerEvent::NewMessage {
+            ticket_id,
+            message: super::super::events::TicketMessageEvent {
+                id: Uuid::new_v4(),
+                ticket_id,
+                sender_id: Some(Uuid::new_v4()),
+                sender_name: None,
+                is_admin_reply: true,
+                is_internal,
+                content: "test".to_string(),
+                created_at: time::OffsetDateTime::now_utc(),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_internal_note_only_reaches_staff() {
+        let room_manager = RoomManager::new();
+        let ticket_id = Uuid::new_v4();
+
+        let (user_tx, mut user_rx) = mpsc::unbounded_channel();
+        let (staff_tx, mut staff_rx) = mpsc::unbounded_channel();
+        let (admin_tx, mut admin_rx) = mpsc::unbounded_channel();
+
+        room_manager
+            .join(
+                ticket_id,
+                Arc::new(Connection::new(Uuid::new_v4(), StaffLevel::User, user_tx)),
+            )
+            .await;
+        room_manager
+            .join(
+                ticket_id,
+                Arc::new(Connection::new(Uuid::new_v4(), StaffLevel::Staff, staff_tx)),
+            )
+            .await;
+        room_manager
+            .join(
+                ticket_id,
+                Arc::new(Connection::new(Uuid::new_v4(), StaffLevel::Admin, admin_tx)),
+            )
+            .await;
+
+        room_manager
+            .broadcast(&ticket_id, message_event(ticket_id, true))
+            .await;
+
+        // Regular user must not see the internal note
+        assert!(user_rx.try_recv().is_err());
+        assert!(staff_rx.try_recv().is_ok());
+        assert!(admin_rx.try_recv().is_ok());
+
+        // Non-internal messages reach everyone
+        room_manager
+            .broadcast(&ticket_id, message_event(ticket_id, false))
+            .await;
+        assert!(user_rx.try_recv().is_ok());
+        assert!(staff_rx.try_recv().is_ok());
+        assert!(admin_rx.try_recv().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_assignment_events_only_reach_admins() {
+        let room_manager = RoomManager::new();
+        let ticket_id = Uuid::new_v4();
+        let assignee = Uuid::new_v4();
+
+        let (staff_tx, mut staff_rx) = mpsc::unbounded_channel();
+        let (admin_tx, mut admin_rx) = mpsc::unbounded_channel();
+
+        room_manager
+            .join(
+                ticket_id,
+                Arc::new(Connection::new(Uuid::new_v4(), StaffLevel::Staff, staff_tx)),
+            )
+            .await;
+        room_manager
+            .join(
+                ticket_id,
+                Arc::new(Connection::new(Uuid::new_v4(), StaffLevel::Admin, admin_tx)),
+            )
+            .await;
+
+        // Assignment-only update: dropped for non-admins
+        room_manager
+            .broadcast(
+                &ticket_id,
+                ServerEvent::TicketUpdated {
+                    ticket_id,
+                    status: None,
+                    priority: None,
+                    assigned_to: Some(assignee),
+                },
+            )
+            .await;
+
+        assert!(staff_rx.try_recv().is_err());
+        match admin_rx.try_recv() {
+            Ok(ServerEvent::TicketUpdated { assigned_to, .. }) => {
+                assert_eq!(assigned_to, Some(assignee));
+            }
+            other => panic!("expected TicketUpdated, got {:?}", other),
+        }
+
+        // Mixed update: non-admins get a copy stripped of the assignment
+        room_manager
+            .broadcast(
+                &ticket_id,
+                ServerEvent::TicketUpdated {
+                    ticket_id,
+                    status: Some("in_progress".to_string()),
+                    priority: None,
+                    assigned_to: Some(assignee),
+                },
+            )
+            .await;
+
+        match staff_rx.try_recv() {
+            Ok(ServerEvent::TicketUpdated {
+                status,
+                assigned_to,
+                ..
+            }) => {
+                assert_eq!(status.as_deref(), Some("in_progress"));
+                assert_eq!(assigned_to, None);
+            }
+            other => panic!("expected stripped TicketUpdated, got {:?}", other),
+        }
+        match admin_rx.try_recv() {
+            Ok(ServerEvent::TicketUpdated { assigned_to, .. }) => {
+                assert_eq!(assigned_to, Some(assignee));
+            }
+            other => panic!("expected full TicketUpdated, got {:?}", other),
+        }
+    }
 }
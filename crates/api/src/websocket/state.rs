@@ -7,7 +7,9 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
+use super::analytics::AnalyticsFeed;
 use super::connection::Connection;
+use super::org_events::OrgEventFeed;
 use super::room::RoomManager;
 
 /// Global WebSocket state shared across all connections
@@ -18,6 +20,12 @@ pub struct WebSocketState {
 
     /// Room manager for ticket subscriptions
     pub rooms: Arc<RoomManager>,
+
+    /// Realtime analytics feed for admin dashboard subscribers
+    pub analytics: Arc<AnalyticsFeed>,
+
+    /// Org-scoped event feed for customer dashboard subscribers
+    pub org_events: Arc<OrgEventFeed>,
 }
 
 impl WebSocketState {
@@ -26,6 +34,8 @@ impl WebSocketState {
         Self {
             connections: Arc::new(RwLock::new(HashMap::new())),
             rooms: Arc::new(RoomManager::new()),
+            analytics: Arc::new(AnalyticsFeed::new()),
+            org_events: Arc::new(OrgEventFeed::new()),
         }
     }
 
@@ -49,8 +59,10 @@ impl WebSocketState {
     pub async fn remove_connection(&self, session_id: &Uuid) {
         let mut connections = self.connections.write().await;
         if let Some(conn) = connections.remove(session_id) {
-            // Also remove from all rooms
+            // Also remove from all rooms and the analytics/org event feeds
             self.rooms.remove_connection(session_id).await;
+            self.analytics.unsubscribe(session_id).await;
+            self.org_events.unsubscribe(session_id).await;
 
             tracing::info!(
                 session_id = %session_id,
@@ -113,6 +125,7 @@ pub struct WebSocketStats {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::websocket::connection::StaffLevel;
     use tokio::sync::mpsc;
 
     #[tokio::test]
@@ -121,7 +134,7 @@ mod tests {
         let (tx, _rx) = mpsc::unbounded_channel();
         let user_id = Uuid::new_v4();
 
-        let conn = Connection::new(user_id, tx);
+        let conn = Connection::new(user_id, StaffLevel::User, tx);
         let session_id = conn.session_id;
 
         // Add connection
@@ -143,8 +156,12 @@ mod tests {
         let (tx1, _rx1) = mpsc::unbounded_channel();
         let (tx2, _rx2) = mpsc::unbounded_channel();
 
-        state.add_connection(Connection::new(user_id, tx1)).await;
-        state.add_connection(Connection::new(user_id, tx2)).await;
+        state
+            .add_connection(Connection::new(user_id, StaffLevel::User, tx1))
+            .await;
+        state
+            .add_connection(Connection::new(user_id, StaffLevel::User, tx2))
+            .await;
 
         let user_conns = state.get_user_connections(&user_id).await;
         assert_eq!(user_conns.len(), 2);
@@ -156,7 +173,7 @@ mod tests {
         let (tx, _rx) = mpsc::unbounded_channel();
 
         state
-            .add_connection(Connection::new(Uuid::new_v4(), tx))
+            .add_connection(Connection::new(Uuid::new_v4(), StaffLevel::User, tx))
             .await;
 
         let stats = state.get_stats().await;
@@ -1,7 +1,10 @@
 //! Stripe customer management
 
 use sqlx::PgPool;
-use stripe::{CreateCustomer, Customer, CustomerId, UpdateCustomer};
+use stripe::{
+    CreateCustomer, Customer, CustomerId, CustomerInvoiceSettings,
+    CustomerInvoiceSettingsCustomFields, UpdateCustomer,
+};
 use uuid::Uuid;
 
 use crate::client::StripeClient;
@@ -115,6 +118,51 @@ impl CustomerService {
         Ok(customer)
     }
 
+    /// Push per-org invoice settings to the Stripe customer
+    ///
+    /// The PO number renders as an invoice custom field and the memo as the
+    /// invoice footer. `None` clears the corresponding Stripe field so
+    /// removed settings disappear from future invoices.
+    pub async fn update_invoice_settings(
+        &self,
+        org_id: Uuid,
+        po_number: Option<&str>,
+        memo: Option<&str>,
+    ) -> BillingResult<Customer> {
+        let customer_id = self.get_customer_id(org_id).await?;
+
+        // An empty list / empty footer clears previously-set values
+        let custom_fields = po_number
+            .map(|po| {
+                vec![CustomerInvoiceSettingsCustomFields {
+                    name: "PO Number".to_string(),
+                    value: po.to_string(),
+                }]
+            })
+            .unwrap_or_default();
+
+        let params = UpdateCustomer {
+            invoice_settings: Some(CustomerInvoiceSettings {
+                custom_fields: Some(custom_fields),
+                footer: Some(memo.unwrap_or_default().to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let customer = Customer::update(self.stripe.inner(), &customer_id, params).await?;
+
+        tracing::info!(
+            org_id = %org_id,
+            customer_id = %customer.id,
+            has_po_number = po_number.is_some(),
+            has_memo = memo.is_some(),
+            "Updated Stripe customer invoice settings"
+        );
+
+        Ok(customer)
+    }
+
     /// Get the Stripe customer ID for an organization
     pub async fn get_customer_id(&self, org_id: Uuid) -> BillingResult<CustomerId> {
         let result: Option<(Option<String>,)> =
@@ -307,6 +307,61 @@ impl BillingEmailService {
         .await
     }
 
+    /// Send invoice-ready notification (sent to billing contacts when an
+    /// invoice is finalized)
+    pub async fn send_invoice_ready(
+        &self,
+        to: &str,
+        org_name: &str,
+        amount_cents: i64,
+        invoice_url: Option<&str>,
+    ) -> BillingResult<bool> {
+        let amount = format!("${:.2}", amount_cents as f64 / 100.0);
+        let invoice_section = invoice_url
+            .map(|url| {
+                format!(
+                    r#"<p>
+        <a href="{}" style="display: inline-block; padding: 12px 24px; background-color: #6366f1; color: white; text-decoration: none; border-radius: 6px; font-weight: bold;">
+            View Invoice
+        </a>
+    </p>"#,
+                    url
+                )
+            })
+            .unwrap_or_default();
+
+        let html = format!(
+            r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"></head>
+<body style="font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; line-height: 1.6; color: #333; max-width: 600px; margin: 0 auto; padding: 20px;">
+    <h2 style="color: #2563eb;">Invoice Ready</h2>
+    <p>Hi there,</p>
+    <p>A new invoice of <strong>{amount}</strong> is ready for <strong>{org_name}</strong>.</p>
+    {invoice_section}
+    <p style="color: #666; font-size: 14px;">
+        You are receiving this because you are listed as a billing contact for {org_name}.
+        If you have any questions, please contact us at <a href="mailto:{support_email}">{support_email}</a>
+    </p>
+    <hr style="border: none; border-top: 1px solid #eee; margin: 20px 0;">
+    <p style="color: #999; font-size: 12px;">{app_name}</p>
+</body>
+</html>"#,
+            amount = amount,
+            org_name = org_name,
+            invoice_section = invoice_section,
+            support_email = self.config.support_email,
+            app_name = self.config.app_name,
+        );
+
+        self.send_email(
+            to,
+            &format!("Invoice Ready - {}", self.config.app_name),
+            &html,
+        )
+        .await
+    }
+
     /// Send dispute alert notification (CRITICAL - chargebacks are serious)
     pub async fn send_dispute_alert(
         &self,
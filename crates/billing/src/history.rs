@@ -264,7 +264,7 @@ impl BillingHistoryService {
         }
 
         // Sort by date descending
-        records.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        records.sort_by_key(|r| std::cmp::Reverse(r.created_at));
 
         Ok(records)
     }
@@ -41,6 +41,7 @@ pub mod overage;
 pub mod portal;
 pub mod rate_limit;
 pub mod refund;
+pub mod revenue;
 pub mod spend_cap;
 pub mod subscriptions;
 pub mod tax;
@@ -101,6 +102,9 @@ pub use rate_limit::{RateLimitConfig, RateLimitResult, RateLimiter};
 // Refund
 pub use refund::{AdminRefund, RefundResult, RefundService, RefundableCharge};
 
+// Revenue
+pub use revenue::{RevenueAnalyticsService, RevenueSnapshot};
+
 // Subscriptions
 pub use subscriptions::{
     AdminTierChangeParams, AdminTierChangeResult, CancelledSubscriptionInfo, Plan,
@@ -157,6 +161,7 @@ pub struct BillingService {
     pub portal: PortalService,
     pub rate_limiter: RateLimiter,
     pub refund: RefundService,
+    pub revenue: RevenueAnalyticsService,
     pub spend_cap: SpendCapService,
     pub subscriptions: SubscriptionService,
     pub usage: UsageMeter,
@@ -185,6 +190,7 @@ impl BillingService {
             portal: PortalService::new(stripe.clone()),
             rate_limiter: RateLimiter::new_in_memory(),
             refund: RefundService::new(stripe.clone(), pool.clone()),
+            revenue: RevenueAnalyticsService::new(pool.clone()),
             spend_cap: SpendCapService::new(pool.clone(), email_service.clone()),
             subscriptions: SubscriptionService::new(stripe.clone(), pool.clone()),
             usage: UsageMeter::new(pool.clone()),
@@ -213,6 +219,7 @@ impl BillingService {
             portal: PortalService::new(stripe.clone()),
             rate_limiter: RateLimiter::new_in_memory(),
             refund: RefundService::new(stripe.clone(), pool.clone()),
+            revenue: RevenueAnalyticsService::new(pool.clone()),
             spend_cap: SpendCapService::new(pool.clone(), email_service.clone()),
             subscriptions: SubscriptionService::new(stripe.clone(), pool.clone()),
             usage: UsageMeter::new(pool.clone()),
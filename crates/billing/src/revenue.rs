@@ -0,0 +1,322 @@
+//! Revenue Analytics
+//!
+//! Computes MRR/ARR by tier, expansion/contraction from tier changes,
+//! overage revenue trends, and cohort LTV estimates from local billing
+//! tables (organizations, tier_change_audit, overage_charges, invoices).
+//!
+//! Snapshots are persisted to `revenue_analytics_snapshots` by a scheduled
+//! worker job and served to platform admins via the admin API. No live
+//! Stripe calls are made here.
+
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use std::collections::BTreeMap;
+use time::Date;
+
+use crate::error::BillingResult;
+
+/// Service for computing and serving revenue analytics snapshots
+pub struct RevenueAnalyticsService {
+    pool: PgPool,
+}
+
+/// A daily revenue snapshot
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct RevenueSnapshot {
+    pub snapshot_date: Date,
+    pub mrr_cents: i64,
+    pub arr_cents: i64,
+    pub mrr_by_tier: serde_json::Value,
+    pub expansion_cents: i64,
+    pub contraction_cents: i64,
+    pub overage_revenue_cents: i64,
+    pub cohort_ltv: serde_json::Value,
+}
+
+#[derive(Debug, FromRow)]
+struct PayingOrgRow {
+    subscription_tier: String,
+    custom_monthly_price_cents: Option<i32>,
+}
+
+#[derive(Debug, FromRow)]
+struct TierChangeRow {
+    from_tier: String,
+    to_tier: String,
+}
+
+#[derive(Debug, FromRow)]
+struct CohortRow {
+    cohort_month: String,
+    org_count: i64,
+    total_revenue_cents: i64,
+}
+
+impl RevenueAnalyticsService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// List price in cents for a tier's monthly subscription
+    ///
+    /// Defaults match the published pricing (Pro $29, Team $99).
+    /// Overridable via `TIER_PRICE_PRO_CENTS` / `TIER_PRICE_TEAM_CENTS` for
+    /// deployments with non-standard pricing. Enterprise has no list price;
+    /// those orgs use `custom_monthly_price_cents`.
+    pub fn tier_list_price_cents(tier: &str) -> Option<i64> {
+        match tier {
+            "pro" => Some(
+                std::env::var("TIER_PRICE_PRO_CENTS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(2_900),
+            ),
+            "team" => Some(
+                std::env::var("TIER_PRICE_TEAM_CENTS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(9_900),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Monthly revenue attributable to an org given its tier and custom price
+    fn org_mrr_cents(tier: &str, custom_price_cents: Option<i32>) -> i64 {
+        custom_price_cents
+            .map(|c| c as i64)
+            .or_else(|| Self::tier_list_price_cents(tier))
+            .unwrap_or(0)
+    }
+
+    /// Compute (or recompute) the revenue snapshot for the current day
+    pub async fn compute_snapshot(&self) -> BillingResult<RevenueSnapshot> {
+        // MRR by tier from orgs with an active/trialing subscription
+        let paying_orgs: Vec<PayingOrgRow> = sqlx::query_as(
+            r#"
+            SELECT o.subscription_tier, o.custom_monthly_price_cents
+            FROM organizations o
+            JOIN subscriptions s ON s.org_id = o.id
+            WHERE s.status IN ('active', 'trialing')
+              AND o.subscription_tier NOT IN ('free', 'starter')
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut mrr_by_tier: BTreeMap<String, i64> = BTreeMap::new();
+        let mut mrr_cents: i64 = 0;
+        for org in &paying_orgs {
+            let org_mrr =
+                Self::org_mrr_cents(&org.subscription_tier, org.custom_monthly_price_cents);
+            mrr_cents += org_mrr;
+            *mrr_by_tier
+                .entry(org.subscription_tier.clone())
+                .or_insert(0) += org_mrr;
+        }
+        let arr_cents = mrr_cents * 12;
+
+        // Expansion/contraction from tier changes in the trailing 30 days
+        let tier_changes: Vec<TierChangeRow> = sqlx::query_as(
+            r#"
+            SELECT from_tier, to_tier
+            FROM tier_change_audit
+            WHERE created_at >= NOW() - INTERVAL '30 days'
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut expansion_cents: i64 = 0;
+        let mut contraction_cents: i64 = 0;
+        for change in &tier_changes {
+            let from = Self::tier_list_price_cents(&change.from_tier).unwrap_or(0);
+            let to = Self::tier_list_price_cents(&change.to_tier).unwrap_or(0);
+            let delta = to - from;
+            if delta > 0 {
+                expansion_cents += delta;
+            } else {
+                contraction_cents += -delta;
+            }
+        }
+
+        // Overage revenue recognized in the trailing 30 days
+        let overage_revenue_cents: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COALESCE(SUM(total_charge_cents), 0)::bigint
+            FROM overage_charges
+            WHERE status = 'paid'
+              AND created_at >= NOW() - INTERVAL '30 days'
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        // Cohort LTV: paid invoice revenue per signup-month cohort
+        let cohorts: Vec<CohortRow> = sqlx::query_as(
+            r#"
+            SELECT
+                to_char(o.created_at, 'YYYY-MM') as cohort_month,
+                COUNT(DISTINCT o.id)::bigint as org_count,
+                COALESCE(SUM(i.amount_cents) FILTER (WHERE i.status = 'paid'), 0)::bigint
+                    as total_revenue_cents
+            FROM organizations o
+            LEFT JOIN invoices i ON i.org_id = o.id
+            GROUP BY cohort_month
+            ORDER BY cohort_month
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut cohort_ltv = serde_json::Map::new();
+        for cohort in &cohorts {
+            let ltv_cents = if cohort.org_count > 0 {
+                cohort.total_revenue_cents / cohort.org_count
+            } else {
+                0
+            };
+            cohort_ltv.insert(
+                cohort.cohort_month.clone(),
+                serde_json::json!({
+                    "orgs": cohort.org_count,
+                    "total_revenue_cents": cohort.total_revenue_cents,
+                    "ltv_cents": ltv_cents,
+                }),
+            );
+        }
+
+        let mrr_by_tier_json =
+            serde_json::to_value(&mrr_by_tier).unwrap_or(serde_json::Value::Null);
+        let cohort_ltv_json = serde_json::Value::Object(cohort_ltv);
+
+        // Upsert today's snapshot
+        let snapshot: RevenueSnapshot = sqlx::query_as(
+            r#"
+            INSERT INTO revenue_analytics_snapshots (
+                snapshot_date, mrr_cents, arr_cents, mrr_by_tier,
+                expansion_cents, contraction_cents, overage_revenue_cents,
+                cohort_ltv, computed_at
+            )
+            VALUES (CURRENT_DATE, $1, $2, $3, $4, $5, $6, $7, NOW())
+            ON CONFLICT (snapshot_date) DO UPDATE SET
+                mrr_cents = EXCLUDED.mrr_cents,
+                arr_cents = EXCLUDED.arr_cents,
+                mrr_by_tier = EXCLUDED.mrr_by_tier,
+                expansion_cents = EXCLUDED.expansion_cents,
+                contraction_cents = EXCLUDED.contraction_cents,
+                overage_revenue_cents = EXCLUDED.overage_revenue_cents,
+                cohort_ltv = EXCLUDED.cohort_ltv,
+                computed_at = EXCLUDED.computed_at
+            RETURNING snapshot_date, mrr_cents, arr_cents, mrr_by_tier,
+                      expansion_cents, contraction_cents, overage_revenue_cents,
+                      cohort_ltv
+            "#,
+        )
+        .bind(mrr_cents)
+        .bind(arr_cents)
+        .bind(&mrr_by_tier_json)
+        .bind(expansion_cents)
+        .bind(contraction_cents)
+        .bind(overage_revenue_cents)
+        .bind(&cohort_ltv_json)
+        .fetch_one(&self.pool)
+        .await?;
+
+        tracing::info!(
+            mrr_cents = mrr_cents,
+            arr_cents = arr_cents,
+            paying_orgs = paying_orgs.len(),
+            "Revenue snapshot computed"
+        );
+
+        Ok(snapshot)
+    }
+
+    /// Get recent snapshots, most recent first
+    pub async fn get_snapshots(&self, days: i64) -> BillingResult<Vec<RevenueSnapshot>> {
+        let snapshots: Vec<RevenueSnapshot> = sqlx::query_as(
+            r#"
+            SELECT snapshot_date, mrr_cents, arr_cents, mrr_by_tier,
+                   expansion_cents, contraction_cents, overage_revenue_cents,
+                   cohort_ltv
+            FROM revenue_analytics_snapshots
+            WHERE snapshot_date >= CURRENT_DATE - $1::int
+            ORDER BY snapshot_date DESC
+            "#,
+        )
+        .bind(days as i32)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(snapshots)
+    }
+
+    /// Get the latest snapshot, if any has been computed
+    pub async fn get_latest_snapshot(&self) -> BillingResult<Option<RevenueSnapshot>> {
+        let snapshot: Option<RevenueSnapshot> = sqlx::query_as(
+            r#"
+            SELECT snapshot_date, mrr_cents, arr_cents, mrr_by_tier,
+                   expansion_cents, contraction_cents, overage_revenue_cents,
+                   cohort_ltv
+            FROM revenue_analytics_snapshots
+            ORDER BY snapshot_date DESC
+            LIMIT 1
+            "#,
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tier_list_prices() {
+        if std::env::var("TIER_PRICE_PRO_CENTS").is_err() {
+            assert_eq!(
+                RevenueAnalyticsService::tier_list_price_cents("pro"),
+                Some(2_900)
+            );
+        }
+        if std::env::var("TIER_PRICE_TEAM_CENTS").is_err() {
+            assert_eq!(
+                RevenueAnalyticsService::tier_list_price_cents("team"),
+                Some(9_900)
+            );
+        }
+        assert_eq!(RevenueAnalyticsService::tier_list_price_cents("free"), None);
+        assert_eq!(
+            RevenueAnalyticsService::tier_list_price_cents("enterprise"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_org_mrr_custom_price_wins() {
+        assert_eq!(
+            RevenueAnalyticsService::org_mrr_cents("enterprise", Some(250_000)),
+            250_000
+        );
+        assert_eq!(
+            RevenueAnalyticsService::org_mrr_cents("pro", Some(1_900)),
+            1_900
+        );
+    }
+
+    #[test]
+    fn test_org_mrr_falls_back_to_list_price() {
+        if std::env::var("TIER_PRICE_PRO_CENTS").is_err() {
+            assert_eq!(RevenueAnalyticsService::org_mrr_cents("pro", None), 2_900);
+        }
+        // Enterprise without a custom price contributes nothing
+        assert_eq!(
+            RevenueAnalyticsService::org_mrr_cents("enterprise", None),
+            0
+        );
+    }
+}
@@ -4,7 +4,7 @@
 
 use plexmcp_shared::SubscriptionTier;
 use sqlx::PgPool;
-use time::{Duration, OffsetDateTime};
+use time::{Date, Duration, OffsetDateTime};
 use uuid::Uuid;
 
 use crate::error::{BillingError, BillingResult};
@@ -421,6 +421,53 @@ impl UsageMeter {
             .collect())
     }
 
+    /// Get usage breakdown by cost center tag
+    ///
+    /// Groups usage by the MCP instance's cost center, falling back to the
+    /// API key's cost center when the MCP is untagged. Records with neither
+    /// tag land in the `untagged` bucket so totals always reconcile.
+    pub async fn get_usage_by_tag(
+        &self,
+        org_id: Uuid,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+    ) -> BillingResult<Vec<TagUsageBreakdown>> {
+        let results: Vec<(String, i64, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT
+                COALESCE(mi.cost_center, ak.cost_center, 'untagged') as cost_center,
+                COALESCE(SUM(ur.request_count), 0)::BIGINT as request_count,
+                COALESCE(SUM(ur.token_count), 0)::BIGINT as token_count,
+                COALESCE(SUM(ur.error_count), 0)::BIGINT as error_count
+            FROM usage_records ur
+            LEFT JOIN mcp_instances mi ON ur.mcp_instance_id = mi.id
+            LEFT JOIN api_keys ak ON ur.api_key_id = ak.id
+            WHERE ur.org_id = $1
+              AND ur.period_start >= $2
+              AND ur.period_start < $3
+            GROUP BY 1
+            ORDER BY request_count DESC
+            "#,
+        )
+        .bind(org_id)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results
+            .into_iter()
+            .map(
+                |(cost_center, requests, tokens, errors)| TagUsageBreakdown {
+                    cost_center,
+                    request_count: requests,
+                    token_count: tokens,
+                    error_count: errors,
+                },
+            )
+            .collect())
+    }
+
     /// Get hourly usage for charts
     pub async fn get_hourly_usage(
         &self,
@@ -459,6 +506,65 @@ impl UsageMeter {
             .collect())
     }
 
+    /// Get per-day usage for heat calendars, bucketed in the given timezone
+    ///
+    /// Days are derived from the hourly rollups by converting each
+    /// `period_hour` into the caller's timezone before truncating, so a day
+    /// means the org's local day rather than the UTC one. Covers the current
+    /// month plus `months - 1` full months before it; days without traffic
+    /// are omitted.
+    pub async fn get_daily_usage(
+        &self,
+        org_id: Uuid,
+        timezone: &str,
+        months: i32,
+    ) -> BillingResult<Vec<DailyUsage>> {
+        // Validate against the server's tz database so a bad name surfaces
+        // as invalid input instead of a query error
+        let tz_known: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM pg_timezone_names WHERE name = $1)")
+                .bind(timezone)
+                .fetch_one(&self.pool)
+                .await?;
+        if !tz_known {
+            return Err(BillingError::InvalidInput(format!(
+                "Unknown timezone: {}",
+                timezone
+            )));
+        }
+
+        let results: Vec<(Date, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT
+                (period_hour AT TIME ZONE $2)::date AS day,
+                SUM(total_requests)::BIGINT AS requests,
+                SUM(total_errors)::BIGINT AS errors
+            FROM usage_aggregates
+            WHERE org_id = $1
+              -- coarse index-friendly bound, refined by the local-day check
+              AND period_hour >= NOW() - make_interval(months => $3, days => 2)
+              AND (period_hour AT TIME ZONE $2)::date >=
+                  (date_trunc('month', NOW() AT TIME ZONE $2) - make_interval(months => $3 - 1))::date
+            GROUP BY 1
+            ORDER BY 1
+            "#,
+        )
+        .bind(org_id)
+        .bind(timezone)
+        .bind(months)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results
+            .into_iter()
+            .map(|(day, requests, errors)| DailyUsage {
+                day,
+                requests,
+                errors,
+            })
+            .collect())
+    }
+
     /// Aggregate usage records into hourly rollups (run periodically)
     pub async fn aggregate_hourly(&self, org_id: Uuid, hour: OffsetDateTime) -> BillingResult<()> {
         let hour_time = time::Time::from_hms(hour.hour(), 0, 0)
@@ -571,6 +677,23 @@ pub struct McpUsageBreakdown {
     pub avg_latency_ms: Option<i32>,
 }
 
+/// Usage breakdown by cost center tag
+#[derive(Debug, Clone)]
+pub struct TagUsageBreakdown {
+    pub cost_center: String,
+    pub request_count: i64,
+    pub token_count: i64,
+    pub error_count: i64,
+}
+
+/// Per-day usage data point (local day in the requested timezone)
+#[derive(Debug, Clone)]
+pub struct DailyUsage {
+    pub day: Date,
+    pub requests: i64,
+    pub errors: i64,
+}
+
 /// Hourly usage data point
 #[derive(Debug, Clone)]
 pub struct HourlyUsage {
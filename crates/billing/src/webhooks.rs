@@ -642,6 +642,19 @@ impl WebhookHandler {
             tracing::warn!(error = %e, "Failed to log invoice paid event");
         }
 
+        // Fan out to org-registered outbound webhooks
+        plexmcp_shared::org_webhooks::emit(
+            &self.pool,
+            org_id,
+            "invoice.paid",
+            serde_json::json!({
+                "invoice_id": invoice_id,
+                "amount_paid_cents": invoice.amount_paid,
+                "total_cents": invoice.total,
+            }),
+        )
+        .await;
+
         // Check for instant charge payment
         let instant_charge_service =
             InstantChargeService::new(self.stripe.clone(), self.pool.clone(), self.email.clone());
@@ -979,9 +992,37 @@ impl WebhookHandler {
         // Store invoice record
         self.store_invoice(org_id, &invoice, "open").await?;
 
+        // Notify billing contacts that the invoice is ready (best-effort)
+        let contacts = self.get_billing_contact_emails(org_id).await?;
+        if !contacts.is_empty() {
+            let org_name: Option<(String,)> =
+                sqlx::query_as("SELECT name FROM organizations WHERE id = $1")
+                    .bind(org_id)
+                    .fetch_optional(&self.pool)
+                    .await?;
+            let org_name = org_name.map(|(n,)| n).unwrap_or_default();
+            let amount_due = invoice.amount_due.unwrap_or(0);
+            let invoice_url = invoice.hosted_invoice_url.as_deref();
+
+            for email in &contacts {
+                if let Err(e) = self
+                    .email
+                    .send_invoice_ready(email, &org_name, amount_due, invoice_url)
+                    .await
+                {
+                    tracing::error!(
+                        org_id = %org_id,
+                        error = %e,
+                        "Failed to send invoice-ready notification to billing contact"
+                    );
+                }
+            }
+        }
+
         tracing::info!(
             org_id = %org_id,
             invoice_id = %invoice.id,
+            billing_contacts_notified = contacts.len(),
             "Invoice finalized"
         );
 
@@ -1536,6 +1577,23 @@ impl WebhookHandler {
         Ok(result)
     }
 
+    /// Get the organization's billing contact emails (primary first)
+    async fn get_billing_contact_emails(&self, org_id: Uuid) -> BillingResult<Vec<String>> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            r#"
+            SELECT email
+            FROM billing_contacts
+            WHERE org_id = $1
+            ORDER BY is_primary DESC, created_at ASC
+            "#,
+        )
+        .bind(org_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|(e,)| e).collect())
+    }
+
     /// Store invoice record with line items and grace period tracking
     async fn store_invoice(
         &self,
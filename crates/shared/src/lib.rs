@@ -4,10 +4,20 @@
 
 pub mod db;
 pub mod error;
+pub mod org_webhooks;
+pub mod proxy_store;
 pub mod rate_limit;
+pub mod storage;
 pub mod types;
 
 pub use db::*;
 pub use error::*;
-pub use rate_limit::{RateLimitConfig, RateLimitError, RateLimitResult2, RateLimiter};
+pub use proxy_store::{ProxyApiKey, ProxyMcp, ProxyOrg, ProxyStore};
+pub use rate_limit::{
+    RateLimitAlgorithm, RateLimitConfig, RateLimitError, RateLimitOverrides, RateLimitResult2,
+    RateLimiter, RateLimiterStore, RedisRateLimiterStore,
+};
+pub use storage::{
+    LocalStorage, S3Storage, Storage, StorageBackend, StorageConfig, StorageError, UploadedPart,
+};
 pub use types::*;
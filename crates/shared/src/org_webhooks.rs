@@ -0,0 +1,269 @@
+//! Outbound webhooks for org events
+//!
+//! Orgs register endpoints subscribed to platform events via
+//! `/api/v1/webhooks`. [`emit`] fans an event out into one pending
+//! delivery row per subscribed endpoint; the worker drains them with
+//! [`process_pending`], signing each POST with the endpoint secret and
+//! retrying failures with exponential backoff until `max_attempts` is
+//! exhausted. The engine lives in the shared crate so both the API
+//! (member invites, ticket replies, MCP status changes) and the billing
+//! crate (invoice events) can emit without a dependency cycle.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Event types an endpoint can subscribe to
+pub const SUPPORTED_EVENTS: &[&str] = &[
+    "mcp.status_changed",
+    "mcp.tools_changed",
+    "invoice.paid",
+    "member.invited",
+    "ticket.replied",
+];
+
+/// Deliveries claimed per processing pass
+const CLAIM_BATCH_SIZE: i64 = 50;
+
+/// Per-request delivery timeout
+const DELIVERY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Compute the X-PlexMCP-Signature value for a payload
+pub fn sign_payload(secret: &str, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body.as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Fan an event out to every enabled endpoint subscribed to it.
+///
+/// Inserts pending delivery rows; actual HTTP delivery happens in the
+/// worker. Errors are logged rather than surfaced so event emission
+/// never breaks the action that triggered it.
+pub async fn emit(pool: &PgPool, org_id: Uuid, event_type: &str, payload: serde_json::Value) {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO org_webhook_deliveries (endpoint_id, org_id, event_type, payload)
+        SELECT id, org_id, $2, $3
+        FROM org_webhook_endpoints
+        WHERE org_id = $1 AND enabled = TRUE AND $2 = ANY(events)
+        "#,
+    )
+    .bind(org_id)
+    .bind(event_type)
+    .bind(&payload)
+    .execute(pool)
+    .await;
+
+    match result {
+        Ok(r) if r.rows_affected() > 0 => {
+            tracing::debug!(
+                org_id = %org_id,
+                event = event_type,
+                endpoints = r.rows_affected(),
+                "Queued org webhook deliveries"
+            );
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!(org_id = %org_id, event = event_type, error = %e, "Failed to queue org webhook deliveries");
+        }
+    }
+}
+
+/// A delivery claimed for an attempt, joined with its endpoint config
+#[derive(sqlx::FromRow)]
+struct ClaimedDelivery {
+    id: Uuid,
+    event_type: String,
+    payload: serde_json::Value,
+    attempts: i32,
+    max_attempts: i32,
+    created_at: OffsetDateTime,
+    url: String,
+    secret: String,
+}
+
+/// Deliver due pending webhooks, one attempt each. Returns how many
+/// deliveries were attempted.
+///
+/// Claims rows with `FOR UPDATE SKIP LOCKED` so concurrent workers never
+/// double-send. Failed attempts back off exponentially (1, 2, 4, 8...
+/// minutes) until `max_attempts`, after which the delivery is marked
+/// permanently failed.
+pub async fn process_pending(pool: &PgPool) -> u64 {
+    // Claim: bump the attempt counter and push next_attempt_at out by the
+    // backoff up front, so a crashed worker just delays the row instead
+    // of losing it
+    let claimed: Vec<ClaimedDelivery> = match sqlx::query_as(
+        r#"
+        UPDATE org_webhook_deliveries d
+        SET attempts = d.attempts + 1,
+            next_attempt_at = NOW() + make_interval(mins => 1 << LEAST(d.attempts, 6))
+        FROM org_webhook_endpoints e
+        WHERE d.id IN (
+            SELECT id FROM org_webhook_deliveries
+            WHERE status = 'pending' AND next_attempt_at <= NOW()
+            ORDER BY next_attempt_at
+            LIMIT $1
+            FOR UPDATE SKIP LOCKED
+        )
+          AND e.id = d.endpoint_id
+        RETURNING d.id, d.event_type, d.payload, d.attempts, d.max_attempts,
+                  d.created_at, e.url, e.secret
+        "#,
+    )
+    .bind(CLAIM_BATCH_SIZE)
+    .fetch_all(pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to claim org webhook deliveries");
+            return 0;
+        }
+    };
+
+    if claimed.is_empty() {
+        return 0;
+    }
+
+    let client = match reqwest::Client::builder().timeout(DELIVERY_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to build org webhook client");
+            return 0;
+        }
+    };
+
+    let attempted = claimed.len() as u64;
+    for delivery in claimed {
+        attempt_delivery(pool, &client, delivery).await;
+    }
+    attempted
+}
+
+/// Make one HTTP attempt for a claimed delivery and record the outcome
+async fn attempt_delivery(pool: &PgPool, client: &reqwest::Client, delivery: ClaimedDelivery) {
+    let envelope = serde_json::json!({
+        "id": delivery.id,
+        "event": delivery.event_type,
+        "created_at": delivery
+            .created_at
+            .format(&time::format_description::well_known::Rfc3339)
+            .unwrap_or_default(),
+        "data": delivery.payload,
+    });
+    let body = match serde_json::to_string(&envelope) {
+        Ok(body) => body,
+        Err(e) => {
+            record_failure(pool, &delivery, None, &format!("Serialize failed: {e}")).await;
+            return;
+        }
+    };
+    let signature = sign_payload(&delivery.secret, &body);
+
+    let response = client
+        .post(&delivery.url)
+        .header("content-type", "application/json")
+        .header("x-plexmcp-signature", signature)
+        .header("x-plexmcp-event", &delivery.event_type)
+        .header("x-plexmcp-delivery", delivery.id.to_string())
+        .body(body)
+        .send()
+        .await;
+
+    match response {
+        Ok(resp) if resp.status().is_success() => {
+            let status = resp.status().as_u16() as i32;
+            if let Err(e) = sqlx::query(
+                r#"
+                UPDATE org_webhook_deliveries
+                SET status = 'delivered', response_status = $2, last_error = NULL,
+                    delivered_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(delivery.id)
+            .bind(status)
+            .execute(pool)
+            .await
+            {
+                tracing::error!(delivery_id = %delivery.id, error = %e, "Failed to record webhook delivery success");
+            }
+        }
+        Ok(resp) => {
+            let status = resp.status().as_u16() as i32;
+            record_failure(
+                pool,
+                &delivery,
+                Some(status),
+                &format!("Endpoint returned HTTP {status}"),
+            )
+            .await;
+        }
+        Err(e) => {
+            record_failure(pool, &delivery, None, &format!("Request failed: {e}")).await;
+        }
+    }
+}
+
+/// Record a failed attempt; the delivery stays pending (retried at
+/// next_attempt_at) until its attempts are exhausted
+async fn record_failure(
+    pool: &PgPool,
+    delivery: &ClaimedDelivery,
+    response_status: Option<i32>,
+    reason: &str,
+) {
+    let exhausted = delivery.attempts >= delivery.max_attempts;
+    if let Err(e) = sqlx::query(
+        r#"
+        UPDATE org_webhook_deliveries
+        SET status = CASE WHEN $4 THEN 'failed' ELSE status END,
+            response_status = $2, last_error = $3
+        WHERE id = $1
+        "#,
+    )
+    .bind(delivery.id)
+    .bind(response_status)
+    .bind(reason)
+    .bind(exhausted)
+    .execute(pool)
+    .await
+    {
+        tracing::error!(delivery_id = %delivery.id, error = %e, "Failed to record webhook delivery failure");
+        return;
+    }
+
+    if exhausted {
+        tracing::warn!(
+            delivery_id = %delivery.id,
+            event = %delivery.event_type,
+            attempts = delivery.attempts,
+            reason = reason,
+            "Org webhook delivery permanently failed"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_payload_format() {
+        let sig = sign_payload("secret", "{}");
+        assert!(sig.starts_with("sha256="));
+        assert_eq!(sig.len(), "sha256=".len() + 64);
+    }
+
+    #[test]
+    fn test_sign_payload_deterministic() {
+        assert_eq!(sign_payload("s", "body"), sign_payload("s", "body"));
+        assert_ne!(sign_payload("s", "body"), sign_payload("other", "body"));
+    }
+}
@@ -0,0 +1,579 @@
+//! Lightweight database abstraction for the proxy hot path
+//!
+//! Most of the platform is Postgres-only, but small self-hosted installs
+//! shouldn't need to run Postgres just to proxy requests. [`ProxyStore`]
+//! covers the four tables the proxy path touches - organizations,
+//! mcp_instances, api_keys, and usage_records - and can run on either the
+//! main Postgres pool or, with the `sqlite` cargo feature, an embedded
+//! SQLite database.
+//!
+//! Every query is written once in the SQL subset both engines accept and is
+//! exercised against in-memory SQLite by the feature-gated tests at the
+//! bottom of this file, so CI catches any divergence.
+//!
+//! # SQLite limitations
+//!
+//! - Proxy path only: auth, billing, admin, and support features still
+//!   require Postgres. The API's proxy handlers can adopt this store
+//!   incrementally; everything else keeps using the Postgres pool directly.
+//! - No row-level security - file permissions are the security boundary.
+//! - Single writer: usage recording serializes on SQLite's write lock, so
+//!   this is only suitable for low-volume installs.
+//! - Values use SQLite's dynamic typing as produced by sqlx (UUIDs as
+//!   16-byte blobs, timestamps as RFC 3339 text).
+
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[cfg(feature = "sqlite")]
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+
+use crate::db::create_pool;
+
+/// Organization columns the proxy path needs
+#[derive(Debug, Clone, FromRow)]
+pub struct ProxyOrg {
+    pub id: Uuid,
+    pub slug: String,
+    pub subscription_tier: String,
+}
+
+/// MCP instance columns the proxy path needs
+#[derive(Debug, Clone, FromRow)]
+pub struct ProxyMcp {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub name: String,
+    pub mcp_type: String,
+    pub status: String,
+    /// The instance's JSON config serialized as text (JSONB on Postgres,
+    /// TEXT on SQLite)
+    pub config_json: String,
+}
+
+/// API key columns the proxy path needs. Expiry is checked in Rust so the
+/// SQL doesn't depend on engine-specific NOW() semantics.
+#[derive(Debug, Clone, FromRow)]
+pub struct ProxyApiKey {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub rate_limit_rpm: i32,
+    pub expires_at: Option<OffsetDateTime>,
+}
+
+impl ProxyApiKey {
+    /// Whether the key has passed its expiry
+    pub fn is_expired(&self, now: OffsetDateTime) -> bool {
+        self.expires_at.is_some_and(|exp| exp <= now)
+    }
+}
+
+/// Proxy-path store over Postgres or (feature-gated) SQLite
+#[derive(Clone)]
+pub enum ProxyStore {
+    Postgres(sqlx::PgPool),
+    #[cfg(feature = "sqlite")]
+    Sqlite(SqlitePool),
+}
+
+// Shared SQL, written in the subset both engines accept: positional $N
+// binds, no engine-specific functions, casts via CAST().
+const FIND_ORG_BY_SLUG: &str =
+    "SELECT id, slug, subscription_tier FROM organizations WHERE slug = $1";
+
+const FIND_ACTIVE_MCP: &str = "SELECT id, org_id, name, mcp_type, status, \
+     CAST(config AS TEXT) as config_json \
+     FROM mcp_instances WHERE org_id = $1 AND name = $2 AND status = 'active'";
+
+const COUNT_ACTIVE_MCPS: &str =
+    "SELECT COUNT(*) FROM mcp_instances WHERE org_id = $1 AND status = 'active'";
+
+const FIND_API_KEY_BY_HASH: &str =
+    "SELECT id, org_id, rate_limit_rpm, expires_at FROM api_keys WHERE key_hash = $1";
+
+const TOUCH_API_KEY: &str =
+    "UPDATE api_keys SET last_used_at = $2, request_count = request_count + 1 WHERE id = $1";
+
+const INSERT_USAGE_RECORD: &str = "INSERT INTO usage_records \
+     (id, org_id, api_key_id, mcp_instance_id, request_count, token_count, error_count, \
+      period_start, period_end, created_at) \
+     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)";
+
+impl ProxyStore {
+    /// Connect based on the URL scheme: `sqlite:` URLs use the embedded
+    /// backend (requires the `sqlite` feature), everything else goes
+    /// through the standard Postgres pool.
+    pub async fn connect(database_url: &str) -> Result<Self, sqlx::Error> {
+        if database_url.starts_with("sqlite:") {
+            #[cfg(feature = "sqlite")]
+            {
+                return Self::connect_sqlite(database_url).await;
+            }
+            #[cfg(not(feature = "sqlite"))]
+            {
+                return Err(sqlx::Error::Configuration(
+                    "sqlite: URLs require building with the 'sqlite' feature".into(),
+                ));
+            }
+        }
+        Ok(ProxyStore::Postgres(create_pool(database_url).await?))
+    }
+
+    /// Open (creating if needed) a SQLite database and apply the proxy
+    /// schema. In-memory databases (`sqlite::memory:`) work for tests.
+    #[cfg(feature = "sqlite")]
+    pub async fn connect_sqlite(database_url: &str) -> Result<Self, sqlx::Error> {
+        use std::str::FromStr;
+
+        let options = SqliteConnectOptions::from_str(database_url)?.create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            // Single connection: SQLite allows one writer, and an
+            // in-memory database exists per-connection
+            .max_connections(1)
+            .connect_with(options)
+            .await?;
+
+        sqlx::raw_sql(SQLITE_PROXY_SCHEMA).execute(&pool).await?;
+        Ok(ProxyStore::Sqlite(pool))
+    }
+
+    /// Backend name for logging
+    pub fn backend_name(&self) -> &'static str {
+        match self {
+            ProxyStore::Postgres(_) => "postgres",
+            #[cfg(feature = "sqlite")]
+            ProxyStore::Sqlite(_) => "sqlite",
+        }
+    }
+
+    /// Look up an organization by slug
+    pub async fn find_org_by_slug(&self, slug: &str) -> Result<Option<ProxyOrg>, sqlx::Error> {
+        match self {
+            ProxyStore::Postgres(pool) => {
+                sqlx::query_as(FIND_ORG_BY_SLUG)
+                    .bind(slug)
+                    .fetch_optional(pool)
+                    .await
+            }
+            #[cfg(feature = "sqlite")]
+            ProxyStore::Sqlite(pool) => {
+                sqlx::query_as(FIND_ORG_BY_SLUG)
+                    .bind(slug)
+                    .fetch_optional(pool)
+                    .await
+            }
+        }
+    }
+
+    /// Look up an active MCP instance by org and name
+    pub async fn find_active_mcp(
+        &self,
+        org_id: Uuid,
+        name: &str,
+    ) -> Result<Option<ProxyMcp>, sqlx::Error> {
+        match self {
+            ProxyStore::Postgres(pool) => {
+                sqlx::query_as(FIND_ACTIVE_MCP)
+                    .bind(org_id)
+                    .bind(name)
+                    .fetch_optional(pool)
+                    .await
+            }
+            #[cfg(feature = "sqlite")]
+            ProxyStore::Sqlite(pool) => {
+                sqlx::query_as(FIND_ACTIVE_MCP)
+                    .bind(org_id)
+                    .bind(name)
+                    .fetch_optional(pool)
+                    .await
+            }
+        }
+    }
+
+    /// Count active MCP instances for tier limit enforcement
+    pub async fn count_active_mcps(&self, org_id: Uuid) -> Result<i64, sqlx::Error> {
+        match self {
+            ProxyStore::Postgres(pool) => {
+                sqlx::query_scalar(COUNT_ACTIVE_MCPS)
+                    .bind(org_id)
+                    .fetch_one(pool)
+                    .await
+            }
+            #[cfg(feature = "sqlite")]
+            ProxyStore::Sqlite(pool) => {
+                sqlx::query_scalar(COUNT_ACTIVE_MCPS)
+                    .bind(org_id)
+                    .fetch_one(pool)
+                    .await
+            }
+        }
+    }
+
+    /// Look up an API key by its hash. Callers must still check
+    /// [`ProxyApiKey::is_expired`].
+    pub async fn find_api_key_by_hash(
+        &self,
+        key_hash: &str,
+    ) -> Result<Option<ProxyApiKey>, sqlx::Error> {
+        match self {
+            ProxyStore::Postgres(pool) => {
+                sqlx::query_as(FIND_API_KEY_BY_HASH)
+                    .bind(key_hash)
+                    .fetch_optional(pool)
+                    .await
+            }
+            #[cfg(feature = "sqlite")]
+            ProxyStore::Sqlite(pool) => {
+                sqlx::query_as(FIND_API_KEY_BY_HASH)
+                    .bind(key_hash)
+                    .fetch_optional(pool)
+                    .await
+            }
+        }
+    }
+
+    /// Record a use of an API key (last_used_at + request_count)
+    pub async fn touch_api_key(
+        &self,
+        key_id: Uuid,
+        now: OffsetDateTime,
+    ) -> Result<(), sqlx::Error> {
+        match self {
+            ProxyStore::Postgres(pool) => {
+                sqlx::query(TOUCH_API_KEY)
+                    .bind(key_id)
+                    .bind(now)
+                    .execute(pool)
+                    .await?;
+            }
+            #[cfg(feature = "sqlite")]
+            ProxyStore::Sqlite(pool) => {
+                sqlx::query(TOUCH_API_KEY)
+                    .bind(key_id)
+                    .bind(now)
+                    .execute(pool)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Insert a usage record for a billing/analytics period
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_usage(
+        &self,
+        org_id: Uuid,
+        api_key_id: Option<Uuid>,
+        mcp_instance_id: Option<Uuid>,
+        request_count: i32,
+        token_count: i32,
+        error_count: i32,
+        period_start: OffsetDateTime,
+        period_end: OffsetDateTime,
+    ) -> Result<(), sqlx::Error> {
+        let id = Uuid::new_v4();
+        let now = OffsetDateTime::now_utc();
+        match self {
+            ProxyStore::Postgres(pool) => {
+                sqlx::query(INSERT_USAGE_RECORD)
+                    .bind(id)
+                    .bind(org_id)
+                    .bind(api_key_id)
+                    .bind(mcp_instance_id)
+                    .bind(request_count)
+                    .bind(token_count)
+                    .bind(error_count)
+                    .bind(period_start)
+                    .bind(period_end)
+                    .bind(now)
+                    .execute(pool)
+                    .await?;
+            }
+            #[cfg(feature = "sqlite")]
+            ProxyStore::Sqlite(pool) => {
+                sqlx::query(INSERT_USAGE_RECORD)
+                    .bind(id)
+                    .bind(org_id)
+                    .bind(api_key_id)
+                    .bind(mcp_instance_id)
+                    .bind(request_count)
+                    .bind(token_count)
+                    .bind(error_count)
+                    .bind(period_start)
+                    .bind(period_end)
+                    .bind(now)
+                    .execute(pool)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Total requests recorded for an org since `since`
+    pub async fn usage_since(
+        &self,
+        org_id: Uuid,
+        since: OffsetDateTime,
+    ) -> Result<i64, sqlx::Error> {
+        const SQL: &str = "SELECT COALESCE(SUM(request_count), 0) \
+             FROM usage_records WHERE org_id = $1 AND period_start >= $2";
+        match self {
+            ProxyStore::Postgres(pool) => {
+                // Postgres SUM(integer) returns bigint directly
+                sqlx::query_scalar(SQL)
+                    .bind(org_id)
+                    .bind(since)
+                    .fetch_one(pool)
+                    .await
+            }
+            #[cfg(feature = "sqlite")]
+            ProxyStore::Sqlite(pool) => {
+                sqlx::query_scalar(SQL)
+                    .bind(org_id)
+                    .bind(since)
+                    .fetch_one(pool)
+                    .await
+            }
+        }
+    }
+}
+
+/// Proxy-path subset of the Postgres schema, translated for SQLite.
+/// Kept in lockstep with the tables in `migrations/` by hand - the proxy
+/// path only reads a handful of columns, all present here.
+#[cfg(feature = "sqlite")]
+const SQLITE_PROXY_SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS organizations (
+    id BLOB PRIMARY KEY,
+    name TEXT NOT NULL,
+    slug TEXT UNIQUE NOT NULL,
+    subscription_tier TEXT NOT NULL DEFAULT 'free',
+    settings TEXT NOT NULL DEFAULT '{}',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS api_keys (
+    id BLOB PRIMARY KEY,
+    org_id BLOB NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    key_hash TEXT NOT NULL,
+    key_prefix TEXT NOT NULL,
+    rate_limit_rpm INTEGER NOT NULL DEFAULT 60,
+    expires_at TEXT,
+    last_used_at TEXT,
+    request_count INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_api_keys_hash ON api_keys(key_hash);
+
+CREATE TABLE IF NOT EXISTS mcp_instances (
+    id BLOB PRIMARY KEY,
+    org_id BLOB NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    mcp_type TEXT NOT NULL,
+    config TEXT NOT NULL DEFAULT '{}',
+    status TEXT NOT NULL DEFAULT 'active',
+    health_status TEXT NOT NULL DEFAULT 'unknown',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_mcp_instances_org ON mcp_instances(org_id);
+
+CREATE TABLE IF NOT EXISTS usage_records (
+    id BLOB PRIMARY KEY,
+    org_id BLOB NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    api_key_id BLOB,
+    mcp_instance_id BLOB,
+    request_count INTEGER NOT NULL DEFAULT 0,
+    token_count INTEGER NOT NULL DEFAULT 0,
+    error_count INTEGER NOT NULL DEFAULT 0,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_usage_records_org ON usage_records(org_id);
+"#;
+
+// =============================================================================
+// Tests (run with `cargo test -p plexmcp-shared --features sqlite`)
+// =============================================================================
+
+#[cfg(all(test, feature = "sqlite"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    async fn store() -> ProxyStore {
+        ProxyStore::connect("sqlite::memory:").await.unwrap()
+    }
+
+    fn pool(store: &ProxyStore) -> &SqlitePool {
+        match store {
+            ProxyStore::Sqlite(pool) => pool,
+            _ => panic!("expected sqlite store"),
+        }
+    }
+
+    async fn seed_org(store: &ProxyStore, slug: &str) -> Uuid {
+        let id = Uuid::new_v4();
+        let now = OffsetDateTime::now_utc();
+        sqlx::query(
+            "INSERT INTO organizations (id, name, slug, subscription_tier, settings, created_at, updated_at) \
+             VALUES ($1, $2, $3, 'pro', '{}', $4, $5)",
+        )
+        .bind(id)
+        .bind(slug)
+        .bind(slug)
+        .bind(now)
+        .bind(now)
+        .execute(pool(store))
+        .await
+        .unwrap();
+        id
+    }
+
+    #[tokio::test]
+    async fn test_connect_detects_scheme() {
+        let store = store().await;
+        assert_eq!(store.backend_name(), "sqlite");
+    }
+
+    #[tokio::test]
+    async fn test_org_lookup() {
+        let store = store().await;
+        let org_id = seed_org(&store, "acme").await;
+
+        let org = store.find_org_by_slug("acme").await.unwrap().unwrap();
+        assert_eq!(org.id, org_id);
+        assert_eq!(org.subscription_tier, "pro");
+
+        assert!(store.find_org_by_slug("missing").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_mcp_lookup_and_count() {
+        let store = store().await;
+        let org_id = seed_org(&store, "acme").await;
+        let now = OffsetDateTime::now_utc();
+
+        for (name, status) in [("github", "active"), ("slack", "paused")] {
+            sqlx::query(
+                "INSERT INTO mcp_instances (id, org_id, name, mcp_type, config, status, created_at, updated_at) \
+                 VALUES ($1, $2, $3, 'http', '{\"url\":\"http://upstream\"}', $4, $5, $6)",
+            )
+            .bind(Uuid::new_v4())
+            .bind(org_id)
+            .bind(name)
+            .bind(status)
+            .bind(now)
+            .bind(now)
+            .execute(pool(&store))
+            .await
+            .unwrap();
+        }
+
+        let mcp = store
+            .find_active_mcp(org_id, "github")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(mcp.org_id, org_id);
+        assert_eq!(mcp.mcp_type, "http");
+        // Config comes back as JSON text and round-trips through serde
+        let config: serde_json::Value = serde_json::from_str(&mcp.config_json).unwrap();
+        assert_eq!(config["url"], "http://upstream");
+
+        // Paused instances don't resolve
+        assert!(store
+            .find_active_mcp(org_id, "slack")
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(store.count_active_mcps(org_id).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_api_key_lookup_and_touch() {
+        let store = store().await;
+        let org_id = seed_org(&store, "acme").await;
+        let key_id = Uuid::new_v4();
+        let now = OffsetDateTime::now_utc();
+
+        sqlx::query(
+            "INSERT INTO api_keys (id, org_id, name, key_hash, key_prefix, rate_limit_rpm, expires_at, created_at) \
+             VALUES ($1, $2, 'ci', 'hash123', 'pk_test', 120, $3, $4)",
+        )
+        .bind(key_id)
+        .bind(org_id)
+        .bind(now + time::Duration::hours(1))
+        .bind(now)
+        .execute(pool(&store))
+        .await
+        .unwrap();
+
+        let key = store
+            .find_api_key_by_hash("hash123")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(key.id, key_id);
+        assert_eq!(key.rate_limit_rpm, 120);
+        assert!(!key.is_expired(now));
+        assert!(key.is_expired(now + time::Duration::hours(2)));
+
+        store.touch_api_key(key_id, now).await.unwrap();
+        store.touch_api_key(key_id, now).await.unwrap();
+        let count: i64 = sqlx::query_scalar("SELECT request_count FROM api_keys WHERE id = $1")
+            .bind(key_id)
+            .fetch_one(pool(&store))
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_usage_recording() {
+        let store = store().await;
+        let org_id = seed_org(&store, "acme").await;
+        let now = OffsetDateTime::now_utc();
+
+        store
+            .record_usage(
+                org_id,
+                None,
+                None,
+                10,
+                500,
+                1,
+                now - time::Duration::hours(1),
+                now,
+            )
+            .await
+            .unwrap();
+        store
+            .record_usage(
+                org_id,
+                None,
+                None,
+                5,
+                250,
+                0,
+                now,
+                now + time::Duration::hours(1),
+            )
+            .await
+            .unwrap();
+
+        let total = store
+            .usage_since(org_id, now - time::Duration::hours(2))
+            .await
+            .unwrap();
+        assert_eq!(total, 15);
+
+        // Window excludes the older record
+        let recent = store.usage_since(org_id, now).await.unwrap();
+        assert_eq!(recent, 5);
+    }
+}
@@ -1,6 +1,10 @@
-//! Rate limiting service using in-memory storage
+//! Rate limiting service
 //!
-//! Provides real-time rate limiting for API requests.
+//! Provides real-time rate limiting for API requests. Enforcement state
+//! lives behind the [`RateLimiterStore`] trait: the default
+//! [`InMemoryRateLimiter`] is per-instance, while [`RedisRateLimiterStore`]
+//! shares counters through Redis so limits hold globally across
+//! instances, degrading to local enforcement when Redis is unreachable.
 //!
 //! Security rate limits are configurable via environment variables:
 //! - `RATE_LIMIT_AUTH_PER_MINUTE`: Auth attempts per IP (default: 10)
@@ -10,6 +14,7 @@
 //! - `RATE_LIMIT_REGISTRATION_PER_MINUTE`: Account registration per IP (default: 3)
 //! - `RATE_LIMIT_OAUTH_PER_MINUTE`: OAuth attempts per IP (default: 10)
 
+use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, OnceLock};
 use time::OffsetDateTime;
 use uuid::Uuid;
@@ -90,6 +95,27 @@ fn get_oauth_rate_limit() -> u32 {
     })
 }
 
+/// Rate limiting algorithm
+///
+/// All algorithms enforce the same steady-state `requests_per_minute`;
+/// they differ in how strictly requests are spaced within the minute:
+/// - `FixedWindow`: cheapest; counts reset on minute boundaries, so up
+///   to 2x the limit can land across a boundary
+/// - `SlidingLog`: exact; tracks individual request timestamps so any
+///   rolling 60s window never exceeds the limit
+/// - `TokenBucket`: smooths to the steady rate but allows `burst` extra
+///   requests above the per-minute capacity for spiky clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RateLimitAlgorithm {
+    #[default]
+    FixedWindow,
+    SlidingLog,
+    TokenBucket {
+        /// Extra requests allowed above `requests_per_minute` in a burst
+        burst: u32,
+    },
+}
+
 /// Rate limit configuration
 #[derive(Debug, Clone)]
 pub struct RateLimitConfig {
@@ -99,6 +125,8 @@ pub struct RateLimitConfig {
     pub requests_per_hour: Option<u32>,
     /// Monthly request limit
     pub monthly_limit: Option<u64>,
+    /// Algorithm used to enforce the per-minute limit
+    pub algorithm: RateLimitAlgorithm,
 }
 
 impl Default for RateLimitConfig {
@@ -107,6 +135,7 @@ impl Default for RateLimitConfig {
             requests_per_minute: 60,
             requests_per_hour: None,
             monthly_limit: None,
+            algorithm: RateLimitAlgorithm::default(),
         }
     }
 }
@@ -115,6 +144,8 @@ impl Default for RateLimitConfig {
 #[derive(Debug, Clone)]
 pub struct RateLimitResult2 {
     pub allowed: bool,
+    /// The enforced per-minute limit (after overrides), for X-RateLimit-Limit
+    pub limit: u32,
     pub remaining_minute: u32,
     pub remaining_hour: Option<u32>,
     pub remaining_monthly: Option<u64>,
@@ -123,24 +154,51 @@ pub struct RateLimitResult2 {
 }
 
 /// In-memory rate limiter (for development without Redis)
-/// Uses a simple sliding window algorithm
+///
+/// Supports three enforcement algorithms (see [`RateLimitAlgorithm`]),
+/// each with its own keyed state
 pub struct InMemoryRateLimiter {
-    /// Store: key -> (count, window_start)
-    windows: tokio::sync::RwLock<std::collections::HashMap<String, (u32, i64)>>,
+    /// Fixed window store: key -> (count, window_start)
+    windows: tokio::sync::RwLock<HashMap<String, (u32, i64)>>,
+    /// Sliding log store: key -> request timestamps in unix millis
+    logs: tokio::sync::RwLock<HashMap<String, VecDeque<i64>>>,
+    /// Token bucket store: key -> (tokens, last_refill_ms)
+    buckets: tokio::sync::RwLock<HashMap<String, (f64, i64)>>,
+}
+
+fn now_unix_millis() -> i64 {
+    (OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000) as i64
 }
 
 impl InMemoryRateLimiter {
     pub fn new() -> Self {
         Self {
-            windows: tokio::sync::RwLock::new(std::collections::HashMap::new()),
+            windows: tokio::sync::RwLock::new(HashMap::new()),
+            logs: tokio::sync::RwLock::new(HashMap::new()),
+            buckets: tokio::sync::RwLock::new(HashMap::new()),
         }
     }
 
-    /// Check and increment rate limit
+    /// Check and increment rate limit using the configured algorithm
     pub async fn check_rate_limit(
         &self,
         key: &str,
         config: &RateLimitConfig,
+    ) -> RateLimitResult<RateLimitResult2> {
+        match config.algorithm {
+            RateLimitAlgorithm::FixedWindow => self.check_fixed_window(key, config).await,
+            RateLimitAlgorithm::SlidingLog => self.check_sliding_log(key, config).await,
+            RateLimitAlgorithm::TokenBucket { burst } => {
+                self.check_token_bucket(key, config, burst).await
+            }
+        }
+    }
+
+    /// Fixed window: counts reset on minute boundaries
+    async fn check_fixed_window(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
     ) -> RateLimitResult<RateLimitResult2> {
         let now = OffsetDateTime::now_utc().unix_timestamp();
         let window_start = now - (now % 60); // 1-minute window
@@ -177,6 +235,7 @@ impl InMemoryRateLimiter {
 
         Ok(RateLimitResult2 {
             allowed,
+            limit: config.requests_per_minute,
             remaining_minute: remaining,
             remaining_hour: None,
             remaining_monthly: None,
@@ -185,13 +244,113 @@ impl InMemoryRateLimiter {
         })
     }
 
-    /// Clean up old windows (call periodically)
+    /// Sliding log: any rolling 60s window never exceeds the limit
+    async fn check_sliding_log(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+    ) -> RateLimitResult<RateLimitResult2> {
+        let now_ms = now_unix_millis();
+
+        let mut logs = self.logs.write().await;
+        let log = logs.entry(format!("{}:log", key)).or_default();
+
+        // Drop entries that have aged out of the rolling window
+        while log.front().is_some_and(|&ts| ts <= now_ms - 60_000) {
+            log.pop_front();
+        }
+
+        let allowed = (log.len() as u32) < config.requests_per_minute;
+        if allowed {
+            log.push_back(now_ms);
+        }
+
+        let remaining = config.requests_per_minute.saturating_sub(log.len() as u32);
+        // The next slot frees when the oldest tracked request ages out
+        let reset_ms = log.front().map(|&ts| ts + 60_000).unwrap_or(now_ms);
+        let reset_at = OffsetDateTime::from_unix_timestamp(reset_ms.div_euclid(1000))
+            .unwrap_or(OffsetDateTime::now_utc());
+        let retry_after = if !allowed {
+            Some(((reset_ms - now_ms).max(0) as u32).div_ceil(1000).max(1))
+        } else {
+            None
+        };
+
+        Ok(RateLimitResult2 {
+            allowed,
+            limit: config.requests_per_minute,
+            remaining_minute: remaining,
+            remaining_hour: None,
+            remaining_monthly: None,
+            reset_at,
+            retry_after_seconds: retry_after,
+        })
+    }
+
+    /// Token bucket: refills at the steady per-minute rate with `burst`
+    /// extra capacity for spikes
+    async fn check_token_bucket(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+        burst: u32,
+    ) -> RateLimitResult<RateLimitResult2> {
+        let now_ms = now_unix_millis();
+        let capacity = f64::from(config.requests_per_minute) + f64::from(burst);
+        let rate_per_ms = f64::from(config.requests_per_minute) / 60_000.0;
+
+        let mut buckets = self.buckets.write().await;
+        let (tokens, last_refill_ms) = buckets
+            .entry(format!("{}:bucket", key))
+            .or_insert((capacity, now_ms));
+
+        let elapsed_ms = (now_ms - *last_refill_ms).max(0) as f64;
+        *tokens = (*tokens + elapsed_ms * rate_per_ms).min(capacity);
+        *last_refill_ms = now_ms;
+
+        let allowed = *tokens >= 1.0;
+        if allowed {
+            *tokens -= 1.0;
+        }
+
+        let remaining = tokens.floor().max(0.0) as u32;
+        // Conservatively report when the bucket is full again
+        let reset_at = OffsetDateTime::now_utc()
+            + time::Duration::milliseconds(((capacity - *tokens) / rate_per_ms) as i64);
+        let retry_after = if !allowed {
+            let ms_until_token = (1.0 - *tokens) / rate_per_ms;
+            Some(((ms_until_token / 1000.0).ceil() as u32).max(1))
+        } else {
+            None
+        };
+
+        Ok(RateLimitResult2 {
+            allowed,
+            limit: config.requests_per_minute,
+            remaining_minute: remaining,
+            remaining_hour: None,
+            remaining_monthly: None,
+            reset_at,
+            retry_after_seconds: retry_after,
+        })
+    }
+
+    /// Clean up old state (call periodically)
     pub async fn cleanup(&self) {
         let now = OffsetDateTime::now_utc().unix_timestamp();
         let cutoff = now - 3600; // Keep last hour
+        let cutoff_ms = (now - 3600) * 1000;
 
         let mut windows = self.windows.write().await;
         windows.retain(|_, (_, start)| *start > cutoff);
+        drop(windows);
+
+        let mut logs = self.logs.write().await;
+        logs.retain(|_, log| log.back().is_some_and(|&ts| ts > cutoff_ms));
+        drop(logs);
+
+        let mut buckets = self.buckets.write().await;
+        buckets.retain(|_, (_, last_refill_ms)| *last_refill_ms > cutoff_ms);
     }
 }
 
@@ -201,16 +360,420 @@ impl Default for InMemoryRateLimiter {
     }
 }
 
+/// Backing store for rate limit state
+///
+/// [`RateLimiter`] handles key construction and overrides; stores only
+/// count requests and answer. Implementations must tolerate concurrent
+/// checks for the same key.
+#[async_trait::async_trait]
+pub trait RateLimiterStore: Send + Sync {
+    /// Check and (when allowed) record a request against `key`
+    async fn check_rate_limit(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+    ) -> RateLimitResult<RateLimitResult2>;
+
+    /// Drop state that can no longer affect a decision (call periodically)
+    async fn cleanup(&self);
+}
+
+#[async_trait::async_trait]
+impl RateLimiterStore for InMemoryRateLimiter {
+    async fn check_rate_limit(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+    ) -> RateLimitResult<RateLimitResult2> {
+        InMemoryRateLimiter::check_rate_limit(self, key, config).await
+    }
+
+    async fn cleanup(&self) {
+        InMemoryRateLimiter::cleanup(self).await;
+    }
+}
+
+/// Token bucket check as a Lua script so refill + consume is atomic
+/// across instances
+fn token_bucket_script() -> &'static redis::Script {
+    static SCRIPT: OnceLock<redis::Script> = OnceLock::new();
+    SCRIPT.get_or_init(|| {
+        redis::Script::new(
+            r#"
+            local tokens = tonumber(redis.call('HGET', KEYS[1], 'tokens'))
+            local ts = tonumber(redis.call('HGET', KEYS[1], 'ts'))
+            local capacity = tonumber(ARGV[1])
+            local rate_per_ms = tonumber(ARGV[2])
+            local now_ms = tonumber(ARGV[3])
+            if tokens == nil then tokens = capacity end
+            if ts == nil then ts = now_ms end
+            tokens = math.min(capacity, tokens + math.max(now_ms - ts, 0) * rate_per_ms)
+            local allowed = 0
+            if tokens >= 1 then
+                tokens = tokens - 1
+                allowed = 1
+            end
+            redis.call('HSET', KEYS[1], 'tokens', tokens, 'ts', now_ms)
+            redis.call('PEXPIRE', KEYS[1], 120000)
+            return {allowed, tostring(tokens)}
+            "#,
+        )
+    })
+}
+
+/// Redis-backed store: counters are shared, so limits hold globally
+/// across all API instances
+///
+/// Uses the same per-algorithm key scheme as [`InMemoryRateLimiter`].
+/// Every check falls back to a local in-memory store when Redis errors,
+/// so an outage degrades to per-instance limits instead of blocking (or
+/// unthrottling) traffic. Redis state expires via TTLs.
+pub struct RedisRateLimiterStore {
+    client: redis::Client,
+    /// Connection established lazily on first check; failures retry on
+    /// later checks (the fallback serves in the meantime)
+    conn: tokio::sync::OnceCell<redis::aio::ConnectionManager>,
+    /// Local fallback used while Redis is unreachable
+    fallback: InMemoryRateLimiter,
+}
+
+impl RedisRateLimiterStore {
+    /// Create a Redis-backed store; fails only on an invalid URL (the
+    /// connection itself is established on first use)
+    pub fn new(redis_url: &str) -> RateLimitResult<Self> {
+        let client = redis::Client::open(redis_url)
+            .map_err(|e| RateLimitError::Internal(format!("Invalid Redis URL: {}", e)))?;
+        Ok(Self {
+            client,
+            conn: tokio::sync::OnceCell::new(),
+            fallback: InMemoryRateLimiter::new(),
+        })
+    }
+
+    async fn manager(&self) -> Result<redis::aio::ConnectionManager, redis::RedisError> {
+        self.conn
+            .get_or_try_init(|| redis::aio::ConnectionManager::new(self.client.clone()))
+            .await
+            .cloned()
+    }
+
+    async fn check_redis(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+    ) -> Result<RateLimitResult2, redis::RedisError> {
+        match config.algorithm {
+            RateLimitAlgorithm::FixedWindow => self.check_fixed_window(key, config).await,
+            RateLimitAlgorithm::SlidingLog => self.check_sliding_log(key, config).await,
+            RateLimitAlgorithm::TokenBucket { burst } => {
+                self.check_token_bucket(key, config, burst).await
+            }
+        }
+    }
+
+    /// Fixed window: one counter key per minute boundary, INCR + TTL
+    async fn check_fixed_window(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+    ) -> Result<RateLimitResult2, redis::RedisError> {
+        use redis::AsyncCommands;
+
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let window_start = now - (now % 60);
+        let redis_key = format!("{}:fw:{}", key, window_start);
+
+        let mut conn = self.manager().await?;
+        let count: u32 = conn.incr(&redis_key, 1).await?;
+        if count == 1 {
+            // Outlive the window slightly so in-flight checks still see it
+            let _: bool = conn.expire(&redis_key, 120).await?;
+        }
+
+        let allowed = count <= config.requests_per_minute;
+        let reset_at = OffsetDateTime::from_unix_timestamp(window_start + 60)
+            .unwrap_or(OffsetDateTime::now_utc());
+
+        Ok(RateLimitResult2 {
+            allowed,
+            limit: config.requests_per_minute,
+            remaining_minute: config.requests_per_minute.saturating_sub(count),
+            remaining_hour: None,
+            remaining_monthly: None,
+            reset_at,
+            retry_after_seconds: if allowed {
+                None
+            } else {
+                Some((window_start + 60 - now) as u32)
+            },
+        })
+    }
+
+    /// Sliding log: sorted set of request timestamps, pruned on every check
+    async fn check_sliding_log(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+    ) -> Result<RateLimitResult2, redis::RedisError> {
+        use redis::AsyncCommands;
+
+        let now_ms = now_unix_millis();
+        let redis_key = format!("{}:log", key);
+
+        let mut conn = self.manager().await?;
+        let _: i64 = conn.zrembyscore(&redis_key, 0, now_ms - 60_000).await?;
+        let count: u32 = conn.zcard(&redis_key).await?;
+
+        let allowed = count < config.requests_per_minute;
+        if allowed {
+            // Unique member per request so same-millisecond hits all count
+            let member = format!("{}-{}", now_ms, Uuid::new_v4());
+            let _: i64 = conn.zadd(&redis_key, member, now_ms).await?;
+            let _: bool = conn.pexpire(&redis_key, 120_000).await?;
+        }
+
+        let oldest: Vec<(String, i64)> = conn.zrange_withscores(&redis_key, 0, 0).await?;
+        let reset_ms = oldest.first().map(|(_, ts)| ts + 60_000).unwrap_or(now_ms);
+        let reset_at = OffsetDateTime::from_unix_timestamp(reset_ms.div_euclid(1000))
+            .unwrap_or(OffsetDateTime::now_utc());
+
+        Ok(RateLimitResult2 {
+            allowed,
+            limit: config.requests_per_minute,
+            remaining_minute: config
+                .requests_per_minute
+                .saturating_sub(count + u32::from(allowed)),
+            remaining_hour: None,
+            remaining_monthly: None,
+            reset_at,
+            retry_after_seconds: if allowed {
+                None
+            } else {
+                Some(((reset_ms - now_ms).max(0) as u32).div_ceil(1000).max(1))
+            },
+        })
+    }
+
+    /// Token bucket: hash of (tokens, last refill), updated atomically
+    /// by a Lua script
+    async fn check_token_bucket(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+        burst: u32,
+    ) -> Result<RateLimitResult2, redis::RedisError> {
+        let now_ms = now_unix_millis();
+        let capacity = f64::from(config.requests_per_minute) + f64::from(burst);
+        let rate_per_ms = f64::from(config.requests_per_minute) / 60_000.0;
+        let redis_key = format!("{}:bucket", key);
+
+        let mut conn = self.manager().await?;
+        let (allowed_flag, tokens_str): (i64, String) = token_bucket_script()
+            .key(&redis_key)
+            .arg(capacity)
+            .arg(rate_per_ms)
+            .arg(now_ms)
+            .invoke_async(&mut conn)
+            .await?;
+
+        let allowed = allowed_flag == 1;
+        let tokens: f64 = tokens_str.parse().unwrap_or(0.0);
+
+        let reset_at = OffsetDateTime::now_utc()
+            + time::Duration::milliseconds(((capacity - tokens) / rate_per_ms) as i64);
+        let retry_after = if !allowed {
+            let ms_until_token = (1.0 - tokens) / rate_per_ms;
+            Some(((ms_until_token / 1000.0).ceil() as u32).max(1))
+        } else {
+            None
+        };
+
+        Ok(RateLimitResult2 {
+            allowed,
+            limit: config.requests_per_minute,
+            remaining_minute: tokens.floor().max(0.0) as u32,
+            remaining_hour: None,
+            remaining_monthly: None,
+            reset_at,
+            retry_after_seconds: retry_after,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl RateLimiterStore for RedisRateLimiterStore {
+    async fn check_rate_limit(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+    ) -> RateLimitResult<RateLimitResult2> {
+        match self.check_redis(key, config).await {
+            Ok(result) => Ok(result),
+            Err(e) => {
+                tracing::warn!(
+                    error = %e,
+                    "Redis rate limit check failed, falling back to local enforcement"
+                );
+                self.fallback.check_rate_limit(key, config).await
+            }
+        }
+    }
+
+    async fn cleanup(&self) {
+        // Redis state expires via TTLs; only the local fallback needs pruning
+        self.fallback.cleanup().await;
+    }
+}
+
+/// Per-org and per-API-key rate limit overrides
+///
+/// Loaded from the `rate_limit_overrides` table and swapped into the
+/// limiter atomically, so admin changes take effect without a restart.
+/// An override replaces the requests-per-minute value the caller passes
+/// in for that org or key.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitOverrides {
+    /// Org-wide requests-per-minute overrides
+    pub org_rpm: HashMap<Uuid, u32>,
+    /// Per-API-key requests-per-minute overrides
+    pub api_key_rpm: HashMap<Uuid, u32>,
+    /// Org-wide temporary exemptions
+    pub org_exemptions: HashMap<Uuid, Exemption>,
+    /// Per-API-key temporary exemptions
+    pub api_key_exemptions: HashMap<Uuid, Exemption>,
+}
+
+/// A temporary rate limit exemption (from `rate_limit_exemptions`)
+///
+/// Applied on top of the effective limit: a multiplier scales it, a
+/// bypass removes it. `expires_at` is re-checked on every lookup so an
+/// exemption stops applying the moment it lapses, without waiting for
+/// the next table reload.
+#[derive(Debug, Clone, Copy)]
+pub struct Exemption {
+    /// Factor applied to the effective limit; `None` means full bypass
+    pub multiplier: Option<f32>,
+    pub expires_at: OffsetDateTime,
+}
+
+impl Exemption {
+    /// Limit after applying this exemption, or the base unchanged once
+    /// the exemption has expired
+    fn apply(&self, base: u32) -> u32 {
+        if self.expires_at <= OffsetDateTime::now_utc() {
+            return base;
+        }
+        match self.multiplier {
+            Some(m) => ((f64::from(base) * f64::from(m)) as u32).max(base),
+            None => u32::MAX,
+        }
+    }
+}
+
+/// Load the current override table from the database
+pub async fn load_overrides(pool: &sqlx::PgPool) -> Result<RateLimitOverrides, sqlx::Error> {
+    let rows: Vec<(Uuid, Option<Uuid>, i32)> =
+        sqlx::query_as("SELECT org_id, api_key_id, requests_per_minute FROM rate_limit_overrides")
+            .fetch_all(pool)
+            .await?;
+
+    let mut overrides = RateLimitOverrides::default();
+    for (org_id, api_key_id, rpm) in rows {
+        match api_key_id {
+            Some(key_id) => {
+                overrides.api_key_rpm.insert(key_id, rpm as u32);
+            }
+            None => {
+                overrides.org_rpm.insert(org_id, rpm as u32);
+            }
+        }
+    }
+
+    // Active exemptions; expired/revoked rows never enter the map, and
+    // Exemption::apply re-checks expiry between reloads
+    let exemptions: Vec<(Uuid, Option<Uuid>, Option<f32>, OffsetDateTime)> = sqlx::query_as(
+        r#"
+        SELECT org_id, api_key_id, multiplier, expires_at
+        FROM rate_limit_exemptions
+        WHERE revoked_at IS NULL AND expires_at > NOW()
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for (org_id, api_key_id, multiplier, expires_at) in exemptions {
+        let exemption = Exemption {
+            multiplier,
+            expires_at,
+        };
+        match api_key_id {
+            Some(key_id) => {
+                overrides.api_key_exemptions.insert(key_id, exemption);
+            }
+            None => {
+                overrides.org_exemptions.insert(org_id, exemption);
+            }
+        }
+    }
+    Ok(overrides)
+}
+
 /// Rate limiter service
 pub struct RateLimiter {
-    inner: Arc<InMemoryRateLimiter>,
+    store: Arc<dyn RateLimiterStore>,
+    overrides: Arc<tokio::sync::RwLock<RateLimitOverrides>>,
 }
 
 impl RateLimiter {
-    /// Create a new in-memory rate limiter
+    /// Create a new in-memory (per-instance) rate limiter
     pub fn new_in_memory() -> Self {
         Self {
-            inner: Arc::new(InMemoryRateLimiter::new()),
+            store: Arc::new(InMemoryRateLimiter::new()),
+            overrides: Arc::new(tokio::sync::RwLock::new(RateLimitOverrides::default())),
+        }
+    }
+
+    /// Create a Redis-backed rate limiter so limits are enforced across
+    /// all instances
+    ///
+    /// Fails only on an invalid URL. The connection is established on
+    /// first check, and per-check Redis errors degrade to local
+    /// enforcement automatically.
+    pub fn new_redis(redis_url: &str) -> RateLimitResult<Self> {
+        Ok(Self {
+            store: Arc::new(RedisRateLimiterStore::new(redis_url)?),
+            overrides: Arc::new(tokio::sync::RwLock::new(RateLimitOverrides::default())),
+        })
+    }
+
+    /// Replace the override table (hot reload; no restart required)
+    pub async fn set_overrides(&self, overrides: RateLimitOverrides) {
+        *self.overrides.write().await = overrides;
+    }
+
+    /// Effective requests-per-minute for an API key: override (or the
+    /// fallback) with any unexpired exemption applied on top
+    pub async fn effective_api_key_rpm(&self, api_key_id: Uuid, fallback: u32) -> u32 {
+        let overrides = self.overrides.read().await;
+        let base = overrides
+            .api_key_rpm
+            .get(&api_key_id)
+            .copied()
+            .unwrap_or(fallback);
+        match overrides.api_key_exemptions.get(&api_key_id) {
+            Some(exemption) => exemption.apply(base),
+            None => base,
+        }
+    }
+
+    /// Effective requests-per-minute for an org: override (or the
+    /// fallback) with any unexpired exemption applied on top
+    pub async fn effective_org_rpm(&self, org_id: Uuid, fallback: u32) -> u32 {
+        let overrides = self.overrides.read().await;
+        let base = overrides.org_rpm.get(&org_id).copied().unwrap_or(fallback);
+        match overrides.org_exemptions.get(&org_id) {
+            Some(exemption) => exemption.apply(base),
+            None => base,
         }
     }
 
@@ -223,10 +786,12 @@ impl RateLimiter {
     ) -> RateLimitResult<RateLimitResult2> {
         let key = format!("ratelimit:apikey:{}", api_key_id);
         let config = RateLimitConfig {
-            requests_per_minute,
+            requests_per_minute: self
+                .effective_api_key_rpm(api_key_id, requests_per_minute)
+                .await,
             ..Default::default()
         };
-        self.inner.check_rate_limit(&key, &config).await
+        self.store.check_rate_limit(&key, &config).await
     }
 
     /// Check rate limit for an organization (overall limit)
@@ -237,10 +802,10 @@ impl RateLimiter {
     ) -> RateLimitResult<RateLimitResult2> {
         let key = format!("ratelimit:org:{}", org_id);
         let config = RateLimitConfig {
-            requests_per_minute,
+            requests_per_minute: self.effective_org_rpm(org_id, requests_per_minute).await,
             ..Default::default()
         };
-        self.inner.check_rate_limit(&key, &config).await
+        self.store.check_rate_limit(&key, &config).await
     }
 
     /// Check rate limit for a specific MCP instance
@@ -255,7 +820,7 @@ impl RateLimiter {
             requests_per_minute,
             ..Default::default()
         };
-        self.inner.check_rate_limit(&key, &config).await
+        self.store.check_rate_limit(&key, &config).await
     }
 
     /// Check combined rate limits (API key + org)
@@ -281,6 +846,7 @@ impl RateLimiter {
         // Return the most restrictive remaining count
         Ok(RateLimitResult2 {
             allowed: true,
+            limit: api_key_result.limit.min(org_result.limit),
             remaining_minute: api_key_result
                 .remaining_minute
                 .min(org_result.remaining_minute),
@@ -291,9 +857,19 @@ impl RateLimiter {
         })
     }
 
+    /// Check a rate limit with an explicit config, selecting the algorithm
+    /// per [`RateLimitConfig::algorithm`]
+    pub async fn check_with_config(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+    ) -> RateLimitResult<RateLimitResult2> {
+        self.store.check_rate_limit(key, config).await
+    }
+
     /// Clean up old rate limit windows
     pub async fn cleanup(&self) {
-        self.inner.cleanup().await;
+        self.store.cleanup().await;
     }
 
     // ==========================================================================
@@ -310,7 +886,7 @@ impl RateLimiter {
             requests_per_minute: get_auth_rate_limit(),
             ..Default::default()
         };
-        self.inner.check_rate_limit(&key, &config).await
+        self.store.check_rate_limit(&key, &config).await
     }
 
     /// Check rate limit for 2FA verification attempts
@@ -323,7 +899,7 @@ impl RateLimiter {
             requests_per_minute: get_2fa_rate_limit(),
             ..Default::default()
         };
-        self.inner.check_rate_limit(&key, &config).await
+        self.store.check_rate_limit(&key, &config).await
     }
 
     /// Check rate limit for password reset requests by IP
@@ -339,7 +915,7 @@ impl RateLimiter {
             requests_per_minute: get_password_reset_rate_limit(),
             ..Default::default()
         };
-        self.inner.check_rate_limit(&key, &config).await
+        self.store.check_rate_limit(&key, &config).await
     }
 
     /// Check rate limit for ticket creation by organization
@@ -352,7 +928,7 @@ impl RateLimiter {
             requests_per_minute: get_ticket_rate_limit(),
             ..Default::default()
         };
-        self.inner.check_rate_limit(&key, &config).await
+        self.store.check_rate_limit(&key, &config).await
     }
 
     /// Check rate limit for account registration by IP
@@ -365,7 +941,7 @@ impl RateLimiter {
             requests_per_minute: get_registration_rate_limit(),
             ..Default::default()
         };
-        self.inner.check_rate_limit(&key, &config).await
+        self.store.check_rate_limit(&key, &config).await
     }
 
     /// Check rate limit for OAuth authentication attempts by IP
@@ -378,14 +954,15 @@ impl RateLimiter {
             requests_per_minute: get_oauth_rate_limit(),
             ..Default::default()
         };
-        self.inner.check_rate_limit(&key, &config).await
+        self.store.check_rate_limit(&key, &config).await
     }
 }
 
 impl Clone for RateLimiter {
     fn clone(&self) -> Self {
         Self {
-            inner: Arc::clone(&self.inner),
+            store: Arc::clone(&self.store),
+            overrides: Arc::clone(&self.overrides),
         }
     }
 }
@@ -534,6 +1111,58 @@ mod tests {
         assert_eq!(config.requests_per_minute, 60);
         assert!(config.requests_per_hour.is_none());
         assert!(config.monthly_limit.is_none());
+        assert_eq!(config.algorithm, RateLimitAlgorithm::FixedWindow);
+    }
+
+    #[tokio::test]
+    async fn test_sliding_log_blocks_at_limit() {
+        let limiter = RateLimiter::new_in_memory();
+        let config = RateLimitConfig {
+            requests_per_minute: 3,
+            algorithm: RateLimitAlgorithm::SlidingLog,
+            ..Default::default()
+        };
+
+        for i in 0..3 {
+            let result = limiter.check_with_config("log_key", &config).await.unwrap();
+            assert!(result.allowed, "Request {} should be allowed", i);
+            assert_eq!(result.remaining_minute, 3 - i - 1);
+        }
+
+        let result = limiter.check_with_config("log_key", &config).await.unwrap();
+        assert!(!result.allowed);
+        assert_eq!(result.limit, 3);
+        // The oldest request ages out within the rolling 60s window
+        assert!(result.retry_after_seconds.is_some_and(|s| s <= 60));
+    }
+
+    #[tokio::test]
+    async fn test_token_bucket_allows_burst_then_blocks() {
+        let limiter = RateLimiter::new_in_memory();
+        let config = RateLimitConfig {
+            requests_per_minute: 5,
+            algorithm: RateLimitAlgorithm::TokenBucket { burst: 3 },
+            ..Default::default()
+        };
+
+        // Full bucket holds requests_per_minute + burst tokens
+        for i in 0..8 {
+            let result = limiter
+                .check_with_config("bucket_key", &config)
+                .await
+                .unwrap();
+            assert!(result.allowed, "Burst request {} should be allowed", i);
+        }
+
+        let result = limiter
+            .check_with_config("bucket_key", &config)
+            .await
+            .unwrap();
+        assert!(!result.allowed);
+        // Next token arrives at the steady refill rate (5/minute = 12s)
+        assert!(result
+            .retry_after_seconds
+            .is_some_and(|s| (1..=12).contains(&s)));
     }
 
     #[tokio::test]
@@ -557,6 +1186,133 @@ mod tests {
         assert!(!result.allowed);
     }
 
+    #[tokio::test]
+    async fn test_api_key_override_replaces_passed_limit() {
+        let limiter = RateLimiter::new_in_memory();
+        let org_id = Uuid::new_v4();
+        let api_key_id = Uuid::new_v4();
+
+        let mut overrides = RateLimitOverrides::default();
+        overrides.api_key_rpm.insert(api_key_id, 2);
+        limiter.set_overrides(overrides).await;
+
+        // Caller passes 100 rpm but the override caps the key at 2
+        limiter
+            .check_api_key(org_id, api_key_id, 100)
+            .await
+            .unwrap();
+        limiter
+            .check_api_key(org_id, api_key_id, 100)
+            .await
+            .unwrap();
+        let result = limiter
+            .check_api_key(org_id, api_key_id, 100)
+            .await
+            .unwrap();
+        assert!(!result.allowed);
+
+        // Keys without an override keep the passed limit
+        let other_key = Uuid::new_v4();
+        let result = limiter.check_api_key(org_id, other_key, 100).await.unwrap();
+        assert!(result.allowed);
+        assert_eq!(result.remaining_minute, 99);
+    }
+
+    #[tokio::test]
+    async fn test_override_hot_swap_clears_previous() {
+        let limiter = RateLimiter::new_in_memory();
+        let org_id = Uuid::new_v4();
+
+        let mut overrides = RateLimitOverrides::default();
+        overrides.org_rpm.insert(org_id, 5);
+        limiter.set_overrides(overrides).await;
+        assert_eq!(limiter.effective_org_rpm(org_id, 60).await, 5);
+
+        // Replacing with an empty table restores the fallback
+        limiter.set_overrides(RateLimitOverrides::default()).await;
+        assert_eq!(limiter.effective_org_rpm(org_id, 60).await, 60);
+    }
+
+    #[tokio::test]
+    async fn test_exemption_multiplier_scales_effective_limit() {
+        let limiter = RateLimiter::new_in_memory();
+        let org_id = Uuid::new_v4();
+
+        let mut overrides = RateLimitOverrides::default();
+        overrides.org_exemptions.insert(
+            org_id,
+            Exemption {
+                multiplier: Some(2.5),
+                expires_at: OffsetDateTime::now_utc() + time::Duration::hours(1),
+            },
+        );
+        limiter.set_overrides(overrides).await;
+
+        assert_eq!(limiter.effective_org_rpm(org_id, 100).await, 250);
+        // Other orgs are unaffected
+        assert_eq!(limiter.effective_org_rpm(Uuid::new_v4(), 100).await, 100);
+    }
+
+    #[tokio::test]
+    async fn test_exemption_bypass_removes_limit() {
+        let limiter = RateLimiter::new_in_memory();
+        let api_key_id = Uuid::new_v4();
+
+        let mut overrides = RateLimitOverrides::default();
+        overrides.api_key_exemptions.insert(
+            api_key_id,
+            Exemption {
+                multiplier: None,
+                expires_at: OffsetDateTime::now_utc() + time::Duration::hours(1),
+            },
+        );
+        limiter.set_overrides(overrides).await;
+
+        assert_eq!(
+            limiter.effective_api_key_rpm(api_key_id, 60).await,
+            u32::MAX
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exemption_stacks_on_override() {
+        let limiter = RateLimiter::new_in_memory();
+        let org_id = Uuid::new_v4();
+
+        let mut overrides = RateLimitOverrides::default();
+        overrides.org_rpm.insert(org_id, 200);
+        overrides.org_exemptions.insert(
+            org_id,
+            Exemption {
+                multiplier: Some(3.0),
+                expires_at: OffsetDateTime::now_utc() + time::Duration::hours(1),
+            },
+        );
+        limiter.set_overrides(overrides).await;
+
+        // Multiplier applies to the overridden value, not the fallback
+        assert_eq!(limiter.effective_org_rpm(org_id, 60).await, 600);
+    }
+
+    #[tokio::test]
+    async fn test_exemption_expires_between_reloads() {
+        let limiter = RateLimiter::new_in_memory();
+        let org_id = Uuid::new_v4();
+
+        // Still in the map (the reload hasn't pruned it) but past expiry
+        let mut overrides = RateLimitOverrides::default();
+        overrides.org_exemptions.insert(
+            org_id,
+            Exemption {
+                multiplier: None,
+                expires_at: OffsetDateTime::now_utc() - time::Duration::seconds(1),
+            },
+        );
+        limiter.set_overrides(overrides).await;
+
+        assert_eq!(limiter.effective_org_rpm(org_id, 60).await, 60);
+    }
+
     #[tokio::test]
     async fn test_rate_limiter_cleanup() {
         let limiter = InMemoryRateLimiter::new();
@@ -580,4 +1336,41 @@ mod tests {
         // At least one key should exist (they're recent)
         assert!(!windows.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_in_memory_store_usable_as_trait_object() {
+        let store: Arc<dyn RateLimiterStore> = Arc::new(InMemoryRateLimiter::new());
+        let config = RateLimitConfig {
+            requests_per_minute: 2,
+            ..Default::default()
+        };
+
+        assert!(
+            store
+                .check_rate_limit("trait:key", &config)
+                .await
+                .unwrap()
+                .allowed
+        );
+        assert!(
+            store
+                .check_rate_limit("trait:key", &config)
+                .await
+                .unwrap()
+                .allowed
+        );
+        assert!(
+            !store
+                .check_rate_limit("trait:key", &config)
+                .await
+                .unwrap()
+                .allowed
+        );
+    }
+
+    #[test]
+    fn test_new_redis_rejects_invalid_url() {
+        // No network involved: URL parsing fails before any connection
+        assert!(RateLimiter::new_redis("not-a-redis-url").is_err());
+    }
 }
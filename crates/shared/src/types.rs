@@ -118,10 +118,11 @@ pub fn is_self_hosted() -> bool {
 // =============================================================================
 
 /// Subscription tier for billing
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "VARCHAR", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 pub enum SubscriptionTier {
+    #[default]
     Free,
     Starter,
     Pro,
@@ -129,12 +130,6 @@ pub enum SubscriptionTier {
     Enterprise,
 }
 
-impl Default for SubscriptionTier {
-    fn default() -> Self {
-        Self::Free
-    }
-}
-
 impl SubscriptionTier {
     /// Monthly request limit for this tier
     /// Unified pricing: Free (1K) → Pro (50K) → Team (250K) → Enterprise (Unlimited)
@@ -243,6 +238,15 @@ impl SubscriptionTier {
         matches!(self, Self::Pro | Self::Team | Self::Enterprise)
     }
 
+    /// Whether keep-warm connections are available for low-latency MCPs
+    /// Pro and above; Self-hosted mode: Always allowed
+    pub fn keep_warm_allowed(&self) -> bool {
+        if is_self_hosted() {
+            return true;
+        }
+        matches!(self, Self::Pro | Self::Team | Self::Enterprise)
+    }
+
     /// Whether SSO is available
     /// Only Team and Enterprise tiers have SSO
     /// Self-hosted mode: Always allowed
@@ -372,22 +376,17 @@ impl std::str::FromStr for SubscriptionTier {
 }
 
 /// User role within an organization
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "VARCHAR", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 pub enum UserRole {
     Owner,
     Admin,
+    #[default]
     Member,
     Viewer,
 }
 
-impl Default for UserRole {
-    fn default() -> Self {
-        Self::Member
-    }
-}
-
 impl UserRole {
     /// Get the permission level for this role (higher = more permissions)
     /// Owner: 3, Admin: 2, Member: 1, Viewer: 0
@@ -430,10 +429,11 @@ impl UserRole {
 }
 
 /// Subscription status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "VARCHAR", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 pub enum SubscriptionStatus {
+    #[default]
     Active,
     PastDue,
     Canceled,
@@ -442,50 +442,35 @@ pub enum SubscriptionStatus {
     Incomplete,
 }
 
-impl Default for SubscriptionStatus {
-    fn default() -> Self {
-        Self::Active
-    }
-}
-
 /// MCP instance status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "VARCHAR", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 pub enum McpStatus {
+    #[default]
     Active,
     Inactive,
     Error,
     Provisioning,
 }
 
-impl Default for McpStatus {
-    fn default() -> Self {
-        Self::Active
-    }
-}
-
 /// Health check status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "VARCHAR", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 pub enum HealthStatus {
     Healthy,
     Unhealthy,
+    #[default]
     Unknown,
 }
 
-impl Default for HealthStatus {
-    fn default() -> Self {
-        Self::Unknown
-    }
-}
-
 /// Invoice status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "VARCHAR", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 pub enum InvoiceStatus {
+    #[default]
     Draft,
     Open,
     Paid,
@@ -493,31 +478,20 @@ pub enum InvoiceStatus {
     Uncollectible,
 }
 
-impl Default for InvoiceStatus {
-    fn default() -> Self {
-        Self::Draft
-    }
-}
-
 /// Member status for team member limit enforcement
 /// - Active: Full access to organization resources
 /// - Suspended: Read-only access due to plan limits
 /// - Pending: Invited but not yet accepted
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "VARCHAR", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 pub enum MemberStatus {
+    #[default]
     Active,
     Suspended,
     Pending,
 }
 
-impl Default for MemberStatus {
-    fn default() -> Self {
-        Self::Active
-    }
-}
-
 impl MemberStatus {
     /// Check if member has full access (can modify resources)
     pub fn has_full_access(&self) -> bool {
@@ -0,0 +1,113 @@
+//! Deprecated API usage notifications
+//!
+//! Finds orgs that called a deprecated route (per the usage rollup in
+//! `api_deprecation_usage`) and haven't been told yet, emails their
+//! owners/admins, and records the notification so each org hears about
+//! each deprecation exactly once. Registry changes (new sunset dates)
+//! are announced through normal channels, not re-sent here.
+
+use plexmcp_api::email::SecurityEmailService;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use tracing::{error, info};
+use uuid::Uuid;
+
+#[derive(Debug, sqlx::FromRow)]
+struct PendingNotice {
+    deprecation_id: Uuid,
+    org_id: Uuid,
+    org_name: String,
+    method: String,
+    path_pattern: String,
+    sunset_at: Option<OffsetDateTime>,
+    successor: Option<String>,
+}
+
+/// Notify orgs with unannounced deprecated-route usage
+pub async fn run_deprecation_notice_pass(pool: &PgPool, email_service: &SecurityEmailService) {
+    if !email_service.is_enabled() {
+        return;
+    }
+
+    let pending: Vec<PendingNotice> = match sqlx::query_as(
+        r#"
+        SELECT DISTINCT d.id AS deprecation_id, u.org_id, o.name AS org_name,
+               d.method, d.path_pattern, d.sunset_at, d.successor
+        FROM api_deprecation_usage u
+        JOIN api_deprecations d ON d.id = u.deprecation_id AND d.enabled = TRUE
+        JOIN organizations o ON o.id = u.org_id
+        WHERE NOT EXISTS (
+            SELECT 1 FROM api_deprecation_notifications n
+            WHERE n.deprecation_id = u.deprecation_id AND n.org_id = u.org_id
+        )
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            error!(error = %e, "Failed to load pending deprecation notices");
+            return;
+        }
+    };
+
+    if pending.is_empty() {
+        return;
+    }
+
+    let date_format = time::format_description::well_known::Rfc3339;
+    let mut sent = 0;
+    for notice in &pending {
+        let recipients: Vec<(String,)> = match sqlx::query_as(
+            "SELECT email FROM users WHERE org_id = $1 AND role IN ('owner', 'admin')",
+        )
+        .bind(notice.org_id)
+        .fetch_all(pool)
+        .await
+        {
+            Ok(rows) => rows,
+            Err(e) => {
+                error!(org_id = %notice.org_id, error = %e, "Failed to load deprecation notice recipients");
+                continue;
+            }
+        };
+
+        let sunset_display = notice
+            .sunset_at
+            .and_then(|dt| dt.format(&date_format).ok().map(|s| s[..10].to_string()));
+        for (email,) in &recipients {
+            email_service
+                .send_api_deprecation_notice(
+                    email,
+                    &notice.org_name,
+                    &notice.method,
+                    &notice.path_pattern,
+                    sunset_display.as_deref(),
+                    notice.successor.as_deref(),
+                )
+                .await;
+        }
+
+        // Record even when the org has no admins to email, so the pass
+        // doesn't retry it forever
+        if let Err(e) = sqlx::query(
+            r#"
+            INSERT INTO api_deprecation_notifications (deprecation_id, org_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(notice.deprecation_id)
+        .bind(notice.org_id)
+        .execute(pool)
+        .await
+        {
+            error!(org_id = %notice.org_id, error = %e, "Failed to record deprecation notification");
+            continue;
+        }
+        sent += 1;
+    }
+
+    info!(notified = sent, "Deprecation notice pass complete");
+}
@@ -0,0 +1,143 @@
+//! API key expiry reminder campaign
+//!
+//! Emails key creators (falling back to the org owner) at 30, 7, and
+//! 1 day(s) before an API key's effective expiry. The effective expiry
+//! is the earliest of the key's explicit `expires_at` and the org's
+//! `max_api_key_age_days` rotation policy measured from the last
+//! rotation. `expiry_reminder_threshold_days` records the smallest
+//! stage already sent so each stage fires exactly once; rotating a key
+//! resets the tracking.
+
+use plexmcp_api::email::SecurityEmailService;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use tracing::{error, info};
+use uuid::Uuid;
+
+/// Maximum reminders sent per pass
+const BATCH_LIMIT: i64 = 200;
+
+/// Key due for a reminder, with the 30/7/1 stage it has reached
+#[derive(sqlx::FromRow)]
+struct ReminderCandidate {
+    id: Uuid,
+    name: String,
+    key_prefix: String,
+    org_id: Uuid,
+    org_name: String,
+    recipient: Option<String>,
+    effective_expires_at: OffsetDateTime,
+    threshold_days: i32,
+}
+
+/// Run one reminder pass: email every active key within 30 days of its
+/// effective expiry that has not yet received its current-stage reminder
+pub async fn run_expiry_reminder_pass(pool: &PgPool, email_service: &SecurityEmailService) {
+    let candidates: Vec<ReminderCandidate> = match sqlx::query_as(
+        r#"
+        SELECT ak.id, ak.name, ak.key_prefix, ak.org_id, o.name AS org_name,
+               COALESCE(u.email, owner.email) AS recipient,
+               eff.expires_at AS effective_expires_at,
+               stage.threshold_days
+        FROM api_keys ak
+        JOIN organizations o ON o.id = ak.org_id
+        LEFT JOIN users u ON u.id = ak.created_by
+        LEFT JOIN LATERAL (
+            SELECT email FROM users
+            WHERE org_id = ak.org_id AND role = 'owner'
+            LIMIT 1
+        ) owner ON true
+        JOIN LATERAL (
+            SELECT LEAST(
+                ak.expires_at,
+                CASE WHEN o.max_api_key_age_days IS NOT NULL
+                     THEN COALESCE(ak.last_rotated_at, ak.created_at)
+                          + make_interval(days => o.max_api_key_age_days)
+                END
+            ) AS expires_at
+        ) eff ON true
+        JOIN LATERAL (
+            SELECT CASE
+                WHEN eff.expires_at <= NOW() + INTERVAL '1 day' THEN 1
+                WHEN eff.expires_at <= NOW() + INTERVAL '7 days' THEN 7
+                ELSE 30
+            END AS threshold_days
+        ) stage ON true
+        WHERE ak.status = 'active'
+          AND eff.expires_at > NOW()
+          AND eff.expires_at <= NOW() + INTERVAL '30 days'
+          AND (ak.expiry_reminder_threshold_days IS NULL
+               OR ak.expiry_reminder_threshold_days > stage.threshold_days)
+        ORDER BY eff.expires_at ASC
+        LIMIT $1
+        "#,
+    )
+    .bind(BATCH_LIMIT)
+    .fetch_all(pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            error!(error = %e, "Failed to fetch key expiry reminder candidates");
+            return;
+        }
+    };
+
+    if candidates.is_empty() {
+        return;
+    }
+
+    let mut reminded = 0;
+    for candidate in candidates {
+        // Record the stage first so a concurrent pass can't double-send
+        let result = sqlx::query(
+            r#"
+            UPDATE api_keys
+            SET expiry_reminder_threshold_days = $2
+            WHERE id = $1
+              AND (expiry_reminder_threshold_days IS NULL
+                   OR expiry_reminder_threshold_days > $2)
+            "#,
+        )
+        .bind(candidate.id)
+        .bind(candidate.threshold_days)
+        .execute(pool)
+        .await;
+
+        match result {
+            Ok(rows) if rows.rows_affected() > 0 => {
+                reminded += 1;
+                if let Some(recipient) = candidate.recipient {
+                    let now = OffsetDateTime::now_utc();
+                    let days_remaining =
+                        ((candidate.effective_expires_at - now).whole_days() + 1).max(1) as i32;
+                    let expires_on = candidate.effective_expires_at.date().to_string();
+
+                    // White-label orgs send from their verified domain
+                    let sender = email_service.for_org(pool, candidate.org_id).await;
+                    sender
+                        .send_api_key_expiry_reminder(
+                            &recipient,
+                            &candidate.org_name,
+                            &candidate.name,
+                            &candidate.key_prefix,
+                            days_remaining,
+                            &expires_on,
+                        )
+                        .await;
+                } else {
+                    info!(
+                        key_id = %candidate.id,
+                        "No recipient email for key expiry reminder"
+                    );
+                }
+            }
+            Ok(_) => {} // Another pass got there first
+            Err(e) => {
+                error!(key_id = %candidate.id, error = %e, "Failed to record key expiry reminder");
+            }
+        }
+    }
+
+    info!(reminded = reminded, "Key expiry reminder pass complete");
+}
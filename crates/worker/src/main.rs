@@ -13,7 +13,20 @@
 //! - Webhook queue processing (every minute)
 //! - Test history cleanup based on subscription tier (daily at 4:00 AM UTC)
 //! - MCP health check monitoring (every 30 minutes)
-
+//! - Ticket lifecycle automation: inactivity reminders and auto-close (hourly)
+//! - Weekly security digest emails for org admins (Mondays 9:00 UTC)
+//! - API key expiry reminders at 30/7/1 days before expiry (daily at 8:00 UTC)
+//! - Customer usage alert evaluation with email/webhook delivery (every 15 minutes)
+//! - Weekly operations digest emails for platform admins (Mondays 9:30 UTC)
+//! - Outbound org event webhook delivery with retries (every minute)
+//! - Deprecated API usage notifications to affected orgs (daily at 10:00 UTC)
+
+mod deprecation_notices;
+mod key_rotation;
+mod ops_digest;
+mod security_digest;
+mod ticket_lifecycle;
+mod usage_alerts;
 mod webhook_processor;
 
 use std::sync::Arc;
@@ -491,13 +504,203 @@ async fn main() -> anyhow::Result<()> {
         .await?;
     info!("Scheduled: MCP health check monitoring (every 30 minutes)");
 
+    // Job 10: Product metrics rollup (daily at 1:00 AM UTC)
+    // Computes activation, weekly active orgs, and churn for the admin dashboard
+    let product_metrics_pool = pool.clone();
+    scheduler
+        .add(Job::new_async("0 0 1 * * *", move |_uuid, _l| {
+            let pool = product_metrics_pool.clone();
+            Box::pin(async move {
+                info!("Running product metrics rollup");
+                match plexmcp_api::routes::admin::product_metrics::compute_daily_rollup(&pool).await
+                {
+                    Ok(()) => info!("Product metrics rollup complete"),
+                    Err(e) => error!(error = %e, "Product metrics rollup failed"),
+                }
+            })
+        })?)
+        .await?;
+    info!("Scheduled: Product metrics rollup (daily at 1:00 AM UTC)");
+
+    // Job 11: Revenue analytics snapshot (daily at 1:30 AM UTC)
+    // Computes MRR/ARR, expansion/contraction, and cohort LTV from local tables
+    let revenue_billing = billing.clone();
+    scheduler
+        .add(Job::new_async("0 30 1 * * *", move |_uuid, _l| {
+            let billing = revenue_billing.clone();
+            Box::pin(async move {
+                info!("Running revenue analytics snapshot");
+                match billing.revenue.compute_snapshot().await {
+                    Ok(snapshot) => info!(
+                        mrr_cents = snapshot.mrr_cents,
+                        "Revenue analytics snapshot complete"
+                    ),
+                    Err(e) => error!(error = %e, "Revenue analytics snapshot failed"),
+                }
+            })
+        })?)
+        .await?;
+    info!("Scheduled: Revenue analytics snapshot (daily at 1:30 AM UTC)");
+
+    // Job 12: Ticket lifecycle automation (hourly at :30)
+    // Sends inactivity reminders and auto-closes tickets per lifecycle policies
+    let lifecycle_pool = pool.clone();
+    let lifecycle_email_service = SecurityEmailService::from_env();
+    scheduler
+        .add(Job::new_async("0 30 * * * *", move |_uuid, _l| {
+            let pool = lifecycle_pool.clone();
+            let email_service = lifecycle_email_service.clone();
+            Box::pin(async move {
+                info!("Running ticket lifecycle automation");
+                ticket_lifecycle::run_lifecycle_pass(&pool, &email_service).await;
+            })
+        })?)
+        .await?;
+    info!("Scheduled: Ticket lifecycle automation (hourly at :30)");
+
+    // Job 13: Weekly security digest (Mondays at 9:00 UTC)
+    // Emails per-org anomaly summaries to opted-in owners/admins
+    let digest_pool = pool.clone();
+    let digest_email_service = SecurityEmailService::from_env().with_pool(digest_pool.clone());
+    scheduler
+        .add(Job::new_async("0 0 9 * * Mon", move |_uuid, _l| {
+            let pool = digest_pool.clone();
+            let email_service = digest_email_service.clone();
+            Box::pin(async move {
+                info!("Running weekly security digest");
+                security_digest::run_weekly_digest(&pool, &email_service).await;
+            })
+        })?)
+        .await?;
+    info!("Scheduled: Weekly security digest (Mondays at 9:00 UTC)");
+
+    // Job 14: Interaction event retention (daily at 4:30 AM UTC)
+    // Scroll/click heatmap events are high volume; cap them at the
+    // configured interaction_retention_days from analytics_settings
+    let interaction_cleanup_pool = pool.clone();
+    scheduler
+        .add(Job::new_async("0 30 4 * * *", move |_uuid, _l| {
+            let pool = interaction_cleanup_pool.clone();
+            Box::pin(async move {
+                info!("Running interaction event retention cleanup");
+
+                let result = sqlx::query(
+                    r#"
+                    DELETE FROM analytics_interaction_events
+                    WHERE created_at < NOW() - make_interval(days =>
+                        (SELECT interaction_retention_days FROM analytics_settings LIMIT 1))
+                "#,
+                )
+                .execute(&pool)
+                .await;
+
+                match result {
+                    Ok(r) => info!(
+                        deleted = r.rows_affected(),
+                        "Interaction event cleanup complete"
+                    ),
+                    Err(e) => error!(error = %e, "Interaction event cleanup failed"),
+                }
+            })
+        })?)
+        .await?;
+    info!("Scheduled: Interaction event retention cleanup (daily at 4:30 AM UTC)");
+
+    // Job 15: API key expiry reminders (daily at 8:00 UTC)
+    // Emails key creators at 30/7/1 days before effective expiry, including
+    // expiry forced by an org's max key age rotation policy
+    let key_rotation_pool = pool.clone();
+    let key_rotation_email_service =
+        SecurityEmailService::from_env().with_pool(key_rotation_pool.clone());
+    scheduler
+        .add(Job::new_async("0 0 8 * * *", move |_uuid, _l| {
+            let pool = key_rotation_pool.clone();
+            let email_service = key_rotation_email_service.clone();
+            Box::pin(async move {
+                info!("Running API key expiry reminder pass");
+                key_rotation::run_expiry_reminder_pass(&pool, &email_service).await;
+            })
+        })?)
+        .await?;
+    info!("Scheduled: API key expiry reminders (daily at 8:00 UTC)");
+
+    // Job 16: Customer usage alerts (every 15 minutes)
+    // Evaluates org-defined thresholds against billing-period usage and
+    // delivers via email/webhook with per-alert cooldowns
+    let usage_alerts_pool = pool.clone();
+    let usage_alerts_meter = billing.usage.clone();
+    let usage_alerts_email_service =
+        SecurityEmailService::from_env().with_pool(usage_alerts_pool.clone());
+    scheduler
+        .add(Job::new_async("0 */15 * * * *", move |_uuid, _l| {
+            let pool = usage_alerts_pool.clone();
+            let meter = usage_alerts_meter.clone();
+            let email_service = usage_alerts_email_service.clone();
+            Box::pin(async move {
+                usage_alerts::run_usage_alert_pass(&pool, &meter, &email_service).await;
+            })
+        })?)
+        .await?;
+    info!("Scheduled: Customer usage alerts (every 15 minutes)");
+
+    // Job 17: Weekly operations digest (Mondays at 9:30 UTC)
+    // Platform-wide summary (new orgs, tier changes, failures, SLA
+    // breaches) emailed to users with a platform admin role
+    let ops_digest_pool = pool.clone();
+    let ops_digest_email_service = SecurityEmailService::from_env();
+    scheduler
+        .add(Job::new_async("0 30 9 * * Mon", move |_uuid, _l| {
+            let pool = ops_digest_pool.clone();
+            let email_service = ops_digest_email_service.clone();
+            Box::pin(async move {
+                info!("Running weekly ops digest");
+                ops_digest::run_weekly_ops_digest(&pool, &email_service).await;
+            })
+        })?)
+        .await?;
+    info!("Scheduled: Weekly operations digest (Mondays at 9:30 UTC)");
+
+    // Job 18: Org webhook delivery (every minute)
+    // Drains pending outbound event webhooks with signed delivery and
+    // exponential backoff (see plexmcp_shared::org_webhooks)
+    let org_webhooks_pool = pool.clone();
+    scheduler
+        .add(Job::new_async("0 * * * * *", move |_uuid, _l| {
+            let pool = org_webhooks_pool.clone();
+            Box::pin(async move {
+                let attempted = plexmcp_shared::org_webhooks::process_pending(&pool).await;
+                if attempted > 0 {
+                    info!(attempted = attempted, "Org webhook delivery pass complete");
+                }
+            })
+        })?)
+        .await?;
+    info!("Scheduled: Org webhook delivery (every minute)");
+
+    // Job 19: Deprecation usage notifications (daily at 10:00 UTC)
+    // Emails org admins the first time their org shows up in the
+    // deprecated-route usage rollup
+    let deprecation_pool = pool.clone();
+    let deprecation_email_service =
+        SecurityEmailService::from_env().with_pool(deprecation_pool.clone());
+    scheduler
+        .add(Job::new_async("0 0 10 * * *", move |_uuid, _l| {
+            let pool = deprecation_pool.clone();
+            let email_service = deprecation_email_service.clone();
+            Box::pin(async move {
+                deprecation_notices::run_deprecation_notice_pass(&pool, &email_service).await;
+            })
+        })?)
+        .await?;
+    info!("Scheduled: Deprecation usage notifications (daily at 10:00 UTC)");
+
     // Start the scheduler
     info!("Starting job scheduler");
     scheduler.start().await?;
 
     info!(
         "PlexMCP Worker started successfully with {} scheduled jobs",
-        9
+        19
     );
 
     // Keep the main task running
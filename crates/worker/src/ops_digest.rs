@@ -0,0 +1,150 @@
+//! Weekly platform operations digest
+//!
+//! Assembles a platform-wide summary for internal operators - new orgs,
+//! tier changes, failed webhook and dunning counts, the upstreams with
+//! the most failed health checks, open critical alerts, and SLA-breached
+//! tickets - and emails it to every user with a platform admin role.
+
+use plexmcp_api::email::{OpsDigest, SecurityEmailService};
+use sqlx::PgPool;
+use tracing::{error, info};
+
+/// Maximum failing upstreams listed per digest (display cap)
+const MAX_ERROR_MCPS: i64 = 5;
+
+/// Send the weekly operations digest to every platform admin
+pub async fn run_weekly_ops_digest(pool: &PgPool, email_service: &SecurityEmailService) {
+    if !email_service.is_enabled() {
+        info!("Email not configured, skipping ops digest run");
+        return;
+    }
+
+    let recipients: Vec<(String,)> = match sqlx::query_as(
+        "SELECT email FROM users WHERE platform_role IN ('admin', 'superadmin') ORDER BY email",
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            error!(error = %e, "Failed to load ops digest recipients");
+            return;
+        }
+    };
+
+    if recipients.is_empty() {
+        info!("No platform admins found, skipping ops digest run");
+        return;
+    }
+
+    let digest = match build_ops_digest(pool).await {
+        Ok(digest) => digest,
+        Err(e) => {
+            error!(error = %e, "Failed to build ops digest");
+            return;
+        }
+    };
+
+    for (email,) in &recipients {
+        email_service.send_admin_ops_digest(email, &digest).await;
+    }
+
+    info!(sent = recipients.len(), "Weekly ops digest run complete");
+}
+
+/// Collect platform-wide digest numbers for the trailing 7-day window
+async fn build_ops_digest(pool: &PgPool) -> Result<OpsDigest, sqlx::Error> {
+    let new_orgs: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM organizations WHERE created_at >= NOW() - INTERVAL '7 days'",
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let tier_changes_by_source: Vec<(String, i64)> = sqlx::query_as(
+        r#"
+        SELECT source, COUNT(*) FROM tier_change_audit
+        WHERE created_at >= NOW() - INTERVAL '7 days'
+        GROUP BY source
+        ORDER BY COUNT(*) DESC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    // Webhooks that exhausted their retries during the window
+    let failed_webhooks: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM webhook_processing_queue
+        WHERE status = 'failed' AND created_at >= NOW() - INTERVAL '7 days'
+        "#,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    // Dunning starts with a failed invoice payment; count the Stripe
+    // events rather than current subscription state so resolved cases
+    // still show up in the week they happened
+    let dunning_events: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM stripe_webhook_events
+        WHERE event_type = 'invoice.payment_failed'
+          AND processed_at >= NOW() - INTERVAL '7 days'
+        "#,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let top_error_mcps: Vec<(String, i64)> = sqlx::query_as(
+        r#"
+        SELECT o.name || ' / ' || m.name, COUNT(*)
+        FROM mcp_test_history h
+        JOIN mcp_instances m ON m.id = h.mcp_id
+        JOIN organizations o ON o.id = h.org_id
+        WHERE h.tested_at >= NOW() - INTERVAL '7 days'
+          AND h.health_status != 'healthy'
+        GROUP BY o.name, m.name
+        ORDER BY COUNT(*) DESC
+        LIMIT $1
+        "#,
+    )
+    .bind(MAX_ERROR_MCPS)
+    .fetch_all(pool)
+    .await?;
+
+    let open_critical_alerts: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM security_alerts WHERE severity = 'critical' AND resolved_at IS NULL",
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let sla_breached_tickets: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM support_tickets
+        WHERE status NOT IN ('resolved'::ticket_status, 'closed'::ticket_status)
+          AND (COALESCE(first_response_breached, false) OR COALESCE(resolution_breached, false))
+        "#,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let now = time::OffsetDateTime::now_utc();
+    let start = now - time::Duration::days(7);
+    let date_format = time::format_description::well_known::Rfc3339;
+    let format_date = |d: time::OffsetDateTime| {
+        d.format(&date_format)
+            .map(|s| s[..10].to_string())
+            .unwrap_or_default()
+    };
+
+    Ok(OpsDigest {
+        period_start: format_date(start),
+        period_end: format_date(now),
+        new_orgs,
+        tier_changes_by_source,
+        failed_webhooks,
+        dunning_events,
+        top_error_mcps,
+        open_critical_alerts,
+        sla_breached_tickets,
+    })
+}
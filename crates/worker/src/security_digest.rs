@@ -0,0 +1,186 @@
+//! Weekly security digest emails
+//!
+//! Assembles per-org anomaly summaries from audit and alert data - new
+//! trusted devices, failed logins, sign-ins from previously unseen
+//! locations, security alert counts by type, and week-over-week usage
+//! spikes - and emails them to org owners/admins who opted into weekly
+//! digests in their notification preferences.
+
+use plexmcp_api::email::{SecurityDigest, SecurityEmailService};
+use sqlx::PgPool;
+use tracing::{error, info};
+use uuid::Uuid;
+
+/// Maximum new sign-in IPs listed per digest (display cap)
+const MAX_NEW_IPS: i64 = 10;
+
+/// Usage must at least double AND clear this floor to count as a spike
+const SPIKE_MIN_REQUESTS: i64 = 1000;
+
+/// Send the weekly security digest for every org with opted-in recipients
+pub async fn run_weekly_digest(pool: &PgPool, email_service: &SecurityEmailService) {
+    if !email_service.is_enabled() {
+        info!("Email not configured, skipping security digest run");
+        return;
+    }
+
+    // Owners/admins who explicitly enabled the weekly digest (default is off)
+    let recipients: Vec<(Uuid, String, String)> = match sqlx::query_as(
+        r#"
+        SELECT u.org_id, o.name, u.email
+        FROM users u
+        JOIN organizations o ON o.id = u.org_id
+        JOIN user_notification_preferences np ON np.user_id = u.id
+        WHERE u.role IN ('owner', 'admin')
+          AND np.weekly_digest = true
+          AND np.security_alerts = true
+        ORDER BY u.org_id
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            error!(error = %e, "Failed to load digest recipients");
+            return;
+        }
+    };
+
+    if recipients.is_empty() {
+        info!("No digest recipients opted in, skipping security digest run");
+        return;
+    }
+
+    let mut sent = 0;
+    let mut current: Option<(Uuid, SecurityDigest)> = None;
+    for (org_id, org_name, email) in &recipients {
+        // Recipients are ordered by org, so the digest is built once per org
+        let digest = match &current {
+            Some((cached_org, digest)) if cached_org == org_id => digest.clone(),
+            _ => match build_org_digest(pool, *org_id).await {
+                Ok(digest) => {
+                    current = Some((*org_id, digest.clone()));
+                    digest
+                }
+                Err(e) => {
+                    error!(org_id = %org_id, error = %e, "Failed to build security digest");
+                    continue;
+                }
+            },
+        };
+
+        email_service
+            .send_security_digest(email, org_name, &digest)
+            .await;
+        sent += 1;
+    }
+
+    info!(sent = sent, "Weekly security digest run complete");
+}
+
+/// Collect one org's digest numbers for the trailing 7-day window
+async fn build_org_digest(pool: &PgPool, org_id: Uuid) -> Result<SecurityDigest, sqlx::Error> {
+    let new_devices: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM user_trusted_devices d
+        JOIN users u ON u.id = d.user_id
+        WHERE u.org_id = $1 AND d.created_at >= NOW() - INTERVAL '7 days'
+        "#,
+    )
+    .bind(org_id)
+    .fetch_one(pool)
+    .await?;
+
+    // Failed logins against org members (matched by user or email since
+    // failed attempts often have no resolved user_id)
+    let failed_logins: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM auth_audit_log a
+        WHERE a.event_type = 'login_failed'
+          AND a.created_at >= NOW() - INTERVAL '7 days'
+          AND (a.user_id IN (SELECT id FROM users WHERE org_id = $1)
+               OR a.email IN (SELECT email FROM users WHERE org_id = $1))
+        "#,
+    )
+    .bind(org_id)
+    .fetch_one(pool)
+    .await?;
+
+    // Session IPs this week that never appeared in the prior 90 days
+    let new_signin_ips: Vec<(String,)> = sqlx::query_as(
+        r#"
+        SELECT DISTINCT host(s.ip_address)
+        FROM sessions s
+        JOIN users u ON u.id = s.user_id
+        WHERE u.org_id = $1
+          AND s.ip_address IS NOT NULL
+          AND s.created_at >= NOW() - INTERVAL '7 days'
+          AND NOT EXISTS (
+              SELECT 1 FROM sessions prior
+              JOIN users pu ON pu.id = prior.user_id
+              WHERE pu.org_id = $1
+                AND prior.ip_address = s.ip_address
+                AND prior.created_at < NOW() - INTERVAL '7 days'
+                AND prior.created_at >= NOW() - INTERVAL '90 days'
+          )
+        LIMIT $2
+        "#,
+    )
+    .bind(org_id)
+    .bind(MAX_NEW_IPS)
+    .fetch_all(pool)
+    .await?;
+
+    let alerts_by_type: Vec<(String, i64)> = sqlx::query_as(
+        r#"
+        SELECT alert_type, COUNT(*) FROM security_alerts
+        WHERE org_id = $1 AND created_at >= NOW() - INTERVAL '7 days'
+        GROUP BY alert_type
+        ORDER BY COUNT(*) DESC
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?;
+
+    let (requests_this_week, requests_prev_week): (i64, i64) = sqlx::query_as(
+        r#"
+        SELECT
+            COALESCE(SUM(total_requests) FILTER (
+                WHERE period_hour >= NOW() - INTERVAL '7 days'), 0)::BIGINT,
+            COALESCE(SUM(total_requests) FILTER (
+                WHERE period_hour < NOW() - INTERVAL '7 days'
+                  AND period_hour >= NOW() - INTERVAL '14 days'), 0)::BIGINT
+        FROM usage_aggregates
+        WHERE org_id = $1 AND period_hour >= NOW() - INTERVAL '14 days'
+        "#,
+    )
+    .bind(org_id)
+    .fetch_one(pool)
+    .await?;
+
+    let usage_spike =
+        requests_this_week >= SPIKE_MIN_REQUESTS && requests_this_week > requests_prev_week * 2;
+
+    let now = time::OffsetDateTime::now_utc();
+    let start = now - time::Duration::days(7);
+    let date_format = time::format_description::well_known::Rfc3339;
+    let format_date = |d: time::OffsetDateTime| {
+        d.format(&date_format)
+            .map(|s| s[..10].to_string())
+            .unwrap_or_default()
+    };
+
+    Ok(SecurityDigest {
+        period_start: format_date(start),
+        period_end: format_date(now),
+        new_devices,
+        failed_logins,
+        new_signin_ips: new_signin_ips.into_iter().map(|(ip,)| ip).collect(),
+        alerts_by_type,
+        requests_this_week,
+        requests_prev_week,
+        usage_spike,
+    })
+}
@@ -0,0 +1,252 @@
+//! Ticket lifecycle automation
+//!
+//! Sends inactivity reminders to customers on tickets awaiting their
+//! response and auto-closes them after a further grace period. Both
+//! thresholds come from `ticket_lifecycle_policies`, resolved most
+//! specific first (org+category, then org-wide, then category-wide,
+//! then the global default). Tickets with SLA breaches are excluded so
+//! breached conversations stay open for review.
+
+use plexmcp_api::email::SecurityEmailService;
+use sqlx::PgPool;
+use tracing::{error, info};
+use uuid::Uuid;
+
+/// Maximum tickets processed per pass for each phase
+const BATCH_LIMIT: i64 = 100;
+
+/// Run one lifecycle pass: send due reminders, then auto-close
+/// tickets whose reminder grace period has elapsed
+pub async fn run_lifecycle_pass(pool: &PgPool, email_service: &SecurityEmailService) {
+    send_inactivity_reminders(pool, email_service).await;
+    auto_close_inactive_tickets(pool, email_service).await;
+}
+
+/// Ticket row eligible for a lifecycle email, with the resolved policy
+/// threshold relevant to the phase (close-after days for reminders,
+/// reopen-window days for auto-close)
+#[derive(sqlx::FromRow)]
+struct LifecycleCandidate {
+    id: Uuid,
+    ticket_number: String,
+    subject: String,
+    recipient: Option<String>,
+    policy_days: i32,
+    organization_id: Option<Uuid>,
+}
+
+/// Remind customers on tickets awaiting their response past the policy threshold
+async fn send_inactivity_reminders(pool: &PgPool, email_service: &SecurityEmailService) {
+    // Candidates: awaiting a customer response longer than the resolved
+    // policy allows, no reminder sent for the current wait, no SLA breach,
+    // and no customer message since the last admin reply
+    let candidates: Vec<LifecycleCandidate> = match sqlx::query_as(
+        r#"
+        SELECT t.id, t.ticket_number, t.subject,
+               COALESCE(t.contact_email, u.email) AS recipient,
+               p.close_after_days AS policy_days, t.organization_id
+        FROM support_tickets t
+        LEFT JOIN users u ON u.id = t.user_id
+        JOIN LATERAL (
+            SELECT lp.reminder_after_days, lp.close_after_days
+            FROM ticket_lifecycle_policies lp
+            WHERE lp.is_active
+              AND (lp.org_id = t.organization_id OR lp.org_id IS NULL)
+              AND (lp.category = t.category OR lp.category IS NULL)
+            ORDER BY lp.org_id NULLS LAST, lp.category NULLS LAST
+            LIMIT 1
+        ) p ON true
+        WHERE t.status = 'awaiting_response'::ticket_status
+          AND NOT COALESCE(t.first_response_breached, false)
+          AND NOT COALESCE(t.resolution_breached, false)
+          AND t.updated_at < NOW() - make_interval(days => p.reminder_after_days)
+          AND (t.inactivity_reminder_sent_at IS NULL
+               OR t.inactivity_reminder_sent_at < t.updated_at)
+          AND NOT EXISTS (
+              SELECT 1 FROM ticket_messages m
+              WHERE m.ticket_id = t.id
+                AND m.is_admin_reply = false
+                AND m.created_at > t.updated_at
+          )
+        ORDER BY t.updated_at ASC
+        LIMIT $1
+        "#,
+    )
+    .bind(BATCH_LIMIT)
+    .fetch_all(pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            error!(error = %e, "Failed to fetch reminder candidates");
+            return;
+        }
+    };
+
+    if candidates.is_empty() {
+        return;
+    }
+
+    let mut reminded = 0;
+    for candidate in candidates {
+        let LifecycleCandidate {
+            id: ticket_id,
+            ticket_number,
+            subject,
+            recipient,
+            policy_days: close_after_days,
+            organization_id: org_id,
+        } = candidate;
+        let result = sqlx::query(
+            r#"
+            UPDATE support_tickets
+            SET inactivity_reminder_sent_at = NOW()
+            WHERE id = $1 AND status = 'awaiting_response'::ticket_status
+            "#,
+        )
+        .bind(ticket_id)
+        .execute(pool)
+        .await;
+
+        match result {
+            Ok(rows) if rows.rows_affected() > 0 => {
+                reminded += 1;
+                if let Some(recipient) = recipient {
+                    // White-label orgs send from their verified domain
+                    let sender = match org_id {
+                        Some(org_id) => email_service.for_org(pool, org_id).await,
+                        None => email_service.clone(),
+                    };
+                    sender
+                        .send_ticket_inactivity_reminder(
+                            &recipient,
+                            &ticket_number,
+                            &subject,
+                            close_after_days,
+                        )
+                        .await;
+                } else {
+                    info!(
+                        ticket_id = %ticket_id,
+                        "No recipient email for inactivity reminder"
+                    );
+                }
+            }
+            Ok(_) => {} // Status changed between fetch and update
+            Err(e) => {
+                error!(ticket_id = %ticket_id, error = %e, "Failed to record inactivity reminder");
+            }
+        }
+    }
+
+    info!(reminded = reminded, "Inactivity reminder pass complete");
+}
+
+/// Close tickets whose post-reminder grace period has elapsed without a reply
+async fn auto_close_inactive_tickets(pool: &PgPool, email_service: &SecurityEmailService) {
+    let candidates: Vec<LifecycleCandidate> = match sqlx::query_as(
+        r#"
+        SELECT t.id, t.ticket_number, t.subject,
+               COALESCE(t.contact_email, u.email) AS recipient,
+               p.reopen_window_days AS policy_days, t.organization_id
+        FROM support_tickets t
+        LEFT JOIN users u ON u.id = t.user_id
+        JOIN LATERAL (
+            SELECT lp.close_after_days, lp.reopen_window_days
+            FROM ticket_lifecycle_policies lp
+            WHERE lp.is_active
+              AND (lp.org_id = t.organization_id OR lp.org_id IS NULL)
+              AND (lp.category = t.category OR lp.category IS NULL)
+            ORDER BY lp.org_id NULLS LAST, lp.category NULLS LAST
+            LIMIT 1
+        ) p ON true
+        WHERE t.status = 'awaiting_response'::ticket_status
+          AND NOT COALESCE(t.first_response_breached, false)
+          AND NOT COALESCE(t.resolution_breached, false)
+          AND t.inactivity_reminder_sent_at IS NOT NULL
+          AND t.inactivity_reminder_sent_at >= t.updated_at
+          AND t.inactivity_reminder_sent_at < NOW() - make_interval(days => p.close_after_days)
+          AND NOT EXISTS (
+              SELECT 1 FROM ticket_messages m
+              WHERE m.ticket_id = t.id
+                AND m.is_admin_reply = false
+                AND m.created_at > t.inactivity_reminder_sent_at
+          )
+        ORDER BY t.inactivity_reminder_sent_at ASC
+        LIMIT $1
+        "#,
+    )
+    .bind(BATCH_LIMIT)
+    .fetch_all(pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            error!(error = %e, "Failed to fetch auto-close candidates");
+            return;
+        }
+    };
+
+    if candidates.is_empty() {
+        return;
+    }
+
+    let mut closed = 0;
+    for candidate in candidates {
+        let LifecycleCandidate {
+            id: ticket_id,
+            ticket_number,
+            subject,
+            recipient,
+            policy_days: reopen_window_days,
+            organization_id: org_id,
+        } = candidate;
+        let result = sqlx::query(
+            r#"
+            UPDATE support_tickets
+            SET status = 'closed'::ticket_status,
+                closed_at = NOW(),
+                auto_closed_at = NOW(),
+                reopen_deadline = NOW() + make_interval(days => $2),
+                updated_at = NOW()
+            WHERE id = $1 AND status = 'awaiting_response'::ticket_status
+            "#,
+        )
+        .bind(ticket_id)
+        .bind(reopen_window_days)
+        .execute(pool)
+        .await;
+
+        match result {
+            Ok(rows) if rows.rows_affected() > 0 => {
+                closed += 1;
+                info!(
+                    ticket_id = %ticket_id,
+                    ticket_number = %ticket_number,
+                    "Ticket auto-closed for inactivity"
+                );
+                if let Some(recipient) = recipient {
+                    let sender = match org_id {
+                        Some(org_id) => email_service.for_org(pool, org_id).await,
+                        None => email_service.clone(),
+                    };
+                    sender
+                        .send_ticket_auto_closed(
+                            &recipient,
+                            ticket_id,
+                            &ticket_number,
+                            &subject,
+                            reopen_window_days,
+                        )
+                        .await;
+                }
+            }
+            Ok(_) => {} // Status changed between fetch and update
+            Err(e) => {
+                error!(ticket_id = %ticket_id, error = %e, "Failed to auto-close ticket");
+            }
+        }
+    }
+
+    info!(closed = closed, "Auto-close pass complete");
+}
@@ -0,0 +1,185 @@
+//! Customer usage alert evaluation
+//!
+//! Orgs define thresholds against their included request quota via
+//! `/api/v1/usage/alerts` ("notify me at 80% of my included calls"). Each
+//! pass loads the enabled alerts, compares the org's billing-period usage
+//! to the threshold, and delivers via email (to the alert creator) and/or
+//! webhook. An alert fires at most once per cooldown window and re-arms
+//! when a new billing period starts.
+
+use plexmcp_api::email::SecurityEmailService;
+use plexmcp_billing::usage::UsageMeter;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Webhook delivery timeout
+const WEBHOOK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[derive(Debug, sqlx::FromRow)]
+struct UsageAlertRow {
+    id: Uuid,
+    org_id: Uuid,
+    org_name: String,
+    name: String,
+    threshold_percent: i32,
+    notify_email: bool,
+    webhook_url: Option<String>,
+    cooldown_hours: i32,
+    last_triggered_at: Option<OffsetDateTime>,
+    last_triggered_period_start: Option<OffsetDateTime>,
+    creator_email: Option<String>,
+}
+
+/// Evaluate every enabled usage alert against metered usage
+pub async fn run_usage_alert_pass(pool: &PgPool, usage: &UsageMeter, email: &SecurityEmailService) {
+    let alerts: Vec<UsageAlertRow> = match sqlx::query_as(
+        r#"
+        SELECT ua.id, ua.org_id, o.name AS org_name, ua.name, ua.threshold_percent,
+               ua.notify_email, ua.webhook_url, ua.cooldown_hours,
+               ua.last_triggered_at, ua.last_triggered_period_start,
+               u.email AS creator_email
+        FROM usage_alerts ua
+        JOIN organizations o ON o.id = ua.org_id
+        LEFT JOIN users u ON u.id = ua.created_by
+        WHERE ua.enabled = TRUE
+        ORDER BY ua.org_id
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            error!(error = %e, "Failed to load usage alerts");
+            return;
+        }
+    };
+
+    if alerts.is_empty() {
+        return;
+    }
+
+    let mut fired = 0;
+    let mut current: Option<(Uuid, plexmcp_billing::usage::BillingPeriodUsage)> = None;
+    for alert in &alerts {
+        // Alerts are ordered by org, so usage is fetched once per org
+        let period = match &current {
+            Some((cached_org, usage)) if *cached_org == alert.org_id => usage.clone(),
+            _ => match usage.get_billing_period_usage(alert.org_id).await {
+                Ok(period) => {
+                    current = Some((alert.org_id, period.clone()));
+                    period
+                }
+                Err(e) => {
+                    error!(org_id = %alert.org_id, error = %e, "Failed to load billing period usage");
+                    continue;
+                }
+            },
+        };
+
+        // Unlimited tiers have nothing to alert against
+        if period.requests_limit == u64::MAX {
+            continue;
+        }
+
+        if period.percentage_used < alert.threshold_percent as f64 {
+            continue;
+        }
+
+        // Already fired this billing period and still cooling down?
+        let now = OffsetDateTime::now_utc();
+        let same_period = alert.last_triggered_period_start == Some(period.period_start);
+        if same_period {
+            if let Some(last) = alert.last_triggered_at {
+                if now - last < time::Duration::hours(alert.cooldown_hours as i64) {
+                    continue;
+                }
+            }
+        }
+
+        if alert.notify_email {
+            match alert.creator_email.as_deref() {
+                Some(to) => {
+                    email
+                        .send_usage_alert(
+                            to,
+                            &alert.org_name,
+                            &alert.name,
+                            alert.threshold_percent,
+                            period.percentage_used,
+                            period.requests_used,
+                            period.requests_limit,
+                        )
+                        .await;
+                }
+                None => {
+                    warn!(alert_id = %alert.id, "Usage alert creator no longer exists; skipping email");
+                }
+            }
+        }
+
+        if let Some(url) = alert.webhook_url.as_deref() {
+            deliver_webhook(url, alert, &period).await;
+        }
+
+        if let Err(e) = sqlx::query(
+            r#"
+            UPDATE usage_alerts
+            SET last_triggered_at = NOW(), last_triggered_period_start = $2, updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(alert.id)
+        .bind(period.period_start)
+        .execute(pool)
+        .await
+        {
+            error!(alert_id = %alert.id, error = %e, "Failed to record usage alert trigger");
+        }
+        fired += 1;
+    }
+
+    if fired > 0 {
+        info!(fired = fired, "Usage alert pass complete");
+    }
+}
+
+/// POST the alert payload to the org-configured webhook (best effort)
+async fn deliver_webhook(
+    url: &str,
+    alert: &UsageAlertRow,
+    period: &plexmcp_billing::usage::BillingPeriodUsage,
+) {
+    let payload = serde_json::json!({
+        "event": "usage.threshold_reached",
+        "alert_id": alert.id,
+        "alert_name": alert.name,
+        "org_id": alert.org_id,
+        "threshold_percent": alert.threshold_percent,
+        "percentage_used": period.percentage_used,
+        "requests_used": period.requests_used,
+        "requests_limit": period.requests_limit,
+        "period_start": period.period_start.to_string(),
+        "period_end": period.period_end.to_string(),
+    });
+
+    let client = match reqwest::Client::builder().timeout(WEBHOOK_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => {
+            error!(error = %e, "Failed to build webhook client");
+            return;
+        }
+    };
+
+    match client.post(url).json(&payload).send().await {
+        Ok(resp) if resp.status().is_success() => {}
+        Ok(resp) => {
+            warn!(alert_id = %alert.id, status = %resp.status(), "Usage alert webhook returned non-success");
+        }
+        Err(e) => {
+            warn!(alert_id = %alert.id, error = %e, "Usage alert webhook delivery failed");
+        }
+    }
+}
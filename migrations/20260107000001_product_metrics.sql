@@ -0,0 +1,47 @@
+-- Product Metrics: activation, weekly active orgs, and churn rollups
+--
+-- Daily rollups are computed by the worker (and on-demand via the admin API)
+-- so the success team can track:
+-- - Activation: orgs that created their first MCP / made their first proxy call
+-- - Weekly active orgs: orgs with any proxied usage in the trailing 7 days
+-- - Churn: orgs with no usage in 30 days after prior activity
+
+CREATE TABLE IF NOT EXISTS product_metrics_rollups (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+
+    -- The day this rollup covers (UTC)
+    metric_date DATE NOT NULL UNIQUE,
+
+    -- Activation counts for the day
+    orgs_created INTEGER NOT NULL DEFAULT 0,
+    orgs_first_mcp INTEGER NOT NULL DEFAULT 0,
+    orgs_first_proxy_call INTEGER NOT NULL DEFAULT 0,
+
+    -- Engagement snapshot (trailing windows ending at metric_date)
+    weekly_active_orgs INTEGER NOT NULL DEFAULT 0,
+
+    -- Churn: orgs whose last usage is 30+ days before metric_date
+    churned_orgs INTEGER NOT NULL DEFAULT 0,
+
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_product_metrics_rollups_date
+    ON product_metrics_rollups(metric_date DESC);
+
+-- Enable RLS
+ALTER TABLE product_metrics_rollups ENABLE ROW LEVEL SECURITY;
+ALTER TABLE product_metrics_rollups FORCE ROW LEVEL SECURITY;
+
+-- Only service_role can read/write rollups (exposed via admin API only)
+CREATE POLICY product_metrics_service_only ON product_metrics_rollups
+    FOR ALL
+    TO postgres, service_role
+    USING (true)
+    WITH CHECK (true);
+
+-- Block regular users from accessing rollups directly
+CREATE POLICY product_metrics_block_users ON product_metrics_rollups
+    FOR ALL
+    TO authenticated
+    USING (false);
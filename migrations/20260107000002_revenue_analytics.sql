@@ -0,0 +1,52 @@
+-- Revenue Analytics: daily MRR/ARR snapshots computed from local billing tables
+--
+-- Snapshots are refreshed by the worker (daily) and on demand from the admin
+-- API. All figures are derived from local subscriptions/invoices/overage data;
+-- no live Stripe calls are made.
+
+CREATE TABLE IF NOT EXISTS revenue_analytics_snapshots (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+
+    -- The day this snapshot covers (UTC)
+    snapshot_date DATE NOT NULL UNIQUE,
+
+    -- Recurring revenue totals (cents)
+    mrr_cents BIGINT NOT NULL DEFAULT 0,
+    arr_cents BIGINT NOT NULL DEFAULT 0,
+
+    -- MRR broken down by tier, e.g. {"pro": 290000, "team": 99000}
+    mrr_by_tier JSONB NOT NULL DEFAULT '{}',
+
+    -- Movement over the trailing 30 days, from tier_change_audit (cents)
+    expansion_cents BIGINT NOT NULL DEFAULT 0,
+    contraction_cents BIGINT NOT NULL DEFAULT 0,
+
+    -- Overage revenue recognized in the trailing 30 days (cents)
+    overage_revenue_cents BIGINT NOT NULL DEFAULT 0,
+
+    -- Cohort LTV estimates keyed by signup month, e.g.
+    -- {"2026-01": {"orgs": 12, "total_revenue_cents": 450000, "ltv_cents": 37500}}
+    cohort_ltv JSONB NOT NULL DEFAULT '{}',
+
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_revenue_snapshots_date
+    ON revenue_analytics_snapshots(snapshot_date DESC);
+
+-- Enable RLS
+ALTER TABLE revenue_analytics_snapshots ENABLE ROW LEVEL SECURITY;
+ALTER TABLE revenue_analytics_snapshots FORCE ROW LEVEL SECURITY;
+
+-- Only service_role can read/write snapshots (exposed via admin API only)
+CREATE POLICY revenue_snapshots_service_only ON revenue_analytics_snapshots
+    FOR ALL
+    TO postgres, service_role
+    USING (true)
+    WITH CHECK (true);
+
+-- Block regular users from accessing snapshots directly
+CREATE POLICY revenue_snapshots_block_users ON revenue_analytics_snapshots
+    FOR ALL
+    TO authenticated
+    USING (false);
@@ -0,0 +1,45 @@
+-- SSH keys for tunneled MCP connections (bastion / jump host access)
+--
+-- Private keys are AES-256-GCM encrypted with TOTP_ENCRYPTION_KEY before
+-- storage (same envelope as 2FA secrets). Only the fingerprint is ever
+-- returned to clients; key material is decrypted server-side when a tunnel
+-- is spawned.
+
+CREATE TABLE IF NOT EXISTS mcp_ssh_keys (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+
+    -- Display name, unique per org (e.g. "prod-bastion")
+    name VARCHAR(255) NOT NULL,
+
+    -- Encrypted PEM private key + AES-GCM nonce (both base64)
+    private_key_encrypted TEXT NOT NULL,
+    private_key_nonce TEXT NOT NULL,
+
+    -- SHA-256 hex digest of the key material, for display/dedup
+    fingerprint VARCHAR(64) NOT NULL,
+
+    created_by UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    UNIQUE(org_id, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_mcp_ssh_keys_org ON mcp_ssh_keys(org_id);
+
+-- Enable RLS
+ALTER TABLE mcp_ssh_keys ENABLE ROW LEVEL SECURITY;
+ALTER TABLE mcp_ssh_keys FORCE ROW LEVEL SECURITY;
+
+-- Only service_role can touch key material (the API enforces org scoping)
+CREATE POLICY mcp_ssh_keys_service_only ON mcp_ssh_keys
+    FOR ALL
+    TO postgres, service_role
+    USING (true)
+    WITH CHECK (true);
+
+-- Block regular users from accessing key material directly
+CREATE POLICY mcp_ssh_keys_block_users ON mcp_ssh_keys
+    FOR ALL
+    TO authenticated
+    USING (false);
@@ -0,0 +1,85 @@
+-- Content moderation rules for proxied MCP traffic
+--
+-- Org admins define regex rules that are evaluated against tool call
+-- arguments (requests) and tool results (responses) on the proxy path.
+-- Matching traffic is denied or redacted; every hit is recorded in
+-- moderation_decisions for audit.
+
+CREATE TABLE IF NOT EXISTS moderation_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+
+    -- Display name, unique per org (e.g. "aws-access-keys")
+    name VARCHAR(255) NOT NULL,
+
+    -- Regex evaluated against string values in the payload
+    pattern TEXT NOT NULL,
+
+    -- 'deny' rejects the request/response, 'redact' masks the match
+    action VARCHAR(16) NOT NULL CHECK (action IN ('deny', 'redact')),
+
+    -- Which side of the proxy call to inspect
+    direction VARCHAR(16) NOT NULL DEFAULT 'both'
+        CHECK (direction IN ('request', 'response', 'both')),
+
+    -- Prefixed tool names ("github:create_issue") exempt from this rule
+    bypass_tools TEXT[] NOT NULL DEFAULT '{}',
+
+    enabled BOOLEAN NOT NULL DEFAULT true,
+    created_by UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    UNIQUE(org_id, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_moderation_rules_org ON moderation_rules(org_id) WHERE enabled;
+
+-- Audit log of moderation hits (allow decisions are not recorded)
+CREATE TABLE IF NOT EXISTS moderation_decisions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    rule_id UUID REFERENCES moderation_rules(id) ON DELETE SET NULL,
+
+    -- Denormalized so the log survives rule deletion
+    rule_name VARCHAR(255) NOT NULL,
+
+    -- Prefixed tool name the decision applied to
+    tool_name VARCHAR(512) NOT NULL,
+    direction VARCHAR(16) NOT NULL,
+    action VARCHAR(16) NOT NULL,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_moderation_decisions_org_time
+    ON moderation_decisions(org_id, created_at DESC);
+
+-- Enable RLS
+ALTER TABLE moderation_rules ENABLE ROW LEVEL SECURITY;
+ALTER TABLE moderation_rules FORCE ROW LEVEL SECURITY;
+ALTER TABLE moderation_decisions ENABLE ROW LEVEL SECURITY;
+ALTER TABLE moderation_decisions FORCE ROW LEVEL SECURITY;
+
+-- Only service_role can access (the API enforces org scoping)
+CREATE POLICY moderation_rules_service_only ON moderation_rules
+    FOR ALL
+    TO postgres, service_role
+    USING (true)
+    WITH CHECK (true);
+
+CREATE POLICY moderation_rules_block_users ON moderation_rules
+    FOR ALL
+    TO authenticated
+    USING (false);
+
+CREATE POLICY moderation_decisions_service_only ON moderation_decisions
+    FOR ALL
+    TO postgres, service_role
+    USING (true)
+    WITH CHECK (true);
+
+CREATE POLICY moderation_decisions_block_users ON moderation_decisions
+    FOR ALL
+    TO authenticated
+    USING (false);
@@ -0,0 +1,14 @@
+-- Cost center tagging for internal chargeback
+--
+-- Org admins tag MCP instances and API keys with a cost center (team,
+-- department, project code). Usage is aggregated by tag via
+-- GET /usage/by-tag and the monthly chargeback CSV export, so platform
+-- teams can bill internal departments.
+
+ALTER TABLE mcp_instances ADD COLUMN IF NOT EXISTS cost_center VARCHAR(100);
+ALTER TABLE api_keys ADD COLUMN IF NOT EXISTS cost_center VARCHAR(100);
+
+CREATE INDEX IF NOT EXISTS idx_mcp_instances_cost_center
+    ON mcp_instances(org_id, cost_center) WHERE cost_center IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_api_keys_cost_center
+    ON api_keys(org_id, cost_center) WHERE cost_center IS NOT NULL;
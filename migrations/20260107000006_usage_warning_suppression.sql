@@ -0,0 +1,8 @@
+-- Per-key suppression of in-band usage warnings
+--
+-- When an org reaches the usage warning threshold (default 90% of quota)
+-- the proxy appends a warning under `_meta` in JSON-RPC responses so
+-- developers see it in their tool output. Keys with this flag set opt out
+-- (e.g. production automation that has its own monitoring).
+
+ALTER TABLE api_keys ADD COLUMN IF NOT EXISTS suppress_usage_warnings BOOLEAN NOT NULL DEFAULT false;
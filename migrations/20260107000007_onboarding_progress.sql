@@ -0,0 +1,32 @@
+-- Onboarding step tracking for activation analytics
+--
+-- The quickstart flow (POST /onboarding/quickstart) records which steps an
+-- org has completed so activation funnels can be measured without
+-- reconstructing them from resource timestamps.
+
+CREATE TABLE IF NOT EXISTS onboarding_progress (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    step VARCHAR(50) NOT NULL,
+    completed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    completed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(org_id, step)
+);
+
+CREATE INDEX IF NOT EXISTS idx_onboarding_progress_org ON onboarding_progress(org_id);
+
+-- Enable RLS
+ALTER TABLE onboarding_progress ENABLE ROW LEVEL SECURITY;
+ALTER TABLE onboarding_progress FORCE ROW LEVEL SECURITY;
+
+-- Only service_role can access (the API enforces org scoping)
+CREATE POLICY onboarding_progress_service_only ON onboarding_progress
+    FOR ALL
+    TO postgres, service_role
+    USING (true)
+    WITH CHECK (true);
+
+CREATE POLICY onboarding_progress_block_users ON onboarding_progress
+    FOR ALL
+    TO authenticated
+    USING (false);
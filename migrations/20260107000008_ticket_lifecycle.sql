@@ -0,0 +1,58 @@
+-- Ticket lifecycle automation: inactivity reminders and auto-close
+--
+-- The worker reminds customers after a ticket has been awaiting their
+-- response for `reminder_after_days`, then auto-closes it a further
+-- `close_after_days` later. Auto-closed tickets can be reopened by
+-- replying within `reopen_window_days`. Policies can be overridden
+-- per-org and per-category; the most specific active policy wins.
+-- Tickets with SLA breaches are excluded from automation.
+
+CREATE TABLE IF NOT EXISTS ticket_lifecycle_policies (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID REFERENCES organizations(id) ON DELETE CASCADE,  -- NULL = global default
+    category ticket_category,                                    -- NULL = all categories
+    reminder_after_days INTEGER NOT NULL DEFAULT 3 CHECK (reminder_after_days > 0),
+    close_after_days INTEGER NOT NULL DEFAULT 4 CHECK (close_after_days > 0),
+    reopen_window_days INTEGER NOT NULL DEFAULT 7 CHECK (reopen_window_days > 0),
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- One active policy per org/category combo (mirrors sla_rules)
+CREATE UNIQUE INDEX IF NOT EXISTS idx_lifecycle_policies_org_category
+    ON ticket_lifecycle_policies(org_id, category)
+    WHERE is_active = true;
+
+-- Global default policy
+INSERT INTO ticket_lifecycle_policies (org_id, category) VALUES (NULL, NULL);
+
+-- Lifecycle tracking on tickets
+ALTER TABLE support_tickets ADD COLUMN IF NOT EXISTS inactivity_reminder_sent_at TIMESTAMPTZ;
+ALTER TABLE support_tickets ADD COLUMN IF NOT EXISTS auto_closed_at TIMESTAMPTZ;
+ALTER TABLE support_tickets ADD COLUMN IF NOT EXISTS reopen_deadline TIMESTAMPTZ;
+
+-- Index for the scheduler's candidate scans
+CREATE INDEX IF NOT EXISTS idx_tickets_awaiting_lifecycle
+    ON support_tickets(updated_at)
+    WHERE status = 'awaiting_response';
+
+-- Enable RLS (admin endpoints enforce platform roles)
+ALTER TABLE ticket_lifecycle_policies ENABLE ROW LEVEL SECURITY;
+ALTER TABLE ticket_lifecycle_policies FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY ticket_lifecycle_policies_service_only ON ticket_lifecycle_policies
+    FOR ALL
+    TO postgres, service_role
+    USING (true)
+    WITH CHECK (true);
+
+CREATE POLICY ticket_lifecycle_policies_block_users ON ticket_lifecycle_policies
+    FOR ALL
+    TO authenticated
+    USING (false);
+
+COMMENT ON TABLE ticket_lifecycle_policies IS 'Inactivity reminder / auto-close thresholds by org and optional category';
+COMMENT ON COLUMN support_tickets.inactivity_reminder_sent_at IS 'When the customer was last reminded about an awaiting-response ticket';
+COMMENT ON COLUMN support_tickets.auto_closed_at IS 'Set when the worker auto-closed the ticket for inactivity';
+COMMENT ON COLUMN support_tickets.reopen_deadline IS 'Customer replies after this point can no longer reopen an auto-closed ticket';
@@ -0,0 +1,59 @@
+-- Spam quarantine for public submissions
+--
+-- The public enterprise-inquiry form (and inbound support email, where
+-- enabled) is scored for spam before a ticket is created. Submissions at
+-- or above the quarantine threshold land here for admin review instead
+-- of creating tickets; submissions at or above the discard threshold are
+-- recorded with status 'discarded' so automatic drops stay auditable.
+
+CREATE TABLE IF NOT EXISTS spam_quarantine (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+
+    -- Where the submission came from
+    source VARCHAR(32) NOT NULL CHECK (source IN ('enterprise_inquiry', 'email')),
+
+    contact_email VARCHAR(255),
+    subject TEXT NOT NULL,
+
+    -- Original submission (content + contact fields), enough to create
+    -- the ticket on approval
+    payload JSONB NOT NULL,
+
+    -- Spam score and the heuristics that contributed to it
+    score INTEGER NOT NULL,
+    signals TEXT[] NOT NULL DEFAULT '{}',
+
+    status VARCHAR(16) NOT NULL DEFAULT 'quarantined'
+        CHECK (status IN ('quarantined', 'approved', 'discarded')),
+
+    -- True when the discard threshold dropped it without human review
+    auto_discarded BOOLEAN NOT NULL DEFAULT false,
+
+    reviewed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    reviewed_at TIMESTAMPTZ,
+
+    -- Ticket created when an entry is approved
+    ticket_id UUID REFERENCES support_tickets(id) ON DELETE SET NULL,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_spam_quarantine_pending
+    ON spam_quarantine(created_at DESC)
+    WHERE status = 'quarantined';
+CREATE INDEX IF NOT EXISTS idx_spam_quarantine_status ON spam_quarantine(status, created_at DESC);
+
+-- Enable RLS
+ALTER TABLE spam_quarantine ENABLE ROW LEVEL SECURITY;
+ALTER TABLE spam_quarantine FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY spam_quarantine_service_only ON spam_quarantine
+    FOR ALL
+    TO postgres, service_role
+    USING (true)
+    WITH CHECK (true);
+
+CREATE POLICY spam_quarantine_block_users ON spam_quarantine
+    FOR ALL
+    TO authenticated
+    USING (false);
@@ -0,0 +1,81 @@
+-- Ticket attachments with asynchronous virus scanning
+--
+-- Attachments are stored through the shared storage backend (local or S3);
+-- this table tracks the object key and the scan lifecycle. Downloads are
+-- blocked by the API until scan_status = 'clean', and detections raise a
+-- 'malware_detected' security alert.
+
+CREATE TABLE IF NOT EXISTS ticket_attachments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    ticket_id UUID NOT NULL REFERENCES support_tickets(id) ON DELETE CASCADE,
+
+    -- NULL for attachments received via email ingestion
+    uploaded_by UUID REFERENCES users(id) ON DELETE SET NULL,
+
+    filename TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size_bytes BIGINT NOT NULL CHECK (size_bytes >= 0),
+
+    -- Object key in the configured storage backend
+    storage_key TEXT NOT NULL,
+
+    -- Scan lifecycle: pending -> scanning -> clean | infected | error
+    scan_status TEXT NOT NULL DEFAULT 'pending' CHECK (scan_status IN (
+        'pending',
+        'scanning',
+        'clean',
+        'infected',
+        'error'
+    )),
+    -- Scanner that produced the verdict (e.g. 'clamav', 'cloud', 'disabled')
+    scan_engine TEXT,
+    -- Signature name for infections, error message for scan failures
+    scan_detail TEXT,
+    scanned_at TIMESTAMPTZ,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_ticket_attachments_ticket
+    ON ticket_attachments(ticket_id);
+
+-- Lets a retry job find attachments whose scan never completed
+CREATE INDEX IF NOT EXISTS idx_ticket_attachments_unscanned
+    ON ticket_attachments(created_at)
+    WHERE scan_status IN ('pending', 'scanning');
+
+-- Row Level Security: service-role access only (API enforces ticket access)
+ALTER TABLE ticket_attachments ENABLE ROW LEVEL SECURITY;
+ALTER TABLE ticket_attachments FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY ticket_attachments_service_only ON ticket_attachments
+    FOR ALL TO postgres, service_role
+    USING (true)
+    WITH CHECK (true);
+
+CREATE POLICY ticket_attachments_block_users ON ticket_attachments
+    FOR ALL TO authenticated
+    USING (false);
+
+-- Register the malware alert type with the security alerting system
+ALTER TABLE security_alerts DROP CONSTRAINT IF EXISTS security_alerts_alert_type_check;
+ALTER TABLE security_alerts ADD CONSTRAINT security_alerts_alert_type_check
+    CHECK (alert_type IN (
+        'brute_force_attack',
+        'privilege_escalation',
+        'data_exfiltration',
+        'configuration_change',
+        'rate_limit_violation',
+        'suspicious_activity',
+        'authentication_anomaly',
+        'malware_detected'
+    ));
+
+INSERT INTO alert_configurations (alert_type, threshold_count, threshold_window_seconds, cooldown_seconds)
+VALUES
+    ('malware_detected', 1, 60, 0)  -- Immediate alert on any detection
+ON CONFLICT (alert_type) DO NOTHING;
+
+COMMENT ON TABLE ticket_attachments IS 'Support ticket file attachments with async virus scan status';
+COMMENT ON COLUMN ticket_attachments.storage_key IS 'Object key in the configured storage backend (local or S3)';
+COMMENT ON COLUMN ticket_attachments.scan_status IS 'pending -> scanning -> clean | infected | error; downloads require clean';
@@ -0,0 +1,47 @@
+-- Organization billing contacts
+--
+-- Email-only records for finance teams that need invoices without dashboard
+-- access. The primary contact's email is propagated to the Stripe customer
+-- so Stripe-sent invoices go to finance, and all contacts receive the
+-- invoice-ready notification when an invoice is finalized.
+
+CREATE TABLE IF NOT EXISTS billing_contacts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+
+    email TEXT NOT NULL,
+    -- Optional display name (e.g. "Accounts Payable")
+    name TEXT,
+
+    -- The primary contact's email becomes the Stripe customer email
+    is_primary BOOLEAN NOT NULL DEFAULT false,
+
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- One row per address per org (case-insensitive)
+CREATE UNIQUE INDEX IF NOT EXISTS idx_billing_contacts_org_email
+    ON billing_contacts(org_id, lower(email));
+
+-- At most one primary contact per org
+CREATE UNIQUE INDEX IF NOT EXISTS idx_billing_contacts_one_primary
+    ON billing_contacts(org_id)
+    WHERE is_primary;
+
+-- Row Level Security: service-role access only (API enforces org roles)
+ALTER TABLE billing_contacts ENABLE ROW LEVEL SECURITY;
+ALTER TABLE billing_contacts FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY billing_contacts_service_only ON billing_contacts
+    FOR ALL TO postgres, service_role
+    USING (true)
+    WITH CHECK (true);
+
+CREATE POLICY billing_contacts_block_users ON billing_contacts
+    FOR ALL TO authenticated
+    USING (false);
+
+COMMENT ON TABLE billing_contacts IS 'Per-org invoice recipients without dashboard accounts';
+COMMENT ON COLUMN billing_contacts.is_primary IS 'Primary contact email is synced to the Stripe customer';
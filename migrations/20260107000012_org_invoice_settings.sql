@@ -0,0 +1,14 @@
+-- Per-org invoice settings for enterprise invoices
+--
+-- Enterprises require purchase order numbers and billing details on their
+-- invoices. Stored locally and pushed to the Stripe customer: the PO number
+-- renders as an invoice custom field and the memo as the invoice footer.
+
+ALTER TABLE organizations
+    ADD COLUMN IF NOT EXISTS invoice_po_number TEXT,
+    ADD COLUMN IF NOT EXISTS invoice_billing_address TEXT,
+    ADD COLUMN IF NOT EXISTS invoice_memo TEXT;
+
+COMMENT ON COLUMN organizations.invoice_po_number IS 'Purchase order number shown on invoices (synced to Stripe custom field)';
+COMMENT ON COLUMN organizations.invoice_billing_address IS 'Free-text billing address shown in invoice detail responses';
+COMMENT ON COLUMN organizations.invoice_memo IS 'Memo text shown on invoices (synced to Stripe invoice footer)';
@@ -0,0 +1,53 @@
+-- Receipt documents for non-Stripe charges
+--
+-- Manual credits, locally recorded invoices, and paid overage charges have
+-- no Stripe-hosted document. The API renders PDF receipts for them (see the
+-- receipts module), stores the bytes through the storage backend, and
+-- tracks them here. Listed with signed download URLs under
+-- GET /billing/documents.
+
+CREATE TABLE IF NOT EXISTS billing_documents (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+
+    document_type TEXT NOT NULL CHECK (document_type IN (
+        'credit_receipt',
+        'manual_invoice',
+        'overage_receipt'
+    )),
+    -- Row the receipt was generated from (admin_refunds, invoices or
+    -- overage_charges depending on document_type)
+    source_id UUID NOT NULL,
+
+    title TEXT NOT NULL,
+    amount_cents BIGINT NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'usd',
+
+    -- Object key of the rendered PDF in the storage backend
+    storage_key TEXT NOT NULL,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- One document per source record
+CREATE UNIQUE INDEX IF NOT EXISTS idx_billing_documents_source
+    ON billing_documents(document_type, source_id);
+
+CREATE INDEX IF NOT EXISTS idx_billing_documents_org
+    ON billing_documents(org_id, created_at DESC);
+
+-- Row Level Security: service-role access only (API enforces org roles)
+ALTER TABLE billing_documents ENABLE ROW LEVEL SECURITY;
+ALTER TABLE billing_documents FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY billing_documents_service_only ON billing_documents
+    FOR ALL TO postgres, service_role
+    USING (true)
+    WITH CHECK (true);
+
+CREATE POLICY billing_documents_block_users ON billing_documents
+    FOR ALL TO authenticated
+    USING (false);
+
+COMMENT ON TABLE billing_documents IS 'Generated PDF receipts for charges with no Stripe-hosted document';
+COMMENT ON COLUMN billing_documents.source_id IS 'Originating row in admin_refunds, invoices or overage_charges';
@@ -0,0 +1,86 @@
+-- Scoped admin API tokens for internal automation
+--
+-- Platform engineers script against admin routes with personal JWTs today.
+-- Admin API tokens are superadmin-issued, scope-restricted (e.g.
+-- support:read, billing:read), expiring and revocable. Every request made
+-- with a token - including scope denials - is recorded in
+-- admin_token_usage_log.
+
+CREATE TABLE IF NOT EXISTS admin_api_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+
+    -- Human-readable purpose, e.g. "support dashboard sync"
+    name TEXT NOT NULL,
+
+    -- SHA-256 of the full token; the token itself is shown once at creation
+    token_hash TEXT NOT NULL UNIQUE,
+    -- Display prefix, e.g. "pmcpa_01abc..."
+    token_prefix TEXT NOT NULL,
+
+    -- Granted scopes as '<area>:<read|write>', e.g. {support:read,billing:read}
+    scopes TEXT[] NOT NULL,
+
+    -- Issuing superadmin; the token authenticates as this user, so tokens
+    -- die with their issuer
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+
+    expires_at TIMESTAMPTZ NOT NULL,
+    last_used_at TIMESTAMPTZ,
+    request_count BIGINT NOT NULL DEFAULT 0,
+
+    revoked_at TIMESTAMPTZ,
+    revoked_by UUID REFERENCES users(id) ON DELETE SET NULL,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_admin_api_tokens_created_by
+    ON admin_api_tokens(created_by);
+
+-- Per-request audit trail for admin tokens
+CREATE TABLE IF NOT EXISTS admin_token_usage_log (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    token_id UUID NOT NULL REFERENCES admin_api_tokens(id) ON DELETE CASCADE,
+
+    method TEXT NOT NULL,
+    path TEXT NOT NULL,
+    -- ok: request allowed; denied_scope: token lacks the required scope;
+    -- denied_path: token used outside /api/v1/admin/
+    outcome TEXT NOT NULL CHECK (outcome IN ('ok', 'denied_scope', 'denied_path')),
+
+    ip_address TEXT,
+    user_agent TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_admin_token_usage_token
+    ON admin_token_usage_log(token_id, created_at DESC);
+
+-- Row Level Security: service-role access only (API enforces superadmin)
+ALTER TABLE admin_api_tokens ENABLE ROW LEVEL SECURITY;
+ALTER TABLE admin_api_tokens FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY admin_api_tokens_service_only ON admin_api_tokens
+    FOR ALL TO postgres, service_role
+    USING (true)
+    WITH CHECK (true);
+
+CREATE POLICY admin_api_tokens_block_users ON admin_api_tokens
+    FOR ALL TO authenticated
+    USING (false);
+
+ALTER TABLE admin_token_usage_log ENABLE ROW LEVEL SECURITY;
+ALTER TABLE admin_token_usage_log FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY admin_token_usage_log_service_only ON admin_token_usage_log
+    FOR ALL TO postgres, service_role
+    USING (true)
+    WITH CHECK (true);
+
+CREATE POLICY admin_token_usage_log_block_users ON admin_token_usage_log
+    FOR ALL TO authenticated
+    USING (false);
+
+COMMENT ON TABLE admin_api_tokens IS 'Superadmin-issued scoped tokens for scripting against admin routes';
+COMMENT ON COLUMN admin_api_tokens.scopes IS 'Granted scopes as <area>:<read|write>, e.g. support:read';
+COMMENT ON TABLE admin_token_usage_log IS 'Per-request audit trail for admin API tokens';
@@ -0,0 +1,25 @@
+-- Alerting for repeated upstream response truncation
+--
+-- The MCP client now enforces per-MCP response size limits during
+-- streaming (see max_response_bytes in the MCP config). When an upstream
+-- repeatedly blows the limit, callers keep receiving truncated errors -
+-- surface that as a security alert so the org can fix or retire the MCP.
+
+ALTER TABLE security_alerts DROP CONSTRAINT IF EXISTS security_alerts_alert_type_check;
+ALTER TABLE security_alerts ADD CONSTRAINT security_alerts_alert_type_check
+    CHECK (alert_type IN (
+        'brute_force_attack',
+        'privilege_escalation',
+        'data_exfiltration',
+        'configuration_change',
+        'rate_limit_violation',
+        'suspicious_activity',
+        'authentication_anomaly',
+        'malware_detected',
+        'upstream_response_truncated'
+    ));
+
+INSERT INTO alert_configurations (alert_type, threshold_count, threshold_window_seconds, cooldown_seconds)
+VALUES
+    ('upstream_response_truncated', 5, 900, 3600)  -- 5 truncations in 15 min, hourly cooldown
+ON CONFLICT (alert_type) DO NOTHING;
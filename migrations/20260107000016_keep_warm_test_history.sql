@@ -0,0 +1,6 @@
+-- Track whether a connection test hit a warm (pre-established) connection
+-- so cold vs warm latency can be compared in the troubleshooting history.
+-- NULL for rows recorded before this column existed.
+ALTER TABLE mcp_test_history ADD COLUMN connection_warm BOOLEAN;
+
+COMMENT ON COLUMN mcp_test_history.connection_warm IS 'Whether a cached session/process existed before the test (warm) or the connection was set up from scratch (cold)';
@@ -0,0 +1,49 @@
+-- Bulk API key provisioning for MSP-style customers
+--
+-- Keys minted through POST /api-keys/bulk carry a shared label so a whole
+-- batch (e.g. one end-customer) can be revoked in one call. Bulk operations
+-- record an idempotency key so a retried request doesn't mint a second
+-- batch; replays return the original batch metadata without secrets.
+
+-- Shared label for keys created in one batch (also settable individually)
+ALTER TABLE api_keys ADD COLUMN label VARCHAR(100);
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_org_label
+    ON api_keys(org_id, label)
+    WHERE label IS NOT NULL;
+
+-- Idempotency ledger for bulk creation requests
+CREATE TABLE IF NOT EXISTS api_key_bulk_operations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+
+    -- Client-supplied idempotency key, unique per org
+    idempotency_key VARCHAR(255) NOT NULL,
+
+    -- IDs of the keys created by this operation
+    key_ids JSONB NOT NULL DEFAULT '[]',
+
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    UNIQUE(org_id, idempotency_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_key_bulk_operations_org
+    ON api_key_bulk_operations(org_id, created_at DESC);
+
+-- Row Level Security: service-role access only (API enforces org scoping)
+ALTER TABLE api_key_bulk_operations ENABLE ROW LEVEL SECURITY;
+ALTER TABLE api_key_bulk_operations FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY api_key_bulk_operations_service_only ON api_key_bulk_operations
+    FOR ALL TO postgres, service_role
+    USING (true)
+    WITH CHECK (true);
+
+CREATE POLICY api_key_bulk_operations_block_users ON api_key_bulk_operations
+    FOR ALL TO authenticated
+    USING (false);
+
+COMMENT ON TABLE api_key_bulk_operations IS 'Idempotency ledger for bulk API key creation';
+COMMENT ON COLUMN api_keys.label IS 'Batch label for grouped provisioning and bulk revocation';
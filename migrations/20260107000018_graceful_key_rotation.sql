@@ -0,0 +1,21 @@
+-- Graceful API key rotation with overlapping validity windows
+--
+-- Rotating a key used to invalidate the old secret instantly, breaking any
+-- client that hadn't been updated yet. Rotation now keeps the previous
+-- secret valid until rotation_expires_at so deployments can roll over at
+-- their own pace; previous_last_used_at shows whether anything still
+-- authenticates with the old secret before the window closes.
+
+ALTER TABLE api_keys ADD COLUMN previous_key_hash VARCHAR(255);
+ALTER TABLE api_keys ADD COLUMN previous_key_prefix VARCHAR(20);
+ALTER TABLE api_keys ADD COLUMN rotation_expires_at TIMESTAMPTZ;
+ALTER TABLE api_keys ADD COLUMN previous_last_used_at TIMESTAMPTZ;
+
+-- Auth looks keys up by hash; cover the previous secret during the overlap
+CREATE INDEX IF NOT EXISTS idx_api_keys_previous_hash
+    ON api_keys(previous_key_hash)
+    WHERE previous_key_hash IS NOT NULL;
+
+COMMENT ON COLUMN api_keys.previous_key_hash IS 'Hash of the pre-rotation secret, valid until rotation_expires_at';
+COMMENT ON COLUMN api_keys.rotation_expires_at IS 'End of the rotation overlap window; previous secret is rejected after this';
+COMMENT ON COLUMN api_keys.previous_last_used_at IS 'Last successful auth with the pre-rotation secret';
@@ -0,0 +1,91 @@
+-- OAuth token vault for upstream MCPs
+--
+-- Upstream MCPs like GitHub and Linear issue short-lived OAuth access
+-- tokens instead of static API keys. The vault stores one credential set
+-- per MCP instance; the proxy refreshes tokens automatically before they
+-- expire. Token material is AES-GCM encrypted with TOTP_ENCRYPTION_KEY,
+-- same envelope as 2FA secrets and SSH keys.
+
+CREATE TABLE IF NOT EXISTS mcp_oauth_credentials (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    -- One credential set per MCP instance
+    mcp_id UUID NOT NULL UNIQUE REFERENCES mcp_instances(id) ON DELETE CASCADE,
+
+    -- Provider token endpoint used for code exchange and refresh
+    token_endpoint TEXT NOT NULL,
+    client_id TEXT NOT NULL,
+    client_secret_encrypted TEXT,
+    client_secret_nonce TEXT,
+
+    access_token_encrypted TEXT NOT NULL,
+    access_token_nonce TEXT NOT NULL,
+    refresh_token_encrypted TEXT,
+    refresh_token_nonce TEXT,
+
+    token_type VARCHAR(32) NOT NULL DEFAULT 'Bearer',
+    scope TEXT,
+    -- NULL when the provider did not report a lifetime (token never
+    -- refreshed proactively in that case)
+    expires_at TIMESTAMPTZ,
+
+    connected_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_mcp_oauth_credentials_org
+    ON mcp_oauth_credentials(org_id);
+
+-- Short-lived CSRF state for in-flight authorization flows
+CREATE TABLE IF NOT EXISTS mcp_oauth_states (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Random token echoed back by the provider in the callback
+    state TEXT NOT NULL UNIQUE,
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    mcp_id UUID NOT NULL REFERENCES mcp_instances(id) ON DELETE CASCADE,
+
+    -- Captured at connect time so the callback needs no request body
+    token_endpoint TEXT NOT NULL,
+    client_id TEXT NOT NULL,
+    client_secret_encrypted TEXT,
+    client_secret_nonce TEXT,
+    redirect_uri TEXT NOT NULL,
+    scope TEXT,
+
+    created_by UUID REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL DEFAULT NOW() + INTERVAL '10 minutes'
+);
+
+CREATE INDEX IF NOT EXISTS idx_mcp_oauth_states_expires
+    ON mcp_oauth_states(expires_at);
+
+-- Row Level Security: service-role access only (API enforces org scoping)
+ALTER TABLE mcp_oauth_credentials ENABLE ROW LEVEL SECURITY;
+ALTER TABLE mcp_oauth_credentials FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY mcp_oauth_credentials_service_only ON mcp_oauth_credentials
+    FOR ALL TO postgres, service_role
+    USING (true)
+    WITH CHECK (true);
+
+CREATE POLICY mcp_oauth_credentials_block_users ON mcp_oauth_credentials
+    FOR ALL TO authenticated
+    USING (false);
+
+ALTER TABLE mcp_oauth_states ENABLE ROW LEVEL SECURITY;
+ALTER TABLE mcp_oauth_states FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY mcp_oauth_states_service_only ON mcp_oauth_states
+    FOR ALL TO postgres, service_role
+    USING (true)
+    WITH CHECK (true);
+
+CREATE POLICY mcp_oauth_states_block_users ON mcp_oauth_states
+    FOR ALL TO authenticated
+    USING (false);
+
+COMMENT ON TABLE mcp_oauth_credentials IS 'Encrypted per-MCP OAuth tokens, auto-refreshed by the proxy';
+COMMENT ON COLUMN mcp_oauth_credentials.expires_at IS 'Access token expiry; NULL when the provider reported none';
+COMMENT ON TABLE mcp_oauth_states IS 'Short-lived CSRF state for in-flight OAuth authorization flows';
@@ -0,0 +1,33 @@
+-- Granular analytics goal conditions
+--
+-- Goals previously matched only an event name or a URL regex. The new
+-- `conditions` JSONB column holds an array of condition objects - path
+-- regex/glob, event property equality/range, referrer substring, device
+-- type - that are ANDed together at ingestion (see the goals module for
+-- the schema). Existing goals are migrated from their legacy columns,
+-- which stay in place for dashboard backwards compatibility.
+
+ALTER TABLE analytics_goals
+    ADD COLUMN IF NOT EXISTS conditions JSONB;
+
+-- Backfill: express the legacy event_name / url_pattern criteria as
+-- condition objects so the ingestion matcher has one format to evaluate
+UPDATE analytics_goals
+SET conditions =
+    CASE WHEN event_name IS NOT NULL
+        THEN jsonb_build_array(jsonb_build_object('kind', 'event_name', 'equals', event_name))
+        ELSE '[]'::jsonb
+    END
+    ||
+    CASE WHEN url_pattern IS NOT NULL
+        THEN jsonb_build_array(jsonb_build_object('kind', 'path', 'regex', url_pattern))
+        ELSE '[]'::jsonb
+    END
+WHERE conditions IS NULL;
+
+-- One conversion per goal per session keeps re-fired events idempotent
+CREATE UNIQUE INDEX IF NOT EXISTS idx_analytics_conversions_goal_session
+    ON analytics_conversions(goal_id, session_id)
+    WHERE session_id IS NOT NULL;
+
+COMMENT ON COLUMN analytics_goals.conditions IS 'Array of condition objects (path/event_name/event_property/referrer_contains/device_type), ANDed at ingestion';
@@ -0,0 +1,8 @@
+-- Allow operators to override the hard-coded severity per alert type.
+-- NULL means "use the built-in default" (see AlertType::default_severity).
+ALTER TABLE alert_configurations
+    ADD COLUMN IF NOT EXISTS severity_override TEXT
+    CHECK (severity_override IS NULL OR severity_override IN ('low', 'medium', 'high', 'critical'));
+
+COMMENT ON COLUMN alert_configurations.severity_override IS
+    'Optional severity override for triggered alerts; NULL uses the built-in default for the alert type';
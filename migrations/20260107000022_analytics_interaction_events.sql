@@ -0,0 +1,51 @@
+-- Heatmap support: scroll-depth and click-position events.
+-- These are far higher volume than analytics_events, so they get their own
+-- table with sampling controls and a dedicated retention cap.
+
+CREATE TABLE analytics_interaction_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    session_id UUID REFERENCES analytics_sessions(id) ON DELETE CASCADE,
+    visitor_id UUID REFERENCES analytics_visitors(id) ON DELETE CASCADE,
+
+    url_path TEXT NOT NULL,
+    event_kind TEXT NOT NULL CHECK (event_kind IN ('scroll_depth', 'click')),
+
+    -- Scroll depth as a percentage of page height (scroll_depth events)
+    scroll_depth_pct SMALLINT CHECK (scroll_depth_pct BETWEEN 0 AND 100),
+
+    -- Click position as a percentage of page width/height (click events),
+    -- stored relative so clicks aggregate across viewport sizes
+    click_x_pct SMALLINT CHECK (click_x_pct BETWEEN 0 AND 100),
+    click_y_pct SMALLINT CHECK (click_y_pct BETWEEN 0 AND 100),
+
+    viewport_width INTEGER,
+    viewport_height INTEGER,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE analytics_interaction_events IS
+    'Sampled scroll-depth and click-position events powering heatmap aggregations; capped by interaction_retention_days';
+
+CREATE INDEX idx_analytics_interaction_path
+    ON analytics_interaction_events(url_path, event_kind, created_at DESC);
+CREATE INDEX idx_analytics_interaction_cleanup
+    ON analytics_interaction_events(created_at);
+
+-- Row-Level Security (service-only table, same as the other analytics tables)
+ALTER TABLE analytics_interaction_events ENABLE ROW LEVEL SECURITY;
+ALTER TABLE analytics_interaction_events FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY analytics_interaction_events_backend ON analytics_interaction_events
+    FOR ALL TO postgres USING (true) WITH CHECK (true);
+
+-- Collection controls: disabled by default, with per-session sampling
+ALTER TABLE analytics_settings
+    ADD COLUMN IF NOT EXISTS interaction_events_enabled BOOLEAN NOT NULL DEFAULT false,
+    ADD COLUMN IF NOT EXISTS interaction_sample_rate DOUBLE PRECISION NOT NULL DEFAULT 0.1
+        CHECK (interaction_sample_rate >= 0.0 AND interaction_sample_rate <= 1.0),
+    ADD COLUMN IF NOT EXISTS interaction_retention_days INTEGER NOT NULL DEFAULT 30
+        CHECK (interaction_retention_days >= 1);
+
+COMMENT ON COLUMN analytics_settings.interaction_sample_rate IS
+    'Fraction of sessions whose scroll/click events are recorded (sampled per session, not per event)';
@@ -0,0 +1,27 @@
+-- Alerting for flapping MCP health checks
+--
+-- The api crate now runs a background health monitor sweep (see
+-- mcp::health_monitor) that re-tests every active MCP on an interval and
+-- records each transition between healthy and unhealthy as an alert
+-- event. An MCP that keeps flipping raises an alert once the configured
+-- number of transitions lands inside the window.
+
+ALTER TABLE security_alerts DROP CONSTRAINT IF EXISTS security_alerts_alert_type_check;
+ALTER TABLE security_alerts ADD CONSTRAINT security_alerts_alert_type_check
+    CHECK (alert_type IN (
+        'brute_force_attack',
+        'privilege_escalation',
+        'data_exfiltration',
+        'configuration_change',
+        'rate_limit_violation',
+        'suspicious_activity',
+        'authentication_anomaly',
+        'malware_detected',
+        'upstream_response_truncated',
+        'mcp_health_flapping'
+    ));
+
+INSERT INTO alert_configurations (alert_type, threshold_count, threshold_window_seconds, cooldown_seconds)
+VALUES
+    ('mcp_health_flapping', 4, 3600, 7200)  -- 4 transitions in an hour, 2h cooldown
+ON CONFLICT (alert_type) DO NOTHING;
@@ -0,0 +1,37 @@
+-- Per-org sender domains for white-label email
+--
+-- White-label customers want ticket and invite emails sent from their own
+-- domain instead of the platform address. Each org can register one sender
+-- domain; the API generates the DKIM/SPF records to publish, verifies them
+-- over DNS (manually via the verify route and in a background poll), and
+-- the email module picks the org's from-address once verified, falling
+-- back to the platform domain when delivery from the custom sender fails.
+
+CREATE TABLE IF NOT EXISTS org_email_domains (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL UNIQUE REFERENCES organizations(id) ON DELETE CASCADE,
+    domain TEXT NOT NULL,
+    -- Full sender address on the domain, e.g. "support@acme.com"
+    from_address TEXT NOT NULL,
+    -- DKIM selector published as <selector>._domainkey.<domain>
+    dkim_selector TEXT NOT NULL DEFAULT 'plexmcp',
+    status TEXT NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'verified', 'failed')),
+    verification_attempts INTEGER NOT NULL DEFAULT 0,
+    last_verification_at TIMESTAMPTZ,
+    verified_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE org_email_domains IS 'Per-org sender domains (DKIM/SPF) for white-label outbound email';
+COMMENT ON COLUMN org_email_domains.from_address IS 'Sender address used once the domain is verified; must be on the registered domain';
+COMMENT ON COLUMN org_email_domains.dkim_selector IS 'DKIM selector; the CNAME is published at <selector>._domainkey.<domain>';
+COMMENT ON COLUMN org_email_domains.status IS 'pending until DNS checks pass; failed after an unsuccessful check (still polled)';
+
+-- Service-only access; tenant scoping is enforced in the API layer
+ALTER TABLE org_email_domains ENABLE ROW LEVEL SECURITY;
+ALTER TABLE org_email_domains FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY org_email_domains_backend ON org_email_domains
+    FOR ALL TO postgres USING (true) WITH CHECK (true);
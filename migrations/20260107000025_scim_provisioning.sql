@@ -0,0 +1,56 @@
+-- SCIM 2.0 provisioning for enterprise orgs
+--
+-- Enterprise customers provision and deprovision team members from their
+-- identity provider (Okta, Azure AD) against /scim/v2. Requests
+-- authenticate with an org-issued bearer token; deprovisioning is a soft
+-- deactivation (organization_members.status = 'suspended') so nothing
+-- the member created is destroyed.
+
+CREATE TABLE IF NOT EXISTS scim_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+
+    -- Human-readable purpose, e.g. "Okta provisioning"
+    name TEXT NOT NULL,
+
+    -- SHA-256 of the full token; the token itself is shown once at creation
+    token_hash TEXT NOT NULL UNIQUE,
+    -- Display prefix, e.g. "pmcps_01abc..."
+    token_prefix TEXT NOT NULL,
+
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+
+    last_used_at TIMESTAMPTZ,
+    request_count BIGINT NOT NULL DEFAULT 0,
+
+    revoked_at TIMESTAMPTZ,
+    revoked_by UUID REFERENCES users(id) ON DELETE SET NULL,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_scim_tokens_org ON scim_tokens(org_id);
+
+-- IdP-side identifier for the membership (SCIM externalId)
+ALTER TABLE organization_members
+ADD COLUMN IF NOT EXISTS scim_external_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_org_members_scim_external
+ON organization_members(org_id, scim_external_id)
+WHERE scim_external_id IS NOT NULL;
+
+-- Row Level Security: service-role access only (API enforces org scoping)
+ALTER TABLE scim_tokens ENABLE ROW LEVEL SECURITY;
+ALTER TABLE scim_tokens FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY scim_tokens_service_only ON scim_tokens
+    FOR ALL TO postgres, service_role
+    USING (true)
+    WITH CHECK (true);
+
+CREATE POLICY scim_tokens_block_users ON scim_tokens
+    FOR ALL TO authenticated
+    USING (false);
+
+COMMENT ON TABLE scim_tokens IS 'Org-issued bearer tokens for SCIM 2.0 provisioning from identity providers';
+COMMENT ON COLUMN organization_members.scim_external_id IS 'SCIM externalId assigned by the provisioning identity provider';
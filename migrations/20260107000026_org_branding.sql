@@ -0,0 +1,41 @@
+-- White-label brand configuration per org
+--
+-- White-label tiers theme the dashboard served on their domain: logo,
+-- color palette, product name, support email, and a hidden "powered by"
+-- footer. The frontend fetches this through the public host-resolution
+-- endpoint and themes itself per domain.
+
+CREATE TABLE IF NOT EXISTS org_branding (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL UNIQUE REFERENCES organizations(id) ON DELETE CASCADE,
+
+    -- Product name shown instead of the platform name
+    product_name VARCHAR(100),
+    support_email VARCHAR(255),
+
+    -- Hex colors, e.g. '#1a73e8'
+    primary_color VARCHAR(7),
+    accent_color VARCHAR(7),
+    background_color VARCHAR(7),
+
+    -- Logo lives in the shared storage backend; served via signed URLs
+    logo_storage_key TEXT,
+    logo_content_type TEXT,
+
+    hide_powered_by BOOLEAN NOT NULL DEFAULT FALSE,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Row Level Security: backend-only access (API enforces org scoping)
+ALTER TABLE org_branding ENABLE ROW LEVEL SECURITY;
+ALTER TABLE org_branding FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY org_branding_backend ON org_branding
+    FOR ALL TO postgres
+    USING (true)
+    WITH CHECK (true);
+
+COMMENT ON TABLE org_branding IS 'White-label brand configuration (logo, palette, product name) per org';
+COMMENT ON COLUMN org_branding.hide_powered_by IS 'Hide the "powered by" footer on white-label dashboards';
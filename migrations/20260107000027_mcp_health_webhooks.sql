@@ -0,0 +1,41 @@
+-- Per-MCP webhooks for health and circuit breaker transitions
+--
+-- Customers running automation want an immediate callback when an MCP
+-- goes down or its circuit breaker opens, without polling the health
+-- endpoints. One webhook per MCP; payloads are HMAC-SHA256 signed with
+-- the webhook secret. Repeated delivery failures disable the webhook.
+
+CREATE TABLE IF NOT EXISTS mcp_health_webhooks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    mcp_id UUID NOT NULL UNIQUE REFERENCES mcp_instances(id) ON DELETE CASCADE,
+
+    url TEXT NOT NULL,
+    -- Shared secret for the X-PlexMCP-Signature HMAC
+    secret TEXT NOT NULL,
+
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    -- Set when delivery failures disable the webhook automatically
+    disabled_reason TEXT,
+
+    consecutive_failures INTEGER NOT NULL DEFAULT 0,
+    last_success_at TIMESTAMPTZ,
+    last_failure_at TIMESTAMPTZ,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_mcp_health_webhooks_org ON mcp_health_webhooks(org_id);
+
+-- Row Level Security: backend-only access (API enforces org scoping)
+ALTER TABLE mcp_health_webhooks ENABLE ROW LEVEL SECURITY;
+ALTER TABLE mcp_health_webhooks FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY mcp_health_webhooks_backend ON mcp_health_webhooks
+    FOR ALL TO postgres
+    USING (true)
+    WITH CHECK (true);
+
+COMMENT ON TABLE mcp_health_webhooks IS 'Per-MCP callbacks for health and circuit breaker state transitions';
+COMMENT ON COLUMN mcp_health_webhooks.secret IS 'HMAC-SHA256 key for the X-PlexMCP-Signature header';
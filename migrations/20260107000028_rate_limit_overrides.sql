@@ -0,0 +1,37 @@
+-- Per-org and per-API-key rate limit overrides
+--
+-- The in-memory RateLimiter uses the rate_limit_rpm stored on each API
+-- key plus tier defaults. This table lets platform admins override those
+-- values for a whole org (api_key_id NULL) or a single key. The API
+-- process reloads the table periodically and on every admin change, so
+-- overrides apply without a restart.
+
+CREATE TABLE IF NOT EXISTS rate_limit_overrides (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    -- NULL = org-wide override; set = override for one API key
+    api_key_id UUID UNIQUE REFERENCES api_keys(id) ON DELETE CASCADE,
+    requests_per_minute INTEGER NOT NULL CHECK (requests_per_minute BETWEEN 1 AND 100000),
+
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- At most one org-wide override per org
+CREATE UNIQUE INDEX IF NOT EXISTS idx_rate_limit_overrides_org_wide
+    ON rate_limit_overrides(org_id) WHERE api_key_id IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_rate_limit_overrides_org ON rate_limit_overrides(org_id);
+
+-- Row Level Security: backend-only access (admin API enforces authorization)
+ALTER TABLE rate_limit_overrides ENABLE ROW LEVEL SECURITY;
+ALTER TABLE rate_limit_overrides FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY rate_limit_overrides_backend ON rate_limit_overrides
+    FOR ALL TO postgres
+    USING (true)
+    WITH CHECK (true);
+
+COMMENT ON TABLE rate_limit_overrides IS 'Admin-managed rate limit overrides, hot-reloaded by the API process';
+COMMENT ON COLUMN rate_limit_overrides.api_key_id IS 'NULL for an org-wide override, set for a per-key override';
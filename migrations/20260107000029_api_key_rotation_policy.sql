@@ -0,0 +1,22 @@
+-- API key rotation policy and expiry reminder tracking
+--
+-- Orgs can force periodic key rotation by setting a maximum key age.
+-- Keys older than the policy (measured from the last rotation, or
+-- creation if never rotated) are rejected by the proxy auth path. The
+-- worker emails reminders at 30/7/1 days before a key's effective
+-- expiry; expiry_reminder_threshold_days records the smallest threshold
+-- already sent so each stage fires exactly once.
+
+ALTER TABLE organizations
+    ADD COLUMN IF NOT EXISTS max_api_key_age_days INTEGER
+        CHECK (max_api_key_age_days BETWEEN 1 AND 3650);
+
+ALTER TABLE api_keys
+    ADD COLUMN IF NOT EXISTS last_rotated_at TIMESTAMPTZ;
+
+ALTER TABLE api_keys
+    ADD COLUMN IF NOT EXISTS expiry_reminder_threshold_days INTEGER;
+
+COMMENT ON COLUMN organizations.max_api_key_age_days IS 'Org rotation policy: keys older than this are treated as expired (NULL = no policy)';
+COMMENT ON COLUMN api_keys.last_rotated_at IS 'When the key secret was last rotated; key age is measured from here, falling back to created_at';
+COMMENT ON COLUMN api_keys.expiry_reminder_threshold_days IS 'Smallest expiry reminder stage (30/7/1 days) already emailed; reset on rotation';
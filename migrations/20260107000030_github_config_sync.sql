@@ -0,0 +1,68 @@
+-- Config-as-code GitHub sync for MCP definitions
+--
+-- Orgs can point a GitHub repo at PlexMCP: JSON/YAML files under a
+-- configured path define MCP instances, and a push webhook triggers a
+-- sync. Each push produces a github_sync_runs row recording the computed
+-- diff; orgs with auto_apply enabled apply immediately, others review
+-- the run in the dashboard before applying. Commit statuses are reported
+-- back to GitHub when a token is configured.
+
+CREATE TABLE IF NOT EXISTS github_sync_configs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL UNIQUE REFERENCES organizations(id) ON DELETE CASCADE,
+    -- "owner/name"
+    repo TEXT NOT NULL,
+    branch TEXT NOT NULL DEFAULT 'main',
+    -- Only files under this prefix are considered MCP definitions
+    path_prefix TEXT NOT NULL DEFAULT 'mcps/',
+    -- Shared secret for X-Hub-Signature-256 verification
+    webhook_secret TEXT NOT NULL,
+    -- Optional token for private repos and commit status reporting
+    github_token TEXT,
+    auto_apply BOOLEAN NOT NULL DEFAULT false,
+    enabled BOOLEAN NOT NULL DEFAULT true,
+    last_synced_sha TEXT,
+
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS github_sync_runs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    commit_sha TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'applied', 'awaiting_review', 'failed', 'rejected')),
+    -- Computed change set: [{action, name, path, definition?}, ...]
+    diff JSONB NOT NULL DEFAULT '[]'::jsonb,
+    error TEXT,
+    reviewed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    reviewed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_github_sync_runs_org
+    ON github_sync_runs(org_id, created_at DESC);
+
+-- Row Level Security: backend-only access (API enforces org scoping)
+ALTER TABLE github_sync_configs ENABLE ROW LEVEL SECURITY;
+ALTER TABLE github_sync_configs FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY github_sync_configs_backend ON github_sync_configs
+    FOR ALL TO postgres
+    USING (true)
+    WITH CHECK (true);
+
+ALTER TABLE github_sync_runs ENABLE ROW LEVEL SECURITY;
+ALTER TABLE github_sync_runs FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY github_sync_runs_backend ON github_sync_runs
+    FOR ALL TO postgres
+    USING (true)
+    WITH CHECK (true);
+
+COMMENT ON TABLE github_sync_configs IS 'Per-org GitHub repo connection for config-as-code MCP definitions';
+COMMENT ON TABLE github_sync_runs IS 'One row per synced push: computed diff, review state, and outcome';
+COMMENT ON COLUMN github_sync_configs.path_prefix IS 'Repo path prefix containing MCP definition files (.json/.yaml/.yml)';
+COMMENT ON COLUMN github_sync_runs.diff IS 'Change set computed from the push: create/update/delete per definition file';
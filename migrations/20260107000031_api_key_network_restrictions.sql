@@ -0,0 +1,15 @@
+-- API key network restrictions: CIDR allowlists and allowed origins
+--
+-- Keys can be pinned to source IP ranges and/or browser origins. Both
+-- lists are enforced in the MCP proxy auth path; NULL (or empty) means
+-- unrestricted. Violations are rejected with 401 and recorded as
+-- suspicious-activity security events.
+
+ALTER TABLE api_keys
+    ADD COLUMN IF NOT EXISTS allowed_cidrs TEXT[];
+
+ALTER TABLE api_keys
+    ADD COLUMN IF NOT EXISTS allowed_origins TEXT[];
+
+COMMENT ON COLUMN api_keys.allowed_cidrs IS 'CIDR ranges (IPv4/IPv6) the key may be used from; NULL = any address';
+COMMENT ON COLUMN api_keys.allowed_origins IS 'Origins (scheme://host[:port], *.wildcard hosts allowed) the key may be used from; NULL = any origin';
@@ -0,0 +1,14 @@
+-- Replay dedup for the local audit/usage write-ahead buffer
+--
+-- When Postgres is briefly unavailable, failed audit and usage writes are
+-- appended to a per-instance disk buffer and replayed on recovery. Each
+-- buffered entry carries a UUID; replay inserts it here in the same
+-- transaction as the replayed write, so a crash mid-replay never applies
+-- an entry twice. Rows older than a week are pruned during replay.
+
+CREATE TABLE IF NOT EXISTS audit_buffer_replayed (
+    entry_id UUID PRIMARY KEY,
+    replayed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE audit_buffer_replayed IS 'Entry IDs from the disk audit buffer already replayed into Postgres (dedup on recovery)';
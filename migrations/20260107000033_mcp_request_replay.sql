@@ -0,0 +1,11 @@
+-- MCP request replay support
+--
+-- Stores the original JSON-RPC payload (method + params) alongside each
+-- mcp_request_log entry so org admins can re-send a logged request against
+-- the upstream from the debugging console. Payloads over the capture limit
+-- are not stored (the column stays NULL and the entry is not replayable).
+
+ALTER TABLE mcp_request_log
+    ADD COLUMN IF NOT EXISTS request_payload JSONB;
+
+COMMENT ON COLUMN mcp_request_log.request_payload IS 'Original JSON-RPC payload ({"method", "params"}) captured for replay; NULL when the request predates capture or exceeded the size limit';
@@ -0,0 +1,26 @@
+-- Unified background scheduler state
+--
+-- One row per registered job. Instances claim a cron slot by updating
+-- last_scheduled_for + locked_until in a single statement, so each slot
+-- runs on exactly one instance even with multiple API replicas. Run
+-- outcomes are recorded for admin visibility (GET /admin/scheduler).
+
+CREATE TABLE IF NOT EXISTS scheduler_jobs (
+    job_name TEXT PRIMARY KEY,
+    -- The cron slot most recently claimed (dedup key across instances)
+    last_scheduled_for TIMESTAMPTZ,
+    last_run_at TIMESTAMPTZ,
+    -- 'success' or 'error' from the most recent completed run
+    last_status TEXT,
+    last_error TEXT,
+    last_duration_ms INTEGER,
+    -- Instance currently holding the job (NULL when idle)
+    locked_by TEXT,
+    -- Claim expiry; a crashed instance's lock is re-claimable after this
+    locked_until TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE scheduler_jobs IS 'Cross-instance locking and run history for the unified background scheduler';
+COMMENT ON COLUMN scheduler_jobs.last_scheduled_for IS 'Cron slot most recently claimed; prevents two instances running the same slot';
+COMMENT ON COLUMN scheduler_jobs.locked_until IS 'Claim expiry - guards against instances dying mid-run';
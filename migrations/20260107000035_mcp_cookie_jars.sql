@@ -0,0 +1,33 @@
+-- Cookie jars for upstream MCPs using cookie/session auth
+--
+-- Some upstream MCPs authenticate with session cookies obtained from a
+-- login endpoint rather than static headers. One jar is stored per MCP
+-- instance; the proxy logs in automatically when the jar is missing or
+-- expired. Cookie material is AES-GCM encrypted with TOTP_ENCRYPTION_KEY,
+-- same envelope as 2FA secrets and OAuth tokens.
+
+CREATE TABLE IF NOT EXISTS mcp_cookie_jars (
+    -- One jar per MCP instance
+    mcp_id UUID PRIMARY KEY REFERENCES mcp_instances(id) ON DELETE CASCADE,
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+
+    cookies_encrypted TEXT NOT NULL,
+    cookies_nonce TEXT NOT NULL,
+
+    -- NULL when the login response carried no cookie lifetime and the MCP
+    -- config sets no session_ttl_secs (jar then lives until invalidated)
+    expires_at TIMESTAMPTZ,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_mcp_cookie_jars_org
+    ON mcp_cookie_jars(org_id);
+
+COMMENT ON TABLE mcp_cookie_jars IS
+    'Encrypted session cookies for upstream MCPs using cookie-based auth';
+COMMENT ON COLUMN mcp_cookie_jars.cookies_encrypted IS
+    'AES-GCM encrypted Cookie header value (name=value pairs joined with "; ")';
+COMMENT ON COLUMN mcp_cookie_jars.expires_at IS
+    'Session expiry derived from Set-Cookie Max-Age or the configured session_ttl_secs';
@@ -0,0 +1,52 @@
+-- Second-admin approval for destructive security actions
+--
+-- Disabling a user's 2FA or an alert rule is a security degradation, so
+-- these actions now capture a mandatory reason and can optionally be
+-- queued for sign-off by a second platform admin. A pending approval
+-- records the action, target and reason; a different admin approves
+-- (which executes the action) or rejects it. Pending approvals expire
+-- after 24 hours.
+
+CREATE TABLE IF NOT EXISTS security_action_approvals (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+
+    -- Action identifier: 'disable_user_2fa' or 'disable_alert_config'
+    action TEXT NOT NULL,
+    target_type TEXT NOT NULL,
+    -- User UUID for 2FA, alert_type string for alert configs
+    target_id TEXT NOT NULL,
+
+    -- Mandatory justification, copied into the audit log on execution
+    reason TEXT NOT NULL,
+    -- Action parameters carried to execution (e.g. re_enable_after_seconds)
+    params JSONB NOT NULL DEFAULT '{}',
+
+    requested_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- pending | approved | rejected | expired
+    status TEXT NOT NULL DEFAULT 'pending',
+    decided_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    decided_at TIMESTAMPTZ,
+    decision_notes TEXT,
+
+    expires_at TIMESTAMPTZ NOT NULL DEFAULT NOW() + INTERVAL '24 hours',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_security_action_approvals_status
+    ON security_action_approvals(status, created_at DESC);
+
+-- Automatic re-enable timer for disabled alert rules: a rule disabled
+-- with re_enable_after_seconds comes back on its own, so a rushed
+-- operator can't silence alerting permanently by accident.
+ALTER TABLE alert_configurations
+    ADD COLUMN IF NOT EXISTS re_enable_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS disabled_reason TEXT;
+
+COMMENT ON TABLE security_action_approvals IS
+    'Pending second-admin approvals for destructive security actions (2FA disable, alert rule disable)';
+COMMENT ON COLUMN security_action_approvals.target_id IS
+    'User UUID for disable_user_2fa, alert_type string for disable_alert_config';
+COMMENT ON COLUMN alert_configurations.re_enable_at IS
+    'When set, the scheduler re-enables this disabled rule automatically at this time';
+COMMENT ON COLUMN alert_configurations.disabled_reason IS
+    'Mandatory justification captured when the rule was disabled';
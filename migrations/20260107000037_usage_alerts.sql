@@ -0,0 +1,42 @@
+-- Customer-facing usage alerts
+--
+-- Orgs define their own thresholds against the billing period's included
+-- request quota ("notify me at 80% of my included calls"). A worker job
+-- evaluates them against metered usage and delivers via email and/or
+-- webhook. last_triggered_period_start re-arms an alert when a new
+-- billing period begins; cooldown_hours stops repeat firing inside one.
+
+CREATE TABLE IF NOT EXISTS usage_alerts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    threshold_percent INTEGER NOT NULL CHECK (threshold_percent BETWEEN 1 AND 100),
+
+    -- Delivery channels (at least one enforced by the API)
+    notify_email BOOLEAN NOT NULL DEFAULT TRUE,
+    webhook_url TEXT,
+
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    cooldown_hours INTEGER NOT NULL DEFAULT 24 CHECK (cooldown_hours BETWEEN 1 AND 168),
+    last_triggered_at TIMESTAMPTZ,
+    last_triggered_period_start TIMESTAMPTZ,
+
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_usage_alerts_org ON usage_alerts(org_id);
+CREATE INDEX IF NOT EXISTS idx_usage_alerts_enabled ON usage_alerts(enabled) WHERE enabled;
+
+-- Row Level Security: backend-only access (API enforces org scoping)
+ALTER TABLE usage_alerts ENABLE ROW LEVEL SECURITY;
+ALTER TABLE usage_alerts FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY usage_alerts_backend ON usage_alerts
+    FOR ALL TO postgres
+    USING (true)
+    WITH CHECK (true);
+
+COMMENT ON TABLE usage_alerts IS 'Org-defined usage thresholds evaluated by the worker against metered usage';
+COMMENT ON COLUMN usage_alerts.last_triggered_period_start IS 'Billing period the alert last fired in; a new period re-arms it';
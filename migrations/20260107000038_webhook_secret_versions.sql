@@ -0,0 +1,11 @@
+-- Webhook secret versioning for the signature verification helper
+--
+-- When a webhook secret is rotated the old one is kept so
+-- POST /webhooks/verify-sample can tell a customer whether a signature
+-- they received was produced with the current or the previous secret
+-- (the usual cause of "verification suddenly broke" tickets).
+
+ALTER TABLE mcp_health_webhooks ADD COLUMN IF NOT EXISTS previous_secret TEXT;
+ALTER TABLE mcp_health_webhooks ADD COLUMN IF NOT EXISTS secret_rotated_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN mcp_health_webhooks.previous_secret IS 'Secret in effect before the last rotation, kept for signature debugging';
@@ -0,0 +1,67 @@
+-- Outbound webhooks for org events
+--
+-- Org admins register endpoints subscribed to platform events
+-- (mcp.status_changed, invoice.paid, member.invited, ticket.replied).
+-- Emitting an event fans out one delivery row per subscribed endpoint;
+-- the worker posts them with HMAC signing and exponential backoff,
+-- and the delivery log backs the debugging endpoint.
+
+CREATE TABLE IF NOT EXISTS org_webhook_endpoints (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    url TEXT NOT NULL,
+    -- Shared secret for the X-PlexMCP-Signature HMAC
+    secret TEXT NOT NULL,
+    description TEXT,
+    -- Subscribed event types (subset of the supported event list)
+    events TEXT[] NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_org_webhook_endpoints_org
+    ON org_webhook_endpoints(org_id, created_at DESC);
+
+CREATE TABLE IF NOT EXISTS org_webhook_deliveries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    endpoint_id UUID NOT NULL REFERENCES org_webhook_endpoints(id) ON DELETE CASCADE,
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    event_type TEXT NOT NULL,
+    payload JSONB NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'delivered', 'failed')),
+    attempts INT NOT NULL DEFAULT 0,
+    max_attempts INT NOT NULL DEFAULT 5,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Last HTTP status received, if the endpoint responded at all
+    response_status INT,
+    last_error TEXT,
+    delivered_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Worker claim query: due pending deliveries
+CREATE INDEX IF NOT EXISTS idx_org_webhook_deliveries_due
+    ON org_webhook_deliveries(next_attempt_at)
+    WHERE status = 'pending';
+
+-- Delivery log endpoint: recent deliveries per endpoint
+CREATE INDEX IF NOT EXISTS idx_org_webhook_deliveries_endpoint
+    ON org_webhook_deliveries(endpoint_id, created_at DESC);
+
+ALTER TABLE org_webhook_endpoints ENABLE ROW LEVEL SECURITY;
+ALTER TABLE org_webhook_endpoints FORCE ROW LEVEL SECURITY;
+CREATE POLICY org_webhook_endpoints_backend ON org_webhook_endpoints
+    FOR ALL TO postgres USING (true) WITH CHECK (true);
+
+ALTER TABLE org_webhook_deliveries ENABLE ROW LEVEL SECURITY;
+ALTER TABLE org_webhook_deliveries FORCE ROW LEVEL SECURITY;
+CREATE POLICY org_webhook_deliveries_backend ON org_webhook_deliveries
+    FOR ALL TO postgres USING (true) WITH CHECK (true);
+
+COMMENT ON TABLE org_webhook_endpoints IS 'Org-registered endpoints for outbound event webhooks';
+COMMENT ON COLUMN org_webhook_endpoints.events IS 'Subscribed event types, e.g. mcp.status_changed';
+COMMENT ON TABLE org_webhook_deliveries IS 'Per-endpoint delivery attempts with retry state';
+COMMENT ON COLUMN org_webhook_deliveries.next_attempt_at IS 'When the worker should (re)attempt delivery';
@@ -0,0 +1,66 @@
+-- API deprecation and sunset registry
+--
+-- Platform admins register deprecated routes; the API serves
+-- Deprecation/Sunset/Link headers on matching requests, tracks which
+-- orgs still call them (daily rollup), and a worker job emails affected
+-- orgs once per deprecation.
+
+CREATE TABLE IF NOT EXISTS api_deprecations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- HTTP method, or '*' for all methods on the path
+    method TEXT NOT NULL,
+    -- Route template with :param segments, e.g. /api/v1/mcps/:mcp_id/test
+    path_pattern TEXT NOT NULL,
+    -- When the route stops working (RFC 8594 Sunset header); NULL while
+    -- deprecated with no removal date yet
+    sunset_at TIMESTAMPTZ,
+    -- Replacement route or migration doc, served in a Link header
+    successor TEXT,
+    note TEXT,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(method, path_pattern)
+);
+
+-- Daily per-org hit counts against deprecated routes
+CREATE TABLE IF NOT EXISTS api_deprecation_usage (
+    deprecation_id UUID NOT NULL REFERENCES api_deprecations(id) ON DELETE CASCADE,
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    hits BIGINT NOT NULL DEFAULT 0,
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (deprecation_id, org_id, day)
+);
+
+-- One notification per org per deprecation
+CREATE TABLE IF NOT EXISTS api_deprecation_notifications (
+    deprecation_id UUID NOT NULL REFERENCES api_deprecations(id) ON DELETE CASCADE,
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    notified_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (deprecation_id, org_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_deprecation_usage_org
+    ON api_deprecation_usage(org_id, day DESC);
+
+ALTER TABLE api_deprecations ENABLE ROW LEVEL SECURITY;
+ALTER TABLE api_deprecations FORCE ROW LEVEL SECURITY;
+CREATE POLICY api_deprecations_backend ON api_deprecations
+    FOR ALL TO postgres USING (true) WITH CHECK (true);
+
+ALTER TABLE api_deprecation_usage ENABLE ROW LEVEL SECURITY;
+ALTER TABLE api_deprecation_usage FORCE ROW LEVEL SECURITY;
+CREATE POLICY api_deprecation_usage_backend ON api_deprecation_usage
+    FOR ALL TO postgres USING (true) WITH CHECK (true);
+
+ALTER TABLE api_deprecation_notifications ENABLE ROW LEVEL SECURITY;
+ALTER TABLE api_deprecation_notifications FORCE ROW LEVEL SECURITY;
+CREATE POLICY api_deprecation_notifications_backend ON api_deprecation_notifications
+    FOR ALL TO postgres USING (true) WITH CHECK (true);
+
+COMMENT ON TABLE api_deprecations IS 'Registry of deprecated API routes with sunset metadata';
+COMMENT ON COLUMN api_deprecations.path_pattern IS 'Route template; : segments match any one path segment';
+COMMENT ON TABLE api_deprecation_usage IS 'Daily per-org request counts against deprecated routes';
+COMMENT ON TABLE api_deprecation_notifications IS 'Orgs already emailed about a deprecation';
@@ -0,0 +1,49 @@
+-- Temporary rate limit exemptions with mandatory expiry
+--
+-- Support sometimes needs to lift limits for a customer during a
+-- migration or backfill. An exemption either multiplies the effective
+-- requests-per-minute (kind 'multiplier') or bypasses the limit entirely
+-- (kind 'bypass'), for a whole org (api_key_id NULL) or a single key.
+-- Every exemption requires a reason and an expiry; the RateLimiter
+-- re-checks expires_at on every lookup so reversion is automatic even
+-- between periodic reloads. Revocation is a soft delete (revoked_at) so
+-- the row remains for audit.
+
+CREATE TABLE IF NOT EXISTS rate_limit_exemptions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    -- NULL = org-wide exemption; set = exemption for one API key
+    api_key_id UUID REFERENCES api_keys(id) ON DELETE CASCADE,
+    kind VARCHAR(20) NOT NULL CHECK (kind IN ('multiplier', 'bypass')),
+    -- Required and > 1 for 'multiplier', NULL for 'bypass'
+    multiplier REAL CHECK (
+        (kind = 'multiplier' AND multiplier > 1)
+        OR (kind = 'bypass' AND multiplier IS NULL)
+    ),
+    reason TEXT NOT NULL CHECK (length(trim(reason)) > 0),
+    expires_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ,
+
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_rate_limit_exemptions_org ON rate_limit_exemptions(org_id);
+
+-- The loader only wants currently-active rows
+CREATE INDEX IF NOT EXISTS idx_rate_limit_exemptions_active
+    ON rate_limit_exemptions(expires_at) WHERE revoked_at IS NULL;
+
+-- Row Level Security: backend-only access (admin API enforces authorization)
+ALTER TABLE rate_limit_exemptions ENABLE ROW LEVEL SECURITY;
+ALTER TABLE rate_limit_exemptions FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY rate_limit_exemptions_backend ON rate_limit_exemptions
+    FOR ALL TO postgres
+    USING (true)
+    WITH CHECK (true);
+
+COMMENT ON TABLE rate_limit_exemptions IS 'Temporary admin-granted rate limit multipliers/bypasses with mandatory expiry and reason';
+COMMENT ON COLUMN rate_limit_exemptions.api_key_id IS 'NULL for an org-wide exemption, set for a per-key exemption';
+COMMENT ON COLUMN rate_limit_exemptions.multiplier IS 'Factor applied to the effective limit; NULL when kind is bypass';
+COMMENT ON COLUMN rate_limit_exemptions.revoked_at IS 'Soft delete: set when an admin revokes the exemption early';
@@ -0,0 +1,43 @@
+-- Session-scoped context storage for MCP clients
+--
+-- Client workflows often need small amounts of state between tool calls
+-- (cursor tokens, workspace selection). The proxy exposes this table
+-- through the special context/get, context/set, and context/delete
+-- JSON-RPC methods on the unified endpoint. Values are AES-GCM encrypted
+-- at rest (same envelope as 2FA secrets and OAuth tokens) and every
+-- entry carries a TTL; expired rows are pruned opportunistically on
+-- writes. Rows are scoped to one API key within one org, so a key can
+-- never read another key's context.
+
+CREATE TABLE IF NOT EXISTS mcp_session_context (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    api_key_id UUID NOT NULL REFERENCES api_keys(id) ON DELETE CASCADE,
+    context_key VARCHAR(128) NOT NULL,
+    -- AES-GCM ciphertext and nonce, both base64
+    value_encrypted TEXT NOT NULL,
+    value_nonce TEXT NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    UNIQUE(api_key_id, context_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_mcp_session_context_expiry
+    ON mcp_session_context(expires_at);
+
+-- Row Level Security: backend-only access (the proxy scopes every query
+-- to the authenticated API key)
+ALTER TABLE mcp_session_context ENABLE ROW LEVEL SECURITY;
+ALTER TABLE mcp_session_context FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY mcp_session_context_backend ON mcp_session_context
+    FOR ALL TO postgres
+    USING (true)
+    WITH CHECK (true);
+
+COMMENT ON TABLE mcp_session_context IS 'Per-API-key KV store for MCP client session state, encrypted at rest with TTL';
+COMMENT ON COLUMN mcp_session_context.context_key IS 'Client-chosen key, unique per API key';
+COMMENT ON COLUMN mcp_session_context.expires_at IS 'Entries past this point read as absent and are pruned on writes';
@@ -0,0 +1,38 @@
+-- Duplicate request rollups for client misbehaviour diagnostics
+--
+-- The proxy detects identical method+params re-sent by the same API key
+-- within a short window (in-memory, per instance). Each detected
+-- duplicate bumps a daily rollup row here, keyed by the request hash, so
+-- the dashboard can show customers which clients are re-sending calls
+-- and which tools they hammer. Raw request bodies are never stored -
+-- only the hash, the method, and the tool name.
+
+CREATE TABLE IF NOT EXISTS mcp_duplicate_requests (
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    api_key_id UUID NOT NULL REFERENCES api_keys(id) ON DELETE CASCADE,
+    method VARCHAR(100) NOT NULL,
+    -- Prefixed tool name for tools/call, NULL for other methods
+    tool_name VARCHAR(255),
+    -- SHA-256 of method + serialized params
+    request_hash VARCHAR(64) NOT NULL,
+    day DATE NOT NULL DEFAULT CURRENT_DATE,
+    duplicate_count BIGINT NOT NULL DEFAULT 0,
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (api_key_id, request_hash, day)
+);
+
+CREATE INDEX IF NOT EXISTS idx_mcp_duplicate_requests_org_day
+    ON mcp_duplicate_requests(org_id, day);
+
+-- Row Level Security: backend-only access (diagnostics API scopes by org)
+ALTER TABLE mcp_duplicate_requests ENABLE ROW LEVEL SECURITY;
+ALTER TABLE mcp_duplicate_requests FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY mcp_duplicate_requests_backend ON mcp_duplicate_requests
+    FOR ALL TO postgres
+    USING (true)
+    WITH CHECK (true);
+
+COMMENT ON TABLE mcp_duplicate_requests IS 'Daily rollup of duplicate proxy requests per API key, for client diagnostics';
+COMMENT ON COLUMN mcp_duplicate_requests.request_hash IS 'SHA-256 of method + params; the request body itself is not stored';
@@ -0,0 +1,57 @@
+-- WebAuthn / passkey credentials
+--
+-- Passkeys are a second 2FA factor alongside TOTP. Credentials store the
+-- public key material produced by the registration ceremony (serialized
+-- webauthn-rs Passkey, JSONB); private keys never leave the
+-- authenticator. In-flight ceremony state lives in
+-- user_webauthn_challenges between the begin and complete requests - one
+-- pending ceremony per user and purpose, expired rows are replaced on
+-- the next begin.
+
+CREATE TABLE IF NOT EXISTS user_webauthn_credentials (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL,
+    -- User-facing nickname ("MacBook Touch ID", "YubiKey")
+    name VARCHAR(100) NOT NULL,
+    -- Base64url credential ID, globally unique per WebAuthn spec
+    credential_id TEXT NOT NULL UNIQUE,
+    -- Serialized passkey (public key, counter, backup flags)
+    credential JSONB NOT NULL,
+    last_used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_webauthn_credentials_user
+    ON user_webauthn_credentials(user_id);
+
+-- In-flight registration/authentication ceremony state
+CREATE TABLE IF NOT EXISTS user_webauthn_challenges (
+    user_id UUID NOT NULL,
+    purpose VARCHAR(20) NOT NULL CHECK (purpose IN ('register', 'authenticate')),
+    -- Serialized PasskeyRegistration / PasskeyAuthentication state
+    state JSONB NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (user_id, purpose)
+);
+
+-- Row Level Security: backend-only access
+ALTER TABLE user_webauthn_credentials ENABLE ROW LEVEL SECURITY;
+ALTER TABLE user_webauthn_credentials FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY user_webauthn_credentials_backend ON user_webauthn_credentials
+    FOR ALL TO postgres
+    USING (true)
+    WITH CHECK (true);
+
+ALTER TABLE user_webauthn_challenges ENABLE ROW LEVEL SECURITY;
+ALTER TABLE user_webauthn_challenges FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY user_webauthn_challenges_backend ON user_webauthn_challenges
+    FOR ALL TO postgres
+    USING (true)
+    WITH CHECK (true);
+
+COMMENT ON TABLE user_webauthn_credentials IS 'Registered WebAuthn passkeys (public keys only), usable as a 2FA factor alongside TOTP';
+COMMENT ON TABLE user_webauthn_challenges IS 'In-flight WebAuthn ceremony state between the begin and complete steps';
@@ -0,0 +1,58 @@
+-- Managed reserved subdomains and org subdomain change history
+--
+-- RESERVED_SUBDOMAINS in the routing layer stays as a hardcoded baseline
+-- (api, www, admin, ...) that can never be removed. This table holds
+-- additional entries platform admins reserve at runtime (trademarks,
+-- abuse takedowns, upcoming product names) without a deploy.
+--
+-- org_subdomain_history records every custom_subdomain change so the
+-- API can enforce the one-change-per-30-days cooldown and keep the old
+-- subdomain resolving (redirecting to the org) for a grace period.
+
+CREATE TABLE IF NOT EXISTS reserved_subdomains (
+    subdomain VARCHAR(63) PRIMARY KEY,
+    -- Why the entry was reserved (lands in the admin UI and audit log)
+    reason TEXT NOT NULL,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS org_subdomain_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    -- NULL when the org claimed its first custom subdomain
+    old_subdomain VARCHAR(63),
+    -- NULL when the org cleared its custom subdomain
+    new_subdomain VARCHAR(63),
+    changed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Cooldown check: latest change per org
+CREATE INDEX IF NOT EXISTS idx_org_subdomain_history_org
+    ON org_subdomain_history(org_id, changed_at DESC);
+
+-- Grace-period resolution: look up a released subdomain by name
+CREATE INDEX IF NOT EXISTS idx_org_subdomain_history_old
+    ON org_subdomain_history(old_subdomain, changed_at DESC)
+    WHERE old_subdomain IS NOT NULL;
+
+-- Row Level Security: backend-only access
+ALTER TABLE reserved_subdomains ENABLE ROW LEVEL SECURITY;
+ALTER TABLE reserved_subdomains FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY reserved_subdomains_backend ON reserved_subdomains
+    FOR ALL TO postgres
+    USING (true)
+    WITH CHECK (true);
+
+ALTER TABLE org_subdomain_history ENABLE ROW LEVEL SECURITY;
+ALTER TABLE org_subdomain_history FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY org_subdomain_history_backend ON org_subdomain_history
+    FOR ALL TO postgres
+    USING (true)
+    WITH CHECK (true);
+
+COMMENT ON TABLE reserved_subdomains IS 'Admin-managed reserved subdomains, layered on top of the hardcoded baseline list';
+COMMENT ON TABLE org_subdomain_history IS 'Custom subdomain changes per org, for cooldown enforcement and grace-period redirects';
@@ -0,0 +1,47 @@
+-- Async org-level support history exports
+--
+-- Compliance teams can request a full export of their org's tickets and
+-- messages (internal notes excluded) plus an attachments manifest. The
+-- export runs in the background, lands in the shared storage backend,
+-- and is delivered through a time-limited signed URL. This table tracks
+-- each request's lifecycle.
+
+CREATE TABLE IF NOT EXISTS support_exports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    requested_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    format VARCHAR(10) NOT NULL CHECK (format IN ('json', 'csv')),
+
+    -- Lifecycle: pending -> processing -> completed | failed
+    status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (status IN (
+        'pending',
+        'processing',
+        'completed',
+        'failed'
+    )),
+
+    -- Object key in the configured storage backend, set on completion
+    storage_key TEXT,
+    ticket_count INTEGER,
+    message_count INTEGER,
+    attachment_count INTEGER,
+    -- Failure detail for status = 'failed'
+    error TEXT,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_support_exports_org
+    ON support_exports(org_id, created_at DESC);
+
+-- Row Level Security: backend-only access
+ALTER TABLE support_exports ENABLE ROW LEVEL SECURITY;
+ALTER TABLE support_exports FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY support_exports_backend ON support_exports
+    FOR ALL TO postgres
+    USING (true)
+    WITH CHECK (true);
+
+COMMENT ON TABLE support_exports IS 'Async org support history export requests, delivered via signed URL';
@@ -0,0 +1,34 @@
+-- Category-level email suppression lists
+--
+-- One-click unsubscribe compliance (RFC 8058): recipients can opt out of
+-- a category of email via a signed link without logging in. Suppressions
+-- are keyed by address, not user id, so they survive account deletion
+-- and re-registration and cover addresses that never had an account.
+-- Security/transactional email is never suppressible.
+
+CREATE TABLE IF NOT EXISTS email_suppressions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    email TEXT NOT NULL,
+    -- Matches EmailCategory in the API: marketing, digest, usage, product
+    category VARCHAR(30) NOT NULL,
+    -- How the suppression was created: unsubscribe_link, preferences,
+    -- complaint, manual
+    source VARCHAR(30) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    UNIQUE (email, category)
+);
+
+CREATE INDEX IF NOT EXISTS idx_email_suppressions_email
+    ON email_suppressions(email);
+
+-- Row Level Security: backend-only access
+ALTER TABLE email_suppressions ENABLE ROW LEVEL SECURITY;
+ALTER TABLE email_suppressions FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY email_suppressions_backend ON email_suppressions
+    FOR ALL TO postgres
+    USING (true)
+    WITH CHECK (true);
+
+COMMENT ON TABLE email_suppressions IS 'Per-address, per-category email opt-outs enforced before every categorized send';
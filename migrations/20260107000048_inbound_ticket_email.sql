@@ -0,0 +1,53 @@
+-- Inbound email-to-ticket ingestion
+--
+-- The support code already reads support_tickets.source /
+-- original_email_from / original_email_to and threads outbound replies
+-- through ticket_email_metadata, but neither the columns nor the table
+-- were ever created by a migration. Backfill both so the inbound email
+-- webhook (and the existing outbound reply path) work on a fresh
+-- database.
+
+ALTER TABLE support_tickets
+ADD COLUMN IF NOT EXISTS source TEXT,
+ADD COLUMN IF NOT EXISTS original_email_from TEXT,
+ADD COLUMN IF NOT EXISTS original_email_to TEXT;
+
+COMMENT ON COLUMN support_tickets.source IS 'How the ticket was created: dashboard (NULL), email, api';
+COMMENT ON COLUMN support_tickets.original_email_from IS 'Customer address for email-sourced tickets; replies go here';
+COMMENT ON COLUMN support_tickets.original_email_to IS 'Support address the original email was sent to; used as the reply From';
+
+-- One row per email sent or received on a ticket, keyed by the RFC 5322
+-- Message-ID so inbound replies can be threaded back to their ticket
+CREATE TABLE IF NOT EXISTS ticket_email_metadata (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    ticket_id UUID NOT NULL REFERENCES support_tickets(id) ON DELETE CASCADE,
+    message_id UUID REFERENCES ticket_messages(id) ON DELETE SET NULL,
+    email_message_id TEXT NOT NULL,
+    in_reply_to TEXT,
+    resend_email_id TEXT,
+    direction TEXT NOT NULL CHECK (direction IN ('inbound', 'outbound')),
+    from_address TEXT NOT NULL,
+    to_addresses TEXT[] NOT NULL DEFAULT '{}',
+    subject TEXT,
+    has_attachments BOOLEAN NOT NULL DEFAULT FALSE,
+    attachment_count INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Unique so redelivered webhooks are idempotent; also the threading lookup
+CREATE UNIQUE INDEX IF NOT EXISTS idx_ticket_email_message_id
+    ON ticket_email_metadata(email_message_id);
+
+CREATE INDEX IF NOT EXISTS idx_ticket_email_ticket
+    ON ticket_email_metadata(ticket_id, created_at DESC);
+
+-- Row Level Security: backend-only access
+ALTER TABLE ticket_email_metadata ENABLE ROW LEVEL SECURITY;
+ALTER TABLE ticket_email_metadata FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY ticket_email_metadata_backend ON ticket_email_metadata
+    FOR ALL TO postgres
+    USING (true)
+    WITH CHECK (true);
+
+COMMENT ON TABLE ticket_email_metadata IS 'Per-email threading metadata (Message-ID chain) for email-sourced support tickets';
@@ -0,0 +1,48 @@
+-- Support ticket routing rules
+--
+-- Platform admins define ordered rules that run when a ticket is created
+-- or a customer replies. Conditions (category, keywords, org tier) and
+-- actions (auto-assign, set priority, canned response, tags) are stored
+-- as JSONB validated by the API; the engine walks rules by position and
+-- stops early when a matching rule says so.
+
+CREATE TABLE IF NOT EXISTS support_routing_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name TEXT NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    -- Evaluation order, lowest first; ties break on created_at
+    position INTEGER NOT NULL DEFAULT 0,
+    -- Which events the rule runs on
+    applies_to TEXT NOT NULL DEFAULT 'ticket_created'
+        CHECK (applies_to IN ('ticket_created', 'customer_reply', 'both')),
+    -- RuleConditions in the API: category, keywords, org_tiers
+    conditions JSONB NOT NULL DEFAULT '{}',
+    -- RuleActions in the API: assign_to, set_priority, add_tags, reply_template
+    actions JSONB NOT NULL DEFAULT '{}',
+    -- Matching this rule prevents later rules from running
+    stop_processing BOOLEAN NOT NULL DEFAULT FALSE,
+    match_count BIGINT NOT NULL DEFAULT 0,
+    last_matched_at TIMESTAMPTZ,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_support_routing_rules_order
+    ON support_routing_rules(position, created_at)
+    WHERE enabled;
+
+-- Tag action target: free-form labels on tickets
+ALTER TABLE support_tickets ADD COLUMN IF NOT EXISTS tags TEXT[] NOT NULL DEFAULT '{}';
+
+-- Row Level Security: backend-only access
+ALTER TABLE support_routing_rules ENABLE ROW LEVEL SECURITY;
+ALTER TABLE support_routing_rules FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY support_routing_rules_backend ON support_routing_rules
+    FOR ALL TO postgres
+    USING (true)
+    WITH CHECK (true);
+
+COMMENT ON TABLE support_routing_rules IS 'Ordered admin-defined rules executed on ticket creation and customer replies';
+COMMENT ON COLUMN support_tickets.tags IS 'Free-form labels, applied manually or by routing rules';
@@ -0,0 +1,94 @@
+-- Tier-aware support prioritization
+--
+-- Paid orgs get a priority floor on new tickets (enterprise: high,
+-- team: medium) applied before SLA selection, and SLA rules can now be
+-- scoped to a subscription tier in addition to priority/category. The
+-- pre-bump priority is preserved in original_priority.
+
+ALTER TABLE sla_rules ADD COLUMN IF NOT EXISTS tier TEXT
+    CHECK (tier IN ('free', 'pro', 'team', 'enterprise'));
+
+COMMENT ON COLUMN sla_rules.tier IS 'Subscription tier the rule applies to; NULL = all tiers';
+
+-- One active rule per priority/category/tier combination
+DROP INDEX IF EXISTS idx_sla_rules_priority_category;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_sla_rules_priority_category_tier
+    ON sla_rules(priority, category, tier)
+    WHERE is_active = true;
+
+-- =============================================================================
+-- Priority Floor by Tier
+-- =============================================================================
+
+CREATE OR REPLACE FUNCTION apply_tier_priority_floor()
+RETURNS TRIGGER AS $$
+DECLARE
+    org_tier TEXT;
+    floor_priority ticket_priority;
+BEGIN
+    IF NEW.organization_id IS NULL THEN
+        RETURN NEW;
+    END IF;
+
+    SELECT subscription_tier INTO org_tier
+    FROM organizations WHERE id = NEW.organization_id;
+
+    floor_priority := CASE org_tier
+        WHEN 'enterprise' THEN 'high'::ticket_priority
+        WHEN 'team' THEN 'medium'::ticket_priority
+        ELSE NULL
+    END;
+
+    IF floor_priority IS NOT NULL AND
+       (CASE NEW.priority WHEN 'urgent' THEN 4 WHEN 'high' THEN 3 WHEN 'medium' THEN 2 ELSE 1 END) <
+       (CASE floor_priority WHEN 'urgent' THEN 4 WHEN 'high' THEN 3 WHEN 'medium' THEN 2 ELSE 1 END)
+    THEN
+        NEW.original_priority := NEW.priority;
+        NEW.priority := floor_priority;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- BEFORE INSERT triggers fire in name order; "apply_..." sorts ahead of
+-- "set_ticket_sla_trigger" so SLA selection sees the bumped priority
+DROP TRIGGER IF EXISTS apply_tier_priority_floor_trigger ON support_tickets;
+CREATE TRIGGER apply_tier_priority_floor_trigger
+    BEFORE INSERT ON support_tickets
+    FOR EACH ROW EXECUTE FUNCTION apply_tier_priority_floor();
+
+-- =============================================================================
+-- Tier-aware SLA Selection
+-- =============================================================================
+
+CREATE OR REPLACE FUNCTION set_ticket_sla()
+RETURNS TRIGGER AS $$
+DECLARE
+    sla_record RECORD;
+    org_tier TEXT;
+BEGIN
+    IF NEW.organization_id IS NOT NULL THEN
+        SELECT subscription_tier INTO org_tier
+        FROM organizations WHERE id = NEW.organization_id;
+    END IF;
+
+    -- Most specific rule wins: tier-specific before catch-all, then
+    -- category-specific before catch-all
+    SELECT first_response_hours, resolution_hours INTO sla_record
+    FROM sla_rules
+    WHERE priority = NEW.priority
+      AND is_active = true
+      AND (category = NEW.category OR category IS NULL)
+      AND (tier = org_tier OR tier IS NULL)
+    ORDER BY tier NULLS LAST, category NULLS LAST
+    LIMIT 1;
+
+    IF FOUND THEN
+        NEW.first_response_sla_hours := sla_record.first_response_hours;
+        NEW.resolution_sla_hours := sla_record.resolution_hours;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,31 @@
+-- Customer satisfaction (CSAT) surveys
+--
+-- When a ticket is resolved or closed the customer gets a signed survey
+-- link (HMAC token, no login required). One response per ticket; the
+-- staff member assigned at submission time is captured so per-staff
+-- CSAT can be reported even after reassignment.
+
+CREATE TABLE IF NOT EXISTS csat_responses (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    ticket_id UUID NOT NULL UNIQUE REFERENCES support_tickets(id) ON DELETE CASCADE,
+    rating SMALLINT NOT NULL CHECK (rating BETWEEN 1 AND 5),
+    comment TEXT,
+    -- assigned_to at submission time; survives later reassignment
+    rated_staff_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_csat_responses_staff ON csat_responses(rated_staff_id)
+    WHERE rated_staff_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_csat_responses_created ON csat_responses(created_at DESC);
+
+COMMENT ON TABLE csat_responses IS 'Customer satisfaction ratings submitted after ticket resolution';
+COMMENT ON COLUMN csat_responses.rating IS '1 (very dissatisfied) to 5 (very satisfied)';
+COMMENT ON COLUMN csat_responses.rated_staff_id IS 'Staff member assigned to the ticket when the rating was submitted';
+
+-- Row Level Security
+ALTER TABLE csat_responses ENABLE ROW LEVEL SECURITY;
+ALTER TABLE csat_responses FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY csat_responses_backend ON csat_responses
+    FOR ALL TO postgres USING (true) WITH CHECK (true);
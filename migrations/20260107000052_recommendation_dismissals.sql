@@ -0,0 +1,27 @@
+-- Usage-based recommendation dismissals
+--
+-- Recommendations are computed on the fly from proxy logs and billing
+-- usage; only dismissals are persisted. Keys are stable per finding
+-- (e.g. 'enable_caching:<mcp_id>') so a dismissed suggestion stays
+-- hidden until it is re-surfaced after the dismissal ages out.
+
+CREATE TABLE IF NOT EXISTS recommendation_dismissals (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    recommendation_key TEXT NOT NULL,
+    dismissed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    dismissed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (org_id, recommendation_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_recommendation_dismissals_org
+    ON recommendation_dismissals(org_id, dismissed_at DESC);
+
+COMMENT ON TABLE recommendation_dismissals IS 'Per-org dismissed usage recommendations, keyed by stable recommendation key';
+
+-- Row Level Security
+ALTER TABLE recommendation_dismissals ENABLE ROW LEVEL SECURITY;
+ALTER TABLE recommendation_dismissals FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY recommendation_dismissals_backend ON recommendation_dismissals
+    FOR ALL TO postgres USING (true) WITH CHECK (true);
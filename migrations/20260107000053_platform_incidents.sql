@@ -0,0 +1,64 @@
+-- Platform incidents and status subscriptions
+--
+-- Platform admins record incidents (severity + affected components) and
+-- post timeline updates; customers read them from the public status
+-- endpoint and can subscribe by email to be notified on every update.
+
+CREATE TABLE IF NOT EXISTS platform_incidents (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    title TEXT NOT NULL,
+    severity TEXT NOT NULL CHECK (severity IN ('minor', 'major', 'critical')),
+    status TEXT NOT NULL DEFAULT 'investigating'
+        CHECK (status IN ('investigating', 'identified', 'monitoring', 'resolved')),
+    -- Component identifiers (api, proxy, dashboard, ...); validated in code
+    affected_components TEXT[] NOT NULL DEFAULT '{}',
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_platform_incidents_active
+    ON platform_incidents(started_at DESC)
+    WHERE status != 'resolved';
+
+-- Timeline entries; the first one is written at incident creation
+CREATE TABLE IF NOT EXISTS platform_incident_updates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    incident_id UUID NOT NULL REFERENCES platform_incidents(id) ON DELETE CASCADE,
+    -- Incident status as of this update
+    status TEXT NOT NULL,
+    message TEXT NOT NULL,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_platform_incident_updates_incident
+    ON platform_incident_updates(incident_id, created_at DESC);
+
+-- Status page email subscribers (no account required)
+CREATE TABLE IF NOT EXISTS status_subscribers (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    email TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE platform_incidents IS 'Platform-level incidents shown on the public status endpoint';
+COMMENT ON TABLE platform_incident_updates IS 'Timeline of status updates per incident';
+COMMENT ON TABLE status_subscribers IS 'Email addresses subscribed to incident update notifications';
+
+-- Row Level Security
+ALTER TABLE platform_incidents ENABLE ROW LEVEL SECURITY;
+ALTER TABLE platform_incidents FORCE ROW LEVEL SECURITY;
+ALTER TABLE platform_incident_updates ENABLE ROW LEVEL SECURITY;
+ALTER TABLE platform_incident_updates FORCE ROW LEVEL SECURITY;
+ALTER TABLE status_subscribers ENABLE ROW LEVEL SECURITY;
+ALTER TABLE status_subscribers FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY platform_incidents_backend ON platform_incidents
+    FOR ALL TO postgres USING (true) WITH CHECK (true);
+CREATE POLICY platform_incident_updates_backend ON platform_incident_updates
+    FOR ALL TO postgres USING (true) WITH CHECK (true);
+CREATE POLICY status_subscribers_backend ON status_subscribers
+    FOR ALL TO postgres USING (true) WITH CHECK (true);
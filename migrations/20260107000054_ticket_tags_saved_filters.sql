@@ -0,0 +1,44 @@
+-- Ticket tag registry and per-admin saved filters
+--
+-- support_tickets.tags (TEXT[]) already exists for the routing rules
+-- engine; this adds a registry so the dashboard can offer consistent,
+-- colored tags, plus saved filter definitions per admin user for
+-- one-click ticket views.
+
+CREATE TABLE IF NOT EXISTS support_tags (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Slug stored in support_tickets.tags entries
+    name TEXT NOT NULL UNIQUE CHECK (name ~ '^[a-z0-9][a-z0-9_-]{0,49}$'),
+    color TEXT NOT NULL DEFAULT '#6366f1' CHECK (color ~ '^#[0-9a-fA-F]{6}$'),
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS admin_saved_filters (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    admin_user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    -- Subset of admin_list_tickets query params; validated in code
+    filters JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (admin_user_id, name)
+);
+
+-- Tag containment lookups for the ticket list filter
+CREATE INDEX IF NOT EXISTS idx_support_tickets_tags
+    ON support_tickets USING GIN (tags);
+
+COMMENT ON TABLE support_tags IS 'Registry of ticket tags; support_tickets.tags entries reference these by name';
+COMMENT ON TABLE admin_saved_filters IS 'Per-admin saved ticket list filters for one-click dashboard views';
+
+-- Row Level Security
+ALTER TABLE support_tags ENABLE ROW LEVEL SECURITY;
+ALTER TABLE support_tags FORCE ROW LEVEL SECURITY;
+ALTER TABLE admin_saved_filters ENABLE ROW LEVEL SECURITY;
+ALTER TABLE admin_saved_filters FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY support_tags_backend ON support_tags
+    FOR ALL TO postgres USING (true) WITH CHECK (true);
+CREATE POLICY admin_saved_filters_backend ON admin_saved_filters
+    FOR ALL TO postgres USING (true) WITH CHECK (true);
@@ -0,0 +1,13 @@
+-- Opt-in MCP payload capture for debugging
+--
+-- Orgs that enable payload_capture (organizations.settings) get a
+-- redacted request/response copy stored with each request log entry,
+-- retrievable via GET /api/v1/mcp/logs/:log_id/payload. A scheduled job
+-- clears payloads once the org's retention window elapses.
+
+ALTER TABLE mcp_request_log ADD COLUMN IF NOT EXISTS captured_payload JSONB;
+
+-- The retention sweep only visits rows that still hold a payload
+CREATE INDEX IF NOT EXISTS idx_mcp_request_log_captured_payload
+    ON mcp_request_log (created_at)
+    WHERE captured_payload IS NOT NULL;